@@ -0,0 +1,37 @@
+use leptos::*;
+use leptos_use::docs::{demo_or_body, BooleanDisplay};
+use leptos_use::{use_bfcache, UseBfcacheReturn};
+
+#[component]
+fn Demo() -> impl IntoView {
+    let UseBfcacheReturn {
+        restored_from_bfcache,
+        persisted,
+        navigation_type,
+    } = use_bfcache();
+
+    let (restore_count, set_restore_count) = create_signal(0);
+
+    create_effect(move |_| {
+        if restored_from_bfcache.get().is_some() {
+            set_restore_count.update(|count| *count += 1);
+        }
+    });
+
+    view! {
+        <p>"💡 Navigate to another page and then use the browser's back button to return."</p>
+        <div>"Navigation type: " {move || format!("{:?}", navigation_type.get())}</div>
+        <div>"Persisted: " <BooleanDisplay value=persisted/></div>
+        <div>"Restored from bfcache: " {restore_count} " times"</div>
+        <a href="https://leptos-use.rs">"Go somewhere else"</a>
+    }
+}
+
+fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+
+    mount_to(demo_or_body(), || {
+        view! { <Demo/> }
+    })
+}
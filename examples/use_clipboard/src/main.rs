@@ -11,6 +11,7 @@ fn Demo() -> impl IntoView {
         text,
         copied,
         copy,
+        ..
     } = use_clipboard();
 
     let permission_read = use_permission("clipboard-read");
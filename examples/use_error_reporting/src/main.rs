@@ -0,0 +1,46 @@
+use leptos::*;
+use leptos_use::docs::demo_or_body;
+use leptos_use::{use_error_reporting, UseErrorReportingReturn};
+use wasm_bindgen::prelude::*;
+
+#[component]
+fn Demo() -> impl IntoView {
+    let UseErrorReportingReturn { errors, clear } = use_error_reporting();
+
+    let throw_error = move |_| {
+        // throw from a timeout so the error isn't caught by the event handler
+        set_timeout(
+            || wasm_bindgen::throw_str("Something went wrong"),
+            std::time::Duration::ZERO,
+        );
+    };
+
+    let reject_promise = move |_| {
+        let _ = js_sys::Promise::reject(&JsValue::from_str("Nobody handled me"));
+    };
+
+    view! {
+        <button on:click=throw_error>"Throw error"</button>
+        <button on:click=reject_promise>"Reject promise"</button>
+        <button on:click=move |_| clear()>"Clear"</button>
+        <ul>
+            {move || {
+                errors
+                    .get()
+                    .into_iter()
+                    .map(|err| view! { <li>{err.to_string()}</li> })
+                    .collect_view()
+            }}
+
+        </ul>
+    }
+}
+
+fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+
+    mount_to(demo_or_body(), || {
+        view! { <Demo/> }
+    })
+}
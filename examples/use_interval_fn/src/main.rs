@@ -1,7 +1,6 @@
 use leptos::*;
 use leptos_use::docs::demo_or_body;
-use leptos_use::use_interval_fn;
-use leptos_use::utils::Pausable;
+use leptos_use::{use_interval_fn, UseIntervalFnReturn};
 
 #[component]
 fn Demo() -> impl IntoView {
@@ -22,10 +21,11 @@ fn Demo() -> impl IntoView {
     let (interval, set_interval) = create_signal(500_u64);
     let (index, set_index) = create_signal(0);
 
-    let Pausable {
+    let UseIntervalFnReturn {
         pause,
         resume,
         is_active,
+        ..
     } = use_interval_fn(
         move || {
             set_index.set((index.get() + 1) % greetings.len());
@@ -0,0 +1,33 @@
+use leptos::*;
+use leptos_use::docs::demo_or_body;
+use leptos_use::{use_page_lifecycle_with_options, UsePageLifecycleOptions};
+
+#[component]
+fn Demo() -> impl IntoView {
+    let (history, set_history) = create_signal(Vec::<String>::new());
+
+    let state = use_page_lifecycle_with_options(UsePageLifecycleOptions::default().on_change(
+        move |prev, next| {
+            set_history.update(|history| history.push(format!("{prev} → {next}")));
+        },
+    ));
+
+    view! {
+        <p>"💡 Click outside of the demo, switch tabs or minimize the window."</p>
+        <div>"Current state: " {move || state.get().to_string()}</div>
+        <ul>
+            <For each=move || history.get().into_iter().enumerate() key=|(i, _)| *i let:entry>
+                <li>{entry.1}</li>
+            </For>
+        </ul>
+    }
+}
+
+fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+
+    mount_to(demo_or_body(), || {
+        view! { <Demo/> }
+    })
+}
@@ -1,12 +1,14 @@
 use crate::{LogDisplay, StreamSend};
+use codee::binary::FromToBytesCodec;
 use leptos::*;
 use leptos_use::core::ConnectionReadyState;
-use leptos_use::BidirStream;
+use leptos_use::{BidirStream, StreamState};
 
 #[component]
 pub fn StreamBidir(
     #[prop(into)] ready_state: Signal<ConnectionReadyState>,
     stream: BidirStream,
+    opened_by: &'static str,
 ) -> impl IntoView {
     let (log, set_log) = create_signal(vec![]);
 
@@ -15,22 +17,30 @@ pub fn StreamBidir(
     };
 
     let _ = watch(
-        stream.bytes,
-        move |bytes, _, _| {
-            if let Some(bytes) = bytes {
-                set_log.update(|log| {
-                    log.push(format!(
-                        "Received datagrams: '{}'",
-                        String::from_utf8(bytes.clone()).expect("valid utf8")
-                    ))
-                });
+        stream.receive_stream.receive::<String, FromToBytesCodec>(),
+        move |message, _, _| {
+            if let Some(message) = message {
+                set_log.update(|log| log.push(format!("Received bidir: '{}'", message)));
             }
         },
         false,
     );
 
+    let stream_state = stream.send_stream.state;
+
+    let on_close = {
+        let send_stream = stream.send_stream.clone();
+
+        move |_| {
+            send_stream.close();
+        }
+    };
+
     view! {
-        <StreamSend ready_state=ready_state send_stream=stream.clone() on_send=on_send />
+        <p>Opened by {opened_by}</p>
+        <StreamSend ready_state=ready_state send_stream=stream.send_stream.clone() on_send=on_send />
         <LogDisplay log=log />
+        <p>Stream state: {move || format!("{:?}", stream_state.get())}</p>
+        <button on:click=on_close disabled=move || ready_state() != ConnectionReadyState::Open || stream_state.get() != StreamState::Open>Close</button>
     }
 }
@@ -1,22 +1,24 @@
+use codee::binary::FromToBytesCodec;
 use leptos::*;
 use leptos_use::core::ConnectionReadyState;
-use leptos_use::SendableStream;
+use leptos_use::{SendStream, StreamState};
 
 #[component]
-pub fn StreamSend<S, F>(
+pub fn StreamSend<F>(
     #[prop(into)] ready_state: Signal<ConnectionReadyState>,
-    send_stream: S,
+    send_stream: SendStream,
     on_send: F,
 ) -> impl IntoView
 where
-    S: SendableStream + 'static,
     F: Fn(String) + 'static,
 {
     let (text, set_text) = create_signal("".to_string());
 
+    let stream_state = send_stream.state;
+
     let on_send = {
         move |_| {
-            send_stream.send_bytes(text().as_bytes());
+            let _ = send_stream.send::<String, FromToBytesCodec>(&text());
             on_send(text());
             set_text("".to_string());
         }
@@ -24,6 +26,6 @@ where
 
     view! {
         <textarea on:change=move |e| set_text(event_target_value(&e)) prop:value=text />
-        <button on:click=on_send disabled=move || ready_state() != ConnectionReadyState::Open>"Send"</button>
+        <button on:click=on_send disabled=move || ready_state() != ConnectionReadyState::Open || stream_state() != StreamState::Open>"Send"</button>
     }
 }
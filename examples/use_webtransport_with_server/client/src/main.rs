@@ -1,3 +1,4 @@
+use codee::binary::FromToBytesCodec;
 use leptos::*;
 use leptos_use::core::ConnectionReadyState;
 use leptos_use::docs::demo_or_body;
@@ -18,7 +19,7 @@ fn Demo() -> impl IntoView {
 
     let id = store_value(0);
 
-    let transport = use_webtransport_with_options(
+    let transport = use_webtransport_with_options::<String, FromToBytesCodec>(
         "https://localhost:4433",
         UseWebTransportOptions::default()
             .on_open(move || {
@@ -34,32 +35,29 @@ fn Demo() -> impl IntoView {
                 set_datagrams_log
                     .update(|log| log.push("Server opened bidirectional stream".to_string()));
                 set_bidir_streams.update(|s| s.push((i, bidir_stream, "server")));
-            }),
+            })
+            .on_error(move |e| set_datagrams_log.update(|log| log.push(format!("Error: {:?}", e)))),
     );
 
     let (text, set_text) = create_signal("".to_string());
 
     let on_send_datagrams = {
-        let transport = transport.clone();
+        let send_datagram = transport.send_datagram.clone();
 
         move |_| {
             set_datagrams_log.update(|log| log.push(format!("Sent datagram: '{}'", text())));
 
-            transport.send_datagrams(text().as_bytes());
+            send_datagram(&text());
             set_text("".to_string());
         }
     };
 
     let _ = watch(
-        transport.datagrams,
-        move |grams, _, _| {
-            if let Some(grams) = grams {
-                set_datagrams_log.update(|log| {
-                    log.push(format!(
-                        "Received datagrams: '{}'",
-                        String::from_utf8(grams.clone()).expect("valid utf8")
-                    ))
-                });
+        transport.datagram,
+        move |datagram, _, _| {
+            if let Some(datagram) = datagram {
+                set_datagrams_log
+                    .update(|log| log.push(format!("Received datagram: '{}'", datagram)));
             }
         },
         false,
@@ -69,10 +67,10 @@ fn Demo() -> impl IntoView {
         let transport = transport.clone();
 
         move |_| {
-            let transport = transport.clone();
+            let bidir_stream = transport.open_bidir_stream();
 
             spawn_local(async move {
-                match transport.open_bidir_stream().await {
+                match bidir_stream.await {
                     Ok(bidir_stream) => {
                         let i = id.get_value();
                         id.set_value(i + 1);
@@ -103,10 +101,10 @@ fn Demo() -> impl IntoView {
         <For
             each=bidir_streams
             key=|(i, _, _)| *i
-            view=move |(i, bidir_stream, opened_by)| view! {
+            view=move |(_, bidir_stream, opened_by)| view! {
                 <StreamBidir
                     ready_state=ready_state
-                    stream=bidir_stream.clone()
+                    stream=bidir_stream
                     opened_by=opened_by
                 />
             }
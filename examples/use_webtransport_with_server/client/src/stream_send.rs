@@ -1,23 +1,24 @@
+use codee::binary::FromToBytesCodec;
 use leptos::*;
 use leptos_use::core::ConnectionReadyState;
-use leptos_use::{SendableStream, StreamState};
+use leptos_use::{SendStream, StreamState};
 
 #[component]
-pub fn StreamSend<S, F>(
+pub fn StreamSend<F>(
     #[prop(into)] ready_state: Signal<ConnectionReadyState>,
-    stream_state: Signal<StreamState>,
-    send_stream: S,
+    send_stream: SendStream,
     on_send: F,
 ) -> impl IntoView
 where
-    S: SendableStream + 'static,
     F: Fn(String) + 'static,
 {
     let (text, set_text) = create_signal("".to_string());
 
+    let stream_state = send_stream.state;
+
     let on_send = {
         move |_| {
-            send_stream.send_bytes(text().as_bytes());
+            let _ = send_stream.send::<String, FromToBytesCodec>(&text());
             on_send(text());
             set_text("".to_string());
         }
@@ -70,11 +70,11 @@ async fn handle_connection_impl(incoming_session: IncomingSession) -> Result<()>
                     None => continue,
                 };
 
-                let str_data = std::str::from_utf8(&buffer[..bytes_read])?;
+                let str_data = std::str::from_utf8(unframe(&buffer[..bytes_read]))?;
 
                 info!("Received (bi) '{str_data}' from client");
 
-                stream.0.write_all(b"ACK").await?;
+                stream.0.write_all(&frame(b"ACK")).await?;
             }
             stream = connection.accept_uni() => {
                 let mut stream = stream?;
@@ -85,12 +85,12 @@ async fn handle_connection_impl(incoming_session: IncomingSession) -> Result<()>
                     None => continue,
                 };
 
-                let str_data = std::str::from_utf8(&buffer[..bytes_read])?;
+                let str_data = std::str::from_utf8(unframe(&buffer[..bytes_read]))?;
 
                 info!("Received (uni) '{str_data}' from client");
 
                 let mut stream = connection.open_uni().await?.await?;
-                stream.write_all(b"ACK").await?;
+                stream.write_all(&frame(b"ACK")).await?;
             }
             dgram = connection.receive_datagram() => {
                 let dgram = dgram?;
@@ -113,7 +113,7 @@ async fn open_bidir_stream(connection: &Connection) -> Result<()> {
 
     info!("Opened BI stream");
 
-    stream.0.write_all(b"Hello from server").await?;
+    stream.0.write_all(&frame(b"Hello from server")).await?;
     info!("Sent 'Hello from server' to client");
 
     let bytes_read = match stream.1.read(&mut buffer).await? {
@@ -124,11 +124,11 @@ async fn open_bidir_stream(connection: &Connection) -> Result<()> {
         }
     };
 
-    let str_data = std::str::from_utf8(&buffer[..bytes_read])?;
+    let str_data = std::str::from_utf8(unframe(&buffer[..bytes_read]))?;
 
     info!("Received (bi) '{str_data}' from client");
 
-    stream.0.write_all(b"ACK").await?;
+    stream.0.write_all(&frame(b"ACK")).await?;
 
     Ok(())
 }
@@ -140,12 +140,24 @@ async fn open_uni_stream(connection: &Connection) -> Result<()> {
 
     info!("Opened UNI stream");
 
-    stream.write_all(b"Hello from server").await?;
+    stream.write_all(&frame(b"Hello from server")).await?;
     info!("Sent 'Hello from server' to client");
 
     Ok(())
 }
 
+/// Prefixes a message with its length like `use_webtransport` expects on streams
+fn frame(message: &[u8]) -> Vec<u8> {
+    let mut framed = (message.len() as u32).to_be_bytes().to_vec();
+    framed.extend_from_slice(message);
+    framed
+}
+
+/// Strips the length prefix of a message sent by `use_webtransport` on a stream
+fn unframe(data: &[u8]) -> &[u8] {
+    data.get(4..).unwrap_or_default()
+}
+
 fn init_logging() {
     let env_filter = EnvFilter::builder()
         .with_default_directive(LevelFilter::INFO.into())
@@ -0,0 +1,424 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// An sRGB color with alpha.
+///
+/// Can be parsed from CSS color strings in hex (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`),
+/// `rgb()`, `hsl()` and `oklch()` notation, both with the modern space separated and the legacy
+/// comma separated syntax. Named colors are not supported.
+///
+/// ```
+/// # use leptos_use::core::Color;
+/// #
+/// let color: Color = "hsl(230 48% 47%)".parse().unwrap();
+///
+/// assert_eq!(color.to_hex(), "#3e51b1");
+/// assert_eq!(color.to_rgb_string(), "rgb(62 81 177)");
+/// assert_eq!(color.darken(0.1).to_hex(), "#31408c");
+///
+/// // WCAG contrast ratio
+/// assert!(Color::WHITE.contrast_ratio(&color) > 4.5);
+/// ```
+///
+/// `Display` formats the color in hex notation so it can be used in styles directly.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Color {
+    /// Red between `0.0` and `1.0`
+    pub red: f64,
+    /// Green between `0.0` and `1.0`
+    pub green: f64,
+    /// Blue between `0.0` and `1.0`
+    pub blue: f64,
+    /// Opacity between `0.0` and `1.0`
+    pub alpha: f64,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+
+    /// Creates a color from components between `0.0` and `1.0`.
+    pub const fn new(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Creates an opaque color from 8 bit components.
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::new(
+            f64::from(red) / 255.0,
+            f64::from(green) / 255.0,
+            f64::from(blue) / 255.0,
+            1.0,
+        )
+    }
+
+    /// Creates an opaque color from hue in degrees and saturation and lightness between `0.0`
+    /// and `1.0`.
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let hue = hue.rem_euclid(360.0);
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+        let m = lightness - chroma / 2.0;
+
+        let (red, green, blue) = match (hue / 60.0) as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        Self::new(red + m, green + m, blue + m, 1.0)
+    }
+
+    /// Creates an opaque color from [OKLCH](https://developer.mozilla.org/en-US/docs/Web/CSS/color_value/oklch)
+    /// lightness between `0.0` and `1.0`, chroma and hue in degrees. Colors outside of the sRGB
+    /// gamut are clipped.
+    pub fn oklch(lightness: f64, chroma: f64, hue: f64) -> Self {
+        let (a, b) = (
+            chroma * hue.to_radians().cos(),
+            chroma * hue.to_radians().sin(),
+        );
+
+        let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+        let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+        let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548 * b).powi(3);
+
+        Self::new(
+            gamma_encode(4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s),
+            gamma_encode(-1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s),
+            gamma_encode(-0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701 * s),
+            1.0,
+        )
+    }
+
+    /// Returns the same color with the given opacity.
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// The 8 bit red, green and blue components
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        (to_u8(self.red), to_u8(self.green), to_u8(self.blue))
+    }
+
+    /// Hue in degrees and saturation and lightness between `0.0` and `1.0`
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (red, green, blue) = self.clamped();
+
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+
+        let hue = if max == red {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if max == green {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+
+        (hue, saturation, lightness)
+    }
+
+    /// OKLCH lightness between `0.0` and `1.0`, chroma and hue in degrees
+    pub fn to_oklch(&self) -> (f64, f64, f64) {
+        let (red, green, blue) = self.clamped();
+        let (red, green, blue) = (gamma_decode(red), gamma_decode(green), gamma_decode(blue));
+
+        let l = (0.412_221_470_8 * red + 0.536_332_536_3 * green + 0.051_445_992_9 * blue).cbrt();
+        let m = (0.211_903_498_2 * red + 0.680_699_545_1 * green + 0.107_396_956_6 * blue).cbrt();
+        let s = (0.088_302_461_9 * red + 0.281_718_837_6 * green + 0.629_978_700_5 * blue).cbrt();
+
+        let lightness = 0.210_454_255_3 * l + 0.793_617_785 * m - 0.004_072_046_8 * s;
+        let a = 1.977_998_495_1 * l - 2.428_592_205 * m + 0.450_593_709_9 * s;
+        let b = 0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766 * s;
+
+        let chroma = a.hypot(b);
+        // the hue of grays is meaningless and only noise
+        let hue = if chroma < 1e-4 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        };
+
+        (lightness, chroma, hue)
+    }
+
+    /// Formats the color as `#rrggbb` or `#rrggbbaa` if it's not opaque.
+    pub fn to_hex(&self) -> String {
+        let (red, green, blue) = self.to_rgb8();
+
+        if self.alpha < 1.0 {
+            format!("#{red:02x}{green:02x}{blue:02x}{:02x}", to_u8(self.alpha))
+        } else {
+            format!("#{red:02x}{green:02x}{blue:02x}")
+        }
+    }
+
+    /// Formats the color like `rgb(62 81 177)` or `rgb(62 81 177 / 0.5)`.
+    pub fn to_rgb_string(&self) -> String {
+        let (red, green, blue) = self.to_rgb8();
+        format!("rgb({red} {green} {blue}{})", self.alpha_suffix())
+    }
+
+    /// Formats the color like `hsl(230 48% 47%)` or `hsl(230 48% 47% / 0.5)`.
+    pub fn to_hsl_string(&self) -> String {
+        let (hue, saturation, lightness) = self.to_hsl();
+        format!(
+            "hsl({} {}% {}%{})",
+            format_number(hue, 1),
+            format_number(saturation * 100.0, 1),
+            format_number(lightness * 100.0, 1),
+            self.alpha_suffix()
+        )
+    }
+
+    /// Formats the color like `oklch(0.476 0.1534 270.94)` or with `/ 0.5` if it's not opaque.
+    pub fn to_oklch_string(&self) -> String {
+        let (lightness, chroma, hue) = self.to_oklch();
+        format!(
+            "oklch({} {} {}{})",
+            format_number(lightness, 4),
+            format_number(chroma, 4),
+            format_number(hue, 2),
+            self.alpha_suffix()
+        )
+    }
+
+    /// Increases the HSL lightness by `amount` which is between `0.0` and `1.0`.
+    pub fn lighten(&self, amount: f64) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+        Self::hsl(hue, saturation, lightness + amount).with_alpha(self.alpha)
+    }
+
+    /// Decreases the HSL lightness by `amount` which is between `0.0` and `1.0`.
+    pub fn darken(&self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// The [relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance) as
+    /// defined by WCAG. The alpha channel is ignored.
+    pub fn relative_luminance(&self) -> f64 {
+        let (red, green, blue) = self.clamped();
+
+        0.2126 * gamma_decode(red) + 0.7152 * gamma_decode(green) + 0.0722 * gamma_decode(blue)
+    }
+
+    /// The [contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between this and
+    /// the other color from `1.0` to `21.0`. Text needs at least `4.5` to pass WCAG AA and
+    /// large text `3.0`.
+    pub fn contrast_ratio(&self, other: &Color) -> f64 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// `true` if white text has a higher contrast on this color than black text.
+    pub fn is_dark(&self) -> bool {
+        self.contrast_ratio(&Self::WHITE) > self.contrast_ratio(&Self::BLACK)
+    }
+
+    fn clamped(&self) -> (f64, f64, f64) {
+        (
+            self.red.clamp(0.0, 1.0),
+            self.green.clamp(0.0, 1.0),
+            self.blue.clamp(0.0, 1.0),
+        )
+    }
+
+    fn alpha_suffix(&self) -> String {
+        if self.alpha < 1.0 {
+            format!(" / {}", format_number(self.alpha, 3))
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim().to_ascii_lowercase();
+        let invalid = || ColorParseError::Invalid(s.to_string());
+
+        if let Some(hex) = input.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(invalid);
+        }
+
+        let (function, args) = input
+            .strip_suffix(')')
+            .and_then(|input| input.split_once('('))
+            .ok_or_else(invalid)?;
+
+        let (channels, alpha) = match args.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (args, None),
+        };
+
+        let mut channels = channels
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|channel| !channel.is_empty())
+            .collect::<Vec<_>>();
+
+        // legacy syntax like `rgba(0, 0, 0, 0.5)`
+        let alpha = match alpha {
+            Some(alpha) => Some(alpha),
+            None if channels.len() == 4 => channels.pop(),
+            None => None,
+        };
+
+        let [first, second, third] = channels[..] else {
+            return Err(invalid());
+        };
+
+        let alpha = match alpha {
+            Some(alpha) => parse_value(alpha, 1.0).ok_or_else(invalid)?,
+            None => 1.0,
+        };
+
+        let color = match function.trim() {
+            "rgb" | "rgba" => Color::new(
+                parse_value(first, 255.0).ok_or_else(invalid)?,
+                parse_value(second, 255.0).ok_or_else(invalid)?,
+                parse_value(third, 255.0).ok_or_else(invalid)?,
+                1.0,
+            ),
+            "hsl" | "hsla" => Color::hsl(
+                parse_hue(first).ok_or_else(invalid)?,
+                parse_percentage(second).ok_or_else(invalid)?,
+                parse_percentage(third).ok_or_else(invalid)?,
+            ),
+            "oklch" => Color::oklch(
+                parse_value(first, 1.0).ok_or_else(invalid)?,
+                // 100% chroma is 0.4
+                parse_value(second, 1.0)
+                    .map(|chroma| {
+                        if second.ends_with('%') {
+                            chroma * 0.4
+                        } else {
+                            chroma
+                        }
+                    })
+                    .ok_or_else(invalid)?,
+                parse_hue(third).ok_or_else(invalid)?,
+            ),
+            _ => return Err(invalid()),
+        };
+
+        Ok(color.with_alpha(alpha))
+    }
+}
+
+/// Error returned when parsing a [`Color`] fails.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ColorParseError {
+    #[error("invalid or unsupported color: {0}")]
+    Invalid(String),
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|digit| [digit, digit]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+
+    let mut components = (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok());
+
+    let red = components.next()??;
+    let green = components.next()??;
+    let blue = components.next()??;
+    let alpha = components.next().flatten().unwrap_or(255);
+
+    Some(Color::rgb(red, green, blue).with_alpha(f64::from(alpha) / 255.0))
+}
+
+/// Parses a number or a percentage. Numbers are divided by `max`.
+fn parse_value(value: &str, max: f64) -> Option<f64> {
+    match value.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f64>().ok().map(|value| value / 100.0),
+        None => value.parse::<f64>().ok().map(|value| value / max),
+    }
+}
+
+/// Parses a percentage. The `%` is optional.
+fn parse_percentage(value: &str) -> Option<f64> {
+    value
+        .strip_suffix('%')
+        .unwrap_or(value)
+        .parse::<f64>()
+        .ok()
+        .map(|value| value / 100.0)
+}
+
+/// Parses a hue in degrees. The unit `deg` is optional.
+fn parse_hue(value: &str) -> Option<f64> {
+    value.strip_suffix("deg").unwrap_or(value).parse().ok()
+}
+
+fn gamma_decode(value: f64) -> f64 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn gamma_encode(value: f64) -> f64 {
+    let value = if value <= 0.003_130_8 {
+        12.92 * value
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+
+    value.clamp(0.0, 1.0)
+}
+
+fn to_u8(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Formats `value` with at most `decimals` decimals and without trailing zeros.
+fn format_number(value: f64, decimals: usize) -> String {
+    let formatted = format!("{value:.decimals$}");
+
+    if formatted.contains('.') {
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        formatted
+    }
+}
@@ -1,3 +1,4 @@
+mod color;
 mod connection_ready_state;
 mod datetime;
 mod direction;
@@ -6,12 +7,15 @@ mod elements_maybe_signal;
 mod maybe_rw_signal;
 mod pointer_type;
 mod position;
+mod reconnect_policy;
+mod shared_connections;
 mod size;
 mod ssr_safe_method;
 mod storage;
 pub(crate) mod url;
 mod use_rw_signal;
 
+pub use color::*;
 pub use connection_ready_state::*;
 pub(crate) use datetime::*;
 pub use direction::*;
@@ -20,6 +24,9 @@ pub use elements_maybe_signal::*;
 pub use maybe_rw_signal::*;
 pub use pointer_type::*;
 pub use position::*;
+pub use reconnect_policy::*;
+pub use shared_connections::provide_shared_connections;
+pub(crate) use shared_connections::SharedConnections;
 pub use size::*;
 pub(crate) use ssr_safe_method::*;
 pub use storage::*;
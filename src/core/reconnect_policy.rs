@@ -0,0 +1,339 @@
+use default_struct_builder::DefaultBuilder;
+use leptos::leptos_dom::helpers::IntervalHandle;
+use leptos::*;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Decides if and when a connection is re-established after it has been lost.
+///
+/// Used by [`crate::use_websocket`] and [`crate::use_event_source`]. [`BackoffReconnectPolicy`]
+/// covers the usual strategies but you can implement this trait for your own.
+///
+/// ```
+/// # use leptos_use::core::ReconnectPolicy;
+/// # use std::time::Duration;
+/// #
+/// /// Reconnects forever, quickly during the first minute and then every minute.
+/// struct Patient;
+///
+/// impl ReconnectPolicy for Patient {
+///     fn should_reconnect(&self, _attempts: u64, _elapsed: u64) -> bool {
+///         true
+///     }
+///
+///     fn delay(&self, attempts: u64) -> Duration {
+///         if attempts < 10 {
+///             Duration::from_secs(5)
+///         } else {
+///             Duration::from_secs(60)
+///         }
+///     }
+/// }
+/// ```
+pub trait ReconnectPolicy {
+    /// Returns `true` if another attempt should be made after `attempts` attempts have already
+    /// been made and `elapsed` ms have passed since the connection was lost.
+    fn should_reconnect(&self, attempts: u64, elapsed: u64) -> bool;
+
+    /// Returns the time to wait before the next attempt after `attempts` attempts have already
+    /// been made. The first attempt is `attempts == 0`.
+    fn delay(&self, attempts: u64) -> Duration;
+
+    /// Like [`ReconnectPolicy::delay`] but with a base interval of `interval` ms that has been
+    /// requested by the server, e.g. with the `retry` field of server-sent events.
+    /// Ignores `interval` by default.
+    fn delay_with_interval(&self, attempts: u64, interval: u64) -> Duration {
+        let _ = interval;
+        self.delay(attempts)
+    }
+}
+
+impl<P: ReconnectPolicy + ?Sized> ReconnectPolicy for Rc<P> {
+    fn should_reconnect(&self, attempts: u64, elapsed: u64) -> bool {
+        (**self).should_reconnect(attempts, elapsed)
+    }
+
+    fn delay(&self, attempts: u64) -> Duration {
+        (**self).delay(attempts)
+    }
+
+    fn delay_with_interval(&self, attempts: u64, interval: u64) -> Duration {
+        (**self).delay_with_interval(attempts, interval)
+    }
+}
+
+/// Upper bound for the interval between two attempts if no `max_interval` is set: 1 hour.
+const DEFAULT_MAX_INTERVAL: u64 = 60 * 60 * 1000;
+
+/// The built-in [`ReconnectPolicy`]. Limits the number of attempts and the time spent on them
+/// and waits according to a [`ReconnectBackoff`] between them.
+///
+/// ```
+/// # use leptos_use::core::{BackoffReconnectPolicy, ReconnectBackoff, ReconnectLimit};
+/// #
+/// // At most 10 attempts within 2 minutes. Waits 1s, 2s, 4s, 8s, ... (at most 30s) between
+/// // attempts with a random jitter of ±20%.
+/// let policy = BackoffReconnectPolicy::default()
+///     .limit(ReconnectLimit::Limited(10))
+///     .interval(1000)
+///     .backoff(ReconnectBackoff::Exponential { factor: 2.0 })
+///     .max_interval(30_000)
+///     .max_elapsed_time(120_000)
+///     .jitter(0.2);
+/// ```
+///
+/// For any other schedule compute the interval yourself from the number of attempts that have
+/// already been made.
+///
+/// ```
+/// # use leptos_use::core::{BackoffReconnectPolicy, ReconnectBackoff};
+/// #
+/// // try again right away once, then after 5s and then every 30s
+/// let policy = BackoffReconnectPolicy::default().backoff(ReconnectBackoff::custom(|attempts| {
+///     match attempts {
+///         0 => 0,
+///         1 => 5_000,
+///         _ => 30_000,
+///     }
+/// }));
+/// ```
+#[derive(DefaultBuilder, Clone, Debug, PartialEq)]
+pub struct BackoffReconnectPolicy {
+    /// Maximum number of attempts. Defaults to `ReconnectLimit::Limited(3)`.
+    /// Use `ReconnectLimit::Infinite` for infinite retries.
+    limit: ReconnectLimit,
+
+    /// Base interval between attempts in ms. Defaults to 3000.
+    interval: u64,
+
+    /// How the interval grows with every attempt. Defaults to `ReconnectBackoff::Fixed`.
+    backoff: ReconnectBackoff,
+
+    /// Upper bound for the interval between two attempts in ms. Defaults to `None` which
+    /// caps the interval at one hour.
+    #[builder(into)]
+    max_interval: Option<u64>,
+
+    /// No more attempts are made when this many ms have passed since the connection was lost.
+    /// Defaults to `None`.
+    #[builder(into)]
+    max_elapsed_time: Option<u64>,
+
+    /// Randomizes the interval by up to this fraction in both directions, e.g. `0.2` for ±20%.
+    /// This prevents many clients from reconnecting at the same time. Only applied in the
+    /// browser. Defaults to `0.0`.
+    jitter: f64,
+}
+
+impl Default for BackoffReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            limit: ReconnectLimit::default(),
+            interval: 3000,
+            backoff: ReconnectBackoff::Fixed,
+            max_interval: None,
+            max_elapsed_time: None,
+            jitter: 0.0,
+        }
+    }
+}
+
+impl ReconnectPolicy for BackoffReconnectPolicy {
+    fn should_reconnect(&self, attempts: u64, elapsed: u64) -> bool {
+        !self.limit.is_exceeded_by(attempts)
+            && self
+                .max_elapsed_time
+                .map_or(true, |max_elapsed| elapsed < max_elapsed)
+    }
+
+    fn delay(&self, attempts: u64) -> Duration {
+        self.delay_with_interval(attempts, self.interval)
+    }
+
+    fn delay_with_interval(&self, attempts: u64, interval: u64) -> Duration {
+        let interval = interval as f64;
+
+        let delay = match self.backoff {
+            ReconnectBackoff::Fixed => interval,
+            ReconnectBackoff::Exponential { factor } => {
+                interval * factor.powi(attempts.min(i32::MAX as u64) as i32)
+            }
+            ReconnectBackoff::Fibonacci => interval * fibonacci(attempts) as f64,
+            ReconnectBackoff::Custom(ref delay) => delay(attempts) as f64,
+        };
+
+        // also keeps an overflowing backoff from wrapping around in `set_timeout`
+        let mut delay = delay.min(self.max_interval.unwrap_or(DEFAULT_MAX_INTERVAL) as f64);
+
+        if self.jitter > 0.0 {
+            // random factor in [1 - jitter, 1 + jitter]
+            delay *= 1.0 + self.jitter * (2.0 * random() - 1.0);
+        }
+
+        Duration::from_millis(delay.max(0.0) as u64)
+    }
+}
+
+/// Resolves the reconnect options of the connection hooks. Without a custom policy a
+/// [`BackoffReconnectPolicy`] with the given `limit` and `interval` is used.
+pub(crate) fn resolve_reconnect_policy(
+    policy: Option<Rc<dyn ReconnectPolicy>>,
+    limit: ReconnectLimit,
+    interval: u64,
+) -> Rc<dyn ReconnectPolicy> {
+    policy.unwrap_or_else(|| {
+        Rc::new(
+            BackoffReconnectPolicy::default()
+                .limit(limit)
+                .interval(interval),
+        )
+    })
+}
+
+/// Random number in [0, 1). Always `0.5` outside the browser which disables the jitter.
+fn random() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Math::random()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0.5
+    }
+}
+
+/// Returns the `n`th number of the sequence 1, 1, 2, 3, 5, 8, ...
+fn fibonacci(n: u64) -> u64 {
+    let (mut a, mut b) = (1_u64, 1_u64);
+    for _ in 0..n.min(100) {
+        (a, b) = (b, a.saturating_add(b));
+    }
+    a
+}
+
+/// How the interval between reconnection attempts of a [`BackoffReconnectPolicy`] grows.
+#[derive(Clone)]
+pub enum ReconnectBackoff {
+    /// Always wait `interval` ms.
+    Fixed,
+    /// Wait `interval * factor ^ attempts` ms.
+    Exponential { factor: f64 },
+    /// Wait `interval` times the Fibonacci number of the attempt, i.e. 1, 1, 2, 3, 5, 8, ... times `interval` ms.
+    Fibonacci,
+    /// Wait the number of ms returned by the function for the number of attempts that have
+    /// already been made. `interval` is ignored but `max_interval` and `jitter` still apply.
+    Custom(Rc<dyn Fn(u64) -> u64>),
+}
+
+impl ReconnectBackoff {
+    /// Shortcut for `ReconnectBackoff::Custom(Rc::new(delay))`.
+    pub fn custom(delay: impl Fn(u64) -> u64 + 'static) -> Self {
+        Self::Custom(Rc::new(delay))
+    }
+}
+
+impl fmt::Debug for ReconnectBackoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed => write!(f, "Fixed"),
+            Self::Exponential { factor } => f
+                .debug_struct("Exponential")
+                .field("factor", factor)
+                .finish(),
+            Self::Fibonacci => write!(f, "Fibonacci"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl PartialEq for ReconnectBackoff {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Fixed, Self::Fixed) | (Self::Fibonacci, Self::Fibonacci) => true,
+            (Self::Exponential { factor: a }, Self::Exponential { factor: b }) => a == b,
+            (Self::Custom(a), Self::Custom(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Maximum number of reconnection attempts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReconnectLimit {
+    Infinite,
+    Limited(u64),
+}
+
+impl Default for ReconnectLimit {
+    fn default() -> Self {
+        ReconnectLimit::Limited(3)
+    }
+}
+
+impl ReconnectLimit {
+    pub fn is_exceeded_by(self, times: u64) -> bool {
+        match self {
+            ReconnectLimit::Infinite => false,
+            ReconnectLimit::Limited(limit) => times >= limit,
+        }
+    }
+}
+
+/// Counts down the seconds until the next scheduled reconnection attempt.
+#[derive(Clone, Copy)]
+pub(crate) struct ReconnectCountdown {
+    seconds: RwSignal<Option<u64>>,
+    interval: StoredValue<Option<IntervalHandle>>,
+}
+
+impl ReconnectCountdown {
+    pub(crate) fn new() -> Self {
+        let countdown = Self {
+            seconds: create_rw_signal(None),
+            interval: store_value(None),
+        };
+
+        on_cleanup(move || {
+            if let Some(interval) = countdown.interval.try_update_value(Option::take).flatten() {
+                interval.clear();
+            }
+        });
+
+        countdown
+    }
+
+    /// Seconds until the next attempt or `None` if no attempt is scheduled
+    pub(crate) fn seconds(self) -> Signal<Option<u64>> {
+        self.seconds.read_only().into()
+    }
+
+    /// Starts counting down from `delay`.
+    pub(crate) fn start(self, delay: Duration) {
+        self.stop();
+
+        let attempt_at = js_sys::Date::now() + delay.as_millis() as f64;
+
+        let update = move || {
+            let remaining = (attempt_at - js_sys::Date::now()).max(0.0);
+            self.seconds.set(Some((remaining / 1000.0).ceil() as u64));
+        };
+
+        update();
+
+        self.interval
+            .set_value(set_interval_with_handle(update, Duration::from_millis(250)).ok());
+    }
+
+    /// Stops counting down because the attempt is made or has been cancelled.
+    pub(crate) fn stop(self) {
+        if let Some(interval) = self.interval.try_update_value(Option::take).flatten() {
+            interval.clear();
+        }
+
+        if self.seconds.try_get_untracked().flatten().is_some() {
+            self.seconds.set(None);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use leptos::*;
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Provides the registry that [`fn@crate::use_shared_event_source`] and
+/// [`fn@crate::use_shared_websocket`] use to share connections.
+///
+/// Only components below the owner of the registry share connections. If none has been
+/// provided, the first of these functions that is called provides one itself, which means that
+/// sibling components don't share connections with each other. So it's best to call this once
+/// at the root of your app.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::core::provide_shared_connections;
+/// #
+/// #[component]
+/// fn App() -> impl IntoView {
+///     provide_shared_connections();
+///
+///     view! { }
+/// }
+/// ```
+pub fn provide_shared_connections() {
+    provide_context(SharedConnections::new());
+}
+
+/// Per-URL registry of connections that are shared between components.
+///
+/// Every connection is created in its own reactive owner below the owner of the registry.
+/// It's disposed, and so closed, as soon as the last subscriber's owner is disposed.
+#[derive(Clone)]
+pub(crate) struct SharedConnections {
+    owner: Option<Owner>,
+    entries: StoredValue<HashMap<(String, TypeId), SharedEntry>>,
+}
+
+struct SharedEntry {
+    connection: Rc<dyn Any>,
+    subscribers: usize,
+    _disposer: Disposer,
+}
+
+impl SharedConnections {
+    fn new() -> Self {
+        Self {
+            owner: Owner::current(),
+            entries: store_value(HashMap::new()),
+        }
+    }
+
+    /// Returns the provided registry or provides a new one.
+    pub(crate) fn use_or_provide() -> Self {
+        if let Some(connections) = use_context::<Self>() {
+            return connections;
+        }
+
+        let connections = Self::new();
+        provide_context(connections.clone());
+
+        connections
+    }
+
+    /// Returns the connection of type `V` for `url` and subscribes the current owner to it.
+    /// If there is none yet, it's created by `create`.
+    pub(crate) fn subscribe<V>(&self, url: &str, create: impl FnOnce() -> V + 'static) -> Rc<V>
+    where
+        V: 'static,
+    {
+        let key = (url.to_string(), TypeId::of::<V>());
+
+        let existing = self.entries.update_value(|entries| {
+            entries.get_mut(&key).map(|entry| {
+                entry.subscribers += 1;
+                Rc::clone(&entry.connection)
+            })
+        });
+
+        let connection = match existing {
+            Some(connection) => connection
+                .downcast::<V>()
+                .unwrap_or_else(|_| unreachable!("the key contains the type id")),
+            None => {
+                let create = Cell::new(Some(create));
+                let create_in_child = move || {
+                    as_child_of_current_owner(move |()| {
+                        Rc::new(create.take().expect("called only once")())
+                    })(())
+                };
+
+                let (connection, disposer) = match self.owner.clone() {
+                    Some(owner) => with_owner(owner, create_in_child),
+                    None => create_in_child(),
+                };
+
+                self.entries.update_value(|entries| {
+                    entries.insert(
+                        key.clone(),
+                        SharedEntry {
+                            connection: Rc::clone(&connection) as Rc<dyn Any>,
+                            subscribers: 1,
+                            _disposer: disposer,
+                        },
+                    );
+                });
+
+                connection
+            }
+        };
+
+        let entries = self.entries;
+        on_cleanup(move || {
+            let unused = entries
+                .try_update_value(|entries| {
+                    let entry = entries.get_mut(&key)?;
+                    entry.subscribers -= 1;
+
+                    if entry.subscribers == 0 {
+                        entries.remove(&key)
+                    } else {
+                        None
+                    }
+                })
+                .flatten();
+
+            // dropping the disposer closes the connection. This mustn't happen while the
+            // entries are borrowed.
+            drop(unused);
+        });
+
+        connection
+    }
+}
@@ -7,13 +7,14 @@ pub mod core;
 pub mod docs;
 #[cfg(feature = "math")]
 pub mod math;
+pub mod opfs;
 pub mod storage;
 pub mod utils;
 
-// #[cfg(web_sys_unstable_apis)]
-// mod use_webtransport;
-// #[cfg(web_sys_unstable_apis)]
-// pub use use_webtransport::*;
+#[cfg(web_sys_unstable_apis)]
+mod use_webtransport;
+#[cfg(web_sys_unstable_apis)]
+pub use use_webtransport::*;
 
 #[cfg(web_sys_unstable_apis)]
 mod use_clipboard;
@@ -25,6 +26,95 @@ mod is_none;
 mod is_ok;
 mod is_some;
 mod on_click_outside;
+mod use_object_inspector;
+mod use_speech_synthesis;
+mod use_speech_recognition;
+mod use_console_capture;
+mod use_device_motion;
+mod use_error_boundary_reporter;
+mod use_file_system_access;
+mod use_hydration_status;
+mod use_web_push;
+mod use_element_lazy_mount;
+mod use_clipboard_items;
+mod use_signature_pad;
+mod use_virtual_list;
+mod use_scratch;
+mod use_carousel;
+mod use_scroll_snap;
+mod use_history;
+mod use_manual_history;
+mod use_contrast_color;
+mod use_shared_websocket;
+mod use_shared_event_source;
+mod use_element_screenshot;
+mod use_fetch;
+mod use_worker_fn;
+mod use_web_worker;
+mod use_optimistic_action;
+mod use_async_iterator_pagination;
+mod until;
+mod use_rtc_peer_connection;
+mod use_rtc_data_channel;
+mod use_abort_controller;
+mod use_stream;
+mod signal_to_stream;
+mod use_websocket_rpc;
+mod use_history_state_scroll_restoration;
+mod use_selection_range;
+mod use_print;
+mod use_element_truncation;
+mod use_text_measurement;
+mod use_font_loading;
+mod use_style_observer;
+mod use_element_transform;
+mod use_scroll_direction;
+mod use_scroll_progress;
+mod use_mouse_pressure;
+mod use_container_breakpoints;
+mod use_iframe_messaging;
+mod use_popup_window;
+mod use_screen_details;
+mod use_device_list;
+mod use_vibrate;
+mod use_is_touch_device;
+mod use_input_capabilities;
+mod use_user_agent_data;
+mod use_device_tier;
+mod use_hardware_concurrency;
+mod use_device_memory;
+mod use_geofence;
+#[cfg(feature = "fuzzy")]
+mod use_fuzzy_search;
+mod use_sort_and_filter;
+mod use_pagination;
+mod use_step_wizard;
+mod use_aria_live_announcer;
+mod use_keyboard_focus_visible;
+mod use_context_menu;
+mod use_window_size;
+mod use_marquee_selection;
+mod use_zoom_pan;
+mod use_resize_handle;
+mod use_text_direction;
+mod use_locale;
+mod use_preferred_languages;
+mod use_dark;
+mod use_theme_color;
+mod use_screen_safe_area;
+mod use_shared_signal;
+mod use_presence;
+mod use_replicated_signal;
+mod use_offline_outbox;
+mod use_online;
+mod use_background_sync;
+mod use_cache_storage;
+mod use_connection_supervisor;
+mod use_dom_batch;
+mod use_error_reporting;
+mod use_csp_violation;
+mod use_page_lifecycle;
+mod use_bfcache;
 mod use_user_media;
 mod signal_debounced;
 mod signal_throttled;
@@ -70,6 +160,8 @@ mod use_resize_observer;
 mod use_scroll;
 mod use_service_worker;
 mod use_sorted;
+#[cfg(feature = "json_patch")]
+mod use_sse_json_patch;
 mod use_supported;
 mod use_throttle_fn;
 mod use_timeout_fn;
@@ -91,6 +183,95 @@ pub use is_none::*;
 pub use is_ok::*;
 pub use is_some::*;
 pub use on_click_outside::*;
+pub use use_object_inspector::*;
+pub use use_speech_synthesis::*;
+pub use use_speech_recognition::*;
+pub use use_console_capture::*;
+pub use use_device_motion::*;
+pub use use_error_boundary_reporter::*;
+pub use use_file_system_access::*;
+pub use use_hydration_status::*;
+pub use use_web_push::*;
+pub use use_element_lazy_mount::*;
+pub use use_clipboard_items::*;
+pub use use_signature_pad::*;
+pub use use_virtual_list::*;
+pub use use_scratch::*;
+pub use use_carousel::*;
+pub use use_scroll_snap::*;
+pub use use_history::*;
+pub use use_manual_history::*;
+pub use use_contrast_color::*;
+pub use use_shared_websocket::*;
+pub use use_shared_event_source::*;
+pub use use_element_screenshot::*;
+pub use use_fetch::*;
+pub use use_worker_fn::*;
+pub use use_web_worker::*;
+pub use use_optimistic_action::*;
+pub use use_async_iterator_pagination::*;
+pub use until::*;
+pub use use_rtc_peer_connection::*;
+pub use use_rtc_data_channel::*;
+pub use use_abort_controller::*;
+pub use use_stream::*;
+pub use signal_to_stream::*;
+pub use use_websocket_rpc::*;
+pub use use_history_state_scroll_restoration::*;
+pub use use_selection_range::*;
+pub use use_print::*;
+pub use use_element_truncation::*;
+pub use use_text_measurement::*;
+pub use use_font_loading::*;
+pub use use_style_observer::*;
+pub use use_element_transform::*;
+pub use use_scroll_direction::*;
+pub use use_scroll_progress::*;
+pub use use_mouse_pressure::*;
+pub use use_container_breakpoints::*;
+pub use use_iframe_messaging::*;
+pub use use_popup_window::*;
+pub use use_screen_details::*;
+pub use use_device_list::*;
+pub use use_vibrate::*;
+pub use use_is_touch_device::*;
+pub use use_input_capabilities::*;
+pub use use_user_agent_data::*;
+pub use use_device_tier::*;
+pub use use_hardware_concurrency::*;
+pub use use_device_memory::*;
+pub use use_geofence::*;
+#[cfg(feature = "fuzzy")]
+pub use use_fuzzy_search::*;
+pub use use_sort_and_filter::*;
+pub use use_pagination::*;
+pub use use_step_wizard::*;
+pub use use_aria_live_announcer::*;
+pub use use_keyboard_focus_visible::*;
+pub use use_context_menu::*;
+pub use use_window_size::*;
+pub use use_marquee_selection::*;
+pub use use_zoom_pan::*;
+pub use use_resize_handle::*;
+pub use use_text_direction::*;
+pub use use_locale::*;
+pub use use_preferred_languages::*;
+pub use use_dark::*;
+pub use use_theme_color::*;
+pub use use_screen_safe_area::*;
+pub use use_shared_signal::*;
+pub use use_presence::*;
+pub use use_replicated_signal::*;
+pub use use_offline_outbox::*;
+pub use use_online::*;
+pub use use_background_sync::*;
+pub use use_cache_storage::*;
+pub use use_connection_supervisor::*;
+pub use use_dom_batch::*;
+pub use use_error_reporting::*;
+pub use use_csp_violation::*;
+pub use use_page_lifecycle::*;
+pub use use_bfcache::*;
 pub use use_user_media::*;
 pub use signal_debounced::*;
 pub use signal_throttled::*;
@@ -136,6 +317,8 @@ pub use use_resize_observer::*;
 pub use use_scroll::*;
 pub use use_service_worker::*;
 pub use use_sorted::*;
+#[cfg(feature = "json_patch")]
+pub use use_sse_json_patch::*;
 pub use use_supported::*;
 pub use use_throttle_fn::*;
 pub use use_timeout_fn::*;
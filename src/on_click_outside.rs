@@ -1,6 +1,7 @@
-use crate::core::{ElementMaybeSignal, ElementsMaybeSignal};
+use crate::core::{ElementMaybeSignal, ElementsMaybeSignal, PointerType};
 use cfg_if::cfg_if;
 use default_struct_builder::DefaultBuilder;
+use leptos::MaybeSignal;
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
     use leptos::*;
@@ -76,6 +77,40 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 ///
 /// ```
 ///
+/// The selectors given to `ignore` are resolved once. If the ignored elements change, use
+/// `ignore_selectors` instead. These are matched against every element that the event passes
+/// through, including elements inside of shadow DOMs.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::logging::log;
+/// # use leptos::html::Div;
+/// # use leptos_use::{on_click_outside_with_options, OnClickOutsideOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let target = create_node_ref::<Div>();
+/// let (ignored, set_ignored) = create_signal(vec![".toolbar".to_string()]);
+///
+/// on_click_outside_with_options(
+///     target,
+///     move |event| { log!("{:?}", event); },
+///     OnClickOutsideOptions::default().ignore_selectors(ignored),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Press or Release
+///
+/// By default the handler is called when a click outside of the target is released. A press
+/// that starts inside of the target and is released outside of it (e.g. when selecting text)
+/// doesn't count. Use `detect_on(ClickOutsideDetectOn::Press)` to call the handler as soon as
+/// the pointer is pressed outside of the target instead.
+///
+/// With `pointer_types` only presses of some pointer types are detected, e.g. to ignore pens.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this amounts to a no-op.
@@ -116,8 +151,11 @@ where
     {
         let OnClickOutsideOptions {
             ignore,
+            ignore_selectors,
             capture,
             detect_iframes,
+            detect_on,
+            pointer_types,
         } = options;
 
         // Fixes: https://github.com/vueuse/vueuse/issues/1520
@@ -147,12 +185,30 @@ where
         let should_ignore = move |event: &web_sys::UiEvent| {
             let ignore = ignore.get_untracked();
 
-            ignore.into_iter().flatten().any(|element| {
+            let ignored_element = ignore.into_iter().flatten().any(|element| {
                 let element: web_sys::EventTarget = element.into();
 
                 event_target::<web_sys::EventTarget>(event) == element
                     || event.composed_path().includes(element.as_ref(), 0)
-            })
+            });
+
+            // the composed path crosses shadow DOM boundaries
+            ignored_element
+                || ignore_selectors.with_untracked(|selectors| {
+                    !selectors.is_empty()
+                        && event.composed_path().iter().any(|target| {
+                            target.dyn_ref::<web_sys::Element>().is_some_and(|element| {
+                                selectors
+                                    .iter()
+                                    .any(|selector| element.matches(selector).unwrap_or_default())
+                            })
+                        })
+                })
+        };
+
+        let is_allowed_pointer = move |event: &web_sys::PointerEvent| {
+            let pointer_type = event.pointer_type();
+            pointer_types.iter().any(|p| p.to_string() == pointer_type)
         };
 
         let target = target.into();
@@ -192,7 +248,8 @@ where
             }
         };
 
-        let remove_click_listener = {
+        // with `Press` the handler is called by the pointer listener
+        let remove_click_listener = (detect_on == ClickOutsideDetectOn::Release).then(|| {
             let mut listener = listener.clone();
 
             use_event_listener_with_options::<_, web_sys::Window, _, _>(
@@ -203,24 +260,41 @@ where
                     .passive(true)
                     .capture(capture),
             )
-        };
+        });
 
         let remove_pointer_listener = {
             let target = target.clone();
             let should_listen = Rc::clone(&should_listen);
+            let mut handler = handler.clone();
 
             use_event_listener_with_options::<_, web_sys::Window, _, _>(
                 window(),
                 pointerdown,
                 move |event| {
                     if let Some(el) = target.get_untracked() {
-                        should_listen.set(
-                            !event.composed_path().includes(el.into().as_ref(), 0)
-                                && !should_ignore(&event),
-                        );
+                        let is_outside = is_allowed_pointer(&event)
+                            && !event.composed_path().includes(el.into().as_ref(), 0)
+                            && !should_ignore(&event);
+
+                        match detect_on {
+                            ClickOutsideDetectOn::Release => should_listen.set(is_outside),
+                            ClickOutsideDetectOn::Press => {
+                                if is_outside {
+                                    #[cfg(debug_assertions)]
+                                    let prev = SpecialNonReactiveZone::enter();
+
+                                    handler(event.into());
+
+                                    #[cfg(debug_assertions)]
+                                    SpecialNonReactiveZone::exit(prev);
+                                }
+                            }
+                        }
                     }
                 },
-                UseEventListenerOptions::default().passive(true),
+                UseEventListenerOptions::default()
+                    .passive(true)
+                    .capture(capture),
             )
         };
 
@@ -256,7 +330,9 @@ where
         };
 
         move || {
-            remove_click_listener();
+            if let Some(f) = remove_click_listener {
+                f();
+            }
             remove_pointer_listener();
             if let Some(f) = remove_blur_listener {
                 f();
@@ -276,11 +352,35 @@ where
     #[builder(skip)]
     ignore: ElementsMaybeSignal<T, web_sys::EventTarget>,
 
+    /// CSS selectors of elements that should not trigger the callback. Unlike `ignore` these are
+    /// matched when the event happens so elements that are added later are ignored as well.
+    /// Defaults to `[]`.
+    #[builder(into)]
+    ignore_selectors: MaybeSignal<Vec<String>>,
+
     /// Use capturing phase for internal event listener. Defaults to `true`.
     capture: bool,
 
     /// Run callback if focus moves to an iframe. Defaults to `false`.
     detect_iframes: bool,
+
+    /// If the callback is called when the pointer is pressed or released outside of the target.
+    /// Defaults to `ClickOutsideDetectOn::Release`.
+    detect_on: ClickOutsideDetectOn,
+
+    /// Pointer types whose presses are detected. Defaults to `[Mouse, Touch, Pen]`.
+    pointer_types: Vec<PointerType>,
+}
+
+/// When [`on_click_outside`] calls the callback.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClickOutsideDetectOn {
+    /// When the pointer is pressed outside of the target.
+    Press,
+    /// When a click outside of the target is released. Presses that start inside of the target
+    /// are ignored.
+    #[default]
+    Release,
 }
 
 impl<T> Default for OnClickOutsideOptions<T>
@@ -290,8 +390,11 @@ where
     fn default() -> Self {
         Self {
             ignore: Default::default(),
+            ignore_selectors: MaybeSignal::default(),
             capture: true,
             detect_iframes: false,
+            detect_on: ClickOutsideDetectOn::default(),
+            pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
         }
     }
 }
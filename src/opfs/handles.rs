@@ -0,0 +1,261 @@
+#![cfg_attr(feature = "ssr", allow(dead_code))]
+
+use crate::js_fut;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+pub(crate) const INTERNAL_OPFS_EVENT: &str = "leptos-use-opfs";
+
+/// Returns the root directory of the origin private file system.
+///
+/// This works in the main thread as well as in workers.
+pub async fn opfs_root() -> Result<web_sys::FileSystemDirectoryHandle, OpfsError> {
+    let global = js_sys::global();
+
+    let storage = js_sys::Reflect::get(&global, &"navigator".into())
+        .and_then(|navigator| js_sys::Reflect::get(&navigator, &"storage".into()))
+        .map_err(OpfsError::NotSupported)?;
+
+    if storage.is_undefined() {
+        return Err(OpfsError::NotSupported(storage));
+    }
+
+    let storage = storage.unchecked_into::<web_sys::StorageManager>();
+
+    js_fut!(storage.get_directory())
+        .await
+        .map(|handle| handle.unchecked_into())
+        .map_err(OpfsError::NotSupported)
+}
+
+/// Returns the directory at `path` which consists of directory names separated by `/`.
+/// An empty path is the root directory. If `create` is `true` missing directories are created.
+pub async fn opfs_directory_handle(
+    path: &str,
+    create: bool,
+) -> Result<web_sys::FileSystemDirectoryHandle, OpfsError> {
+    let mut directory = opfs_root().await?;
+
+    let mut options = web_sys::FileSystemGetDirectoryOptions::new();
+    options.create(create);
+
+    for name in path_segments(path) {
+        directory = js_fut!(directory.get_directory_handle_with_options(name, &options))
+            .await
+            .map(|handle| handle.unchecked_into())
+            .map_err(OpfsError::from_get_handle)?;
+    }
+
+    Ok(directory)
+}
+
+/// Returns the file at `path`. If `create` is `true` the file and its parent directories
+/// are created if they don't exist yet.
+pub async fn opfs_file_handle(
+    path: &str,
+    create: bool,
+) -> Result<web_sys::FileSystemFileHandle, OpfsError> {
+    let (directory_path, file_name) = split_path(path);
+    let directory = opfs_directory_handle(directory_path, create).await?;
+
+    let mut options = web_sys::FileSystemGetFileOptions::new();
+    options.create(create);
+
+    js_fut!(directory.get_file_handle_with_options(file_name, &options))
+        .await
+        .map(|handle| handle.unchecked_into())
+        .map_err(OpfsError::from_get_handle)
+}
+
+/// Creates a synchronous access handle for the file at `path` which is created if it doesn't exist.
+///
+/// Synchronous access handles are only available in dedicated workers. They are the fastest way
+/// to read and write files and are meant for high-throughput persistence like databases.
+/// Only one access handle can be open for a file at a time so make sure to `close()` it.
+///
+/// ```
+/// # use leptos_use::opfs::opfs_sync_access_handle;
+/// #
+/// # async fn in_worker() {
+/// let handle = opfs_sync_access_handle("db/data.bin").await.unwrap();
+///
+/// let _ = handle.write_with_u8_array(&[1, 2, 3]);
+/// let _ = handle.flush();
+/// handle.close();
+/// # }
+/// ```
+pub async fn opfs_sync_access_handle(
+    path: &str,
+) -> Result<web_sys::FileSystemSyncAccessHandle, OpfsError> {
+    let file = opfs_file_handle(path, true).await?;
+
+    js_fut!(file.create_sync_access_handle())
+        .await
+        .map(|handle| handle.unchecked_into())
+        .map_err(OpfsError::GetHandle)
+}
+
+/// Errors of the OPFS functions and hooks.
+#[derive(Error, Debug, Clone)]
+pub enum OpfsError {
+    #[error("origin private file system not supported")]
+    NotSupported(JsValue),
+    #[error("entry not found")]
+    NotFound,
+    #[error("failed to get handle")]
+    GetHandle(JsValue),
+    #[error("failed to read file")]
+    Read(JsValue),
+    #[error("failed to write file")]
+    Write(JsValue),
+    #[error("failed to list directory")]
+    List(JsValue),
+    #[error("failed to remove entry")]
+    Remove(JsValue),
+}
+
+impl OpfsError {
+    fn from_get_handle(err: JsValue) -> Self {
+        Self::not_found_or(err, OpfsError::GetHandle)
+    }
+
+    fn from_remove(err: JsValue) -> Self {
+        Self::not_found_or(err, OpfsError::Remove)
+    }
+
+    /// Maps a `NotFoundError` to [`OpfsError::NotFound`] and any other error with `other`.
+    fn not_found_or(err: JsValue, other: fn(JsValue) -> Self) -> Self {
+        let name = js_sys::Reflect::get(&err, &"name".into())
+            .ok()
+            .and_then(|name| name.as_string());
+
+        if name.as_deref() == Some("NotFoundError") {
+            OpfsError::NotFound
+        } else {
+            other(err)
+        }
+    }
+}
+
+/// Contents of a file as they are read from or written to it.
+pub(crate) enum OpfsContents {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+/// Reads the whole file either as text or as bytes.
+pub(crate) async fn read_file(
+    handle: &web_sys::FileSystemFileHandle,
+    binary: bool,
+) -> Result<OpfsContents, OpfsError> {
+    let file = js_fut!(handle.get_file())
+        .await
+        .map_err(OpfsError::Read)?
+        .unchecked_into::<web_sys::File>();
+
+    if binary {
+        let buffer = js_fut!(file.array_buffer())
+            .await
+            .map_err(OpfsError::Read)?;
+        Ok(OpfsContents::Bytes(
+            js_sys::Uint8Array::new(&buffer).to_vec(),
+        ))
+    } else {
+        let text = js_fut!(file.text()).await.map_err(OpfsError::Read)?;
+        Ok(OpfsContents::Text(text.as_string().unwrap_or_default()))
+    }
+}
+
+/// Replaces the contents of the file.
+pub(crate) async fn write_file(
+    handle: &web_sys::FileSystemFileHandle,
+    contents: &OpfsContents,
+) -> Result<(), OpfsError> {
+    let writable = js_fut!(handle.create_writable())
+        .await
+        .map_err(OpfsError::Write)?
+        .unchecked_into::<web_sys::FileSystemWritableFileStream>();
+
+    let write = match contents {
+        OpfsContents::Text(text) => writable.write_with_str(text),
+        OpfsContents::Bytes(bytes) => writable.write_with_u8_array(bytes),
+    }
+    .map_err(OpfsError::Write)?;
+
+    js_fut!(write).await.map_err(OpfsError::Write)?;
+
+    // the contents are only persisted when the stream is closed
+    js_fut!(writable.close()).await.map_err(OpfsError::Write)?;
+
+    Ok(())
+}
+
+/// Removes the entry at `path`. Directories are removed recursively.
+pub(crate) async fn remove_entry(path: &str) -> Result<(), OpfsError> {
+    let (directory_path, name) = split_path(path);
+    let directory = opfs_directory_handle(directory_path, false).await?;
+
+    let mut options = web_sys::FileSystemRemoveOptions::new();
+    options.recursive(true);
+
+    js_fut!(directory.remove_entry_with_options(name, &options))
+        .await
+        .map(|_| ())
+        .map_err(OpfsError::from_remove)
+}
+
+/// Notifies the OPFS hooks on this page that the entry at `path` has changed.
+#[cfg(not(feature = "ssr"))]
+pub(crate) fn dispatch_opfs_event(path: &str) {
+    let path = normalize_path(path);
+
+    leptos::queue_microtask(move || {
+        let mut init = web_sys::CustomEventInit::new();
+        init.detail(&JsValue::from_str(&path));
+
+        if let Ok(event) =
+            web_sys::CustomEvent::new_with_event_init_dict(INTERNAL_OPFS_EVENT, &init)
+        {
+            let _ = leptos::window().dispatch_event(&event);
+        }
+    });
+}
+
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// Removes empty segments so that paths can be compared.
+pub(crate) fn normalize_path(path: &str) -> String {
+    path_segments(path).collect::<Vec<_>>().join("/")
+}
+
+/// Splits a path into the path of the parent directory and the name of the last entry.
+pub(crate) fn split_path(path: &str) -> (&str, &str) {
+    let path = path.trim_end_matches('/');
+
+    match path.rsplit_once('/') {
+        Some((directory, name)) => (directory, name),
+        None => ("", path),
+    }
+}
+
+/// Returns `true` if a change of the entry at `changed` affects the entry at `path`,
+/// i.e. if it is the same entry or one of its parent directories.
+pub(crate) fn is_affected_by(path: &str, changed: &str) -> bool {
+    changed.is_empty() || path == changed || path.starts_with(&format!("{changed}/"))
+}
+
+impl From<OpfsError> for JsValue {
+    fn from(err: OpfsError) -> Self {
+        match err {
+            OpfsError::NotSupported(value)
+            | OpfsError::GetHandle(value)
+            | OpfsError::Read(value)
+            | OpfsError::Write(value)
+            | OpfsError::List(value)
+            | OpfsError::Remove(value) => value,
+            OpfsError::NotFound => JsValue::from_str(&err.to_string()),
+        }
+    }
+}
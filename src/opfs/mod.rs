@@ -0,0 +1,11 @@
+mod handles;
+mod opfs_backend;
+mod use_opfs_directory;
+mod use_opfs_file;
+
+pub use handles::{
+    opfs_directory_handle, opfs_file_handle, opfs_root, opfs_sync_access_handle, OpfsError,
+};
+pub use opfs_backend::*;
+pub use use_opfs_directory::*;
+pub use use_opfs_file::*;
@@ -0,0 +1,88 @@
+use super::handles::*;
+use crate::storage::{StorageBackend, StorageCapabilities};
+use async_trait::async_trait;
+use wasm_bindgen::JsValue;
+
+/// A [`StorageBackend`] that stores every key in its own file in a directory of the
+/// origin private file system. Use it with [`use_storage_async`].
+///
+/// The keys are used as file names. Changes from other tabs are not reported.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::opfs::OpfsBackend;
+/// # use leptos_use::storage::use_storage_async;
+/// # use codee::string::JsonSerdeCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let settings = use_storage_async::<Vec<String>, JsonSerdeCodec>(
+///     OpfsBackend::new("settings"),
+///     "recent-files",
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// [`use_storage_async`]: crate::storage::use_storage_async
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpfsBackend {
+    directory: String,
+}
+
+impl OpfsBackend {
+    /// Creates a backend that stores its files in `directory`. Missing directories are created on the first write.
+    pub fn new(directory: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, key: &str) -> String {
+        format!("{}/{key}", self.directory)
+    }
+}
+
+impl Default for OpfsBackend {
+    fn default() -> Self {
+        Self::new("leptos-use-storage")
+    }
+}
+
+#[async_trait(?Send)]
+impl StorageBackend for OpfsBackend {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+        let handle = match opfs_file_handle(&self.path(key), false).await {
+            Ok(handle) => handle,
+            Err(OpfsError::NotFound) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        match read_file(&handle, false).await? {
+            OpfsContents::Text(text) => Ok(Some(text)),
+            OpfsContents::Bytes(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        }
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        let handle = opfs_file_handle(&self.path(key), true).await?;
+        write_file(&handle, &OpfsContents::Text(value.to_string())).await?;
+
+        Ok(())
+    }
+
+    async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+        match remove_entry(&self.path(key)).await {
+            Ok(()) | Err(OpfsError::NotFound) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn capabilities(&self) -> StorageCapabilities {
+        StorageCapabilities {
+            cross_tab_events: false,
+            max_item_size: None,
+        }
+    }
+}
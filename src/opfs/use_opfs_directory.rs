@@ -0,0 +1,225 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use super::handles::*;
+use crate::{js_fut, use_event_listener, watch_with_options, WatchOptions};
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive listing of a directory in the [origin private file system](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system) (OPFS).
+///
+/// The entries of the directory at `path` are listed sorted by name. The listing is refreshed
+/// automatically when [`use_opfs_file`] or this hook change something in the directory.
+/// Changes made by other tabs or workers are only picked up when you call `refresh`.
+///
+/// The path consists of directory names separated by `/`. An empty path is the root directory.
+/// As long as the directory doesn't exist the listing is empty.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::opfs::{use_opfs_directory, UseOpfsDirectoryReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseOpfsDirectoryReturn {
+///     entries,
+///     remove,
+///     ..
+/// } = use_opfs_directory("notes");
+///
+/// view! {
+///     <ul>
+///         <For each=move || entries.get() key=|entry| entry.name.clone() let:entry>
+///             <li>
+///                 {entry.name.clone()}
+///                 <button on:click={
+///                     let remove = remove.clone();
+///                     move |_| remove(&entry.name)
+///                 }>"Delete"</button>
+///             </li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `entries` is always empty and `refresh` and `remove` do nothing.
+///
+/// [`use_opfs_file`]: crate::opfs::use_opfs_file
+pub fn use_opfs_directory(
+    path: impl Into<MaybeSignal<String>>,
+) -> UseOpfsDirectoryReturn<impl Fn() + Clone, impl Fn(&str) + Clone> {
+    let path = path.into();
+
+    let (entries, set_entries) = create_signal(Vec::<OpfsEntry>::new());
+    let (is_loading, set_loading) = create_signal(false);
+    let (error, set_error) = create_signal(None::<OpfsError>);
+
+    // Only the result of the latest listing is applied
+    let latest_listing = store_value(0_usize);
+
+    let refresh = {
+        let path = path.clone();
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                latest_listing.update_value(|id| *id += 1);
+                let listing_id = latest_listing.get_value();
+                let path = path.get_untracked();
+
+                set_loading.set(true);
+
+                spawn_local(async move {
+                    let result = match opfs_directory_handle(&path, false).await {
+                        Ok(directory) => list_directory(&directory).await,
+                        Err(OpfsError::NotFound) => Ok(vec![]),
+                        Err(err) => Err(err),
+                    };
+
+                    // outdated or the hook has been disposed in the meantime
+                    if latest_listing.try_get_value() != Some(listing_id) {
+                        return;
+                    }
+
+                    match result {
+                        Ok(entries) => {
+                            set_entries.set(entries);
+                            set_error.set(None);
+                        }
+                        Err(err) => set_error.set(Some(err)),
+                    }
+
+                    set_loading.set(false);
+                });
+            }
+        }
+    };
+
+    let remove = {
+        let path = path.clone();
+
+        move |name: &str| {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let entry_path = normalize_path(&format!("{}/{name}", path.get_untracked()));
+
+                spawn_local(async move {
+                    match remove_entry(&entry_path).await {
+                        Ok(()) => dispatch_opfs_event(&entry_path),
+                        Err(err) => set_error.set(Some(err)),
+                    }
+                });
+            }
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = watch_with_options(
+            {
+                let path = path.clone();
+                move || path.get()
+            },
+            {
+                let refresh = refresh.clone();
+                move |_, _, _| refresh()
+            },
+            WatchOptions::default().immediate(true),
+        );
+
+        let refresh = refresh.clone();
+        let _ = use_event_listener(
+            window(),
+            ev::Custom::<web_sys::CustomEvent>::new(INTERNAL_OPFS_EVENT),
+            move |event| {
+                if let Some(changed) = event.detail().as_string() {
+                    let path = normalize_path(&path.get_untracked());
+
+                    // an entry in this directory or the directory itself has changed
+                    if split_path(&changed).0 == path || is_affected_by(&path, &changed) {
+                        refresh();
+                    }
+                }
+            },
+        );
+    }
+
+    UseOpfsDirectoryReturn {
+        entries: entries.into(),
+        is_loading: is_loading.into(),
+        error: error.into(),
+        refresh,
+        remove,
+    }
+}
+
+/// Lists the entries of the directory sorted by name.
+#[cfg(not(feature = "ssr"))]
+async fn list_directory(
+    directory: &web_sys::FileSystemDirectoryHandle,
+) -> Result<Vec<OpfsEntry>, OpfsError> {
+    // `values()` is not available in `web_sys`
+    let iterator = js_sys::Reflect::get(directory, &"values".into())
+        .and_then(|values| values.unchecked_ref::<js_sys::Function>().call0(directory))
+        .map_err(OpfsError::List)?
+        .unchecked_into::<js_sys::AsyncIterator>();
+
+    let mut entries = vec![];
+
+    loop {
+        let next = js_fut!(iterator.next().map_err(OpfsError::List)?)
+            .await
+            .map_err(OpfsError::List)?
+            .unchecked_into::<js_sys::IteratorNext>();
+
+        if next.done() {
+            break;
+        }
+
+        let handle = next.value().unchecked_into::<web_sys::FileSystemHandle>();
+
+        entries.push(OpfsEntry {
+            name: handle.name(),
+            kind: handle.kind(),
+        });
+    }
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(entries)
+}
+
+/// An entry of a directory listed by [`use_opfs_directory`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpfsEntry {
+    /// The name of the file or directory.
+    pub name: String,
+    /// If this is a file or a directory.
+    pub kind: web_sys::FileSystemHandleKind,
+}
+
+/// Return type of [`use_opfs_directory`].
+pub struct UseOpfsDirectoryReturn<RefreshFn, RemoveFn>
+where
+    RefreshFn: Fn() + Clone,
+    RemoveFn: Fn(&str) + Clone,
+{
+    /// The entries of the directory sorted by name.
+    pub entries: Signal<Vec<OpfsEntry>>,
+
+    /// `true` while the directory is being listed.
+    pub is_loading: Signal<bool>,
+
+    /// The error of the latest listing or remove if it failed.
+    pub error: Signal<Option<OpfsError>>,
+
+    /// Lists the directory again.
+    pub refresh: RefreshFn,
+
+    /// Removes the entry with the given name from the directory. Directories are removed recursively.
+    pub remove: RemoveFn,
+}
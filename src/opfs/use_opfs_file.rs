@@ -0,0 +1,298 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use super::handles::*;
+use crate::{use_event_listener, watch_with_options, WatchOptions};
+use codee::{
+    CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
+};
+use leptos::*;
+use thiserror::Error;
+
+/// Reactive file in the [origin private file system](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system) (OPFS).
+///
+/// The contents of the file at `path` are read and decoded with the given codec. Calling `write`
+/// updates `data` immediately and writes the encoded value through to the file. Other
+/// `use_opfs_file` and [`use_opfs_directory`] hooks on the same page are notified about the change.
+///
+/// The path consists of names separated by `/`. Missing directories and the file are created on
+/// the first write. As long as the file doesn't exist `data` is `None`.
+///
+/// Values are (en)decoded via the given codec. You can use any of the string or binary codecs.
+/// Binary codecs read and write the raw bytes of the file.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::opfs::{use_opfs_file, UseOpfsFileReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseOpfsFileReturn {
+///     data,
+///     is_loading,
+///     write,
+///     remove,
+///     ..
+/// } = use_opfs_file::<String, FromToStringCodec>("notes/today.txt");
+///
+/// view! {
+///     <textarea
+///         prop:value=move || data.get().unwrap_or_default()
+///         on:input=move |e| write(event_target_value(&e))
+///     />
+///     <button on:click=move |_| remove()>"Delete"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Workers
+///
+/// For high-throughput persistence from a dedicated worker use [`opfs_sync_access_handle`].
+///
+/// ## Server-Side Rendering
+///
+/// On the server `data` is always `None` and `write`, `remove` and `reload` do nothing.
+///
+/// [`use_opfs_directory`]: crate::opfs::use_opfs_directory
+/// [`opfs_sync_access_handle`]: crate::opfs::opfs_sync_access_handle
+pub fn use_opfs_file<T, C>(
+    path: impl Into<MaybeSignal<String>>,
+) -> UseOpfsFileReturn<
+    T,
+    HybridCoderError<<C as Encoder<T>>::Error>,
+    HybridCoderError<<C as Decoder<T>>::Error>,
+    impl Fn(T) + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+>
+where
+    T: Clone + 'static,
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    let path = path.into();
+
+    let (data, set_data) = create_signal(None::<T>);
+    let (is_loading, set_loading) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseOpfsFileError<_, _>>);
+
+    // Only the result of the latest read is applied
+    let latest_read = store_value(0_usize);
+
+    let reload = {
+        let path = path.clone();
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                latest_read.update_value(|id| *id += 1);
+                let read_id = latest_read.get_value();
+                let path = path.get_untracked();
+
+                set_loading.set(true);
+
+                spawn_local(async move {
+                    let result = read_value::<T, C>(&path).await;
+
+                    // outdated or the hook has been disposed in the meantime
+                    if latest_read.try_get_value() != Some(read_id) {
+                        return;
+                    }
+
+                    match result {
+                        Ok(value) => {
+                            set_data.set(value);
+                            set_error.set(None);
+                        }
+                        Err(err) => set_error.set(Some(err)),
+                    }
+
+                    set_loading.set(false);
+                });
+            }
+        }
+    };
+
+    let write = {
+        let path = path.clone();
+
+        move |value: T| {
+            let encoded = if C::is_binary() {
+                C::encode_bin(&value).map(OpfsContents::Bytes)
+            } else {
+                C::encode_str(&value).map(OpfsContents::Text)
+            };
+
+            let contents = match encoded {
+                Ok(contents) => contents,
+                Err(err) => {
+                    set_error.set(Some(UseOpfsFileError::Codec(CodecError::Encode(err))));
+                    return;
+                }
+            };
+
+            // pending reads must not overwrite the new value
+            latest_read.update_value(|id| *id += 1);
+            set_loading.set(false);
+            set_data.set(Some(value));
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                let path = path.get_untracked();
+
+                spawn_local(async move {
+                    let result = match opfs_file_handle(&path, true).await {
+                        Ok(handle) => write_file(&handle, &contents).await,
+                        Err(err) => Err(err),
+                    };
+
+                    match result {
+                        Ok(()) => {
+                            set_error.set(None);
+                            dispatch_opfs_event(&path);
+                        }
+                        Err(err) => set_error.set(Some(err.into())),
+                    }
+                });
+            }
+        }
+    };
+
+    let remove = {
+        let path = path.clone();
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let path = path.get_untracked();
+
+                spawn_local(async move {
+                    match remove_entry(&path).await {
+                        Ok(()) | Err(OpfsError::NotFound) => {
+                            latest_read.update_value(|id| *id += 1);
+                            set_data.set(None);
+                            set_error.set(None);
+                            dispatch_opfs_event(&path);
+                        }
+                        Err(err) => set_error.set(Some(err.into())),
+                    }
+                });
+            }
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = watch_with_options(
+            {
+                let path = path.clone();
+                move || path.get()
+            },
+            {
+                let reload = reload.clone();
+                move |_, _, _| reload()
+            },
+            WatchOptions::default().immediate(true),
+        );
+
+        let reload = reload.clone();
+        let _ = use_event_listener(
+            window(),
+            ev::Custom::<web_sys::CustomEvent>::new(INTERNAL_OPFS_EVENT),
+            move |event| {
+                if let Some(changed) = event.detail().as_string() {
+                    if is_affected_by(&normalize_path(&path.get_untracked()), &changed) {
+                        reload();
+                    }
+                }
+            },
+        );
+    }
+
+    UseOpfsFileReturn {
+        data: data.into(),
+        is_loading: is_loading.into(),
+        error: error.into(),
+        write,
+        remove,
+        reload,
+    }
+}
+
+/// Reads and decodes the file at `path`. Returns `None` if the file doesn't exist.
+#[cfg(not(feature = "ssr"))]
+async fn read_value<T, C>(
+    path: &str,
+) -> Result<
+    Option<T>,
+    UseOpfsFileError<
+        HybridCoderError<<C as Encoder<T>>::Error>,
+        HybridCoderError<<C as Decoder<T>>::Error>,
+    >,
+>
+where
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+{
+    let handle = match opfs_file_handle(path, false).await {
+        Ok(handle) => handle,
+        Err(OpfsError::NotFound) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    let decoded = match read_file(&handle, C::is_binary()).await? {
+        OpfsContents::Text(text) => C::decode_str(&text),
+        OpfsContents::Bytes(bytes) => C::decode_bin(bytes.as_slice()),
+    };
+
+    match decoded {
+        Ok(value) => Ok(Some(value)),
+        Err(err) => Err(UseOpfsFileError::Codec(CodecError::Decode(err))),
+    }
+}
+
+/// Errors of [`use_opfs_file`].
+#[derive(Error, Debug)]
+pub enum UseOpfsFileError<E, D> {
+    #[error("OPFS error: {0}")]
+    Opfs(#[from] OpfsError),
+    #[error("failed to encode / decode file contents: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
+
+/// Return type of [`use_opfs_file`].
+pub struct UseOpfsFileReturn<T, E, D, WriteFn, RemoveFn, ReloadFn>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+    WriteFn: Fn(T) + Clone,
+    RemoveFn: Fn() + Clone,
+    ReloadFn: Fn() + Clone,
+{
+    /// The decoded contents of the file. `None` if the file doesn't exist (yet).
+    pub data: Signal<Option<T>>,
+
+    /// `true` while the file is being read.
+    pub is_loading: Signal<bool>,
+
+    /// The error of the latest read, write or remove if it failed.
+    pub error: Signal<Option<UseOpfsFileError<E, D>>>,
+
+    /// Sets `data` and writes it to the file.
+    pub write: WriteFn,
+
+    /// Deletes the file and sets `data` to `None`.
+    pub remove: RemoveFn,
+
+    /// Reads the file again.
+    pub reload: ReloadFn,
+}
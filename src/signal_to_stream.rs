@@ -0,0 +1,201 @@
+use default_struct_builder::DefaultBuilder;
+use futures_util::stream::Stream;
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+/// Turns a signal into an async `Stream` of its values.
+///
+/// Every time the signal is set the new value is yielded by the stream. Values that are set
+/// while the stream isn't polled are buffered according to the `buffer` option. By default
+/// every value is kept, see [`StreamBuffer`] for how to bound the buffer if the consumer might
+/// be slower than the signal changes.
+///
+/// The stream ends when the reactive owner it has been created in is disposed.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use futures_util::StreamExt;
+/// # use leptos_use::signal_to_stream;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (query, set_query) = create_signal(String::new());
+///
+/// let mut queries = signal_to_stream(query);
+///
+/// spawn_local(async move {
+///     while let Some(query) = queries.next().await {
+///         logging::log!("searching for {query}");
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Options
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{signal_to_stream_with_options, SignalToStreamOptions, StreamBuffer};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (position, set_position) = create_signal((0.0, 0.0));
+///
+/// // yields the current position right away and skips positions that are outdated already
+/// let positions = signal_to_stream_with_options(
+///     position,
+///     SignalToStreamOptions::default()
+///         .immediate(true)
+///         .buffer(StreamBuffer::Latest),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// See [`use_stream`] for the other direction.
+///
+/// ## Server-Side Rendering
+///
+/// Works the same on the server. The stream ends when the reactive owner is disposed after
+/// rendering.
+pub fn signal_to_stream<T>(signal: impl Into<Signal<T>>) -> SignalStream<T>
+where
+    T: Clone + 'static,
+{
+    signal_to_stream_with_options(signal, SignalToStreamOptions::default())
+}
+
+/// Version of [`signal_to_stream`] that takes a `SignalToStreamOptions`. See [`signal_to_stream`] for how to use.
+pub fn signal_to_stream_with_options<T>(
+    signal: impl Into<Signal<T>>,
+    options: SignalToStreamOptions,
+) -> SignalStream<T>
+where
+    T: Clone + 'static,
+{
+    let SignalToStreamOptions { immediate, buffer } = options;
+
+    let signal = signal.into();
+
+    let inner = Rc::new(RefCell::new(SignalStreamInner {
+        queue: VecDeque::new(),
+        waker: None,
+        ended: false,
+    }));
+
+    let _ = watch(
+        move || signal.get(),
+        {
+            let inner = Rc::clone(&inner);
+
+            move |value, _, _| {
+                SignalStreamInner::wake(&inner, |inner| inner.push(value.clone(), buffer));
+            }
+        },
+        immediate,
+    );
+
+    on_cleanup({
+        let inner = Rc::clone(&inner);
+        move || SignalStreamInner::wake(&inner, |inner| inner.ended = true)
+    });
+
+    SignalStream { inner }
+}
+
+struct SignalStreamInner<T> {
+    queue: VecDeque<T>,
+    waker: Option<Waker>,
+    ended: bool,
+}
+
+impl<T> SignalStreamInner<T> {
+    fn wake(inner: &RefCell<Self>, change: impl FnOnce(&mut Self)) {
+        let waker = {
+            let mut inner = inner.borrow_mut();
+            change(&mut inner);
+            inner.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn push(&mut self, value: T, buffer: StreamBuffer) {
+        match buffer {
+            StreamBuffer::Unbounded => self.queue.push_back(value),
+            StreamBuffer::Latest => self.push(value, StreamBuffer::DropOldest(1)),
+            StreamBuffer::DropOldest(capacity) => {
+                if capacity == 0 {
+                    return;
+                }
+                while self.queue.len() >= capacity {
+                    self.queue.pop_front();
+                }
+                self.queue.push_back(value);
+            }
+            StreamBuffer::DropNewest(capacity) => {
+                if self.queue.len() < capacity {
+                    self.queue.push_back(value);
+                }
+            }
+        }
+    }
+}
+
+/// Stream returned by [`signal_to_stream`].
+pub struct SignalStream<T> {
+    inner: Rc<RefCell<SignalStreamInner<T>>>,
+}
+
+impl<T> Stream for SignalStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut inner = self.inner.borrow_mut();
+
+        if let Some(value) = inner.queue.pop_front() {
+            Poll::Ready(Some(value))
+        } else if inner.ended {
+            Poll::Ready(None)
+        } else {
+            inner.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// How [`signal_to_stream`] buffers values that haven't been consumed yet.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StreamBuffer {
+    /// Keep every value
+    #[default]
+    Unbounded,
+    /// Keep only the most recent value
+    Latest,
+    /// Keep at most this many values. When full, the oldest value is dropped.
+    DropOldest(usize),
+    /// Keep at most this many values. When full, new values are dropped.
+    DropNewest(usize),
+}
+
+/// Options for [`signal_to_stream_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct SignalToStreamOptions {
+    /// If `true` the current value of the signal is yielded first. Otherwise only values that
+    /// are set from now on are yielded. Defaults to `false`.
+    immediate: bool,
+
+    /// How values are buffered until they are consumed. Defaults to [`StreamBuffer::Unbounded`].
+    buffer: StreamBuffer,
+}
@@ -0,0 +1,206 @@
+#![cfg_attr(feature = "ssr", allow(unused_imports, dead_code))]
+
+use super::{StorageBackend, StorageCapabilities};
+use async_trait::async_trait;
+use default_struct_builder::DefaultBuilder;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+/// [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API) as a
+/// [`StorageBackend`] for [`use_storage_async`].
+///
+/// Unlike local storage, IndexedDB doesn't block the main thread while reading and writing and
+/// can store much larger values. The values are encoded with the codec given to
+/// [`use_storage_async`] and stored as strings in the object store `store` of the database
+/// `database` under the storage key.
+///
+/// IndexedDB doesn't notify other tabs about changes. So after every write the key is posted to
+/// a [BroadcastChannel](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel) that
+/// the other tabs listen to.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_storage_async, IndexedDbStorage, UseStorageAsyncReturn};
+/// # use codee::string::JsonSerdeCodec;
+/// #
+/// # #[component]
+/// # pub fn Demo() -> impl IntoView {
+/// let UseStorageAsyncReturn {
+///     data: drafts,
+///     set_data: set_drafts,
+///     is_ready,
+///     ..
+/// } = use_storage_async::<Vec<String>, JsonSerdeCodec>(
+///     IndexedDbStorage::default().database("my-app").store("drafts"),
+///     "all",
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Several stores can share a database. A missing store is created when it's used the first
+/// time.
+///
+/// [`use_storage_async`]: crate::storage::use_storage_async
+#[derive(DefaultBuilder, Clone, Debug, PartialEq, Eq)]
+pub struct IndexedDbStorage {
+    /// Name of the database. Defaults to `"leptos-use"`.
+    #[builder(into)]
+    database: String,
+
+    /// Name of the object store. Defaults to `"storage"`.
+    #[builder(into)]
+    store: String,
+}
+
+impl Default for IndexedDbStorage {
+    fn default() -> Self {
+        Self {
+            database: "leptos-use".to_string(),
+            store: "storage".to_string(),
+        }
+    }
+}
+
+impl IndexedDbStorage {
+    fn channel_name(&self) -> String {
+        format!("leptos-use-idb:{}/{}", self.database, self.store)
+    }
+
+    /// Tells the other tabs that the value of `key` has changed.
+    #[cfg(not(feature = "ssr"))]
+    fn notify(&self, key: &str) {
+        if let Ok(channel) = web_sys::BroadcastChannel::new(&self.channel_name()) {
+            let _ = channel.post_message(&JsValue::from_str(key));
+            channel.close();
+        }
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    async fn object_store(
+        &self,
+        mode: web_sys::IdbTransactionMode,
+    ) -> Result<(web_sys::IdbDatabase, web_sys::IdbObjectStore), JsValue> {
+        use crate::utils::{idb_open, idb_store};
+
+        let db = idb_open(&self.database, &self.store, false).await?;
+        let store = idb_store(&db, &self.store, mode)?;
+
+        Ok((db, store))
+    }
+}
+
+#[async_trait(?Send)]
+impl StorageBackend for IndexedDbStorage {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+        #[cfg(not(feature = "ssr"))]
+        {
+            use crate::utils::idb_request;
+
+            let (db, store) = self
+                .object_store(web_sys::IdbTransactionMode::Readonly)
+                .await?;
+            let value = idb_request(&store.get(&JsValue::from_str(key))?).await;
+            // the transaction still completes after closing
+            db.close();
+
+            Ok(value?.as_string())
+        }
+
+        #[cfg(feature = "ssr")]
+        {
+            let _ = key;
+            Ok(None)
+        }
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        #[cfg(not(feature = "ssr"))]
+        {
+            use crate::utils::idb_request;
+
+            let (db, store) = self
+                .object_store(web_sys::IdbTransactionMode::Readwrite)
+                .await?;
+            let result = idb_request(
+                &store.put_with_key(&JsValue::from_str(value), &JsValue::from_str(key))?,
+            )
+            .await;
+            db.close();
+
+            result?;
+            self.notify(key);
+        }
+
+        #[cfg(feature = "ssr")]
+        {
+            let _ = key;
+            let _ = value;
+        }
+
+        Ok(())
+    }
+
+    async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+        #[cfg(not(feature = "ssr"))]
+        {
+            use crate::utils::idb_request;
+
+            let (db, store) = self
+                .object_store(web_sys::IdbTransactionMode::Readwrite)
+                .await?;
+            let result = idb_request(&store.delete(&JsValue::from_str(key))?).await;
+            db.close();
+
+            result?;
+            self.notify(key);
+        }
+
+        #[cfg(feature = "ssr")]
+        {
+            let _ = key;
+        }
+
+        Ok(())
+    }
+
+    fn capabilities(&self) -> StorageCapabilities {
+        StorageCapabilities {
+            cross_tab_events: true,
+            // limited by the available disk space, not by a fixed size per value
+            max_item_size: None,
+        }
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn()>) {
+        #[cfg(not(feature = "ssr"))]
+        {
+            use crate::use_event_listener;
+            use leptos::{ev, on_cleanup};
+
+            let Ok(channel) = web_sys::BroadcastChannel::new(&self.channel_name()) else {
+                return;
+            };
+            let key = key.to_string();
+
+            let _ = use_event_listener(
+                channel.clone(),
+                ev::Custom::<web_sys::MessageEvent>::new("message"),
+                move |event| {
+                    if event.data().as_string().as_ref() == Some(&key) {
+                        on_change();
+                    }
+                },
+            );
+
+            on_cleanup(move || channel.close());
+        }
+
+        #[cfg(feature = "ssr")]
+        {
+            let _ = key;
+            let _ = on_change;
+        }
+    }
+}
@@ -1,8 +1,14 @@
+mod indexed_db_storage;
+mod storage_backend;
 mod use_local_storage;
 mod use_session_storage;
 mod use_storage;
+mod use_storage_async;
 
 pub use crate::core::StorageType;
+pub use indexed_db_storage::*;
+pub use storage_backend::*;
 pub use use_local_storage::*;
 pub use use_session_storage::*;
 pub use use_storage::*;
+pub use use_storage_async::*;
@@ -0,0 +1,191 @@
+use crate::core::StorageType;
+use async_trait::async_trait;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+/// An asynchronous key-value store that can be used with [`use_storage_async`].
+///
+/// Implement this for your own store, for example a remote key-value service, to use it behind
+/// the same reactive API as the browser storages.
+///
+/// ```
+/// # use leptos_use::storage::{StorageBackend, StorageCapabilities};
+/// # use async_trait::async_trait;
+/// # use wasm_bindgen::JsValue;
+/// #
+/// struct RemoteStore {
+///     base_url: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl StorageBackend for RemoteStore {
+///     async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+///         // fetch format!("{}/{key}", self.base_url)
+/// #       Ok(None)
+///     }
+///
+///     async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+///         // put the value
+/// #       Ok(())
+///     }
+///
+///     async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+///         // delete the value
+/// #       Ok(())
+///     }
+///
+///     fn capabilities(&self) -> StorageCapabilities {
+///         StorageCapabilities::default()
+///     }
+/// }
+/// ```
+///
+/// [`use_storage_async`]: crate::storage::use_storage_async
+#[async_trait(?Send)]
+pub trait StorageBackend {
+    /// Returns the value stored under `key` or `None` if there is none.
+    async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue>;
+
+    /// Stores `value` under `key`.
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue>;
+
+    /// Removes the value stored under `key`.
+    async fn remove_item(&self, key: &str) -> Result<(), JsValue>;
+
+    /// What this backend supports.
+    fn capabilities(&self) -> StorageCapabilities;
+
+    /// Calls `on_change` whenever the value of `key` has been changed by another tab.
+    /// Only called if [`StorageCapabilities::cross_tab_events`] is `true`. This is called inside
+    /// of the hook so you can register cleanups with `on_cleanup`. Does nothing by default.
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn()>) {
+        let _ = key;
+        let _ = on_change;
+    }
+}
+
+/// Allows to choose the backend at runtime, for example with `Rc<dyn StorageBackend>`.
+#[async_trait(?Send)]
+impl<B: StorageBackend + ?Sized> StorageBackend for Rc<B> {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+        (**self).get_item(key).await
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        (**self).set_item(key, value).await
+    }
+
+    async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+        (**self).remove_item(key).await
+    }
+
+    fn capabilities(&self) -> StorageCapabilities {
+        (**self).capabilities()
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn()>) {
+        (**self).subscribe(key, on_change)
+    }
+}
+
+#[async_trait(?Send)]
+impl<B: StorageBackend + ?Sized> StorageBackend for Box<B> {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+        (**self).get_item(key).await
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        (**self).set_item(key, value).await
+    }
+
+    async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+        (**self).remove_item(key).await
+    }
+
+    fn capabilities(&self) -> StorageCapabilities {
+        (**self).capabilities()
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn()>) {
+        (**self).subscribe(key, on_change)
+    }
+}
+
+/// Describes what a [`StorageBackend`] supports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageCapabilities {
+    /// The backend notifies about changes made in other tabs through [`StorageBackend::subscribe`].
+    pub cross_tab_events: bool,
+
+    /// The maximum size of a single encoded value in bytes. `None` means there is no known limit.
+    /// Larger values are not written and `UseStorageError::ItemTooLarge` is reported instead.
+    pub max_item_size: Option<usize>,
+}
+
+impl StorageType {
+    fn storage(&self) -> Result<web_sys::Storage, JsValue> {
+        let storage = match self {
+            StorageType::Local => leptos::window().local_storage()?,
+            StorageType::Session => leptos::window().session_storage()?,
+            StorageType::Custom(storage) => Some(storage.clone()),
+        };
+
+        storage.ok_or_else(|| JsValue::from_str("storage not returned from window"))
+    }
+}
+
+/// Local and session storage. The values are read and written synchronously.
+/// Only local storage reports changes from other tabs.
+#[async_trait(?Send)]
+impl StorageBackend for StorageType {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+        self.storage()?.get_item(key)
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        self.storage()?.set_item(key, value)
+    }
+
+    async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+        self.storage()?.remove_item(key)
+    }
+
+    fn capabilities(&self) -> StorageCapabilities {
+        StorageCapabilities {
+            cross_tab_events: !matches!(self, StorageType::Session),
+            // browser quotas apply per origin, not per item
+            max_item_size: None,
+        }
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn()>) {
+        #[cfg(not(feature = "ssr"))]
+        {
+            use crate::use_event_listener;
+            use leptos::{ev, window};
+
+            let Ok(storage) = self.storage() else {
+                return;
+            };
+            let key = key.to_string();
+
+            let _ = use_event_listener(window(), ev::storage, move |event| {
+                if event.storage_area().as_ref() != Some(&storage) {
+                    return;
+                }
+
+                // key matches or all keys deleted (None)
+                let event_key = event.key();
+                if event_key.as_ref() == Some(&key) || event_key.is_none() {
+                    on_change();
+                }
+            });
+        }
+
+        #[cfg(feature = "ssr")]
+        {
+            let _ = key;
+            let _ = on_change;
+        }
+    }
+}
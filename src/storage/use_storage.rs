@@ -1,15 +1,16 @@
+use super::StorageBackend;
 use crate::{
-    core::{MaybeRwSignal, StorageType},
-    utils::FilterOptions,
+    core::MaybeRwSignal,
+    utils::{FilterOptions, StringDecoder, StringEncoder},
 };
-use codee::{CodecError, Decoder, Encoder};
+use codee::CodecError;
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
 use std::rc::Rc;
 use thiserror::Error;
 use wasm_bindgen::JsValue;
 
-const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
+pub(super) const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 
 /// Reactive [Storage](https://developer.mozilla.org/en-US/docs/Web/API/Storage).
 ///
@@ -21,14 +22,15 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 ///
 /// ## Usage
 ///
-/// Pass a [`StorageType`] to determine the kind of key-value browser storage to use.
+/// Pass a [`StorageBackend`] to determine where the values are stored. Usually this is a
+/// [`StorageType`] for the key-value browser storages.
 /// The specified key is where data is stored. All values are stored as UTF-16 strings which
 /// is then encoded and decoded via the given [`Codec`]. This value is synced with other calls using
 /// the same key on the smae page and across tabs for local storage.
 /// See [`UseStorageOptions`] to see how behavior can be further customised.
 ///
-/// Values are (en)decoded via the given codec. You can use any of the string codecs or a
-/// binary codec wrapped in [`Base64`].
+/// Values are (en)decoded via the given codec. You can use any of the string or binary codecs.
+/// The output of binary codecs is stored base64 encoded (see [`StringEncoder`]).
 ///
 /// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
 ///   available and what feature flags they require.
@@ -39,7 +41,7 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 /// # use leptos::*;
 /// # use leptos_use::storage::{StorageType, use_local_storage, use_session_storage, use_storage};
 /// # use serde::{Deserialize, Serialize};
-/// # use codee::string::{FromToStringCodec, JsonSerdeCodec, Base64};
+/// # use codee::string::{FromToStringCodec, JsonSerdeCodec};
 /// # use codee::binary::ProstCodec;
 /// #
 /// # #[component]
@@ -56,7 +58,7 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 /// let (count, set_count, _) = use_session_storage::<i32, JsonSerdeCodec>("my-count-kept-in-js");
 ///
 /// // Bind string with SessionStorage stored in ProtoBuf format:
-/// let (id, set_id, _) = use_storage::<String, Base64<ProstCodec>>(
+/// let (id, set_id, _) = use_storage::<String, ProstCodec>(
 ///     StorageType::Session,
 ///     "my-id",
 /// );
@@ -83,6 +85,59 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 /// }
 /// ```
 ///
+/// ## Custom Backends
+///
+/// Any [`StorageBackend`] can be used instead of [`StorageType`], for example a key-value store
+/// exposed through a Tauri command or [`IndexedDbStorage`]. Backends that answer right away like
+/// the browser storages are read and written synchronously. For asynchronous backends the signal
+/// contains the initial value until the stored value has been loaded. Use [`use_storage_async`]
+/// if you need to know when that has happened.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_storage, StorageBackend, StorageCapabilities};
+/// # use async_trait::async_trait;
+/// # use codee::string::FromToStringCodec;
+/// # use wasm_bindgen::JsValue;
+/// #
+/// struct TauriStore;
+///
+/// #[async_trait(?Send)]
+/// impl StorageBackend for TauriStore {
+///     async fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+///         // invoke("store_get", key)
+/// #       Ok(None)
+///     }
+///
+///     async fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+///         // invoke("store_set", key, value)
+/// #       Ok(())
+///     }
+///
+///     async fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+///         // invoke("store_remove", key)
+/// #       Ok(())
+///     }
+///
+///     fn capabilities(&self) -> StorageCapabilities {
+///         StorageCapabilities::default()
+///     }
+/// }
+///
+/// # #[component]
+/// # pub fn Demo() -> impl IntoView {
+/// let (volume, set_volume, _) = use_storage::<u8, FromToStringCodec>(TauriStore, "volume");
+/// #    view! { }
+/// # }
+/// ```
+///
+/// The backend can also be chosen at runtime by passing an `Rc<dyn StorageBackend>`.
+///
+/// [`StorageType`]: crate::storage::StorageType
+/// [`StorageBackend`]: crate::storage::StorageBackend
+/// [`IndexedDbStorage`]: crate::storage::IndexedDbStorage
+/// [`use_storage_async`]: crate::storage::use_storage_async
+///
 /// ## Server-Side Rendering
 ///
 /// On the server the returned signals will just read/manipulate the `initial_value` without persistence.
@@ -146,25 +201,25 @@ const INTERNAL_STORAGE_EVENT: &str = "leptos-use-storage";
 /// ```
 #[inline(always)]
 pub fn use_storage<T, C>(
-    storage_type: StorageType,
+    backend: impl StorageBackend + 'static,
     key: impl AsRef<str>,
 ) -> (Signal<T>, WriteSignal<T>, impl Fn() + Clone)
 where
     T: Default + Clone + PartialEq,
-    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+    C: StringEncoder<T> + StringDecoder<T>,
 {
-    use_storage_with_options::<T, C>(storage_type, key, UseStorageOptions::default())
+    use_storage_with_options::<T, C>(backend, key, UseStorageOptions::default())
 }
 
 /// Version of [`use_storage`] that accepts [`UseStorageOptions`].
 pub fn use_storage_with_options<T, C>(
-    storage_type: StorageType,
+    backend: impl StorageBackend + 'static,
     key: impl AsRef<str>,
-    options: UseStorageOptions<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error>,
+    options: UseStorageOptions<T, <C as StringEncoder<T>>::Error, <C as StringDecoder<T>>::Error>,
 ) -> (Signal<T>, WriteSignal<T>, impl Fn() + Clone)
 where
     T: Clone + PartialEq,
-    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+    C: StringEncoder<T> + StringDecoder<T>,
 {
     let UseStorageOptions {
         on_error,
@@ -183,7 +238,7 @@ where
         let _ = listen_to_storage_changes;
         let _ = filter;
         let _ = delay_during_hydration;
-        let _ = storage_type;
+        let _ = backend;
         let _ = key;
         let _ = INTERNAL_STORAGE_EVENT;
 
@@ -198,20 +253,20 @@ where
     {
         use crate::{use_event_listener, use_window, watch_with_options, WatchOptions};
 
-        // Get storage API
-        let storage = storage_type
-            .into_storage()
-            .map_err(UseStorageError::StorageNotAvailable)
-            .and_then(|s| s.ok_or(UseStorageError::StorageReturnedNone));
-        let storage = handle_error(&on_error, storage);
+        let backend: Rc<dyn StorageBackend> = Rc::new(backend);
+        let capabilities = backend.capabilities();
+        let key = key.as_ref().to_owned();
+
+        // Incremented whenever data is set from storage so that it isn't written back
+        let (fetch_id, set_fetch_id) = create_signal(0_usize);
 
         // Schedules a storage event microtask. Uses a queue to avoid re-entering the runtime
         let dispatch_storage_event = {
-            let key = key.as_ref().to_owned();
-            let on_error = on_error.to_owned();
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
             move || {
-                let key = key.to_owned();
-                let on_error = on_error.to_owned();
+                let key = key.clone();
+                let on_error = Rc::clone(&on_error);
                 queue_microtask(move || {
                     // TODO : better to use a BroadcastChannel (use_broadcast_channel)?
                     // Note: we cannot construct a full StorageEvent so we _must_ rely on a custom event
@@ -231,147 +286,168 @@ where
             }
         };
 
-        // Fetches direct from browser storage and fills set_data if changed (memo)
+        // Fetches from storage and fills set_data if changed
         let fetch_from_storage = {
-            let storage = storage.to_owned();
-            let key = key.as_ref().to_owned();
-            let on_error = on_error.to_owned();
+            let backend = Rc::clone(&backend);
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
 
             move || {
-                let fetched = storage
-                    .to_owned()
-                    .and_then(|storage| {
-                        // Get directly from storage
-                        let result = storage
-                            .get_item(&key)
-                            .map_err(UseStorageError::GetItemFailed);
-                        handle_error(&on_error, result)
-                    })
-                    .unwrap_or_default() // Drop handled Err(())
-                    .as_ref()
-                    .map(|encoded| {
-                        // Decode item
-                        let result = C::decode(encoded)
-                            .map_err(|e| UseStorageError::ItemCodecError(CodecError::Decode(e)));
-                        handle_error(&on_error, result)
-                    })
-                    .transpose()
-                    .unwrap_or_default(); // Drop handled Err(())
-
-                match fetched {
-                    Some(value) => {
-                        // Replace data if changed
-                        if value != data.get_untracked() {
-                            set_data.set(value)
-                        }
-                    }
+                let backend = Rc::clone(&backend);
+                let key = key.clone();
+                let on_error = Rc::clone(&on_error);
+                let default = default.clone();
 
-                    // Revert to default
-                    None => set_data.set(default.clone()),
-                };
-            }
-        };
+                run_storage_future(async move { backend.get_item(&key).await }, move |result| {
+                    let fetched =
+                        handle_error(&on_error, result.map_err(UseStorageError::GetItemFailed))
+                            .unwrap_or_default() // Drop handled Err(())
+                            .as_ref()
+                            .map(|encoded| {
+                                // Decode item
+                                let result = C::decode_string(encoded).map_err(|e| {
+                                    UseStorageError::ItemCodecError(CodecError::Decode(e))
+                                });
+                                handle_error(&on_error, result)
+                            })
+                            .transpose()
+                            .unwrap_or_default(); // Drop handled Err(())
+
+                    match fetched {
+                        // Replace data if changed
+                        Some(value) => batch(move || {
+                            set_fetch_id.try_update(|id| *id += 1);
 
-        // Fires when storage needs to be fetched
-        let notify = create_trigger();
+                            if data.try_with_untracked(|data| *data != value) == Some(true) {
+                                set_data.set(value);
+                            }
+                        }),
 
-        // Refetch from storage. Keeps track of how many times we've been notified. Does not increment for calls to set_data
-        let notify_id = create_memo::<usize>(move |prev| {
-            notify.track();
-            match prev {
-                None => 1, // Avoid async fetch of initial value
-                Some(prev) => {
-                    fetch_from_storage();
-                    prev + 1
-                }
+                        // Revert to default. Like above this isn't written back to storage.
+                        None => batch(move || {
+                            set_fetch_id.try_update(|id| *id += 1);
+                            set_data.try_set(default);
+                        }),
+                    };
+                });
             }
-        });
+        };
 
         // Set item on internal (non-event) page changes to the data signal
         {
-            let storage = storage.to_owned();
-            let key = key.as_ref().to_owned();
-            let on_error = on_error.to_owned();
-            let dispatch_storage_event = dispatch_storage_event.to_owned();
+            let backend = Rc::clone(&backend);
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
+            let dispatch_storage_event = dispatch_storage_event.clone();
             let _ = watch_with_options(
-                move || (notify_id.get(), data.get()),
+                move || (fetch_id.get(), data.get()),
                 move |(id, value), prev, _| {
-                    // Skip setting storage on changes from external events. The ID will change on external events.
+                    // Skip setting storage on changes from storage. The ID will change in that case.
                     if prev.map(|(prev_id, _)| *prev_id != *id).unwrap_or_default() {
                         return;
                     }
 
-                    if let Ok(storage) = &storage {
-                        // Encode value
-                        let result = C::encode(value)
-                            .map_err(|e| UseStorageError::ItemCodecError(CodecError::Encode(e)))
-                            .and_then(|enc_value| {
-                                // Set storage -- sends a global event
-                                storage
-                                    .set_item(&key, &enc_value)
-                                    .map_err(UseStorageError::SetItemFailed)
-                            });
-                        let result = handle_error(&on_error, result);
-                        // Send internal storage event
-                        if result.is_ok() {
-                            dispatch_storage_event();
-                        }
-                    }
+                    // Encode value
+                    let result = C::encode_string(value)
+                        .map_err(|e| UseStorageError::ItemCodecError(CodecError::Encode(e)))
+                        .and_then(|encoded| match capabilities.max_item_size {
+                            Some(max_item_size) if encoded.len() > max_item_size => {
+                                Err(UseStorageError::ItemTooLarge(encoded.len()))
+                            }
+                            _ => Ok(encoded),
+                        });
+
+                    let Ok(encoded) = handle_error(&on_error, result) else {
+                        return;
+                    };
+
+                    let backend = Rc::clone(&backend);
+                    let key = key.clone();
+                    let on_error = Rc::clone(&on_error);
+                    let dispatch_storage_event = dispatch_storage_event.clone();
+
+                    run_storage_future(
+                        async move { backend.set_item(&key, &encoded).await },
+                        move |result| {
+                            let result = result.map_err(UseStorageError::SetItemFailed);
+                            // Send internal storage event
+                            if handle_error(&on_error, result).is_ok() {
+                                dispatch_storage_event();
+                            }
+                        },
+                    );
                 },
                 WatchOptions::default().filter(filter),
             );
         }
 
         // Fetch initial value
-        if delay_during_hydration && leptos::leptos_dom::HydrationCtx::is_hydrating() {
-            request_animation_frame(fetch_from_storage.clone());
+        if delay_during_hydration {
+            crate::after_hydration(fetch_from_storage.clone());
         } else {
             fetch_from_storage();
         }
 
         if listen_to_storage_changes {
-            let check_key = key.as_ref().to_owned();
-            // Listen to global storage events
-            let _ = use_event_listener(use_window(), leptos::ev::storage, move |ev| {
-                let ev_key = ev.key();
-                // Key matches or all keys deleted (None)
-                if ev_key == Some(check_key.clone()) || ev_key.is_none() {
-                    notify.notify()
-                }
-            });
+            // Listen to changes from other tabs
+            if capabilities.cross_tab_events {
+                backend.subscribe(&key, Rc::new(fetch_from_storage.clone()));
+            }
+
             // Listen to internal storage events
-            let check_key = key.as_ref().to_owned();
+            let check_key = key.clone();
+            let fetch_from_storage = fetch_from_storage.clone();
             let _ = use_event_listener(
                 use_window(),
                 ev::Custom::new(INTERNAL_STORAGE_EVENT),
                 move |ev: web_sys::CustomEvent| {
                     if Some(check_key.clone()) == ev.detail().as_string() {
-                        notify.notify()
+                        fetch_from_storage();
                     }
                 },
             );
         };
 
         // Remove from storage fn
-        let remove = {
-            let key = key.as_ref().to_owned();
-            move || {
-                let _ = storage.as_ref().map(|storage| {
-                    // Delete directly from storage
-                    let result = storage
-                        .remove_item(&key)
-                        .map_err(UseStorageError::RemoveItemFailed);
+        let remove = move || {
+            let backend = Rc::clone(&backend);
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
+            let fetch_from_storage = fetch_from_storage.clone();
+            let dispatch_storage_event = dispatch_storage_event.clone();
+
+            run_storage_future(
+                async move { backend.remove_item(&key).await },
+                move |result| {
+                    let result = result.map_err(UseStorageError::RemoveItemFailed);
                     let _ = handle_error(&on_error, result);
-                    notify.notify();
+                    fetch_from_storage();
                     dispatch_storage_event();
-                });
-            }
+                },
+            );
         };
 
         (data, set_data, remove)
     }
 }
 
+/// Runs `future` to completion right away if it doesn't have to wait for anything like the
+/// browser storages and calls `on_done` with the result. Otherwise it's spawned.
+#[cfg(not(feature = "ssr"))]
+fn run_storage_future<R: 'static>(
+    future: impl std::future::Future<Output = R> + 'static,
+    on_done: impl FnOnce(R) + 'static,
+) {
+    use futures_util::FutureExt;
+
+    let mut future = future.boxed_local();
+
+    match (&mut future).now_or_never() {
+        Some(result) => on_done(result),
+        None => spawn_local(async move { on_done(future.await) }),
+    }
+}
+
 /// Session handling errors returned by [`use_storage_with_options`].
 #[derive(Error, Debug)]
 pub enum UseStorageError<E, D> {
@@ -389,9 +465,11 @@ pub enum UseStorageError<E, D> {
     NotifyItemChangedFailed(JsValue),
     #[error("failed to encode / decode item value")]
     ItemCodecError(CodecError<E, D>),
+    #[error("item of {0} bytes is larger than the storage backend allows")]
+    ItemTooLarge(usize),
 }
 
-/// Options for use with [`use_local_storage_with_options`], [`use_session_storage_with_options`] and [`use_storage_with_options`].
+/// Options for use with [`use_local_storage_with_options`], [`use_session_storage_with_options`], [`use_storage_with_options`] and [`use_storage_async_with_options`](crate::storage::use_storage_async_with_options).
 #[derive(DefaultBuilder)]
 pub struct UseStorageOptions<T, E, D>
 where
@@ -399,24 +477,24 @@ where
 {
     // Callback for when an error occurs
     #[builder(skip)]
-    on_error: Rc<dyn Fn(UseStorageError<E, D>)>,
+    pub(super) on_error: Rc<dyn Fn(UseStorageError<E, D>)>,
     // Whether to continuously listen to changes from browser storage
-    listen_to_storage_changes: bool,
+    pub(super) listen_to_storage_changes: bool,
     // Initial value to use when the storage key is not set
     #[builder(skip)]
-    initial_value: MaybeRwSignal<T>,
+    pub(super) initial_value: MaybeRwSignal<T>,
     // Debounce or throttle the writing to storage whenever the value changes
     #[builder(into)]
-    filter: FilterOptions,
+    pub(super) filter: FilterOptions,
     /// Delays the reading of the value from storage by one animation frame during hydration.
     /// This ensures that during hydration the value is the initial value just like it is on the server
     /// which helps prevent hydration errors. Defaults to `false`.
-    delay_during_hydration: bool,
+    pub(super) delay_during_hydration: bool,
 }
 
 /// Calls the on_error callback with the given error. Removes the error from the Result to avoid double error handling.
 #[cfg(not(feature = "ssr"))]
-fn handle_error<T, E, D>(
+pub(super) fn handle_error<T, E, D>(
     on_error: &Rc<dyn Fn(UseStorageError<E, D>)>,
     result: Result<T, UseStorageError<E, D>>,
 ) -> Result<T, ()> {
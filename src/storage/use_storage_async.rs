@@ -0,0 +1,365 @@
+#![cfg_attr(feature = "ssr", allow(unused_imports))]
+
+use super::{StorageBackend, UseStorageError, UseStorageOptions};
+use crate::utils::{StringDecoder, StringEncoder};
+use codee::CodecError;
+use leptos::*;
+use std::rc::Rc;
+
+/// Reactive storage on top of an asynchronous [`StorageBackend`].
+///
+/// This works like [`use_storage`] but the values are read from and written to any store that
+/// implements [`StorageBackend`]. Besides local and session storage (through [`StorageType`])
+/// and IndexedDB (through [`IndexedDbStorage`]) this can be for example a remote key-value store
+/// of your own.
+///
+/// Because reading is asynchronous, `data` contains the initial value until the stored value has
+/// been loaded. `is_ready` tells you when that has happened. If you set the value before it has been
+/// loaded, your value wins and is written to the backend.
+///
+/// What a backend supports is described by its [`StorageCapabilities`]. Changes from other tabs
+/// are only picked up by backends that support cross-tab events. Values that are larger than
+/// the backend allows are not written and reported as `UseStorageError::ItemTooLarge` instead.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_storage_async, StorageType, UseStorageAsyncReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # pub fn Demo() -> impl IntoView {
+/// let UseStorageAsyncReturn {
+///     data: count,
+///     set_data: set_count,
+///     is_ready,
+///     remove,
+/// } = use_storage_async::<i32, FromToStringCodec>(StorageType::Local, "my-count");
+///
+/// view! {
+///     <Show when=move || is_ready.get() fallback=|| "Loading...">
+///         <button on:click=move |_| set_count.update(|count| *count += 1)>
+///             {count}
+///         </button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// The same options as for [`use_storage_with_options`] can be used.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::storage::{use_storage_async_with_options, StorageType, UseStorageOptions};
+/// # use codee::string::JsonSerdeCodec;
+/// #
+/// # #[component]
+/// # pub fn Demo() -> impl IntoView {
+/// let state = use_storage_async_with_options::<Vec<String>, JsonSerdeCodec>(
+///     StorageType::Session,
+///     "my-list",
+///     UseStorageOptions::default()
+///         .initial_value(vec!["first".to_string()])
+///         .on_error(|err| leptos::logging::error!("{err}")),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the returned signals will just read/manipulate the `initial_value` without persistence
+/// and `is_ready` is always `false`.
+///
+/// [`StorageType`]: crate::storage::StorageType
+/// [`IndexedDbStorage`]: crate::storage::IndexedDbStorage
+/// [`StorageCapabilities`]: crate::storage::StorageCapabilities
+/// [`use_storage`]: crate::storage::use_storage
+/// [`use_storage_with_options`]: crate::storage::use_storage_with_options
+pub fn use_storage_async<T, C>(
+    backend: impl StorageBackend + 'static,
+    key: impl AsRef<str>,
+) -> UseStorageAsyncReturn<T, impl Fn() + Clone>
+where
+    T: Default + Clone + PartialEq,
+    C: StringEncoder<T> + StringDecoder<T>,
+{
+    use_storage_async_with_options::<T, C>(backend, key, UseStorageOptions::default())
+}
+
+/// Version of [`use_storage_async`] that accepts [`UseStorageOptions`].
+pub fn use_storage_async_with_options<T, C>(
+    backend: impl StorageBackend + 'static,
+    key: impl AsRef<str>,
+    options: UseStorageOptions<T, <C as StringEncoder<T>>::Error, <C as StringDecoder<T>>::Error>,
+) -> UseStorageAsyncReturn<T, impl Fn() + Clone>
+where
+    T: Clone + PartialEq,
+    C: StringEncoder<T> + StringDecoder<T>,
+{
+    let UseStorageOptions {
+        on_error,
+        listen_to_storage_changes,
+        initial_value,
+        filter,
+        delay_during_hydration,
+    } = options;
+
+    let (data, set_data) = initial_value.into_signal();
+    let default = data.get_untracked();
+
+    let (is_ready, set_ready) = create_signal(false);
+
+    #[cfg(feature = "ssr")]
+    {
+        let _ = on_error;
+        let _ = listen_to_storage_changes;
+        let _ = filter;
+        let _ = delay_during_hydration;
+        let _ = backend;
+        let _ = key;
+        let _ = set_ready;
+
+        let remove = move || {
+            set_data.set(default.clone());
+        };
+
+        UseStorageAsyncReturn {
+            data,
+            set_data,
+            is_ready: is_ready.into(),
+            remove,
+        }
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        use super::use_storage::{handle_error, INTERNAL_STORAGE_EVENT};
+        use crate::{use_event_listener, use_window, watch_with_options, WatchOptions};
+        use wasm_bindgen::JsValue;
+
+        let backend: Rc<dyn StorageBackend> = Rc::new(backend);
+        let capabilities = backend.capabilities();
+        let key = key.as_ref().to_owned();
+
+        // Incremented whenever data is set from the backend so that it isn't written back
+        let (fetch_id, set_fetch_id) = create_signal(0_usize);
+        // Only the result of the latest read is applied
+        let latest_read = store_value(0_usize);
+        let written_before_ready = store_value(false);
+
+        // Schedules a storage event microtask. Uses a queue to avoid re-entering the runtime
+        let dispatch_storage_event = {
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
+
+            move || {
+                let key = key.clone();
+                let on_error = Rc::clone(&on_error);
+
+                queue_microtask(move || {
+                    let mut custom = web_sys::CustomEventInit::new();
+                    custom.detail(&JsValue::from_str(&key));
+                    let result = window()
+                        .dispatch_event(
+                            &web_sys::CustomEvent::new_with_event_init_dict(
+                                INTERNAL_STORAGE_EVENT,
+                                &custom,
+                            )
+                            .expect("failed to create custom storage event"),
+                        )
+                        .map_err(UseStorageError::NotifyItemChangedFailed);
+                    let _ = handle_error(&on_error, result);
+                })
+            }
+        };
+
+        let set_data_from_backend = move |value: T| {
+            batch(move || {
+                set_fetch_id.update(|id| *id += 1);
+
+                if value != data.get_untracked() {
+                    set_data.set(value);
+                }
+            });
+        };
+
+        // Reads from the backend and fills set_data if changed
+        let fetch_from_backend = {
+            let backend = Rc::clone(&backend);
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
+            let default = default.clone();
+
+            move || {
+                latest_read.update_value(|id| *id += 1);
+                let read_id = latest_read.get_value();
+
+                let backend = Rc::clone(&backend);
+                let key = key.clone();
+                let on_error = Rc::clone(&on_error);
+                let default = default.clone();
+
+                spawn_local(async move {
+                    let result = backend
+                        .get_item(&key)
+                        .await
+                        .map_err(UseStorageError::GetItemFailed);
+
+                    // outdated or the hook has been disposed in the meantime
+                    if latest_read.try_get_value() != Some(read_id) {
+                        return;
+                    }
+
+                    let fetched = handle_error(&on_error, result)
+                        .unwrap_or_default() // Drop handled Err(())
+                        .as_ref()
+                        .map(|encoded| {
+                            let result = C::decode_string(encoded).map_err(|e| {
+                                UseStorageError::ItemCodecError(CodecError::Decode(e))
+                            });
+                            handle_error(&on_error, result)
+                        })
+                        .transpose()
+                        .unwrap_or_default(); // Drop handled Err(())
+
+                    let keep_local_value =
+                        !is_ready.get_untracked() && written_before_ready.get_value();
+
+                    if !keep_local_value {
+                        set_data_from_backend(fetched.unwrap_or(default));
+                    }
+
+                    set_ready.set(true);
+                });
+            }
+        };
+
+        // Write to the backend whenever the data signal is changed on this page
+        {
+            let backend = Rc::clone(&backend);
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
+            let dispatch_storage_event = dispatch_storage_event.clone();
+
+            let _ = watch_with_options(
+                move || (fetch_id.get(), data.get()),
+                move |(id, value), prev, _| {
+                    // Skip values that have just been read from the backend. The ID changes in that case.
+                    if prev.map(|(prev_id, _)| *prev_id != *id).unwrap_or_default() {
+                        return;
+                    }
+
+                    if !is_ready.get_untracked() {
+                        written_before_ready.set_value(true);
+                    }
+
+                    let result = C::encode_string(value)
+                        .map_err(|e| UseStorageError::ItemCodecError(CodecError::Encode(e)))
+                        .and_then(|encoded| match capabilities.max_item_size {
+                            Some(max_item_size) if encoded.len() > max_item_size => {
+                                Err(UseStorageError::ItemTooLarge(encoded.len()))
+                            }
+                            _ => Ok(encoded),
+                        });
+
+                    let Ok(encoded) = handle_error(&on_error, result) else {
+                        return;
+                    };
+
+                    let backend = Rc::clone(&backend);
+                    let key = key.clone();
+                    let on_error = Rc::clone(&on_error);
+                    let dispatch_storage_event = dispatch_storage_event.clone();
+
+                    spawn_local(async move {
+                        let result = backend
+                            .set_item(&key, &encoded)
+                            .await
+                            .map_err(UseStorageError::SetItemFailed);
+
+                        if handle_error(&on_error, result).is_ok() {
+                            dispatch_storage_event();
+                        }
+                    });
+                },
+                WatchOptions::default().filter(filter),
+            );
+        }
+
+        // Fetch initial value
+        if delay_during_hydration {
+            crate::after_hydration(fetch_from_backend.clone());
+        } else {
+            fetch_from_backend();
+        }
+
+        if listen_to_storage_changes {
+            if capabilities.cross_tab_events {
+                backend.subscribe(&key, Rc::new(fetch_from_backend.clone()));
+            }
+
+            // Listen to internal storage events
+            let check_key = key.clone();
+            let fetch_from_backend = fetch_from_backend.clone();
+            let _ = use_event_listener(
+                use_window(),
+                ev::Custom::new(INTERNAL_STORAGE_EVENT),
+                move |ev: web_sys::CustomEvent| {
+                    if Some(check_key.clone()) == ev.detail().as_string() {
+                        fetch_from_backend();
+                    }
+                },
+            );
+        }
+
+        // Remove from backend fn
+        let remove = move || {
+            let backend = Rc::clone(&backend);
+            let key = key.clone();
+            let on_error = Rc::clone(&on_error);
+            let default = default.clone();
+            let dispatch_storage_event = dispatch_storage_event.clone();
+
+            spawn_local(async move {
+                let result = backend
+                    .remove_item(&key)
+                    .await
+                    .map_err(UseStorageError::RemoveItemFailed);
+
+                if handle_error(&on_error, result).is_ok() {
+                    set_data_from_backend(default);
+                    dispatch_storage_event();
+                }
+            });
+        };
+
+        UseStorageAsyncReturn {
+            data,
+            set_data,
+            is_ready: is_ready.into(),
+            remove,
+        }
+    }
+}
+
+/// Return type of [`use_storage_async`].
+pub struct UseStorageAsyncReturn<T, RemoveFn>
+where
+    T: 'static,
+    RemoveFn: Fn() + Clone,
+{
+    /// The current value. This is the initial value until the stored value has been loaded.
+    pub data: Signal<T>,
+
+    /// Sets the value and writes it to the backend.
+    pub set_data: WriteSignal<T>,
+
+    /// `true` as soon as the stored value has been loaded from the backend.
+    pub is_ready: Signal<bool>,
+
+    /// Removes the value from the backend and resets `data` to the initial value.
+    pub remove: RemoveFn,
+}
@@ -0,0 +1,280 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables))]
+
+use futures_util::future::{select, Either};
+use leptos::*;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use thiserror::Error;
+
+/// Wait for a signal to fulfill a condition.
+///
+/// Returns a builder whose methods return a future that resolves with the value of the signal
+/// once the condition is fulfilled. If it's fulfilled already the future resolves right away.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{until, use_websocket, UseWebSocketReturn};
+/// # use leptos_use::core::ConnectionReadyState;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn { ready_state, send, .. } =
+///     use_websocket::<String, FromToStringCodec>("wss://echo.websocket.events/");
+///
+/// spawn_local(async move {
+///     until(ready_state).is(ConnectionReadyState::Open).await;
+///
+///     send(&"Hello!".to_string());
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Conditions
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::until;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (count, set_count) = create_signal(0);
+/// let (user, set_user) = create_signal(None::<String>);
+///
+/// spawn_local(async move {
+///     let above_seven = until(count).matches(|count| *count > 7).await;
+///     let next_count = until(count).changed().await;
+///     let name: String = until(user).not_null().await;
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Timeout
+///
+/// Every future can be limited to a number of milliseconds. It then resolves with a `Result`
+/// that is [`UntilTimeoutError`] if the condition hasn't been fulfilled in time.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::until;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let (is_ready, set_ready) = create_signal(false);
+/// spawn_local(async move {
+///     match until(is_ready).is(true).timeout(5000).await {
+///         Ok(_) => { /* ready */ }
+///         Err(_) => { /* still not ready after 5 seconds */ }
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// If the reactive owner is disposed before the condition is fulfilled the future never
+/// resolves and the task waiting for it is dropped.
+///
+/// ## Server-Side Rendering
+///
+/// On the server effects don't run, so only conditions that are fulfilled already when the
+/// future is created resolve. Timeouts never elapse.
+pub fn until<T>(source: impl Into<Signal<T>>) -> Until<T>
+where
+    T: Clone + 'static,
+{
+    Until {
+        source: source.into(),
+    }
+}
+
+/// Builder returned by [`until`].
+#[derive(Clone, Copy)]
+pub struct Until<T>
+where
+    T: 'static,
+{
+    source: Signal<T>,
+}
+
+impl<T> Until<T>
+where
+    T: Clone + 'static,
+{
+    /// Resolves when the signal is equal to `value`.
+    pub fn is(self, value: T) -> UntilFuture<T>
+    where
+        T: PartialEq,
+    {
+        self.matches(move |current| current == &value)
+    }
+
+    /// Resolves when `predicate` returns `true` for the value of the signal.
+    pub fn matches(self, predicate: impl Fn(&T) -> bool + 'static) -> UntilFuture<T> {
+        until_filtered(self.source, true, move |value: &T| {
+            predicate(value).then(|| value.clone())
+        })
+    }
+
+    /// Resolves the next time the signal changes.
+    pub fn changed(self) -> UntilFuture<T> {
+        until_filtered(self.source, false, |value: &T| Some(value.clone()))
+    }
+}
+
+impl<T> Until<Option<T>>
+where
+    T: Clone + 'static,
+{
+    /// Resolves with the inner value when the signal is `Some`.
+    pub fn not_null(self) -> UntilFuture<T> {
+        until_filtered(self.source, true, Option::clone)
+    }
+}
+
+fn until_filtered<S, T>(
+    source: Signal<S>,
+    check_current: bool,
+    filter: impl Fn(&S) -> Option<T> + 'static,
+) -> UntilFuture<T>
+where
+    S: Clone + 'static,
+    T: 'static,
+{
+    let state = Rc::new(RefCell::new(UntilState {
+        value: None,
+        resolved: false,
+        waker: None,
+        stop: None,
+    }));
+
+    if check_current {
+        if let Some(value) = source.with_untracked(&filter) {
+            state.borrow_mut().value = Some(value);
+            return UntilFuture { state };
+        }
+    }
+
+    let filter = Rc::new(filter);
+
+    let stop = watch(
+        move || source.get(),
+        {
+            let state = Rc::clone(&state);
+
+            move |value, _, _| {
+                if state.borrow().resolved {
+                    return;
+                }
+
+                if let Some(value) = filter(value) {
+                    UntilState::wake(&state, |state| {
+                        state.value = Some(value);
+                        state.resolved = true;
+                    });
+                }
+            }
+        },
+        false,
+    );
+    state.borrow_mut().stop = Some(Box::new(stop));
+
+    on_cleanup({
+        let state = Rc::clone(&state);
+        // without the waker the waiting task is dropped
+        move || state.borrow_mut().waker = None
+    });
+
+    UntilFuture { state }
+}
+
+struct UntilState<T> {
+    value: Option<T>,
+    resolved: bool,
+    waker: Option<Waker>,
+    stop: Option<Box<dyn Fn()>>,
+}
+
+impl<T> UntilState<T> {
+    fn wake(state: &RefCell<Self>, change: impl FnOnce(&mut Self)) {
+        let waker = {
+            let mut state = state.borrow_mut();
+            change(&mut state);
+            state.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn stop(state: &RefCell<Self>) {
+        let stop = state.borrow_mut().stop.take();
+
+        if let Some(stop) = stop {
+            stop();
+        }
+    }
+}
+
+/// Future returned by the methods of [`Until`].
+pub struct UntilFuture<T> {
+    state: Rc<RefCell<UntilState<T>>>,
+}
+
+impl<T> UntilFuture<T>
+where
+    T: 'static,
+{
+    /// Resolves with [`UntilTimeoutError`] if the condition isn't fulfilled within `ms`
+    /// milliseconds.
+    pub fn timeout(self, ms: u64) -> impl Future<Output = Result<T, UntilTimeoutError>> {
+        #[cfg(not(feature = "ssr"))]
+        let timer = gloo_timers::future::sleep(std::time::Duration::from_millis(ms));
+        #[cfg(feature = "ssr")]
+        let timer = std::future::pending::<()>();
+
+        async move {
+            match select(self, Box::pin(timer)).await {
+                Either::Left((value, _)) => Ok(value),
+                Either::Right(_) => Err(UntilTimeoutError),
+            }
+        }
+    }
+}
+
+impl<T> Future for UntilFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let value = self.state.borrow_mut().value.take();
+
+        if let Some(value) = value {
+            UntilState::stop(&self.state);
+            Poll::Ready(value)
+        } else {
+            self.state.borrow_mut().waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for UntilFuture<T> {
+    fn drop(&mut self) {
+        UntilState::stop(&self.state);
+    }
+}
+
+/// Error of [`UntilFuture::timeout`] when the condition hasn't been fulfilled in time.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The condition hasn't been fulfilled before the timeout")]
+pub struct UntilTimeoutError;
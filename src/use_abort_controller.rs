@@ -0,0 +1,246 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use default_struct_builder::DefaultBuilder;
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// [AbortController](https://developer.mozilla.org/en-US/docs/Web/API/AbortController) that is
+/// tied to the lifecycle of the component.
+///
+/// The controller is aborted automatically when the component is unmounted, so everything
+/// that has been started with its signal is cancelled. `aborted` is a reactive signal that
+/// becomes `true` once the controller has been aborted, no matter if it was aborted by calling
+/// `abort`, by a timeout, by its parent or on cleanup.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_abort_controller;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let controller = use_abort_controller();
+///
+/// spawn_local(async move {
+///     let mut init = web_sys::RequestInit::new();
+///     init.signal(controller.signal().as_ref());
+///
+///     let _ = wasm_bindgen_futures::JsFuture::from(
+///         window().fetch_with_str_and_init("/api/report", &init),
+///     )
+///     .await;
+/// });
+///
+/// view! {
+///     <button on:click=move |_| controller.abort() disabled=controller.aborted()>"Cancel"</button>
+/// }
+/// # }
+/// ```
+///
+/// ### Children and Timeouts
+///
+/// A child controller is aborted together with its parent but can also be aborted on its own,
+/// e.g. to cancel a single request of a page that has a controller for all of them. A timeout
+/// aborts the controller after the given number of milliseconds.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_abort_controller, use_abort_controller_with_options, UseAbortControllerOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let page = use_abort_controller();
+///
+/// let search = use_abort_controller_with_options(
+///     UseAbortControllerOptions::default().parent(page).timeout(5000),
+/// );
+/// // same as
+/// let search = page.child();
+/// search.abort_after(5000);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Cancelling Other Functions
+///
+/// The controller can be passed to [`crate::use_event_listener_with_options`],
+/// [`crate::use_event_source_with_options`], [`crate::use_websocket_with_options`] and
+/// [`crate::use_fetch_with_options`]. The event listener is removed, the connections are closed
+/// and the running request is aborted when the controller is aborted.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::ev::scroll;
+/// # use leptos_use::{use_abort_controller, use_event_listener_with_options, use_window, UseEventListenerOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let controller = use_abort_controller();
+///
+/// let _ = use_event_listener_with_options(
+///     use_window(),
+///     scroll,
+///     |_| { /* ... */ },
+///     UseEventListenerOptions::default().abort_controller(controller),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server there is no underlying `AbortController` and `signal` returns `None`.
+/// `abort` still sets `aborted` to `true`.
+pub fn use_abort_controller() -> ManagedAbortController {
+    use_abort_controller_with_options(UseAbortControllerOptions::default())
+}
+
+/// Version of [`use_abort_controller`] that takes a `UseAbortControllerOptions`. See [`use_abort_controller`] for how to use.
+pub fn use_abort_controller_with_options(
+    options: UseAbortControllerOptions,
+) -> ManagedAbortController {
+    let UseAbortControllerOptions { parent, timeout } = options;
+
+    let (aborted, set_aborted) = create_signal(false);
+
+    let controller = ManagedAbortController {
+        inner: store_value(None),
+        aborted,
+        set_aborted,
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    if let Ok(inner) = web_sys::AbortController::new() {
+        // also catches aborting through the JS signal directly
+        inner.signal().set_onabort(Some(
+            Closure::once_into_js(move || {
+                if aborted.try_get_untracked() == Some(false) {
+                    set_aborted.set(true);
+                }
+            })
+            .unchecked_ref(),
+        ));
+
+        controller.inner.set_value(Some(inner));
+    }
+
+    if let Some(parent) = parent {
+        parent.on_abort(move || controller.abort());
+    }
+
+    if let Some(timeout) = timeout {
+        controller.abort_after(timeout);
+    }
+
+    on_cleanup(move || controller.abort());
+
+    controller
+}
+
+/// Options for [`use_abort_controller_with_options`].
+#[derive(DefaultBuilder, Default, Clone, Copy)]
+pub struct UseAbortControllerOptions {
+    /// If set, the controller is aborted when the parent is aborted. Defaults to `None`.
+    #[builder(into)]
+    parent: Option<ManagedAbortController>,
+
+    /// If set, the controller is aborted after this many milliseconds. Defaults to `None`.
+    #[builder(into)]
+    timeout: Option<u64>,
+}
+
+/// Abort controller returned by [`use_abort_controller`].
+///
+/// It's `Copy` so it can be passed to several places like closures, async blocks and the
+/// options of other functions.
+#[derive(Clone, Copy)]
+pub struct ManagedAbortController {
+    inner: StoredValue<Option<web_sys::AbortController>>,
+    aborted: ReadSignal<bool>,
+    set_aborted: WriteSignal<bool>,
+}
+
+impl ManagedAbortController {
+    /// `true` once the controller has been aborted
+    pub fn aborted(&self) -> Signal<bool> {
+        self.aborted.into()
+    }
+
+    /// The `AbortSignal` to pass to `fetch` and other APIs that can be cancelled.
+    /// `None` on the server.
+    pub fn signal(&self) -> Option<web_sys::AbortSignal> {
+        self.inner
+            .try_with_value(|inner| inner.as_ref().map(|inner| inner.signal()))
+            .flatten()
+    }
+
+    /// Aborts the controller. Does nothing if it has been aborted already.
+    pub fn abort(&self) {
+        self.abort_with_reason(&JsValue::UNDEFINED);
+    }
+
+    /// Aborts the controller with the given `reason` which is available as `signal.reason`.
+    /// Does nothing if it has been aborted already.
+    pub fn abort_with_reason(&self, reason: &JsValue) {
+        if self.aborted.try_get_untracked().unwrap_or(true) {
+            return;
+        }
+
+        self.set_aborted.set(true);
+
+        self.inner.try_with_value(|inner| {
+            if let Some(inner) = inner {
+                if reason.is_undefined() {
+                    inner.abort();
+                } else {
+                    inner.abort_with_reason(reason);
+                }
+            }
+        });
+    }
+
+    /// Calls `callback` when the controller is aborted or right away if it has been aborted
+    /// already. Stops watching when the current reactive owner is disposed.
+    pub fn on_abort(&self, callback: impl Fn() + 'static) {
+        let aborted = self.aborted;
+
+        let _ = watch(
+            move || aborted.get(),
+            move |aborted, _, _| {
+                if *aborted {
+                    callback();
+                }
+            },
+            true,
+        );
+    }
+
+    /// Creates a new controller that is aborted when this one is aborted. Aborting the child
+    /// doesn't affect this controller. The child is tied to the current reactive owner.
+    pub fn child(&self) -> Self {
+        use_abort_controller_with_options(UseAbortControllerOptions::default().parent(*self))
+    }
+
+    /// Aborts the controller after `timeout` milliseconds. The timeout is cleared when the
+    /// current reactive owner is disposed.
+    pub fn abort_after(&self, timeout: u64) {
+        let controller = *self;
+
+        let handle: StoredValue<Option<TimeoutHandle>> = store_value(
+            set_timeout_with_handle(move || controller.abort(), Duration::from_millis(timeout))
+                .ok(),
+        );
+
+        on_cleanup(move || {
+            if let Some(handle) = handle.try_update_value(Option::take).flatten() {
+                handle.clear();
+            }
+        });
+    }
+}
@@ -0,0 +1,327 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::now;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Accessible announcements for screen readers.
+///
+/// Manages two visually hidden live regions, one polite and one assertive, that are added to
+/// the end of `<body>`. Messages passed to `announce` are read out by screen readers without
+/// moving the focus. This makes toasts, form errors and results of async operations accessible.
+///
+/// Messages are queued and written to the live regions one after another so that screen readers
+/// don't skip any of them. Assertive messages skip the queue. A message that is already queued
+/// or has just been announced is ignored.
+///
+/// The announcer is provided as a context. All calls to `use_aria_live_announcer` below the
+/// component that first called it share the same live regions. It's best to call it once at the
+/// root of your app.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_aria_live_announcer, AriaLivePoliteness};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let announcer = use_aria_live_announcer();
+///
+/// let save = move |_| {
+///     // ... save the document
+///     announcer.announce("Document saved", AriaLivePoliteness::Polite);
+/// };
+///
+/// view! {
+///     <button on:click=save>"Save"</button>
+/// }
+/// # }
+/// ```
+///
+/// In other components the announcer can be used the same way. It's also possible to get it
+/// with `expect_context::<AriaLiveAnnouncer>()`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{AriaLiveAnnouncer, AriaLivePoliteness};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let announcer = expect_context::<AriaLiveAnnouncer>();
+///
+/// announcer.announce("Connection lost", AriaLivePoliteness::Assertive);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server no live regions are created and announcements are ignored.
+pub fn use_aria_live_announcer() -> AriaLiveAnnouncer {
+    use_aria_live_announcer_with_options(UseAriaLiveAnnouncerOptions::default())
+}
+
+/// Version of [`use_aria_live_announcer`] that takes a `UseAriaLiveAnnouncerOptions`. See [`use_aria_live_announcer`] for how to use.
+///
+/// The options are only used if no announcer has been provided yet.
+pub fn use_aria_live_announcer_with_options(
+    options: UseAriaLiveAnnouncerOptions,
+) -> AriaLiveAnnouncer {
+    if let Some(announcer) = use_context::<AriaLiveAnnouncer>() {
+        return announcer;
+    }
+
+    let announcer = AriaLiveAnnouncer::new(options);
+    provide_context(announcer);
+
+    announcer
+}
+
+/// Options for [`use_aria_live_announcer_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseAriaLiveAnnouncerOptions {
+    /// Time in ms a message stays in its live region before the next queued message is
+    /// announced. Defaults to `500`.
+    interval: u64,
+
+    /// Time in ms during which a message that has just been announced is ignored.
+    /// Defaults to `1000`.
+    dedupe_interval: u64,
+
+    /// Time in ms after which the live regions are emptied again. Old messages would be read
+    /// out again when the user navigates to them. Defaults to `Some(5000)`.
+    #[builder(into)]
+    clear_after: Option<u64>,
+}
+
+impl Default for UseAriaLiveAnnouncerOptions {
+    fn default() -> Self {
+        Self {
+            interval: 500,
+            dedupe_interval: 1000,
+            clear_after: Some(5000),
+        }
+    }
+}
+
+/// How urgently a message is announced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AriaLivePoliteness {
+    /// The message is read out when the screen reader is idle.
+    #[default]
+    Polite,
+    /// The message interrupts what the screen reader is currently reading.
+    Assertive,
+}
+
+impl AriaLivePoliteness {
+    /// The value of the `aria-live` attribute
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Polite => "polite",
+            Self::Assertive => "assertive",
+        }
+    }
+}
+
+impl Display for AriaLivePoliteness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Announces messages to screen readers. Returned by [`use_aria_live_announcer`].
+#[derive(Clone, Copy)]
+pub struct AriaLiveAnnouncer {
+    queue: StoredValue<VecDeque<(String, AriaLivePoliteness)>>,
+    is_busy: StoredValue<bool>,
+    last_announced: StoredValue<Option<(String, f64)>>,
+    regions: StoredValue<Option<(web_sys::Element, web_sys::Element)>>,
+    last_message: RwSignal<Option<String>>,
+    interval: u64,
+    dedupe_interval: u64,
+    clear_after: Option<u64>,
+}
+
+impl AriaLiveAnnouncer {
+    fn new(options: UseAriaLiveAnnouncerOptions) -> Self {
+        let UseAriaLiveAnnouncerOptions {
+            interval,
+            dedupe_interval,
+            clear_after,
+        } = options;
+
+        let regions = store_value(None);
+
+        #[cfg(not(feature = "ssr"))]
+        {
+            regions.set_value(
+                create_live_region(AriaLivePoliteness::Polite)
+                    .zip(create_live_region(AriaLivePoliteness::Assertive)),
+            );
+
+            on_cleanup(move || {
+                if let Some((polite, assertive)) = regions.try_update_value(Option::take).flatten()
+                {
+                    polite.remove();
+                    assertive.remove();
+                }
+            });
+        }
+
+        Self {
+            queue: store_value(VecDeque::new()),
+            is_busy: store_value(false),
+            last_announced: store_value(None),
+            regions,
+            last_message: create_rw_signal(None),
+            interval,
+            dedupe_interval,
+            clear_after,
+        }
+    }
+
+    /// Queues a message to be read out by screen readers.
+    pub fn announce(&self, message: impl Into<String>, politeness: AriaLivePoliteness) {
+        let message = message.into();
+
+        if message.trim().is_empty() {
+            return;
+        }
+
+        let is_queued = self
+            .queue
+            .with_value(|queue| queue.iter().any(|(queued, _)| queued == &message));
+
+        let is_recent = self.last_announced.with_value(|last| {
+            last.as_ref().is_some_and(|(last, at)| {
+                last == &message && now() - at < self.dedupe_interval as f64
+            })
+        });
+
+        if is_queued || is_recent {
+            return;
+        }
+
+        self.queue.update_value(|queue| match politeness {
+            // assertive messages go before all polite ones
+            AriaLivePoliteness::Assertive => {
+                let index = queue
+                    .iter()
+                    .position(|(_, p)| *p == AriaLivePoliteness::Polite)
+                    .unwrap_or(queue.len());
+                queue.insert(index, (message, politeness));
+            }
+            AriaLivePoliteness::Polite => queue.push_back((message, politeness)),
+        });
+
+        if !self.is_busy.get_value() {
+            self.process_queue();
+        }
+    }
+
+    /// Removes all queued messages and empties the live regions.
+    pub fn clear(&self) {
+        self.queue.update_value(VecDeque::clear);
+        self.set_region_text(None);
+    }
+
+    /// The message that has been announced last
+    pub fn last_message(&self) -> Signal<Option<String>> {
+        self.last_message.into()
+    }
+
+    fn process_queue(self) {
+        let Some(Some((message, politeness))) = self.queue.try_update_value(VecDeque::pop_front)
+        else {
+            self.is_busy.set_value(false);
+            return;
+        };
+
+        self.is_busy.set_value(true);
+
+        // Emptying the regions first makes screen readers announce the message even if the
+        // same text has been in the region before.
+        self.set_region_text(None);
+
+        set_timeout(
+            move || {
+                self.set_region_text(Some((&message, politeness)));
+                self.last_announced
+                    .set_value(Some((message.clone(), now())));
+                self.last_message.set(Some(message.clone()));
+
+                if let Some(clear_after) = self.clear_after {
+                    let last_announced = self.last_announced;
+
+                    set_timeout(
+                        move || {
+                            // only clear if no other message has been announced since
+                            let is_current = last_announced
+                                .try_with_value(|last| {
+                                    last.as_ref().is_some_and(|(last, _)| last == &message)
+                                })
+                                .unwrap_or_default();
+
+                            if is_current {
+                                self.set_region_text(None);
+                            }
+                        },
+                        Duration::from_millis(clear_after),
+                    );
+                }
+
+                set_timeout(
+                    move || self.process_queue(),
+                    Duration::from_millis(self.interval),
+                );
+            },
+            Duration::from_millis(REGION_UPDATE_DELAY),
+        );
+    }
+
+    fn set_region_text(&self, message: Option<(&str, AriaLivePoliteness)>) {
+        let _ = self.regions.try_with_value(|regions| {
+            if let Some((polite, assertive)) = regions {
+                let (polite_text, assertive_text) = match message {
+                    Some((message, AriaLivePoliteness::Polite)) => (Some(message), None),
+                    Some((message, AriaLivePoliteness::Assertive)) => (None, Some(message)),
+                    None => (None, None),
+                };
+
+                polite.set_text_content(polite_text);
+                assertive.set_text_content(assertive_text);
+            }
+        });
+    }
+}
+
+/// Time in ms between emptying a live region and writing the new message into it
+const REGION_UPDATE_DELAY: u64 = 100;
+
+const VISUALLY_HIDDEN_STYLE: &str = "position: absolute; width: 1px; height: 1px; padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;";
+
+#[cfg(not(feature = "ssr"))]
+fn create_live_region(politeness: AriaLivePoliteness) -> Option<web_sys::Element> {
+    let document = document();
+    let region = document.create_element("div").ok()?;
+
+    let role = match politeness {
+        AriaLivePoliteness::Polite => "status",
+        AriaLivePoliteness::Assertive => "alert",
+    };
+
+    let _ = region.set_attribute("role", role);
+    let _ = region.set_attribute("aria-live", politeness.as_str());
+    let _ = region.set_attribute("aria-atomic", "true");
+    let _ = region.set_attribute("style", VISUALLY_HIDDEN_STYLE);
+
+    document.body()?.append_child(&region).ok()?;
+
+    Some(region)
+}
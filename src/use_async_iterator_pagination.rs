@@ -0,0 +1,279 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use default_struct_builder::DefaultBuilder;
+use futures_util::future::{self, FutureExt, LocalBoxFuture};
+use leptos::*;
+use std::future::Future;
+use std::rc::Rc;
+
+/// Cursor based pagination that accumulates the loaded items.
+///
+/// `fetch` is called with the current `key` and the cursor of the next page (`None` for the
+/// first page) and returns a [`CursorPage`]. Its items are appended to `items` and its
+/// `next_cursor` is used for the next call of `load_more`. Once a page has no `next_cursor`,
+/// `has_more` becomes `false` and `load_more` does nothing.
+///
+/// When `key` changes, for example because the search query or filter changed, everything is
+/// reset and the first page is loaded again. Responses that arrive after a reset are ignored.
+///
+/// ## Usage
+///
+/// Together with [`crate::use_infinite_scroll`] the next page is loaded when the end of the list
+/// is reached.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{
+/// #     use_async_iterator_pagination, use_infinite_scroll, CursorPage,
+/// #     UseAsyncIteratorPaginationReturn,
+/// # };
+/// #
+/// # async fn fetch_posts(query: String, cursor: Option<u32>) -> Result<(Vec<String>, Option<u32>), String> {
+/// #     Ok((vec![], None))
+/// # }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+/// let (query, set_query) = create_signal(String::new());
+///
+/// let UseAsyncIteratorPaginationReturn {
+///     items,
+///     has_more,
+///     is_loading,
+///     error,
+///     load_more,
+///     ..
+/// } = use_async_iterator_pagination(query, |query: String, cursor: Option<u32>| async move {
+///     let (posts, next_cursor) = fetch_posts(query, cursor).await?;
+///     Ok::<_, String>(CursorPage::new(posts, next_cursor))
+/// });
+///
+/// let _ = use_infinite_scroll(el, move |_| load_more());
+///
+/// view! {
+///     <input on:input=move |e| set_query.set(event_target_value(&e)) />
+///     <div node_ref=el>
+///         <For each=move || items.get() key=|post| post.clone() let:post>
+///             <p>{post}</p>
+///         </For>
+///         <Show when=move || is_loading.get()>"Loading..."</Show>
+///         <Show when=move || !has_more.get()>"That's all"</Show>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// `load_more` returns a future that resolves when the page has been loaded. To call it from an
+/// event handler, spawn it with `spawn_local(load_more())`.
+///
+/// If `fetch` fails, the error is available in `error` and the next call of `load_more` retries
+/// the same page. Pass `()` as `key` if the list doesn't depend on anything.
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is loaded. `items` is empty and `has_more` is `true`.
+pub fn use_async_iterator_pagination<T, C, K, E, F, Fut>(
+    key: impl Into<MaybeSignal<K>>,
+    fetch: F,
+) -> UseAsyncIteratorPaginationReturn<
+    T,
+    E,
+    impl Fn() -> LocalBoxFuture<'static, ()> + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    C: Clone + 'static,
+    K: Clone + 'static,
+    E: 'static,
+    F: Fn(K, Option<C>) -> Fut + 'static,
+    Fut: Future<Output = Result<CursorPage<T, C>, E>> + 'static,
+{
+    use_async_iterator_pagination_with_options(
+        key,
+        fetch,
+        UseAsyncIteratorPaginationOptions::default(),
+    )
+}
+
+/// Version of [`use_async_iterator_pagination`] that takes a `UseAsyncIteratorPaginationOptions`. See [`use_async_iterator_pagination`] for how to use.
+pub fn use_async_iterator_pagination_with_options<T, C, K, E, F, Fut>(
+    key: impl Into<MaybeSignal<K>>,
+    fetch: F,
+    options: UseAsyncIteratorPaginationOptions,
+) -> UseAsyncIteratorPaginationReturn<
+    T,
+    E,
+    impl Fn() -> LocalBoxFuture<'static, ()> + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: 'static,
+    C: Clone + 'static,
+    K: Clone + 'static,
+    E: 'static,
+    F: Fn(K, Option<C>) -> Fut + 'static,
+    Fut: Future<Output = Result<CursorPage<T, C>, E>> + 'static,
+{
+    let UseAsyncIteratorPaginationOptions { immediate } = options;
+
+    let key = key.into();
+    let fetch = Rc::new(fetch);
+
+    let (items, set_items) = create_signal(Vec::<T>::new());
+    let (has_more, set_has_more) = create_signal(true);
+    let (is_loading, set_loading) = create_signal(false);
+    let (error, set_error) = create_signal(None::<E>);
+
+    let cursor = store_value(None::<C>);
+    // incremented on every reset so responses of earlier requests can be ignored
+    let generation = store_value(0_u64);
+
+    let load_more = {
+        let key = key.clone();
+
+        move || -> LocalBoxFuture<'static, ()> {
+            #[cfg(feature = "ssr")]
+            return future::ready(()).boxed_local();
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                if is_loading.get_untracked() || !has_more.get_untracked() {
+                    return future::ready(()).boxed_local();
+                }
+
+                set_loading.set(true);
+
+                let current_generation = generation.get_value();
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                let page = fetch(key.get_untracked(), cursor.get_value());
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                async move {
+                    let page = page.await;
+
+                    if generation.try_get_value() != Some(current_generation) {
+                        // reset or disposed in the meantime
+                        return;
+                    }
+
+                    match page {
+                        Ok(CursorPage { items, next_cursor }) => {
+                            set_has_more.set(next_cursor.is_some());
+                            cursor.set_value(next_cursor);
+                            set_items.update(|all_items| all_items.extend(items));
+                            set_error.set(None);
+                        }
+                        Err(err) => {
+                            set_error.set(Some(err));
+                        }
+                    }
+
+                    set_loading.set(false);
+                }
+                .boxed_local()
+            }
+        }
+    };
+
+    let reset = {
+        let load_more = load_more.clone();
+
+        move || {
+            generation.update_value(|generation| *generation += 1);
+            cursor.set_value(None);
+
+            set_items.set(Vec::new());
+            set_has_more.set(true);
+            set_loading.set(false);
+            set_error.set(None);
+
+            #[cfg(not(feature = "ssr"))]
+            if immediate {
+                spawn_local(load_more());
+            }
+        }
+    };
+
+    let _ = watch(
+        move || key.get(),
+        {
+            let reset = reset.clone();
+            move |_, _, _| reset()
+        },
+        false,
+    );
+
+    #[cfg(not(feature = "ssr"))]
+    if immediate {
+        spawn_local(load_more());
+    }
+
+    UseAsyncIteratorPaginationReturn {
+        items: items.into(),
+        has_more: has_more.into(),
+        is_loading: is_loading.into(),
+        error: error.into(),
+        load_more,
+        reset,
+    }
+}
+
+/// Options for [`use_async_iterator_pagination_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseAsyncIteratorPaginationOptions {
+    /// If `true` the first page is loaded right away and again after every reset.
+    /// Otherwise only when `load_more` is called. Defaults to `true`.
+    immediate: bool,
+}
+
+impl Default for UseAsyncIteratorPaginationOptions {
+    fn default() -> Self {
+        Self { immediate: true }
+    }
+}
+
+/// A page returned by the `fetch` function of [`use_async_iterator_pagination`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CursorPage<T, C> {
+    /// The items of this page
+    pub items: Vec<T>,
+    /// The cursor of the following page or `None` if this is the last page
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> CursorPage<T, C> {
+    /// Creates a page from its items and the cursor of the following page.
+    pub fn new(items: Vec<T>, next_cursor: Option<C>) -> Self {
+        Self { items, next_cursor }
+    }
+}
+
+/// Return type of [`use_async_iterator_pagination`].
+pub struct UseAsyncIteratorPaginationReturn<T, E, LoadMoreFn, ResetFn>
+where
+    T: 'static,
+    E: 'static,
+    LoadMoreFn: Fn() -> LocalBoxFuture<'static, ()> + Clone + 'static,
+    ResetFn: Fn() + Clone + 'static,
+{
+    /// All items loaded so far
+    pub items: Signal<Vec<T>>,
+    /// `false` once the last page has been loaded
+    pub has_more: Signal<bool>,
+    /// `true` while a page is being loaded
+    pub is_loading: Signal<bool>,
+    /// The error of the last failed `fetch` or `None` if it succeeded
+    pub error: Signal<Option<E>>,
+    /// Loads the next page. Does nothing while loading or if there are no more pages.
+    pub load_more: LoadMoreFn,
+    /// Removes all items and starts again with the first page
+    pub reset: ResetFn,
+}
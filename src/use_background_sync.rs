@@ -0,0 +1,379 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_event_listener, use_permission, use_supported, PermissionState};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [Background Synchronization API](https://developer.mozilla.org/en-US/docs/Web/API/Background_Synchronization_API)
+/// and [Periodic Background Synchronization API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Periodic_Background_Synchronization_API).
+///
+/// Registers sync tags with the active service worker. The service worker then receives a `sync`
+/// (or `periodicsync`) event as soon as the browser has connectivity (or periodically).
+/// The currently registered tags and the permission states are available as signals.
+///
+/// To get notified when the service worker has finished a sync, post a message like
+/// `{ type: "sync-completed", tag: "my-tag" }` to the clients from your service worker.
+/// These messages are reported in `last_completed` and passed to the `on_completed` callback.
+///
+/// ```js
+/// // service-worker.js
+/// self.addEventListener("sync", (event) => {
+///     event.waitUntil(sendOutbox().then(async () => {
+///         for (const client of await self.clients.matchAll()) {
+///             client.postMessage({ type: "sync-completed", tag: event.tag });
+///         }
+///     }));
+/// });
+/// ```
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_background_sync, UseBackgroundSyncReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseBackgroundSyncReturn {
+///     is_supported,
+///     tags,
+///     last_completed,
+///     register,
+///     ..
+/// } = use_background_sync();
+///
+/// view! {
+///     <button on:click=move |_| register("send-outbox")>"Sync when online"</button>
+///     <p>"Pending: " {move || tags.get().join(", ")}</p>
+///     <p>"Last completed: " {move || last_completed.get().map(|c| c.tag)}</p>
+/// }
+/// # }
+/// ```
+///
+/// Periodic syncs need a minimum interval in ms. Browsers only allow them for installed apps.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_background_sync, PermissionState, UseBackgroundSyncReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseBackgroundSyncReturn {
+///     is_periodic_supported,
+///     periodic_permission,
+///     register_periodic,
+///     ..
+/// } = use_background_sync();
+///
+/// create_effect(move |_| {
+///     if is_periodic_supported.get() && periodic_permission.get() == PermissionState::Granted {
+///         register_periodic("refresh-news", 24 * 60 * 60 * 1000);
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is supported, the tags are always empty and the functions do nothing.
+///
+/// ## See also
+///
+/// - [`use_service_worker`]
+pub fn use_background_sync() -> UseBackgroundSyncReturn<
+    impl Fn(&str) + Clone,
+    impl Fn(&str, u64) + Clone,
+    impl Fn(&str) + Clone,
+    impl Fn() + Clone,
+> {
+    use_background_sync_with_options(UseBackgroundSyncOptions::default())
+}
+
+/// Version of [`use_background_sync`] that takes a `UseBackgroundSyncOptions`. See [`use_background_sync`] for how to use.
+pub fn use_background_sync_with_options(
+    options: UseBackgroundSyncOptions,
+) -> UseBackgroundSyncReturn<
+    impl Fn(&str) + Clone,
+    impl Fn(&str, u64) + Clone,
+    impl Fn(&str) + Clone,
+    impl Fn() + Clone,
+> {
+    let UseBackgroundSyncOptions {
+        completed_message_type,
+        on_completed,
+    } = options;
+
+    let is_supported = use_supported(|| js!("SyncManager" in &window()));
+    let is_periodic_supported = use_supported(|| js!("PeriodicSyncManager" in &window()));
+
+    let permission = use_permission("background-sync");
+    let periodic_permission = use_permission("periodic-background-sync");
+
+    let (tags, set_tags) = create_signal(Vec::<String>::new());
+    let (periodic_tags, set_periodic_tags) = create_signal(Vec::<String>::new());
+    let (last_completed, set_last_completed) = create_signal(None::<SyncCompleted>);
+    let (error, set_error) = create_signal(None::<JsValue>);
+
+    let refresh = move || {
+        #[cfg(not(feature = "ssr"))]
+        spawn_local(async move {
+            if is_supported.get_untracked() {
+                match get_tags(SYNC_MANAGER).await {
+                    Ok(new_tags) => set_tags.set(new_tags),
+                    Err(err) => set_error.set(Some(err)),
+                }
+            }
+
+            if is_periodic_supported.get_untracked() {
+                match get_tags(PERIODIC_SYNC_MANAGER).await {
+                    Ok(new_tags) => set_periodic_tags.set(new_tags),
+                    Err(err) => set_error.set(Some(err)),
+                }
+            }
+        });
+    };
+
+    let register = move |tag: &str| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            let tag = tag.to_string();
+
+            spawn_local(async move {
+                let args = js_sys::Array::of1(&JsValue::from_str(&tag));
+
+                match call_manager(SYNC_MANAGER, "register", &args).await {
+                    Ok(_) => {
+                        set_error.set(None);
+                        refresh();
+                    }
+                    Err(err) => set_error.set(Some(err)),
+                }
+            });
+        }
+    };
+
+    let register_periodic = move |tag: &str, min_interval: u64| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            if !is_periodic_supported.get_untracked() {
+                return;
+            }
+
+            let tag = tag.to_string();
+
+            spawn_local(async move {
+                let options = js_sys::Object::new();
+                js!(options["minInterval"] = min_interval as f64);
+
+                let args = js_sys::Array::of2(&JsValue::from_str(&tag), &options);
+
+                match call_manager(PERIODIC_SYNC_MANAGER, "register", &args).await {
+                    Ok(_) => {
+                        set_error.set(None);
+                        refresh();
+                    }
+                    Err(err) => set_error.set(Some(err)),
+                }
+            });
+        }
+    };
+
+    let unregister_periodic = move |tag: &str| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            if !is_periodic_supported.get_untracked() {
+                return;
+            }
+
+            let tag = tag.to_string();
+
+            spawn_local(async move {
+                let args = js_sys::Array::of1(&JsValue::from_str(&tag));
+
+                match call_manager(PERIODIC_SYNC_MANAGER, "unregister", &args).await {
+                    Ok(_) => {
+                        set_error.set(None);
+                        refresh();
+                    }
+                    Err(err) => set_error.set(Some(err)),
+                }
+            });
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        refresh();
+
+        let _ = use_event_listener(
+            window().navigator().service_worker(),
+            ev::message,
+            move |event| {
+                let data = event.data();
+
+                let message_type = js!(data["type"]).ok().and_then(|t| t.as_string());
+                if message_type.as_ref() != Some(&completed_message_type) {
+                    return;
+                }
+
+                let completed = SyncCompleted {
+                    tag: js!(data["tag"])
+                        .ok()
+                        .and_then(|tag| tag.as_string())
+                        .unwrap_or_default(),
+                    data,
+                };
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_completed(&completed);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_last_completed.set(Some(completed));
+
+                // finished one-off syncs are no longer registered
+                refresh();
+            },
+        );
+    }
+
+    UseBackgroundSyncReturn {
+        is_supported,
+        is_periodic_supported,
+        permission,
+        periodic_permission,
+        tags: tags.into(),
+        periodic_tags: periodic_tags.into(),
+        last_completed: last_completed.into(),
+        error: error.into(),
+        register,
+        register_periodic,
+        unregister_periodic,
+        refresh,
+    }
+}
+
+const SYNC_MANAGER: &str = "sync";
+const PERIODIC_SYNC_MANAGER: &str = "periodicSync";
+
+/// Calls `method` of the `SyncManager` or `PeriodicSyncManager` of the active service worker
+/// registration. These are not available in `web_sys`.
+#[cfg(not(feature = "ssr"))]
+async fn call_manager(
+    manager_name: &str,
+    method: &str,
+    args: &js_sys::Array,
+) -> Result<JsValue, JsValue> {
+    let registration = js_fut!(window().navigator().service_worker().ready()?).await?;
+
+    let manager = js_sys::Reflect::get(&registration, &manager_name.into())?;
+    if manager.is_undefined() {
+        return Err(JsValue::from_str(&format!(
+            "{manager_name} is not supported"
+        )));
+    }
+
+    let promise = js_sys::Reflect::get(&manager, &method.into())?
+        .dyn_into::<js_sys::Function>()?
+        .apply(&manager, args)?
+        .dyn_into::<js_sys::Promise>()?;
+
+    js_fut!(promise).await
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn get_tags(manager_name: &str) -> Result<Vec<String>, JsValue> {
+    let tags = call_manager(manager_name, "getTags", &js_sys::Array::new()).await?;
+
+    Ok(tags
+        .dyn_into::<js_sys::Array>()?
+        .iter()
+        .filter_map(|tag| tag.as_string())
+        .collect())
+}
+
+/// A sync completed message posted by the service worker.
+#[derive(Clone, Debug)]
+pub struct SyncCompleted {
+    /// The tag of the sync that has been completed.
+    pub tag: String,
+    /// The whole message.
+    pub data: JsValue,
+}
+
+/// Options for [`use_background_sync_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseBackgroundSyncOptions {
+    /// The `type` of the messages posted by the service worker when a sync has been completed.
+    /// Defaults to `"sync-completed"`.
+    #[builder(into)]
+    completed_message_type: String,
+
+    /// Called for every sync completed message of the service worker.
+    on_completed: Rc<dyn Fn(&SyncCompleted)>,
+}
+
+impl Default for UseBackgroundSyncOptions {
+    fn default() -> Self {
+        Self {
+            completed_message_type: "sync-completed".to_string(),
+            on_completed: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_background_sync`].
+pub struct UseBackgroundSyncReturn<RegisterFn, RegisterPeriodicFn, UnregisterPeriodicFn, RefreshFn>
+where
+    RegisterFn: Fn(&str) + Clone,
+    RegisterPeriodicFn: Fn(&str, u64) + Clone,
+    UnregisterPeriodicFn: Fn(&str) + Clone,
+    RefreshFn: Fn() + Clone,
+{
+    /// `true` if background sync is supported.
+    pub is_supported: Signal<bool>,
+
+    /// `true` if periodic background sync is supported.
+    pub is_periodic_supported: Signal<bool>,
+
+    /// The state of the `background-sync` permission.
+    pub permission: Signal<PermissionState>,
+
+    /// The state of the `periodic-background-sync` permission.
+    pub periodic_permission: Signal<PermissionState>,
+
+    /// The tags of the one-off syncs that are waiting to be run.
+    pub tags: Signal<Vec<String>>,
+
+    /// The tags of the registered periodic syncs.
+    pub periodic_tags: Signal<Vec<String>>,
+
+    /// The latest sync completed message posted by the service worker.
+    pub last_completed: Signal<Option<SyncCompleted>>,
+
+    /// The error of the latest registration if it failed.
+    pub error: Signal<Option<JsValue>>,
+
+    /// Registers a one-off sync with the given tag.
+    pub register: RegisterFn,
+
+    /// Registers a periodic sync with the given tag and minimum interval in ms.
+    pub register_periodic: RegisterPeriodicFn,
+
+    /// Unregisters the periodic sync with the given tag.
+    pub unregister_periodic: UnregisterPeriodicFn,
+
+    /// Reads the registered tags again.
+    pub refresh: RefreshFn,
+}
@@ -0,0 +1,175 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::use_event_listener;
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pagehide, pageshow};
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Reactively detect when the page is restored from the
+/// [back/forward cache](https://developer.mozilla.org/en-US/docs/Glossary/bfcache) (bfcache).
+///
+/// When a page is restored from the bfcache it isn't reloaded. Timers are resumed where they
+/// were frozen and network connections like `WebSocket`s or `EventSource`s have usually been
+/// closed by the browser. This hook listens to the `pageshow` and `pagehide` events and lets you
+/// react to such a restore, for example to refresh stale data or to reopen connections.
+///
+/// ## Demo
+///
+/// [Link to Demo](https://github.com/Synphonyte/leptos-use/tree/main/examples/use_bfcache)
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_bfcache, UseBfcacheReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseBfcacheReturn {
+///     restored_from_bfcache,
+///     navigation_type,
+///     ..
+/// } = use_bfcache();
+///
+/// create_effect(move |_| {
+///     if restored_from_bfcache.get().is_some() {
+///         // refresh data, reopen sockets, ...
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// You can also provide callbacks that are called for every `pageshow` that is a bfcache restore and for every
+/// `pagehide`. The `persisted` flag of the `pagehide` event tells you if the page might be put into the bfcache.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::logging::log;
+/// # use leptos_use::{use_bfcache_with_options, UseBfcacheOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// use_bfcache_with_options(
+///     UseBfcacheOptions::default()
+///         .on_restore(|_| log!("Restored from bfcache"))
+///         .on_page_hide(|evt: web_sys::PageTransitionEvent| log!("Persisted: {}", evt.persisted())),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `restored_from_bfcache` is always `None`, `persisted` is always `false`
+/// and `navigation_type` is always `None`.
+pub fn use_bfcache() -> UseBfcacheReturn {
+    use_bfcache_with_options(UseBfcacheOptions::default())
+}
+
+/// Version of [`use_bfcache`] that takes a `UseBfcacheOptions`. See [`use_bfcache`] for how to use.
+pub fn use_bfcache_with_options(options: UseBfcacheOptions) -> UseBfcacheReturn {
+    let UseBfcacheOptions {
+        on_restore,
+        on_page_hide,
+    } = options;
+
+    let (restored_from_bfcache, set_restored_from_bfcache) =
+        create_signal(None::<web_sys::PageTransitionEvent>);
+    let (persisted, set_persisted) = create_signal(false);
+
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        let initial_navigation_type = None;
+    } else {
+        let initial_navigation_type = initial_navigation_type();
+    }}
+
+    let (navigation_type, set_navigation_type) = create_signal(initial_navigation_type);
+
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let _ = use_event_listener(window(), pageshow, move |evt| {
+            set_persisted.set(evt.persisted());
+
+            if evt.persisted() {
+                set_navigation_type.set(Some(web_sys::NavigationType::BackForward));
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_restore(evt.clone());
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_restored_from_bfcache.set(Some(evt));
+            }
+        });
+
+        let _ = use_event_listener(window(), pagehide, move |evt| {
+            set_persisted.set(evt.persisted());
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_page_hide(evt);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        });
+    }}
+
+    UseBfcacheReturn {
+        restored_from_bfcache: restored_from_bfcache.into(),
+        persisted: persisted.into(),
+        navigation_type: navigation_type.into(),
+    }
+}
+
+/// Reads the type of the initial navigation from the `PerformanceNavigationTiming` entry.
+fn initial_navigation_type() -> Option<web_sys::NavigationType> {
+    window()
+        .performance()?
+        .get_entries_by_type("navigation")
+        .get(0)
+        .dyn_into::<web_sys::PerformanceNavigationTiming>()
+        .ok()
+        .map(|entry| entry.type_())
+}
+
+/// Options for [`use_bfcache_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseBfcacheOptions {
+    /// Called with the `pageshow` event whenever the page is restored from the bfcache.
+    on_restore: Rc<dyn Fn(web_sys::PageTransitionEvent)>,
+
+    /// Called with the `pagehide` event whenever the page is hidden.
+    on_page_hide: Rc<dyn Fn(web_sys::PageTransitionEvent)>,
+}
+
+impl Default for UseBfcacheOptions {
+    fn default() -> Self {
+        Self {
+            on_restore: Rc::new(|_| {}),
+            on_page_hide: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_bfcache`].
+pub struct UseBfcacheReturn {
+    /// The latest `pageshow` event that restored the page from the bfcache. `None` until the
+    /// first restore happens.
+    pub restored_from_bfcache: Signal<Option<web_sys::PageTransitionEvent>>,
+
+    /// The `persisted` flag of the latest `pageshow` or `pagehide` event.
+    pub persisted: Signal<bool>,
+
+    /// How the page was navigated to. This is initialized from the `PerformanceNavigationTiming`
+    /// entry and set to `BackForward` when the page is restored from the bfcache.
+    pub navigation_type: Signal<Option<web_sys::NavigationType>>,
+}
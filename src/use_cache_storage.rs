@@ -0,0 +1,497 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_event_listener, use_supported};
+use cfg_if::cfg_if;
+use codee::{CodecError, Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+const INTERNAL_CACHE_EVENT: &str = "leptos-use-cache-storage";
+
+const CACHED_AT_HEADER: &str = "x-leptos-use-cached-at";
+const EXPIRES_AT_HEADER: &str = "x-leptos-use-expires-at";
+const SIZE_HEADER: &str = "x-leptos-use-size";
+
+/// Reactive [Cache API](https://developer.mozilla.org/en-US/docs/Web/API/Cache).
+///
+/// Opens the cache with the given name and keeps a list of its entries. Values are stored as
+/// responses whose bodies are encoded with the given codec. Together with each value the time it
+/// was cached, an optional expiry time (TTL) and its size are stored. Expired entries are never
+/// returned and removed when the list is refreshed.
+///
+/// This is useful for offline-first apps that want to manage cached assets and data themselves.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_cache_storage_with_options, UseCacheStorageOptions, UseCacheStorageReturn};
+/// # use codee::string::JsonSerdeCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone)]
+/// pub struct Article {
+///     pub title: String,
+///     pub body: String,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseCacheStorageReturn {
+///     entries,
+///     total_size,
+///     cache,
+///     ..
+/// } = use_cache_storage_with_options::<Article, JsonSerdeCodec>(
+///     "articles",
+///     UseCacheStorageOptions::default().default_ttl(24 * 60 * 60 * 1000), // one day
+/// );
+///
+/// let save = move |_| {
+///     let cache = cache.clone();
+///     spawn_local(async move {
+///         let article = Article { title: "Hello".to_string(), body: "World".to_string() };
+///         let _ = cache.put("/articles/hello", &article).await;
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=save>"Save for offline"</button>
+///     <p>{move || entries.get().len()} " entries using " {total_size} " bytes"</p>
+/// }
+/// # }
+/// ```
+///
+/// The keys are request URLs. Relative URLs are resolved against the URL of the page.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `entries` is always empty and all methods of `cache` return
+/// `UseCacheStorageError::NotSupported`.
+pub fn use_cache_storage<T, C>(
+    cache_name: impl Into<String>,
+) -> UseCacheStorageReturn<T, C, impl Fn() + Clone>
+where
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    use_cache_storage_with_options::<T, C>(cache_name, UseCacheStorageOptions::default())
+}
+
+/// Version of [`use_cache_storage`] that takes a `UseCacheStorageOptions`. See [`use_cache_storage`] for how to use.
+pub fn use_cache_storage_with_options<T, C>(
+    cache_name: impl Into<String>,
+    options: UseCacheStorageOptions,
+) -> UseCacheStorageReturn<T, C, impl Fn() + Clone>
+where
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    let UseCacheStorageOptions {
+        default_ttl,
+        remove_expired,
+    } = options;
+
+    let cache_name = cache_name.into();
+
+    let is_supported = use_supported(|| js!("caches" in &window()));
+
+    let (entries, set_entries) = create_signal(Vec::<CacheEntry>::new());
+    let (is_loading, set_loading) = create_signal(false);
+    let (error, set_error) = create_signal(None::<JsValue>);
+
+    // Only the result of the latest listing is applied
+    let latest_listing = store_value(0_usize);
+
+    let refresh = {
+        let cache_name = cache_name.clone();
+
+        move || {
+            if !is_supported.get_untracked() {
+                return;
+            }
+
+            latest_listing.update_value(|id| *id += 1);
+            let listing_id = latest_listing.get_value();
+            let cache_name = cache_name.clone();
+
+            set_loading.set(true);
+
+            spawn_local(async move {
+                let result = list_entries(&cache_name, remove_expired).await;
+
+                // outdated or the hook has been disposed in the meantime
+                if latest_listing.try_get_value() != Some(listing_id) {
+                    return;
+                }
+
+                match result {
+                    Ok(entries) => {
+                        set_entries.set(entries);
+                        set_error.set(None);
+                    }
+                    Err(err) => set_error.set(Some(err)),
+                }
+
+                set_loading.set(false);
+            });
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        refresh();
+
+        let check_name = cache_name.clone();
+        let refresh = refresh.clone();
+        let _ = use_event_listener(
+            window(),
+            ev::Custom::<web_sys::CustomEvent>::new(INTERNAL_CACHE_EVENT),
+            move |event| {
+                if event.detail().as_string().as_ref() == Some(&check_name) {
+                    refresh();
+                }
+            },
+        );
+    }
+
+    let total_size = Signal::derive(move || {
+        entries.with(|entries| entries.iter().filter_map(|entry| entry.size).sum())
+    });
+
+    UseCacheStorageReturn {
+        is_supported,
+        entries: entries.into(),
+        total_size,
+        is_loading: is_loading.into(),
+        error: error.into(),
+        cache: CacheHandle {
+            cache_name,
+            default_ttl,
+            _marker: PhantomData,
+        },
+        refresh,
+    }
+}
+
+/// Handle to read and write the values of the cache opened by [`use_cache_storage`].
+///
+/// All changes are reflected in the `entries` of every [`use_cache_storage`] with the same cache name.
+pub struct CacheHandle<T, C> {
+    cache_name: String,
+    default_ttl: Option<u64>,
+    _marker: PhantomData<(T, C)>,
+}
+
+impl<T, C> Clone for CacheHandle<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            cache_name: self.cache_name.clone(),
+            default_ttl: self.default_ttl,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, C> CacheHandle<T, C>
+where
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    /// Returns the value stored under `key` or `None` if there is none or it has expired.
+    pub async fn get(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, UseCacheStorageError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>>
+    {
+        let cache = self.open().await?;
+
+        let response = js_fut!(cache.match_with_str(key))
+            .await
+            .map_err(UseCacheStorageError::Get)?;
+
+        if response.is_undefined() {
+            return Ok(None);
+        }
+
+        let response = response.unchecked_into::<web_sys::Response>();
+
+        if CacheEntry::from_response(key.to_string(), &response).is_expired() {
+            let _ = js_fut!(cache.delete_with_str(key)).await;
+            self.notify_changed();
+
+            return Ok(None);
+        }
+
+        let text = js_fut!(response.text().map_err(UseCacheStorageError::Get)?)
+            .await
+            .map_err(UseCacheStorageError::Get)?
+            .as_string()
+            .unwrap_or_default();
+
+        C::decode(&text)
+            .map(Some)
+            .map_err(|err| UseCacheStorageError::Codec(CodecError::Decode(err)))
+    }
+
+    /// Stores `value` under `key` with the `default_ttl` of the options.
+    pub async fn put(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), UseCacheStorageError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>> {
+        self.put_with_ttl(key, value, self.default_ttl).await
+    }
+
+    /// Stores `value` under `key`. It expires after `ttl` ms. `None` means it never expires.
+    pub async fn put_with_ttl(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<u64>,
+    ) -> Result<(), UseCacheStorageError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>> {
+        let encoded =
+            C::encode(value).map_err(|err| UseCacheStorageError::Codec(CodecError::Encode(err)))?;
+
+        let response = build_response(&encoded, ttl).map_err(UseCacheStorageError::Put)?;
+
+        let cache = self.open().await?;
+        js_fut!(cache.put_with_str(key, &response))
+            .await
+            .map_err(UseCacheStorageError::Put)?;
+
+        self.notify_changed();
+
+        Ok(())
+    }
+
+    /// Removes the value stored under `key`. Returns `true` if there was one.
+    pub async fn delete(
+        &self,
+        key: &str,
+    ) -> Result<bool, UseCacheStorageError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>>
+    {
+        let cache = self.open().await?;
+
+        let deleted = js_fut!(cache.delete_with_str(key))
+            .await
+            .map_err(UseCacheStorageError::Delete)?
+            .as_bool()
+            .unwrap_or_default();
+
+        self.notify_changed();
+
+        Ok(deleted)
+    }
+
+    /// Removes all values by deleting the whole cache.
+    pub async fn clear(
+        &self,
+    ) -> Result<(), UseCacheStorageError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>> {
+        let caches = cache_storage().map_err(UseCacheStorageError::NotSupported)?;
+
+        js_fut!(caches.delete(&self.cache_name))
+            .await
+            .map_err(UseCacheStorageError::Delete)?;
+
+        self.notify_changed();
+
+        Ok(())
+    }
+
+    async fn open(
+        &self,
+    ) -> Result<
+        web_sys::Cache,
+        UseCacheStorageError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>,
+    > {
+        let caches = cache_storage().map_err(UseCacheStorageError::NotSupported)?;
+
+        open_cache(&caches, &self.cache_name)
+            .await
+            .map_err(UseCacheStorageError::Open)
+    }
+
+    /// Notifies all hooks with the same cache name on this page.
+    fn notify_changed(&self) {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let mut init = web_sys::CustomEventInit::new();
+            init.detail(&JsValue::from_str(&self.cache_name));
+
+            if let Ok(event) =
+                web_sys::CustomEvent::new_with_event_init_dict(INTERNAL_CACHE_EVENT, &init)
+            {
+                let _ = window().dispatch_event(&event);
+            }
+        }
+    }
+}
+
+fn cache_storage() -> Result<web_sys::CacheStorage, JsValue> {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        Err(JsValue::UNDEFINED)
+    } else {
+        window().caches()
+    }}
+}
+
+async fn open_cache(
+    caches: &web_sys::CacheStorage,
+    cache_name: &str,
+) -> Result<web_sys::Cache, JsValue> {
+    js_fut!(caches.open(cache_name))
+        .await
+        .map(|cache| cache.unchecked_into())
+}
+
+fn build_response(encoded: &str, ttl: Option<u64>) -> Result<web_sys::Response, JsValue> {
+    let now = js_sys::Date::now();
+
+    let headers = web_sys::Headers::new()?;
+    headers.set("content-type", "text/plain;charset=utf-8")?;
+    headers.set(CACHED_AT_HEADER, &now.to_string())?;
+    headers.set(SIZE_HEADER, &encoded.len().to_string())?;
+    if let Some(ttl) = ttl {
+        headers.set(EXPIRES_AT_HEADER, &(now + ttl as f64).to_string())?;
+    }
+
+    let mut init = web_sys::ResponseInit::new();
+    init.headers(&headers);
+
+    web_sys::Response::new_with_opt_str_and_init(Some(encoded), &init)
+}
+
+async fn list_entries(cache_name: &str, remove_expired: bool) -> Result<Vec<CacheEntry>, JsValue> {
+    let cache = open_cache(&cache_storage()?, cache_name).await?;
+
+    let requests = js_fut!(cache.keys())
+        .await?
+        .unchecked_into::<js_sys::Array>();
+
+    let mut entries = vec![];
+
+    for request in requests.iter() {
+        let request = request.unchecked_into::<web_sys::Request>();
+
+        let response = js_fut!(cache.match_with_request(&request)).await?;
+        if response.is_undefined() {
+            continue;
+        }
+
+        let entry = CacheEntry::from_response(request.url(), &response.unchecked_into());
+
+        if remove_expired && entry.is_expired() {
+            let _ = js_fut!(cache.delete_with_request(&request)).await;
+            continue;
+        }
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// An entry of the cache opened by [`use_cache_storage`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheEntry {
+    /// The URL of the request the value is stored under.
+    pub key: String,
+    /// The size of the stored value in bytes if known.
+    pub size: Option<usize>,
+    /// When the value was stored in ms since the Unix epoch if known.
+    pub cached_at: Option<f64>,
+    /// When the value expires in ms since the Unix epoch. `None` means it never expires.
+    pub expires_at: Option<f64>,
+}
+
+impl CacheEntry {
+    fn from_response(key: String, response: &web_sys::Response) -> Self {
+        let headers = response.headers();
+        let header = |name: &str| headers.get(name).ok().flatten();
+
+        Self {
+            key,
+            size: header(SIZE_HEADER)
+                .or_else(|| header("content-length"))
+                .and_then(|size| size.parse().ok()),
+            cached_at: header(CACHED_AT_HEADER).and_then(|time| time.parse().ok()),
+            expires_at: header(EXPIRES_AT_HEADER).and_then(|time| time.parse().ok()),
+        }
+    }
+
+    /// Returns `true` if the value has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= js_sys::Date::now())
+    }
+}
+
+/// Errors of [`CacheHandle`].
+#[derive(Error, Debug)]
+pub enum UseCacheStorageError<E, D> {
+    #[error("cache storage not supported")]
+    NotSupported(JsValue),
+    #[error("failed to open cache")]
+    Open(JsValue),
+    #[error("failed to get value")]
+    Get(JsValue),
+    #[error("failed to put value")]
+    Put(JsValue),
+    #[error("failed to delete value")]
+    Delete(JsValue),
+    #[error("failed to encode / decode value")]
+    Codec(CodecError<E, D>),
+}
+
+/// Options for [`use_cache_storage_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseCacheStorageOptions {
+    /// The time in ms after which values stored with `put` expire. `None` means they never expire.
+    /// Defaults to `None`.
+    #[builder(into)]
+    default_ttl: Option<u64>,
+
+    /// Remove expired entries from the cache when the list of entries is refreshed.
+    /// Defaults to `true`.
+    remove_expired: bool,
+}
+
+impl Default for UseCacheStorageOptions {
+    fn default() -> Self {
+        Self {
+            default_ttl: None,
+            remove_expired: true,
+        }
+    }
+}
+
+/// Return type of [`use_cache_storage`].
+pub struct UseCacheStorageReturn<T, C, RefreshFn>
+where
+    RefreshFn: Fn() + Clone,
+{
+    /// `true` if the Cache API is supported by the browser.
+    pub is_supported: Signal<bool>,
+
+    /// The entries of the cache.
+    pub entries: Signal<Vec<CacheEntry>>,
+
+    /// The sum of the sizes of all entries in bytes.
+    pub total_size: Signal<usize>,
+
+    /// `true` while the entries are being listed.
+    pub is_loading: Signal<bool>,
+
+    /// The error of the latest listing if it failed.
+    pub error: Signal<Option<JsValue>>,
+
+    /// Read and write values.
+    pub cache: CacheHandle<T, C>,
+
+    /// Lists the entries of the cache again.
+    pub refresh: RefreshFn,
+}
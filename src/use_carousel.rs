@@ -0,0 +1,396 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{now, ElementMaybeSignal};
+use crate::utils::{Pausable, TimerHiddenBehavior};
+use crate::{
+    use_element_hover, use_event_listener, use_interval_fn_with_options, use_raf_fn_with_options,
+    use_scroll_snap_with_options, ScrollSnapAxis, UseIntervalFnOptions, UseIntervalFnReturn,
+    UseRafFnOptions, UseScrollSnapOptions, UseScrollSnapReturn,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointerdown, pointerleave, pointerup};
+use leptos::*;
+
+/// Carousel on top of a CSS scroll snap container with autoplay.
+///
+/// The slides are the children of `target` which should be a
+/// [scroll snap container](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_scroll_snap).
+/// Which slide is active is determined by [`fn@crate::use_scroll_snap`]. On top of that this
+/// adds navigation that wraps around at the ends, autoplay, and swiping with the mouse.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_carousel_with_options, UseCarouselOptions, UseCarouselReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let slides = create_node_ref::<Div>();
+///
+/// let UseCarouselReturn {
+///     active_index,
+///     count,
+///     autoplay_progress,
+///     go_to,
+///     next,
+///     prev,
+///     ..
+/// } = use_carousel_with_options(slides, UseCarouselOptions::default().autoplay(true));
+///
+/// view! {
+///     <div
+///         node_ref=slides
+///         style="display: flex; overflow-x: auto; scroll-snap-type: x mandatory"
+///     >
+///         <img src="1.jpg" draggable="false" style="flex: 0 0 100%; scroll-snap-align: start" />
+///         <img src="2.jpg" draggable="false" style="flex: 0 0 100%; scroll-snap-align: start" />
+///         <img src="3.jpg" draggable="false" style="flex: 0 0 100%; scroll-snap-align: start" />
+///     </div>
+///
+///     <progress max="1" value=autoplay_progress />
+///
+///     <button on:click=move |_| prev()>"Previous"</button>
+///     {move || (0..count.get()).map(|i| {
+///         let go_to = go_to.clone();
+///         view! {
+///             <button on:click=move |_| go_to(i) class:active=move || active_index.get() == i>
+///                 {i + 1}
+///             </button>
+///         }
+///     }).collect_view()}
+///     <button on:click=move |_| next()>"Next"</button>
+/// }
+/// # }
+/// ```
+///
+/// ### Autoplay
+///
+/// With the option `autoplay` the carousel advances every `interval` milliseconds. You can
+/// start and stop it with `play` and `pause`. It's paused while the mouse hovers over the
+/// carousel (see option `pause_on_hover`) and while the page is hidden. After the active slide
+/// has changed, for example because the user scrolled, the time starts over.
+///
+/// ### Looping
+///
+/// By default `next` on the last slide goes back to the first and `prev` on the first slide
+/// goes to the last one. Set the option `loop_around` to `false` to stop at the ends instead.
+/// The autoplay then stops at the last slide.
+///
+/// ### Swiping
+///
+/// Touch devices swipe through the slides by scrolling natively. With the option `swipe` mouse
+/// users can drag the carousel to go to the next or previous slide as well. Images in the slides
+/// should have `draggable="false"` for this to work.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `active_index` and `count` are `0` and the returned functions do nothing.
+pub fn use_carousel<El, T>(
+    target: El,
+) -> UseCarouselReturn<
+    impl Fn(usize) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_carousel_with_options(target, UseCarouselOptions::default())
+}
+
+/// Version of [`use_carousel`] that takes a `UseCarouselOptions`. See [`use_carousel`] for how to use.
+pub fn use_carousel_with_options<El, T>(
+    target: El,
+    options: UseCarouselOptions,
+) -> UseCarouselReturn<
+    impl Fn(usize) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseCarouselOptions {
+        axis,
+        loop_around,
+        autoplay,
+        interval,
+        pause_on_hover,
+        swipe,
+        swipe_threshold,
+    } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let UseScrollSnapReturn {
+        index,
+        count,
+        is_start,
+        is_end,
+        snap_to,
+        prev: snap_prev,
+        next: snap_next,
+        ..
+    } = use_scroll_snap_with_options(target, UseScrollSnapOptions::default().axis(axis));
+
+    let next = {
+        let snap_to = snap_to.clone();
+
+        move || {
+            if !is_end.get_untracked() {
+                snap_next();
+            } else if loop_around && count.get_untracked() > 1 {
+                snap_to(0);
+            }
+        }
+    };
+
+    let prev = {
+        let snap_to = snap_to.clone();
+
+        move || {
+            if !is_start.get_untracked() {
+                snap_prev();
+            } else if loop_around && count.get_untracked() > 1 {
+                snap_to(count.get_untracked() - 1);
+            }
+        }
+    };
+
+    let (is_autoplay_enabled, set_autoplay_enabled) = create_signal(autoplay);
+    let (autoplay_progress, set_autoplay_progress) = create_signal(0.0_f64);
+
+    // Start of the current autoplay interval
+    let interval_start = store_value(now());
+
+    let is_hovered = if pause_on_hover {
+        use_element_hover(target)
+    } else {
+        Signal::derive(|| false)
+    };
+
+    let UseIntervalFnReturn {
+        is_active: is_ticking,
+        is_suspended,
+        pause: stop_ticking,
+        resume: start_ticking,
+        ..
+    } = use_interval_fn_with_options(
+        {
+            let next = next.clone();
+
+            move || {
+                interval_start.set_value(now());
+
+                if !loop_around && is_end.get_untracked() {
+                    set_autoplay_enabled.set(false);
+                } else {
+                    next();
+                }
+            }
+        },
+        interval,
+        UseIntervalFnOptions::default()
+            .immediate(false)
+            .hidden_behavior(TimerHiddenBehavior::Pause),
+    );
+
+    let is_playing =
+        Signal::derive(move || is_autoplay_enabled.get() && !is_hovered.get() && count.get() > 1);
+
+    // Starts the autoplay interval over
+    let restart = move || {
+        stop_ticking();
+        interval_start.set_value(now());
+        set_autoplay_progress.set(0.0);
+
+        if is_playing.get_untracked() {
+            start_ticking();
+        }
+    };
+
+    let _ = watch(
+        move || (is_playing.get(), index.get()),
+        move |_, _, _| restart(),
+        true,
+    );
+
+    // use_interval_fn starts the interval over when the page becomes visible again
+    let _ = watch(
+        move || is_suspended.get(),
+        move |is_suspended, _, _| {
+            if !is_suspended {
+                interval_start.set_value(now());
+            }
+        },
+        false,
+    );
+
+    let Pausable {
+        pause: stop_progress,
+        resume: start_progress,
+        ..
+    } = use_raf_fn_with_options(
+        move |_| {
+            let elapsed = now() - interval_start.get_value();
+            let interval = interval.get_untracked().max(1) as f64;
+            set_autoplay_progress.set((elapsed / interval).clamp(0.0, 1.0));
+        },
+        UseRafFnOptions::default().immediate(false),
+    );
+
+    let _ = watch(
+        move || is_ticking.get() && !is_suspended.get(),
+        move |is_ticking, _, _| {
+            if *is_ticking {
+                start_progress();
+            } else {
+                stop_progress();
+            }
+        },
+        true,
+    );
+
+    #[cfg(not(feature = "ssr"))]
+    if swipe {
+        use web_sys::PointerEvent;
+
+        let swipe_start = store_value(None::<(i32, i32)>);
+
+        let _ = use_event_listener(target, pointerdown, move |event: PointerEvent| {
+            if event.pointer_type() == "mouse" && event.button() == 0 {
+                swipe_start.set_value(Some((event.client_x(), event.client_y())));
+            }
+        });
+
+        let _ = use_event_listener(target, pointerup, {
+            let next = next.clone();
+            let prev = prev.clone();
+
+            move |event: PointerEvent| {
+                let Some((start_x, start_y)) = swipe_start.get_value() else {
+                    return;
+                };
+                swipe_start.set_value(None);
+
+                let distance = f64::from(match axis {
+                    ScrollSnapAxis::Horizontal => event.client_x() - start_x,
+                    ScrollSnapAxis::Vertical => event.client_y() - start_y,
+                });
+
+                if distance <= -swipe_threshold {
+                    next();
+                } else if distance >= swipe_threshold {
+                    prev();
+                }
+            }
+        });
+
+        let _ = use_event_listener(target, pointerleave, move |_| {
+            swipe_start.set_value(None);
+        });
+    }
+
+    UseCarouselReturn {
+        active_index: index,
+        count,
+        progress: Signal::derive(move || {
+            let count = count.get();
+            if count > 1 {
+                index.get() as f64 / (count - 1) as f64
+            } else {
+                0.0
+            }
+        }),
+        autoplay_progress: autoplay_progress.into(),
+        is_playing: Signal::derive(move || is_playing.get() && !is_suspended.get()),
+        go_to: snap_to,
+        next,
+        prev,
+        play: move || set_autoplay_enabled.set(true),
+        pause: move || set_autoplay_enabled.set(false),
+    }
+}
+
+/// Options for [`use_carousel_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseCarouselOptions {
+    /// The direction in which the carousel scrolls. Defaults to `ScrollSnapAxis::Horizontal`.
+    axis: ScrollSnapAxis,
+
+    /// If `true`, `next` on the last slide goes to the first one and `prev` on the first slide
+    /// goes to the last one. Defaults to `true`.
+    loop_around: bool,
+
+    /// Start the autoplay right away. Defaults to `false`.
+    autoplay: bool,
+
+    /// Time in ms after which the autoplay advances to the next slide. Defaults to `5000`.
+    #[builder(into)]
+    interval: MaybeSignal<u64>,
+
+    /// Pause the autoplay while the mouse hovers over the carousel. Defaults to `true`.
+    pause_on_hover: bool,
+
+    /// Allow to drag through the slides with the mouse. Defaults to `true`.
+    swipe: bool,
+
+    /// Distance in pixels the mouse has to be dragged to go to the next or previous slide.
+    /// Defaults to `50.0`.
+    swipe_threshold: f64,
+}
+
+impl Default for UseCarouselOptions {
+    fn default() -> Self {
+        Self {
+            axis: ScrollSnapAxis::default(),
+            loop_around: true,
+            autoplay: false,
+            interval: 5000.into(),
+            pause_on_hover: true,
+            swipe: true,
+            swipe_threshold: 50.0,
+        }
+    }
+}
+
+/// Return type of [`use_carousel`].
+pub struct UseCarouselReturn<GoToFn, NextFn, PrevFn, PlayFn, PauseFn>
+where
+    GoToFn: Fn(usize) + Clone + 'static,
+    NextFn: Fn() + Clone + 'static,
+    PrevFn: Fn() + Clone + 'static,
+    PlayFn: Fn() + Clone + 'static,
+    PauseFn: Fn() + Clone + 'static,
+{
+    /// Index of the active slide
+    pub active_index: Signal<usize>,
+    /// Number of slides
+    pub count: Signal<usize>,
+    /// Position of the active slide between `0.0` for the first and `1.0` for the last one
+    pub progress: Signal<f64>,
+    /// Time that has passed until the autoplay advances, between `0.0` and `1.0`
+    pub autoplay_progress: Signal<f64>,
+    /// `true` while the autoplay is running. `false` while it's paused, also because of hovering
+    /// or because the page is hidden.
+    pub is_playing: Signal<bool>,
+    /// Scrolls to the slide with the given index
+    pub go_to: GoToFn,
+    /// Scrolls to the next slide
+    pub next: NextFn,
+    /// Scrolls to the previous slide
+    pub prev: PrevFn,
+    /// Starts the autoplay
+    pub play: PlayFn,
+    /// Stops the autoplay
+    pub pause: PauseFn,
+}
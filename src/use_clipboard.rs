@@ -1,7 +1,10 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
 use crate::{js, js_fut, use_event_listener, use_supported, UseTimeoutFnReturn};
 use default_struct_builder::DefaultBuilder;
 use leptos::ev::{copy, cut};
 use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
 
 /// Reactive [Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API).
 /// Provides the ability to respond to clipboard commands (cut, copy, and paste)
@@ -10,6 +13,9 @@ use leptos::*;
 /// [Permissions API](https://developer.mozilla.org/en-US/docs/Web/API/Permissions_API).
 /// Without user permission, reading or altering the clipboard contents is not permitted.
 ///
+/// This only deals with text. To copy and paste images or other MIME types
+/// see [`fn@crate::use_clipboard_items`].
+///
 /// > This function requires `--cfg=web_sys_unstable_apis` to be activated as
 /// [described in the wasm-bindgen guide](https://rustwasm.github.io/docs/wasm-bindgen/web-sys/unstable-apis.html).
 ///
@@ -25,7 +31,7 @@ use leptos::*;
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
-/// let UseClipboardReturn { is_supported, text, copied, copy } = use_clipboard();
+/// let UseClipboardReturn { is_supported, text, copied, copy, .. } = use_clipboard();
 ///
 /// view! {
 ///     <Show
@@ -45,17 +51,79 @@ use leptos::*;
 /// # }
 /// ```
 ///
+/// ## Reset Delay per Call
+///
+/// `copy_with_reset_delay` overrides [`UseClipboardOptions::copied_reset_delay`] for a single
+/// call. While `copied` is `true`, `copied_value` contains the value that has been copied so you
+/// can show which of several buttons has been clicked.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_clipboard, UseClipboardReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseClipboardReturn { copied_value, copy_with_reset_delay, .. } = use_clipboard();
+///
+/// view! {
+///     <For each=|| vec!["npm i", "cargo add leptos-use"] key=|cmd| *cmd let:cmd>
+///         <button on:click={
+///             let copy_with_reset_delay = copy_with_reset_delay.clone();
+///             move |_| copy_with_reset_delay(cmd, 3000.0)
+///         }>
+///             {move || if copied_value.get().as_deref() == Some(cmd) { "Copied!" } else { cmd }}
+///         </button>
+///     </For>
+/// }
+/// # }
+/// ```
+///
+/// ## Copy an Element
+///
+/// `copy_element` copies the text of an element. Where the browser supports it the HTML of the
+/// element is copied as well so pasting it into a rich text editor keeps the formatting.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_clipboard, UseClipboardReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UseClipboardReturn { copy_element, .. } = use_clipboard();
+///
+/// view! {
+///     <div node_ref=el>"Some " <b>"formatted"</b> " text"</div>
+///     <button on:click=move |_| {
+///         if let Some(el) = el.get() {
+///             copy_element(&el);
+///         }
+///     }>"Copy"</button>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
-/// On the server the returnd `text` signal will always be `None` and `copy` is a no-op.
-pub fn use_clipboard() -> UseClipboardReturn<impl Fn(&str) + Clone> {
+/// On the server the returnd `text` signal will always be `None` and the copy functions are no-ops.
+pub fn use_clipboard() -> UseClipboardReturn<
+    impl Fn(&str) + Clone,
+    impl Fn(&str, f64) + Clone,
+    impl Fn(&web_sys::Element) + Clone,
+> {
     use_clipboard_with_options(UseClipboardOptions::default())
 }
 
 /// Version of [`use_clipboard`] that takes a `UseClipboardOptions`. See [`use_clipboard`] for how to use.
 pub fn use_clipboard_with_options(
     options: UseClipboardOptions,
-) -> UseClipboardReturn<impl Fn(&str) + Clone> {
+) -> UseClipboardReturn<
+    impl Fn(&str) + Clone,
+    impl Fn(&str, f64) + Clone,
+    impl Fn(&web_sys::Element) + Clone,
+> {
     let UseClipboardOptions {
         copied_reset_delay,
         read,
@@ -68,14 +136,28 @@ pub fn use_clipboard_with_options(
 
     let (text, set_text) = create_signal(None);
     let (copied, set_copied) = create_signal(false);
+    let (copied_value, set_copied_value) = create_signal(None::<String>);
+    let (reset_delay, set_reset_delay) = create_signal(copied_reset_delay);
 
-    let UseTimeoutFnReturn { start, .. } = crate::use_timeout_fn::use_timeout_fn(
+    let UseTimeoutFnReturn { start, stop, .. } = crate::use_timeout_fn::use_timeout_fn(
         move |_: ()| {
             set_copied.set(false);
+            set_copied_value.set(None);
         },
-        copied_reset_delay,
+        reset_delay,
     );
 
+    let on_copied = move |value: String, delay: f64| {
+        // a previous copy must not reset the state of this one
+        stop();
+
+        set_text.set(Some(value.clone()));
+        set_copied_value.set(Some(value));
+        set_copied.set(true);
+        set_reset_delay.set(delay);
+        start(());
+    };
+
     let update_text = move |_| {
         if is_supported.get() {
             spawn_local(async move {
@@ -93,20 +175,18 @@ pub fn use_clipboard_with_options(
         let _ = use_event_listener(window(), cut, update_text);
     }
 
-    let do_copy = {
-        let start = start.clone();
+    let copy_with_reset_delay = {
+        let on_copied = on_copied.clone();
 
-        move |value: &str| {
+        move |value: &str, delay: f64| {
             if is_supported.get() {
-                let start = start.clone();
+                let on_copied = on_copied.clone();
                 let value = value.to_owned();
 
                 spawn_local(async move {
                     if let Some(clipboard) = window().navigator().clipboard() {
                         if js_fut!(clipboard.write_text(&value)).await.is_ok() {
-                            set_text.set(Some(value));
-                            set_copied.set(true);
-                            start(());
+                            on_copied(value, delay);
                         }
                     }
                 });
@@ -114,14 +194,91 @@ pub fn use_clipboard_with_options(
         }
     };
 
+    let do_copy = {
+        let copy_with_reset_delay = copy_with_reset_delay.clone();
+
+        move |value: &str| copy_with_reset_delay(value, copied_reset_delay)
+    };
+
+    let copy_element = move |el: &web_sys::Element| {
+        if !is_supported.get() {
+            return;
+        }
+
+        let text = match el.dyn_ref::<web_sys::HtmlElement>() {
+            Some(el) => el.inner_text(),
+            None => el.text_content().unwrap_or_default(),
+        };
+        let html = el.inner_html();
+
+        let on_copied = on_copied.clone();
+
+        spawn_local(async move {
+            if let Some(clipboard) = window().navigator().clipboard() {
+                let written = match rich_clipboard_write(&clipboard, &text, &html) {
+                    Some(promise) => js_fut!(promise).await.is_ok(),
+                    None => js_fut!(clipboard.write_text(&text)).await.is_ok(),
+                };
+
+                if written {
+                    on_copied(text, copied_reset_delay);
+                }
+            }
+        });
+    };
+
     UseClipboardReturn {
         is_supported,
         text: text.into(),
         copied: copied.into(),
+        copied_value: copied_value.into(),
         copy: do_copy,
+        copy_with_reset_delay,
+        copy_element,
     }
 }
 
+/// Writes `text` as `text/plain` and `html` as `text/html` to the clipboard.
+/// Returns `None` if the browser doesn't support `ClipboardItem`.
+fn rich_clipboard_write(
+    clipboard: &web_sys::Clipboard,
+    text: &str,
+    html: &str,
+) -> Option<js_sys::Promise> {
+    let window = window();
+    let constructor = js!(window["ClipboardItem"])
+        .ok()
+        .filter(|constructor| constructor.is_function())?;
+
+    let record = js_sys::Object::new();
+    for (mime_type, content) in [("text/plain", text), ("text/html", html)] {
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_(mime_type);
+
+        let blob = web_sys::Blob::new_with_str_sequence_and_options(
+            &js_sys::Array::of1(&JsValue::from_str(content)),
+            &options,
+        )
+        .ok()?;
+
+        js_sys::Reflect::set(&record, &mime_type.into(), &blob).ok()?;
+    }
+
+    let item = js_sys::Reflect::construct(
+        constructor.unchecked_ref::<js_sys::Function>(),
+        &js_sys::Array::of1(&record),
+    )
+    .ok()?;
+
+    js!(clipboard["write"])
+        .ok()
+        .filter(|write| write.is_function())?
+        .unchecked_into::<js_sys::Function>()
+        .call1(clipboard, &js_sys::Array::of1(&item))
+        .ok()
+        .map(JsCast::unchecked_into)
+}
+
 /// Options for [`use_clipboard_with_options`].
 #[derive(DefaultBuilder)]
 pub struct UseClipboardOptions {
@@ -130,7 +287,7 @@ pub struct UseClipboardOptions {
     read: bool,
 
     /// After how many milliseconds after copying should the returned signal `copied` be set to `false`?
-    /// Defaults to 1500.
+    /// Can be overridden per call with `copy_with_reset_delay`. Defaults to 1500.
     copied_reset_delay: f64,
 }
 
@@ -144,9 +301,11 @@ impl Default for UseClipboardOptions {
 }
 
 /// Return type of [`use_clipboard`].
-pub struct UseClipboardReturn<CopyFn>
+pub struct UseClipboardReturn<CopyFn, CopyWithResetDelayFn, CopyElementFn>
 where
     CopyFn: Fn(&str) + Clone,
+    CopyWithResetDelayFn: Fn(&str, f64) + Clone,
+    CopyElementFn: Fn(&web_sys::Element) + Clone,
 {
     /// Whether the Clipboard API is supported.
     pub is_supported: Signal<bool>,
@@ -157,6 +316,17 @@ where
     /// `true` for [`UseClipboardOptions::copied_reset_delay`] milliseconds after copying.
     pub copied: Signal<bool>,
 
+    /// The copied value while `copied` is `true`. `None` otherwise.
+    pub copied_value: Signal<Option<String>>,
+
     /// Copy the given text to the clipboard.
     pub copy: CopyFn,
+
+    /// Copy the given text to the clipboard. `copied` is reset after the given number of
+    /// milliseconds instead of [`UseClipboardOptions::copied_reset_delay`].
+    pub copy_with_reset_delay: CopyWithResetDelayFn,
+
+    /// Copy the text content of the given element to the clipboard. If supported by the browser
+    /// the HTML content is copied as well.
+    pub copy_element: CopyElementFn,
 }
@@ -0,0 +1,641 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_event_listener, use_permission, use_supported, PermissionState};
+use default_struct_builder::DefaultBuilder;
+use futures_util::future::{self, FutureExt, LocalBoxFuture};
+use leptos::ev::paste;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+/// Read and write clipboard contents of any MIME type like images with the
+/// [async Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API).
+///
+/// Where [`fn@crate::use_clipboard`] only deals with text, this works with
+/// [`ClipboardItem`](https://developer.mozilla.org/en-US/docs/Web/API/ClipboardItem)s. Every
+/// representation of the clipboard contents is returned as a [`ClipboardBlob`] which is a
+/// `Blob` together with its MIME type.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::{use_clipboard_items, UseClipboardItemsReturn};
+/// # use futures_util::FutureExt;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let canvas = create_node_ref::<Canvas>();
+///
+/// let UseClipboardItemsReturn {
+///     is_supported,
+///     image_url,
+///     copy_canvas,
+///     read,
+///     ..
+/// } = use_clipboard_items();
+///
+/// view! {
+///     <Show
+///         when=is_supported
+///         fallback=move || view! { <p>"Your browser does not support the Clipboard API"</p> }
+///     >
+///         <canvas node_ref=canvas width="200" height="100" />
+///
+///         <button on:click={
+///             let copy_canvas = copy_canvas.clone();
+///             move |_| {
+///                 if let Some(canvas) = canvas.get() {
+///                     spawn_local(copy_canvas(&canvas).map(|_| ()));
+///                 }
+///             }
+///         }>"Copy image"</button>
+///
+///         <button on:click={
+///             let read = read.clone();
+///             move |_| spawn_local(read().map(|_| ()))
+///         }>"Paste image"</button>
+///     </Show>
+///
+///     <Show when=move || image_url.get().is_some()>
+///         <img src=move || image_url.get() />
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ### Writing
+///
+/// `write` puts several representations of the same content on the clipboard at once. Types
+/// that the browser can't write (see [`is_clipboard_type_supported`]) are skipped. Only if none
+/// of them is supported the returned future resolves to an error. In browsers without
+/// `ClipboardItem` the `text/plain` representation is written as text if there is one.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_clipboard_items, ClipboardBlob, UseClipboardItemsReturn};
+/// # use futures_util::FutureExt;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseClipboardItemsReturn { write, .. } = use_clipboard_items();
+///
+/// let copy_link = move |_| {
+///     let items = [
+///         ClipboardBlob::from_text("text/plain", "https://leptos.dev"),
+///         ClipboardBlob::from_text("text/html", r#"<a href="https://leptos.dev">Leptos</a>"#),
+///     ];
+///
+///     if let Ok(items) = items.into_iter().collect::<Result<Vec<_>, _>>() {
+///         spawn_local(write(items).map(|_| ()));
+///     }
+/// };
+/// #
+/// # view! { <button on:click=copy_link>"Copy link"</button> }
+/// # }
+/// ```
+///
+/// The browser only allows writing to the clipboard in reaction to a user interaction. Call
+/// `write` and `copy_canvas` right in the event handler. They access the clipboard before
+/// returning so awaiting the returned future later is fine.
+///
+/// ### Reading
+///
+/// `read` asks for the clipboard contents which usually makes the browser prompt the user for
+/// permission. The current permission states are returned as `read_permission` and
+/// `write_permission`.
+///
+/// When the user pastes into the page the pasted contents are picked up from the `paste` event.
+/// This works without permission and in browsers without the async Clipboard API. Set the
+/// option `read_on_paste` to `false` to disable this.
+///
+/// In both cases `items` contains all representations and `image`, `image_bytes` and
+/// `image_url` the first image among them.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `items` is always empty, the permissions are `PermissionState::Unknown` and the
+/// returned futures resolve to `UseClipboardItemsError::NotSupported`.
+pub fn use_clipboard_items() -> UseClipboardItemsReturn<
+    impl Fn() -> LocalBoxFuture<'static, Result<Vec<ClipboardBlob>, UseClipboardItemsError>>
+        + Clone
+        + 'static,
+    impl Fn(Vec<ClipboardBlob>) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>>
+        + Clone
+        + 'static,
+    impl Fn(
+            &web_sys::HtmlCanvasElement,
+        ) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>>
+        + Clone
+        + 'static,
+> {
+    use_clipboard_items_with_options(UseClipboardItemsOptions::default())
+}
+
+/// Version of [`use_clipboard_items`] that takes a `UseClipboardItemsOptions`. See [`use_clipboard_items`] for how to use.
+pub fn use_clipboard_items_with_options(
+    options: UseClipboardItemsOptions,
+) -> UseClipboardItemsReturn<
+    impl Fn() -> LocalBoxFuture<'static, Result<Vec<ClipboardBlob>, UseClipboardItemsError>>
+        + Clone
+        + 'static,
+    impl Fn(Vec<ClipboardBlob>) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>>
+        + Clone
+        + 'static,
+    impl Fn(
+            &web_sys::HtmlCanvasElement,
+        ) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>>
+        + Clone
+        + 'static,
+> {
+    let UseClipboardItemsOptions { read_on_paste } = options;
+
+    let is_supported = use_supported(|| {
+        js!("clipboard" in &window().navigator()) && js!("ClipboardItem" in &window())
+    });
+
+    let read_permission = use_permission("clipboard-read");
+    let write_permission = use_permission("clipboard-write");
+
+    let (items, set_items) = create_signal(Vec::<ClipboardBlob>::new());
+    let (image_bytes, set_image_bytes) = create_signal(None::<Vec<u8>>);
+
+    let image = Signal::derive(move || {
+        items.with(|items| items.iter().find(|item| item.is_image()).cloned())
+    });
+
+    // Counts the updates of `items` so that the bytes of an older image don't overwrite newer ones
+    let generation = store_value(0_usize);
+
+    let set_contents = move |contents: Vec<ClipboardBlob>| {
+        let image = contents.iter().find(|item| item.is_image()).cloned();
+        set_items.set(contents);
+
+        generation.update_value(|generation| *generation += 1);
+        let current_generation = generation.get_value();
+
+        match image {
+            Some(image) => spawn_local(async move {
+                let bytes = image.to_bytes().await.ok();
+
+                if generation.get_value() == current_generation {
+                    set_image_bytes.set(bytes);
+                }
+            }),
+            None => set_image_bytes.set(None),
+        }
+    };
+
+    let image_url = create_memo(move |prev: Option<&Option<String>>| {
+        if let Some(Some(url)) = prev {
+            let _ = web_sys::Url::revoke_object_url(url);
+        }
+
+        image.with(|image| {
+            image
+                .as_ref()
+                .and_then(|image| web_sys::Url::create_object_url_with_blob(&image.blob).ok())
+        })
+    });
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        if read_on_paste {
+            let _ = use_event_listener(window(), paste, move |event: web_sys::ClipboardEvent| {
+                if let Some(data) = event.clipboard_data() {
+                    set_contents(data_transfer_contents(&data));
+                }
+            });
+        }
+
+        on_cleanup(move || {
+            if let Some(Some(url)) = image_url.try_get_untracked() {
+                let _ = web_sys::Url::revoke_object_url(&url);
+            }
+        });
+    }
+
+    let read =
+        move || -> LocalBoxFuture<'static, Result<Vec<ClipboardBlob>, UseClipboardItemsError>> {
+            #[cfg(feature = "ssr")]
+            return future::ready(Err(UseClipboardItemsError::NotSupported)).boxed_local();
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                async move {
+                    let contents = read_clipboard().await?;
+                    set_contents(contents.clone());
+
+                    Ok(contents)
+                }
+                .boxed_local()
+            }
+        };
+
+    UseClipboardItemsReturn {
+        is_supported,
+        read_permission,
+        write_permission,
+        items: items.into(),
+        image,
+        image_bytes: image_bytes.into(),
+        image_url: image_url.into(),
+        read,
+        write: write_contents,
+        copy_canvas: copy_canvas_as_png,
+    }
+}
+
+/// Whether the browser can write the given MIME type to the clipboard.
+///
+/// Uses [`ClipboardItem.supports()`](https://developer.mozilla.org/en-US/docs/Web/API/ClipboardItem/supports_static)
+/// where available. Other browsers that support `ClipboardItem` are assumed to support the types
+/// every browser has to support: `text/plain`, `text/html` and `image/png`.
+///
+/// Always `false` on the server.
+pub fn is_clipboard_type_supported(mime_type: &str) -> bool {
+    #[cfg(feature = "ssr")]
+    return false;
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let Some(constructor) = clipboard_item_constructor() else {
+            return false;
+        };
+
+        match js!(constructor["supports"])
+            .ok()
+            .filter(JsValue::is_function)
+        {
+            Some(supports) => supports
+                .unchecked_into::<js_sys::Function>()
+                .call1(&constructor, &JsValue::from_str(mime_type))
+                .map(|supported| supported.is_truthy())
+                .unwrap_or(false),
+            None => matches!(mime_type, "text/plain" | "text/html" | "image/png"),
+        }
+    }
+}
+
+fn write_contents(
+    contents: Vec<ClipboardBlob>,
+) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>> {
+    #[cfg(feature = "ssr")]
+    return future::ready(Err(UseClipboardItemsError::NotSupported)).boxed_local();
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let representations = contents
+            .iter()
+            .map(|item| (item.mime_type.clone(), JsValue::from(&item.blob)))
+            .collect();
+
+        match write_clipboard(representations) {
+            Ok(promise) => async move {
+                js_fut!(promise)
+                    .await
+                    .map(|_| ())
+                    .map_err(UseClipboardItemsError::Js)
+            }
+            .boxed_local(),
+            Err(UseClipboardItemsError::NotSupported) => write_text_fallback(contents),
+            Err(err) => future::ready(Err(err)).boxed_local(),
+        }
+    }
+}
+
+fn copy_canvas_as_png(
+    canvas: &web_sys::HtmlCanvasElement,
+) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>> {
+    #[cfg(feature = "ssr")]
+    return future::ready(Err(UseClipboardItemsError::NotSupported)).boxed_local();
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        // The `ClipboardItem` is created with the promise of the PNG so the clipboard is
+        // accessed right away while the user interaction is still active.
+        let result = canvas_png_promise(canvas)
+            .map_err(UseClipboardItemsError::Js)
+            .and_then(|png| write_clipboard(vec![("image/png".to_string(), png.into())]));
+
+        async move {
+            js_fut!(result?)
+                .await
+                .map(|_| ())
+                .map_err(UseClipboardItemsError::Js)
+        }
+        .boxed_local()
+    }
+}
+
+fn clipboard() -> Option<JsValue> {
+    let navigator = window().navigator();
+
+    js!(navigator["clipboard"])
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined() && !clipboard.is_null())
+}
+
+fn clipboard_item_constructor() -> Option<js_sys::Function> {
+    let window = window();
+
+    js!(window["ClipboardItem"])
+        .ok()
+        .filter(JsValue::is_function)
+        .map(JsCast::unchecked_into)
+}
+
+/// Returns the method `name` of the clipboard if the clipboard has it.
+fn clipboard_method(clipboard: &JsValue, name: &str) -> Option<js_sys::Function> {
+    js_sys::Reflect::get(clipboard, &JsValue::from_str(name))
+        .ok()
+        .filter(JsValue::is_function)
+        .map(JsCast::unchecked_into)
+}
+
+/// Writes one `ClipboardItem` with the given representations. The values are `Blob`s or promises
+/// that resolve to `Blob`s. Representations of unsupported types are skipped.
+fn write_clipboard(
+    representations: Vec<(String, JsValue)>,
+) -> Result<js_sys::Promise, UseClipboardItemsError> {
+    let clipboard = clipboard().ok_or(UseClipboardItemsError::NotSupported)?;
+    let write =
+        clipboard_method(&clipboard, "write").ok_or(UseClipboardItemsError::NotSupported)?;
+    let constructor = clipboard_item_constructor().ok_or(UseClipboardItemsError::NotSupported)?;
+
+    let (supported, unsupported): (Vec<_>, Vec<_>) = representations
+        .into_iter()
+        .partition(|(mime_type, _)| is_clipboard_type_supported(mime_type));
+
+    if supported.is_empty() {
+        return Err(UseClipboardItemsError::UnsupportedTypes(
+            unsupported
+                .into_iter()
+                .map(|(mime_type, _)| mime_type)
+                .collect(),
+        ));
+    }
+
+    let record = js_sys::Object::new();
+    for (mime_type, value) in supported {
+        js_sys::Reflect::set(&record, &JsValue::from_str(&mime_type), &value)
+            .map_err(UseClipboardItemsError::Js)?;
+    }
+
+    let item = js_sys::Reflect::construct(&constructor, &js_sys::Array::of1(&record))
+        .map_err(UseClipboardItemsError::Js)?;
+
+    write
+        .call1(&clipboard, &js_sys::Array::of1(&item))
+        .map(JsCast::unchecked_into)
+        .map_err(UseClipboardItemsError::Js)
+}
+
+/// Writes the `text/plain` representation with `writeText` for browsers without `ClipboardItem`.
+fn write_text_fallback(
+    contents: Vec<ClipboardBlob>,
+) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>> {
+    let Some(write_text) = clipboard()
+        .and_then(|clipboard| Some((clipboard_method(&clipboard, "writeText")?, clipboard)))
+    else {
+        return future::ready(Err(UseClipboardItemsError::NotSupported)).boxed_local();
+    };
+
+    let Some(text) = contents
+        .into_iter()
+        .find(|item| item.mime_type == "text/plain")
+    else {
+        return future::ready(Err(UseClipboardItemsError::NotSupported)).boxed_local();
+    };
+
+    async move {
+        let (write_text, clipboard) = write_text;
+        let text = text.to_text().await.map_err(UseClipboardItemsError::Js)?;
+
+        let promise: js_sys::Promise = write_text
+            .call1(&clipboard, &JsValue::from_str(&text))
+            .map_err(UseClipboardItemsError::Js)?
+            .unchecked_into();
+
+        js_fut!(promise)
+            .await
+            .map(|_| ())
+            .map_err(UseClipboardItemsError::Js)
+    }
+    .boxed_local()
+}
+
+/// Reads all representations of all items on the clipboard.
+async fn read_clipboard() -> Result<Vec<ClipboardBlob>, UseClipboardItemsError> {
+    let clipboard = clipboard().ok_or(UseClipboardItemsError::NotSupported)?;
+    let read = clipboard_method(&clipboard, "read").ok_or(UseClipboardItemsError::NotSupported)?;
+
+    let promise: js_sys::Promise = read
+        .call0(&clipboard)
+        .map_err(UseClipboardItemsError::Js)?
+        .unchecked_into();
+
+    let items = js_fut!(promise).await.map_err(UseClipboardItemsError::Js)?;
+
+    let mut contents = vec![];
+
+    for item in js_sys::Array::from(&items).iter() {
+        let types = js!(item["types"]).map_err(UseClipboardItemsError::Js)?;
+        let get_type: js_sys::Function = js!(item["getType"])
+            .map_err(UseClipboardItemsError::Js)?
+            .unchecked_into();
+
+        for mime_type in js_sys::Array::from(&types).iter() {
+            let promise: js_sys::Promise = get_type
+                .call1(&item, &mime_type)
+                .map_err(UseClipboardItemsError::Js)?
+                .unchecked_into();
+
+            let blob = js_fut!(promise).await.map_err(UseClipboardItemsError::Js)?;
+
+            contents.push(ClipboardBlob::new(
+                mime_type.as_string().unwrap_or_default(),
+                blob.unchecked_into(),
+            ));
+        }
+    }
+
+    Ok(contents)
+}
+
+/// The text and files of a `paste` event.
+#[cfg(not(feature = "ssr"))]
+fn data_transfer_contents(data: &web_sys::DataTransfer) -> Vec<ClipboardBlob> {
+    let mut contents = vec![];
+
+    for mime_type in ["text/plain", "text/html"] {
+        if let Some(Ok(item)) = data
+            .get_data(mime_type)
+            .ok()
+            .filter(|text| !text.is_empty())
+            .map(|text| ClipboardBlob::from_text(mime_type, &text))
+        {
+            contents.push(item);
+        }
+    }
+
+    if let Some(files) = data.files() {
+        for index in 0..files.length() {
+            if let Some(file) = files.get(index) {
+                contents.push(ClipboardBlob::new(file.type_(), file.into()));
+            }
+        }
+    }
+
+    contents
+}
+
+/// Encodes the canvas as PNG. Returns a promise that resolves to the `Blob`.
+#[cfg(not(feature = "ssr"))]
+fn canvas_png_promise(canvas: &web_sys::HtmlCanvasElement) -> Result<js_sys::Promise, JsValue> {
+    let mut to_blob_result = Ok(());
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_blob = Closure::once_into_js(move |blob: JsValue| {
+            if blob.is_null() {
+                let _ = reject.call1(
+                    &JsValue::UNDEFINED,
+                    &JsValue::from_str("canvas couldn't be encoded"),
+                );
+            } else {
+                let _ = resolve.call1(&JsValue::UNDEFINED, &blob);
+            }
+        });
+
+        to_blob_result = canvas.to_blob(on_blob.unchecked_ref());
+    });
+    to_blob_result?;
+
+    Ok(promise)
+}
+
+/// One representation of the clipboard contents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClipboardBlob {
+    /// MIME type like `"image/png"` or `"text/plain"`
+    pub mime_type: String,
+    /// The data
+    pub blob: web_sys::Blob,
+}
+
+impl ClipboardBlob {
+    /// Wraps a `Blob` that contains data of the given MIME type.
+    pub fn new(mime_type: impl Into<String>, blob: web_sys::Blob) -> Self {
+        Self {
+            mime_type: mime_type.into(),
+            blob,
+        }
+    }
+
+    /// Creates a `Blob` of the given type that contains `text`.
+    pub fn from_text(mime_type: &str, text: &str) -> Result<Self, JsValue> {
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_(mime_type);
+
+        let blob = web_sys::Blob::new_with_str_sequence_and_options(
+            &js_sys::Array::of1(&JsValue::from_str(text)),
+            &options,
+        )?;
+
+        Ok(Self::new(mime_type, blob))
+    }
+
+    /// Creates a `Blob` of the given type that contains `bytes`.
+    pub fn from_bytes(mime_type: &str, bytes: &[u8]) -> Result<Self, JsValue> {
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_(mime_type);
+
+        let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(
+            &js_sys::Array::of1(&js_sys::Uint8Array::from(bytes)),
+            &options,
+        )?;
+
+        Ok(Self::new(mime_type, blob))
+    }
+
+    /// Whether the MIME type is an image type
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+
+    /// Reads the content of the `Blob`.
+    pub async fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        let buffer = js_fut!(self.blob.array_buffer()).await?;
+
+        Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+    }
+
+    /// Reads the content of the `Blob` as UTF-8 text.
+    pub async fn to_text(&self) -> Result<String, JsValue> {
+        let text = js_fut!(self.blob.text()).await?;
+
+        Ok(text.as_string().unwrap_or_default())
+    }
+}
+
+/// Options for [`use_clipboard_items_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseClipboardItemsOptions {
+    /// Update `items` with the contents of `paste` events. Defaults to `true`.
+    read_on_paste: bool,
+}
+
+impl Default for UseClipboardItemsOptions {
+    fn default() -> Self {
+        Self {
+            read_on_paste: true,
+        }
+    }
+}
+
+/// Return type of [`use_clipboard_items`].
+pub struct UseClipboardItemsReturn<ReadFn, WriteFn, CopyCanvasFn>
+where
+    ReadFn: Fn() -> LocalBoxFuture<'static, Result<Vec<ClipboardBlob>, UseClipboardItemsError>>
+        + Clone
+        + 'static,
+    WriteFn: Fn(Vec<ClipboardBlob>) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>>
+        + Clone
+        + 'static,
+    CopyCanvasFn: Fn(
+            &web_sys::HtmlCanvasElement,
+        ) -> LocalBoxFuture<'static, Result<(), UseClipboardItemsError>>
+        + Clone
+        + 'static,
+{
+    /// Whether the async Clipboard API with `ClipboardItem` is supported
+    pub is_supported: Signal<bool>,
+    /// Permission to read the clipboard
+    pub read_permission: Signal<PermissionState>,
+    /// Permission to write to the clipboard
+    pub write_permission: Signal<PermissionState>,
+    /// All representations of the last read or pasted clipboard contents
+    pub items: Signal<Vec<ClipboardBlob>>,
+    /// The first image in `items`
+    pub image: Signal<Option<ClipboardBlob>>,
+    /// The bytes of `image`. Set shortly after `image` because the `Blob` is read asynchronously.
+    pub image_bytes: Signal<Option<Vec<u8>>>,
+    /// Object URL of `image` that can be used as `src` of an `<img>`
+    pub image_url: Signal<Option<String>>,
+    /// Reads the clipboard contents into `items` and returns them
+    pub read: ReadFn,
+    /// Writes the given representations as one item to the clipboard
+    pub write: WriteFn,
+    /// Copies the content of the canvas as `image/png` to the clipboard
+    pub copy_canvas: CopyCanvasFn,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum UseClipboardItemsError {
+    #[error("the async Clipboard API is not supported")]
+    NotSupported,
+    #[error("none of the types {0:?} is supported by the clipboard")]
+    UnsupportedTypes(Vec<String>),
+    #[error("failed to access the clipboard: {0:?}")]
+    Js(JsValue),
+}
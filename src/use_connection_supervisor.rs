@@ -0,0 +1,334 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ConnectionReadyState;
+use crate::utils::Pausable;
+use crate::{use_document_visibility, use_online, watch_with_options, WatchOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+
+/// Supervise several connections like `WebSocket`s, `EventSource`s or fetch polling
+/// and combine their states into one overall health signal.
+///
+/// The supervisor also pauses all connections when the browser goes offline and resumes them
+/// when it's back online. Optionally the same happens when the page is hidden.
+/// You can pause and resume all connections manually as well.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_connection_supervisor, use_event_source, use_interval_fn, use_websocket,
+/// #     SupervisedConnection, UseConnectionSupervisorReturn, UseEventSourceReturn,
+/// #     UseWebSocketReturn,
+/// # };
+/// # use leptos_use::utils::Pausable;
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn {
+///     ready_state, open, close, ..
+/// } = use_websocket::<String, FromToStringCodec>("wss://echo.websocket.events/");
+///
+/// let UseEventSourceReturn {
+///     ready_state: es_ready_state,
+///     open: es_open,
+///     close: es_close,
+///     ..
+/// } = use_event_source::<String, FromToStringCodec>("https://event-source-url");
+///
+/// let polling = Pausable::from(use_interval_fn(|| { /* fetch something */ }, 5000));
+///
+/// let UseConnectionSupervisorReturn {
+///     health,
+///     connections,
+///     pause_all,
+///     resume_all,
+///     ..
+/// } = use_connection_supervisor(vec![
+///     SupervisedConnection::new("chat", ready_state, close, open),
+///     SupervisedConnection::new("notifications", es_ready_state, es_close, es_open),
+///     SupervisedConnection::from_pausable("polling", &polling),
+/// ]);
+///
+/// view! {
+///     <p>"Health: " {move || health.get().to_string()}</p>
+///     <ul>
+///         {move || connections.get().into_iter().map(|c| view! {
+///             <li>{c.name} ": " {c.ready_state.to_string()}</li>
+///         }).collect_view()}
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the connections are never paused or resumed. `health` and `connections` reflect
+/// the states of the supervised connections.
+pub fn use_connection_supervisor(
+    connections: Vec<SupervisedConnection>,
+) -> UseConnectionSupervisorReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_connection_supervisor_with_options(connections, UseConnectionSupervisorOptions::default())
+}
+
+/// Version of [`use_connection_supervisor`] that takes a `UseConnectionSupervisorOptions`. See [`use_connection_supervisor`] for how to use.
+pub fn use_connection_supervisor_with_options(
+    connections: Vec<SupervisedConnection>,
+    options: UseConnectionSupervisorOptions,
+) -> UseConnectionSupervisorReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseConnectionSupervisorOptions {
+        pause_when_offline,
+        pause_when_hidden,
+    } = options;
+
+    let connections = Rc::new(connections);
+
+    let statuses = Signal::derive({
+        let connections = Rc::clone(&connections);
+
+        move || {
+            connections
+                .iter()
+                .map(|connection| ConnectionStatus {
+                    name: connection.name.clone(),
+                    ready_state: connection.ready_state.get(),
+                })
+                .collect::<Vec<_>>()
+        }
+    });
+
+    let is_online = use_online();
+
+    let visibility = use_document_visibility();
+
+    let auto_paused = Signal::derive(move || {
+        (pause_when_offline && !is_online.get())
+            || (pause_when_hidden && visibility.get() == web_sys::VisibilityState::Hidden)
+    });
+
+    let (manually_paused, set_manually_paused) = create_signal(false);
+
+    let pause_connections = {
+        let connections = Rc::clone(&connections);
+
+        move || {
+            for connection in connections.iter() {
+                (connection.pause)();
+            }
+        }
+    };
+
+    let resume_connections = {
+        let connections = Rc::clone(&connections);
+
+        move || {
+            for connection in connections.iter() {
+                (connection.resume)();
+            }
+        }
+    };
+
+    let _ = watch_with_options(
+        move || auto_paused.get(),
+        {
+            let pause_connections = pause_connections.clone();
+            let resume_connections = resume_connections.clone();
+
+            move |auto_paused, prev_auto_paused, _| {
+                if manually_paused.get_untracked() {
+                    return;
+                }
+
+                if *auto_paused {
+                    pause_connections();
+                } else if prev_auto_paused.is_some() {
+                    resume_connections();
+                }
+            }
+        },
+        WatchOptions::default().immediate(true),
+    );
+
+    let paused = Signal::derive(move || manually_paused.get() || auto_paused.get());
+
+    let health = Signal::derive(move || {
+        if paused.get() {
+            return ConnectionHealth::Paused;
+        }
+
+        statuses.with(|statuses| {
+            let open_count = statuses
+                .iter()
+                .filter(|status| status.ready_state == ConnectionReadyState::Open)
+                .count();
+
+            if open_count == statuses.len() {
+                ConnectionHealth::Healthy
+            } else if open_count == 0 {
+                ConnectionHealth::Down
+            } else {
+                ConnectionHealth::Degraded
+            }
+        })
+    });
+
+    let pause_all = move || {
+        set_manually_paused.set(true);
+
+        // already paused otherwise
+        if !auto_paused.get_untracked() {
+            pause_connections();
+        }
+    };
+
+    let resume_all = move || {
+        set_manually_paused.set(false);
+
+        // resumed as soon as the browser is back online or the page is visible again otherwise
+        if !auto_paused.get_untracked() {
+            resume_connections();
+        }
+    };
+
+    UseConnectionSupervisorReturn {
+        health,
+        connections: statuses,
+        paused,
+        pause_all,
+        resume_all,
+    }
+}
+
+/// A connection that is supervised by [`use_connection_supervisor`].
+#[derive(Clone)]
+pub struct SupervisedConnection {
+    name: String,
+    ready_state: Signal<ConnectionReadyState>,
+    pause: Rc<dyn Fn()>,
+    resume: Rc<dyn Fn()>,
+}
+
+impl SupervisedConnection {
+    /// Creates a supervised connection from its state and the functions to pause and resume it.
+    /// For connection hooks like [`crate::use_websocket`] these are usually `close` and `open`.
+    pub fn new(
+        name: impl Into<String>,
+        ready_state: impl Into<Signal<ConnectionReadyState>>,
+        pause: impl Fn() + 'static,
+        resume: impl Fn() + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            ready_state: ready_state.into(),
+            pause: Rc::new(pause),
+            resume: Rc::new(resume),
+        }
+    }
+
+    /// Creates a supervised connection from a pausable like the one returned by
+    /// [`crate::use_interval_fn`] which is useful for fetch polling.
+    /// It counts as `ConnectionReadyState::Open` while active and as `ConnectionReadyState::Closed` while paused.
+    pub fn from_pausable<PauseFn, ResumeFn>(
+        name: impl Into<String>,
+        pausable: &Pausable<PauseFn, ResumeFn>,
+    ) -> Self
+    where
+        PauseFn: Fn() + Clone + 'static,
+        ResumeFn: Fn() + Clone + 'static,
+    {
+        let is_active = pausable.is_active;
+
+        Self::new(
+            name,
+            Signal::derive(move || {
+                if is_active.get() {
+                    ConnectionReadyState::Open
+                } else {
+                    ConnectionReadyState::Closed
+                }
+            }),
+            pausable.pause.clone(),
+            pausable.resume.clone(),
+        )
+    }
+}
+
+/// The state of a single connection as reported by [`use_connection_supervisor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionStatus {
+    /// The name given to [`SupervisedConnection::new`].
+    pub name: String,
+    /// The current state of the connection.
+    pub ready_state: ConnectionReadyState,
+}
+
+/// The overall health of the connections supervised by [`use_connection_supervisor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConnectionHealth {
+    /// All connections are open. This is also the case if there are no connections.
+    Healthy,
+    /// Some but not all connections are open.
+    Degraded,
+    /// No connection is open.
+    Down,
+    /// The connections have been paused manually or because the browser is offline or the page is hidden.
+    Paused,
+}
+
+impl std::fmt::Display for ConnectionHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ConnectionHealth::Healthy => write!(f, "Healthy"),
+            ConnectionHealth::Degraded => write!(f, "Degraded"),
+            ConnectionHealth::Down => write!(f, "Down"),
+            ConnectionHealth::Paused => write!(f, "Paused"),
+        }
+    }
+}
+
+/// Options for [`use_connection_supervisor_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseConnectionSupervisorOptions {
+    /// Pause all connections while the browser is offline and resume them when it's back online.
+    /// Defaults to `true`.
+    pause_when_offline: bool,
+
+    /// Pause all connections while the page is hidden and resume them when it's visible again.
+    /// Defaults to `false`.
+    pause_when_hidden: bool,
+}
+
+impl Default for UseConnectionSupervisorOptions {
+    fn default() -> Self {
+        Self {
+            pause_when_offline: true,
+            pause_when_hidden: false,
+        }
+    }
+}
+
+/// Return type of [`use_connection_supervisor`].
+pub struct UseConnectionSupervisorReturn<PauseAllFn, ResumeAllFn>
+where
+    PauseAllFn: Fn() + Clone,
+    ResumeAllFn: Fn() + Clone,
+{
+    /// The combined health of all connections.
+    pub health: Signal<ConnectionHealth>,
+
+    /// The state of every connection in the order they were given.
+    pub connections: Signal<Vec<ConnectionStatus>>,
+
+    /// `true` while the connections are paused manually or automatically.
+    pub paused: Signal<bool>,
+
+    /// Pauses all connections until `resume_all` is called.
+    pub pause_all: PauseAllFn,
+
+    /// Resumes all connections unless they are paused because the browser is offline
+    /// or the page is hidden. In that case they are resumed as soon as that changes.
+    pub resume_all: ResumeAllFn,
+}
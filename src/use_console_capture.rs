@@ -0,0 +1,270 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::now;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::fmt::Display;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Captures the messages written to the browser console into a reactive list.
+///
+/// This is useful to build an in-app debug console for mobile browsers and web views where the
+/// dev tools aren't easily available. The methods of `console` are replaced by wrappers that
+/// record every call and then forward it to the original method. When the component is
+/// unmounted the original methods are restored.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_console_capture, UseConsoleCaptureReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseConsoleCaptureReturn { entries, clear } = use_console_capture();
+///
+/// view! {
+///     <button on:click=move |_| clear()>"Clear"</button>
+///     <ul>
+///         <For each=move || entries.get() key=|entry| entry.id let:entry>
+///             <li class=entry.level.to_string()>{entry.message}</li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// Messages are formatted like in the console: strings as they are, errors with their message and
+/// everything else as JSON. Only the latest `max_entries` messages are kept.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_console_capture_with_options, ConsoleLevel, UseConsoleCaptureOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let capture = use_console_capture_with_options(
+///     UseConsoleCaptureOptions::default()
+///         .levels(vec![ConsoleLevel::Warn, ConsoleLevel::Error])
+///         .max_entries(50),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// If several instances of this hook are active at the same time, unmount them in the reverse
+/// order they were mounted in. Otherwise a wrapper that belongs to an unmounted hook stays in
+/// place and just forwards the messages.
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is captured and `entries` is always empty.
+pub fn use_console_capture() -> UseConsoleCaptureReturn<impl Fn() + Clone> {
+    use_console_capture_with_options(UseConsoleCaptureOptions::default())
+}
+
+/// Version of [`use_console_capture`] that takes a `UseConsoleCaptureOptions`. See [`use_console_capture`] for how to use.
+pub fn use_console_capture_with_options(
+    options: UseConsoleCaptureOptions,
+) -> UseConsoleCaptureReturn<impl Fn() + Clone> {
+    let UseConsoleCaptureOptions {
+        levels,
+        max_entries,
+        forward,
+    } = options;
+
+    let (entries, set_entries) = create_signal(Vec::<ConsoleEntry>::new());
+    let next_id = store_value(0_usize);
+
+    let clear = move || set_entries.set(vec![]);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        use wasm_bindgen::closure::Closure;
+
+        let console = js_sys::Reflect::get(&window(), &"console".into()).unwrap_or_default();
+        let mut restores = vec![];
+
+        for level in levels {
+            let Some(original) = js_sys::Reflect::get(&console, &level.to_string().into())
+                .ok()
+                .and_then(|original| original.dyn_into::<js_sys::Function>().ok())
+            else {
+                continue;
+            };
+
+            let capture = Closure::<dyn Fn(js_sys::Array)>::new(move |args: js_sys::Array| {
+                let message = args
+                    .iter()
+                    .map(|arg| format_console_arg(&arg))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let timestamp = now();
+
+                // Deferred because the message might be logged while `entries` is borrowed
+                queue_microtask(move || {
+                    let Some(id) = next_id.try_update_value(|id| {
+                        *id += 1;
+                        *id
+                    }) else {
+                        return;
+                    };
+
+                    set_entries.try_update(|entries| {
+                        entries.push(ConsoleEntry {
+                            id,
+                            level,
+                            message,
+                            timestamp,
+                        });
+
+                        if entries.len() > max_entries {
+                            entries.drain(..entries.len() - max_entries);
+                        }
+                    });
+                });
+            })
+            .into_js_value();
+
+            let Some(wrapper) = wrap_console_method(&original, &capture, forward) else {
+                continue;
+            };
+
+            let _ = js_sys::Reflect::set(&console, &level.to_string().into(), &wrapper);
+
+            restores.push((level, original, wrapper));
+        }
+
+        on_cleanup(move || {
+            for (level, original, wrapper) in restores {
+                let key = JsValue::from(level.to_string());
+
+                // only restore if no other wrapper has been installed on top of this one
+                if js_sys::Reflect::get(&console, &key).ok().as_ref() == Some(&wrapper) {
+                    let _ = js_sys::Reflect::set(&console, &key, &original);
+                }
+            }
+        });
+    }
+
+    UseConsoleCaptureReturn {
+        entries: entries.into(),
+        clear,
+    }
+}
+
+/// Creates a function that passes its arguments as an array to `capture` and then calls
+/// `original` with them if `forward` is `true`.
+#[cfg(not(feature = "ssr"))]
+fn wrap_console_method(
+    original: &js_sys::Function,
+    capture: &JsValue,
+    forward: bool,
+) -> Option<JsValue> {
+    let factory = js_sys::Function::new_with_args(
+        "original, capture, forward",
+        "return function (...args) { capture(args); if (forward) { return original.apply(this, args); } };",
+    );
+
+    factory
+        .call3(&JsValue::NULL, original, capture, &forward.into())
+        .ok()
+}
+
+/// Formats one argument of a console call similar to how the console displays it.
+#[cfg(not(feature = "ssr"))]
+fn format_console_arg(arg: &JsValue) -> String {
+    if let Some(string) = arg.as_string() {
+        return string;
+    }
+
+    if let Some(error) = arg.dyn_ref::<js_sys::Error>() {
+        return String::from(error.to_string());
+    }
+
+    if arg.is_undefined() {
+        return "undefined".to_string();
+    }
+
+    js_sys::JSON::stringify(arg)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_else(|| format!("{arg:?}"))
+}
+
+/// The method of `console` that a message has been written with.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ConsoleLevel {
+    Debug,
+    Log,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Display for ConsoleLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ConsoleLevel::Debug => write!(f, "debug"),
+            ConsoleLevel::Log => write!(f, "log"),
+            ConsoleLevel::Info => write!(f, "info"),
+            ConsoleLevel::Warn => write!(f, "warn"),
+            ConsoleLevel::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A message that has been written to the console.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsoleEntry {
+    /// Increasing number that identifies the entry. Useful as key when rendering the entries.
+    pub id: usize,
+    /// The console method that has been called
+    pub level: ConsoleLevel,
+    /// The arguments of the call formatted and joined by spaces
+    pub message: String,
+    /// When the message has been written in milliseconds since the Unix epoch
+    pub timestamp: f64,
+}
+
+/// Options for [`use_console_capture_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseConsoleCaptureOptions {
+    /// The console methods that are captured. Defaults to all of them.
+    levels: Vec<ConsoleLevel>,
+
+    /// Maximum number of entries that are kept. The oldest ones are removed first.
+    /// Defaults to `500`.
+    max_entries: usize,
+
+    /// Whether the messages are still written to the browser console. Defaults to `true`.
+    forward: bool,
+}
+
+impl Default for UseConsoleCaptureOptions {
+    fn default() -> Self {
+        Self {
+            levels: vec![
+                ConsoleLevel::Debug,
+                ConsoleLevel::Log,
+                ConsoleLevel::Info,
+                ConsoleLevel::Warn,
+                ConsoleLevel::Error,
+            ],
+            max_entries: 500,
+            forward: true,
+        }
+    }
+}
+
+/// Return type of [`use_console_capture`].
+pub struct UseConsoleCaptureReturn<ClearFn>
+where
+    ClearFn: Fn() + Clone,
+{
+    /// The captured messages, the oldest first
+    pub entries: Signal<Vec<ConsoleEntry>>,
+    /// Removes all captured messages
+    pub clear: ClearFn,
+}
@@ -0,0 +1,292 @@
+use crate::core::{ElementMaybeSignal, Size};
+use crate::{use_element_size_with_options, UseElementSizeOptions, UseElementSizeReturn};
+use default_struct_builder::DefaultBuilder;
+use leptos::logging::error;
+use leptos::*;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Reactive breakpoints of a container element.
+///
+/// Works like [`fn@crate::use_breakpoints`] but compares the breakpoints to the width of an
+/// element (measured with a `ResizeObserver`) instead of the width of the viewport. This way a
+/// component can adapt its layout to the space it actually gets, e.g. in a sidebar or a grid
+/// cell.
+///
+/// The returned [`ContainerBreakpoints`] are provided as context so that deeply nested
+/// children can get them with [`use_container_breakpoints_context`] without passing the
+/// element around. The closest container wins if containers are nested.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_container_breakpoints, use_container_breakpoints_context, BreakpointsTailwind, breakpoints_tailwind};
+/// #
+/// #[component]
+/// fn Card(children: Children) -> impl IntoView {
+///     let el = create_node_ref::<Div>();
+///
+///     use_container_breakpoints(el, breakpoints_tailwind());
+///
+///     view! { <div node_ref=el>{children()}</div> }
+/// }
+///
+/// #[component]
+/// fn CardContent() -> impl IntoView {
+///     let container = use_container_breakpoints_context::<BreakpointsTailwind>()
+///         .expect("inside of a Card");
+///
+///     let is_wide = container.ge(BreakpointsTailwind::Md);
+///
+///     view! {
+///         <Show when=is_wide fallback=|| view! { <p>"Compact"</p> }>
+///             <p>"Wide"</p>
+///         </Show>
+///     }
+/// }
+/// ```
+///
+/// `current` contains the largest breakpoint that the container has reached.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_container_breakpoints, breakpoints_tailwind};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let container = use_container_breakpoints(el, breakpoints_tailwind());
+///
+/// view! {
+///     <div node_ref=el>{move || format!("{:?}", container.current.get())}</div>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the width is always `initial_width` which defaults to `0.0`.
+pub fn use_container_breakpoints<El, T, K>(
+    target: El,
+    breakpoints: HashMap<K, u32>,
+) -> ContainerBreakpoints<K>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>> + Clone,
+    T: Into<web_sys::Element> + Clone + 'static,
+    K: Eq + Hash + Debug + Clone + 'static,
+{
+    use_container_breakpoints_with_options(
+        target,
+        breakpoints,
+        UseContainerBreakpointsOptions::default(),
+    )
+}
+
+/// Version of [`use_container_breakpoints`] that takes a `UseContainerBreakpointsOptions`. See [`use_container_breakpoints`] for how to use.
+pub fn use_container_breakpoints_with_options<El, T, K>(
+    target: El,
+    breakpoints: HashMap<K, u32>,
+    options: UseContainerBreakpointsOptions,
+) -> ContainerBreakpoints<K>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>> + Clone,
+    T: Into<web_sys::Element> + Clone + 'static,
+    K: Eq + Hash + Debug + Clone + 'static,
+{
+    let UseContainerBreakpointsOptions {
+        initial_width,
+        provide,
+    } = options;
+
+    let UseElementSizeReturn { width, .. } = use_element_size_with_options(
+        target,
+        UseElementSizeOptions::default()
+            .initial_size(Size {
+                width: initial_width,
+                height: 0.0,
+            })
+            .measure_on_mount(true),
+    );
+
+    let breakpoints = store_value(breakpoints);
+
+    let current = create_memo(move |_| {
+        let width = width.get();
+
+        breakpoints.with_value(|breakpoints| {
+            breakpoints
+                .iter()
+                .filter(|(_, value)| **value as f64 <= width)
+                .max_by_key(|(_, value)| **value)
+                .map(|(key, _)| key.clone())
+        })
+    });
+
+    let container = ContainerBreakpoints {
+        width,
+        current: current.into(),
+        breakpoints,
+    };
+
+    if provide {
+        provide_context(container);
+    }
+
+    container
+}
+
+/// Returns the [`ContainerBreakpoints`] of the closest container that has called
+/// [`use_container_breakpoints`] with breakpoints of type `K`. `None` if there is no such
+/// container.
+pub fn use_container_breakpoints_context<K>() -> Option<ContainerBreakpoints<K>>
+where
+    K: Eq + Hash + Debug + Clone + 'static,
+{
+    use_context::<ContainerBreakpoints<K>>()
+}
+
+/// Options for [`use_container_breakpoints_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseContainerBreakpointsOptions {
+    /// Width that is used until the container has been measured. Defaults to `0.0`.
+    initial_width: f64,
+
+    /// Provide the breakpoints as context for the children. Defaults to `true`.
+    provide: bool,
+}
+
+impl Default for UseContainerBreakpointsOptions {
+    fn default() -> Self {
+        Self {
+            initial_width: 0.0,
+            provide: true,
+        }
+    }
+}
+
+/// Return type of [`use_container_breakpoints`]. This is also provided as context.
+pub struct ContainerBreakpoints<K>
+where
+    K: 'static,
+{
+    /// Width of the container
+    pub width: Signal<f64>,
+
+    /// The largest breakpoint that is smaller than or equal to the width of the container.
+    /// `None` if the container is smaller than all breakpoints.
+    pub current: Signal<Option<K>>,
+
+    breakpoints: StoredValue<HashMap<K, u32>>,
+}
+
+impl<K> Clone for ContainerBreakpoints<K>
+where
+    K: 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for ContainerBreakpoints<K> where K: 'static {}
+
+impl<K> ContainerBreakpoints<K>
+where
+    K: Eq + Hash + Debug + Clone + 'static,
+{
+    fn compare(&self, key: K, cmp: fn(f64, f64) -> bool) -> Signal<bool> {
+        let width = self.width;
+
+        match self.value(&key) {
+            Some(value) => Signal::derive(move || cmp(width.get(), value)),
+            None => Signal::derive(|| false),
+        }
+    }
+
+    fn is_compare(&self, key: K, cmp: fn(f64, f64) -> bool) -> bool {
+        self.value(&key)
+            .map(|value| cmp(self.width.get_untracked(), value))
+            .unwrap_or_default()
+    }
+
+    fn value(&self, key: &K) -> Option<f64> {
+        let value = self
+            .breakpoints
+            .with_value(|breakpoints| breakpoints.get(key).copied());
+
+        if value.is_none() {
+            error!("Breakpoint \"{:?}\" not found", key);
+        }
+
+        value.map(f64::from)
+    }
+
+    /// Reactive check if `[container width]` > `key`
+    pub fn gt(&self, key: K) -> Signal<bool> {
+        self.compare(key, |width, value| width > value)
+    }
+
+    /// Reactive check if `[container width]` >= `key`
+    pub fn ge(&self, key: K) -> Signal<bool> {
+        self.compare(key, |width, value| width >= value)
+    }
+
+    /// Reactive check if `[container width]` < `key`
+    pub fn lt(&self, key: K) -> Signal<bool> {
+        self.compare(key, |width, value| width < value)
+    }
+
+    /// Reactive check if `[container width]` <= `key`
+    pub fn le(&self, key: K) -> Signal<bool> {
+        self.compare(key, |width, value| width <= value)
+    }
+
+    /// Static check if `[container width]` > `key`
+    pub fn is_gt(&self, key: K) -> bool {
+        self.is_compare(key, |width, value| width > value)
+    }
+
+    /// Static check if `[container width]` >= `key`
+    pub fn is_ge(&self, key: K) -> bool {
+        self.is_compare(key, |width, value| width >= value)
+    }
+
+    /// Static check if `[container width]` < `key`
+    pub fn is_lt(&self, key: K) -> bool {
+        self.is_compare(key, |width, value| width < value)
+    }
+
+    /// Static check if `[container width]` <= `key`
+    pub fn is_le(&self, key: K) -> bool {
+        self.is_compare(key, |width, value| width <= value)
+    }
+
+    /// Reactive check if `min_key` <= `[container width]` < `max_key`
+    pub fn between(&self, min_key: K, max_key: K) -> Signal<bool> {
+        let width = self.width;
+
+        match (self.value(&min_key), self.value(&max_key)) {
+            (Some(min), Some(max)) => Signal::derive(move || {
+                let width = width.get();
+                width >= min && width < max
+            }),
+            _ => Signal::derive(|| false),
+        }
+    }
+
+    /// Static check if `min_key` <= `[container width]` < `max_key`
+    pub fn is_between(&self, min_key: K, max_key: K) -> bool {
+        match (self.value(&min_key), self.value(&max_key)) {
+            (Some(min), Some(max)) => {
+                let width = self.width.get_untracked();
+                width >= min && width < max
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,284 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ElementMaybeSignal, Position};
+use crate::{
+    on_click_outside, use_element_size, use_event_listener, use_event_listener_with_options,
+    use_window, use_window_size, UseElementSizeReturn, UseEventListenerOptions,
+    UseWindowSizeReturn,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{contextmenu, keydown, pointercancel, pointerdown, pointermove, pointerup};
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use std::time::Duration;
+use web_sys::PointerEvent;
+
+/// Custom context menu for an element.
+///
+/// Right clicking the `target` (or pressing the context menu key) opens the menu at the
+/// position of the pointer. On touch devices a long press opens it. The position is moved
+/// so that the `menu` stays inside of the window.
+///
+/// The menu is closed by clicking outside of it or by pressing `Escape`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_context_menu, UseContextMenuReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let target = create_node_ref::<Div>();
+/// let menu = create_node_ref::<Div>();
+///
+/// let UseContextMenuReturn {
+///     is_open,
+///     style,
+///     close,
+///     ..
+/// } = use_context_menu(target, menu);
+///
+/// view! {
+///     <div node_ref=target>"Right click me"</div>
+///
+///     <div node_ref=menu style=style class:hidden=move || !is_open.get()>
+///         <button on:click=move |_| close()>"Copy"</button>
+///         <button on:click=move |_| close()>"Paste"</button>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// The `menu` has to be rendered all the time so its size can be measured. Hide it with CSS
+/// while `is_open` is `false`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the menu is always closed.
+pub fn use_context_menu<El, T, MenuEl, MenuT>(
+    target: El,
+    menu: MenuEl,
+) -> UseContextMenuReturn<impl Fn(Position) + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+    MenuEl: Into<ElementMaybeSignal<MenuT, web_sys::Element>> + Clone,
+    MenuT: Into<web_sys::Element> + Clone + 'static,
+{
+    use_context_menu_with_options(target, menu, UseContextMenuOptions::default())
+}
+
+/// Version of [`use_context_menu`] that takes a `UseContextMenuOptions`. See [`use_context_menu`] for how to use.
+pub fn use_context_menu_with_options<El, T, MenuEl, MenuT>(
+    target: El,
+    menu: MenuEl,
+    options: UseContextMenuOptions,
+) -> UseContextMenuReturn<impl Fn(Position) + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+    MenuEl: Into<ElementMaybeSignal<MenuT, web_sys::Element>> + Clone,
+    MenuT: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseContextMenuOptions {
+        long_press_delay,
+        long_press_tolerance,
+    } = options;
+
+    let target = target.into();
+
+    let (position, set_position) = create_signal(None::<Position>);
+
+    let UseWindowSizeReturn {
+        width: window_width,
+        height: window_height,
+    } = use_window_size();
+    let UseElementSizeReturn {
+        width: menu_width,
+        height: menu_height,
+    } = use_element_size(menu.clone());
+
+    let open = move |position: Position| set_position.set(Some(position));
+
+    let close = move || {
+        if position.get_untracked().is_some() {
+            set_position.set(None);
+        }
+    };
+
+    let long_press = store_value(None::<(TimeoutHandle, Position)>);
+
+    // the click that ends the long press that opened the menu must not close it again
+    let ignore_next_click = store_value(false);
+
+    let cancel_long_press = move || {
+        if let Some((handle, _)) = long_press.try_update_value(Option::take).flatten() {
+            handle.clear();
+        }
+    };
+
+    let _ = use_event_listener(target.clone(), contextmenu, move |event| {
+        event.prevent_default();
+        cancel_long_press();
+
+        open(Position {
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+        });
+    });
+
+    if let Some(delay) = long_press_delay {
+        let _ = use_event_listener(target.clone(), pointerdown, move |event: PointerEvent| {
+            if event.pointer_type() != "touch" {
+                return;
+            }
+
+            cancel_long_press();
+
+            let start = Position {
+                x: event.client_x() as f64,
+                y: event.client_y() as f64,
+            };
+
+            if let Ok(handle) = set_timeout_with_handle(
+                move || {
+                    long_press.try_update_value(|long_press| *long_press = None);
+
+                    if position.try_with_untracked(Option::is_none) == Some(true) {
+                        ignore_next_click.try_update_value(|ignore| *ignore = true);
+                        open(start);
+                    }
+                },
+                Duration::from_millis(delay),
+            ) {
+                long_press.set_value(Some((handle, start)));
+            }
+        });
+
+        let _ = use_event_listener(target.clone(), pointermove, move |event: PointerEvent| {
+            let moved_too_far = long_press.with_value(|long_press| {
+                long_press.as_ref().is_some_and(|(_, start)| {
+                    (event.client_x() as f64 - start.x).hypot(event.client_y() as f64 - start.y)
+                        > long_press_tolerance
+                })
+            });
+
+            if moved_too_far {
+                cancel_long_press();
+            }
+        });
+
+        let _ = use_event_listener(target.clone(), pointerup, move |_| cancel_long_press());
+        let _ = use_event_listener(target, pointercancel, move |_| cancel_long_press());
+
+        on_cleanup(cancel_long_press);
+    }
+
+    // a new interaction starts so a pending ignored click won't come anymore
+    let _ = use_event_listener_with_options(
+        use_window(),
+        pointerdown,
+        move |_| ignore_next_click.set_value(false),
+        UseEventListenerOptions::default()
+            .capture(true)
+            .passive(true),
+    );
+
+    let menu: ElementMaybeSignal<MenuT, web_sys::Element> = menu.into();
+    let _ = on_click_outside(
+        Signal::derive(move || {
+            menu.get().map(|menu| {
+                let menu: web_sys::Element = menu.into();
+                web_sys::EventTarget::from(menu)
+            })
+        }),
+        move |_| {
+            if ignore_next_click.get_value() {
+                ignore_next_click.set_value(false);
+            } else {
+                close();
+            }
+        },
+    );
+
+    let _ = use_event_listener(use_window(), keydown, move |event| {
+        if event.key() == "Escape" && position.get_untracked().is_some() {
+            event.prevent_default();
+            close();
+        }
+    });
+
+    // keep the menu inside of the window
+    let clamped = Signal::derive(move || {
+        position.get().map(|position| Position {
+            x: position
+                .x
+                .min(window_width.get() - menu_width.get())
+                .max(0.0),
+            y: position
+                .y
+                .min(window_height.get() - menu_height.get())
+                .max(0.0),
+        })
+    });
+
+    UseContextMenuReturn {
+        is_open: Signal::derive(move || position.with(Option::is_some)),
+        position: clamped,
+        style: Signal::derive(move || {
+            let position = clamped.get().unwrap_or_default();
+            format!(
+                "position: fixed; left: {}px; top: {}px;",
+                position.x, position.y
+            )
+        }),
+        open,
+        close,
+    }
+}
+
+/// Options for [`use_context_menu_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseContextMenuOptions {
+    /// Time in ms a touch has to be held down to open the menu. `None` disables opening the menu
+    /// with a long press. Defaults to `Some(500)`.
+    #[builder(into)]
+    long_press_delay: Option<u64>,
+
+    /// Distance in pixels a touch can move before the long press is canceled. Defaults to `10.0`.
+    long_press_tolerance: f64,
+}
+
+impl Default for UseContextMenuOptions {
+    fn default() -> Self {
+        Self {
+            long_press_delay: Some(500),
+            long_press_tolerance: 10.0,
+        }
+    }
+}
+
+/// Return type of [`use_context_menu`].
+pub struct UseContextMenuReturn<OpenFn, CloseFn>
+where
+    OpenFn: Fn(Position) + Clone,
+    CloseFn: Fn() + Clone,
+{
+    /// `true` if the menu is open
+    pub is_open: Signal<bool>,
+
+    /// Position of the menu in client coordinates while it is open, moved so that it stays
+    /// inside of the window
+    pub position: Signal<Option<Position>>,
+
+    /// Style attribute for the menu, `"position: fixed; left: {x}px; top: {y}px;"`
+    pub style: Signal<String>,
+
+    /// Opens the menu at the given position in client coordinates
+    pub open: OpenFn,
+
+    /// Closes the menu
+    pub close: CloseFn,
+}
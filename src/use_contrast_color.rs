@@ -0,0 +1,174 @@
+use crate::core::Color;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+
+/// Reactive readable foreground color for a background color.
+///
+/// Parses the CSS color in `color` into a [`Color`] and picks the foreground color with enough
+/// [WCAG contrast](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) to be readable on it.
+/// It also derives a lighter and a darker shade, for example for hover states. See [`Color`]
+/// for the supported notations and for converting between hex, `rgb()`, `hsl()` and `oklch()`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_contrast_color, UseContrastColorReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (brand, set_brand) = create_signal("#3f51b5".to_string());
+///
+/// let UseContrastColorReturn {
+///     foreground, darker, ..
+/// } = use_contrast_color(brand);
+///
+/// let style = move || {
+///     format!(
+///         "background: {}; color: {}; border-color: {}",
+///         brand.get(),
+///         foreground.get().map(|c| c.to_string()).unwrap_or_default(),
+///         darker.get().map(|c| c.to_string()).unwrap_or_default(),
+///     )
+/// };
+///
+/// view! {
+///     <input type="color" prop:value=brand on:input=move |e| set_brand.set(event_target_value(&e)) />
+///     <button style=style>"Readable"</button>
+/// }
+/// # }
+/// ```
+///
+/// Colors that can't be parsed result in `None`.
+///
+/// ## Generating Themes
+///
+/// Together with [`fn@crate::use_color_mode`] you can derive the colors of a theme from a single
+/// brand color.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_color_mode, use_contrast_color_with_options, ColorMode, UseColorModeReturn,
+/// #     UseContrastColorOptions, UseContrastColorReturn,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseColorModeReturn { mode, .. } = use_color_mode();
+///
+/// let UseContrastColorReturn {
+///     color,
+///     lighter,
+///     foreground,
+///     ..
+/// } = use_contrast_color_with_options(
+///     "oklch(0.55 0.2 265)",
+///     UseContrastColorOptions::default().shade_amount(0.2),
+/// );
+///
+/// let theme = move || {
+///     let primary = match mode.get() {
+///         ColorMode::Dark => lighter.get(),
+///         _ => color.get(),
+///     };
+///
+///     format!(
+///         "--primary: {}; --on-primary: {}",
+///         primary.map(|c| c.to_oklch_string()).unwrap_or_default(),
+///         foreground.get().map(|c| c.to_oklch_string()).unwrap_or_default(),
+///     )
+/// };
+///
+/// view! { <main style=theme>"..."</main> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// This works the same on the server.
+pub fn use_contrast_color(color: impl Into<MaybeSignal<String>>) -> UseContrastColorReturn {
+    use_contrast_color_with_options(color, UseContrastColorOptions::default())
+}
+
+/// Version of [`use_contrast_color`] that takes a `UseContrastColorOptions`. See [`use_contrast_color`] for how to use.
+pub fn use_contrast_color_with_options(
+    color: impl Into<MaybeSignal<String>>,
+    options: UseContrastColorOptions,
+) -> UseContrastColorReturn {
+    let UseContrastColorOptions {
+        candidates,
+        min_contrast,
+        shade_amount,
+    } = options;
+
+    let color = color.into();
+
+    let parsed = create_memo(move |_| color.with(|color| color.parse::<Color>().ok()));
+
+    let foreground = create_memo(move |_| {
+        let background = parsed.get()?;
+
+        candidates
+            .iter()
+            .find(|candidate| candidate.contrast_ratio(&background) >= min_contrast)
+            .or_else(|| {
+                candidates.iter().max_by(|a, b| {
+                    a.contrast_ratio(&background)
+                        .total_cmp(&b.contrast_ratio(&background))
+                })
+            })
+            .copied()
+    });
+
+    UseContrastColorReturn {
+        color: parsed.into(),
+        foreground: foreground.into(),
+        contrast_ratio: Signal::derive(move || {
+            Some(parsed.get()?.contrast_ratio(&foreground.get()?))
+        }),
+        lighter: Signal::derive(move || parsed.get().map(|color| color.lighten(shade_amount))),
+        darker: Signal::derive(move || parsed.get().map(|color| color.darken(shade_amount))),
+    }
+}
+
+/// Options for [`use_contrast_color_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseContrastColorOptions {
+    /// The colors to choose the foreground from in the order of preference. The first one
+    /// that reaches `min_contrast` is picked. If none does, the one with the highest contrast is
+    /// picked. Defaults to `[Color::BLACK, Color::WHITE]`.
+    candidates: Vec<Color>,
+
+    /// Contrast ratio the foreground color should at least have. Defaults to `4.5` which is
+    /// what WCAG AA requires for normal text.
+    min_contrast: f64,
+
+    /// By how much the HSL lightness of `lighter` and `darker` differs from the color. Between
+    /// `0.0` and `1.0`. Defaults to `0.1`.
+    shade_amount: f64,
+}
+
+impl Default for UseContrastColorOptions {
+    fn default() -> Self {
+        Self {
+            candidates: vec![Color::BLACK, Color::WHITE],
+            min_contrast: 4.5,
+            shade_amount: 0.1,
+        }
+    }
+}
+
+/// Return type of [`use_contrast_color`].
+pub struct UseContrastColorReturn {
+    /// The parsed color or `None` if it's invalid
+    pub color: Signal<Option<Color>>,
+    /// Readable color for text on top of `color`
+    pub foreground: Signal<Option<Color>>,
+    /// Contrast ratio between `color` and `foreground`
+    pub contrast_ratio: Signal<Option<f64>>,
+    /// `color` with increased lightness
+    pub lighter: Signal<Option<Color>>,
+    /// `color` with decreased lightness
+    pub darker: Signal<Option<Color>>,
+}
@@ -0,0 +1,187 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use crate::use_event_listener;
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+
+/// Collect [Content Security Policy](https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP) violations
+/// reported by the browser through the
+/// [`securitypolicyviolation`](https://developer.mozilla.org/en-US/docs/Web/API/Element/securitypolicyviolation_event) event.
+///
+/// The violations are stored in a reactive buffer so you can display them in an in-app console
+/// or forward them to your telemetry via the `on_violation` callback.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_csp_violation, UseCspViolationReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseCspViolationReturn { violations, clear } = use_csp_violation();
+///
+/// view! {
+///     <ul>
+///         <For each=move || violations.get() key=|v| v.timestamp as u64 let:violation>
+///             <li>{violation.effective_directive} " blocked " {violation.blocked_uri}</li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// You can provide a reporter callback and limit the size of the buffer.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::logging::log;
+/// # use leptos_use::{use_csp_violation_with_options, UseCspViolationOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let violations = use_csp_violation_with_options(
+///     UseCspViolationOptions::default()
+///         .max_entries(20)
+///         .on_violation(|violation| log!("{:?}", violation)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the buffer always stays empty and `clear` does nothing.
+///
+/// ## See also
+///
+/// - [`use_error_reporting`]
+pub fn use_csp_violation() -> UseCspViolationReturn<impl Fn() + Clone> {
+    use_csp_violation_with_options(UseCspViolationOptions::default())
+}
+
+/// Version of [`use_csp_violation`] that takes a `UseCspViolationOptions`. See [`use_csp_violation`] for how to use.
+pub fn use_csp_violation_with_options(
+    options: UseCspViolationOptions,
+) -> UseCspViolationReturn<impl Fn() + Clone> {
+    let UseCspViolationOptions {
+        max_entries,
+        on_violation,
+    } = options;
+
+    let (violations, set_violations) = create_signal(Vec::<CspViolation>::new());
+
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let _ = use_event_listener(
+            document(),
+            ev::Custom::<web_sys::SecurityPolicyViolationEvent>::new("securitypolicyviolation"),
+            move |event| {
+                let violation = CspViolation::from(event);
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_violation(&violation);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                set_violations.update(|violations| {
+                    violations.push(violation);
+
+                    if violations.len() > max_entries {
+                        let overflow = violations.len() - max_entries;
+                        violations.drain(..overflow);
+                    }
+                });
+            },
+        );
+    }}
+
+    UseCspViolationReturn {
+        violations: violations.into(),
+        clear: move || set_violations.update(|violations| violations.clear()),
+    }
+}
+
+/// A single CSP violation. This is a plain copy of the relevant fields of a
+/// `web_sys::SecurityPolicyViolationEvent` so it can be stored, cloned and sent elsewhere.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CspViolation {
+    /// The URI of the resource that was blocked.
+    pub blocked_uri: String,
+    /// The directive whose enforcement caused the violation.
+    pub effective_directive: String,
+    /// The directive that was violated.
+    pub violated_directive: String,
+    /// The policy that was violated.
+    pub original_policy: String,
+    /// The URI of the document in which the violation occurred.
+    pub document_uri: String,
+    /// The URI of the script that caused the violation, if any.
+    pub source_file: String,
+    /// The line number in `source_file` at which the violation occurred.
+    pub line_number: i32,
+    /// The column number in `source_file` at which the violation occurred.
+    pub column_number: i32,
+    /// A sample of the resource that caused the violation, if the policy contains `'report-sample'`.
+    pub sample: String,
+    /// `true` if the policy is enforced and `false` if it's only reported.
+    pub enforced: bool,
+    /// The timestamp of the event in milliseconds.
+    pub timestamp: f64,
+}
+
+impl From<web_sys::SecurityPolicyViolationEvent> for CspViolation {
+    fn from(event: web_sys::SecurityPolicyViolationEvent) -> Self {
+        Self {
+            blocked_uri: event.blocked_uri(),
+            effective_directive: event.effective_directive(),
+            violated_directive: event.violated_directive(),
+            original_policy: event.original_policy(),
+            document_uri: event.document_uri(),
+            source_file: event.source_file(),
+            line_number: event.line_number(),
+            column_number: event.column_number(),
+            sample: event.sample(),
+            enforced: event.disposition()
+                == web_sys::SecurityPolicyViolationEventDisposition::Enforce,
+            timestamp: event.time_stamp(),
+        }
+    }
+}
+
+/// Options for [`use_csp_violation_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseCspViolationOptions {
+    /// The maximum number of violations that are kept in the buffer. Older violations are
+    /// dropped first. Defaults to `100`.
+    max_entries: usize,
+
+    /// Called for every violation. Use this to forward violations to your telemetry.
+    on_violation: Rc<dyn Fn(&CspViolation)>,
+}
+
+impl Default for UseCspViolationOptions {
+    fn default() -> Self {
+        Self {
+            max_entries: 100,
+            on_violation: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_csp_violation`].
+pub struct UseCspViolationReturn<ClearFn>
+where
+    ClearFn: Fn() + Clone,
+{
+    /// The buffered violations, oldest first.
+    pub violations: Signal<Vec<CspViolation>>,
+
+    /// Empties the buffer.
+    pub clear: ClearFn,
+}
@@ -0,0 +1,283 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use crate::core::{ElementMaybeSignal, StorageType};
+use crate::{use_color_mode_with_options, ColorMode, UseColorModeOptions, UseColorModeReturn};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Reactive dark mode with auto data persistence.
+///
+/// This is a simpler interface to [`fn@crate::use_color_mode`] for the most common case of only
+/// having a light and a dark mode. Until the user explicitly chooses a mode it follows the
+/// preference of the system. Choosing the mode that the system prefers anyway resets it to
+/// following the system again so the choice is only persisted if it actually overrides the
+/// system.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_dark, UseDarkReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseDarkReturn { is_dark, toggle, .. } = use_dark();
+///
+/// view! {
+///     <button on:click=move |_| toggle()>
+///         {move || if is_dark.get() { "Dark" } else { "Light" }}
+///     </button>
+/// }
+/// # }
+/// ```
+///
+/// By default the class `dark` or `light` is set on the `<html>` element and its CSS property
+/// `color-scheme` is set accordingly so that scrollbars and form controls match the mode.
+/// Set `emit_color_scheme` to `false` to leave `color-scheme` alone.
+///
+/// ## Overrides
+///
+/// Some pages might only work in one of the modes. With the `force` option the mode can be
+/// overridden temporarily without changing the persisted choice of the user.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_dark_with_options, UseDarkOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (is_presentation, set_presentation) = create_signal(false);
+///
+/// let dark = use_dark_with_options(
+///     UseDarkOptions::default().force(Signal::derive(move || is_presentation.get().then_some(true))),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_dark` is `false` unless forced. Use `cookie_enabled` to render the chosen
+/// mode on the server as well. See [`fn@crate::use_color_mode`] for details.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_color_mode`]
+/// * [`fn@crate::use_preferred_dark`]
+pub fn use_dark() -> UseDarkReturn<impl Fn(bool) + Clone, impl Fn() + Clone, impl Fn() + Clone> {
+    use_dark_with_options(UseDarkOptions::default())
+}
+
+/// Version of [`use_dark`] that takes a `UseDarkOptions`. See [`use_dark`] for how to use.
+pub fn use_dark_with_options<El, T>(
+    options: UseDarkOptions<El, T>,
+) -> UseDarkReturn<impl Fn(bool) + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Clone,
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseDarkOptions {
+        target,
+        attribute,
+        force,
+        emit_color_scheme,
+        storage_key,
+        storage,
+        storage_enabled,
+        cookie_enabled,
+        transition_enabled,
+        ..
+    } = options;
+
+    let force: Signal<Option<bool>> = force.into();
+    let element: ElementMaybeSignal<T, web_sys::Element> = target.clone().into();
+
+    let to_mode = |dark: bool| {
+        if dark {
+            ColorMode::Dark
+        } else {
+            ColorMode::Light
+        }
+    };
+
+    // Applies the forced mode instead of `mode` if there is one
+    let default_handler = store_value(None::<Rc<dyn Fn(ColorMode)>>);
+    let apply = move |mode: ColorMode| {
+        let mode = force.get_untracked().map(to_mode).unwrap_or(mode);
+
+        if let Some(handler) = default_handler.get_value() {
+            handler(mode.clone());
+        }
+
+        #[cfg(not(feature = "ssr"))]
+        if emit_color_scheme {
+            if let Some(el) = element.get_untracked() {
+                let el: web_sys::Element = el.into();
+                let _ = el
+                    .unchecked_ref::<web_sys::HtmlElement>()
+                    .style()
+                    .set_property(
+                        "color-scheme",
+                        if mode == ColorMode::Dark {
+                            "dark"
+                        } else {
+                            "light"
+                        },
+                    );
+            }
+        }
+    };
+
+    let UseColorModeReturn {
+        store,
+        set_store,
+        system,
+        state,
+        ..
+    } = use_color_mode_with_options(
+        UseColorModeOptions::default()
+            .target(target)
+            .attribute(attribute)
+            .storage_key(storage_key.clone())
+            .storage(storage)
+            .storage_enabled(storage_enabled)
+            .cookie_name(storage_key)
+            .cookie_enabled(cookie_enabled)
+            .transition_enabled(transition_enabled)
+            .on_changed({
+                let apply = apply.clone();
+
+                move |mode: ColorMode, handler: Rc<dyn Fn(ColorMode)>| {
+                    default_handler.set_value(Some(handler));
+                    apply(mode);
+                }
+            }),
+    );
+
+    let _ = watch(
+        move || force.get(),
+        move |_, _, _| apply(state.get_untracked()),
+        false,
+    );
+
+    let is_dark = Signal::derive(move || {
+        force
+            .get()
+            .unwrap_or_else(|| state.get() == ColorMode::Dark)
+    });
+
+    let set_dark = move |dark: bool| {
+        let system_dark = system.get_untracked() == ColorMode::Dark;
+
+        set_store.set(if dark == system_dark {
+            ColorMode::Auto
+        } else {
+            to_mode(dark)
+        });
+    };
+
+    let toggle = move || set_dark(state.get_untracked() != ColorMode::Dark);
+
+    let reset = move || set_store.set(ColorMode::Auto);
+
+    UseDarkReturn {
+        is_dark,
+        is_system: Signal::derive(move || store.get() == ColorMode::Auto),
+        set_dark,
+        toggle,
+        reset,
+    }
+}
+
+/// Options for [`use_dark_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseDarkOptions<El, T>
+where
+    El: Clone,
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    /// Element that the mode will be applied to. Defaults to `"html"`.
+    target: El,
+
+    /// HTML attribute that is set to `"dark"` or `"light"` on the target element.
+    /// Defaults to `"class"`.
+    #[builder(into)]
+    attribute: String,
+
+    /// Overrides the mode while it is `Some(is_dark)` without changing the persisted choice.
+    /// Defaults to `None`.
+    #[builder(into)]
+    force: MaybeSignal<Option<bool>>,
+
+    /// If the CSS property `color-scheme` of the target element should be set to the mode.
+    /// Defaults to `true`.
+    emit_color_scheme: bool,
+
+    /// Key to persist the choice in storage or the name of the cookie.
+    /// Defaults to `"leptos-use-color-scheme"`.
+    #[builder(into)]
+    storage_key: String,
+
+    /// Storage type, can be `Local` or `Session` or custom. Defaults to `Local`.
+    storage: StorageType,
+
+    /// If the choice should be persisted in storage. Defaults to `true`.
+    storage_enabled: bool,
+
+    /// If the choice should be persisted in a cookie. See [`fn@crate::use_color_mode`].
+    /// Defaults to `false`.
+    cookie_enabled: bool,
+
+    /// If transitions on mode change are enabled. Defaults to `false`.
+    transition_enabled: bool,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl Default for UseDarkOptions<&'static str, web_sys::Element> {
+    fn default() -> Self {
+        Self {
+            target: "html",
+            attribute: "class".into(),
+            force: None.into(),
+            emit_color_scheme: true,
+            storage_key: "leptos-use-color-scheme".into(),
+            storage: StorageType::default(),
+            storage_enabled: true,
+            cookie_enabled: false,
+            transition_enabled: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Return type of [`use_dark`].
+pub struct UseDarkReturn<SetFn, ToggleFn, ResetFn>
+where
+    SetFn: Fn(bool) + Clone,
+    ToggleFn: Fn() + Clone,
+    ResetFn: Fn() + Clone,
+{
+    /// `true` if the dark mode is active
+    pub is_dark: Signal<bool>,
+
+    /// `true` if the mode follows the preference of the system
+    pub is_system: Signal<bool>,
+
+    /// Switches to dark (`true`) or light (`false`) mode and persists the choice. Choosing the
+    /// mode that the system prefers resets to following the system.
+    pub set_dark: SetFn,
+
+    /// Switches between dark and light mode
+    pub toggle: ToggleFn,
+
+    /// Follows the preference of the system again
+    pub reset: ResetFn,
+}
@@ -0,0 +1,409 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [`mediaDevices.enumerateDevices`](https://developer.mozilla.org/en-US/docs/Web/API/MediaDevices/enumerateDevices)
+/// listing the available cameras, microphones and speakers.
+///
+/// The lists are updated whenever a device is plugged in or removed.
+///
+/// Browsers only report the labels of the devices after the user has granted the permission to
+/// use a camera or microphone. Until then `label` is `None` and [`MediaDevice::display_label`]
+/// returns a generic name like `"Camera 1"`. Call `ensure_permissions` to ask for the
+/// permissions so the real labels are available.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_device_list, UseDeviceListReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseDeviceListReturn {
+///     video_inputs,
+///     has_permissions,
+///     ensure_permissions,
+///     ..
+/// } = use_device_list();
+///
+/// view! {
+///     <Show when=move || !has_permissions.get()>
+///         <button on:click={
+///             let ensure_permissions = ensure_permissions.clone();
+///             move |_| ensure_permissions()
+///         }>"Show camera names"</button>
+///     </Show>
+///     <select>
+///         <For each=move || video_inputs.get() key=|device| device.device_id.clone() let:device>
+///             <option value=device.device_id.clone()>{device.display_label()}</option>
+///         </For>
+///     </select>
+/// }
+/// # }
+/// ```
+///
+/// ## Audio Output
+///
+/// `select_output` lets an `<audio>` or `<video>` element play through one of the
+/// `audio_outputs` by calling [`setSinkId`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLMediaElement/setSinkId)
+/// on it. Errors are reported in `error`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Audio;
+/// # use leptos_use::{use_device_list, UseDeviceListReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let audio = create_node_ref::<Audio>();
+///
+/// let UseDeviceListReturn { audio_outputs, select_output, .. } = use_device_list();
+///
+/// view! {
+///     <audio node_ref=audio src="/ring.mp3" />
+///     <select on:change=move |ev| {
+///         if let Some(audio) = audio.get() {
+///             select_output(&audio, &event_target_value(&ev));
+///         }
+///     }>
+///         <For each=move || audio_outputs.get() key=|device| device.device_id.clone() let:device>
+///             <option value=device.device_id.clone()>{device.display_label()}</option>
+///         </For>
+///     </select>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server all lists are empty and the functions do nothing.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_user_media`]
+pub fn use_device_list() -> UseDeviceListReturn<
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn(&web_sys::HtmlMediaElement, &str) + Clone,
+> {
+    use_device_list_with_options(UseDeviceListOptions::default())
+}
+
+/// Version of [`use_device_list`] that takes a `UseDeviceListOptions`. See [`use_device_list`] for how to use.
+pub fn use_device_list_with_options(
+    options: UseDeviceListOptions,
+) -> UseDeviceListReturn<
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn(&web_sys::HtmlMediaElement, &str) + Clone,
+> {
+    let UseDeviceListOptions {
+        request_permissions,
+        video,
+        audio,
+    } = options;
+
+    let is_supported = use_supported(|| {
+        let navigator = window().navigator();
+        js!("mediaDevices" in &navigator)
+    });
+
+    let (devices, set_devices) = create_signal(Vec::<MediaDevice>::new());
+    let (selected_output, set_selected_output) = create_signal(None::<String>);
+    let (error, set_error) = create_signal(None::<JsValue>);
+
+    let update = move || {
+        #[cfg(not(feature = "ssr"))]
+        if is_supported.get_untracked() {
+            spawn_local(async move {
+                match enumerate_devices().await {
+                    Ok(list) => set_devices.set(list),
+                    Err(err) => set_error.set(Some(err)),
+                }
+            });
+        }
+    };
+
+    let ensure_permissions = {
+        let update = update.clone();
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            if is_supported.get_untracked() {
+                let update = update.clone();
+
+                spawn_local(async move {
+                    match request_media_permissions(video, audio).await {
+                        Ok(()) => update(),
+                        Err(err) => set_error.set(Some(err)),
+                    }
+                });
+            }
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    if is_supported.get_untracked() {
+        if let Ok(media_devices) = window().navigator().media_devices() {
+            let _ = use_event_listener(media_devices, Custom::<ev::Event>::new("devicechange"), {
+                let update = update.clone();
+                move |_| update()
+            });
+        }
+
+        if request_permissions {
+            ensure_permissions();
+        } else {
+            update();
+        }
+    }
+
+    let select_output = move |el: &web_sys::HtmlMediaElement, sink_id: &str| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let Some(set_sink_id) = js!(el["setSinkId"])
+                .ok()
+                .filter(|set_sink_id| set_sink_id.is_function())
+            else {
+                set_error.set(Some(JsValue::from_str(
+                    "setSinkId is not supported by this browser",
+                )));
+                return;
+            };
+
+            let promise = set_sink_id
+                .unchecked_into::<js_sys::Function>()
+                .call1(el, &JsValue::from_str(sink_id));
+
+            let sink_id = sink_id.to_string();
+
+            spawn_local(async move {
+                let result = match promise {
+                    Ok(promise) => js_fut!(promise.unchecked_into::<js_sys::Promise>())
+                        .await
+                        .map(|_| ()),
+                    Err(err) => Err(err),
+                };
+
+                match result {
+                    Ok(()) => set_selected_output.set(Some(sink_id)),
+                    Err(err) => set_error.set(Some(err)),
+                }
+            });
+        }
+    };
+
+    let of_kind = move |kind: MediaDeviceKind| {
+        Signal::derive(move || {
+            devices.with(|devices| {
+                devices
+                    .iter()
+                    .filter(|device| device.kind == kind)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+        })
+    };
+
+    UseDeviceListReturn {
+        is_supported,
+        devices: devices.into(),
+        video_inputs: of_kind(MediaDeviceKind::VideoInput),
+        audio_inputs: of_kind(MediaDeviceKind::AudioInput),
+        audio_outputs: of_kind(MediaDeviceKind::AudioOutput),
+        has_permissions: Signal::derive(move || {
+            devices.with(|devices| devices.iter().any(|device| device.label.is_some()))
+        }),
+        selected_output: selected_output.into(),
+        error: error.into(),
+        update,
+        ensure_permissions,
+        select_output,
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn enumerate_devices() -> Result<Vec<MediaDevice>, JsValue> {
+    let media_devices = window().navigator().media_devices()?;
+    let list = js_fut!(media_devices.enumerate_devices()?).await?;
+
+    let mut devices = list
+        .unchecked_into::<js_sys::Array>()
+        .iter()
+        .filter_map(|info| {
+            let info = info.unchecked_into::<web_sys::MediaDeviceInfo>();
+
+            let kind = match info.kind() {
+                web_sys::MediaDeviceKind::Videoinput => MediaDeviceKind::VideoInput,
+                web_sys::MediaDeviceKind::Audioinput => MediaDeviceKind::AudioInput,
+                web_sys::MediaDeviceKind::Audiooutput => MediaDeviceKind::AudioOutput,
+                _ => return None,
+            };
+
+            Some(MediaDevice {
+                device_id: info.device_id(),
+                group_id: info.group_id(),
+                kind,
+                label: Some(info.label()).filter(|label| !label.is_empty()),
+                index: 0,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for kind in [
+        MediaDeviceKind::VideoInput,
+        MediaDeviceKind::AudioInput,
+        MediaDeviceKind::AudioOutput,
+    ] {
+        for (index, device) in devices
+            .iter_mut()
+            .filter(|device| device.kind == kind)
+            .enumerate()
+        {
+            device.index = index;
+        }
+    }
+
+    Ok(devices)
+}
+
+/// Asks for the permission to use a camera and/or microphone by opening a stream that is
+/// stopped again right away.
+#[cfg(not(feature = "ssr"))]
+async fn request_media_permissions(video: bool, audio: bool) -> Result<(), JsValue> {
+    let media_devices = window().navigator().media_devices()?;
+
+    let mut constraints = web_sys::MediaStreamConstraints::new();
+    constraints.video(&JsValue::from(video));
+    constraints.audio(&JsValue::from(audio));
+
+    let stream = js_fut!(media_devices.get_user_media_with_constraints(&constraints)?).await?;
+
+    for track in stream.unchecked_into::<web_sys::MediaStream>().get_tracks() {
+        track.unchecked_into::<web_sys::MediaStreamTrack>().stop();
+    }
+
+    Ok(())
+}
+
+/// Options for [`use_device_list_with_options`].
+#[derive(DefaultBuilder, Clone, Copy, Debug)]
+pub struct UseDeviceListOptions {
+    /// Ask for the permissions right away instead of waiting for `ensure_permissions` to be
+    /// called. Defaults to `false`.
+    request_permissions: bool,
+
+    /// Ask for the permission to use a camera in `ensure_permissions`. Defaults to `true`.
+    video: bool,
+
+    /// Ask for the permission to use a microphone in `ensure_permissions`. Defaults to `true`.
+    audio: bool,
+}
+
+impl Default for UseDeviceListOptions {
+    fn default() -> Self {
+        Self {
+            request_permissions: false,
+            video: true,
+            audio: true,
+        }
+    }
+}
+
+/// Return type of [`use_device_list`].
+pub struct UseDeviceListReturn<UpdateFn, EnsurePermissionsFn, SelectOutputFn>
+where
+    UpdateFn: Fn() + Clone,
+    EnsurePermissionsFn: Fn() + Clone,
+    SelectOutputFn: Fn(&web_sys::HtmlMediaElement, &str) + Clone,
+{
+    /// Whether `navigator.mediaDevices` is supported
+    pub is_supported: Signal<bool>,
+
+    /// All devices in the order reported by the browser
+    pub devices: Signal<Vec<MediaDevice>>,
+
+    /// Cameras
+    pub video_inputs: Signal<Vec<MediaDevice>>,
+
+    /// Microphones
+    pub audio_inputs: Signal<Vec<MediaDevice>>,
+
+    /// Speakers and headphones
+    pub audio_outputs: Signal<Vec<MediaDevice>>,
+
+    /// `true` if the browser reports the labels of the devices which means a permission has
+    /// been granted
+    pub has_permissions: Signal<bool>,
+
+    /// Id of the audio output that has last been selected successfully with `select_output`
+    pub selected_output: Signal<Option<String>>,
+
+    /// The last error of enumerating the devices, asking for permissions or selecting an output
+    pub error: Signal<Option<JsValue>>,
+
+    /// Enumerates the devices again
+    pub update: UpdateFn,
+
+    /// Asks for the permissions to use a camera and/or microphone and enumerates the devices
+    /// again so the labels are available
+    pub ensure_permissions: EnsurePermissionsFn,
+
+    /// Lets the given media element play through the audio output with the given device id
+    pub select_output: SelectOutputFn,
+}
+
+/// A media device of [`use_device_list`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MediaDevice {
+    /// Id of the device. Pass this as `deviceId` constraint or to `select_output`.
+    pub device_id: String,
+
+    /// Devices that belong to the same physical device (like the microphone and speaker of a
+    /// headset) share the group id
+    pub group_id: String,
+
+    /// Kind of the device
+    pub kind: MediaDeviceKind,
+
+    /// Label of the device. `None` until a permission has been granted.
+    pub label: Option<String>,
+
+    /// Index of the device among the devices of the same kind
+    pub index: usize,
+}
+
+impl MediaDevice {
+    /// The label of the device or, as long as there is no label, a generic name like
+    /// `"Microphone 2"`.
+    pub fn display_label(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => {
+                let name = match self.kind {
+                    MediaDeviceKind::VideoInput => "Camera",
+                    MediaDeviceKind::AudioInput => "Microphone",
+                    MediaDeviceKind::AudioOutput => "Speaker",
+                };
+
+                format!("{name} {}", self.index + 1)
+            }
+        }
+    }
+}
+
+/// Kind of a [`MediaDevice`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MediaDeviceKind {
+    /// A camera
+    VideoInput,
+    /// A microphone
+    AudioInput,
+    /// A speaker or headphones
+    AudioOutput,
+}
@@ -0,0 +1,46 @@
+use leptos::*;
+
+/// Reactive [`navigator.deviceMemory`](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/deviceMemory)
+///
+/// Approximate amount of RAM of the device in gigabytes. Browsers round it down to a power of
+/// two and cap it (usually at `8.0`) to prevent fingerprinting.
+///
+/// > NOTE: the value doesn't change while the page is open. The signal is only there for
+/// > consistency with the other hooks.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_device_memory;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let memory = use_device_memory();
+///
+/// view! {
+///     <p>{move || memory.get().map(|gb| format!("{gb} GB")).unwrap_or("Unknown".to_string())}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server and in browsers that don't support it (Firefox and Safari) this returns a
+/// `Signal` that is always `None`.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_hardware_concurrency`]
+/// * [`fn@crate::use_device_tier`]
+pub fn use_device_memory() -> Signal<Option<f64>> {
+    #[cfg(feature = "ssr")]
+    let memory = None;
+
+    #[cfg(not(feature = "ssr"))]
+    let memory = js_sys::Reflect::get(&window().navigator(), &"deviceMemory".into())
+        .ok()
+        .and_then(|memory| memory.as_f64());
+
+    Signal::derive(move || memory)
+}
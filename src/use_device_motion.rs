@@ -0,0 +1,180 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::use_device_orientation::use_device_sensor_permission;
+use crate::PermissionState;
+use cfg_if::cfg_if;
+use leptos::*;
+
+/// Reactive [DeviceMotionEvent](https://developer.mozilla.org/en-US/docs/Web/API/DeviceMotionEvent).
+/// Provides information about the acceleration and the rotation rate of the device running
+/// the web page.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_device_motion, UseDeviceMotionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseDeviceMotionReturn {
+///     acceleration,
+///     acceleration_including_gravity,
+///     rotation_rate,
+///     ..
+/// } = use_device_motion();
+///
+/// view! {
+///     <p>"Acceleration along the x axis: " {move || acceleration.get().x}</p>
+///     <p>"Rotation around the z axis: " {move || rotation_rate.get().alpha}</p>
+/// }
+/// # }
+/// ```
+///
+/// ### Permission
+///
+/// Like [`fn@crate::use_device_orientation`] Safari on iOS requires the user to grant permission
+/// before any motion events are delivered. Dispatch `request_permission` in reaction to a user
+/// interaction while `permission` is `PermissionState::Prompt`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_device_motion, PermissionState, UseDeviceMotionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseDeviceMotionReturn {
+///     permission,
+///     request_permission,
+///     ..
+/// } = use_device_motion();
+///
+/// view! {
+///     <Show when=move || permission.get() == PermissionState::Prompt>
+///         <button on:click=move |_| request_permission.dispatch(())>"Enable motion"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this function returns values as if the motion sensors were not supported by
+/// the device.
+pub fn use_device_motion() -> UseDeviceMotionReturn {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        let is_supported = Signal::derive(|| false);
+        let acceleration = DeviceMotionAcceleration::default;
+        let acceleration_including_gravity = DeviceMotionAcceleration::default;
+        let rotation_rate = DeviceMotionRotationRate::default;
+        let interval = || 0.0;
+    } else {
+        use crate::{use_event_listener_with_options, UseEventListenerOptions, use_supported, js};
+        use leptos::ev::devicemotion;
+
+        let is_supported = use_supported(|| js!("DeviceMotionEvent" in &window()));
+        let (acceleration, set_acceleration) = create_signal(DeviceMotionAcceleration::default());
+        let (acceleration_including_gravity, set_acceleration_including_gravity) =
+            create_signal(DeviceMotionAcceleration::default());
+        let (rotation_rate, set_rotation_rate) = create_signal(DeviceMotionRotationRate::default());
+        let (interval, set_interval) = create_signal(0.0);
+
+        if is_supported.get_untracked() {
+            let cleanup = use_event_listener_with_options(
+                window(),
+                devicemotion,
+                move |event: web_sys::DeviceMotionEvent| {
+                    set_acceleration.set(event.acceleration().into());
+                    set_acceleration_including_gravity
+                        .set(event.acceleration_including_gravity().into());
+                    set_rotation_rate.set(event.rotation_rate().into());
+                    set_interval.set(event.interval().unwrap_or_default());
+                },
+                UseEventListenerOptions::default()
+                    .capture(false)
+                    .passive(true)
+                    .once(false),
+            );
+
+            leptos::on_cleanup(cleanup);
+        }
+    }}
+
+    let (permission, request_permission) =
+        use_device_sensor_permission("DeviceMotionEvent", is_supported);
+
+    UseDeviceMotionReturn {
+        is_supported,
+        acceleration: acceleration.into(),
+        acceleration_including_gravity: acceleration_including_gravity.into(),
+        rotation_rate: rotation_rate.into(),
+        interval: interval.into(),
+        permission,
+        request_permission,
+    }
+}
+
+/// Acceleration of the device in m/s² along the three axes. `None` if the device can't provide it.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct DeviceMotionAcceleration {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub z: Option<f64>,
+}
+
+impl From<Option<web_sys::DeviceAcceleration>> for DeviceMotionAcceleration {
+    fn from(acceleration: Option<web_sys::DeviceAcceleration>) -> Self {
+        acceleration
+            .map(|acceleration| Self {
+                x: acceleration.x(),
+                y: acceleration.y(),
+                z: acceleration.z(),
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Rotation rate of the device in degrees per second around the three axes. `None` if the
+/// device can't provide it.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct DeviceMotionRotationRate {
+    /// Around the z axis
+    pub alpha: Option<f64>,
+    /// Around the x axis
+    pub beta: Option<f64>,
+    /// Around the y axis
+    pub gamma: Option<f64>,
+}
+
+impl From<Option<web_sys::DeviceRotationRate>> for DeviceMotionRotationRate {
+    fn from(rotation_rate: Option<web_sys::DeviceRotationRate>) -> Self {
+        rotation_rate
+            .map(|rotation_rate| Self {
+                alpha: rotation_rate.alpha(),
+                beta: rotation_rate.beta(),
+                gamma: rotation_rate.gamma(),
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Return type of [`use_device_motion`].
+#[derive(Clone)]
+pub struct UseDeviceMotionReturn {
+    /// Whether the browser supports motion events
+    pub is_supported: Signal<bool>,
+    /// Acceleration of the device without the effect of gravity
+    pub acceleration: Signal<DeviceMotionAcceleration>,
+    /// Acceleration of the device including the effect of gravity
+    pub acceleration_including_gravity: Signal<DeviceMotionAcceleration>,
+    /// Rotation rate of the device
+    pub rotation_rate: Signal<DeviceMotionRotationRate>,
+    /// Interval in milliseconds at which the data is obtained from the device
+    pub interval: Signal<f64>,
+    /// Whether the motion events are delivered. Safari on iOS requires the user to grant
+    /// permission first. `PermissionState::Unknown` if not supported.
+    pub permission: Signal<PermissionState>,
+    /// Asks the user for permission to receive motion events if required. Has to be
+    /// dispatched in reaction to a user interaction.
+    pub request_permission: Action<(), PermissionState>,
+}
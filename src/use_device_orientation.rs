@@ -1,3 +1,6 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::PermissionState;
 use cfg_if::cfg_if;
 use leptos::*;
 
@@ -23,12 +26,40 @@ use leptos::*;
 ///     alpha,
 ///     beta,
 ///     gamma,
+///     ..
 /// } = use_device_orientation();
 /// #
 /// # view! { }
 /// # }
 /// ```
 ///
+/// ### Permission
+///
+/// Safari on iOS only delivers orientation events after the user has granted permission. The
+/// prompt can only be shown in reaction to a user interaction so dispatch `request_permission`
+/// in an event handler. In other browsers no permission is needed and `permission` is
+/// `PermissionState::Granted` right away.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_device_orientation, PermissionState, UseDeviceOrientationReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseDeviceOrientationReturn {
+///     permission,
+///     request_permission,
+///     ..
+/// } = use_device_orientation();
+///
+/// view! {
+///     <Show when=move || permission.get() == PermissionState::Prompt>
+///         <button on:click=move |_| request_permission.dispatch(())>"Enable orientation"</button>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this function returns values as if the orientation
@@ -70,15 +101,95 @@ pub fn use_device_orientation() -> UseDeviceOrientationReturn {
         }
     }}
 
+    let (permission, request_permission) =
+        use_device_sensor_permission("DeviceOrientationEvent", is_supported);
+
     UseDeviceOrientationReturn {
         is_supported,
         absolute: absolute.into(),
         alpha: alpha.into(),
         beta: beta.into(),
         gamma: gamma.into(),
+        permission,
+        request_permission,
     }
 }
 
+/// Permission flow of the device sensor events that is shared with [`fn@crate::use_device_motion`].
+///
+/// `event_class` is the name of the event class that has the static `requestPermission` method
+/// in Safari on iOS. Browsers without this method don't require any permission.
+pub(crate) fn use_device_sensor_permission(
+    event_class: &'static str,
+    is_supported: Signal<bool>,
+) -> (Signal<PermissionState>, Action<(), PermissionState>) {
+    let (permission, set_permission) = create_signal(PermissionState::Unknown);
+
+    #[cfg(not(feature = "ssr"))]
+    if is_supported.get_untracked() {
+        set_permission.set(if request_permission_fn(event_class).is_some() {
+            PermissionState::Prompt
+        } else {
+            PermissionState::Granted
+        });
+    }
+
+    let request_permission = create_action(move |(): &()| {
+        // Safari only shows the prompt if this is called synchronously by a user interaction
+        // which is why it's not done inside the future.
+        #[cfg(not(feature = "ssr"))]
+        let request =
+            request_permission_fn(event_class).map(|(class, request)| request.call0(&class));
+
+        async move {
+            #[cfg(feature = "ssr")]
+            return PermissionState::Unknown;
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                use crate::js_fut;
+                use wasm_bindgen::JsCast;
+
+                let state = match request {
+                    None if is_supported.get_untracked() => PermissionState::Granted,
+                    None => PermissionState::Unknown,
+                    Some(Ok(promise)) => {
+                        match js_fut!(promise.unchecked_into::<js_sys::Promise>()).await {
+                            Ok(state) => match state.as_string().as_deref() {
+                                Some("granted") => PermissionState::Granted,
+                                Some("denied") => PermissionState::Denied,
+                                _ => PermissionState::Prompt,
+                            },
+                            // rejected if not called by a user interaction
+                            Err(_) => PermissionState::Prompt,
+                        }
+                    }
+                    Some(Err(_)) => PermissionState::Prompt,
+                };
+
+                set_permission.set(state);
+                state
+            }
+        }
+    });
+
+    (permission.into(), request_permission)
+}
+
+/// The class `event_class` together with its static `requestPermission` method if it exists.
+#[cfg(not(feature = "ssr"))]
+fn request_permission_fn(event_class: &str) -> Option<(wasm_bindgen::JsValue, js_sys::Function)> {
+    use wasm_bindgen::JsCast;
+
+    let class = js_sys::Reflect::get(&window(), &event_class.into()).ok()?;
+    let request = js_sys::Reflect::get(&class, &"requestPermission".into()).ok()?;
+
+    request
+        .dyn_into::<js_sys::Function>()
+        .ok()
+        .map(|request| (class, request))
+}
+
 /// Return type of [`use_device_orientation`].
 #[derive(Clone)]
 pub struct UseDeviceOrientationReturn {
@@ -87,4 +198,10 @@ pub struct UseDeviceOrientationReturn {
     pub alpha: Signal<Option<f64>>,
     pub beta: Signal<Option<f64>>,
     pub gamma: Signal<Option<f64>>,
+    /// Whether the orientation events are delivered. Safari on iOS requires the user to grant
+    /// permission first. `PermissionState::Unknown` if not supported.
+    pub permission: Signal<PermissionState>,
+    /// Asks the user for permission to receive orientation events if required. Has to be
+    /// dispatched in reaction to a user interaction.
+    pub request_permission: Action<(), PermissionState>,
 }
@@ -27,16 +27,20 @@ use leptos::*;
 ///
 /// ## Server-Side Rendering
 ///
-/// On the server this function returns a Signal that is always `1.0`.
+/// On the server this function returns a Signal that is always `1.0`. During hydration it's `1.0`
+/// too until the hydration has finished.
 pub fn use_device_pixel_ratio() -> Signal<f64> {
     cfg_if! { if #[cfg(feature = "ssr")] {
         Signal::derive(|| 1.0)
     } else {
-        use crate::{use_event_listener_with_options, UseEventListenerOptions};
+        use crate::{after_hydration, use_event_listener_with_options, UseEventListenerOptions};
         use leptos::ev::change;
 
-        let initial_pixel_ratio = window().device_pixel_ratio();
-        let (pixel_ratio, set_pixel_ratio) = create_signal(initial_pixel_ratio);
+        let (pixel_ratio, set_pixel_ratio) = create_signal(1.0);
+
+        after_hydration(move || {
+            let _ = set_pixel_ratio.try_set(window().device_pixel_ratio());
+        });
 
         create_effect(move |_| {
             let media = window().match_media(
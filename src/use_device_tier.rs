@@ -0,0 +1,131 @@
+use crate::{use_device_memory, use_hardware_concurrency};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::fmt::Display;
+
+/// Rough classification of the device's performance.
+///
+/// Derived from [`fn@crate::use_device_memory`] and [`fn@crate::use_hardware_concurrency`]. Use
+/// it to adapt expensive features like animations, the size of worker pools or the quality of
+/// media to the device.
+///
+/// A device is
+///
+/// - `Low` if it has at most `low_memory` GB of memory or at most `low_concurrency` logical
+///   processors,
+/// - `High` if it has at least `high_memory` GB of memory and at least `high_concurrency`
+///   logical processors,
+/// - `Mid` otherwise.
+///
+/// Values that the browser doesn't report are ignored. If neither is reported the tier is
+/// `unknown_tier`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_device_tier, DeviceTier};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let tier = use_device_tier();
+///
+/// let animations_enabled = move || tier.get() != DeviceTier::Low;
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this returns a `Signal` that is always `unknown_tier`.
+pub fn use_device_tier() -> Signal<DeviceTier> {
+    use_device_tier_with_options(UseDeviceTierOptions::default())
+}
+
+/// Version of [`use_device_tier`] that takes a `UseDeviceTierOptions`. See [`use_device_tier`] for how to use.
+pub fn use_device_tier_with_options(options: UseDeviceTierOptions) -> Signal<DeviceTier> {
+    let UseDeviceTierOptions {
+        low_memory,
+        high_memory,
+        low_concurrency,
+        high_concurrency,
+        unknown_tier,
+    } = options;
+
+    let memory = use_device_memory();
+    let concurrency = use_hardware_concurrency();
+
+    Signal::derive(move || {
+        let memory = memory.get();
+        let concurrency = concurrency.get();
+
+        if memory.is_none() && concurrency.is_none() {
+            return unknown_tier;
+        }
+
+        let is_low = memory.is_some_and(|memory| memory <= low_memory)
+            || concurrency.is_some_and(|concurrency| concurrency <= low_concurrency);
+
+        let is_high = memory.map_or(true, |memory| memory >= high_memory)
+            && concurrency.map_or(true, |concurrency| concurrency >= high_concurrency);
+
+        if is_low {
+            DeviceTier::Low
+        } else if is_high {
+            DeviceTier::High
+        } else {
+            DeviceTier::Mid
+        }
+    })
+}
+
+/// Options for [`use_device_tier_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseDeviceTierOptions {
+    /// Devices with at most this much memory in GB are `Low`. Defaults to `2.0`.
+    low_memory: f64,
+
+    /// Devices with at least this much memory in GB can be `High`. Defaults to `8.0`.
+    high_memory: f64,
+
+    /// Devices with at most this many logical processors are `Low`. Defaults to `2`.
+    low_concurrency: usize,
+
+    /// Devices with at least this many logical processors can be `High`. Defaults to `8`.
+    high_concurrency: usize,
+
+    /// Tier if the browser reports neither memory nor processors. Defaults to `DeviceTier::Mid`.
+    unknown_tier: DeviceTier,
+}
+
+impl Default for UseDeviceTierOptions {
+    fn default() -> Self {
+        Self {
+            low_memory: 2.0,
+            high_memory: 8.0,
+            low_concurrency: 2,
+            high_concurrency: 8,
+            unknown_tier: DeviceTier::Mid,
+        }
+    }
+}
+
+/// Performance tier of the device. Returned by [`use_device_tier`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeviceTier {
+    Low,
+    #[default]
+    Mid,
+    High,
+}
+
+impl Display for DeviceTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Low => "low",
+            Self::Mid => "mid",
+            Self::High => "high",
+        })
+    }
+}
@@ -28,6 +28,10 @@ use leptos::*;
 /// ## Server-Side Rendering
 ///
 /// On the server this returns a `Signal` that always contains the value `web_sys::VisibilityState::Hidden`.
+///
+/// ## See also
+///
+/// - [`use_page_lifecycle`]
 pub fn use_document_visibility() -> Signal<web_sys::VisibilityState> {
     cfg_if! { if #[cfg(feature = "ssr")] {
         let inital_visibility = web_sys::VisibilityState::Hidden;
@@ -0,0 +1,163 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use leptos::*;
+use std::cell::RefCell;
+
+/// Batch DOM reads and writes into `requestAnimationFrame` aligned phases to avoid layout thrashing.
+///
+/// Interleaving reads (like `get_bounding_client_rect()`) and writes (like setting a style) forces
+/// the browser to recalculate the layout over and over again. This utility queues reads as
+/// *measure* jobs and writes as *mutate* jobs. On the next animation frame all measure jobs run
+/// first and then all mutate jobs. Measure jobs scheduled from inside a mutate job run in the
+/// following frame.
+///
+/// All instances share one global queue so the jobs of all components are batched together.
+/// Jobs that have been scheduled by a component that is cleaned up before they ran are dropped.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::{html::Div, *};
+/// # use leptos_use::use_dom_batch;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let batch = use_dom_batch();
+///
+/// let on_click = move |_| {
+///     batch.measure(move || {
+///         let Some(el) = el.get_untracked() else {
+///             return;
+///         };
+///         let width = el.get_bounding_client_rect().width();
+///
+///         batch.mutate(move || {
+///             let _ = el.style("height", format!("{width}px"));
+///         });
+///     });
+/// };
+///
+/// view! { <div node_ref=el on:click=on_click>"Make me square"</div> }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the jobs are never run.
+pub fn use_dom_batch() -> DomBatch {
+    let owner = SCHEDULER.with(|scheduler| {
+        let mut scheduler = scheduler.borrow_mut();
+        scheduler.next_owner += 1;
+        scheduler.next_owner
+    });
+
+    on_cleanup(move || {
+        SCHEDULER.with(|scheduler| {
+            let mut scheduler = scheduler.borrow_mut();
+            scheduler.measure.retain(|(o, _)| *o != owner);
+            scheduler.mutate.retain(|(o, _)| *o != owner);
+        });
+    });
+
+    DomBatch { owner }
+}
+
+/// Handle returned by [`use_dom_batch`] to schedule DOM reads and writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DomBatch {
+    owner: usize,
+}
+
+impl DomBatch {
+    /// Schedule a job that reads from the DOM. It runs before all mutate jobs of the next frame.
+    pub fn measure(&self, job: impl FnOnce() + 'static) {
+        self.schedule(Phase::Measure, Box::new(job));
+    }
+
+    /// Schedule a job that writes to the DOM. It runs after all measure jobs of the next frame.
+    pub fn mutate(&self, job: impl FnOnce() + 'static) {
+        self.schedule(Phase::Mutate, Box::new(job));
+    }
+
+    fn schedule(&self, phase: Phase, job: Box<dyn FnOnce()>) {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let request_frame = SCHEDULER.with(|scheduler| {
+                let mut scheduler = scheduler.borrow_mut();
+
+                match phase {
+                    Phase::Measure => scheduler.measure.push((self.owner, job)),
+                    Phase::Mutate => scheduler.mutate.push((self.owner, job)),
+                }
+
+                !std::mem::replace(&mut scheduler.frame_requested, true)
+            });
+
+            if request_frame {
+                request_flush();
+            }
+        }
+    }
+}
+
+enum Phase {
+    Measure,
+    Mutate,
+}
+
+type Job = (usize, Box<dyn FnOnce()>);
+
+#[derive(Default)]
+struct Scheduler {
+    measure: Vec<Job>,
+    mutate: Vec<Job>,
+    frame_requested: bool,
+    next_owner: usize,
+}
+
+thread_local! {
+    static SCHEDULER: RefCell<Scheduler> = RefCell::new(Scheduler::default());
+}
+
+#[cfg(not(feature = "ssr"))]
+fn request_flush() {
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+
+    let _ = window().request_animation_frame(Closure::once_into_js(flush).as_ref().unchecked_ref());
+}
+
+#[cfg(not(feature = "ssr"))]
+fn flush() {
+    // measure jobs can schedule further measure jobs which still run in this frame
+    loop {
+        let jobs = SCHEDULER.with(|scheduler| std::mem::take(&mut scheduler.borrow_mut().measure));
+        if jobs.is_empty() {
+            break;
+        }
+        run_jobs(jobs);
+    }
+
+    let jobs = SCHEDULER.with(|scheduler| {
+        let mut scheduler = scheduler.borrow_mut();
+        // from here on new jobs go into the next frame
+        scheduler.frame_requested = false;
+        std::mem::take(&mut scheduler.mutate)
+    });
+    run_jobs(jobs);
+}
+
+#[cfg(not(feature = "ssr"))]
+fn run_jobs(jobs: Vec<Job>) {
+    #[cfg(debug_assertions)]
+    let prev = SpecialNonReactiveZone::enter();
+
+    for (_, job) in jobs {
+        job();
+    }
+
+    #[cfg(debug_assertions)]
+    SpecialNonReactiveZone::exit(prev);
+}
@@ -1,8 +1,11 @@
 use crate::core::{ElementMaybeSignal, MaybeRwSignal, PointerType, Position};
-use crate::{use_event_listener_with_options, use_window, UseEventListenerOptions, UseWindow};
+use crate::{
+    use_dom_batch, use_event_listener_with_options, use_window, UseEventListenerOptions, UseWindow,
+};
 use default_struct_builder::DefaultBuilder;
 use leptos::ev::{pointerdown, pointermove, pointerup};
 use leptos::*;
+use std::cell::RefCell;
 use std::marker::PhantomData;
 use std::rc::Rc;
 use wasm_bindgen::JsCast;
@@ -44,6 +47,9 @@ use web_sys::PointerEvent;
 /// }
 /// # }
 /// ```
+///
+/// Pointer moves are coalesced and applied in the mutate phase of [`fn@crate::use_dom_batch`]
+/// so `position` and `on_move` are updated at most once per animation frame.
 pub fn use_draggable<El, T>(target: El) -> UseDraggableReturn
 where
     El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
@@ -156,32 +162,54 @@ where
         }
     };
 
+    // Moves the element according to the latest pointer move that hasn't been applied yet
+    let pending_move = Rc::new(RefCell::new(None::<PointerEvent>));
+    let apply_move = {
+        let pending_move = Rc::clone(&pending_move);
+
+        move || {
+            let Some(event) = pending_move.take() else {
+                return;
+            };
+            let Some(start_position) = start_position.try_get_untracked().flatten() else {
+                return;
+            };
+
+            let position = Position {
+                x: event.client_x() as f64 - start_position.x,
+                y: event.client_y() as f64 - start_position.y,
+            };
+            set_position.set(position);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_move(UseDraggableCallbackArgs { position, event });
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    };
+
     let on_pointer_move = {
         let filter_event = filter_event.clone();
+        let apply_move = apply_move.clone();
+        let batch = use_dom_batch();
 
         move |event: PointerEvent| {
             if !filter_event(&event) {
                 return;
             }
-            if let Some(start_position) = start_position.get_untracked() {
-                let position = Position {
-                    x: event.client_x() as f64 - start_position.x,
-                    y: event.client_y() as f64 - start_position.y,
-                };
-                set_position.set(position);
-
-                #[cfg(debug_assertions)]
-                let prev = SpecialNonReactiveZone::enter();
-
-                on_move(UseDraggableCallbackArgs {
-                    position,
-                    event: event.clone(),
-                });
+            if start_position.get_untracked().is_none() {
+                return;
+            }
 
-                #[cfg(debug_assertions)]
-                SpecialNonReactiveZone::exit(prev);
+            handle_event(event.clone());
 
-                handle_event(event);
+            // Pointer moves can fire many times per frame. Only the latest one is applied in the
+            // mutate phase of the next frame so the layout isn't invalidated in between.
+            if pending_move.replace(Some(event)).is_none() {
+                batch.mutate(apply_move.clone());
             }
         }
     };
@@ -193,6 +221,9 @@ where
         if start_position.get_untracked().is_none() {
             return;
         }
+
+        // the last move must not be lost
+        apply_move();
         set_start_position.set(None);
 
         #[cfg(debug_assertions)]
@@ -71,10 +71,12 @@ where
         let update = move || ();
     } else {
         use crate::{
-            use_event_listener_with_options, use_resize_observer, use_window,
+            use_dom_batch, use_event_listener_with_options, use_resize_observer, use_window,
             UseEventListenerOptions,
         };
         use leptos::ev::{resize, scroll};
+        use std::cell::Cell;
+        use std::rc::Rc;
 
         let UseElementBoundingOptions {
             reset,
@@ -115,6 +117,26 @@ where
             }
         };
 
+        // Scroll and resize events can fire many times per frame. Reading the layout is deferred
+        // to the measure phase of the next frame and only done once.
+        let batched_update = {
+            let update = update.clone();
+            let batch = use_dom_batch();
+            let pending = Rc::new(Cell::new(false));
+
+            move || {
+                if !pending.replace(true) {
+                    let update = update.clone();
+                    let pending = Rc::clone(&pending);
+
+                    batch.measure(move || {
+                        pending.set(false);
+                        update();
+                    });
+                }
+            }
+        };
+
         use_resize_observer(target.clone(), {
             let update = update.clone();
 
@@ -139,8 +161,8 @@ where
                 use_window(),
                 scroll,
                 {
-                    let update = update.clone();
-                    move |_| update()
+                    let batched_update = batched_update.clone();
+                    move |_| batched_update()
                 },
                 UseEventListenerOptions::default()
                     .capture(true)
@@ -152,10 +174,7 @@ where
             let _ = use_event_listener_with_options(
                 use_window(),
                 resize,
-                {
-                    let update = update.clone();
-                    move |_| update()
-                },
+                move |_| batched_update(),
                 UseEventListenerOptions::default().passive(true),
             );
         }
@@ -0,0 +1,211 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{
+    js, use_intersection_observer_with_options, UseIntersectionObserverOptions,
+    UseIntersectionObserverReturn,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+
+/// Defers rendering of expensive content until its placeholder element comes close to the viewport.
+///
+/// The returned `should_render` signal becomes `true` once `target` is within `root_margin` of the
+/// viewport. Put `target` where the content will be and render the content inside of it only
+/// when `should_render` is `true`. Give the placeholder a size that matches the content
+/// roughly so the layout doesn't jump when it's mounted.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::use_element_lazy_mount;
+/// #
+/// # #[component]
+/// # fn ExpensiveChart() -> impl IntoView { view! { } }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let placeholder = create_node_ref::<Div>();
+///
+/// let should_render = use_element_lazy_mount(placeholder);
+///
+/// view! {
+///     <div node_ref=placeholder style="min-height: 400px">
+///         <Show when=should_render>
+///             <ExpensiveChart />
+///         </Show>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ### Idle Time
+///
+/// With the option `idle_timeout` the content is also rendered as soon as the browser is idle
+/// (see [`requestIdleCallback`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback))
+/// but at the latest after the given number of milliseconds. That way it's ready before the user
+/// scrolls to it.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_lazy_mount_with_options, UseElementLazyMountOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let placeholder = create_node_ref::<Div>();
+/// let should_render = use_element_lazy_mount_with_options(
+///     placeholder,
+///     UseElementLazyMountOptions::default()
+///         .root_margin("400px")
+///         .idle_timeout(Some(2000)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Keep Alive
+///
+/// Once mounted the content stays mounted by default. Set the option `keep_alive` to `false` to
+/// unmount it again when the placeholder moves out of `root_margin` of the viewport.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the returned signal is always `false` so the placeholder is rendered.
+pub fn use_element_lazy_mount<El, T>(target: El) -> Signal<bool>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_element_lazy_mount_with_options::<El, T, web_sys::Element, web_sys::Element>(
+        target,
+        UseElementLazyMountOptions::default(),
+    )
+}
+
+/// Version of [`use_element_lazy_mount`] that takes a `UseElementLazyMountOptions`. See [`use_element_lazy_mount`] for how to use.
+pub fn use_element_lazy_mount_with_options<El, T, RootEl, RootT>(
+    target: El,
+    options: UseElementLazyMountOptions<RootEl, RootT>,
+) -> Signal<bool>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+    RootEl: Into<ElementMaybeSignal<RootT, web_sys::Element>>,
+    RootT: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseElementLazyMountOptions {
+        root,
+        root_margin,
+        idle_timeout,
+        keep_alive,
+        ..
+    } = options;
+
+    let (should_render, set_should_render) = create_signal(false);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let UseIntersectionObserverReturn { stop, .. } = use_intersection_observer_with_options(
+            target.into(),
+            move |entries, _| {
+                if let Some(entry) = entries.last() {
+                    if entry.is_intersecting() {
+                        set_should_render.set(true);
+                    } else if !keep_alive {
+                        set_should_render.set(false);
+                    }
+                }
+            },
+            UseIntersectionObserverOptions::default()
+                .root(root)
+                .root_margin(root_margin),
+        );
+
+        if keep_alive {
+            let _ = watch(
+                move || should_render.get(),
+                move |should_render, _, _| {
+                    if *should_render {
+                        stop();
+                    }
+                },
+                false,
+            );
+        }
+
+        if let Some(idle_timeout) = idle_timeout {
+            use std::time::Duration;
+            use wasm_bindgen::closure::Closure;
+            use wasm_bindgen::JsCast;
+
+            let on_idle = move || {
+                if !should_render.get_untracked() {
+                    set_should_render.set(true);
+                }
+            };
+
+            if js!("requestIdleCallback" in &window()) {
+                let mut idle_options = web_sys::IdleRequestOptions::new();
+                idle_options.timeout(idle_timeout);
+
+                let callback = Closure::once_into_js(on_idle);
+
+                if let Ok(handle) = window()
+                    .request_idle_callback_with_options(callback.unchecked_ref(), &idle_options)
+                {
+                    on_cleanup(move || window().cancel_idle_callback(handle));
+                }
+            } else if let Ok(handle) =
+                set_timeout_with_handle(on_idle, Duration::from_millis(idle_timeout as u64))
+            {
+                on_cleanup(move || handle.clear());
+            }
+        }
+    }
+
+    should_render.into()
+}
+
+/// Options for [`use_element_lazy_mount_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseElementLazyMountOptions<El, T>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    /// The scroll container that is considered the viewport. Defaults to `None` which means the
+    /// browser viewport is used.
+    root: Option<El>,
+
+    /// How far away from the viewport the placeholder may be to be rendered. Uses the syntax of
+    /// the CSS `margin` property. Defaults to `"200px"`.
+    #[builder(into)]
+    root_margin: String,
+
+    /// If `Some`, render as soon as the browser is idle but at the latest after this many
+    /// milliseconds. Defaults to `None`.
+    idle_timeout: Option<u32>,
+
+    /// Keep the content rendered once it has been mounted. Defaults to `true`.
+    keep_alive: bool,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl Default for UseElementLazyMountOptions<web_sys::Element, web_sys::Element> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            root_margin: "200px".into(),
+            idle_timeout: None,
+            keep_alive: true,
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,517 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use default_struct_builder::DefaultBuilder;
+use futures_util::future::{self, FutureExt, LocalBoxFuture};
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+/// Renders an element into an image, for example to share or export it.
+///
+/// Calling `capture` rasterizes the current content of `target` and resolves to a [`Screenshot`]
+/// that contains the image as a `Blob` and as a data URL. The latest screenshot is also
+/// available in the signal `screenshot`.
+///
+/// By default the element is cloned with the computed styles of all its descendants inlined,
+/// serialized into an SVG `<foreignObject>` and then drawn onto a canvas. This needs no
+/// dependencies but has the usual limitations of this technique: pseudo elements aren't
+/// rendered, web fonts and images have to be available as data URLs and cross-origin images
+/// make the capture fail.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_screenshot, UseElementScreenshotReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let card = create_node_ref::<Div>();
+///
+/// let UseElementScreenshotReturn {
+///     screenshot,
+///     is_capturing,
+///     capture,
+///     ..
+/// } = use_element_screenshot(card);
+///
+/// view! {
+///     <div node_ref=card style="padding: 1rem; background: teal; color: white">
+///         "Share me"
+///     </div>
+///     <button on:click=move |_| { let _ = capture(); } disabled=is_capturing>"Capture"</button>
+///     <Show when=move || screenshot.with(Option::is_some)>
+///         <img src=move || screenshot.get().map(|s| s.data_url) />
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// `capture` returns a future as well, so you can wait for the result directly.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_screenshot_with_options, UseElementScreenshotOptions, UseElementScreenshotReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let card = create_node_ref::<Div>();
+/// let UseElementScreenshotReturn { capture, .. } = use_element_screenshot_with_options(
+///     card,
+///     UseElementScreenshotOptions::default()
+///         .scale(2.0)
+///         .background("white".to_string())
+///         .mime_type("image/jpeg")
+///         .quality(0.9),
+/// );
+///
+/// let download = move |_| {
+///     let capture = capture.clone();
+///
+///     spawn_local(async move {
+///         if let Ok(screenshot) = capture().await {
+///             logging::log!("{} bytes", screenshot.blob.size());
+///         }
+///     });
+/// };
+/// #
+/// # view! { <button on:click=download>"Download"</button> }
+/// # }
+/// ```
+///
+/// ## Custom Renderer
+///
+/// If the built-in rendering isn't good enough for your content you can plug in a library like
+/// [html-to-image](https://github.com/bubkoo/html-to-image) with the option `renderer`. It
+/// receives the element together with the requested scale and background and has to resolve
+/// to a canvas. Encoding the canvas into the screenshot is still done by this function.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_screenshot_with_options, ScreenshotRenderRequest, UseElementScreenshotOptions};
+/// # use futures_util::FutureExt;
+/// # use wasm_bindgen::prelude::*;
+/// #
+/// #[wasm_bindgen]
+/// extern "C" {
+///     #[wasm_bindgen(js_namespace = htmlToImage, js_name = toCanvas)]
+///     fn to_canvas(node: &web_sys::Element, options: &JsValue) -> js_sys::Promise;
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let card = create_node_ref::<Div>();
+/// let screenshot = use_element_screenshot_with_options(
+///     card,
+///     UseElementScreenshotOptions::default().renderer(|request: ScreenshotRenderRequest| {
+///         let options = js_sys::Object::new();
+///         let _ = js_sys::Reflect::set(&options, &"pixelRatio".into(), &request.scale.into());
+///         if let Some(background) = &request.background {
+///             let _ = js_sys::Reflect::set(&options, &"backgroundColor".into(), &background.into());
+///         }
+///
+///         let promise = to_canvas(&request.element, &options);
+///
+///         async move {
+///             wasm_bindgen_futures::JsFuture::from(promise)
+///                 .await
+///                 .map(JsCast::unchecked_into)
+///         }
+///         .boxed_local()
+///     }),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is rendered. `capture` resolves to `Err(UseElementScreenshotError::NoElement)`.
+pub fn use_element_screenshot<El, T>(
+    target: El,
+) -> UseElementScreenshotReturn<
+    impl Fn() -> LocalBoxFuture<'static, Result<Screenshot, UseElementScreenshotError>>
+        + Clone
+        + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_element_screenshot_with_options(target, UseElementScreenshotOptions::default())
+}
+
+/// Version of [`use_element_screenshot`] that takes a `UseElementScreenshotOptions`. See [`use_element_screenshot`] for how to use.
+pub fn use_element_screenshot_with_options<El, T>(
+    target: El,
+    options: UseElementScreenshotOptions,
+) -> UseElementScreenshotReturn<
+    impl Fn() -> LocalBoxFuture<'static, Result<Screenshot, UseElementScreenshotError>>
+        + Clone
+        + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseElementScreenshotOptions {
+        scale,
+        background,
+        mime_type,
+        quality,
+        inline_styles,
+        renderer,
+    } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let (screenshot, set_screenshot) = create_signal(None::<Screenshot>);
+    let (error, set_error) = create_signal(None::<UseElementScreenshotError>);
+    let (is_capturing, set_capturing) = create_signal(false);
+
+    // incremented on every capture so only the result of the latest one is stored
+    let generation = store_value(0_u64);
+
+    let capture =
+        move || -> LocalBoxFuture<'static, Result<Screenshot, UseElementScreenshotError>> {
+            #[cfg(feature = "ssr")]
+            return future::ready(Err(UseElementScreenshotError::NoElement)).boxed_local();
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                let Some(element) = target.get_untracked() else {
+                    set_error.set(Some(UseElementScreenshotError::NoElement));
+                    return future::ready(Err(UseElementScreenshotError::NoElement)).boxed_local();
+                };
+
+                let current_generation = generation.get_value() + 1;
+                generation.set_value(current_generation);
+                set_capturing.set(true);
+
+                let request = ScreenshotRenderRequest {
+                    element,
+                    scale,
+                    background: background.clone(),
+                };
+
+                let canvas = match &renderer {
+                    Some(renderer) => {
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        let canvas = renderer(request);
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+
+                        canvas
+                    }
+                    None => render_to_canvas(request, inline_styles).boxed_local(),
+                };
+
+                let mime_type = mime_type.clone();
+
+                async move {
+                    let result = match canvas.await {
+                        Ok(canvas) => encode_canvas(&canvas, &mime_type, quality).await,
+                        Err(err) => Err(UseElementScreenshotError::Render(err)),
+                    };
+
+                    if generation.try_get_value() == Some(current_generation) {
+                        match &result {
+                            Ok(screenshot) => {
+                                set_screenshot.set(Some(screenshot.clone()));
+                                set_error.set(None);
+                            }
+                            Err(err) => set_error.set(Some(err.clone())),
+                        }
+                        set_capturing.set(false);
+                    }
+
+                    result
+                }
+                .boxed_local()
+            }
+        };
+
+    UseElementScreenshotReturn {
+        screenshot: screenshot.into(),
+        error: error.into(),
+        is_capturing: is_capturing.into(),
+        capture,
+    }
+}
+
+/// Clones `element` with inlined styles, wraps it in an SVG `<foreignObject>` and draws that
+/// onto a new canvas.
+#[cfg(not(feature = "ssr"))]
+async fn render_to_canvas(
+    request: ScreenshotRenderRequest,
+    inline_styles: bool,
+) -> Result<web_sys::HtmlCanvasElement, JsValue> {
+    let ScreenshotRenderRequest {
+        element,
+        scale,
+        background,
+    } = request;
+
+    let rect = element.get_bounding_client_rect();
+    let (width, height) = (rect.width(), rect.height());
+
+    let clone: web_sys::Element = element.clone_node_with_deep(true)?.unchecked_into();
+    if inline_styles {
+        inline_computed_styles(&element, &clone)?;
+    }
+
+    let mut root_style = clone.get_attribute("style").unwrap_or_default();
+    // the position of the element in the page doesn't matter in the image
+    root_style.push_str("margin:0;");
+    if let Some(background) = &background {
+        root_style.push_str(&format!("background-color:{background};"));
+    }
+    clone.set_attribute("style", &root_style)?;
+
+    let xhtml = web_sys::XmlSerializer::new()?.serialize_to_string(&clone)?;
+
+    let canvas_width = (width * scale).ceil();
+    let canvas_height = (height * scale).ceil();
+
+    // rendering the SVG at the scaled size keeps text sharp
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{canvas_width}" height="{canvas_height}" viewBox="0 0 {width} {height}"><foreignObject x="0" y="0" width="100%" height="100%">{xhtml}</foreignObject></svg>"#
+    );
+    let src = format!(
+        "data:image/svg+xml;charset=utf-8,{}",
+        js_sys::encode_uri_component(&svg)
+    );
+
+    let image = web_sys::HtmlImageElement::new()?;
+    let loaded = js_sys::Promise::new(&mut |resolve, reject| {
+        image.set_onload(Some(&resolve));
+        image.set_onerror(Some(&reject));
+    });
+    image.set_src(&src);
+    wasm_bindgen_futures::JsFuture::from(loaded).await?;
+
+    let canvas: web_sys::HtmlCanvasElement = document().create_element("canvas")?.unchecked_into();
+    canvas.set_width(canvas_width as u32);
+    canvas.set_height(canvas_height as u32);
+
+    let context: web_sys::CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2d context not available"))?
+        .unchecked_into();
+    context.draw_image_with_html_image_element_and_dw_and_dh(
+        &image,
+        0.0,
+        0.0,
+        canvas_width,
+        canvas_height,
+    )?;
+
+    Ok(canvas)
+}
+
+/// Copies the computed style of every element in `source` to the same element in `target`.
+/// `target` has to be a deep clone of `source`.
+#[cfg(not(feature = "ssr"))]
+fn inline_computed_styles(
+    source: &web_sys::Element,
+    target: &web_sys::Element,
+) -> Result<(), JsValue> {
+    if let Some(style) = window().get_computed_style(source)? {
+        let mut css = String::new();
+
+        for i in 0..style.length() {
+            let property = style.item(i);
+            let value = style.get_property_value(&property)?;
+            css.push_str(&format!("{property}:{value};"));
+        }
+
+        target.set_attribute("style", &css)?;
+    }
+
+    let source_children = source.children();
+    let target_children = target.children();
+
+    for i in 0..source_children.length() {
+        if let (Some(source), Some(target)) = (source_children.item(i), target_children.item(i)) {
+            inline_computed_styles(&source, &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn encode_canvas(
+    canvas: &web_sys::HtmlCanvasElement,
+    mime_type: &str,
+    quality: Option<f64>,
+) -> Result<Screenshot, UseElementScreenshotError> {
+    let quality = quality.map(JsValue::from).unwrap_or(JsValue::UNDEFINED);
+
+    let data_url = canvas
+        .to_data_url_with_type_and_encoder_options(mime_type, &quality)
+        .map_err(UseElementScreenshotError::Encode)?;
+
+    let mut to_blob_result = Ok(());
+    let blob = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_blob = Closure::once_into_js(move |blob: JsValue| {
+            if blob.is_null() {
+                let _ = reject.call1(
+                    &JsValue::UNDEFINED,
+                    &JsValue::from_str("canvas couldn't be encoded"),
+                );
+            } else {
+                let _ = resolve.call1(&JsValue::UNDEFINED, &blob);
+            }
+        });
+
+        to_blob_result = canvas.to_blob_with_type_and_encoder_options(
+            on_blob.unchecked_ref(),
+            mime_type,
+            &quality,
+        );
+    });
+    to_blob_result.map_err(UseElementScreenshotError::Encode)?;
+
+    let blob = wasm_bindgen_futures::JsFuture::from(blob)
+        .await
+        .map_err(UseElementScreenshotError::Encode)?;
+
+    Ok(Screenshot {
+        blob: blob.unchecked_into(),
+        data_url,
+        width: canvas.width(),
+        height: canvas.height(),
+    })
+}
+
+/// Renders the element of a [`ScreenshotRenderRequest`] onto a canvas.
+pub type ScreenshotRenderer = Rc<
+    dyn Fn(
+        ScreenshotRenderRequest,
+    ) -> LocalBoxFuture<'static, Result<web_sys::HtmlCanvasElement, JsValue>>,
+>;
+
+/// Options for [`use_element_screenshot_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseElementScreenshotOptions {
+    /// Factor between the size of the element in CSS pixels and the size of the image.
+    /// Use `window().device_pixel_ratio()` for an image as sharp as the screen. Defaults to `1.0`.
+    scale: f64,
+
+    /// CSS color that the element is drawn on. Defaults to `None` which keeps the background
+    /// transparent where the element doesn't paint one itself.
+    #[builder(into)]
+    background: Option<String>,
+
+    /// Image format of the screenshot. Defaults to `"image/png"`.
+    #[builder(into)]
+    mime_type: String,
+
+    /// Quality between `0.0` and `1.0` for lossy formats like `"image/jpeg"` or `"image/webp"`.
+    /// Defaults to `None` which uses the browser's default.
+    #[builder(into)]
+    quality: Option<f64>,
+
+    /// If `true` the computed styles are inlined into the cloned elements. Without this, only
+    /// inline styles are applied because stylesheets aren't part of the SVG. Defaults to `true`.
+    /// Only used by the built-in renderer.
+    inline_styles: bool,
+
+    /// Renders the element instead of the built-in SVG `<foreignObject>` renderer.
+    /// Defaults to `None`.
+    #[builder(skip)]
+    renderer: Option<ScreenshotRenderer>,
+}
+
+impl Default for UseElementScreenshotOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            background: None,
+            mime_type: "image/png".to_string(),
+            quality: None,
+            inline_styles: true,
+            renderer: None,
+        }
+    }
+}
+
+impl UseElementScreenshotOptions {
+    /// Renders the element instead of the built-in SVG `<foreignObject>` renderer.
+    /// See [`use_element_screenshot`] for an example.
+    pub fn renderer<F>(self, renderer: F) -> Self
+    where
+        F: Fn(
+                ScreenshotRenderRequest,
+            ) -> LocalBoxFuture<'static, Result<web_sys::HtmlCanvasElement, JsValue>>
+            + 'static,
+    {
+        Self {
+            renderer: Some(Rc::new(renderer)),
+            ..self
+        }
+    }
+}
+
+/// What a custom renderer of [`use_element_screenshot`] should render.
+#[derive(Clone, Debug)]
+pub struct ScreenshotRenderRequest {
+    /// The element to render
+    pub element: web_sys::Element,
+    /// Factor between the size of the element in CSS pixels and the size of the canvas
+    pub scale: f64,
+    /// CSS color that the element should be drawn on
+    pub background: Option<String>,
+}
+
+/// An image of an element created by [`use_element_screenshot`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Screenshot {
+    /// The encoded image
+    pub blob: web_sys::Blob,
+    /// The encoded image as a `data:` URL that can be used as the `src` of an `<img>`
+    pub data_url: String,
+    /// Width of the image in pixels
+    pub width: u32,
+    /// Height of the image in pixels
+    pub height: u32,
+}
+
+/// Return type of [`use_element_screenshot`].
+pub struct UseElementScreenshotReturn<CaptureFn>
+where
+    CaptureFn: Fn() -> LocalBoxFuture<'static, Result<Screenshot, UseElementScreenshotError>>
+        + Clone
+        + 'static,
+{
+    /// The latest successful screenshot
+    pub screenshot: Signal<Option<Screenshot>>,
+    /// The error of the latest capture if it failed
+    pub error: Signal<Option<UseElementScreenshotError>>,
+    /// `true` while a capture is in progress
+    pub is_capturing: Signal<bool>,
+    /// Renders the element. The returned future resolves to the screenshot. You don't have to
+    /// await it, `screenshot` and `error` are updated either way.
+    pub capture: CaptureFn,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum UseElementScreenshotError {
+    #[error("no element to capture")]
+    NoElement,
+    #[error("failed to render element: {0:?}")]
+    Render(JsValue),
+    #[error("failed to encode image: {0:?}")]
+    Encode(JsValue),
+}
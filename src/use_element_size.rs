@@ -4,8 +4,10 @@ use default_struct_builder::DefaultBuilder;
 use leptos::*;
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
-    use crate::{use_resize_observer_with_options, UseResizeObserverOptions};
+    use crate::{use_dom_batch, use_resize_observer_with_options, UseResizeObserverOptions};
     use crate::{watch_with_options, WatchOptions};
+    use std::cell::Cell;
+    use std::rc::Rc;
     use wasm_bindgen::JsCast;
 }}
 
@@ -39,6 +41,39 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Initial Size and Coalescing
+///
+/// The `ResizeObserver` reports the first size only after the element has been laid out. Until then
+/// the signals contain `initial_size`. To avoid rendering a frame with a wrong size you can enable
+/// `measure_on_mount` which reads the size synchronously with `get_bounding_client_rect()` as soon
+/// as the element is available. Please note that this is always the size of the border box without
+/// transforms applied. It is corrected by the first `ResizeObserver` notification if necessary.
+///
+/// With `coalesce` enabled, bursts of resize notifications result in only one update of the
+/// signals per animation frame.
+///
+/// ```
+/// # use leptos::{html::Div, *};
+/// # use leptos_use::{use_element_size_with_options, UseElementSizeOptions, UseElementSizeReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UseElementSizeReturn { width, height } = use_element_size_with_options(
+///     el,
+///     UseElementSizeOptions::default()
+///         .measure_on_mount(true)
+///         .coalesce(true),
+/// );
+/// #
+/// # view! { <div node_ref=el></div> }
+/// # }
+/// ```
+///
+/// Like [`use_resize_observer`] this shares one `ResizeObserver` per `box_` option with all
+/// other calls, so it can be used for many elements at once.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server the returned signals always contain the value of the `initial_size` option.
@@ -64,7 +99,12 @@ where
     El: Into<ElementMaybeSignal<T, web_sys::Element>> + Clone,
     T: Into<web_sys::Element> + Clone + 'static,
 {
-    let UseElementSizeOptions { box_, initial_size } = options;
+    let UseElementSizeOptions {
+        box_,
+        initial_size,
+        measure_on_mount,
+        coalesce,
+    } = options;
 
     let (width, set_width) = create_signal(initial_size.width);
     let (height, set_height) = create_signal(initial_size.height);
@@ -75,6 +115,30 @@ where
 
         let target = target.into();
 
+        let batch = use_dom_batch();
+
+        // with `coalesce` only the latest size of a frame is written to the signals
+        let pending_size = Rc::new(Cell::new(None::<(f64, f64)>));
+
+        let set_size = move |width: f64, height: f64| {
+            if !coalesce {
+                set_width.set(width);
+                set_height.set(height);
+                return;
+            }
+
+            if pending_size.replace(Some((width, height))).is_none() {
+                let pending_size = Rc::clone(&pending_size);
+
+                batch.mutate(move || {
+                    if let Some((width, height)) = pending_size.take() {
+                        set_width.set(width);
+                        set_height.set(height);
+                    }
+                });
+            }
+        };
+
         let is_svg = {
             let target = target.clone();
 
@@ -111,18 +175,14 @@ where
                     if is_svg() {
                         if let Some(target) = target.get() {
                             if let Ok(Some(styles)) = window().get_computed_style(&target.into()) {
-                                set_height.set(
-                                    styles
-                                        .get_property_value("height")
-                                        .map(|v| v.parse().unwrap_or_default())
-                                        .unwrap_or_default(),
-                                );
-                                set_width.set(
+                                let parse = |property: &str| {
                                     styles
-                                        .get_property_value("width")
+                                        .get_property_value(property)
                                         .map(|v| v.parse().unwrap_or_default())
-                                        .unwrap_or_default(),
-                                );
+                                        .unwrap_or_default()
+                                };
+
+                                set_size(parse("width"), parse("height"));
                             }
                         }
                     } else if !box_size.is_null()
@@ -135,42 +195,91 @@ where
                             vec![box_size.into()]
                         };
 
-                        set_width.set(format_box_size.iter().fold(0.0, |acc, v| {
-                            acc + v
-                                .as_ref()
-                                .clone()
-                                .unchecked_into::<web_sys::ResizeObserverSize>()
-                                .inline_size()
-                        }));
-                        set_height.set(format_box_size.iter().fold(0.0, |acc, v| {
-                            acc + v
-                                .as_ref()
-                                .clone()
-                                .unchecked_into::<web_sys::ResizeObserverSize>()
-                                .block_size()
-                        }))
+                        set_size(
+                            format_box_size.iter().fold(0.0, |acc, v| {
+                                acc + v
+                                    .as_ref()
+                                    .clone()
+                                    .unchecked_into::<web_sys::ResizeObserverSize>()
+                                    .inline_size()
+                            }),
+                            format_box_size.iter().fold(0.0, |acc, v| {
+                                acc + v
+                                    .as_ref()
+                                    .clone()
+                                    .unchecked_into::<web_sys::ResizeObserverSize>()
+                                    .block_size()
+                            }),
+                        );
                     } else {
                         // fallback
-                        set_width.set(entry.content_rect().width());
-                        set_height.set(entry.content_rect().height())
+                        set_size(entry.content_rect().width(), entry.content_rect().height());
                     }
                 },
                 UseResizeObserverOptions::default().box_(box_),
             );
         }
 
+        // Reads the size of the same box that the `ResizeObserver` reports so that its first
+        // notification doesn't change the size again.
+        let measure = move |el: web_sys::Element| {
+            if box_ == web_sys::ResizeObserverBoxOptions::DevicePixelContentBox {
+                // can't be computed reliably from the layout => wait for the observer
+                return;
+            }
+
+            let rect = el.get_bounding_client_rect();
+            let mut width = rect.width();
+            let mut height = rect.height();
+
+            if box_ == web_sys::ResizeObserverBoxOptions::ContentBox {
+                if let Ok(Some(styles)) = window().get_computed_style(&el) {
+                    let px = |property: &str| {
+                        styles
+                            .get_property_value(property)
+                            .ok()
+                            .and_then(|value| value.trim_end_matches("px").parse::<f64>().ok())
+                            .unwrap_or_default()
+                    };
+
+                    width -= px("padding-left")
+                        + px("padding-right")
+                        + px("border-left-width")
+                        + px("border-right-width");
+                    height -= px("padding-top")
+                        + px("padding-bottom")
+                        + px("border-top-width")
+                        + px("border-bottom-width");
+                }
+            }
+
+            set_width.set(width.max(0.0));
+            set_height.set(height.max(0.0));
+        };
+
         let _ = watch_with_options(
             move || target.get(),
-            move |ele, _, _| {
-                if ele.is_some() {
-                    set_width.set(initial_size.width);
-                    set_height.set(initial_size.height);
-                } else {
+            move |ele, prev_ele, _| {
+                if let Some(ele) = ele {
+                    if measure_on_mount {
+                        let ele: web_sys::Element = ele.clone().into();
+
+                        if ele.is_connected() {
+                            measure(ele);
+                        } else {
+                            // not in the document yet => measure before the next layout
+                            batch.measure(move || measure(ele));
+                        }
+                    } else {
+                        set_width.set(initial_size.width);
+                        set_height.set(initial_size.height);
+                    }
+                } else if prev_ele.is_some() {
                     set_width.set(0.0);
                     set_height.set(0.0);
                 }
             },
-            WatchOptions::default().immediate(false),
+            WatchOptions::default().immediate(measure_on_mount),
         );
     }
 
@@ -190,6 +299,16 @@ pub struct UseElementSizeOptions {
     /// The box that is used to determine the dimensions of the target. Defaults to `ContentBox`.
     #[builder(into)]
     pub box_: Option<web_sys::ResizeObserverBoxOptions>,
+
+    /// If `true` the size is read synchronously with `get_bounding_client_rect()` as soon as the
+    /// `target` is available instead of waiting for the first `ResizeObserver` notification.
+    /// For the content box the padding and border are subtracted. The device pixel content box
+    /// can't be read this way so it's always reported by the observer. Defaults to `false`.
+    measure_on_mount: bool,
+
+    /// If `true` multiple resize notifications within one animation frame only update the
+    /// signals once. Defaults to `false`.
+    coalesce: bool,
 }
 
 /// The return value of [`use_element_size`].
@@ -0,0 +1,240 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::use_dom_batch;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Binds reactive translation, scale and rotation to the CSS transform of an element.
+///
+/// Instead of every drag or zoom hook writing styles on its own, hand the signals to this hook.
+/// Changes are batched with [`fn@crate::use_dom_batch`] so the style is written at most once per
+/// animation frame, no matter how many of the signals change.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_draggable, use_element_transform_with_options, UseDraggableReturn, UseElementTransformOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UseDraggableReturn { x, y, .. } = use_draggable(el);
+/// let (rotation, set_rotation) = create_signal(0.0);
+///
+/// use_element_transform_with_options(
+///     el,
+///     UseElementTransformOptions::default()
+///         .translate_x(x)
+///         .translate_y(y)
+///         .rotate(rotation),
+/// );
+///
+/// view! {
+///     <div node_ref=el style="position: fixed; transform: translate(-50%, -50%)">"Drag me"</div>
+///     <button on:click=move |_| set_rotation.update(|r| *r += 15.0)>"Rotate"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Composing
+///
+/// In the default `TransformMode::Transform` the inline `transform` that the element has when it
+/// is bound is kept and appended to the generated one. In the example above the element stays
+/// centered on the dragged position.
+///
+/// With `TransformMode::Individual` the individual CSS properties `translate`, `rotate` and
+/// `scale` are written instead. The browser composes them with any `transform` from inline
+/// styles or stylesheets.
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is written. The returned `transform` can be rendered into the style
+/// attribute to avoid a jump on hydration.
+pub fn use_element_transform<El, T>(target: El) -> UseElementTransformReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_element_transform_with_options(target, UseElementTransformOptions::default())
+}
+
+/// Version of [`use_element_transform`] that takes a `UseElementTransformOptions`. See [`use_element_transform`] for how to use.
+pub fn use_element_transform_with_options<El, T>(
+    target: El,
+    options: UseElementTransformOptions,
+) -> UseElementTransformReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseElementTransformOptions {
+        translate_x,
+        translate_y,
+        scale,
+        rotate,
+        mode,
+    } = options;
+
+    let translate_x: Signal<f64> = translate_x.into();
+    let translate_y: Signal<f64> = translate_y.into();
+    let scale: Signal<f64> = scale.into();
+    let rotate: Signal<f64> = rotate.into();
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let transform = create_memo(move |_| {
+        format!(
+            "translate({}px, {}px) rotate({}deg) scale({})",
+            translate_x.get(),
+            translate_y.get(),
+            rotate.get(),
+            scale.get()
+        )
+    });
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let batch = use_dom_batch();
+
+        // the bound element and its original inline transform
+        let bound = store_value(None::<(web_sys::Element, String)>);
+        let is_pending = store_value(false);
+
+        let unbind = move || {
+            if let Some((el, original)) = bound.try_get_value().flatten() {
+                let style = el.unchecked_ref::<web_sys::HtmlElement>().style();
+                match mode {
+                    TransformMode::Transform => {
+                        let _ = style.set_property("transform", &original);
+                    }
+                    TransformMode::Individual => {
+                        for property in ["translate", "rotate", "scale"] {
+                            let _ = style.remove_property(property);
+                        }
+                    }
+                }
+            }
+            let _ = bound.try_set_value(None);
+        };
+
+        let write = move || {
+            is_pending.set_value(false);
+
+            let Some(el) = target.get_untracked() else {
+                unbind();
+                return;
+            };
+
+            if bound.with_value(|bound| bound.as_ref().map(|(bound, _)| bound) != Some(&el)) {
+                unbind();
+                let original = el
+                    .unchecked_ref::<web_sys::HtmlElement>()
+                    .style()
+                    .get_property_value("transform")
+                    .unwrap_or_default();
+                bound.set_value(Some((el.clone(), original)));
+            }
+
+            let style = el.unchecked_ref::<web_sys::HtmlElement>().style();
+
+            match mode {
+                TransformMode::Transform => {
+                    let original = bound
+                        .with_value(|bound| bound.as_ref().map(|(_, original)| original.clone()))
+                        .unwrap_or_default();
+                    let transform = transform.get_untracked();
+                    let _ = style
+                        .set_property("transform", format!("{transform} {original}").trim_end());
+                }
+                TransformMode::Individual => {
+                    let _ = style.set_property(
+                        "translate",
+                        &format!(
+                            "{}px {}px",
+                            translate_x.get_untracked(),
+                            translate_y.get_untracked()
+                        ),
+                    );
+                    let _ = style.set_property("rotate", &format!("{}deg", rotate.get_untracked()));
+                    let _ = style.set_property("scale", &scale.get_untracked().to_string());
+                }
+            }
+        };
+
+        let _ = watch(
+            move || (target.get(), transform.get()),
+            move |_, _, _| {
+                if !is_pending.get_value() {
+                    is_pending.set_value(true);
+                    batch.mutate(write);
+                }
+            },
+            true,
+        );
+
+        on_cleanup(unbind);
+    }
+
+    UseElementTransformReturn {
+        transform: transform.into(),
+    }
+}
+
+/// How [`use_element_transform`] writes the transform to the element.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TransformMode {
+    /// Write the `transform` property and keep the original inline transform after it
+    #[default]
+    Transform,
+
+    /// Write the individual properties `translate`, `rotate` and `scale`
+    Individual,
+}
+
+/// Options for [`use_element_transform_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseElementTransformOptions {
+    /// Horizontal translation in pixels. Defaults to `0.0`.
+    #[builder(into)]
+    translate_x: MaybeSignal<f64>,
+
+    /// Vertical translation in pixels. Defaults to `0.0`.
+    #[builder(into)]
+    translate_y: MaybeSignal<f64>,
+
+    /// Scale factor. Defaults to `1.0`.
+    #[builder(into)]
+    scale: MaybeSignal<f64>,
+
+    /// Clockwise rotation in degrees. Defaults to `0.0`.
+    #[builder(into)]
+    rotate: MaybeSignal<f64>,
+
+    /// How the transform is written. Defaults to `TransformMode::Transform`.
+    mode: TransformMode,
+}
+
+impl Default for UseElementTransformOptions {
+    fn default() -> Self {
+        Self {
+            translate_x: 0.0.into(),
+            translate_y: 0.0.into(),
+            scale: 1.0.into(),
+            rotate: 0.0.into(),
+            mode: TransformMode::default(),
+        }
+    }
+}
+
+/// Return type of [`use_element_transform`].
+pub struct UseElementTransformReturn {
+    /// The generated transform like `"translate(10px, 20px) rotate(0deg) scale(1)"` without the
+    /// original transform of the element
+    pub transform: Signal<String>,
+}
@@ -0,0 +1,143 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{
+    use_font_loading, use_mutation_observer_with_options, use_resize_observer,
+    UseFontLoadingReturn, UseMutationObserverOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+
+/// Reactive check if the content of an element is clipped.
+///
+/// Tells if text is cut off by `text-overflow: ellipsis` or `line-clamp` or if the content
+/// overflows the element in general. This way a tooltip with the full text can be shown only
+/// when it's actually needed.
+///
+/// The check is repeated when the element is resized, when its content changes and when fonts
+/// have been loaded (see [`fn@crate::use_font_loading`]).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Span;
+/// # use leptos_use::{use_element_truncation, UseElementTruncationReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Span>();
+/// let text = "A label that might be too long for its container";
+///
+/// let UseElementTruncationReturn { is_truncated, .. } = use_element_truncation(el);
+///
+/// view! {
+///     <span
+///         node_ref=el
+///         style="display: block; overflow: hidden; white-space: nowrap; text-overflow: ellipsis"
+///         title=move || is_truncated.get().then_some(text)
+///     >
+///         {text}
+///     </span>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the content is never considered truncated.
+pub fn use_element_truncation<El, T>(target: El) -> UseElementTruncationReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_element_truncation_with_options(target, UseElementTruncationOptions::default())
+}
+
+/// Version of [`use_element_truncation`] that takes a `UseElementTruncationOptions`. See [`use_element_truncation`] for how to use.
+pub fn use_element_truncation_with_options<El, T>(
+    target: El,
+    options: UseElementTruncationOptions,
+) -> UseElementTruncationReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseElementTruncationOptions { tolerance } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let (is_overflowing_x, set_overflowing_x) = create_signal(false);
+    let (is_overflowing_y, set_overflowing_y) = create_signal(false);
+
+    let update = move || {
+        let (x, y) = target
+            .get_untracked()
+            .map(|el| {
+                (
+                    f64::from(el.scroll_width() - el.client_width()) > tolerance,
+                    f64::from(el.scroll_height() - el.client_height()) > tolerance,
+                )
+            })
+            .unwrap_or_default();
+
+        if x != is_overflowing_x.get_untracked() {
+            set_overflowing_x.set(x);
+        }
+        if y != is_overflowing_y.get_untracked() {
+            set_overflowing_y.set(y);
+        }
+    };
+
+    let _ = use_resize_observer(target, move |_, _| update());
+
+    let _ = use_mutation_observer_with_options(
+        target,
+        move |_, _| update(),
+        UseMutationObserverOptions::default()
+            .subtree(true)
+            .child_list(true)
+            .character_data(true),
+    );
+
+    let UseFontLoadingReturn { fonts_changed, .. } = use_font_loading();
+
+    let _ = watch(
+        move || (target.get(), fonts_changed.get()),
+        move |_, _, _| update(),
+        true,
+    );
+
+    UseElementTruncationReturn {
+        is_truncated: Signal::derive(move || is_overflowing_x.get() || is_overflowing_y.get()),
+        is_overflowing_x: is_overflowing_x.into(),
+        is_overflowing_y: is_overflowing_y.into(),
+    }
+}
+
+/// Options for [`use_element_truncation_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseElementTruncationOptions {
+    /// Number of pixels the content may overflow before it's considered truncated. Helps
+    /// against rounding issues with fractional sizes. Defaults to `0.0`.
+    tolerance: f64,
+}
+
+impl Default for UseElementTruncationOptions {
+    fn default() -> Self {
+        Self { tolerance: 0.0 }
+    }
+}
+
+/// Return type of [`use_element_truncation`].
+pub struct UseElementTruncationReturn {
+    /// `true` if the content is clipped in either direction
+    pub is_truncated: Signal<bool>,
+
+    /// `true` if the content is wider than the element
+    pub is_overflowing_x: Signal<bool>,
+
+    /// `true` if the content is higher than the element
+    pub is_overflowing_y: Signal<bool>,
+}
@@ -34,6 +34,9 @@ use crate::{use_intersection_observer_with_options, UseIntersectionObserverOptio
 /// # }
 /// ```
 ///
+/// Elements with the same `viewport` share one `IntersectionObserver`. See
+/// [`fn@crate::use_intersection_observer`] for details.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this returns a `Signal` that always contains the value `false`.
@@ -0,0 +1,246 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::now;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+
+/// Collects the errors of the surrounding `<ErrorBoundary/>` into a reactive log and reports
+/// them to a remote service.
+///
+/// Call this in a component that is a child of an `ErrorBoundary`. Every error that is caught
+/// by the boundary is added to the returned `errors` once, together with where and when it
+/// happened. If the same error occurs again after the boundary has been reset it's added again.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_error_boundary_reporter_with_options, UseErrorBoundaryReporterOptions};
+/// #
+/// #[component]
+/// fn ErrorReporter() -> impl IntoView {
+///     let _ = use_error_boundary_reporter_with_options(
+///         UseErrorBoundaryReporterOptions::default()
+///             .component("Checkout")
+///             .sample_rate(0.5)
+///             .reporter(|error| {
+///                 // send `error` to your error tracking service
+///             }),
+///     );
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let value = Ok::<_, std::num::ParseIntError>(5);
+/// view! {
+///     <ErrorBoundary fallback=|errors| view! { <p>"Something went wrong"</p> }>
+///         <ErrorReporter />
+///         {value}
+///     </ErrorBoundary>
+/// }
+/// # }
+/// ```
+///
+/// ### Sampling and Rate Limits
+///
+/// Only a fraction of the errors given by the option `sample_rate` is passed to `reporter`. On
+/// top of that at most `max_reports_per_minute` errors are reported by one hook to protect your
+/// service from a flood of reports when something goes wrong repeatedly. The log in `errors`
+/// always contains all errors up to `max_log_size`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the errors are collected into `errors` but the `reporter` isn't called.
+pub fn use_error_boundary_reporter() -> UseErrorBoundaryReporterReturn<impl Fn() + Clone> {
+    use_error_boundary_reporter_with_options(UseErrorBoundaryReporterOptions::default())
+}
+
+/// Version of [`use_error_boundary_reporter`] that takes a `UseErrorBoundaryReporterOptions`. See [`use_error_boundary_reporter`] for how to use.
+pub fn use_error_boundary_reporter_with_options(
+    options: UseErrorBoundaryReporterOptions,
+) -> UseErrorBoundaryReporterReturn<impl Fn() + Clone> {
+    let UseErrorBoundaryReporterOptions {
+        component,
+        metadata,
+        reporter,
+        sample_rate,
+        max_reports_per_minute,
+        max_log_size,
+    } = options;
+
+    let (log, set_log) = create_signal(Vec::<ReportedError>::new());
+
+    // Keys of the errors in the boundary that have been logged already
+    let logged_keys = store_value(HashSet::<String>::new());
+    // Times of the reports within the last minute
+    let report_times = store_value(VecDeque::<f64>::new());
+
+    let report = move |error: &ReportedError| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            if sample_rate < 1.0 && js_sys::Math::random() >= sample_rate {
+                return;
+            }
+
+            let time = now();
+            let allowed = report_times.try_update_value(|times| {
+                while times.front().is_some_and(|sent| time - sent >= 60_000.0) {
+                    times.pop_front();
+                }
+
+                let allowed = times.len() < max_reports_per_minute;
+                if allowed {
+                    times.push_back(time);
+                }
+                allowed
+            });
+
+            if allowed == Some(true) {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                reporter(error);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        }
+    };
+
+    match use_context::<RwSignal<Errors>>() {
+        Some(boundary_errors) => {
+            let _ = watch(
+                move || {
+                    boundary_errors.with(|errors| {
+                        errors
+                            .iter()
+                            .map(|(key, error)| (format!("{key:?}"), error.to_string()))
+                            .collect::<Vec<_>>()
+                    })
+                },
+                move |current, _, _| {
+                    // forget errors that have been removed so they are logged again if they recur
+                    logged_keys.update_value(|keys| {
+                        keys.retain(|key| current.iter().any(|(current_key, _)| current_key == key))
+                    });
+
+                    for (key, message) in current {
+                        let is_new = logged_keys.try_update_value(|keys| keys.insert(key.clone()));
+                        if is_new != Some(true) {
+                            continue;
+                        }
+
+                        let error = ReportedError {
+                            message: message.clone(),
+                            component: component.clone(),
+                            metadata: metadata.clone(),
+                            url: current_url(),
+                            timestamp: now(),
+                        };
+
+                        set_log.update(|log| {
+                            log.push(error.clone());
+
+                            if log.len() > max_log_size {
+                                log.drain(..log.len() - max_log_size);
+                            }
+                        });
+
+                        report(&error);
+                    }
+                },
+                true,
+            );
+        }
+        None => {
+            logging::debug_warn!(
+                "use_error_boundary_reporter has been called outside of an <ErrorBoundary/>"
+            );
+        }
+    }
+
+    UseErrorBoundaryReporterReturn {
+        errors: log.into(),
+        clear: move || set_log.set(vec![]),
+    }
+}
+
+/// The URL of the current page. `None` on the server.
+fn current_url() -> Option<String> {
+    #[cfg(feature = "ssr")]
+    return None;
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        window().location().href().ok()
+    }
+}
+
+/// An error that has been caught by an `ErrorBoundary`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportedError {
+    /// The error message
+    pub message: String,
+    /// The option `component` of the hook that has caught the error
+    pub component: Option<String>,
+    /// The option `metadata` of the hook that has caught the error
+    pub metadata: Vec<(String, String)>,
+    /// The URL of the page where the error happened. `None` on the server.
+    pub url: Option<String>,
+    /// When the error has been caught in milliseconds since the Unix epoch
+    pub timestamp: f64,
+}
+
+/// Options for [`use_error_boundary_reporter_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseErrorBoundaryReporterOptions {
+    /// Name of the component or part of the app that the error boundary surrounds. Added to
+    /// every error. Defaults to `None`.
+    #[builder(into)]
+    component: Option<String>,
+
+    /// Additional key value pairs that are added to every error like the id of the current
+    /// user. Defaults to none.
+    metadata: Vec<(String, String)>,
+
+    /// Called with every error that is sampled and not rate limited to send it to a remote
+    /// service. Defaults to doing nothing.
+    reporter: Rc<dyn Fn(&ReportedError)>,
+
+    /// Fraction between `0.0` and `1.0` of the errors that is passed to `reporter`.
+    /// Defaults to `1.0`.
+    sample_rate: f64,
+
+    /// Maximum number of errors passed to `reporter` per minute. Defaults to `10`.
+    max_reports_per_minute: usize,
+
+    /// Maximum number of errors kept in `errors`. The oldest ones are removed first.
+    /// Defaults to `100`.
+    max_log_size: usize,
+}
+
+impl Default for UseErrorBoundaryReporterOptions {
+    fn default() -> Self {
+        Self {
+            component: None,
+            metadata: vec![],
+            reporter: Rc::new(|_| {}),
+            sample_rate: 1.0,
+            max_reports_per_minute: 10,
+            max_log_size: 100,
+        }
+    }
+}
+
+/// Return type of [`use_error_boundary_reporter`].
+pub struct UseErrorBoundaryReporterReturn<ClearFn>
+where
+    ClearFn: Fn() + Clone,
+{
+    /// All errors caught by the surrounding error boundary, the oldest first
+    pub errors: Signal<Vec<ReportedError>>,
+    /// Empties `errors`
+    pub clear: ClearFn,
+}
@@ -0,0 +1,299 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, use_event_listener};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+/// Collect uncaught errors, unhandled promise rejections and browser reports into a reactive buffer.
+///
+/// This listens to
+/// - [`error`](https://developer.mozilla.org/en-US/docs/Web/API/Window/error_event) events on `window`,
+/// - [`unhandledrejection`](https://developer.mozilla.org/en-US/docs/Web/API/Window/unhandledrejection_event) events on `window` and
+/// - reports delivered to a [`ReportingObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ReportingObserver)
+///   (deprecations, interventions, ...) if the browser supports it.
+///
+/// Every captured error is passed to the `reporter` callback which makes it easy to plug in your
+/// telemetry of choice. The buffer can be used to build an in-app error console.
+///
+/// ## Demo
+///
+/// [Link to Demo](https://github.com/Synphonyte/leptos-use/tree/main/examples/use_error_reporting)
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_error_reporting, UseErrorReportingReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseErrorReportingReturn { errors, clear } = use_error_reporting();
+///
+/// view! {
+///     <ul>
+///         {move || errors.get().into_iter().map(|err| view! { <li>{err.to_string()}</li> }).collect_view()}
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ### Reporter
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::logging::log;
+/// # use leptos_use::{use_error_reporting_with_options, UseErrorReportingOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let errors = use_error_reporting_with_options(
+///     UseErrorReportingOptions::default()
+///         .reporter(|err| log!("Send this to the server: {}", err))
+///         .report_types(vec!["deprecation".to_string()]),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the buffer always stays empty and `clear` does nothing.
+///
+/// ## See also
+///
+/// - [`use_csp_violation`]
+pub fn use_error_reporting() -> UseErrorReportingReturn<impl Fn() + Clone> {
+    use_error_reporting_with_options(UseErrorReportingOptions::default())
+}
+
+/// Version of [`use_error_reporting`] that takes a `UseErrorReportingOptions`. See [`use_error_reporting`] for how to use.
+pub fn use_error_reporting_with_options(
+    options: UseErrorReportingOptions,
+) -> UseErrorReportingReturn<impl Fn() + Clone> {
+    let UseErrorReportingOptions {
+        max_entries,
+        reporter,
+        capture_errors,
+        capture_unhandled_rejections,
+        capture_reports,
+        report_types,
+    } = options;
+
+    let (errors, set_errors) = create_signal(Vec::<ReportedError>::new());
+
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let push = move |error: ReportedError| {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            reporter(&error);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            set_errors.update(|errors| {
+                errors.push(error);
+
+                if errors.len() > max_entries {
+                    let overflow = errors.len() - max_entries;
+                    errors.drain(..overflow);
+                }
+            });
+        };
+
+        if capture_errors {
+            let push = push.clone();
+
+            let _ = use_event_listener(
+                window(),
+                ev::Custom::<web_sys::ErrorEvent>::new("error"),
+                move |event| {
+                    push(ReportedError::Error {
+                        message: event.message(),
+                        filename: event.filename(),
+                        line: event.lineno(),
+                        column: event.colno(),
+                        error: event.error(),
+                    })
+                },
+            );
+        }
+
+        if capture_unhandled_rejections {
+            let push = push.clone();
+
+            let _ = use_event_listener(
+                window(),
+                ev::Custom::<web_sys::PromiseRejectionEvent>::new("unhandledrejection"),
+                move |event| {
+                    push(ReportedError::UnhandledRejection {
+                        reason: event.reason(),
+                    })
+                },
+            );
+        }
+
+        if capture_reports && js!("ReportingObserver" in &window()) {
+            observe_reports(push, report_types);
+        }
+    }}
+
+    UseErrorReportingReturn {
+        errors: errors.into(),
+        clear: move || set_errors.update(|errors| errors.clear()),
+    }
+}
+
+/// Creates a `ReportingObserver` (which is not available in `web_sys`) and disconnects it on cleanup.
+fn observe_reports(push: impl Fn(ReportedError) + 'static, report_types: Option<Vec<String>>) {
+    let callback = Closure::<dyn Fn(js_sys::Array)>::new(move |reports: js_sys::Array| {
+        for report in reports.iter() {
+            let type_ = js!(report["type"])
+                .ok()
+                .and_then(|v| v.as_string())
+                .unwrap_or_default();
+            let url = js!(report["url"])
+                .ok()
+                .and_then(|v| v.as_string())
+                .unwrap_or_default();
+            let body = js!(report["body"]).unwrap_or(JsValue::UNDEFINED);
+
+            push(ReportedError::Report { type_, url, body });
+        }
+    })
+    .into_js_value();
+
+    let options = js_sys::Object::new();
+    js!(options["buffered"] = true);
+    if let Some(report_types) = report_types {
+        let types = report_types
+            .into_iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>();
+        js!(options["types"] = types);
+    }
+
+    let window = window();
+    let Ok(constructor) = js!(window["ReportingObserver"]) else {
+        return;
+    };
+
+    let Ok(observer) = js_sys::Reflect::construct(
+        constructor.unchecked_ref(),
+        &js_sys::Array::of2(&callback, &options),
+    ) else {
+        return;
+    };
+
+    let call_method = move |name: &str| {
+        if let Ok(method) = js_sys::Reflect::get(&observer, &name.into()) {
+            let _ = method.unchecked_ref::<js_sys::Function>().call0(&observer);
+        }
+    };
+
+    call_method("observe");
+
+    on_cleanup(move || call_method("disconnect"));
+}
+
+/// An error captured by [`use_error_reporting`].
+#[derive(Clone, Debug)]
+pub enum ReportedError {
+    /// An uncaught error from an `error` event.
+    Error {
+        message: String,
+        filename: String,
+        line: u32,
+        column: u32,
+        /// The thrown value. Often a JS `Error` object.
+        error: JsValue,
+    },
+
+    /// A promise was rejected and no handler was attached.
+    UnhandledRejection {
+        /// The value the promise was rejected with.
+        reason: JsValue,
+    },
+
+    /// A report delivered by the `ReportingObserver`.
+    Report {
+        /// The type of the report like `"deprecation"` or `"intervention"`.
+        type_: String,
+        /// The URL of the document that generated the report.
+        url: String,
+        /// The body of the report. Its shape depends on `type_`.
+        body: JsValue,
+    },
+}
+
+impl std::fmt::Display for ReportedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportedError::Error {
+                message,
+                filename,
+                line,
+                column,
+                ..
+            } => write!(f, "{message} ({filename}:{line}:{column})"),
+            ReportedError::UnhandledRejection { reason } => {
+                write!(f, "Unhandled rejection: {reason:?}")
+            }
+            ReportedError::Report { type_, url, .. } => write!(f, "Report '{type_}' from {url}"),
+        }
+    }
+}
+
+/// Options for [`use_error_reporting_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseErrorReportingOptions {
+    /// The maximum number of errors that are kept in the buffer. Older errors are dropped first.
+    /// Defaults to `100`.
+    max_entries: usize,
+
+    /// Called for every captured error. Use this to forward errors to your telemetry.
+    reporter: Rc<dyn Fn(&ReportedError)>,
+
+    /// Capture `error` events on `window`. Defaults to `true`.
+    capture_errors: bool,
+
+    /// Capture `unhandledrejection` events on `window`. Defaults to `true`.
+    capture_unhandled_rejections: bool,
+
+    /// Capture reports of a `ReportingObserver` if supported by the browser. Defaults to `true`.
+    capture_reports: bool,
+
+    /// The report types the `ReportingObserver` should observe. `None` means all types.
+    /// Defaults to `None`.
+    #[builder(into)]
+    report_types: Option<Vec<String>>,
+}
+
+impl Default for UseErrorReportingOptions {
+    fn default() -> Self {
+        Self {
+            max_entries: 100,
+            reporter: Rc::new(|_| {}),
+            capture_errors: true,
+            capture_unhandled_rejections: true,
+            capture_reports: true,
+            report_types: None,
+        }
+    }
+}
+
+/// Return type of [`use_error_reporting`].
+pub struct UseErrorReportingReturn<ClearFn>
+where
+    ClearFn: Fn() + Clone,
+{
+    /// The buffered errors, oldest first.
+    pub errors: Signal<Vec<ReportedError>>,
+
+    /// Empties the buffer.
+    pub clear: ClearFn,
+}
@@ -1,4 +1,5 @@
 use crate::core::ElementMaybeSignal;
+use crate::ManagedAbortController;
 use cfg_if::cfg_if;
 use default_struct_builder::DefaultBuilder;
 use leptos::ev::EventDescriptor;
@@ -191,6 +192,10 @@ where
 
         on_cleanup(stop.clone());
 
+        if let Some(abort_controller) = options.abort_controller {
+            abort_controller.on_abort(stop.clone());
+        }
+
         stop
     }
 }
@@ -224,6 +229,11 @@ pub struct UseEventListenerOptions {
     /// to learn more.
     #[builder(into)]
     passive: Option<bool>,
+
+    /// If set, the listener is removed when the controller is aborted.
+    /// See [`crate::use_abort_controller`]. Defaults to `None`.
+    #[builder(into)]
+    abort_controller: Option<ManagedAbortController>,
 }
 
 impl UseEventListenerOptions {
@@ -233,6 +243,7 @@ impl UseEventListenerOptions {
             capture,
             once,
             passive,
+            ..
         } = self;
 
         let mut options = web_sys::AddEventListenerOptions::new();
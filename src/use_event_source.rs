@@ -1,13 +1,19 @@
-use crate::core::ConnectionReadyState;
-use crate::{js, use_event_listener, ReconnectLimit};
-use codee::Decoder;
+use crate::core::{
+    resolve_reconnect_policy, ConnectionReadyState, ReconnectCountdown, ReconnectLimit,
+    ReconnectPolicy,
+};
+use crate::utils::{SseParsed, SseParser, StringDecoder};
+use crate::{js, js_fut, signal_to_stream, use_event_listener, ManagedAbortController};
 use default_struct_builder::DefaultBuilder;
+use futures_util::stream::{Stream, StreamExt};
+use leptos::leptos_dom::helpers::TimeoutHandle;
 use leptos::*;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::future;
 use std::marker::PhantomData;
 use std::rc::Rc;
-use std::time::Duration;
 use thiserror::Error;
+use wasm_bindgen::prelude::*;
 
 /// Reactive [EventSource](https://developer.mozilla.org/en-US/docs/Web/API/EventSource)
 ///
@@ -18,8 +24,8 @@ use thiserror::Error;
 ///
 /// ## Usage
 ///
-/// Values are decoded via the given decoder. You can use any of the string codecs or a
-/// binary codec wrapped in [`Base64`].
+/// Values are decoded via the given decoder. You can use any of the string or binary codecs.
+/// Binary codecs expect the event data to be base64 encoded (see [`StringDecoder`]).
 ///
 /// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
 ///   available and what feature flags they require.
@@ -74,6 +80,48 @@ use thiserror::Error;
 /// # }
 /// ```
 ///
+/// ### Typed Named Events
+///
+/// If the named events carry different kinds of data, give each of them its own
+/// [`EventSourceChannel`]. A channel has its own type and codec and its own `data` and `error`
+/// signals. Events that are received on a channel don't change `data` of the return value.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_event_source_with_options, EventSourceChannel, UseEventSourceOptions};
+/// # use codee::string::{FromToStringCodec, JsonSerdeCodec};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// pub struct Notice {
+///     pub text: String,
+/// }
+///
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// pub struct Progress {
+///     pub done: u32,
+///     pub total: u32,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let notice = EventSourceChannel::<Notice, JsonSerdeCodec>::new("notice");
+/// let progress = EventSourceChannel::<Progress, JsonSerdeCodec>::new("progress");
+///
+/// let _ = use_event_source_with_options::<String, FromToStringCodec>(
+///     "https://event-source-url",
+///     UseEventSourceOptions::default()
+///         .channel(notice)
+///         .channel(progress),
+/// );
+///
+/// view! {
+///     <p>{move || notice.data.get().map(|notice| notice.text)}</p>
+///     <p>{move || progress.data.get().map(|p| format!("{} / {}", p.done, p.total))}</p>
+/// }
+/// # }
+/// ```
+///
 /// ### Immediate
 ///
 /// Auto-connect (enabled by default).
@@ -89,7 +137,8 @@ use thiserror::Error;
 ///
 /// ```
 /// # use leptos::*;
-/// # use leptos_use::{use_event_source_with_options, UseEventSourceReturn, UseEventSourceOptions, ReconnectLimit};
+/// # use leptos_use::{use_event_source_with_options, UseEventSourceReturn, UseEventSourceOptions};
+/// # use leptos_use::core::ReconnectLimit;
 /// # use codee::string::FromToStringCodec;
 /// #
 /// # #[component]
@@ -107,18 +156,131 @@ use thiserror::Error;
 /// # }
 /// ```
 ///
-/// To disable auto-reconnection, set `reconnect_limit` to `0`.
+/// To disable auto-reconnection, set `reconnect_limit` to `ReconnectLimit::Limited(0)`.
+///
+/// For backoff strategies, jitter and a time limit provide a [`BackoffReconnectPolicy`] or your
+/// own [`ReconnectPolicy`] instead.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_event_source_with_options, UseEventSourceReturn, UseEventSourceOptions};
+/// # use leptos_use::core::{BackoffReconnectPolicy, ReconnectBackoff, ReconnectLimit};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseEventSourceReturn {
+///     reconnect_attempts, ..
+/// } = use_event_source_with_options::<bool, FromToStringCodec>(
+///     "https://event-source-url",
+///     UseEventSourceOptions::default().reconnect_policy(
+///         BackoffReconnectPolicy::default()
+///             .limit(ReconnectLimit::Infinite)
+///             .interval(1000)
+///             .backoff(ReconnectBackoff::Exponential { factor: 2.0 })
+///             .max_interval(60_000)
+///             .jitter(0.2),
+///     ),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// While waiting for the next attempt `reconnect_countdown` contains the remaining seconds.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_event_source, UseEventSourceReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseEventSourceReturn {
+///     reconnect_countdown, ..
+/// } = use_event_source::<String, FromToStringCodec>("https://event-source-url");
+///
+/// view! {
+///     <Show when=move || reconnect_countdown.get().is_some()>
+///         <p>"Reconnecting in " {reconnect_countdown} "s..."</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ### Fetch Transport
+///
+/// The native `EventSource` can't send custom headers or a request body. If you need this, for
+/// example to send an `Authorization` header, use the fetch transport. It reads the event stream
+/// from the body of a `fetch` request and supports named events, the `Last-Event-ID` header and
+/// the `retry` field just like the native `EventSource`. When the server ends the stream the
+/// connection is re-established according to the `reconnect_policy`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_event_source_with_options, EventSourceTransport, FetchTransportOptions,
+/// #     UseEventSourceOptions, UseEventSourceReturn,
+/// # };
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (token, set_token) = create_signal("secret".to_string());
+///
+/// let UseEventSourceReturn { data, .. } = use_event_source_with_options::<String, FromToStringCodec>(
+///     "https://event-source-url",
+///     UseEventSourceOptions::default().transport(EventSourceTransport::Fetch(
+///         FetchTransportOptions::default()
+///             .method("POST")
+///             .headers(Signal::derive(move || {
+///                 vec![("Authorization".to_string(), format!("Bearer {}", token.get()))]
+///             }))
+///             .body(r#"{"topic": "news"}"#.to_string()),
+///     )),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// If you only need to send headers there is the shortcut `headers` which switches to the fetch
+/// transport.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_event_source_with_options, UseEventSourceOptions, UseEventSourceReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseEventSourceReturn { data, .. } = use_event_source_with_options::<String, FromToStringCodec>(
+///     "https://event-source-url",
+///     UseEventSourceOptions::default()
+///         .headers(vec![("Authorization".to_string(), "Bearer secret".to_string())]),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The id of the last received event is sent in the `Last-Event-ID` header when reconnecting so
+/// the server can resume the stream where it left off.
+///
+/// If several components need the same stream, use [`fn@crate::use_shared_event_source`] to open
+/// only one connection for all of them.
 ///
 /// ## Server-Side Rendering
 ///
 /// On the server-side, `use_event_source` will always return `ready_state` as `ConnectionReadyState::Closed`,
 /// `data`, `event` and `error` will always be `None`, and `open` and `close` will do nothing.
+/// The `data` of channels stays `None` as well.
 pub fn use_event_source<T, C>(
     url: &str,
 ) -> UseEventSourceReturn<T, C::Error, impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
 where
     T: Clone + PartialEq + 'static,
-    C: Decoder<T, Encoded = str>,
+    C: StringDecoder<T>,
 {
     use_event_source_with_options::<T, C>(url, UseEventSourceOptions::<T>::default())
 }
@@ -130,18 +292,25 @@ pub fn use_event_source_with_options<T, C>(
 ) -> UseEventSourceReturn<T, C::Error, impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
 where
     T: Clone + PartialEq + 'static,
-    C: Decoder<T, Encoded = str>,
+    C: StringDecoder<T>,
 {
     let UseEventSourceOptions {
         reconnect_limit,
         reconnect_interval,
+        reconnect_policy,
         on_failed,
         immediate,
         named_events,
+        channels,
         with_credentials,
+        transport,
+        abort_controller,
         _marker,
     } = options;
 
+    let reconnect_policy =
+        resolve_reconnect_policy(reconnect_policy, reconnect_limit, reconnect_interval);
+
     let url = url.to_owned();
 
     let (event, set_event) = create_signal(None::<web_sys::Event>);
@@ -151,11 +320,26 @@ where
     let (error, set_error) = create_signal(None::<UseEventSourceError<C::Error>>);
 
     let explicitly_closed = Rc::new(Cell::new(false));
-    let retried = Rc::new(Cell::new(0));
+    let (reconnect_attempts, set_reconnect_attempts) = create_signal(0_u64);
+    let connection_lost_at = Rc::new(Cell::new(None::<f64>));
+    let reconnect_countdown = ReconnectCountdown::new();
+    let reconnect_timer = store_value(None::<TimeoutHandle>);
+
+    let clear_reconnect_timer = move || {
+        if let Some(timer) = reconnect_timer.try_update_value(Option::take).flatten() {
+            timer.clear();
+        }
+    };
+
+    // state of the fetch transport
+    let fetch_abort = store_value(None::<web_sys::AbortController>);
+    let fetch_connection_id = Rc::new(Cell::new(0_u64));
+    let last_event_id = Rc::new(RefCell::new(String::new()));
+    let server_retry = Rc::new(Cell::new(None::<u64>));
 
     let set_data_from_string = move |data_string: Option<String>| {
         if let Some(data_string) = data_string {
-            match C::decode(&data_string) {
+            match C::decode_string(&data_string) {
                 Ok(data) => set_data.set(Some(data)),
                 Err(err) => set_error.set(Some(UseEventSourceError::Deserialize(err))),
             }
@@ -166,49 +350,227 @@ where
         let explicitly_closed = Rc::clone(&explicitly_closed);
 
         move || {
+            let mut was_connected = false;
+
             if let Some(event_source) = event_source.get_untracked() {
                 event_source.close();
                 set_event_source.set(None);
+                was_connected = true;
+            }
+
+            if let Some(controller) = fetch_abort.try_update_value(|c| c.take()).flatten() {
+                controller.abort();
+                was_connected = true;
+            }
+
+            clear_reconnect_timer();
+            reconnect_countdown.stop();
+
+            if was_connected {
                 set_ready_state.set(ConnectionReadyState::Closed);
                 explicitly_closed.set(true);
             }
         }
     };
 
+    let on_opened = {
+        let connection_lost_at = Rc::clone(&connection_lost_at);
+
+        move || {
+            set_ready_state.set(ConnectionReadyState::Open);
+            set_error.set(None);
+            set_reconnect_attempts.set(0);
+            connection_lost_at.set(None);
+        }
+    };
+
     let init = store_value(None::<Rc<dyn Fn()>>);
 
-    init.set_value(Some(Rc::new({
+    let reconnect = {
         let explicitly_closed = Rc::clone(&explicitly_closed);
-        let retried = Rc::clone(&retried);
+        let connection_lost_at = Rc::clone(&connection_lost_at);
+        let server_retry = Rc::clone(&server_retry);
 
         move || {
-            use wasm_bindgen::prelude::*;
+            if explicitly_closed.get() {
+                return;
+            }
+
+            let now = js_sys::Date::now();
+            let lost_at = connection_lost_at.get().unwrap_or(now);
+            connection_lost_at.set(Some(lost_at));
+
+            let attempts = reconnect_attempts.get_untracked();
+
+            if reconnect_policy.should_reconnect(attempts, (now - lost_at) as u64) {
+                // the server can change the reconnection time with the `retry` field
+                let delay = match server_retry.get() {
+                    Some(retry) => reconnect_policy.delay_with_interval(attempts, retry),
+                    None => reconnect_policy.delay(attempts),
+                };
+                reconnect_countdown.start(delay);
+
+                clear_reconnect_timer();
+                reconnect_timer.set_value(
+                    set_timeout_with_handle(
+                        move || {
+                            reconnect_timer.set_value(None);
+                            reconnect_countdown.stop();
+
+                            if let Some(init) = init.get_value() {
+                                set_reconnect_attempts.update(|current| *current += 1);
+                                init();
+                            }
+                        },
+                        delay,
+                    )
+                    .ok(),
+                );
+            } else {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_failed();
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        }
+    };
 
+    init.set_value(Some(Rc::new({
+        let explicitly_closed = Rc::clone(&explicitly_closed);
+
+        move || {
             if explicitly_closed.get() {
                 return;
             }
 
+            // don't leak the previous connection
+            if let Some(event_source) = event_source.get_untracked() {
+                event_source.close();
+            }
+            if let Some(controller) = fetch_abort.try_update_value(|c| c.take()).flatten() {
+                controller.abort();
+            }
+
+            set_ready_state.set(ConnectionReadyState::Connecting);
+
+            let fetch_options = match &transport {
+                EventSourceTransport::Native => None,
+                EventSourceTransport::Fetch(fetch_options) => Some(fetch_options.clone()),
+            };
+
+            if let Some(fetch_options) = fetch_options {
+                let controller = web_sys::AbortController::new().unwrap_throw();
+                fetch_abort.set_value(Some(controller.clone()));
+
+                let connection_id = fetch_connection_id.get() + 1;
+                fetch_connection_id.set(connection_id);
+
+                let url = url.clone();
+                let named_events = named_events.clone();
+                let channels = channels.clone();
+                let fetch_connection_id = Rc::clone(&fetch_connection_id);
+                let explicitly_closed = Rc::clone(&explicitly_closed);
+                let last_event_id = Rc::clone(&last_event_id);
+                let server_retry = Rc::clone(&server_retry);
+                let on_opened = on_opened.clone();
+                let reconnect = reconnect.clone();
+
+                spawn_local(async move {
+                    let mut parser = SseParser::new(last_event_id.borrow().clone());
+
+                    let result = read_event_stream(
+                        &url,
+                        &fetch_options,
+                        with_credentials,
+                        &controller.signal(),
+                        &mut parser,
+                        on_opened,
+                        |parsed: SseParsed| {
+                            if let Some(retry) = parsed.retry {
+                                server_retry.set(Some(retry));
+                            }
+
+                            for event in parsed.events {
+                                if event.event_type == "message" {
+                                    set_data_from_string(Some(event.data));
+                                    continue;
+                                }
+
+                                let is_named = named_events.contains(&event.event_type);
+                                let receivers = channels
+                                    .iter()
+                                    .filter(|(name, _)| *name == event.event_type)
+                                    .map(|(_, receive)| receive)
+                                    .collect::<Vec<_>>();
+
+                                if !is_named && receivers.is_empty() {
+                                    continue;
+                                }
+
+                                let mut event_init = web_sys::MessageEventInit::new();
+                                event_init
+                                    .data(&JsValue::from_str(&event.data))
+                                    .last_event_id(&event.last_event_id);
+
+                                if let Ok(e) = web_sys::MessageEvent::new_with_event_init_dict(
+                                    &event.event_type,
+                                    &event_init,
+                                ) {
+                                    set_event.set(Some(e.into()));
+                                }
+
+                                for receive in receivers {
+                                    receive(&event.data);
+                                }
+
+                                if is_named {
+                                    set_data_from_string(Some(event.data));
+                                }
+                            }
+                        },
+                    )
+                    .await;
+
+                    *last_event_id.borrow_mut() = parser.last_event_id().to_string();
+
+                    // closed or replaced by a new connection in the meantime
+                    if fetch_connection_id.get() != connection_id || explicitly_closed.get() {
+                        return;
+                    }
+
+                    set_ready_state.set(ConnectionReadyState::Closed);
+                    if let Err(err) = result {
+                        set_error.set(Some(UseEventSourceError::Fetch(err)));
+                    }
+
+                    // like the native `EventSource` reconnect when the server ends the stream
+                    reconnect();
+                });
+
+                return;
+            }
+
             let mut event_src_opts = web_sys::EventSourceInit::new();
             event_src_opts.with_credentials(with_credentials);
 
             let es = web_sys::EventSource::new_with_event_source_init_dict(&url, &event_src_opts)
                 .unwrap_throw();
 
-            set_ready_state.set(ConnectionReadyState::Connecting);
-
             set_event_source.set(Some(es.clone()));
 
-            let on_open = Closure::wrap(Box::new(move |_: web_sys::Event| {
-                set_ready_state.set(ConnectionReadyState::Open);
-                set_error.set(None);
+            let on_open = Closure::wrap(Box::new({
+                let on_opened = on_opened.clone();
+
+                move |_: web_sys::Event| on_opened()
             }) as Box<dyn FnMut(web_sys::Event)>);
             es.set_onopen(Some(on_open.as_ref().unchecked_ref()));
             on_open.forget();
 
             let on_error = Closure::wrap(Box::new({
-                let explicitly_closed = Rc::clone(&explicitly_closed);
-                let retried = Rc::clone(&retried);
-                let on_failed = Rc::clone(&on_failed);
+                let reconnect = reconnect.clone();
                 let es = es.clone();
 
                 move |e: web_sys::Event| {
@@ -217,32 +579,9 @@ where
 
                     // only reconnect if EventSource isn't reconnecting by itself
                     // this is the case when the connection is closed (readyState is 2)
-                    if es.ready_state() == 2
-                        && !explicitly_closed.get()
-                        && matches!(reconnect_limit, ReconnectLimit::Limited(_))
-                    {
+                    if es.ready_state() == 2 {
                         es.close();
-
-                        retried.set(retried.get() + 1);
-
-                        if reconnect_limit.is_exceeded_by(retried.get()) {
-                            set_timeout(
-                                move || {
-                                    if let Some(init) = init.get_value() {
-                                        init();
-                                    }
-                                },
-                                Duration::from_millis(reconnect_interval),
-                            );
-                        } else {
-                            #[cfg(debug_assertions)]
-                            let prev = SpecialNonReactiveZone::enter();
-
-                            on_failed();
-
-                            #[cfg(debug_assertions)]
-                            SpecialNonReactiveZone::exit(prev);
-                        }
+                        reconnect();
                     }
                 }
             }) as Box<dyn FnMut(web_sys::Event)>);
@@ -266,6 +605,20 @@ where
                     },
                 );
             }
+
+            for (event_name, receive) in channels.clone() {
+                let _ = use_event_listener(
+                    es.clone(),
+                    ev::Custom::<ev::Event>::new(event_name),
+                    move |e| {
+                        set_event.set(Some(e.clone()));
+                        let data_string = js!(e["data"]).ok().and_then(|d| d.as_string());
+                        if let Some(data_string) = data_string {
+                            receive(&data_string);
+                        }
+                    },
+                );
+            }
         }
     })));
 
@@ -276,12 +629,13 @@ where
         open = {
             let close = close.clone();
             let explicitly_closed = Rc::clone(&explicitly_closed);
-            let retried = Rc::clone(&retried);
+            let connection_lost_at = Rc::clone(&connection_lost_at);
 
             move || {
                 close();
                 explicitly_closed.set(false);
-                retried.set(0);
+                set_reconnect_attempts.set(0);
+                connection_lost_at.set(None);
                 if let Some(init) = init.get_value() {
                     init();
                 }
@@ -300,17 +654,95 @@ where
 
     on_cleanup(close.clone());
 
+    if let Some(abort_controller) = abort_controller {
+        abort_controller.on_abort(close.clone());
+    }
+
     UseEventSourceReturn {
         event_source: event_source.into(),
         event: event.into(),
         data: data.into(),
         ready_state: ready_state.into(),
         error: error.into(),
+        reconnect_attempts: reconnect_attempts.into(),
+        reconnect_countdown: reconnect_countdown.seconds(),
         open,
         close,
     }
 }
 
+/// Sends the request of the fetch transport and feeds the response body into `parser` until the
+/// stream ends or the request is aborted.
+async fn read_event_stream(
+    url: &str,
+    options: &FetchTransportOptions,
+    with_credentials: bool,
+    abort_signal: &web_sys::AbortSignal,
+    parser: &mut SseParser,
+    on_open: impl Fn(),
+    on_parsed: impl Fn(SseParsed),
+) -> Result<(), JsValue> {
+    let headers = web_sys::Headers::new()?;
+    headers.append("Accept", "text/event-stream")?;
+    if !parser.last_event_id().is_empty() {
+        headers.append("Last-Event-ID", parser.last_event_id())?;
+    }
+    for (name, value) in options.headers.get_untracked() {
+        headers.append(&name, &value)?;
+    }
+
+    let mut init = web_sys::RequestInit::new();
+    init.method(&options.method)
+        .headers(&headers)
+        .signal(Some(abort_signal))
+        .credentials(if with_credentials {
+            web_sys::RequestCredentials::Include
+        } else {
+            web_sys::RequestCredentials::SameOrigin
+        });
+    if let Some(body) = &options.body {
+        init.body(Some(&JsValue::from_str(body)));
+    }
+
+    let response = js_fut!(window().fetch_with_str_and_init(url, &init))
+        .await?
+        .unchecked_into::<web_sys::Response>();
+
+    if !response.ok() {
+        return Err(JsValue::from_str(&format!(
+            "Unexpected response status {}",
+            response.status()
+        )));
+    }
+
+    let reader = response
+        .body()
+        .ok_or_else(|| JsValue::from_str("Response has no body"))?
+        .get_reader()
+        .unchecked_into::<web_sys::ReadableStreamDefaultReader>();
+
+    on_open();
+
+    let decoder = web_sys::TextDecoder::new()?;
+    let mut decode_options = web_sys::TextDecodeOptions::new();
+    decode_options.stream(true);
+
+    loop {
+        let chunk = js_fut!(reader.read()).await?;
+
+        if js!(chunk["done"])?.is_truthy() {
+            return Ok(());
+        }
+
+        let mut bytes = js!(chunk["value"])?
+            .unchecked_into::<js_sys::Uint8Array>()
+            .to_vec();
+        let text = decoder.decode_with_u8_array_and_options(&mut bytes, &decode_options)?;
+
+        on_parsed(parser.feed(&text));
+    }
+}
+
 /// Options for [`use_event_source_with_options`].
 #[derive(DefaultBuilder)]
 pub struct UseEventSourceOptions<T>
@@ -318,13 +750,17 @@ where
     T: 'static,
 {
     /// Retry times. Defaults to `ReconnectLimit::Limited(3)`. Use `ReconnectLimit::Infinite` for
-    /// infinite retries.
+    /// infinite retries. Ignored if a `reconnect_policy` is set.
     reconnect_limit: ReconnectLimit,
-
-    /// Retry interval in ms. Defaults to 3000.
+    /// Retry interval in ms. Defaults to 3000. Ignored if a `reconnect_policy` is set.
     reconnect_interval: u64,
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`]. Defaults to `None` which uses `reconnect_limit` and
+    /// `reconnect_interval`.
+    #[builder(skip)]
+    reconnect_policy: Option<Rc<dyn ReconnectPolicy>>,
 
-    /// On maximum retry times reached.
+    /// Called when the `reconnect_policy` doesn't allow any more attempts.
     on_failed: Rc<dyn Fn()>,
 
     /// If `true` the `EventSource` connection will immediately be opened when calling this function.
@@ -336,9 +772,22 @@ where
     #[builder(into)]
     named_events: Vec<String>,
 
+    /// Named events with their own type. Added with [`UseEventSourceOptions::channel`].
+    #[builder(skip)]
+    channels: Vec<(String, Rc<dyn Fn(&str)>)>,
+
     /// If CORS should be set to `include` credentials. Defaults to `false`.
     with_credentials: bool,
 
+    /// How to connect to the server. Defaults to the native `EventSource`.
+    /// See [`EventSourceTransport`].
+    transport: EventSourceTransport,
+
+    /// If set, the connection is closed when the controller is aborted.
+    /// See [`crate::use_abort_controller`]. Defaults to `None`.
+    #[builder(into)]
+    abort_controller: Option<ManagedAbortController>,
+
     _marker: PhantomData<T>,
 }
 
@@ -347,15 +796,165 @@ impl<T> Default for UseEventSourceOptions<T> {
         Self {
             reconnect_limit: ReconnectLimit::default(),
             reconnect_interval: 3000,
+            reconnect_policy: None,
             on_failed: Rc::new(|| {}),
             immediate: true,
             named_events: vec![],
+            channels: vec![],
             with_credentials: false,
+            transport: EventSourceTransport::default(),
+            abort_controller: None,
             _marker: PhantomData,
         }
     }
 }
 
+impl<T> UseEventSourceOptions<T> {
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`]. Replaces `reconnect_limit` and `reconnect_interval`.
+    pub fn reconnect_policy(self, policy: impl ReconnectPolicy + 'static) -> Self {
+        Self {
+            reconnect_policy: Some(Rc::new(policy)),
+            ..self
+        }
+    }
+
+    /// Request headers like `Authorization`. Since the native `EventSource` can't send headers
+    /// this switches to the fetch transport, keeping its other options if it's already used.
+    /// Shortcut for setting the headers of [`EventSourceTransport::Fetch`].
+    pub fn headers(self, headers: impl Into<MaybeSignal<Vec<(String, String)>>>) -> Self {
+        let fetch_options = match self.transport {
+            EventSourceTransport::Fetch(fetch_options) => fetch_options,
+            EventSourceTransport::Native => FetchTransportOptions::default(),
+        };
+
+        Self {
+            transport: EventSourceTransport::Fetch(fetch_options.headers(headers)),
+            ..self
+        }
+    }
+
+    /// Listens for the named event of the channel and decodes its data into the channel
+    /// instead of `data`. Can be called multiple times. See [`EventSourceChannel`].
+    pub fn channel<U, C>(mut self, channel: EventSourceChannel<U, C>) -> Self
+    where
+        U: 'static,
+        C: StringDecoder<U>,
+        C::Error: 'static,
+    {
+        self.channels.push((
+            channel.name.get_value(),
+            Rc::new(move |data: &str| channel.receive(data)),
+        ));
+        self
+    }
+}
+
+/// A named event of [`use_event_source`] that is decoded with its own codec `C` into its own
+/// `data` signal. Add it with [`UseEventSourceOptions::channel`].
+pub struct EventSourceChannel<T, C>
+where
+    T: 'static,
+    C: StringDecoder<T>,
+    C::Error: 'static,
+{
+    /// Latest data received for this event
+    pub data: Signal<Option<T>>,
+
+    /// The latest error decoding the data of this event
+    pub error: Signal<Option<C::Error>>,
+
+    name: StoredValue<String>,
+    set_data: WriteSignal<Option<T>>,
+    set_error: WriteSignal<Option<C::Error>>,
+}
+
+impl<T, C> EventSourceChannel<T, C>
+where
+    T: 'static,
+    C: StringDecoder<T>,
+    C::Error: 'static,
+{
+    /// Creates a channel for the events named `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        let (data, set_data) = create_signal(None::<T>);
+        let (error, set_error) = create_signal(None::<C::Error>);
+
+        Self {
+            data: data.into(),
+            error: error.into(),
+            name: store_value(name.into()),
+            set_data,
+            set_error,
+        }
+    }
+
+    fn receive(&self, data: &str) {
+        match C::decode_string(data) {
+            Ok(data) => self.set_data.set(Some(data)),
+            Err(err) => self.set_error.set(Some(err)),
+        }
+    }
+}
+
+impl<T, C> Clone for EventSourceChannel<T, C>
+where
+    T: 'static,
+    C: StringDecoder<T>,
+    C::Error: 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C> Copy for EventSourceChannel<T, C>
+where
+    T: 'static,
+    C: StringDecoder<T>,
+    C::Error: 'static,
+{
+}
+
+/// How [`use_event_source`] connects to the server.
+#[derive(Clone, Default)]
+pub enum EventSourceTransport {
+    /// The browser's native [`EventSource`](https://developer.mozilla.org/en-US/docs/Web/API/EventSource).
+    #[default]
+    Native,
+
+    /// Reads the event stream from the body of a `fetch` request. This allows custom headers,
+    /// other methods than `GET` and a request body. `event_source` stays `None`.
+    Fetch(FetchTransportOptions),
+}
+
+/// Options of [`EventSourceTransport::Fetch`].
+#[derive(DefaultBuilder, Clone)]
+pub struct FetchTransportOptions {
+    /// The HTTP method of the request. Defaults to `"GET"`.
+    #[builder(into)]
+    method: String,
+
+    /// Additional request headers like `Authorization`. They are read again on every
+    /// reconnect so you can provide a signal to refresh expired credentials. Defaults to none.
+    #[builder(into)]
+    headers: MaybeSignal<Vec<(String, String)>>,
+
+    /// The body of the request. Defaults to `None`.
+    #[builder(into)]
+    body: Option<String>,
+}
+
+impl Default for FetchTransportOptions {
+    fn default() -> Self {
+        Self {
+            method: "GET".to_string(),
+            headers: MaybeSignal::default(),
+            body: None,
+        }
+    }
+}
+
 /// Return type of [`use_event_source`].
 pub struct UseEventSourceReturn<T, Err, OpenFn, CloseFn>
 where
@@ -376,6 +975,13 @@ where
     /// The current error
     pub error: Signal<Option<UseEventSourceError<Err>>>,
 
+    /// Number of reconnection attempts since the connection was lost. Reset to `0` when the
+    /// connection is opened.
+    pub reconnect_attempts: Signal<u64>,
+
+    /// Seconds until the next reconnection attempt or `None` if no attempt is scheduled
+    pub reconnect_countdown: Signal<Option<u64>>,
+
     /// (Re-)Opens the `EventSource` connection
     /// If the current one is active, will close it before opening a new one.
     pub open: OpenFn,
@@ -387,6 +993,41 @@ where
     pub event_source: Signal<Option<web_sys::EventSource>>,
 }
 
+impl<T, Err, OpenFn, CloseFn> UseEventSourceReturn<T, Err, OpenFn, CloseFn>
+where
+    Err: 'static,
+    T: Clone + 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+{
+    /// Returns a stream of the data of every event that is received from now on. The stream
+    /// ends when the reactive owner of the caller is disposed.
+    ///
+    /// ```
+    /// # use leptos::*;
+    /// # use leptos_use::use_event_source;
+    /// # use codee::string::FromToStringCodec;
+    /// # use futures_util::StreamExt;
+    /// #
+    /// # #[component]
+    /// # fn Demo() -> impl IntoView {
+    /// let mut updates =
+    ///     use_event_source::<String, FromToStringCodec>("https://event-source-url").into_stream();
+    ///
+    /// spawn_local(async move {
+    ///     while let Some(update) = updates.next().await {
+    ///         logging::log!("{update}");
+    ///     }
+    /// });
+    /// #
+    /// # view! { }
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> impl Stream<Item = T> {
+        signal_to_stream(self.data).filter_map(future::ready)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum UseEventSourceError<Err> {
     #[error("Error event: {0:?}")]
@@ -394,4 +1035,7 @@ pub enum UseEventSourceError<Err> {
 
     #[error("Error decoding value")]
     Deserialize(Err),
+
+    #[error("Error of the fetch transport: {0:?}")]
+    Fetch(JsValue),
 }
@@ -0,0 +1,592 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js_fut, ManagedAbortController};
+use codee::Decoder;
+use default_struct_builder::DefaultBuilder;
+use futures_util::future::LocalBoxFuture;
+use leptos::*;
+use std::fmt::Debug;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [Fetch API](https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API).
+///
+/// The response body is decoded into `T` via the given string codec.
+///
+/// ## Usage
+///
+/// The request is sent right away and again whenever `url` changes. A request that is still
+/// running when a new one is started is aborted.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::JsonSerdeCodec;
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::{use_fetch, UseFetchReturn};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (id, set_id) = create_signal(1);
+///
+/// let UseFetchReturn {
+///     data,
+///     error,
+///     is_fetching,
+///     ..
+/// } = use_fetch::<User, JsonSerdeCodec>(Signal::derive(move || format!("/api/users/{}", id.get())));
+///
+/// view! {
+///     <Show when=move || !is_fetching.get() fallback=|| "Loading...">
+///         <p>{move || data.get().map(|user| user.name)}</p>
+///     </Show>
+///     <p>{move || error.with(|error| error.as_ref().map(ToString::to_string))}</p>
+///     <button on:click=move |_| set_id.update(|id| *id += 1)>"Next"</button>
+/// }
+/// # }
+/// ```
+///
+/// `execute` sends the request again with the current `url` and `abort` aborts the running
+/// request. With the option `immediate` set to `false` nothing is sent until `execute` is called.
+///
+/// ### Interceptors
+///
+/// `before_fetch` can change the request before it's sent, for example to add an authorization
+/// header. `after_fetch` can change the response before it's decoded.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_fetch_with_options, UseFetchOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (token, set_token) = create_signal("secret".to_string());
+///
+/// let greeting = use_fetch_with_options::<String, FromToStringCodec>(
+///     "/api/greeting",
+///     UseFetchOptions::default()
+///         .before_fetch(move |request| {
+///             request
+///                 .headers
+///                 .push(("Authorization".to_string(), format!("Bearer {}", token.get_untracked())));
+///         })
+///         .retry(3),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Retries
+///
+/// With the option `retry` failed requests are sent again up to that many times, waiting
+/// `retry_delay` milliseconds in between. A request counts as failed if it couldn't be sent or
+/// if the server responded with a status of 500 or above or 429 (Too Many Requests).
+///
+/// ## Server-Side Rendering
+///
+/// The response is loaded with a [`Resource`]. So if you provide a `server_fetch` function that
+/// sends the request on the server, the data is loaded during server rendering and hydrated in
+/// the browser. To have the server wait for it, read `data` inside of a `<Suspense/>` or
+/// `<Transition/>`. Since the server and the browser have to create the same kind of resource,
+/// `server_fetch` has to be set in both builds. It's only called on the server.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_fetch_with_options, FetchResponse, UseFetchOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let greeting = use_fetch_with_options::<String, FromToStringCodec>(
+///     "https://example.com/api/greeting",
+///     UseFetchOptions::default().server_fetch(|request| {
+///         Box::pin(async move {
+///             // send `request` with the HTTP client of your server, e.g. reqwest
+///             Ok(FetchResponse {
+///                 status: 200,
+///                 headers: vec![],
+///                 body: "Hello".to_string(),
+///             })
+///         })
+///     }),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Without `server_fetch` nothing is loaded on the server and the request is sent by the browser
+/// after hydration.
+pub fn use_fetch<T, C>(
+    url: impl Into<MaybeSignal<String>>,
+) -> UseFetchReturn<
+    T,
+    <C as Decoder<T>>::Error,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: PartialEq + 'static,
+    C: Decoder<T, Encoded = str>,
+    <C as Decoder<T>>::Error: Debug,
+{
+    use_fetch_with_options::<T, C>(url, UseFetchOptions::default())
+}
+
+/// Version of [`use_fetch`] that takes a `UseFetchOptions`. See [`use_fetch`] for how to use.
+pub fn use_fetch_with_options<T, C>(
+    url: impl Into<MaybeSignal<String>>,
+    options: UseFetchOptions,
+) -> UseFetchReturn<
+    T,
+    <C as Decoder<T>>::Error,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: PartialEq + 'static,
+    C: Decoder<T, Encoded = str>,
+    <C as Decoder<T>>::Error: Debug,
+{
+    let UseFetchOptions {
+        method,
+        headers,
+        body,
+        immediate,
+        retry,
+        retry_delay,
+        before_fetch,
+        after_fetch,
+        server_fetch,
+        abort_controller: managed_controller,
+    } = options;
+
+    let url = url.into();
+
+    let (executions, set_executions) = create_signal(0_usize);
+    let (aborted, set_aborted) = create_signal(false);
+    let abort_controller = store_value(None::<web_sys::AbortController>);
+    // Id of the latest fetcher run. A run that has been replaced neither retries nor aborts.
+    let latest_run = store_value(0_usize);
+
+    let abort_running = move || {
+        abort_controller.try_with_value(|controller| {
+            if let Some(controller) = controller {
+                controller.abort();
+            }
+        });
+    };
+
+    let use_server_fetch = server_fetch.is_some();
+
+    let fetcher = move |(url, execution): (String, usize)| {
+        let method = method.clone();
+        let headers = headers.clone();
+        let body = body.clone();
+        let before_fetch = Rc::clone(&before_fetch);
+        let after_fetch = Rc::clone(&after_fetch);
+        let server_fetch = server_fetch.clone();
+
+        async move {
+            if !immediate && execution == 0 {
+                return None;
+            }
+
+            let mut request = FetchRequest {
+                url,
+                method,
+                headers,
+                body,
+            };
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            before_fetch(&mut request);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            let run = latest_run
+                .try_update_value(|id| {
+                    *id += 1;
+                    *id
+                })
+                .unwrap_or_default();
+            let is_current = move || latest_run.try_get_value() == Some(run);
+
+            // a new execution starts so a previous abort doesn't apply anymore
+            #[cfg(not(feature = "ssr"))]
+            let _ = set_aborted.try_set(false);
+
+            let mut attempt = 0;
+
+            let result = loop {
+                #[cfg(feature = "ssr")]
+                let result = match &server_fetch {
+                    Some(server_fetch) => server_fetch(request.clone()).await,
+                    None => Err("no server_fetch provided".to_string()),
+                };
+
+                #[cfg(not(feature = "ssr"))]
+                let result = {
+                    // don't abort the request of the run that replaced this one
+                    if !is_current() {
+                        break Err("the request has been replaced".to_string());
+                    }
+
+                    // nothing is sent anymore once the managed controller has been aborted
+                    if managed_controller
+                        .is_some_and(|controller| controller.aborted().get_untracked())
+                    {
+                        let _ = set_aborted.try_set(true);
+                        break Err("the request has been aborted".to_string());
+                    }
+
+                    abort_running();
+
+                    let controller = web_sys::AbortController::new().ok();
+                    let signal = controller.as_ref().map(|controller| controller.signal());
+                    let _ = abort_controller.try_set_value(controller);
+
+                    browser_fetch(&request, signal).await
+                };
+
+                let should_retry = match &result {
+                    Ok(response) => response.status >= 500 || response.status == 429,
+                    Err(_) => !aborted.try_get_untracked().unwrap_or(true),
+                } && is_current();
+
+                if !should_retry || attempt >= retry {
+                    break result;
+                }
+
+                attempt += 1;
+
+                #[cfg(not(feature = "ssr"))]
+                {
+                    gloo_timers::future::sleep(std::time::Duration::from_millis(retry_delay)).await;
+
+                    // `abort` has been called while waiting for the next attempt
+                    if aborted.try_get_untracked().unwrap_or(true) {
+                        break Err("the request has been aborted".to_string());
+                    }
+
+                    // a new run has started while waiting for the next attempt
+                    if !is_current() {
+                        break Err("the request has been replaced".to_string());
+                    }
+                }
+            };
+
+            Some(result.map(|mut response| {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                after_fetch(&mut response);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                (response.status, response.body)
+            }))
+        }
+    };
+
+    let source = move || (url.get(), executions.get());
+
+    let resource = if use_server_fetch {
+        create_resource(source, fetcher)
+    } else {
+        create_local_resource(source, fetcher)
+    };
+
+    let response = move || resource.get().flatten();
+
+    let data = create_memo(move |_| match response() {
+        Some(Ok((status, body))) if is_ok_status(status) => C::decode(&body).ok(),
+        _ => None,
+    });
+
+    let error = create_memo(move |_| match response()? {
+        Ok((status, body)) => {
+            if !is_ok_status(status) {
+                Some(UseFetchError::Status(status))
+            } else if data.with(Option::is_some) {
+                None
+            } else {
+                C::decode(&body).err().map(UseFetchError::Decode)
+            }
+        }
+        Err(_) if aborted.get() => Some(UseFetchError::Aborted),
+        Err(message) => Some(UseFetchError::Fetch(message)),
+    });
+
+    let status_code = Signal::derive(move || match response() {
+        Some(Ok((status, _))) => Some(status),
+        _ => None,
+    });
+
+    let execute = move || {
+        set_executions.update(|executions| *executions += 1);
+    };
+
+    let abort = move || {
+        #[cfg(not(feature = "ssr"))]
+        {
+            set_aborted.set(true);
+            abort_running();
+        }
+    };
+
+    if let Some(managed_controller) = managed_controller {
+        managed_controller.on_abort(abort);
+    }
+
+    on_cleanup(abort_running);
+
+    UseFetchReturn {
+        data: data.into(),
+        error: error.into(),
+        is_fetching: resource.loading(),
+        status_code,
+        abort,
+        execute,
+    }
+}
+
+fn is_ok_status(status: u16) -> bool {
+    (200..300).contains(&status)
+}
+
+async fn browser_fetch(
+    request: &FetchRequest,
+    signal: Option<web_sys::AbortSignal>,
+) -> Result<FetchResponse, String> {
+    let headers = web_sys::Headers::new().map_err(js_error_message)?;
+    for (name, value) in &request.headers {
+        headers.append(name, value).map_err(js_error_message)?;
+    }
+
+    let mut init = web_sys::RequestInit::new();
+    init.method(&request.method)
+        .headers(&headers)
+        .signal(signal.as_ref());
+    if let Some(body) = &request.body {
+        init.body(Some(&JsValue::from_str(body)));
+    }
+
+    let response = js_fut!(window().fetch_with_str_and_init(&request.url, &init))
+        .await
+        .map_err(js_error_message)?
+        .unchecked_into::<web_sys::Response>();
+
+    let headers = js_sys::try_iter(&response.headers())
+        .ok()
+        .flatten()
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let entry = js_sys::Array::from(&entry.ok()?);
+                    Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let body = js_fut!(response.text().map_err(js_error_message)?)
+        .await
+        .map_err(js_error_message)?
+        .as_string()
+        .unwrap_or_default();
+
+    Ok(FetchResponse {
+        status: response.status(),
+        headers,
+        body,
+    })
+}
+
+fn js_error_message(err: JsValue) -> String {
+    err.dyn_ref::<js_sys::Error>()
+        .map(|err| String::from(err.message()))
+        .unwrap_or_else(|| format!("{err:?}"))
+}
+
+type ServerFetchFn =
+    Rc<dyn Fn(FetchRequest) -> LocalBoxFuture<'static, Result<FetchResponse, String>>>;
+
+/// Options for [`use_fetch_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseFetchOptions {
+    /// HTTP method of the request. Defaults to `"GET"`.
+    #[builder(into)]
+    method: String,
+
+    /// Headers of the request. Defaults to none.
+    headers: Vec<(String, String)>,
+
+    /// Body of the request. Defaults to `None`.
+    #[builder(into)]
+    body: Option<String>,
+
+    /// If `true` the request is sent right away. Otherwise only after `execute` has been called.
+    /// Defaults to `true`.
+    immediate: bool,
+
+    /// How many times a failed request is sent again. Defaults to `0`.
+    retry: u32,
+
+    /// Time in milliseconds to wait before sending a failed request again. Defaults to `1000`.
+    retry_delay: u64,
+
+    /// Called with the request before it's sent. Can change it, e.g. add headers.
+    #[builder(skip)]
+    before_fetch: Rc<dyn Fn(&mut FetchRequest)>,
+
+    /// Called with the response before it's decoded. Can change it.
+    #[builder(skip)]
+    after_fetch: Rc<dyn Fn(&mut FetchResponse)>,
+
+    /// Sends the request on the server. See "Server-Side Rendering" in [`use_fetch`].
+    #[builder(skip)]
+    server_fetch: Option<ServerFetchFn>,
+
+    /// If set, the running request is aborted and no new requests are sent when the controller
+    /// is aborted. See [`crate::use_abort_controller`]. Defaults to `None`.
+    #[builder(into)]
+    abort_controller: Option<ManagedAbortController>,
+}
+
+impl UseFetchOptions {
+    /// Called with the request before it's sent. Can change it, e.g. add headers.
+    pub fn before_fetch<F>(self, handler: F) -> Self
+    where
+        F: Fn(&mut FetchRequest) + 'static,
+    {
+        Self {
+            before_fetch: Rc::new(handler),
+            ..self
+        }
+    }
+
+    /// Called with the response before it's decoded. Can change it.
+    pub fn after_fetch<F>(self, handler: F) -> Self
+    where
+        F: Fn(&mut FetchResponse) + 'static,
+    {
+        Self {
+            after_fetch: Rc::new(handler),
+            ..self
+        }
+    }
+
+    /// Sends the request on the server. See "Server-Side Rendering" in [`use_fetch`].
+    pub fn server_fetch<F>(self, server_fetch: F) -> Self
+    where
+        F: Fn(FetchRequest) -> LocalBoxFuture<'static, Result<FetchResponse, String>> + 'static,
+    {
+        Self {
+            server_fetch: Some(Rc::new(server_fetch)),
+            ..self
+        }
+    }
+}
+
+impl Default for UseFetchOptions {
+    fn default() -> Self {
+        Self {
+            method: "GET".to_string(),
+            headers: vec![],
+            body: None,
+            immediate: true,
+            retry: 0,
+            retry_delay: 1000,
+            before_fetch: Rc::new(|_| {}),
+            after_fetch: Rc::new(|_| {}),
+            server_fetch: None,
+            abort_controller: None,
+        }
+    }
+}
+
+/// A request of [`use_fetch`]. Passed to the `before_fetch` interceptor and `server_fetch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchRequest {
+    /// The URL the request is sent to
+    pub url: String,
+    /// HTTP method like `"GET"`
+    pub method: String,
+    /// Names and values of the headers
+    pub headers: Vec<(String, String)>,
+    /// The body of the request
+    pub body: Option<String>,
+}
+
+/// A response of [`use_fetch`]. Passed to the `after_fetch` interceptor and returned by
+/// `server_fetch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Names and values of the headers
+    pub headers: Vec<(String, String)>,
+    /// The body of the response as text
+    pub body: String,
+}
+
+/// Return type of [`use_fetch`].
+pub struct UseFetchReturn<T, D, ExecuteFn, AbortFn>
+where
+    T: 'static,
+    D: 'static,
+    ExecuteFn: Fn() + Clone + 'static,
+    AbortFn: Fn() + Clone + 'static,
+{
+    /// The decoded body of the latest successful response
+    pub data: Signal<Option<T>>,
+    /// The error of the latest request or `None` if it succeeded
+    pub error: Signal<Option<UseFetchError<D>>>,
+    /// `true` while a request is running
+    pub is_fetching: Signal<bool>,
+    /// The HTTP status of the latest response
+    pub status_code: Signal<Option<u16>>,
+    /// Aborts the running request
+    pub abort: AbortFn,
+    /// Sends the request again
+    pub execute: ExecuteFn,
+}
+
+#[derive(Error, Debug)]
+pub enum UseFetchError<D> {
+    #[error("failed to fetch: {0}")]
+    Fetch(String),
+    #[error("fetch was aborted")]
+    Aborted,
+    #[error("unexpected response status {0}")]
+    Status(u16),
+    #[error("failed to decode response: {0:?}")]
+    Decode(D),
+}
+
+// Decode errors usually don't implement `PartialEq` so they are compared by their debug output
+impl<D: Debug> PartialEq for UseFetchError<D> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (UseFetchError::Fetch(a), UseFetchError::Fetch(b)) => a == b,
+            (UseFetchError::Aborted, UseFetchError::Aborted) => true,
+            (UseFetchError::Status(a), UseFetchError::Status(b)) => a == b,
+            (UseFetchError::Decode(a), UseFetchError::Decode(b)) => {
+                format!("{a:?}") == format!("{b:?}")
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,585 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_supported};
+use codee::{
+    CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use web_sys::FileSystemFileHandle;
+
+/// Open and save files on the user's disk with the
+/// [File System Access API](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API).
+///
+/// `open` lets the user pick a file whose contents are decoded with the given codec into `data`.
+/// After changing `data` with `set_data`, `save` writes it back to the same file and `save_as`
+/// lets the user pick a new file to save to. `is_dirty` tells you if there are unsaved changes.
+///
+/// Values are (en)decoded via the given codec. You can use any of the string or binary codecs.
+/// Binary codecs read and write the raw bytes of the file.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_file_system_access_with_options, FileSystemAccessType, UseFileSystemAccessOptions,
+/// #     UseFileSystemAccessReturn,
+/// # };
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseFileSystemAccessReturn {
+///     file_name,
+///     data,
+///     set_data,
+///     is_dirty,
+///     open,
+///     save,
+///     save_as,
+///     ..
+/// } = use_file_system_access_with_options::<String, FromToStringCodec>(
+///     UseFileSystemAccessOptions::default()
+///         .types(vec![FileSystemAccessType::new("Text files", "text/plain", [".txt", ".md"])])
+///         .suggested_name("notes.txt"),
+/// );
+///
+/// view! {
+///     <button on:click=move |_| open()>"Open"</button>
+///     <button on:click=move |_| save() disabled=move || !is_dirty.get()>"Save"</button>
+///     <button on:click=move |_| save_as()>"Save as"</button>
+///
+///     <p>{move || file_name.get().unwrap_or_else(|| "No file".to_string())}</p>
+///
+///     <textarea
+///         prop:value=move || data.get().unwrap_or_default()
+///         on:input=move |e| set_data(event_target_value(&e))
+///     />
+/// }
+/// # }
+/// ```
+///
+/// Browsers only show the file pickers in reaction to a user interaction. Call `open`, `save`
+/// and `save_as` in event handlers.
+///
+/// ### Fallback
+///
+/// In browsers without the File System Access API (see `is_supported`) `open` uses an
+/// `<input type="file">` and `save` as well as `save_as` download the file instead. There is no
+/// `file_handle` then so `save` can't overwrite the opened file.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `data` is always `None` and `open`, `save` and `save_as` do nothing.
+pub fn use_file_system_access<T, C>() -> UseFileSystemAccessReturn<
+    T,
+    HybridCoderError<<C as Encoder<T>>::Error>,
+    HybridCoderError<<C as Decoder<T>>::Error>,
+    impl Fn(T) + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+>
+where
+    T: Clone + 'static,
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    use_file_system_access_with_options::<T, C>(UseFileSystemAccessOptions::default())
+}
+
+/// Version of [`use_file_system_access`] that takes a `UseFileSystemAccessOptions`. See [`use_file_system_access`] for how to use.
+pub fn use_file_system_access_with_options<T, C>(
+    options: UseFileSystemAccessOptions,
+) -> UseFileSystemAccessReturn<
+    T,
+    HybridCoderError<<C as Encoder<T>>::Error>,
+    HybridCoderError<<C as Decoder<T>>::Error>,
+    impl Fn(T) + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+>
+where
+    T: Clone + 'static,
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    let UseFileSystemAccessOptions {
+        types,
+        suggested_name,
+        exclude_accept_all_option,
+    } = options;
+
+    let types = store_value(types);
+    let suggested_name = store_value(suggested_name);
+
+    let is_supported = use_supported(|| js!("showOpenFilePicker" in &window()));
+
+    let (file_handle, set_file_handle) = create_signal(None::<FileSystemFileHandle>);
+    let (file_name, set_file_name) = create_signal(None::<String>);
+    let (data, set_data) = create_signal(None::<T>);
+    let (is_dirty, set_dirty) = create_signal(false);
+    let (error, set_error) = create_signal(None::<UseFileSystemAccessError<_, _>>);
+
+    let on_opened =
+        move |name: String, handle: Option<FileSystemFileHandle>, contents: FileContents| {
+            let decoded = match contents {
+                FileContents::Text(text) => C::decode_str(&text),
+                FileContents::Bytes(bytes) => C::decode_bin(bytes.as_slice()),
+            };
+
+            match decoded {
+                Ok(value) => {
+                    set_data.set(Some(value));
+                    set_file_name.set(Some(name));
+                    set_file_handle.set(handle);
+                    set_dirty.set(false);
+                    set_error.set(None);
+                }
+                Err(err) => set_error.set(Some(UseFileSystemAccessError::Codec(
+                    CodecError::Decode(err),
+                ))),
+            }
+        };
+
+    let on_saved = move |name: String, handle: Option<FileSystemFileHandle>| {
+        set_file_name.set(Some(name));
+        if handle.is_some() {
+            set_file_handle.set(handle);
+        }
+        set_dirty.set(false);
+        set_error.set(None);
+    };
+
+    let open = move || {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let binary = C::is_binary();
+
+            if !is_supported.get_untracked() {
+                let accept = types.with_value(|types| accept_attribute(types));
+
+                open_with_input(&accept, move |file| {
+                    spawn_local(async move {
+                        match read_file(&file, binary).await {
+                            Ok(contents) => on_opened(file.name(), None, contents),
+                            Err(err) => set_error.set(Some(UseFileSystemAccessError::Open(err))),
+                        }
+                    });
+                });
+
+                return;
+            }
+
+            let options = js_sys::Object::new();
+            js!(options["types"] = types.with_value(|types| picker_types(types)));
+            js!(options["excludeAcceptAllOption"] = exclude_accept_all_option);
+
+            spawn_local(async move {
+                let result: Result<_, JsValue> = async {
+                    let Some(handles) = show_file_picker("showOpenFilePicker", &options).await?
+                    else {
+                        return Ok(None);
+                    };
+
+                    let handle: FileSystemFileHandle =
+                        js_sys::Array::from(&handles).get(0).unchecked_into();
+                    let file: web_sys::File = js_fut!(handle.get_file()).await?.unchecked_into();
+                    let contents = read_file(&file, binary).await?;
+
+                    Ok(Some((handle, contents)))
+                }
+                .await;
+
+                match result {
+                    Ok(Some((handle, contents))) => {
+                        on_opened(handle.name(), Some(handle), contents)
+                    }
+                    Ok(None) => {}
+                    Err(err) => set_error.set(Some(UseFileSystemAccessError::Open(err))),
+                }
+            });
+        }
+    };
+
+    // Encodes `data` or sets `error` if that fails
+    let encoded_data = move || -> Option<FileContents> {
+        let value = data.get_untracked()?;
+
+        let encoded = if C::is_binary() {
+            C::encode_bin(&value).map(FileContents::Bytes)
+        } else {
+            C::encode_str(&value).map(FileContents::Text)
+        };
+
+        match encoded {
+            Ok(contents) => Some(contents),
+            Err(err) => {
+                set_error.set(Some(UseFileSystemAccessError::Codec(CodecError::Encode(
+                    err,
+                ))));
+                None
+            }
+        }
+    };
+
+    let save_to_new_file = move |contents: FileContents| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let name = file_name
+                .get_untracked()
+                .unwrap_or_else(|| suggested_name.get_value());
+
+            if !is_supported.get_untracked() {
+                let mime_type = types
+                    .with_value(|types| types.first().map(|ty| ty.mime_type.clone()))
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+
+                match download(&name, &mime_type, &contents) {
+                    Ok(()) => on_saved(name, None),
+                    Err(err) => set_error.set(Some(UseFileSystemAccessError::Save(err))),
+                }
+
+                return;
+            }
+
+            let options = js_sys::Object::new();
+            js!(options["suggestedName"] = name);
+            js!(options["types"] = types.with_value(|types| picker_types(types)));
+            js!(options["excludeAcceptAllOption"] = exclude_accept_all_option);
+
+            spawn_local(async move {
+                let result: Result<_, JsValue> = async {
+                    let Some(handle) = show_file_picker("showSaveFilePicker", &options).await?
+                    else {
+                        return Ok(None);
+                    };
+
+                    let handle: FileSystemFileHandle = handle.unchecked_into();
+                    write_file(&handle, &contents).await?;
+
+                    Ok(Some(handle))
+                }
+                .await;
+
+                match result {
+                    Ok(Some(handle)) => on_saved(handle.name(), Some(handle)),
+                    Ok(None) => {}
+                    Err(err) => set_error.set(Some(UseFileSystemAccessError::Save(err))),
+                }
+            });
+        }
+    };
+
+    let save = move || {
+        let Some(contents) = encoded_data() else {
+            return;
+        };
+
+        #[cfg(not(feature = "ssr"))]
+        {
+            match file_handle.get_untracked() {
+                Some(handle) => spawn_local(async move {
+                    match write_file(&handle, &contents).await {
+                        Ok(()) => on_saved(handle.name(), None),
+                        Err(err) => set_error.set(Some(UseFileSystemAccessError::Save(err))),
+                    }
+                }),
+                None => save_to_new_file(contents),
+            }
+        }
+    };
+
+    let save_as = move || {
+        if let Some(contents) = encoded_data() {
+            save_to_new_file(contents);
+        }
+    };
+
+    UseFileSystemAccessReturn {
+        is_supported,
+        file_handle: file_handle.into(),
+        file_name: file_name.into(),
+        data: data.into(),
+        set_data: move |value: T| {
+            set_data.set(Some(value));
+            set_dirty.set(true);
+        },
+        is_dirty: is_dirty.into(),
+        error: error.into(),
+        open,
+        save,
+        save_as,
+    }
+}
+
+/// Contents of a file as they are read from or written to it.
+enum FileContents {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+/// Calls `window[method](options)`. Returns `None` if the user closed the picker.
+#[cfg(not(feature = "ssr"))]
+async fn show_file_picker(method: &str, options: &JsValue) -> Result<Option<JsValue>, JsValue> {
+    let window = window();
+    let picker: js_sys::Function =
+        js_sys::Reflect::get(&window, &JsValue::from_str(method))?.dyn_into()?;
+
+    let promise: js_sys::Promise = picker.call1(&window, options)?.unchecked_into();
+
+    match js_fut!(promise).await {
+        Ok(result) => Ok(Some(result)),
+        Err(err) if is_abort_error(&err) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn is_abort_error(err: &JsValue) -> bool {
+    js!(err["name"])
+        .ok()
+        .and_then(|name| name.as_string())
+        .as_deref()
+        == Some("AbortError")
+}
+
+/// Lets the user pick a file with an `<input type="file">`.
+#[cfg(not(feature = "ssr"))]
+fn open_with_input(accept: &str, on_file: impl FnOnce(web_sys::File) + 'static) {
+    let Ok(input) = document().create_element("input") else {
+        return;
+    };
+    let input: web_sys::HtmlInputElement = input.unchecked_into();
+
+    input.set_type("file");
+    input.set_accept(accept);
+
+    let on_change = Closure::once_into_js({
+        let input = input.clone();
+
+        move || {
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                on_file(file);
+            }
+        }
+    });
+    input.set_onchange(Some(on_change.unchecked_ref()));
+
+    input.click();
+}
+
+/// Downloads the contents as a file called `name`.
+#[cfg(not(feature = "ssr"))]
+fn download(name: &str, mime_type: &str, contents: &FileContents) -> Result<(), JsValue> {
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime_type);
+
+    let blob = match contents {
+        FileContents::Text(text) => web_sys::Blob::new_with_str_sequence_and_options(
+            &js_sys::Array::of1(&JsValue::from_str(text)),
+            &options,
+        ),
+        FileContents::Bytes(bytes) => web_sys::Blob::new_with_u8_array_sequence_and_options(
+            &js_sys::Array::of1(&js_sys::Uint8Array::from(bytes.as_slice())),
+            &options,
+        ),
+    }?;
+
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let anchor: web_sys::HtmlAnchorElement = document().create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(name);
+    anchor.click();
+
+    // revoking right away can cancel the download in some browsers
+    set_timeout(
+        move || {
+            let _ = web_sys::Url::revoke_object_url(&url);
+        },
+        Duration::from_secs(1),
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn read_file(file: &web_sys::File, binary: bool) -> Result<FileContents, JsValue> {
+    if binary {
+        let buffer = js_fut!(file.array_buffer()).await?;
+        Ok(FileContents::Bytes(
+            js_sys::Uint8Array::new(&buffer).to_vec(),
+        ))
+    } else {
+        let text = js_fut!(file.text()).await?;
+        Ok(FileContents::Text(text.as_string().unwrap_or_default()))
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn write_file(handle: &FileSystemFileHandle, contents: &FileContents) -> Result<(), JsValue> {
+    let writable: web_sys::FileSystemWritableFileStream =
+        js_fut!(handle.create_writable()).await?.unchecked_into();
+
+    let write = match contents {
+        FileContents::Text(text) => writable.write_with_str(text),
+        FileContents::Bytes(bytes) => writable.write_with_u8_array(bytes),
+    }?;
+    js_fut!(write).await?;
+
+    // the contents are only persisted when the stream is closed
+    js_fut!(writable.close()).await?;
+
+    Ok(())
+}
+
+/// The `types` option of the file pickers.
+fn picker_types(types: &[FileSystemAccessType]) -> js_sys::Array {
+    types
+        .iter()
+        .map(|ty| {
+            let accept = js_sys::Object::new();
+            let _ = js_sys::Reflect::set(
+                &accept,
+                &JsValue::from_str(&ty.mime_type),
+                &ty.extensions
+                    .iter()
+                    .map(|extension| JsValue::from_str(extension))
+                    .collect::<js_sys::Array>(),
+            );
+
+            let picker_type = js_sys::Object::new();
+            js!(picker_type["description"] = ty.description.as_str());
+            js!(picker_type["accept"] = accept);
+
+            JsValue::from(picker_type)
+        })
+        .collect()
+}
+
+/// The `accept` attribute of the `<input type="file">` fallback.
+fn accept_attribute(types: &[FileSystemAccessType]) -> String {
+    types
+        .iter()
+        .flat_map(|ty| {
+            std::iter::once(ty.mime_type.as_str()).chain(ty.extensions.iter().map(String::as_str))
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A type of file that can be picked with [`use_file_system_access`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSystemAccessType {
+    /// Description that is shown in the file picker like `"Images"`
+    pub description: String,
+    /// MIME type like `"image/png"`
+    pub mime_type: String,
+    /// File extensions including the dot like `".png"`
+    pub extensions: Vec<String>,
+}
+
+impl FileSystemAccessType {
+    /// Creates a type with the given MIME type and file extensions.
+    pub fn new(
+        description: impl Into<String>,
+        mime_type: impl Into<String>,
+        extensions: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            mime_type: mime_type.into(),
+            extensions: extensions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Options for [`use_file_system_access_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseFileSystemAccessOptions {
+    /// The types of files the user can pick. Defaults to an empty `Vec` which allows all files.
+    types: Vec<FileSystemAccessType>,
+
+    /// Name that the save picker suggests if no file has been opened yet. Defaults to `"untitled"`.
+    #[builder(into)]
+    suggested_name: String,
+
+    /// Don't offer the option to pick any type of file in the pickers. Defaults to `false`.
+    exclude_accept_all_option: bool,
+}
+
+impl Default for UseFileSystemAccessOptions {
+    fn default() -> Self {
+        Self {
+            types: vec![],
+            suggested_name: "untitled".to_string(),
+            exclude_accept_all_option: false,
+        }
+    }
+}
+
+/// Errors of [`use_file_system_access`].
+#[derive(Error, Debug)]
+pub enum UseFileSystemAccessError<E, D> {
+    #[error("failed to open file: {0:?}")]
+    Open(JsValue),
+    #[error("failed to save file: {0:?}")]
+    Save(JsValue),
+    #[error("failed to encode / decode file contents: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
+
+/// Return type of [`use_file_system_access`].
+pub struct UseFileSystemAccessReturn<T, E, D, SetDataFn, OpenFn, SaveFn, SaveAsFn>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+    SetDataFn: Fn(T) + Clone,
+    OpenFn: Fn() + Clone,
+    SaveFn: Fn() + Clone,
+    SaveAsFn: Fn() + Clone,
+{
+    /// Whether the File System Access API is supported. If not, a fallback is used.
+    pub is_supported: Signal<bool>,
+
+    /// Handle of the opened or saved file. Always `None` without File System Access API.
+    pub file_handle: Signal<Option<FileSystemFileHandle>>,
+
+    /// Name of the opened or saved file.
+    pub file_name: Signal<Option<String>>,
+
+    /// The decoded contents of the file. `None` until a file has been opened or `set_data` has
+    /// been called.
+    pub data: Signal<Option<T>>,
+
+    /// Sets `data` without saving it. `is_dirty` becomes `true`.
+    pub set_data: SetDataFn,
+
+    /// `true` if `data` has been changed since it was opened or saved the last time.
+    pub is_dirty: Signal<bool>,
+
+    /// The error of the latest open or save if it failed.
+    pub error: Signal<Option<UseFileSystemAccessError<E, D>>>,
+
+    /// Lets the user pick a file and reads it into `data`.
+    pub open: OpenFn,
+
+    /// Writes `data` to the opened file. Works like `save_as` if there is none.
+    pub save: SaveFn,
+
+    /// Lets the user pick a new file and writes `data` to it.
+    pub save_as: SaveAsFn,
+}
@@ -0,0 +1,302 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Reactive [CSS Font Loading API](https://developer.mozilla.org/en-US/docs/Web/API/CSS_Font_Loading_API).
+///
+/// Loads fonts programmatically with `load` and tells when the fonts of the document are ready.
+/// Text that is drawn on a canvas or measured with JavaScript has to be redone once the fonts
+/// have arrived. `fonts_changed` is incremented every time fonts have been loaded, so tracking it
+/// in an effect re-runs the effect at the right moments.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_font_loading, FontSource, FontStatus, UseFontLoadingReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseFontLoadingReturn {
+///     fonts_ready,
+///     fonts_changed,
+///     load,
+///     ..
+/// } = use_font_loading();
+///
+/// let inter = load(FontSource::new("Inter", "url(/fonts/Inter.woff2)").weight("100 900"));
+///
+/// create_effect(move |_| {
+///     fonts_changed.track();
+///     // measure the text or redraw the canvas
+/// });
+///
+/// view! {
+///     <Show when=move || inter.get() == FontStatus::Loaded fallback=|| "Loading font...">
+///         <p style="font-family: Inter">"Hello"</p>
+///     </Show>
+///     <p>"All fonts ready: " {fonts_ready}</p>
+/// }
+/// # }
+/// ```
+///
+/// `check` tells if a font like `"bold 16px Inter"` can be used right away without loading
+/// anything.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `fonts_ready` is always `false`, `load` returns a status that stays
+/// `FontStatus::Unloaded` and `check` returns `false`.
+pub fn use_font_loading() -> UseFontLoadingReturn<
+    impl Fn(FontSource) -> Signal<FontStatus> + Clone,
+    impl Fn(&str) -> bool + Clone,
+> {
+    use_font_loading_with_options(UseFontLoadingOptions::default())
+}
+
+/// Version of [`use_font_loading`] that takes a `UseFontLoadingOptions`. See [`use_font_loading`] for how to use.
+pub fn use_font_loading_with_options(
+    options: UseFontLoadingOptions,
+) -> UseFontLoadingReturn<
+    impl Fn(FontSource) -> Signal<FontStatus> + Clone,
+    impl Fn(&str) -> bool + Clone,
+> {
+    let UseFontLoadingOptions { on_fonts_change } = options;
+
+    let is_supported = use_supported(|| {
+        let document = document();
+        js!("fonts" in &document)
+    });
+
+    let (fonts_ready, set_fonts_ready) = create_signal(false);
+    let (fonts_changed, set_fonts_changed) = create_signal(0_usize);
+
+    let font_set = Signal::derive(move || {
+        if is_supported.get() {
+            Some(document().fonts())
+        } else {
+            None
+        }
+    });
+
+    let update_ready = move || {
+        if let Some(font_set) = font_set.get_untracked() {
+            set_fonts_ready.set(font_set.status() == web_sys::FontFaceSetLoadStatus::Loaded);
+        }
+    };
+
+    let notify = move || {
+        update_ready();
+        set_fonts_changed.update(|count| *count += 1);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_fonts_change();
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    };
+
+    let event_target = Signal::derive(move || {
+        font_set
+            .get()
+            .map(JsCast::unchecked_into::<web_sys::EventTarget>)
+    });
+
+    let _ = use_event_listener(
+        event_target,
+        Custom::<ev::Event>::new("loading"),
+        move |_| set_fonts_ready.set(false),
+    );
+
+    let _ = use_event_listener(event_target, Custom::<ev::Event>::new("loadingdone"), {
+        let notify = notify.clone();
+        move |_| notify()
+    });
+
+    let _ = use_event_listener(event_target, Custom::<ev::Event>::new("loadingerror"), {
+        let notify = notify.clone();
+        move |_| notify()
+    });
+
+    #[cfg(not(feature = "ssr"))]
+    if let Some(ready) = font_set
+        .get_untracked()
+        .and_then(|font_set| font_set.ready().ok())
+    {
+        update_ready();
+
+        spawn_local(async move {
+            if js_fut!(ready).await.is_ok() {
+                notify();
+            }
+        });
+    }
+
+    let load = move |source: FontSource| {
+        let (status, set_status) = create_signal(FontStatus::Unloaded);
+
+        #[cfg(not(feature = "ssr"))]
+        if let Some(font_set) = font_set.get_untracked() {
+            let FontSource {
+                family,
+                source,
+                weight,
+                style,
+                display,
+                unicode_range,
+            } = source;
+
+            let mut descriptors = web_sys::FontFaceDescriptors::new();
+            if let Some(weight) = weight {
+                descriptors.weight(&weight);
+            }
+            if let Some(style) = style {
+                descriptors.style(&style);
+            }
+            if let Some(display) = display {
+                descriptors.display(&display);
+            }
+            if let Some(unicode_range) = unicode_range {
+                descriptors.unicode_range(&unicode_range);
+            }
+
+            let face =
+                web_sys::FontFace::new_with_str_and_descriptors(&family, &source, &descriptors)
+                    .and_then(|face| font_set.add(&face).map(|_| face))
+                    .and_then(|face| face.load());
+
+            match face {
+                Ok(promise) => {
+                    set_status.set(FontStatus::Loading);
+
+                    spawn_local(async move {
+                        match js_fut!(promise).await {
+                            Ok(_) => set_status.set(FontStatus::Loaded),
+                            Err(_) => set_status.set(FontStatus::Error),
+                        }
+                    });
+                }
+                Err(_) => set_status.set(FontStatus::Error),
+            }
+        }
+
+        status.into()
+    };
+
+    let check = move |font: &str| {
+        font_set
+            .get_untracked()
+            .and_then(|font_set| font_set.check(font).ok())
+            .unwrap_or_default()
+    };
+
+    UseFontLoadingReturn {
+        is_supported,
+        fonts_ready: fonts_ready.into(),
+        fonts_changed: fonts_changed.into(),
+        load,
+        check,
+    }
+}
+
+/// A font face to load with the `load` function of [`use_font_loading`]. This corresponds to an
+/// `@font-face` rule in CSS.
+#[derive(DefaultBuilder, Clone, Debug, PartialEq)]
+pub struct FontSource {
+    /// Name of the font family that is used in `font-family`
+    #[builder(into)]
+    family: String,
+
+    /// Like the `src` of `@font-face`, e.g. `"url(/fonts/Inter.woff2)"`
+    #[builder(into)]
+    source: String,
+
+    /// Like `font-weight`, e.g. `"bold"` or a range like `"100 900"` for variable fonts
+    #[builder(into)]
+    weight: Option<String>,
+
+    /// Like `font-style`, e.g. `"italic"`
+    #[builder(into)]
+    style: Option<String>,
+
+    /// Like `font-display`, e.g. `"swap"`
+    #[builder(into)]
+    display: Option<String>,
+
+    /// Like `unicode-range`, e.g. `"U+0000-00FF"`
+    #[builder(into)]
+    unicode_range: Option<String>,
+}
+
+impl FontSource {
+    /// Font face of `family` that is loaded from `source`, like `"url(/fonts/Inter.woff2)"`.
+    pub fn new(family: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            family: family.into(),
+            source: source.into(),
+            weight: None,
+            style: None,
+            display: None,
+            unicode_range: None,
+        }
+    }
+}
+
+/// Loading status of a font of [`use_font_loading`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FontStatus {
+    /// The font hasn't been requested
+    #[default]
+    Unloaded,
+    /// The font is being loaded
+    Loading,
+    /// The font has been loaded and is ready to use
+    Loaded,
+    /// The font couldn't be loaded
+    Error,
+}
+
+/// Options for [`use_font_loading_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseFontLoadingOptions {
+    /// Called every time fonts of the document have been loaded.
+    on_fonts_change: Rc<dyn Fn()>,
+}
+
+impl Default for UseFontLoadingOptions {
+    fn default() -> Self {
+        Self {
+            on_fonts_change: Rc::new(|| {}),
+        }
+    }
+}
+
+/// Return type of [`use_font_loading`].
+pub struct UseFontLoadingReturn<LoadFn, CheckFn>
+where
+    LoadFn: Fn(FontSource) -> Signal<FontStatus> + Clone,
+    CheckFn: Fn(&str) -> bool + Clone,
+{
+    /// Whether the CSS Font Loading API is supported
+    pub is_supported: Signal<bool>,
+
+    /// `true` when all fonts that the document uses so far have been loaded
+    pub fonts_ready: Signal<bool>,
+
+    /// Incremented every time fonts have been loaded. Track it to re-run code that depends on
+    /// the fonts like text measurements.
+    pub fonts_changed: Signal<usize>,
+
+    /// Adds a font face to the document and loads it. Returns its loading status.
+    pub load: LoadFn,
+
+    /// Whether a font like `"bold 16px Inter"` can be used without loading anything
+    pub check: CheckFn,
+}
@@ -0,0 +1,322 @@
+use crate::signal_debounced;
+use default_struct_builder::DefaultBuilder;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use leptos::*;
+use std::rc::Rc;
+
+/// Client-side fuzzy search over a reactive list.
+/// For this to work you have to enable the **`fuzzy` feature** flag.
+///
+/// Every item is matched against the query in one or more fields. The matches are sorted by
+/// their score, best first, and contain the positions of the matched characters so they can be
+/// highlighted. Items with equal scores keep their original order. While the query is empty all
+/// items are returned in their original order.
+///
+/// The query is debounced so typing fast doesn't search for every key stroke.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_fuzzy_search, UseFuzzySearchReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let fruits = vec!["Apple", "Banana", "Cherry", "Pineapple"];
+/// let (query, set_query) = create_signal(String::new());
+///
+/// let UseFuzzySearchReturn { matches, .. } = use_fuzzy_search(fruits, query);
+///
+/// view! {
+///     <input on:input=move |ev| set_query.set(event_target_value(&ev)) />
+///     <ul>
+///         <For each=move || matches.get() key=|m| m.index let:m>
+///             <li>
+///                 {match m.fields.first() {
+///                     Some(field) => field
+///                         .segments()
+///                         .into_iter()
+///                         .map(|(text, is_match)| view! { <span class:font-bold=is_match>{text}</span> })
+///                         .collect_view(),
+///                     None => m.item.into_view(),
+///                 }}
+///             </li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ## Fields
+///
+/// For structs you provide a field extractor for every field that should be searched. Fields
+/// can be weighted to make matches in some fields count more.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_fuzzy_search_with_options, UseFuzzySearchOptions};
+/// #
+/// #[derive(Clone, PartialEq)]
+/// struct Contact {
+///     name: String,
+///     email: String,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let contacts: Vec<Contact> = vec![];
+/// # let (query, set_query) = create_signal(String::new());
+/// let search = use_fuzzy_search_with_options(
+///     contacts,
+///     query,
+///     UseFuzzySearchOptions::default()
+///         .field("name", 2.0, |contact: &Contact| contact.name.clone())
+///         .field("email", 1.0, |contact: &Contact| contact.email.clone())
+///         .limit(20),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The returned `query` is the debounced query. It can be used to additionally fetch results
+/// from a server while the local results are shown already.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the query isn't debounced.
+pub fn use_fuzzy_search<T>(
+    items: impl Into<MaybeSignal<Vec<T>>>,
+    query: impl Into<Signal<String>>,
+) -> UseFuzzySearchReturn<T>
+where
+    T: ToString + Clone + PartialEq + 'static,
+{
+    use_fuzzy_search_with_options(
+        items,
+        query,
+        UseFuzzySearchOptions::default().field("", 1.0, T::to_string),
+    )
+}
+
+/// Version of [`use_fuzzy_search`] that takes a `UseFuzzySearchOptions`. See [`use_fuzzy_search`] for how to use.
+pub fn use_fuzzy_search_with_options<T>(
+    items: impl Into<MaybeSignal<Vec<T>>>,
+    query: impl Into<Signal<String>>,
+    options: UseFuzzySearchOptions<T>,
+) -> UseFuzzySearchReturn<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let UseFuzzySearchOptions {
+        fields,
+        debounce,
+        limit,
+        min_score,
+        case_sensitive,
+    } = options;
+
+    let items: MaybeSignal<Vec<T>> = items.into();
+    let query = query.into();
+
+    let debounced_query = signal_debounced(query, debounce);
+
+    let matcher = Rc::new(if case_sensitive {
+        SkimMatcherV2::default().respect_case()
+    } else {
+        SkimMatcherV2::default().ignore_case()
+    });
+
+    let matches = create_memo(move |_| {
+        let query = debounced_query.get();
+        let query = query.trim();
+
+        items.with(|items| {
+            let mut matches = items
+                .iter()
+                .enumerate()
+                .filter_map(|(index, item)| {
+                    if query.is_empty() {
+                        return Some(FuzzyMatch {
+                            item: item.clone(),
+                            index,
+                            score: 0,
+                            fields: vec![],
+                        });
+                    }
+
+                    let field_matches = fields
+                        .iter()
+                        .filter_map(|field| {
+                            let text = (field.extract)(item);
+
+                            matcher.fuzzy_indices(&text, query).map(|(score, indices)| {
+                                FuzzyFieldMatch {
+                                    field: field.name.clone(),
+                                    score: (score as f64 * field.weight) as i64,
+                                    text,
+                                    indices,
+                                }
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    let score = field_matches.iter().map(|m| m.score).max()?;
+
+                    (score >= min_score).then(|| FuzzyMatch {
+                        item: item.clone(),
+                        index,
+                        score,
+                        fields: field_matches,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            // stable so equal scores keep their original order
+            matches.sort_by(|a, b| b.score.cmp(&a.score));
+
+            if let Some(limit) = limit {
+                matches.truncate(limit);
+            }
+
+            matches
+        })
+    });
+
+    UseFuzzySearchReturn {
+        matches: matches.into(),
+        query: debounced_query,
+        is_pending: Signal::derive(move || query.with(|q| debounced_query.with(|d| q != d))),
+    }
+}
+
+/// A field of the items that is searched by [`use_fuzzy_search`].
+struct FuzzyField<T> {
+    name: String,
+    weight: f64,
+    extract: Rc<dyn Fn(&T) -> String>,
+}
+
+/// Options for [`use_fuzzy_search_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseFuzzySearchOptions<T>
+where
+    T: 'static,
+{
+    /// The fields that are searched. Use the `field` method to add fields.
+    #[builder(skip)]
+    fields: Vec<FuzzyField<T>>,
+
+    /// Time in ms the query is debounced. Defaults to `150.0`.
+    debounce: f64,
+
+    /// Maximum number of matches. `None` returns all of them. Defaults to `None`.
+    #[builder(into)]
+    limit: Option<usize>,
+
+    /// Matches with a lower score are dropped. Defaults to `0`.
+    min_score: i64,
+
+    /// If upper and lower case have to match. Defaults to `false`.
+    case_sensitive: bool,
+}
+
+impl<T> UseFuzzySearchOptions<T>
+where
+    T: 'static,
+{
+    /// Adds a field that is searched. `weight` scales the score of matches in this field.
+    pub fn field<F>(mut self, name: impl Into<String>, weight: f64, extract: F) -> Self
+    where
+        F: Fn(&T) -> String + 'static,
+    {
+        self.fields.push(FuzzyField {
+            name: name.into(),
+            weight,
+            extract: Rc::new(extract),
+        });
+        self
+    }
+}
+
+impl<T> Default for UseFuzzySearchOptions<T>
+where
+    T: 'static,
+{
+    fn default() -> Self {
+        Self {
+            fields: vec![],
+            debounce: 150.0,
+            limit: None,
+            min_score: 0,
+            case_sensitive: false,
+        }
+    }
+}
+
+/// An item that matches the query of [`use_fuzzy_search`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuzzyMatch<T> {
+    /// The matching item
+    pub item: T,
+
+    /// Index of the item in the searched list
+    pub index: usize,
+
+    /// Score of the best matching field. Higher is better.
+    pub score: i64,
+
+    /// All fields that match the query. Empty if the query is empty.
+    pub fields: Vec<FuzzyFieldMatch>,
+}
+
+/// A field that matches the query of [`use_fuzzy_search`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuzzyFieldMatch {
+    /// Name of the field
+    pub field: String,
+
+    /// Text of the field
+    pub text: String,
+
+    /// Score of the match, scaled by the weight of the field
+    pub score: i64,
+
+    /// Indices of the matched chars in `text`
+    pub indices: Vec<usize>,
+}
+
+impl FuzzyFieldMatch {
+    /// Splits `text` into segments that are either matched or not, e.g. for highlighting.
+    /// Returns `(segment, is_match)` pairs.
+    pub fn segments(&self) -> Vec<(String, bool)> {
+        let mut segments: Vec<(String, bool)> = vec![];
+
+        for (i, c) in self.text.chars().enumerate() {
+            let is_match = self.indices.contains(&i);
+
+            match segments.last_mut() {
+                Some((segment, last_is_match)) if *last_is_match == is_match => segment.push(c),
+                _ => segments.push((c.to_string(), is_match)),
+            }
+        }
+
+        segments
+    }
+}
+
+/// Return type of [`use_fuzzy_search`].
+pub struct UseFuzzySearchReturn<T>
+where
+    T: 'static,
+{
+    /// The matches, best first
+    pub matches: Signal<Vec<FuzzyMatch<T>>>,
+
+    /// The debounced query
+    pub query: Signal<String>,
+
+    /// `true` while the query has changed but the debounce time hasn't passed yet
+    pub is_pending: Signal<bool>,
+}
@@ -0,0 +1,324 @@
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+
+/// Reactive geofencing on top of a location signal like the one of [`fn@crate::use_geolocation`].
+///
+/// Takes a list of circular or polygonal regions and tracks which of them contain the current
+/// location. When the location enters or leaves a region the respective callback is called.
+///
+/// To avoid jitter when the location is close to the boundary of a region, a region is entered
+/// as soon as the location is inside of it but only left when the location is more than
+/// `hysteresis` meters outside of it.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::logging::log;
+/// # use leptos_use::{use_geofence_with_options, use_geolocation, GeoPoint, GeofenceRegion, UseGeofenceOptions, UseGeolocationReturn, UseGeofenceReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseGeolocationReturn { coords, .. } = use_geolocation();
+///
+/// let UseGeofenceReturn { inside, .. } = use_geofence_with_options(
+///     coords,
+///     vec![
+///         GeofenceRegion::circle("office", GeoPoint::new(52.5200, 13.4050), 150.0),
+///         GeofenceRegion::polygon(
+///             "park",
+///             vec![
+///                 GeoPoint::new(52.5145, 13.3501),
+///                 GeoPoint::new(52.5163, 13.3777),
+///                 GeoPoint::new(52.5098, 13.3760),
+///             ],
+///         ),
+///     ],
+///     UseGeofenceOptions::default()
+///         .on_enter(|id: &&'static str| log!("entered {id}"))
+///         .on_exit(|id: &&'static str| log!("left {id}")),
+/// );
+///
+/// view! {
+///     <p>"Inside: " {move || inside.get().join(", ")}</p>
+/// }
+/// # }
+/// ```
+///
+/// While the location is `None` the regions that contain the location stay the same.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the location is usually `None` so no region contains it.
+pub fn use_geofence<C, Id>(
+    coords: impl Into<Signal<Option<C>>>,
+    regions: impl Into<MaybeSignal<Vec<GeofenceRegion<Id>>>>,
+) -> UseGeofenceReturn<Id>
+where
+    C: Into<GeoPoint> + Clone + 'static,
+    Id: Clone + PartialEq + 'static,
+{
+    use_geofence_with_options(coords, regions, UseGeofenceOptions::default())
+}
+
+/// Version of [`use_geofence`] that takes a `UseGeofenceOptions`. See [`use_geofence`] for how to use.
+pub fn use_geofence_with_options<C, Id>(
+    coords: impl Into<Signal<Option<C>>>,
+    regions: impl Into<MaybeSignal<Vec<GeofenceRegion<Id>>>>,
+    options: UseGeofenceOptions<Id>,
+) -> UseGeofenceReturn<Id>
+where
+    C: Into<GeoPoint> + Clone + 'static,
+    Id: Clone + PartialEq + 'static,
+{
+    let UseGeofenceOptions {
+        hysteresis,
+        on_enter,
+        on_exit,
+    } = options;
+
+    let coords = coords.into();
+    let regions: MaybeSignal<Vec<GeofenceRegion<Id>>> = regions.into();
+
+    let (inside, set_inside) = create_signal(Vec::<Id>::new());
+
+    let _ = watch(
+        move || {
+            coords.track();
+            regions.track();
+        },
+        move |_, _, _| {
+            let location = coords.get_untracked().map(Into::<GeoPoint>::into);
+            let previous = inside.get_untracked();
+
+            let current = regions.with_untracked(|regions| {
+                regions
+                    .iter()
+                    .filter(|region| {
+                        let was_inside = previous.contains(&region.id);
+
+                        match location {
+                            Some(location) => {
+                                let distance = region.shape.signed_distance(location);
+
+                                if was_inside {
+                                    distance <= hysteresis
+                                } else {
+                                    distance <= 0.0
+                                }
+                            }
+                            None => was_inside,
+                        }
+                    })
+                    .map(|region| region.id.clone())
+                    .collect::<Vec<_>>()
+            });
+
+            if current == previous {
+                return;
+            }
+
+            set_inside.set(current.clone());
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            // removed regions count as left as well
+            for id in previous.iter().filter(|id| !current.contains(id)) {
+                on_exit(id);
+            }
+            for id in current.iter().filter(|id| !previous.contains(id)) {
+                on_enter(id);
+            }
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+        true,
+    );
+
+    UseGeofenceReturn {
+        inside: inside.into(),
+    }
+}
+
+/// Options for [`use_geofence_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseGeofenceOptions<Id>
+where
+    Id: 'static,
+{
+    /// Distance in meters the location has to be outside of a region before it is left.
+    /// Defaults to `10.0`.
+    hysteresis: f64,
+
+    /// Called with the id of a region when the location enters it.
+    on_enter: Rc<dyn Fn(&Id)>,
+
+    /// Called with the id of a region when the location leaves it or when the region is removed
+    /// while the location is inside of it.
+    on_exit: Rc<dyn Fn(&Id)>,
+}
+
+impl<Id> Default for UseGeofenceOptions<Id>
+where
+    Id: 'static,
+{
+    fn default() -> Self {
+        Self {
+            hysteresis: 10.0,
+            on_enter: Rc::new(|_| {}),
+            on_exit: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_geofence`].
+pub struct UseGeofenceReturn<Id>
+where
+    Id: 'static,
+{
+    /// Ids of the regions that contain the current location in the order of the regions
+    pub inside: Signal<Vec<Id>>,
+}
+
+/// A point on earth in degrees.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GeoPoint {
+    /// Latitude in degrees
+    pub latitude: f64,
+    /// Longitude in degrees
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    /// Creates a point from latitude and longitude in degrees.
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// Great-circle distance to `other` in meters.
+    pub fn distance_to(&self, other: GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+
+    /// Position of `other` in meters relative to this point on a locally flat earth.
+    fn project(&self, other: GeoPoint) -> (f64, f64) {
+        let x = (other.longitude - self.longitude).to_radians()
+            * self.latitude.to_radians().cos()
+            * EARTH_RADIUS;
+        let y = (other.latitude - self.latitude).to_radians() * EARTH_RADIUS;
+
+        (x, y)
+    }
+}
+
+impl From<web_sys::Coordinates> for GeoPoint {
+    fn from(coords: web_sys::Coordinates) -> Self {
+        Self::new(coords.latitude(), coords.longitude())
+    }
+}
+
+/// Mean radius of the earth in meters
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// A region of [`use_geofence`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeofenceRegion<Id> {
+    /// Id of the region that is reported in `inside` and the callbacks
+    pub id: Id,
+    /// Shape of the region
+    pub shape: GeofenceShape,
+}
+
+impl<Id> GeofenceRegion<Id> {
+    /// A circle around `center` with `radius` in meters.
+    pub fn circle(id: Id, center: GeoPoint, radius: f64) -> Self {
+        Self {
+            id,
+            shape: GeofenceShape::Circle { center, radius },
+        }
+    }
+
+    /// A polygon with the given corners. The polygon is closed automatically.
+    pub fn polygon(id: Id, points: Vec<GeoPoint>) -> Self {
+        Self {
+            id,
+            shape: GeofenceShape::Polygon(points),
+        }
+    }
+}
+
+/// Shape of a [`GeofenceRegion`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum GeofenceShape {
+    /// A circle around `center` with `radius` in meters.
+    Circle { center: GeoPoint, radius: f64 },
+    /// A polygon with the given corners.
+    Polygon(Vec<GeoPoint>),
+}
+
+impl GeofenceShape {
+    /// Distance in meters from `point` to the boundary of the shape. Negative if `point` is
+    /// inside of the shape.
+    pub fn signed_distance(&self, point: GeoPoint) -> f64 {
+        match self {
+            Self::Circle { center, radius } => center.distance_to(point) - radius,
+            Self::Polygon(points) => {
+                if points.is_empty() {
+                    return f64::INFINITY;
+                }
+
+                // `point` is the origin of the projected polygon
+                let corners = points
+                    .iter()
+                    .map(|corner| point.project(*corner))
+                    .collect::<Vec<_>>();
+
+                let mut is_inside = false;
+                let mut distance = f64::INFINITY;
+
+                for (i, &(x1, y1)) in corners.iter().enumerate() {
+                    let (x2, y2) = corners[(i + 1) % corners.len()];
+
+                    // ray casting along the positive x axis
+                    if (y1 > 0.0) != (y2 > 0.0) && x1 + (0.0 - y1) * (x2 - x1) / (y2 - y1) > 0.0 {
+                        is_inside = !is_inside;
+                    }
+
+                    distance = distance.min(distance_to_segment((x1, y1), (x2, y2)));
+                }
+
+                if is_inside {
+                    -distance
+                } else {
+                    distance
+                }
+            }
+        }
+    }
+}
+
+/// Distance of the origin to the segment from `a` to `b`.
+fn distance_to_segment(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        ((-a.0 * dx - a.1 * dy) / length_squared).clamp(0.0, 1.0)
+    };
+
+    (a.0 + t * dx).hypot(a.1 + t * dy)
+}
@@ -0,0 +1,45 @@
+use leptos::*;
+
+/// Reactive [`navigator.hardwareConcurrency`](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/hardwareConcurrency)
+///
+/// Number of logical processors that are available to run threads. Useful to choose the size
+/// of a pool of web workers.
+///
+/// > NOTE: the value doesn't change while the page is open. The signal is only there for
+/// > consistency with the other hooks.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_hardware_concurrency;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let cores = use_hardware_concurrency();
+///
+/// let worker_count = move || cores.get().unwrap_or(2).saturating_sub(1).max(1);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this returns a `Signal` that is always `None`.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_device_memory`]
+/// * [`fn@crate::use_device_tier`]
+pub fn use_hardware_concurrency() -> Signal<Option<usize>> {
+    #[cfg(feature = "ssr")]
+    let concurrency = None;
+
+    // browsers that don't support it report `0`
+    #[cfg(not(feature = "ssr"))]
+    let concurrency = Some(window().navigator().hardware_concurrency() as usize)
+        .filter(|concurrency| *concurrency > 0);
+
+    Signal::derive(move || concurrency)
+}
@@ -0,0 +1,327 @@
+use crate::core::UseRwSignal;
+use crate::filter_builder_methods;
+use crate::utils::{
+    create_filter_wrapper_with_arg, DebounceOptions, FilterOptions, ThrottleOptions,
+};
+use crate::{
+    use_manual_history_with_options, HistoryRecord, HistoryState, UseManualHistoryOptions,
+    UseManualHistoryReturn,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+
+/// Track the change history of a signal with undo and redo.
+///
+/// Every change of `source` is recorded automatically. `undo` and `redo` move through the
+/// recorded values and set `source` accordingly. This is [`fn@crate::use_manual_history`] with
+/// a watcher that calls `commit` for you.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_history, UseHistoryReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let text = create_rw_signal(String::new());
+///
+/// let UseHistoryReturn {
+///     undo,
+///     redo,
+///     can_undo,
+///     can_redo,
+///     ..
+/// } = use_history(text);
+///
+/// view! {
+///     <input prop:value=text on:input=move |e| text.set(event_target_value(&e)) />
+///     <button on:click=move |_| undo() disabled=move || !can_undo.get()>"Undo"</button>
+///     <button on:click=move |_| redo() disabled=move || !can_redo.get()>"Redo"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Debounced Capture
+///
+/// Recording every keystroke is usually too fine grained. Debounce or throttle the recording
+/// to get one record per burst of changes. Changes that haven't been recorded yet are discarded
+/// by `undo` and `redo`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_history_with_options, UseHistoryOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let text = create_rw_signal(String::new());
+/// let history = use_history_with_options(
+///     text,
+///     UseHistoryOptions::default().debounce(500.0).capacity(50),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Batched Capture
+///
+/// Several changes can be recorded as one with `batch`. While it runs the closure, the
+/// tracking is paused. Afterwards a single record is committed.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_history;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let items = create_rw_signal(vec![1, 2, 3]);
+/// let history = use_history(items);
+///
+/// history.batch(|| {
+///     items.update(|items| items.push(4));
+///     items.update(|items| items.retain(|item| item % 2 == 0));
+/// });
+///
+/// assert_eq!(history.history.get().len(), 2);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// You can also `pause` the tracking and `resume` it yourself. Changes while paused aren't
+/// recorded unless you call `commit`.
+///
+/// The history can be persisted like the one of [`fn@crate::use_manual_history`] with the
+/// option `persisted_state`.
+///
+/// ## Server-Side Rendering
+///
+/// This works the same on the server.
+#[allow(clippy::type_complexity)]
+pub fn use_history<T>(
+    source: impl Into<UseRwSignal<T>>,
+) -> UseHistoryReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: Clone + 'static,
+{
+    use_history_with_options(source, UseHistoryOptions::default())
+}
+
+/// Version of [`use_history`] that takes a `UseHistoryOptions`. See [`use_history`] for how to use.
+#[allow(clippy::type_complexity)]
+pub fn use_history_with_options<T>(
+    source: impl Into<UseRwSignal<T>>,
+    options: UseHistoryOptions<T>,
+) -> UseHistoryReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: Clone + 'static,
+{
+    let UseHistoryOptions {
+        capacity,
+        persisted_state,
+        filter,
+    } = options;
+
+    let source = source.into();
+
+    let mut manual_options = UseManualHistoryOptions::default().capacity(capacity);
+    if let Some(persisted_state) = persisted_state {
+        manual_options = manual_options.persisted_state(persisted_state);
+    }
+
+    let UseManualHistoryReturn {
+        history,
+        can_undo,
+        can_redo,
+        commit,
+        undo,
+        redo,
+        clear,
+        reset,
+    } = use_manual_history_with_options(source, manual_options);
+
+    let (is_tracking, set_tracking) = create_signal(true);
+
+    // set while the source is changed by the history itself
+    let ignore = store_value(false);
+    // incremented whenever the source is set from the history so commits that are still
+    // pending in the filter are dropped
+    let generation = store_value(0_u64);
+
+    let filtered_commit = create_filter_wrapper_with_arg(filter.filter_fn(), {
+        let commit = commit.clone();
+
+        move |scheduled_generation: u64| {
+            if generation.try_get_value() == Some(scheduled_generation) {
+                commit();
+            }
+        }
+    });
+
+    let _ = watch(
+        move || source.with(|_| ()),
+        move |_, _, _| {
+            if is_tracking.get_untracked() && !ignore.get_value() {
+                filtered_commit(generation.get_value());
+            }
+        },
+        false,
+    );
+
+    // Runs `f` which changes the source without recording it
+    let without_tracking = move |f: &dyn Fn()| {
+        ignore.set_value(true);
+        generation.update_value(|generation| *generation += 1);
+        f();
+        ignore.set_value(false);
+    };
+
+    let pause = move || set_tracking.set(false);
+
+    let resume = move || set_tracking.set(true);
+
+    UseHistoryReturn {
+        history,
+        can_undo,
+        can_redo,
+        is_tracking: is_tracking.into(),
+        commit: move || {
+            generation.update_value(|generation| *generation += 1);
+            commit();
+        },
+        undo: move || without_tracking(&undo),
+        redo: move || without_tracking(&redo),
+        clear,
+        reset: move || without_tracking(&reset),
+        pause,
+        resume,
+    }
+}
+
+/// Options for [`use_history_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseHistoryOptions<T>
+where
+    T: 'static,
+{
+    /// Maximum number of records that can be undone. Defaults to `None` which keeps all of them.
+    #[builder(into)]
+    capacity: Option<usize>,
+
+    /// Signal that holds the history instead of an internal one. See
+    /// [`fn@crate::use_manual_history`] for how to persist the history with it. Defaults to `None`.
+    #[builder(skip)]
+    persisted_state: Option<UseRwSignal<HistoryState<T>>>,
+
+    /// Allows to debounce or throttle the recording of changes. Defaults to no filter.
+    filter: FilterOptions,
+}
+
+impl<T> Default for UseHistoryOptions<T> {
+    fn default() -> Self {
+        Self {
+            capacity: None,
+            persisted_state: None,
+            filter: FilterOptions::default(),
+        }
+    }
+}
+
+impl<T> UseHistoryOptions<T> {
+    /// Signal that holds the history instead of an internal one. See
+    /// [`fn@crate::use_manual_history`] for how to persist the history with it.
+    pub fn persisted_state(self, state: impl Into<UseRwSignal<HistoryState<T>>>) -> Self {
+        Self {
+            persisted_state: Some(state.into()),
+            ..self
+        }
+    }
+
+    filter_builder_methods!(
+        /// the recording of changes
+        filter
+    );
+}
+
+/// Return type of [`use_history`].
+pub struct UseHistoryReturn<T, CommitFn, UndoFn, RedoFn, ClearFn, ResetFn, PauseFn, ResumeFn>
+where
+    T: 'static,
+    CommitFn: Fn() + Clone + 'static,
+    UndoFn: Fn() + Clone + 'static,
+    RedoFn: Fn() + Clone + 'static,
+    ClearFn: Fn() + Clone + 'static,
+    ResetFn: Fn() + Clone + 'static,
+    PauseFn: Fn() + Clone + 'static,
+    ResumeFn: Fn() + Clone + 'static,
+{
+    /// All records that can be undone, the most recent first. The first one is the record of
+    /// the current value.
+    pub history: Signal<Vec<HistoryRecord<T>>>,
+    /// `true` if there is a record to undo to
+    pub can_undo: Signal<bool>,
+    /// `true` if there is a record to redo to
+    pub can_redo: Signal<bool>,
+    /// `true` unless the tracking has been paused
+    pub is_tracking: Signal<bool>,
+    /// Records the current value of the source right away. Clears the records that could be
+    /// redone.
+    pub commit: CommitFn,
+    /// Sets the source to the previous record
+    pub undo: UndoFn,
+    /// Sets the source to the next record that has been undone
+    pub redo: RedoFn,
+    /// Removes all records except the one of the current value
+    pub clear: ClearFn,
+    /// Sets the source to the last record which discards changes that haven't been recorded
+    pub reset: ResetFn,
+    /// Stops recording changes of the source
+    pub pause: PauseFn,
+    /// Continues recording changes of the source
+    pub resume: ResumeFn,
+}
+
+impl<T, CommitFn, UndoFn, RedoFn, ClearFn, ResetFn, PauseFn, ResumeFn>
+    UseHistoryReturn<T, CommitFn, UndoFn, RedoFn, ClearFn, ResetFn, PauseFn, ResumeFn>
+where
+    T: 'static,
+    CommitFn: Fn() + Clone + 'static,
+    UndoFn: Fn() + Clone + 'static,
+    RedoFn: Fn() + Clone + 'static,
+    ClearFn: Fn() + Clone + 'static,
+    ResetFn: Fn() + Clone + 'static,
+    PauseFn: Fn() + Clone + 'static,
+    ResumeFn: Fn() + Clone + 'static,
+{
+    /// Runs `f` with the tracking paused and records the result as a single change.
+    pub fn batch(&self, f: impl FnOnce()) {
+        let was_tracking = self.is_tracking.get_untracked();
+
+        (self.pause)();
+        f();
+        (self.commit)();
+
+        if was_tracking {
+            (self.resume)();
+        }
+    }
+}
@@ -0,0 +1,246 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::utils::random_id;
+use crate::{use_event_listener, use_window};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{popstate, scroll};
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use wasm_bindgen::JsCast;
+
+/// Property of `history.state` that identifies a history entry.
+const KEY_PROPERTY: &str = "__leptos_use_scroll_key";
+
+/// Saves and restores the scroll position of a scroll container per history entry.
+///
+/// The browser restores the scroll position of the window when going back and forward but not
+/// the one of other scroll containers like a sidebar or the main area of an app layout. Opt in
+/// a container by calling this function with the element and an `id` that is unique among the
+/// containers. Its scroll position is recorded for the current history entry while the user
+/// scrolls. When the user navigates back or forward to that entry the position is restored,
+/// either right away if the container stays on the page or as soon as it's mounted again.
+///
+/// History entries are identified by a key that is added to `history.state`. This way it works
+/// with the navigations of `leptos_router` and anything else that uses the History API.
+/// Positions are kept in memory for at most `max_entries` history entries.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Main;
+/// # use leptos_use::{
+/// #     use_history_state_scroll_restoration, UseHistoryStateScrollRestorationReturn,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Main>();
+///
+/// let UseHistoryStateScrollRestorationReturn { restore, .. } =
+///     use_history_state_scroll_restoration(el, "main");
+///
+/// view! {
+///     <main node_ref=el style="overflow: auto; height: 100vh">
+///         // routes
+///     </main>
+/// }
+/// # }
+/// ```
+///
+/// If the content of the container is loaded asynchronously it might not be high enough yet
+/// when the position is restored. Call `restore` again once the content is there.
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is recorded and `save` and `restore` do nothing.
+pub fn use_history_state_scroll_restoration<El, T>(
+    target: El,
+    id: &str,
+) -> UseHistoryStateScrollRestorationReturn<impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_history_state_scroll_restoration_with_options(
+        target,
+        id,
+        UseHistoryStateScrollRestorationOptions::default(),
+    )
+}
+
+/// Version of [`use_history_state_scroll_restoration`] that takes a `UseHistoryStateScrollRestorationOptions`. See [`use_history_state_scroll_restoration`] for how to use.
+pub fn use_history_state_scroll_restoration_with_options<El, T>(
+    target: El,
+    id: &str,
+    options: UseHistoryStateScrollRestorationOptions,
+) -> UseHistoryStateScrollRestorationReturn<impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseHistoryStateScrollRestorationOptions { max_entries } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let id = store_value(id.to_string());
+
+    let save = move || {
+        if let Some(el) = target.get_untracked() {
+            let position = (el.scroll_left(), el.scroll_top());
+            let key = entry_key();
+
+            id.with_value(|id| save_position(key, id, position, max_entries));
+        }
+    };
+
+    let restore = move || {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let Some(el) = target.get_untracked() else {
+                return;
+            };
+
+            let position =
+                existing_entry_key().and_then(|key| id.with_value(|id| saved_position(&key, id)));
+
+            if let Some((left, top)) = position {
+                el.set_scroll_left(left);
+                el.set_scroll_top(top);
+            }
+        }
+    };
+
+    let _ = use_event_listener(target, scroll, {
+        let save = save.clone();
+        move |_| save()
+    });
+
+    // wait a frame so the router can render the content of the entry first
+    let restore_in_next_frame = {
+        let restore = restore.clone();
+        move || request_animation_frame(restore.clone())
+    };
+
+    let _ = use_event_listener(use_window(), popstate, {
+        let restore_in_next_frame = restore_in_next_frame.clone();
+        move |_| restore_in_next_frame()
+    });
+
+    let _ = watch(
+        move || target.get(),
+        move |el, _, _| {
+            if el.is_some() {
+                restore_in_next_frame();
+            }
+        },
+        true,
+    );
+
+    UseHistoryStateScrollRestorationReturn { save, restore }
+}
+
+/// Scroll positions of the containers by their id
+type Positions = HashMap<String, (i32, i32)>;
+
+thread_local! {
+    static ENTRIES: RefCell<VecDeque<(String, Positions)>> = const { RefCell::new(VecDeque::new()) };
+}
+
+fn save_position(key: String, id: &str, position: (i32, i32), max_entries: usize) {
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+
+        // the most recently used entry is at the back
+        let index = entries.iter().position(|(k, _)| *k == key);
+        let mut entry = match index {
+            Some(index) => entries.remove(index).unwrap_or_default(),
+            None => (key, Positions::new()),
+        };
+        entry.1.insert(id.to_string(), position);
+        entries.push_back(entry);
+
+        while entries.len() > max_entries {
+            entries.pop_front();
+        }
+    });
+}
+
+fn saved_position(key: &str, id: &str) -> Option<(i32, i32)> {
+    ENTRIES.with(|entries| {
+        entries
+            .borrow()
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, positions)| positions.get(id).copied())
+    })
+}
+
+/// The key of the current history entry if it has one already.
+fn existing_entry_key() -> Option<String> {
+    let state = window().history().ok()?.state().ok()?;
+
+    if !state.is_object() {
+        return None;
+    }
+
+    js_sys::Reflect::get(&state, &KEY_PROPERTY.into())
+        .ok()?
+        .as_string()
+}
+
+/// The key of the current history entry. If it doesn't have one yet a new key is added to
+/// `history.state`, keeping everything else that's in there.
+fn entry_key() -> String {
+    if let Some(key) = existing_entry_key() {
+        return key;
+    }
+
+    let key = random_id();
+
+    #[cfg(not(feature = "ssr"))]
+    if let Ok(history) = window().history() {
+        let state = js_sys::Object::new();
+        if let Ok(current) = history.state() {
+            if current.is_object() {
+                let _ = js_sys::Object::assign(&state, current.unchecked_ref());
+            }
+        }
+        let _ = js_sys::Reflect::set(&state, &KEY_PROPERTY.into(), &key.as_str().into());
+
+        let _ = history.replace_state(&state, "");
+    }
+
+    key
+}
+
+/// Options for [`use_history_state_scroll_restoration_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseHistoryStateScrollRestorationOptions {
+    /// Maximum number of history entries that positions are kept for. When a position is saved
+    /// the least recently used entries beyond this number are dropped. Defaults to `50`.
+    max_entries: usize,
+}
+
+impl Default for UseHistoryStateScrollRestorationOptions {
+    fn default() -> Self {
+        Self { max_entries: 50 }
+    }
+}
+
+/// Return type of [`use_history_state_scroll_restoration`].
+pub struct UseHistoryStateScrollRestorationReturn<SaveFn, RestoreFn>
+where
+    SaveFn: Fn() + Clone,
+    RestoreFn: Fn() + Clone,
+{
+    /// Records the current scroll position for the current history entry. This happens
+    /// automatically while scrolling.
+    pub save: SaveFn,
+
+    /// Scrolls the container to the position recorded for the current history entry
+    pub restore: RestoreFn,
+}
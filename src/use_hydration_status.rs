@@ -0,0 +1,114 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use leptos::*;
+use std::cell::RefCell;
+
+/// Whether the hydration of the server rendered HTML has completed.
+///
+/// Code that reads browser state like storage, media queries or the window size and renders
+/// something depending on it produces different HTML than the server during hydration. The
+/// result are hydration warnings and parts of the page that never update. Render with the same
+/// values as the server until the returned signal is `true` to avoid this.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_hydration_status, use_preferred_dark};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let is_hydrated = use_hydration_status();
+/// let is_dark_preferred = use_preferred_dark();
+///
+/// view! {
+///     <Show when=move || is_hydrated.get() && is_dark_preferred.get()>
+///         <p>"Dark mode"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// In an app that is rendered in the browser only (no hydration) the signal is `true` right away.
+///
+/// ### Deferring Work
+///
+/// To run code once after hydration instead use [`after_hydration`].
+///
+/// ## Server-Side Rendering
+///
+/// On the server the returned signal is always `false`.
+pub fn use_hydration_status() -> Signal<bool> {
+    let (is_hydrated, set_hydrated) = create_signal(false);
+
+    #[cfg(not(feature = "ssr"))]
+    after_hydration(move || {
+        let _ = set_hydrated.try_set(true);
+    });
+
+    is_hydrated.into()
+}
+
+thread_local! {
+    /// Callbacks that wait for the hydration to finish. `None` if no flush is scheduled.
+    static PENDING_CALLBACKS: RefCell<Option<Vec<Box<dyn FnOnce()>>>> = RefCell::new(None);
+}
+
+/// Runs `callback` once the hydration has completed.
+///
+/// While hydrating, the callbacks are collected and run in the order they have been registered
+/// in the next animation frame. When not hydrating the callback is run immediately.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::after_hydration;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (width, set_width) = create_signal(0.0);
+///
+/// after_hydration(move || {
+///     let width = window().inner_width().ok().and_then(|width| width.as_f64());
+///     let _ = set_width.try_set(width.unwrap_or_default());
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The owning component might have been unmounted by the time the callback is run. Don't
+/// access signals that might have been disposed already without the `try_` methods.
+///
+/// On the server the callback is never called.
+pub fn after_hydration(callback: impl FnOnce() + 'static) {
+    #[cfg(feature = "ssr")]
+    let _ = callback;
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        if !leptos::leptos_dom::HydrationCtx::is_hydrating() {
+            callback();
+            return;
+        }
+
+        PENDING_CALLBACKS.with(|pending| {
+            let mut pending = pending.borrow_mut();
+
+            match pending.as_mut() {
+                Some(callbacks) => callbacks.push(Box::new(callback)),
+                None => {
+                    *pending = Some(vec![Box::new(callback)]);
+                    request_animation_frame(run_pending_callbacks);
+                }
+            }
+        });
+    }
+}
+
+fn run_pending_callbacks() {
+    let callbacks = PENDING_CALLBACKS.with(|pending| pending.borrow_mut().take());
+
+    for callback in callbacks.into_iter().flatten() {
+        callback();
+    }
+}
@@ -0,0 +1,660 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::use_window::use_window;
+use crate::{js, js_fut, use_event_listener};
+use cfg_if::cfg_if;
+use codee::{CodecError, Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Typed [`postMessage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/postMessage)
+/// bridge between a page and an iframe.
+///
+/// Call this in the parent page with the iframe element and [`use_parent_messaging`] in the
+/// page that is loaded in the iframe. Both sides get the same functionality:
+///
+/// - `is_ready` becomes `true` as soon as the other side has answered the handshake. Messages
+///   that are sent before are queued.
+/// - `request` sends a request to the other side and resolves with its response. Requests are
+///   answered by the `on_request` option of the other side.
+/// - `emit` sends an event to the other side which is received in `event`.
+///
+/// Values are (en)decoded via the given codec, like in [`fn@crate::use_broadcast_channel`].
+/// Usually `T` is an enum of all messages.
+///
+/// Messages are only sent to and accepted from `target_origin` which is the origin of this
+/// page by default. Messages from other windows are ignored.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Iframe;
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::{use_iframe_messaging_with_options, UseIframeMessagingOptions, UseIframeMessagingReturn};
+/// # use codee::string::JsonSerdeCodec;
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// enum Message {
+///     GetTitle,
+///     Title(String),
+///     Scrolled(f64),
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let iframe = create_node_ref::<Iframe>();
+///
+/// let UseIframeMessagingReturn {
+///     is_ready,
+///     event,
+///     request,
+///     ..
+/// } = use_iframe_messaging_with_options::<Message, JsonSerdeCodec, _, _>(
+///     iframe,
+///     UseIframeMessagingOptions::default().target_origin("https://widgets.example.com"),
+/// );
+///
+/// let get_title = move |_| {
+///     let request = request.clone();
+///
+///     spawn_local(async move {
+///         if let Ok(Message::Title(title)) = request(&Message::GetTitle).await {
+///             logging::log!("iframe title: {title}");
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <iframe node_ref=iframe src="https://widgets.example.com/widget" />
+///     <button disabled=move || !is_ready.get() on:click=get_title>"Get title"</button>
+/// }
+/// # }
+/// ```
+///
+/// And inside of the iframe:
+///
+/// ```
+/// # use leptos::*;
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::{use_parent_messaging_with_options, UseIframeMessagingOptions};
+/// # use codee::string::JsonSerdeCodec;
+/// #
+/// # #[derive(Serialize, Deserialize, Clone, PartialEq)]
+/// # enum Message {
+/// #     GetTitle,
+/// #     Title(String),
+/// #     Scrolled(f64),
+/// # }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let messaging = use_parent_messaging_with_options::<Message, JsonSerdeCodec>(
+///     UseIframeMessagingOptions::default()
+///         .target_origin("https://app.example.com")
+///         .on_request(|message: Message| match message {
+///             Message::GetTitle => Some(Message::Title(document().title())),
+///             _ => None,
+///         }),
+/// );
+///
+/// (messaging.emit)(&Message::Scrolled(120.0));
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_ready` is always `false`, `emit` does nothing and requests never resolve.
+pub fn use_iframe_messaging<T, C, El, ElT>(
+    iframe: El,
+) -> UseIframeMessagingReturn<
+    T,
+    impl Fn(&T) + Clone,
+    impl Fn(&T) -> IframeRequestFuture<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error> + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str> + 'static,
+    El: Into<ElementMaybeSignal<ElT, web_sys::EventTarget>>,
+    ElT: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    use_iframe_messaging_with_options::<T, C, El, ElT>(iframe, UseIframeMessagingOptions::default())
+}
+
+/// Version of [`use_iframe_messaging`] that takes a `UseIframeMessagingOptions`. See [`use_iframe_messaging`] for how to use.
+pub fn use_iframe_messaging_with_options<T, C, El, ElT>(
+    iframe: El,
+    options: UseIframeMessagingOptions<T>,
+) -> UseIframeMessagingReturn<
+    T,
+    impl Fn(&T) + Clone,
+    impl Fn(&T) -> IframeRequestFuture<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error> + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str> + 'static,
+    El: Into<ElementMaybeSignal<ElT, web_sys::EventTarget>>,
+    ElT: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let iframe = iframe.into();
+
+    let peer = {
+        let iframe = iframe.clone();
+
+        move || {
+            iframe
+                .get_untracked()
+                .map(Into::<web_sys::EventTarget>::into)
+                .and_then(|el| el.dyn_into::<web_sys::HtmlIFrameElement>().ok())
+                .and_then(|el| el.content_window())
+        }
+    };
+
+    let messaging = use_messaging::<T, C>(Rc::new(peer), options);
+
+    // the iframe says hello when it's loaded but a reload has to reset the handshake
+    let _ = use_event_listener(iframe, ev::load, {
+        let restart = messaging.restart.clone();
+        move |_| restart()
+    });
+
+    messaging.into_return()
+}
+
+/// Counterpart of [`use_iframe_messaging`] that is used in the page inside of the iframe to
+/// communicate with the parent page. See [`use_iframe_messaging`] for how to use.
+pub fn use_parent_messaging<T, C>() -> UseIframeMessagingReturn<
+    T,
+    impl Fn(&T) + Clone,
+    impl Fn(&T) -> IframeRequestFuture<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error> + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str> + 'static,
+{
+    use_parent_messaging_with_options::<T, C>(UseIframeMessagingOptions::default())
+}
+
+/// Version of [`use_parent_messaging`] that takes a `UseIframeMessagingOptions`. See [`use_iframe_messaging`] for how to use.
+pub fn use_parent_messaging_with_options<T, C>(
+    options: UseIframeMessagingOptions<T>,
+) -> UseIframeMessagingReturn<
+    T,
+    impl Fn(&T) + Clone,
+    impl Fn(&T) -> IframeRequestFuture<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error> + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str> + 'static,
+{
+    let peer = || {
+        window()
+            .parent()
+            .ok()
+            .flatten()
+            .filter(|parent| JsValue::from(parent.clone()) != JsValue::from(window()))
+    };
+
+    let messaging = use_messaging::<T, C>(Rc::new(peer), options);
+    (messaging.restart)();
+
+    messaging.into_return()
+}
+
+/// The future returned by the `request` function of [`use_iframe_messaging`].
+pub type IframeRequestFuture<T, E, D> =
+    Pin<Box<dyn Future<Output = Result<T, UseIframeMessagingError<E, D>>>>>;
+
+struct Messaging<T, EmitFn, RequestFn, RestartFn, E, D>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+{
+    is_ready: Signal<bool>,
+    event: Signal<Option<T>>,
+    error: Signal<Option<UseIframeMessagingError<E, D>>>,
+    emit: EmitFn,
+    request: RequestFn,
+    restart: RestartFn,
+}
+
+impl<T, EmitFn, RequestFn, RestartFn, E, D> Messaging<T, EmitFn, RequestFn, RestartFn, E, D>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+    EmitFn: Fn(&T) + Clone,
+    RequestFn: Fn(&T) -> IframeRequestFuture<T, E, D> + Clone,
+{
+    fn into_return(self) -> UseIframeMessagingReturn<T, EmitFn, RequestFn, E, D> {
+        UseIframeMessagingReturn {
+            is_ready: self.is_ready,
+            event: self.event,
+            error: self.error,
+            emit: self.emit,
+            request: self.request,
+        }
+    }
+}
+
+/// Kinds of the envelopes that are sent between the windows
+const HELLO: &str = "hello";
+const HELLO_ACK: &str = "hello-ack";
+const REQUEST: &str = "request";
+const RESPONSE: &str = "response";
+const EVENT: &str = "event";
+
+/// Rejection reasons of requests that haven't been answered by the other side
+const TIMEOUT: &str = "timeout";
+const DISPOSED: &str = "disposed";
+
+fn use_messaging<T, C>(
+    peer: Rc<dyn Fn() -> Option<web_sys::Window>>,
+    options: UseIframeMessagingOptions<T>,
+) -> Messaging<
+    T,
+    impl Fn(&T) + Clone,
+    impl Fn(&T) -> IframeRequestFuture<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error> + Clone,
+    impl Fn() + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str> + 'static,
+{
+    let UseIframeMessagingOptions {
+        channel,
+        target_origin,
+        request_timeout,
+        on_request,
+    } = options;
+
+    let target_origin = target_origin.unwrap_or_else(|| {
+        cfg_if! { if #[cfg(feature = "ssr")] {
+            "*".to_string()
+        } else {
+            window().location().origin().unwrap_or_else(|_| "*".to_string())
+        }}
+    });
+
+    let (is_ready, set_ready) = create_signal(false);
+    let (event, set_event) = create_signal(None::<T>);
+    let (error, set_error) = create_signal(
+        None::<UseIframeMessagingError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>>,
+    );
+
+    let queue = store_value(Vec::<JsValue>::new());
+    let pending = store_value(HashMap::<u32, (js_sys::Function, js_sys::Function)>::new());
+    let next_id = store_value(0_u32);
+
+    let envelope = {
+        let channel = channel.clone();
+
+        move |kind: &str, id: u32, payload: Option<&str>, reason: Option<&str>| {
+            let envelope = js_sys::Object::new();
+            js!(envelope["channel"] = channel.as_str());
+            js!(envelope["kind"] = kind);
+            js!(envelope["id"] = id);
+            if let Some(payload) = payload {
+                js!(envelope["payload"] = payload);
+            }
+            if let Some(reason) = reason {
+                js!(envelope["reason"] = reason);
+            }
+            JsValue::from(envelope)
+        }
+    };
+
+    let post_now = {
+        let peer = Rc::clone(&peer);
+        let target_origin = target_origin.clone();
+
+        move |envelope: &JsValue| {
+            #[cfg(not(feature = "ssr"))]
+            if let Some(peer) = peer() {
+                if let Err(err) = peer.post_message(envelope, &target_origin) {
+                    set_error.set(Some(UseIframeMessagingError::PostMessage(err)));
+                }
+            }
+        }
+    };
+
+    // messages are queued until the other side has answered the handshake
+    let send = {
+        let post_now = post_now.clone();
+
+        move |envelope: JsValue| {
+            if is_ready.get_untracked() {
+                post_now(&envelope);
+            } else {
+                queue.update_value(|queue| queue.push(envelope));
+            }
+        }
+    };
+
+    let set_connected = {
+        let post_now = post_now.clone();
+
+        move || {
+            set_ready.set(true);
+
+            for envelope in queue.try_update_value(std::mem::take).unwrap_or_default() {
+                post_now(&envelope);
+            }
+        }
+    };
+
+    let restart = {
+        let envelope = envelope.clone();
+        let post_now = post_now.clone();
+
+        move || {
+            set_ready.set(false);
+            post_now(&envelope(HELLO, 0, None, None));
+        }
+    };
+
+    let _ = use_event_listener(use_window(), ev::message, {
+        let peer = Rc::clone(&peer);
+        let target_origin = target_origin.clone();
+        let envelope = envelope.clone();
+        let send = send.clone();
+
+        move |message| {
+            let Some(current_peer) = peer() else {
+                return;
+            };
+
+            let is_from_peer = message
+                .source()
+                .is_some_and(|source| JsValue::from(source) == JsValue::from(current_peer));
+
+            if !is_from_peer || (target_origin != "*" && message.origin() != target_origin) {
+                return;
+            }
+
+            let data = message.data();
+
+            if js!(data["channel"])
+                .ok()
+                .and_then(|c| c.as_string())
+                .as_ref()
+                != Some(&channel)
+            {
+                return;
+            }
+
+            let kind = js!(data["kind"]).ok().and_then(|kind| kind.as_string());
+            let id = js!(data["id"])
+                .ok()
+                .and_then(|id| id.as_f64())
+                .unwrap_or_default() as u32;
+            let payload = js!(data["payload"]).ok().and_then(|p| p.as_string());
+
+            match kind.as_deref() {
+                Some(HELLO) => {
+                    post_now(&envelope(HELLO_ACK, 0, None, None));
+                    set_connected();
+                }
+                Some(HELLO_ACK) => set_connected(),
+                Some(REQUEST) => {
+                    let response = payload.map(|payload| C::decode(&payload)).map(|request| {
+                        request.map(|request| {
+                            #[cfg(debug_assertions)]
+                            let prev = SpecialNonReactiveZone::enter();
+
+                            let response = on_request(request);
+
+                            #[cfg(debug_assertions)]
+                            SpecialNonReactiveZone::exit(prev);
+
+                            response
+                        })
+                    });
+
+                    let reply = match response {
+                        Some(Ok(Some(response))) => match C::encode(&response) {
+                            Ok(encoded) => envelope(RESPONSE, id, Some(&encoded), None),
+                            Err(err) => {
+                                set_error.set(Some(UseIframeMessagingError::Codec(
+                                    CodecError::Encode(err),
+                                )));
+                                envelope(RESPONSE, id, None, Some("failed to encode response"))
+                            }
+                        },
+                        Some(Ok(None)) => envelope(RESPONSE, id, None, Some("unhandled request")),
+                        Some(Err(err)) => {
+                            set_error.set(Some(UseIframeMessagingError::Codec(
+                                CodecError::Decode(err),
+                            )));
+                            envelope(RESPONSE, id, None, Some("failed to decode request"))
+                        }
+                        None => envelope(RESPONSE, id, None, Some("request has no payload")),
+                    };
+
+                    send(reply);
+                }
+                Some(RESPONSE) => {
+                    let Some((resolve, reject)) = pending
+                        .try_update_value(|pending| pending.remove(&id))
+                        .flatten()
+                    else {
+                        return;
+                    };
+
+                    let _ = match payload {
+                        Some(payload) => resolve.call1(&JsValue::NULL, &payload.into()),
+                        None => reject.call1(
+                            &JsValue::NULL,
+                            &js!(data["reason"]).unwrap_or(JsValue::UNDEFINED),
+                        ),
+                    };
+                }
+                Some(EVENT) => {
+                    if let Some(payload) = payload {
+                        match C::decode(&payload) {
+                            Ok(value) => set_event.set(Some(value)),
+                            Err(err) => set_error.set(Some(UseIframeMessagingError::Codec(
+                                CodecError::Decode(err),
+                            ))),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+
+    let emit = {
+        let envelope = envelope.clone();
+        let send = send.clone();
+
+        move |value: &T| match C::encode(value) {
+            Ok(encoded) => send(envelope(EVENT, 0, Some(&encoded), None)),
+            Err(err) => set_error.set(Some(UseIframeMessagingError::Codec(CodecError::Encode(
+                err,
+            )))),
+        }
+    };
+
+    let request = move |value: &T| -> IframeRequestFuture<
+        T,
+        <C as Encoder<T>>::Error,
+        <C as Decoder<T>>::Error,
+    > {
+        let encoded = C::encode(value);
+        let envelope = envelope.clone();
+        let send = send.clone();
+
+        Box::pin(async move {
+            let encoded =
+                encoded.map_err(|err| UseIframeMessagingError::Codec(CodecError::Encode(err)))?;
+
+            let id = next_id
+                .try_update_value(|next_id| {
+                    *next_id = next_id.wrapping_add(1);
+                    *next_id
+                })
+                .ok_or(UseIframeMessagingError::Disposed)?;
+
+            let promise = js_sys::Promise::new(&mut |resolve, reject| {
+                pending.update_value(|pending| {
+                    pending.insert(id, (resolve, reject));
+                });
+            });
+
+            send(envelope(REQUEST, id, Some(&encoded), None));
+
+            if let Some(request_timeout) = request_timeout {
+                set_timeout(
+                    move || {
+                        if let Some((_, reject)) = pending
+                            .try_update_value(|pending| pending.remove(&id))
+                            .flatten()
+                        {
+                            let _ = reject.call1(&JsValue::NULL, &TIMEOUT.into());
+                        }
+                    },
+                    Duration::from_millis(request_timeout),
+                );
+            }
+
+            match js_fut!(promise).await {
+                Ok(response) => {
+                    let response = response
+                        .as_string()
+                        .ok_or(UseIframeMessagingError::ValueNotString)?;
+
+                    C::decode(&response)
+                        .map_err(|err| UseIframeMessagingError::Codec(CodecError::Decode(err)))
+                }
+                Err(reason) => Err(match reason.as_string() {
+                    Some(reason) if reason == TIMEOUT => UseIframeMessagingError::Timeout,
+                    Some(reason) if reason == DISPOSED => UseIframeMessagingError::Disposed,
+                    Some(reason) => UseIframeMessagingError::Remote(reason),
+                    None => UseIframeMessagingError::Remote(String::new()),
+                }),
+            }
+        })
+    };
+
+    on_cleanup(move || {
+        for (_, reject) in pending
+            .try_update_value(std::mem::take)
+            .unwrap_or_default()
+            .into_values()
+        {
+            let _ = reject.call1(&JsValue::NULL, &DISPOSED.into());
+        }
+    });
+
+    Messaging {
+        is_ready: is_ready.into(),
+        event: event.into(),
+        error: error.into(),
+        emit,
+        request,
+        restart,
+    }
+}
+
+/// Options for [`use_iframe_messaging_with_options`] and [`use_parent_messaging_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseIframeMessagingOptions<T>
+where
+    T: 'static,
+{
+    /// Name of the channel. Both sides have to use the same name. This allows several bridges
+    /// between the same windows. Defaults to `"leptos-use"`.
+    #[builder(into)]
+    channel: String,
+
+    /// Origin of the other side that messages are sent to and accepted from. `"*"` allows any
+    /// origin. Defaults to `None` which is the origin of this page.
+    #[builder(into)]
+    target_origin: Option<String>,
+
+    /// Time in milliseconds after which a request fails with `UseIframeMessagingError::Timeout`.
+    /// `None` waits forever. Defaults to `Some(10000)`.
+    #[builder(into)]
+    request_timeout: Option<u64>,
+
+    /// Answers requests of the other side. If this returns `None` the request fails on the
+    /// other side with `UseIframeMessagingError::Remote`. Defaults to answering no requests.
+    on_request: Rc<dyn Fn(T) -> Option<T>>,
+}
+
+impl<T> Default for UseIframeMessagingOptions<T>
+where
+    T: 'static,
+{
+    fn default() -> Self {
+        Self {
+            channel: "leptos-use".to_string(),
+            target_origin: None,
+            request_timeout: Some(10000),
+            on_request: Rc::new(|_| None),
+        }
+    }
+}
+
+/// Return type of [`use_iframe_messaging`] and [`use_parent_messaging`].
+pub struct UseIframeMessagingReturn<T, EmitFn, RequestFn, E, D>
+where
+    T: 'static,
+    EmitFn: Fn(&T) + Clone,
+    RequestFn: Fn(&T) -> IframeRequestFuture<T, E, D> + Clone,
+    E: 'static,
+    D: 'static,
+{
+    /// `true` as soon as the other side has answered the handshake
+    pub is_ready: Signal<bool>,
+
+    /// Latest event received from the other side
+    pub event: Signal<Option<T>>,
+
+    /// Latest error
+    pub error: Signal<Option<UseIframeMessagingError<E, D>>>,
+
+    /// Sends an event to the other side
+    pub emit: EmitFn,
+
+    /// Sends a request to the other side and resolves with its response
+    pub request: RequestFn,
+}
+
+#[derive(Debug, Error)]
+pub enum UseIframeMessagingError<E, D> {
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("failed to (de)encode value")]
+    Codec(CodecError<E, D>),
+    #[error("received value is not a string")]
+    ValueNotString,
+    #[error("the request timed out")]
+    Timeout,
+    #[error("the other side failed to answer the request: {0}")]
+    Remote(String),
+    #[error("the bridge has been disposed")]
+    Disposed,
+}
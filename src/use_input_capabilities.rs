@@ -0,0 +1,124 @@
+use crate::use_media_query;
+use leptos::*;
+use std::fmt::Display;
+
+/// Reactive pointer and hover capabilities of the device.
+///
+/// Based on the media queries [`pointer`](https://developer.mozilla.org/en-US/docs/Web/CSS/@media/pointer),
+/// [`any-pointer`](https://developer.mozilla.org/en-US/docs/Web/CSS/@media/any-pointer),
+/// [`hover`](https://developer.mozilla.org/en-US/docs/Web/CSS/@media/hover) and
+/// [`any-hover`](https://developer.mozilla.org/en-US/docs/Web/CSS/@media/any-hover).
+/// The signals update when input devices are added or removed, e.g. when a mouse is attached
+/// to a tablet. This way the UI can swap interaction affordances at runtime, like showing
+/// actions on hover only if any input device can hover.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_input_capabilities, PointerAccuracy, UseInputCapabilitiesReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseInputCapabilitiesReturn {
+///     any_hover,
+///     primary_pointer,
+///     ..
+/// } = use_input_capabilities();
+///
+/// view! {
+///     <Show when=move || !any_hover.get()>
+///         <button>"Show actions"</button>
+///     </Show>
+///     <button class:large=move || primary_pointer.get() == PointerAccuracy::Coarse>"Save"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server all capabilities are `false`, `primary_pointer` is `PointerAccuracy::None`
+/// and `max_touch_points` is `0`.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_is_touch_device`]
+/// * [`fn@crate::use_media_query`]
+pub fn use_input_capabilities() -> UseInputCapabilitiesReturn {
+    let can_hover = use_media_query("(hover: hover)");
+    let any_hover = use_media_query("(any-hover: hover)");
+
+    let is_fine = use_media_query("(pointer: fine)");
+    let is_coarse = use_media_query("(pointer: coarse)");
+    let any_fine_pointer = use_media_query("(any-pointer: fine)");
+    let any_coarse_pointer = use_media_query("(any-pointer: coarse)");
+
+    let primary_pointer = Signal::derive(move || {
+        if is_fine.get() {
+            PointerAccuracy::Fine
+        } else if is_coarse.get() {
+            PointerAccuracy::Coarse
+        } else {
+            PointerAccuracy::None
+        }
+    });
+
+    #[cfg(feature = "ssr")]
+    let max_touch_points = 0;
+
+    #[cfg(not(feature = "ssr"))]
+    let max_touch_points = window().navigator().max_touch_points().max(0) as u32;
+
+    UseInputCapabilitiesReturn {
+        can_hover,
+        any_hover,
+        primary_pointer,
+        any_fine_pointer,
+        any_coarse_pointer,
+        max_touch_points: Signal::derive(move || max_touch_points),
+    }
+}
+
+/// Return type of [`use_input_capabilities`].
+pub struct UseInputCapabilitiesReturn {
+    /// `true` if the primary input device can hover over elements
+    pub can_hover: Signal<bool>,
+
+    /// `true` if any of the input devices can hover over elements
+    pub any_hover: Signal<bool>,
+
+    /// Accuracy of the primary pointing device
+    pub primary_pointer: Signal<PointerAccuracy>,
+
+    /// `true` if any of the input devices is an accurate pointing device like a mouse
+    pub any_fine_pointer: Signal<bool>,
+
+    /// `true` if any of the input devices is an inaccurate pointing device like a finger
+    pub any_coarse_pointer: Signal<bool>,
+
+    /// Maximum number of simultaneous touch points the device supports. `0` if it doesn't
+    /// support touch.
+    pub max_touch_points: Signal<u32>,
+}
+
+/// Accuracy of a pointing device. Returned by [`use_input_capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointerAccuracy {
+    /// An accurate pointing device like a mouse or a stylus
+    Fine,
+    /// An inaccurate pointing device like a finger
+    Coarse,
+    /// No pointing device, e.g. only a keyboard
+    #[default]
+    None,
+}
+
+impl Display for PointerAccuracy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointerAccuracy::Fine => write!(f, "fine"),
+            PointerAccuracy::Coarse => write!(f, "coarse"),
+            PointerAccuracy::None => write!(f, "none"),
+        }
+    }
+}
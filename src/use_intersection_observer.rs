@@ -5,9 +5,8 @@ use leptos::*;
 use std::marker::PhantomData;
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
+    use crate::utils::{element_id, ObserverPool, INTERSECTION_OBSERVER_POOL};
     use crate::{watch_with_options, WatchOptions};
-    use std::cell::RefCell;
-    use std::rc::Rc;
     use wasm_bindgen::prelude::*;
 }}
 
@@ -46,6 +45,19 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Shared Observers
+///
+/// All calls of `use_intersection_observer` with the same `root`, `root_margin` and `thresholds`
+/// share one `IntersectionObserver` instance. The entries it reports are dispatched to the
+/// callbacks of the hooks that observe the respective elements. This keeps the overhead low even
+/// if hundreds of elements are observed.
+///
+/// Because of this the `IntersectionObserver` that is passed to the callback is shared as well.
+/// Please don't call `disconnect()` on it but use the returned `stop` or `pause` functions instead.
+/// Or use [`use_intersection_observer_with_handle`] whose callback receives an
+/// [`IntersectionObserverHandle`] instead. Calling `disconnect()` on it only stops observing the
+/// targets of this hook call.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this amounts to a no-op.
@@ -70,10 +82,9 @@ where
 }
 
 /// Version of [`use_intersection_observer`] that takes a [`UseIntersectionObserverOptions`]. See [`use_intersection_observer`] for how to use.
-#[cfg_attr(feature = "ssr", allow(unused_variables, unused_mut))]
 pub fn use_intersection_observer_with_options<El, T, RootEl, RootT, F>(
     target: El,
-    mut callback: F,
+    callback: F,
     options: UseIntersectionObserverOptions<RootEl, RootT>,
 ) -> UseIntersectionObserverReturn<impl Fn() + Clone, impl Fn() + Clone, impl Fn() + Clone>
 where
@@ -82,6 +93,81 @@ where
     RootEl: Into<ElementMaybeSignal<RootT, web_sys::Element>>,
     RootT: Into<web_sys::Element> + Clone + 'static,
     F: FnMut(Vec<web_sys::IntersectionObserverEntry>, web_sys::IntersectionObserver) + 'static,
+{
+    let mut callback = callback;
+
+    use_pooled_intersection_observer(
+        target,
+        move |entries, observer, _| callback(entries, observer),
+        options,
+    )
+}
+
+/// Version of [`use_intersection_observer_with_options`] whose callback receives an
+/// [`IntersectionObserverHandle`] instead of the shared `IntersectionObserver`.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_intersection_observer_with_handle, UseIntersectionObserverOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+/// let (seen, set_seen) = create_signal(false);
+///
+/// use_intersection_observer_with_handle(
+///     el,
+///     move |entries, handle| {
+///         if entries[0].is_intersecting() {
+///             set_seen.set(true);
+///             // stop observing once the element has been seen
+///             handle.disconnect();
+///         }
+///     },
+///     UseIntersectionObserverOptions::default(),
+/// );
+/// #
+/// # view! { <div node_ref=el></div> }
+/// # }
+/// ```
+pub fn use_intersection_observer_with_handle<El, T, RootEl, RootT, F>(
+    target: El,
+    callback: F,
+    options: UseIntersectionObserverOptions<RootEl, RootT>,
+) -> UseIntersectionObserverReturn<impl Fn() + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+    RootEl: Into<ElementMaybeSignal<RootT, web_sys::Element>>,
+    RootT: Into<web_sys::Element> + Clone + 'static,
+    F: FnMut(Vec<web_sys::IntersectionObserverEntry>, IntersectionObserverHandle) + 'static,
+{
+    let mut callback = callback;
+
+    use_pooled_intersection_observer(
+        target,
+        move |entries, _, handle| callback(entries, handle),
+        options,
+    )
+}
+
+#[cfg_attr(feature = "ssr", allow(unused_variables))]
+fn use_pooled_intersection_observer<El, T, RootEl, RootT, F>(
+    target: El,
+    callback: F,
+    options: UseIntersectionObserverOptions<RootEl, RootT>,
+) -> UseIntersectionObserverReturn<impl Fn() + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+    RootEl: Into<ElementMaybeSignal<RootT, web_sys::Element>>,
+    RootT: Into<web_sys::Element> + Clone + 'static,
+    F: FnMut(
+            Vec<web_sys::IntersectionObserverEntry>,
+            web_sys::IntersectionObserver,
+            IntersectionObserverHandle,
+        ) + 'static,
 {
     let UseIntersectionObserverOptions {
         immediate,
@@ -98,38 +184,13 @@ where
         let cleanup = || {};
         let stop = || {};
     } else {
-        let closure_js = Closure::<dyn FnMut(js_sys::Array, web_sys::IntersectionObserver)>::new(
-            move |entries: js_sys::Array, observer| {
-                #[cfg(debug_assertions)]
-                let prev = SpecialNonReactiveZone::enter();
-
-                callback(
-                    entries
-                        .to_vec()
-                        .into_iter()
-                        .map(|v| v.unchecked_into::<web_sys::IntersectionObserverEntry>())
-                        .collect(),
-                    observer,
-                );
-
-                #[cfg(debug_assertions)]
-                SpecialNonReactiveZone::exit(prev);
-            },
-        )
-        .into_js_value();
-
-        let observer: Rc<RefCell<Option<web_sys::IntersectionObserver>>> =
-            Rc::new(RefCell::new(None));
-
-        let cleanup = {
-            let obsserver = Rc::clone(&observer);
+        let mut callback = callback;
+        let subscriber =
+            ObserverPool::subscribe(&INTERSECTION_OBSERVER_POOL, move |entries, observer, subscriber| {
+                callback(entries, observer, IntersectionObserverHandle { subscriber })
+            });
 
-            move || {
-                if let Some(o) = obsserver.take() {
-                    o.disconnect();
-                }
-            }
-        };
+        let cleanup = move || ObserverPool::unobserve_all(&INTERSECTION_OBSERVER_POOL, subscriber);
 
         let targets = target.into();
         let root = root.map(|root| (root).into());
@@ -154,44 +215,51 @@ where
                         return;
                     }
 
-                    let mut options = web_sys::IntersectionObserverInit::new();
-                    options.root_margin(&root_margin).threshold(
-                        &thresholds
-                            .iter()
-                            .copied()
-                            .map(JsValue::from)
-                            .collect::<js_sys::Array>(),
+                    let root: Option<web_sys::Element> = root.flatten().map(|root| root.into());
+
+                    // one shared observer per set of options
+                    let key = format!(
+                        "{:?}|{}|{:?}",
+                        root.as_ref().map(element_id),
+                        root_margin,
+                        thresholds
                     );
 
-                    if let Some(Some(root)) = root {
-                        let root: web_sys::Element = root.clone().into();
-                        options.root(Some(&root));
-                    }
+                    let options = || {
+                        let mut options = web_sys::IntersectionObserverInit::new();
+                        options.root_margin(&root_margin).threshold(
+                            &thresholds
+                                .iter()
+                                .copied()
+                                .map(JsValue::from)
+                                .collect::<js_sys::Array>(),
+                        );
 
-                    let obs = web_sys::IntersectionObserver::new_with_options(
-                        closure_js.clone().as_ref().unchecked_ref(),
-                        &options,
-                    )
-                    .expect("failed to create IntersectionObserver");
+                        if let Some(root) = &root {
+                            options.root(Some(root));
+                        }
+
+                        options
+                    };
 
                     for target in targets.iter().flatten() {
                         let target: web_sys::Element = target.clone().into();
-                        obs.observe(&target);
+                        ObserverPool::observe(
+                            &INTERSECTION_OBSERVER_POOL,
+                            &key,
+                            options,
+                            subscriber,
+                            &target,
+                        );
                     }
-
-                    observer.replace(Some(obs));
                 },
                 WatchOptions::default().immediate(immediate),
             )
         };
 
-        let stop = {
-            let cleanup = cleanup.clone();
-
-            move || {
-                cleanup();
-                stop_watch();
-            }
+        let stop = move || {
+            stop_watch();
+            ObserverPool::unsubscribe(&INTERSECTION_OBSERVER_POOL, subscriber);
         };
 
         on_cleanup(stop.clone());
@@ -270,6 +338,23 @@ impl Default for UseIntersectionObserverOptions<web_sys::Element, web_sys::Eleme
     }
 }
 
+/// Handle to the shared `IntersectionObserver` that is passed to the callback of
+/// [`use_intersection_observer_with_handle`].
+#[derive(Clone, Debug)]
+pub struct IntersectionObserverHandle {
+    #[cfg(not(feature = "ssr"))]
+    subscriber: usize,
+}
+
+impl IntersectionObserverHandle {
+    /// Stops observing the targets of the hook call this handle belongs to. Other hook calls
+    /// that share the same `IntersectionObserver` are not affected.
+    pub fn disconnect(&self) {
+        #[cfg(not(feature = "ssr"))]
+        ObserverPool::unobserve_all(&INTERSECTION_OBSERVER_POOL, self.subscriber);
+    }
+}
+
 /// The return value of [`use_intersection_observer`].
 pub struct UseIntersectionObserverReturn<StopFn, PauseFn, ResumeFn>
 where
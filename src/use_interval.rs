@@ -1,12 +1,20 @@
-use crate::utils::Pausable;
-use crate::{use_interval_fn_with_options, UseIntervalFnOptions};
+use crate::core::now;
+use crate::utils::{use_visibility_scheduler, TimerHiddenBehavior};
+use crate::{use_interval_fn_with_options, UseIntervalFnOptions, UseIntervalFnReturn};
 use default_struct_builder::DefaultBuilder;
 use std::rc::Rc;
 
 use leptos::*;
 
+/// How early in milliseconds a tick of the timer may fire and still be counted.
+const TIMER_TOLERANCE: f64 = 1.0;
+
 /// Reactive counter increases on every interval.
 ///
+/// The counter is derived from the elapsed wall-clock time instead of the number of timer ticks.
+/// So it doesn't drift when the browser throttles the timer, for example in a background tab.
+/// If ticks have been missed the counter jumps to the correct value with the next tick.
+///
 /// ## Demo
 ///
 /// [Link to Demo](https://github.com/Synphonyte/leptos-use/tree/main/examples/use_interval)
@@ -30,6 +38,29 @@ use leptos::*;
 /// # }
 /// ```
 ///
+/// The time while paused isn't counted.
+///
+/// ### Hidden Page
+///
+/// With the option `hidden_behavior` you can control what happens while the page is hidden.
+/// See [`TimerHiddenBehavior`] for details. With `Pause` the time while hidden isn't counted and
+/// with `CatchUp` the counter jumps to the correct value when the page becomes visible again.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_interval_with_options, UseIntervalOptions, UseIntervalReturn};
+/// # use leptos_use::utils::TimerHiddenBehavior;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseIntervalReturn { counter, .. } = use_interval_with_options(
+///     1000,
+///     UseIntervalOptions::default().hidden_behavior(TimerHiddenBehavior::CatchUp),
+/// );
+/// # view! { }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this function will simply be ignored.
@@ -53,35 +84,189 @@ where
     let UseIntervalOptions {
         immediate,
         callback,
+        hidden_behavior,
     } = options;
 
+    let interval = interval.into();
+
     let (counter, set_counter) = create_signal(0u64);
+    let (is_active, set_active) = create_signal(immediate);
 
-    let update = move || set_counter.update(|count| *count += 1);
-    let reset = move || set_counter.set(0);
+    // The counter is `base_count` plus the number of intervals in the counted time since then.
+    let base_count = store_value(0u64);
+    let current_interval = store_value(interval.get_untracked());
+    // Counted time of the segments that have ended
+    let counted = store_value(0.0_f64);
+    // Start of the segment that is being counted right now
+    let segment_start = store_value(None::<f64>);
 
-    let cb = move || {
-        update();
-        callback(counter.get());
+    let start_segment = move || {
+        if segment_start.get_value().is_none() {
+            segment_start.set_value(Some(now()));
+        }
     };
 
-    let Pausable {
-        is_active,
-        pause,
-        resume,
+    let end_segment = move || {
+        if let Some(start) = segment_start.get_value() {
+            counted.update_value(|counted| *counted += now() - start);
+            segment_start.set_value(None);
+        }
+    };
+
+    // Count including the time that will have passed in `tolerance` milliseconds
+    let count_at = move |tolerance: f64| {
+        let interval = current_interval.get_value();
+        if interval == 0 {
+            return base_count.get_value();
+        }
+
+        let elapsed = counted.get_value()
+            + segment_start
+                .get_value()
+                .map(|start| now() - start)
+                .unwrap_or(0.0);
+
+        base_count.get_value() + ((elapsed + tolerance) / interval as f64).floor() as u64
+    };
+
+    let elapsed_count = move || count_at(0.0);
+
+    let set_count = move |count: u64| {
+        // never count backwards after a tick that has been counted early
+        if count > counter.get_untracked() {
+            set_counter.set(count);
+            callback(count);
+        }
+    };
+
+    let sync = {
+        let set_count = set_count.clone();
+        move || set_count(elapsed_count())
+    };
+
+    // Timers can fire a tiny bit early because of the clock resolution. A tick that is due
+    // within that tolerance is counted right away instead of only with the next tick.
+    let tick = move || set_count(count_at(TIMER_TOLERANCE));
+
+    let UseIntervalFnReturn {
+        is_active: is_ticking,
+        pause: stop_ticking,
+        resume: start_ticking,
+        ..
     } = use_interval_fn_with_options(
-        cb,
+        tick,
         interval,
-        UseIntervalFnOptions {
-            immediate,
-            immediate_callback: false,
+        UseIntervalFnOptions::default().immediate(false),
+    );
+
+    let scheduler = use_visibility_scheduler(
+        hidden_behavior,
+        move || is_active.get_untracked(),
+        {
+            let sync = sync.clone();
+            let stop_ticking = stop_ticking.clone();
+
+            move || {
+                // with `CatchUp` the time while hidden is still counted
+                if hidden_behavior == TimerHiddenBehavior::Pause {
+                    end_segment();
+                }
+                sync();
+                stop_ticking();
+            }
+        },
+        {
+            let sync = sync.clone();
+            let start_ticking = start_ticking.clone();
+
+            move || {
+                start_segment();
+                sync();
+                if !is_ticking.get_untracked() {
+                    start_ticking();
+                }
+            }
+        },
+    );
+
+    // Starts or stops counting and the timer depending on the pause and visibility state
+    let apply = {
+        let sync = sync.clone();
+
+        move || {
+            let active = is_active.get_untracked();
+
+            let suspended = if active {
+                scheduler.defer()
+            } else {
+                scheduler.cancel();
+                false
+            };
+
+            let counting = active && !(suspended && hidden_behavior == TimerHiddenBehavior::Pause);
+            let ticking = active && !suspended;
+
+            if counting {
+                start_segment();
+            } else {
+                end_segment();
+            }
+
+            sync();
+
+            if ticking {
+                if !is_ticking.get_untracked() {
+                    start_ticking();
+                }
+            } else {
+                stop_ticking();
+            }
+        }
+    };
+
+    let _ = watch(
+        move || interval.get(),
+        move |interval, _, _| {
+            // keep the current count and continue from there with the new interval
+            base_count.set_value(elapsed_count().max(counter.get_untracked()));
+            current_interval.set_value(*interval);
+            counted.set_value(0.0);
+            if segment_start.get_value().is_some() {
+                segment_start.set_value(Some(now()));
+            }
         },
+        false,
     );
 
+    apply();
+
+    let reset = move || {
+        base_count.set_value(0);
+        counted.set_value(0.0);
+        if segment_start.get_value().is_some() {
+            segment_start.set_value(Some(now()));
+        }
+        set_counter.set(0);
+    };
+
+    let pause = {
+        let apply = apply.clone();
+
+        move || {
+            set_active.set(false);
+            apply();
+        }
+    };
+
+    let resume = move || {
+        set_active.set(true);
+        apply();
+    };
+
     UseIntervalReturn {
         counter: counter.into(),
         reset,
-        is_active,
+        is_active: is_active.into(),
         pause,
         resume,
     }
@@ -93,8 +278,11 @@ pub struct UseIntervalOptions {
     /// Start the timer immediately. Defaults to `true`.
     immediate: bool,
 
-    /// Callback on every interval.
+    /// Callback that is called with the new value whenever the counter changes.
     callback: Rc<dyn Fn(u64)>,
+
+    /// What happens while the page is hidden. Defaults to [`TimerHiddenBehavior::Continue`].
+    hidden_behavior: TimerHiddenBehavior,
 }
 
 impl Default for UseIntervalOptions {
@@ -102,6 +290,7 @@ impl Default for UseIntervalOptions {
         Self {
             immediate: true,
             callback: Rc::new(|_: u64| {}),
+            hidden_behavior: TimerHiddenBehavior::default(),
         }
     }
 }
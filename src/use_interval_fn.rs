@@ -1,8 +1,9 @@
 #![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
 
-use crate::utils::Pausable;
+use crate::core::now;
+use crate::utils::{use_visibility_scheduler, Pausable, TimerHiddenBehavior};
 use default_struct_builder::DefaultBuilder;
-use leptos::leptos_dom::helpers::IntervalHandle;
+use leptos::leptos_dom::helpers::{IntervalHandle, TimeoutHandle};
 use leptos::*;
 use std::cell::Cell;
 use std::rc::Rc;
@@ -18,12 +19,11 @@ use std::time::Duration;
 ///
 /// ```
 /// # use leptos::*;
-/// # use leptos_use::use_interval_fn;
-/// # use leptos_use::utils::Pausable;
+/// # use leptos_use::{use_interval_fn, UseIntervalFnReturn};
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
-/// let Pausable { pause, resume, is_active } = use_interval_fn(
+/// let UseIntervalFnReturn { pause, resume, is_active, .. } = use_interval_fn(
 ///     || {
 ///         // do something
 ///     },
@@ -33,13 +33,45 @@ use std::time::Duration;
 /// # }
 /// ```
 ///
+/// ### Hidden Page
+///
+/// Browsers throttle timers in background tabs. With the option `hidden_behavior` the timer can
+/// be stopped while the page is hidden instead. See [`TimerHiddenBehavior`] for details.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_interval_fn_with_options, UseIntervalFnOptions, UseIntervalFnReturn};
+/// # use leptos_use::utils::TimerHiddenBehavior;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseIntervalFnReturn {
+///     is_suspended,
+///     hidden_duration,
+///     ..
+/// } = use_interval_fn_with_options(
+///     || {
+///         // poll something
+///     },
+///     5000,
+///     UseIntervalFnOptions::default().hidden_behavior(TimerHiddenBehavior::CatchUp),
+/// );
+/// # view! { }
+/// # }
+/// ```
+///
+/// `is_suspended` is `true` while the timer is stopped because of this and `hidden_duration`
+/// tells you for how long the page has been hidden the last time.
+///
+/// The returned value can be converted into a [`Pausable`].
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this function will simply be ignored.
 pub fn use_interval_fn<CbFn, N>(
     callback: CbFn,
     interval: N,
-) -> Pausable<impl Fn() + Clone, impl Fn() + Clone>
+) -> UseIntervalFnReturn<impl Fn() + Clone, impl Fn() + Clone>
 where
     CbFn: Fn() + Clone + 'static,
     N: Into<MaybeSignal<u64>>,
@@ -52,7 +84,7 @@ pub fn use_interval_fn_with_options<CbFn, N>(
     callback: CbFn,
     interval: N,
     options: UseIntervalFnOptions,
-) -> Pausable<impl Fn() + Clone, impl Fn() + Clone>
+) -> UseIntervalFnReturn<impl Fn() + Clone, impl Fn() + Clone>
 where
     CbFn: Fn() + Clone + 'static,
     N: Into<MaybeSignal<u64>>,
@@ -60,33 +92,121 @@ where
     let UseIntervalFnOptions {
         immediate,
         immediate_callback,
+        hidden_behavior,
     } = options;
 
     let timer: Rc<Cell<Option<IntervalHandle>>> = Rc::new(Cell::new(None));
+    // Timeout for the rest of the interval that was running when the timer has been suspended
+    let rest_timer: Rc<Cell<Option<TimeoutHandle>>> = Rc::new(Cell::new(None));
 
     let (is_active, set_active) = create_signal(false);
 
+    // Time of the last call of the callback or of the start of the timer
+    let last_tick = store_value(0.0_f64);
+    // Time that was left of the current interval when the timer has been suspended
+    let remaining = store_value(0.0_f64);
+
     let clean = {
         let timer = Rc::clone(&timer);
+        let rest_timer = Rc::clone(&rest_timer);
 
         move || {
             if let Some(handle) = timer.take() {
                 handle.clear();
             }
+            if let Some(handle) = rest_timer.take() {
+                handle.clear();
+            }
+        }
+    };
+
+    let interval = interval.into();
+
+    let callback = move || {
+        last_tick.set_value(now());
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        callback();
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    };
+
+    let start_timer = {
+        let clean = clean.clone();
+        let callback = callback.clone();
+
+        move || {
+            clean();
+            last_tick.set_value(now());
+
+            timer.set(
+                set_interval_with_handle(
+                    callback.clone(),
+                    Duration::from_millis(interval.get_untracked()),
+                )
+                .ok(),
+            );
         }
     };
 
+    let scheduler = use_visibility_scheduler(
+        hidden_behavior,
+        move || is_active.get_untracked(),
+        {
+            let clean = clean.clone();
+
+            move || {
+                remaining.set_value(
+                    (interval.get_untracked() as f64 - (now() - last_tick.get_value())).max(0.0),
+                );
+                clean();
+            }
+        },
+        {
+            let start_timer = start_timer.clone();
+            let callback = callback.clone();
+
+            move || match hidden_behavior {
+                TimerHiddenBehavior::CatchUp => {
+                    if now() - last_tick.get_value() >= interval.get_untracked() as f64 {
+                        callback();
+                    }
+
+                    start_timer();
+                }
+                _ => {
+                    // finish the interval that was running when the page has been hidden
+                    let start_timer = start_timer.clone();
+                    let callback = callback.clone();
+
+                    rest_timer.set(
+                        set_timeout_with_handle(
+                            move || {
+                                callback();
+                                start_timer();
+                            },
+                            Duration::from_millis(remaining.get_value() as u64),
+                        )
+                        .ok(),
+                    );
+                }
+            }
+        },
+    );
+
     let pause = {
         let clean = clean.clone();
 
         move || {
             set_active.set(false);
+            scheduler.cancel();
             clean();
         }
     };
 
-    let interval = interval.into();
-
     let resume = move || {
         #[cfg(not(feature = "ssr"))]
         {
@@ -97,29 +217,17 @@ where
 
             set_active.set(true);
 
-            let callback = {
-                let callback = callback.clone();
-
-                move || {
-                    #[cfg(debug_assertions)]
-                    let prev = SpecialNonReactiveZone::enter();
-
-                    callback();
-
-                    #[cfg(debug_assertions)]
-                    SpecialNonReactiveZone::exit(prev);
-                }
-            };
-
             if immediate_callback {
-                callback.clone()();
+                callback();
             }
             clean();
 
-            timer.set(
-                set_interval_with_handle(callback.clone(), Duration::from_millis(interval_value))
-                    .ok(),
-            );
+            if scheduler.defer() {
+                // start with a full interval when the page becomes visible
+                remaining.set_value(interval_value as f64);
+            } else {
+                start_timer();
+            }
         }
     };
 
@@ -144,8 +252,10 @@ where
 
     on_cleanup(pause.clone());
 
-    Pausable {
+    UseIntervalFnReturn {
         is_active: is_active.into(),
+        is_suspended: scheduler.is_suspended.into(),
+        hidden_duration: scheduler.hidden_duration.into(),
         pause,
         resume,
     }
@@ -159,6 +269,9 @@ pub struct UseIntervalFnOptions {
 
     /// Execute the callback immediate after calling this function. Defaults to `false`
     pub immediate_callback: bool,
+
+    /// What happens while the page is hidden. Defaults to [`TimerHiddenBehavior::Continue`].
+    pub hidden_behavior: TimerHiddenBehavior,
 }
 
 impl Default for UseIntervalFnOptions {
@@ -166,6 +279,45 @@ impl Default for UseIntervalFnOptions {
         Self {
             immediate: true,
             immediate_callback: false,
+            hidden_behavior: TimerHiddenBehavior::default(),
+        }
+    }
+}
+
+/// Return type of [`use_interval_fn`].
+pub struct UseIntervalFnReturn<PauseFn, ResumeFn>
+where
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    /// A Signal that indicates whether the interval is active. `false` when paused.
+    pub is_active: Signal<bool>,
+
+    /// `true` while the timer is stopped because the page is hidden. See the option
+    /// `hidden_behavior`.
+    pub is_suspended: Signal<bool>,
+
+    /// How long in ms the page has been hidden the last time. Updated when the page becomes
+    /// visible again. Always `0.0` with [`TimerHiddenBehavior::Continue`].
+    pub hidden_duration: Signal<f64>,
+
+    /// Temporarily pause the interval
+    pub pause: PauseFn,
+
+    /// Resume the interval
+    pub resume: ResumeFn,
+}
+
+impl<PauseFn, ResumeFn> From<UseIntervalFnReturn<PauseFn, ResumeFn>> for Pausable<PauseFn, ResumeFn>
+where
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    fn from(value: UseIntervalFnReturn<PauseFn, ResumeFn>) -> Self {
+        Pausable {
+            is_active: value.is_active,
+            pause: value.pause,
+            resume: value.resume,
         }
     }
 }
@@ -0,0 +1,37 @@
+use crate::{use_input_capabilities, UseInputCapabilitiesReturn};
+use leptos::*;
+
+/// Reactive check if the device has a touch screen.
+///
+/// A device counts as touch device if any of its pointing devices is coarse or if it supports
+/// touch points. Laptops with touch screens are touch devices as well, so don't use this to
+/// hide mouse specific features. Use [`fn@crate::use_input_capabilities`] for that.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_is_touch_device;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let is_touch_device = use_is_touch_device();
+///
+/// view! {
+///     <p>{move || if is_touch_device.get() { "Tap to start" } else { "Click to start" }}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this returns a `Signal` that is always `false`.
+pub fn use_is_touch_device() -> Signal<bool> {
+    let UseInputCapabilitiesReturn {
+        any_coarse_pointer,
+        max_touch_points,
+        ..
+    } = use_input_capabilities();
+
+    Signal::derive(move || any_coarse_pointer.get() || max_touch_points.get() > 0)
+}
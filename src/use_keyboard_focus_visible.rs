@@ -0,0 +1,233 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{use_document, use_event_listener_with_options, use_window, UseEventListenerOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{focusin, focusout, keydown, mousedown, pointerdown, touchstart};
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive focus-visible heuristic.
+///
+/// Tracks whether the most recent interaction was done with the keyboard or with a pointer.
+/// Like the `:focus-visible` CSS pseudo-class, a focused element should only show a focus ring
+/// if it has been focused with the keyboard. Text inputs, text areas and editable elements
+/// always count as focus-visible because they accept keyboard input after being focused.
+///
+/// Unlike `:focus-visible` the result is the same in all browsers and is available in Rust.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Button;
+/// # use leptos_use::{use_keyboard_focus_visible, UseKeyboardFocusVisibleReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let button = create_node_ref::<Button>();
+///
+/// let UseKeyboardFocusVisibleReturn {
+///     is_keyboard,
+///     is_focus_visible,
+///     ..
+/// } = use_keyboard_focus_visible();
+///
+/// view! {
+///     <button
+///         node_ref=button
+///         class:focus-ring=move || button.get().is_some_and(|el| is_focus_visible(&el))
+///     >
+///         "Save"
+///     </button>
+/// }
+/// # }
+/// ```
+///
+/// `is_keyboard` is `true` after a key has been pressed and `false` after a pointer has been
+/// pressed. Key presses together with `Meta`, `Ctrl` or `Alt` are shortcuts and don't switch
+/// to keyboard mode.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_keyboard` is `initial_value` and no element is focus-visible.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_active_element`]
+pub fn use_keyboard_focus_visible(
+) -> UseKeyboardFocusVisibleReturn<impl Fn(&web_sys::Element) -> bool + Clone> {
+    use_keyboard_focus_visible_with_options(UseKeyboardFocusVisibleOptions::default())
+}
+
+/// Version of [`use_keyboard_focus_visible`] that takes a `UseKeyboardFocusVisibleOptions`. See [`use_keyboard_focus_visible`] for how to use.
+pub fn use_keyboard_focus_visible_with_options(
+    options: UseKeyboardFocusVisibleOptions,
+) -> UseKeyboardFocusVisibleReturn<impl Fn(&web_sys::Element) -> bool + Clone> {
+    let UseKeyboardFocusVisibleOptions { initial_value } = options;
+
+    let (is_keyboard, set_keyboard) = create_signal(initial_value);
+    let (focus_visible_element, set_focus_visible_element) =
+        create_signal(None::<web_sys::Element>);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let listener_options = UseEventListenerOptions::default()
+            .capture(true)
+            .passive(true);
+
+        let _ = use_event_listener_with_options(
+            use_window(),
+            keydown,
+            move |event| {
+                if event.meta_key() || event.ctrl_key() || event.alt_key() {
+                    return;
+                }
+
+                if is_keyboard.get_untracked() {
+                    return;
+                }
+
+                set_keyboard.set(true);
+
+                // an element that has been focused with a pointer shows its focus from now on
+                let document = use_document();
+                if let Some(active) = document.active_element() {
+                    let is_body = document.body().is_some_and(|body| {
+                        let body: &web_sys::Element = body.as_ref();
+                        body == &active
+                    });
+
+                    if !is_body {
+                        set_focus_visible_element.set(Some(active));
+                    }
+                }
+            },
+            listener_options,
+        );
+
+        let on_pointer = move || {
+            if is_keyboard.get_untracked() {
+                set_keyboard.set(false);
+            }
+        };
+
+        // mouse and touch events are needed for browsers without pointer events
+        let _ = use_event_listener_with_options(
+            use_window(),
+            pointerdown,
+            move |_| on_pointer(),
+            listener_options,
+        );
+        let _ = use_event_listener_with_options(
+            use_window(),
+            mousedown,
+            move |_| on_pointer(),
+            listener_options,
+        );
+        let _ = use_event_listener_with_options(
+            use_window(),
+            touchstart,
+            move |_| on_pointer(),
+            listener_options,
+        );
+
+        let _ = use_event_listener_with_options(
+            use_window(),
+            focusin,
+            move |event| {
+                let Some(element) = event
+                    .composed_path()
+                    .get(0)
+                    .dyn_into::<web_sys::Element>()
+                    .ok()
+                else {
+                    return;
+                };
+
+                if is_keyboard.get_untracked() || accepts_keyboard_input(&element) {
+                    set_focus_visible_element.set(Some(element));
+                } else {
+                    set_focus_visible_element.set(None);
+                }
+            },
+            listener_options,
+        );
+
+        let _ = use_event_listener_with_options(
+            use_window(),
+            focusout,
+            move |event| {
+                // focus moves to another element which is handled by `focusin`
+                if event.related_target().is_none() {
+                    set_focus_visible_element.set(None);
+                }
+            },
+            listener_options,
+        );
+    }
+
+    let is_focus_visible = move |element: &web_sys::Element| {
+        focus_visible_element.with(|focused| focused.as_ref() == Some(element))
+    };
+
+    UseKeyboardFocusVisibleReturn {
+        is_keyboard: is_keyboard.into(),
+        focus_visible_element: focus_visible_element.into(),
+        is_focus_visible,
+    }
+}
+
+/// Text fields show a focus ring no matter how they have been focused.
+fn accepts_keyboard_input(element: &web_sys::Element) -> bool {
+    if let Some(input) = element.dyn_ref::<web_sys::HtmlInputElement>() {
+        return !input.read_only()
+            && matches!(
+                input.type_().as_str(),
+                "text"
+                    | "search"
+                    | "url"
+                    | "tel"
+                    | "email"
+                    | "password"
+                    | "number"
+                    | "date"
+                    | "month"
+                    | "week"
+                    | "time"
+                    | "datetime"
+                    | "datetime-local"
+            );
+    }
+
+    if let Some(textarea) = element.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        return !textarea.read_only();
+    }
+
+    element
+        .dyn_ref::<web_sys::HtmlElement>()
+        .is_some_and(|el| el.is_content_editable())
+}
+
+/// Options for [`use_keyboard_focus_visible_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UseKeyboardFocusVisibleOptions {
+    /// If the keyboard is considered the last used input before any interaction.
+    /// Defaults to `false`.
+    initial_value: bool,
+}
+
+/// Return type of [`use_keyboard_focus_visible`].
+pub struct UseKeyboardFocusVisibleReturn<IsFocusVisibleFn>
+where
+    IsFocusVisibleFn: Fn(&web_sys::Element) -> bool + Clone,
+{
+    /// `true` if the most recent interaction was done with the keyboard
+    pub is_keyboard: Signal<bool>,
+
+    /// The focused element if its focus should be visible
+    pub focus_visible_element: Signal<Option<web_sys::Element>>,
+
+    /// Returns `true` if the given element is focused and its focus should be visible.
+    /// This is reactive.
+    pub is_focus_visible: IsFocusVisibleFn,
+}
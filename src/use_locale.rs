@@ -0,0 +1,234 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use crate::core::StorageType;
+use crate::storage::{use_storage_with_options, UseStorageOptions};
+use crate::use_preferred_languages;
+use codee::string::FromToStringCodec;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+
+/// Reactive locale of the app negotiated from the languages the user prefers.
+///
+/// The languages of [`fn@crate::use_preferred_languages`] are matched against the locales your
+/// app supports with [`negotiate_locale`]. If the user explicitly chooses a locale with
+/// `set_locale` the choice is persisted in storage and takes precedence.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_locale, UseLocaleReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseLocaleReturn { locale, set_locale, .. } = use_locale(["en-US", "de", "fr-FR"]);
+///
+/// // with navigator.languages = ["de-CH", "en"] this is "de"
+/// locale.get();
+///
+/// view! {
+///     <button on:click=move |_| set_locale("fr-FR")>"Français"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Negotiation
+///
+/// The preferred languages are checked in order. For every language the supported locales are
+/// searched for
+///
+/// 1. the same locale, e.g. `de-CH` for `de-CH`,
+/// 2. the same language without region, e.g. `de` for `de-CH`,
+/// 3. the same language with any region, e.g. `de-DE` for `de-CH`.
+///
+/// Language tags are compared case-insensitively and `_` is treated like `-`. If none of the
+/// preferred languages match, the `fallback` option is used which defaults to the first supported
+/// locale.
+///
+/// ## Server-Side Rendering
+///
+/// On the server there are no preferred languages and no storage so the locale is always the
+/// fallback.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_preferred_languages`]
+pub fn use_locale<S>(
+    supported: impl IntoIterator<Item = S>,
+) -> UseLocaleReturn<impl Fn(&str) + Clone, impl Fn() + Clone>
+where
+    S: Into<String>,
+{
+    use_locale_with_options(supported, UseLocaleOptions::default())
+}
+
+/// Version of [`use_locale`] that takes a `UseLocaleOptions`. See [`use_locale`] for how to use.
+pub fn use_locale_with_options<S>(
+    supported: impl IntoIterator<Item = S>,
+    options: UseLocaleOptions,
+) -> UseLocaleReturn<impl Fn(&str) + Clone, impl Fn() + Clone>
+where
+    S: Into<String>,
+{
+    let UseLocaleOptions {
+        fallback,
+        storage_key,
+        storage,
+        storage_enabled,
+    } = options;
+
+    let supported: Rc<[String]> = supported.into_iter().map(Into::into).collect();
+    let fallback = fallback
+        .or_else(|| supported.first().cloned())
+        .unwrap_or_default();
+
+    let preferred = use_preferred_languages();
+
+    // explicitly chosen locale, empty if there is none
+    let (chosen, set_chosen) = if storage_enabled {
+        let (chosen, set_chosen, _) = use_storage_with_options::<String, FromToStringCodec>(
+            storage,
+            storage_key,
+            UseStorageOptions::default(),
+        );
+        (chosen, set_chosen)
+    } else {
+        let (chosen, set_chosen) = create_signal(String::new());
+        (chosen.into(), set_chosen)
+    };
+
+    let locale = Signal::derive({
+        let supported = Rc::clone(&supported);
+
+        move || {
+            let chosen = chosen.get();
+
+            // the choice is ignored if the locale isn't supported (anymore)
+            negotiate_locale([chosen], supported.iter())
+                .or_else(|| {
+                    preferred.with(|preferred| negotiate_locale(preferred, supported.iter()))
+                })
+                .unwrap_or_else(|| fallback.clone())
+        }
+    });
+
+    let set_locale = move |locale: &str| set_chosen.set(locale.to_string());
+
+    let reset = move || set_chosen.set(String::new());
+
+    UseLocaleReturn {
+        locale,
+        preferred,
+        is_chosen: Signal::derive(move || chosen.with(|chosen| !chosen.is_empty())),
+        set_locale,
+        reset,
+    }
+}
+
+/// Returns the first of the `supported` locales that matches one of the `preferred` languages.
+/// See [`fn@use_locale`] for the rules.
+///
+/// ```
+/// # use leptos_use::negotiate_locale;
+/// #
+/// assert_eq!(negotiate_locale(["de-CH", "en"], ["en-US", "de"]), Some("de".to_string()));
+/// assert_eq!(negotiate_locale(["en-GB"], ["de", "en-US"]), Some("en-US".to_string()));
+/// assert_eq!(negotiate_locale(["fr"], ["de", "en"]), None);
+/// ```
+pub fn negotiate_locale(
+    preferred: impl IntoIterator<Item = impl AsRef<str>>,
+    supported: impl IntoIterator<Item = impl AsRef<str>> + Clone,
+) -> Option<String> {
+    for language in preferred {
+        let language = normalize_tag(language.as_ref());
+        if language.is_empty() {
+            continue;
+        }
+        let base = base_language(&language);
+
+        let mut base_match = None;
+        let mut region_match = None;
+
+        for locale in supported.clone() {
+            let locale = locale.as_ref();
+            let normalized = normalize_tag(locale);
+
+            if normalized == language {
+                return Some(locale.to_string());
+            }
+
+            if normalized == base {
+                base_match.get_or_insert(locale);
+            } else if base_language(&normalized) == base {
+                region_match.get_or_insert(locale);
+            }
+        }
+
+        if let Some(locale) = base_match.or(region_match) {
+            return Some(locale.to_string());
+        }
+    }
+
+    None
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().replace('_', "-").to_lowercase()
+}
+
+fn base_language(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+/// Options for [`use_locale_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseLocaleOptions {
+    /// Locale that is used if none of the preferred languages is supported.
+    /// Defaults to the first supported locale.
+    #[builder(into)]
+    fallback: Option<String>,
+
+    /// Key to persist the chosen locale in storage. Defaults to `"leptos-use-locale"`.
+    #[builder(into)]
+    storage_key: String,
+
+    /// Storage type, can be `Local` or `Session` or custom. Defaults to `Local`.
+    storage: StorageType,
+
+    /// If the chosen locale should be persisted. Defaults to `true`.
+    storage_enabled: bool,
+}
+
+impl Default for UseLocaleOptions {
+    fn default() -> Self {
+        Self {
+            fallback: None,
+            storage_key: "leptos-use-locale".to_string(),
+            storage: StorageType::default(),
+            storage_enabled: true,
+        }
+    }
+}
+
+/// Return type of [`use_locale`].
+pub struct UseLocaleReturn<SetFn, ResetFn>
+where
+    SetFn: Fn(&str) + Clone,
+    ResetFn: Fn() + Clone,
+{
+    /// The resolved locale. Always one of the supported locales or the fallback.
+    pub locale: Signal<String>,
+
+    /// The languages preferred by the user. See [`fn@crate::use_preferred_languages`].
+    pub preferred: Signal<Vec<String>>,
+
+    /// `true` if the locale has been chosen explicitly with `set_locale`
+    pub is_chosen: Signal<bool>,
+
+    /// Chooses a locale explicitly and persists the choice
+    pub set_locale: SetFn,
+
+    /// Forgets the chosen locale so the locale is negotiated from the preferred languages again
+    pub reset: ResetFn,
+}
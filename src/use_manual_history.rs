@@ -0,0 +1,338 @@
+use crate::core::{now, UseRwSignal};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+
+/// Manually track the change history of a signal with undo and redo.
+///
+/// The current value of `source` is recorded when `commit` is called. `undo` and `redo` move
+/// through the recorded values and set `source` accordingly. If you want every change to be
+/// recorded automatically, use [`fn@crate::use_history`] instead.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_manual_history, UseManualHistoryReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let counter = create_rw_signal(0);
+///
+/// let UseManualHistoryReturn {
+///     history,
+///     commit,
+///     undo,
+///     redo,
+///     can_undo,
+///     can_redo,
+///     ..
+/// } = use_manual_history(counter);
+///
+/// counter.update(|counter| *counter += 1);
+/// commit();
+///
+/// assert_eq!(
+///     history.get().iter().map(|record| record.snapshot).collect::<Vec<_>>(),
+///     vec![1, 0],
+/// );
+///
+/// undo();
+/// assert_eq!(counter.get(), 0);
+///
+/// view! {
+///     <button on:click=move |_| undo() disabled=move || !can_undo.get()>"Undo"</button>
+///     <button on:click=move |_| redo() disabled=move || !can_redo.get()>"Redo"</button>
+/// }
+/// # }
+/// ```
+///
+/// Changes that haven't been committed yet are discarded by `reset`.
+///
+/// ## Limiting the History
+///
+/// By default every commit is kept. Use the option `capacity` to keep only the given number of
+/// records that can be undone. The oldest ones are dropped first.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_manual_history_with_options, UseManualHistoryOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let text = create_rw_signal(String::new());
+///
+/// let history = use_manual_history_with_options(
+///     text,
+///     UseManualHistoryOptions::default().capacity(20),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Persisting the History
+///
+/// The history is kept in a [`HistoryState`]. You can pass your own signal for it with the
+/// option `persisted_state`, for example one returned by
+/// [`fn@crate::storage::use_local_storage`], so the history survives a reload. When the
+/// `serde` feature is enabled [`HistoryState`] can be (de)serialized with the serde codecs.
+///
+/// ```ignore
+/// # use leptos::*;
+/// # use leptos_use::storage::use_local_storage;
+/// # use leptos_use::{use_manual_history_with_options, HistoryState, UseManualHistoryOptions};
+/// # use codee::string::JsonSerdeCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (text, set_text, _) = use_local_storage::<String, JsonSerdeCodec>("text");
+/// let (text_history, set_text_history, _) =
+///     use_local_storage::<HistoryState<String>, JsonSerdeCodec>("text-history");
+///
+/// let history = use_manual_history_with_options(
+///     (text, set_text),
+///     UseManualHistoryOptions::default().persisted_state((text_history, set_text_history)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// If the persisted state already contains records, the value of `source` isn't changed. So it
+/// should be persisted as well.
+///
+/// ## Server-Side Rendering
+///
+/// This works the same on the server.
+pub fn use_manual_history<T>(
+    source: impl Into<UseRwSignal<T>>,
+) -> UseManualHistoryReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: Clone + 'static,
+{
+    use_manual_history_with_options(source, UseManualHistoryOptions::default())
+}
+
+/// Version of [`use_manual_history`] that takes a `UseManualHistoryOptions`. See [`use_manual_history`] for how to use.
+pub fn use_manual_history_with_options<T>(
+    source: impl Into<UseRwSignal<T>>,
+    options: UseManualHistoryOptions<T>,
+) -> UseManualHistoryReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    T: Clone + 'static,
+{
+    let UseManualHistoryOptions {
+        capacity,
+        persisted_state,
+    } = options;
+
+    let source = source.into();
+    let state: UseRwSignal<HistoryState<T>> =
+        persisted_state.unwrap_or_else(|| create_rw_signal(HistoryState::default()).into());
+
+    if state.with_untracked(|state| state.last.is_none()) {
+        state.update(|state| state.last = Some(HistoryRecord::new(source.get_untracked())));
+    }
+
+    let commit = move || {
+        let record = HistoryRecord::new(source.get_untracked());
+
+        state.update(|state| {
+            if let Some(last) = state.last.replace(record) {
+                state.undo.push(last);
+            }
+            state.redo.clear();
+
+            if let Some(capacity) = capacity {
+                let excess = state.undo.len().saturating_sub(capacity);
+                state.undo.drain(..excess);
+            }
+        });
+    };
+
+    // Sets `source` to the snapshot of the last record
+    let restore = move || {
+        if let Some(snapshot) =
+            state.with_untracked(|state| state.last.as_ref().map(|last| last.snapshot.clone()))
+        {
+            source.set(snapshot);
+        }
+    };
+
+    let undo = move || {
+        if state.with_untracked(|state| state.undo.is_empty()) {
+            return;
+        }
+
+        state.update(|state| {
+            let previous = state.undo.pop();
+            if let Some(last) = std::mem::replace(&mut state.last, previous) {
+                state.redo.push(last);
+            }
+        });
+
+        restore();
+    };
+
+    let redo = move || {
+        if state.with_untracked(|state| state.redo.is_empty()) {
+            return;
+        }
+
+        state.update(|state| {
+            let next = state.redo.pop();
+            if let Some(last) = std::mem::replace(&mut state.last, next) {
+                state.undo.push(last);
+            }
+        });
+
+        restore();
+    };
+
+    let clear = move || {
+        state.update(|state| {
+            state.undo.clear();
+            state.redo.clear();
+        });
+    };
+
+    UseManualHistoryReturn {
+        history: Signal::derive(move || {
+            state.with(|state| {
+                state
+                    .last
+                    .iter()
+                    .chain(state.undo.iter().rev())
+                    .cloned()
+                    .collect()
+            })
+        }),
+        can_undo: Signal::derive(move || state.with(|state| !state.undo.is_empty())),
+        can_redo: Signal::derive(move || state.with(|state| !state.redo.is_empty())),
+        commit,
+        undo,
+        redo,
+        clear,
+        reset: restore,
+    }
+}
+
+/// Options for [`use_manual_history_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseManualHistoryOptions<T>
+where
+    T: 'static,
+{
+    /// Maximum number of records that can be undone. Defaults to `None` which keeps all of them.
+    #[builder(into)]
+    capacity: Option<usize>,
+
+    /// Signal that holds the history instead of an internal one. See
+    /// [`use_manual_history`] for how to persist the history with it. Defaults to `None`.
+    #[builder(skip)]
+    persisted_state: Option<UseRwSignal<HistoryState<T>>>,
+}
+
+impl<T> Default for UseManualHistoryOptions<T> {
+    fn default() -> Self {
+        Self {
+            capacity: None,
+            persisted_state: None,
+        }
+    }
+}
+
+impl<T> UseManualHistoryOptions<T> {
+    /// Signal that holds the history instead of an internal one. See
+    /// [`use_manual_history`] for how to persist the history with it.
+    pub fn persisted_state(self, state: impl Into<UseRwSignal<HistoryState<T>>>) -> Self {
+        Self {
+            persisted_state: Some(state.into()),
+            ..self
+        }
+    }
+}
+
+/// A recorded value of [`use_manual_history`] and [`fn@crate::use_history`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryRecord<T> {
+    /// The value of the source when it has been recorded
+    pub snapshot: T,
+    /// Time in ms since the Unix epoch (like `Date.now()`) when the value has been recorded
+    pub timestamp: f64,
+}
+
+impl<T> HistoryRecord<T> {
+    fn new(snapshot: T) -> Self {
+        Self {
+            snapshot,
+            timestamp: now(),
+        }
+    }
+}
+
+/// All records of [`use_manual_history`] and [`fn@crate::use_history`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryState<T> {
+    /// The record of the current value. Only `None` before the history has been initialized.
+    pub last: Option<HistoryRecord<T>>,
+    /// The records that can be undone. The most recent one is the last.
+    pub undo: Vec<HistoryRecord<T>>,
+    /// The records that can be redone. The one that has been undone last is the last.
+    pub redo: Vec<HistoryRecord<T>>,
+}
+
+impl<T> Default for HistoryState<T> {
+    fn default() -> Self {
+        Self {
+            last: None,
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+}
+
+/// Return type of [`use_manual_history`].
+pub struct UseManualHistoryReturn<T, CommitFn, UndoFn, RedoFn, ClearFn, ResetFn>
+where
+    T: 'static,
+    CommitFn: Fn() + Clone + 'static,
+    UndoFn: Fn() + Clone + 'static,
+    RedoFn: Fn() + Clone + 'static,
+    ClearFn: Fn() + Clone + 'static,
+    ResetFn: Fn() + Clone + 'static,
+{
+    /// All records that can be undone, the most recent first. The first one is the record of
+    /// the current value.
+    pub history: Signal<Vec<HistoryRecord<T>>>,
+    /// `true` if there is a record to undo to
+    pub can_undo: Signal<bool>,
+    /// `true` if there is a record to redo to
+    pub can_redo: Signal<bool>,
+    /// Records the current value of the source. Clears the records that could be redone.
+    pub commit: CommitFn,
+    /// Sets the source to the previous record
+    pub undo: UndoFn,
+    /// Sets the source to the next record that has been undone
+    pub redo: RedoFn,
+    /// Removes all records except the one of the current value
+    pub clear: ClearFn,
+    /// Sets the source to the last record which discards changes that haven't been committed
+    pub reset: ResetFn,
+}
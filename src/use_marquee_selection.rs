@@ -0,0 +1,356 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ElementMaybeSignal, Position};
+use crate::use_event_listener;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::collections::HashSet;
+use std::hash::Hash;
+use wasm_bindgen::JsCast;
+use web_sys::PointerEvent;
+
+/// Rubber band selection of items inside of a container.
+///
+/// Dragging over the empty space of the `container` draws a selection rectangle. All registered
+/// items that intersect with the rectangle are selected. Items are registered with a key that
+/// identifies them in the selection.
+///
+/// Holding `Shift`, `Ctrl` or `Meta` while starting to drag adds the items to the current
+/// selection, holding `Alt` removes them from it. Clicking the empty space without any modifier
+/// clears the selection.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_marquee_selection, UseMarqueeSelectionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Div>();
+///
+/// let UseMarqueeSelectionReturn {
+///     selected,
+///     style,
+///     register,
+///     ..
+/// } = use_marquee_selection::<u32, _, _>(container);
+///
+/// view! {
+///     <div node_ref=container style="position: relative; user-select: none">
+///         {(0..20)
+///             .map(|id| {
+///                 let item = create_node_ref::<Div>();
+///                 let register = register.clone();
+///                 item.on_load(move |el| register(id, (*el).clone().into()));
+///
+///                 view! {
+///                     <div
+///                         node_ref=item
+///                         class:selected=move || selected.with(|selected| selected.contains(&id))
+///                     >
+///                         {id}
+///                     </div>
+///                 }
+///             })
+///             .collect_view()}
+///         <div style=style class="marquee"></div>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// `style` positions the selection rectangle absolutely inside of the `container` and hides it
+/// while nothing is being selected.
+///
+/// ## Server-Side Rendering
+///
+/// On the server nothing is ever selected.
+pub fn use_marquee_selection<K, El, T>(
+    container: El,
+) -> UseMarqueeSelectionReturn<
+    K,
+    impl Fn(K, web_sys::Element) + Clone,
+    impl Fn(&K) + Clone,
+    impl Fn() + Clone,
+>
+where
+    K: Eq + Hash + Clone + 'static,
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_marquee_selection_with_options(container, UseMarqueeSelectionOptions::default())
+}
+
+/// Version of [`use_marquee_selection`] that takes a `UseMarqueeSelectionOptions`. See [`use_marquee_selection`] for how to use.
+pub fn use_marquee_selection_with_options<K, El, T>(
+    container: El,
+    options: UseMarqueeSelectionOptions,
+) -> UseMarqueeSelectionReturn<
+    K,
+    impl Fn(K, web_sys::Element) + Clone,
+    impl Fn(&K) + Clone,
+    impl Fn() + Clone,
+>
+where
+    K: Eq + Hash + Clone + 'static,
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseMarqueeSelectionOptions { threshold } = options;
+
+    let container = container.into();
+    let element: Signal<Option<web_sys::Element>> =
+        Signal::derive(move || container.get().map(|container| container.into()));
+    let event_target: Signal<Option<web_sys::EventTarget>> =
+        Signal::derive(move || element.get().map(|element| element.into()));
+
+    let (selected, set_selected) = create_signal(HashSet::<K>::new());
+    let (rect, set_rect) = create_signal(None::<MarqueeRect>);
+
+    let items = store_value(Vec::<(K, web_sys::Element)>::new());
+    let drag = store_value(None::<MarqueeDrag<K>>);
+
+    // converts client coordinates to the coordinates of the (scrolled) content of the container
+    let to_local = move |client: Position| {
+        element
+            .get_untracked()
+            .map(|element| {
+                let rect = element.get_bounding_client_rect();
+                Position {
+                    x: client.x - rect.left() + element.scroll_left() as f64,
+                    y: client.y - rect.top() + element.scroll_top() as f64,
+                }
+            })
+            .unwrap_or(client)
+    };
+
+    let _ = use_event_listener(event_target, pointerdown, move |event: PointerEvent| {
+        if event.button() != 0 {
+            return;
+        }
+
+        // items can still be clicked or dragged themselves
+        if let Some(target) = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+        {
+            let on_item = items
+                .with_value(|items| items.iter().any(|(_, item)| item.contains(Some(&target))));
+
+            if on_item {
+                return;
+            }
+        }
+
+        let mode = if event.alt_key() {
+            MarqueeMode::Remove
+        } else if event.shift_key() || event.ctrl_key() || event.meta_key() {
+            MarqueeMode::Add
+        } else {
+            MarqueeMode::Replace
+        };
+
+        if let Some(element) = element.get_untracked() {
+            let _ = element.set_pointer_capture(event.pointer_id());
+        }
+
+        drag.set_value(Some(MarqueeDrag {
+            start: Position {
+                x: event.client_x() as f64,
+                y: event.client_y() as f64,
+            },
+            base: selected.get_untracked(),
+            mode,
+            is_active: false,
+        }));
+    });
+
+    let _ = use_event_listener(event_target, pointermove, move |event: PointerEvent| {
+        let current = Position {
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+        };
+
+        let Some((start, base, mode)) = drag
+            .try_update_value(|drag| {
+                let drag = drag.as_mut()?;
+
+                if !drag.is_active {
+                    if (current.x - drag.start.x).hypot(current.y - drag.start.y) < threshold {
+                        return None;
+                    }
+                    drag.is_active = true;
+                }
+
+                Some((drag.start, drag.base.clone(), drag.mode))
+            })
+            .flatten()
+        else {
+            return;
+        };
+
+        let client_rect = MarqueeRect::from_corners(start, current);
+
+        let hits = items.with_value(|items| {
+            items
+                .iter()
+                .filter(|(_, item)| client_rect.intersects(&item.get_bounding_client_rect()))
+                .map(|(key, _)| key.clone())
+                .collect::<HashSet<_>>()
+        });
+
+        set_selected.set(match mode {
+            MarqueeMode::Replace => hits,
+            MarqueeMode::Add => base.union(&hits).cloned().collect(),
+            MarqueeMode::Remove => base.difference(&hits).cloned().collect(),
+        });
+
+        set_rect.set(Some(MarqueeRect::from_corners(
+            to_local(start),
+            to_local(current),
+        )));
+    });
+
+    let end_drag = move |_: PointerEvent| {
+        let Some(ended) = drag.try_update_value(Option::take).flatten() else {
+            return;
+        };
+
+        if ended.is_active {
+            set_rect.set(None);
+        } else if ended.mode == MarqueeMode::Replace {
+            // a click on the empty space
+            set_selected.update(|selected| selected.clear());
+        }
+    };
+
+    let _ = use_event_listener(event_target, pointerup, end_drag);
+    let _ = use_event_listener(event_target, pointercancel, end_drag);
+
+    let register = move |key: K, item: web_sys::Element| {
+        items.update_value(|items| {
+            items.retain(|(existing, _)| *existing != key);
+            items.push((key, item));
+        });
+    };
+
+    let unregister = move |key: &K| {
+        items.update_value(|items| items.retain(|(existing, _)| existing != key));
+        set_selected.update(|selected| {
+            selected.remove(key);
+        });
+    };
+
+    let clear = move || set_selected.update(|selected| selected.clear());
+
+    UseMarqueeSelectionReturn {
+        selected: selected.into(),
+        set_selected,
+        rect: rect.into(),
+        is_selecting: Signal::derive(move || rect.with(Option::is_some)),
+        style: Signal::derive(move || match rect.get() {
+            Some(rect) => format!(
+                "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px;",
+                rect.x, rect.y, rect.width, rect.height
+            ),
+            None => "display: none;".to_string(),
+        }),
+        register,
+        unregister,
+        clear,
+    }
+}
+
+struct MarqueeDrag<K> {
+    /// Client coordinates where the drag started
+    start: Position,
+    /// Selection when the drag started
+    base: HashSet<K>,
+    mode: MarqueeMode,
+    /// `true` once the pointer has moved further than the threshold
+    is_active: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MarqueeMode {
+    Replace,
+    Add,
+    Remove,
+}
+
+/// A rectangle of [`use_marquee_selection`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MarqueeRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl MarqueeRect {
+    fn from_corners(a: Position, b: Position) -> Self {
+        Self {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+            width: (a.x - b.x).abs(),
+            height: (a.y - b.y).abs(),
+        }
+    }
+
+    fn intersects(&self, rect: &web_sys::DomRect) -> bool {
+        self.x < rect.right()
+            && self.x + self.width > rect.left()
+            && self.y < rect.bottom()
+            && self.y + self.height > rect.top()
+    }
+}
+
+/// Options for [`use_marquee_selection_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseMarqueeSelectionOptions {
+    /// Distance in pixels the pointer has to move before the selection starts. Smaller movements
+    /// count as a click. Defaults to `4.0`.
+    threshold: f64,
+}
+
+impl Default for UseMarqueeSelectionOptions {
+    fn default() -> Self {
+        Self { threshold: 4.0 }
+    }
+}
+
+/// Return type of [`use_marquee_selection`].
+pub struct UseMarqueeSelectionReturn<K, RegisterFn, UnregisterFn, ClearFn>
+where
+    K: 'static,
+    RegisterFn: Fn(K, web_sys::Element) + Clone,
+    UnregisterFn: Fn(&K) + Clone,
+    ClearFn: Fn() + Clone,
+{
+    /// Keys of the selected items
+    pub selected: Signal<HashSet<K>>,
+
+    /// Sets the selection
+    pub set_selected: WriteSignal<HashSet<K>>,
+
+    /// The selection rectangle in coordinates of the content of the container while selecting
+    pub rect: Signal<Option<MarqueeRect>>,
+
+    /// `true` while the selection rectangle is dragged
+    pub is_selecting: Signal<bool>,
+
+    /// Style attribute for the selection rectangle
+    pub style: Signal<String>,
+
+    /// Registers an item that can be selected under the given key
+    pub register: RegisterFn,
+
+    /// Removes an item and deselects it
+    pub unregister: UnregisterFn,
+
+    /// Clears the selection
+    pub clear: ClearFn,
+}
@@ -0,0 +1,334 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ElementMaybeSignal, PointerType};
+use crate::{js, use_event_listener_with_options, UseEventListenerOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointerleave, pointermove, pointerup};
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Reactive pressure, tilt and twist of pointers (especially pens) over an element.
+///
+/// Drawing apps need more than the position of the pointer. This hook reads the pressure, the
+/// tilt, the twist and the buttons of the pen from the
+/// [pointer events](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent) on the
+/// target.
+///
+/// Browsers fire at most one `pointermove` per frame but pens report positions much more often.
+/// The positions in between are available as
+/// [coalesced events](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/getCoalescedEvents).
+/// `on_sample` is called with every one of them so strokes are smooth.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::{use_mouse_pressure, UseMousePressureReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Canvas>();
+///
+/// let UseMousePressureReturn {
+///     pressure,
+///     tilt_x,
+///     tilt_y,
+///     is_eraser,
+///     ..
+/// } = use_mouse_pressure(el);
+///
+/// view! {
+///     <canvas node_ref=el style="touch-action: none"></canvas>
+///     <p>"Pressure: " {pressure} " Tilt: " {tilt_x} " / " {tilt_y}</p>
+///     <Show when=is_eraser>"Erasing"</Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Strokes
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Canvas;
+/// # use leptos_use::{use_mouse_pressure_with_options, PenSample, UseMousePressureOptions};
+/// # use leptos_use::core::PointerType;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Canvas>();
+///
+/// use_mouse_pressure_with_options(
+///     el,
+///     UseMousePressureOptions::default()
+///         .pointer_types(vec![PointerType::Pen])
+///         .on_sample(move |sample: &PenSample| {
+///             if sample.is_pressed() {
+///                 // draw a line to (sample.x, sample.y) with a width depending on sample.pressure
+///             }
+///         }),
+/// );
+/// #
+/// # view! { <canvas node_ref=el></canvas> }
+/// # }
+/// ```
+///
+/// Set `touch-action: none` on the target. Otherwise the browser starts scrolling when a pen or
+/// a finger moves over the target and cancels the pointer.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the returned signals always contain their default values.
+pub fn use_mouse_pressure<El, T>(target: El) -> UseMousePressureReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>> + Clone,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    use_mouse_pressure_with_options(target, UseMousePressureOptions::default())
+}
+
+/// Version of [`use_mouse_pressure`] that takes a `UseMousePressureOptions`. See [`use_mouse_pressure`] for how to use.
+pub fn use_mouse_pressure_with_options<El, T>(
+    target: El,
+    options: UseMousePressureOptions,
+) -> UseMousePressureReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>> + Clone,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UseMousePressureOptions {
+        pointer_types,
+        on_sample,
+    } = options;
+
+    let (sample, set_sample) = create_signal(None::<PenSample>);
+
+    let handle = move |event: web_sys::PointerEvent, coalesced: bool| {
+        let Ok(pointer_type) = event.pointer_type().parse::<PointerType>() else {
+            return;
+        };
+        if !pointer_types.contains(&pointer_type) {
+            return;
+        }
+
+        let mut events = if coalesced {
+            coalesced_events(&event)
+        } else {
+            vec![]
+        };
+        if events.is_empty() {
+            events.push(event);
+        }
+
+        let samples = events
+            .iter()
+            .map(|event| PenSample::new(event, pointer_type))
+            .collect::<Vec<_>>();
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        for sample in &samples {
+            on_sample(sample);
+        }
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+
+        set_sample.set(samples.last().cloned());
+    };
+
+    let listener_options = UseEventListenerOptions::default().passive(true);
+
+    let _ = use_event_listener_with_options(
+        target.clone(),
+        pointerdown,
+        {
+            let handle = handle.clone();
+            move |event| handle(event, false)
+        },
+        listener_options,
+    );
+
+    let _ = use_event_listener_with_options(
+        target.clone(),
+        pointermove,
+        {
+            let handle = handle.clone();
+            move |event| handle(event, true)
+        },
+        listener_options,
+    );
+
+    let _ = use_event_listener_with_options(
+        target.clone(),
+        pointerup,
+        move |event| handle(event, false),
+        listener_options,
+    );
+
+    let reset = move |_| set_sample.set(None);
+    let _ = use_event_listener_with_options(target.clone(), pointerleave, reset, listener_options);
+    let _ = use_event_listener_with_options(target, pointercancel, reset, listener_options);
+
+    let value = move |f: fn(&PenSample) -> f64| {
+        Signal::derive(move || sample.with(|sample| sample.as_ref().map(f).unwrap_or_default()))
+    };
+    let flag = move |f: fn(&PenSample) -> bool| {
+        Signal::derive(move || sample.with(|sample| sample.as_ref().is_some_and(f)))
+    };
+
+    UseMousePressureReturn {
+        sample: sample.into(),
+        pressure: value(|sample| sample.pressure),
+        tangential_pressure: value(|sample| sample.tangential_pressure),
+        tilt_x: value(|sample| sample.tilt_x),
+        tilt_y: value(|sample| sample.tilt_y),
+        twist: value(|sample| sample.twist),
+        pointer_type: Signal::derive(move || {
+            sample.with(|sample| sample.as_ref().map(|sample| sample.pointer_type))
+        }),
+        is_pressed: flag(PenSample::is_pressed),
+        is_barrel_button_pressed: flag(PenSample::is_barrel_button_pressed),
+        is_eraser: flag(PenSample::is_eraser),
+    }
+}
+
+/// The events that have been coalesced into `event`. Empty if not supported by the browser.
+fn coalesced_events(event: &web_sys::PointerEvent) -> Vec<web_sys::PointerEvent> {
+    js!(event["getCoalescedEvents"])
+        .ok()
+        .filter(|get_coalesced_events| get_coalesced_events.is_function())
+        .and_then(|get_coalesced_events| {
+            get_coalesced_events
+                .unchecked_into::<js_sys::Function>()
+                .call0(event)
+                .ok()
+        })
+        .filter(|events| events.is_array())
+        .map(|events| {
+            events
+                .unchecked_into::<js_sys::Array>()
+                .iter()
+                .map(JsCast::unchecked_into)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Options for [`use_mouse_pressure_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseMousePressureOptions {
+    /// Only pointers of these types are tracked. Defaults to all types.
+    pointer_types: Vec<PointerType>,
+
+    /// Called for every sample including the coalesced ones, in the order they happened.
+    on_sample: Rc<dyn Fn(&PenSample)>,
+}
+
+impl Default for UseMousePressureOptions {
+    fn default() -> Self {
+        Self {
+            pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
+            on_sample: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_mouse_pressure`].
+pub struct UseMousePressureReturn {
+    /// The latest sample. `None` when the pointer isn't over the target.
+    pub sample: Signal<Option<PenSample>>,
+
+    /// Pressure from `0.0` to `1.0`
+    pub pressure: Signal<f64>,
+
+    /// Pressure of the barrel control (like the finger wheel of an airbrush) from `-1.0` to `1.0`
+    pub tangential_pressure: Signal<f64>,
+
+    /// Tilt to the right in degrees from `-90.0` to `90.0`
+    pub tilt_x: Signal<f64>,
+
+    /// Tilt towards the user in degrees from `-90.0` to `90.0`
+    pub tilt_y: Signal<f64>,
+
+    /// Clockwise rotation around the pen's axis in degrees from `0.0` to `359.0`
+    pub twist: Signal<f64>,
+
+    /// Type of the pointer
+    pub pointer_type: Signal<Option<PointerType>>,
+
+    /// `true` while the pen touches the surface or a mouse button is pressed
+    pub is_pressed: Signal<bool>,
+
+    /// `true` while the barrel button of the pen is pressed
+    pub is_barrel_button_pressed: Signal<bool>,
+
+    /// `true` while the eraser end of the pen is used
+    pub is_eraser: Signal<bool>,
+}
+
+/// A sample of a pointer of [`use_mouse_pressure`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PenSample {
+    /// Horizontal position relative to the target
+    pub x: f64,
+    /// Vertical position relative to the target
+    pub y: f64,
+    /// Pressure from `0.0` to `1.0`
+    pub pressure: f64,
+    /// Pressure of the barrel control from `-1.0` to `1.0`
+    pub tangential_pressure: f64,
+    /// Tilt to the right in degrees from `-90.0` to `90.0`
+    pub tilt_x: f64,
+    /// Tilt towards the user in degrees from `-90.0` to `90.0`
+    pub tilt_y: f64,
+    /// Clockwise rotation around the pen's axis in degrees from `0.0` to `359.0`
+    pub twist: f64,
+    /// Width of the contact geometry in CSS pixels
+    pub width: f64,
+    /// Height of the contact geometry in CSS pixels
+    pub height: f64,
+    /// Type of the pointer
+    pub pointer_type: PointerType,
+    /// Pressed buttons as a bit mask like
+    /// [`MouseEvent.buttons`](https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/buttons)
+    pub buttons: u16,
+    /// Time of the sample in ms
+    pub timestamp: f64,
+}
+
+impl PenSample {
+    fn new(event: &web_sys::PointerEvent, pointer_type: PointerType) -> Self {
+        Self {
+            x: f64::from(event.offset_x()),
+            y: f64::from(event.offset_y()),
+            pressure: f64::from(event.pressure()),
+            tangential_pressure: f64::from(event.tangential_pressure()),
+            tilt_x: f64::from(event.tilt_x()),
+            tilt_y: f64::from(event.tilt_y()),
+            twist: f64::from(event.twist()),
+            width: f64::from(event.width()),
+            height: f64::from(event.height()),
+            pointer_type,
+            buttons: event.buttons(),
+            timestamp: event.time_stamp(),
+        }
+    }
+
+    /// `true` while the pen touches the surface or a mouse button is pressed
+    pub fn is_pressed(&self) -> bool {
+        self.buttons != 0
+    }
+
+    /// `true` while the barrel button of the pen is pressed
+    pub fn is_barrel_button_pressed(&self) -> bool {
+        self.pointer_type == PointerType::Pen && self.buttons & 2 != 0
+    }
+
+    /// `true` while the eraser end of the pen is used
+    pub fn is_eraser(&self) -> bool {
+        self.pointer_type == PointerType::Pen && self.buttons & 32 != 0
+    }
+}
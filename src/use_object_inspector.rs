@@ -0,0 +1,281 @@
+#![cfg_attr(
+    not(debug_assertions),
+    allow(unused_variables, unused_imports, dead_code)
+)]
+
+use crate::core::now;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Records the values of a signal over time for an in-app inspector panel.
+///
+/// Every time `source` changes a snapshot of the new value is added to `snapshots` together with
+/// the time of the change. Only the latest `capacity` snapshots are kept. This is meant for
+/// debugging and is compiled out in release builds where `snapshots` is always empty and
+/// `source` isn't tracked at all.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_object_inspector, UseObjectInspectorReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (count, set_count) = create_signal(0);
+///
+/// let UseObjectInspectorReturn { snapshots, clear } = use_object_inspector(count);
+///
+/// view! {
+///     <button on:click=move |_| set_count.update(|count| *count += 1)>"Increment"</button>
+///     <button on:click=move |_| clear()>"Clear"</button>
+///     <ul>
+///         {move || {
+///             snapshots
+///                 .get()
+///                 .into_iter()
+///                 .map(|snapshot| view! { <li>{snapshot.timestamp} ": " {snapshot.debug}</li> })
+///                 .collect_view()
+///         }}
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ### Change Diffs
+///
+/// With the `serde` feature enabled, types that implement `Serialize` can record what has
+/// changed compared to the previous snapshot. Every change contains the
+/// [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) of the changed field and the
+/// old and new values as JSON.
+///
+/// ```ignore
+/// # use leptos::*;
+/// # use leptos_use::{use_object_inspector_with_options, UseObjectInspectorOptions};
+/// # use serde::Serialize;
+/// #
+/// #[derive(Serialize, Clone, Debug)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (user, set_user) = create_signal(User { name: "Alice".to_string(), age: 30 });
+///
+/// let inspector = use_object_inspector_with_options(
+///     user,
+///     UseObjectInspectorOptions::default().capacity(20).serde_diff(),
+/// );
+///
+/// set_user.update(|user| user.age += 1);
+/// // The last snapshot has the change `/age` from `30` to `31`.
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// This works the same on the server as in the browser.
+pub fn use_object_inspector<T>(
+    source: impl Into<Signal<T>>,
+) -> UseObjectInspectorReturn<T, impl Fn() + Clone>
+where
+    T: Clone + Debug + 'static,
+{
+    use_object_inspector_with_options(source, UseObjectInspectorOptions::default())
+}
+
+/// Version of [`use_object_inspector`] that takes a `UseObjectInspectorOptions`. See [`use_object_inspector`] for how to use.
+pub fn use_object_inspector_with_options<T>(
+    source: impl Into<Signal<T>>,
+    options: UseObjectInspectorOptions<T>,
+) -> UseObjectInspectorReturn<T, impl Fn() + Clone>
+where
+    T: Clone + Debug + 'static,
+{
+    let UseObjectInspectorOptions { capacity, diff } = options;
+
+    let source = source.into();
+
+    let (snapshots, set_snapshots) = create_signal(Vec::<InspectorSnapshot<T>>::new());
+
+    #[cfg(debug_assertions)]
+    {
+        let _ = watch(
+            move || source.get(),
+            move |value, prev_value, _| {
+                let changes = match (&diff, prev_value) {
+                    (Some(diff), Some(prev_value)) => diff(prev_value, value),
+                    _ => vec![],
+                };
+
+                let snapshot = InspectorSnapshot {
+                    value: value.clone(),
+                    debug: format!("{value:#?}"),
+                    changes,
+                    timestamp: now(),
+                };
+
+                set_snapshots.update(|snapshots| {
+                    snapshots.push(snapshot);
+
+                    if snapshots.len() > capacity {
+                        snapshots.drain(..snapshots.len() - capacity);
+                    }
+                });
+            },
+            true,
+        );
+    }
+
+    UseObjectInspectorReturn {
+        snapshots: snapshots.into(),
+        clear: move || set_snapshots.set(vec![]),
+    }
+}
+
+/// A recorded value of [`use_object_inspector`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InspectorSnapshot<T> {
+    /// The value of the source when it has been recorded
+    pub value: T,
+    /// The pretty printed `Debug` representation of `value`
+    pub debug: String,
+    /// What has changed compared to the previous snapshot. Only recorded with a `diff`.
+    pub changes: Vec<InspectorChange>,
+    /// Time in ms since the Unix epoch (like `Date.now()`) when the value has been recorded
+    pub timestamp: f64,
+}
+
+/// A changed field between two snapshots of [`use_object_inspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InspectorChange {
+    /// JSON Pointer of the field like `/user/name`. Empty if the whole value has changed.
+    pub path: String,
+    /// The previous value as JSON. `None` if the field has been added.
+    pub old: Option<String>,
+    /// The new value as JSON. `None` if the field has been removed.
+    pub new: Option<String>,
+}
+
+/// Options for [`use_object_inspector_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseObjectInspectorOptions<T> {
+    /// Maximum number of snapshots that are kept. The oldest ones are removed first.
+    /// Defaults to `100`.
+    capacity: usize,
+
+    /// Computes the changes from the previous to the current value. Use
+    /// [`UseObjectInspectorOptions::serde_diff`] for types that implement `Serialize`.
+    /// Defaults to `None` which means no changes are recorded.
+    diff: Option<Rc<dyn Fn(&T, &T) -> Vec<InspectorChange>>>,
+}
+
+impl<T> Default for UseObjectInspectorOptions<T> {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            diff: None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> UseObjectInspectorOptions<T>
+where
+    T: serde::Serialize,
+{
+    /// Records the changes by comparing the JSON representations of the values.
+    pub fn serde_diff(self) -> Self {
+        self.diff(Some(Rc::new(|old: &T, new: &T| {
+            match (serde_json::to_value(old), serde_json::to_value(new)) {
+                (Ok(old), Ok(new)) => {
+                    let mut changes = vec![];
+                    diff_json("", &old, &new, &mut changes);
+                    changes
+                }
+                _ => vec![],
+            }
+        })))
+    }
+}
+
+/// Adds the differences between `old` and `new` at `path` to `changes`.
+#[cfg(feature = "serde")]
+fn diff_json(
+    path: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    changes: &mut Vec<InspectorChange>,
+) {
+    use serde_json::Value;
+
+    let child_path = |key: &str| format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"));
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                match new.get(key) {
+                    Some(new_value) => diff_json(&child_path(key), old_value, new_value, changes),
+                    None => changes.push(InspectorChange {
+                        path: child_path(key),
+                        old: Some(old_value.to_string()),
+                        new: None,
+                    }),
+                }
+            }
+
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    changes.push(InspectorChange {
+                        path: child_path(key),
+                        old: None,
+                        new: Some(new_value.to_string()),
+                    });
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let path = child_path(&index.to_string());
+
+                match (old.get(index), new.get(index)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_json(&path, old_value, new_value, changes)
+                    }
+                    (old_value, new_value) => changes.push(InspectorChange {
+                        path,
+                        old: old_value.map(Value::to_string),
+                        new: new_value.map(Value::to_string),
+                    }),
+                }
+            }
+        }
+        (old, new) => {
+            if old != new {
+                changes.push(InspectorChange {
+                    path: path.to_string(),
+                    old: Some(old.to_string()),
+                    new: Some(new.to_string()),
+                });
+            }
+        }
+    }
+}
+
+/// Return type of [`use_object_inspector`].
+pub struct UseObjectInspectorReturn<T, ClearFn>
+where
+    T: 'static,
+    ClearFn: Fn() + Clone,
+{
+    /// The recorded snapshots, the oldest first. Always empty in release builds.
+    pub snapshots: Signal<Vec<InspectorSnapshot<T>>>,
+    /// Removes all snapshots
+    pub clear: ClearFn,
+}
@@ -0,0 +1,653 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::utils::{idb_entries, idb_open, idb_request, idb_store};
+use crate::{
+    js, use_background_sync, use_online, watch_with_options, UseBackgroundSyncReturn, WatchOptions,
+};
+use codee::{
+    CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::future::Future;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+const OUTBOX_STORE: &str = "outbox";
+
+/// Queue of mutations that is persisted to [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API)
+/// and replayed in order as soon as the browser is online.
+///
+/// Every value passed to `enqueue` is encoded with the given codec and appended to the queue.
+/// While the browser is online (see [`use_online`]) the queue is replayed right away by calling
+/// `send` for every item in the order they have been enqueued. Items are removed from the queue
+/// when `send` returns `Ok`. Because the queue is persisted, items that have been enqueued before
+/// the page was closed are replayed the next time the hook is used.
+///
+/// If `send` returns an error the item stays in the queue and is reported in `failures`.
+/// By default the replay stops at the first failure to keep the order of the mutations.
+/// It's resumed the next time the browser comes online or when you call `replay`.
+///
+/// Values are (en)decoded via the given codec. You can use any of the string or binary codecs.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_offline_outbox, UseOfflineOutboxReturn};
+/// # use codee::string::JsonSerdeCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # #[derive(Serialize, Deserialize, Clone)]
+/// # struct Comment {
+/// #     text: String,
+/// # }
+/// #
+/// # async fn post_comment(comment: Comment) -> Result<(), String> { Ok(()) }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseOfflineOutboxReturn {
+///     queue_length,
+///     progress,
+///     failures,
+///     enqueue,
+///     ..
+/// } = use_offline_outbox::<Comment, JsonSerdeCodec, _, _, _>("comments", post_comment);
+///
+/// view! {
+///     <button on:click=move |_| enqueue(Comment { text: "Hello".to_string() })>"Comment"</button>
+///     <p>{move || queue_length.get()} " comments waiting to be sent"</p>
+///     <p>"Sent " {move || progress.get().completed} " of " {move || progress.get().total}</p>
+///     <p>{move || failures.with(|failures| failures.len())} " comments failed"</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Background Sync
+///
+/// When you provide a `sync_tag`, a Background Sync with this tag is registered via
+/// [`use_background_sync`] whenever an item is enqueued while offline. This way your service
+/// worker can replay the queue even if the page has been closed in the meantime.
+/// When the service worker posts a sync completed message for this tag, the queue is read again.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_offline_outbox_with_options, UseOfflineOutboxOptions};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # async fn send(message: String) -> Result<(), ()> { Ok(()) }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let outbox = use_offline_outbox_with_options::<String, FromToStringCodec, _, _, _>(
+///     "messages",
+///     send,
+///     UseOfflineOutboxOptions::default().sync_tag("send-messages"),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The queue is stored in the object store `"outbox"` of the IndexedDB database with the given
+/// name. The keys are auto incremented and every record has the shape
+/// `{ payload, enqueuedAt, attempts }` where `payload` is the encoded value (a string or a
+/// `Uint8Array` for binary codecs). Your service worker has to send the payloads and remove the
+/// records it has sent.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the queue is always empty and `enqueue`, `replay`, `discard` and `clear` do nothing.
+///
+/// ## See also
+///
+/// - [`use_online`]
+/// - [`use_background_sync`]
+pub fn use_offline_outbox<T, C, S, F, Fut>(
+    name: impl Into<String>,
+    send: F,
+) -> UseOfflineOutboxReturn<
+    T,
+    HybridCoderError<<C as Encoder<T>>::Error>,
+    HybridCoderError<<C as Decoder<T>>::Error>,
+    S,
+    impl Fn(T) + Clone,
+    impl Fn() + Clone,
+    impl Fn(u64) + Clone,
+    impl Fn() + Clone,
+>
+where
+    T: 'static,
+    C: Encoder<T> + Decoder<T> + 'static,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+    S: 'static,
+    F: Fn(T) -> Fut + 'static,
+    Fut: Future<Output = Result<(), S>> + 'static,
+{
+    use_offline_outbox_with_options::<T, C, S, F, Fut>(
+        name,
+        send,
+        UseOfflineOutboxOptions::default(),
+    )
+}
+
+/// Version of [`use_offline_outbox`] that takes a `UseOfflineOutboxOptions`. See [`use_offline_outbox`] for how to use.
+pub fn use_offline_outbox_with_options<T, C, S, F, Fut>(
+    name: impl Into<String>,
+    send: F,
+    options: UseOfflineOutboxOptions,
+) -> UseOfflineOutboxReturn<
+    T,
+    HybridCoderError<<C as Encoder<T>>::Error>,
+    HybridCoderError<<C as Decoder<T>>::Error>,
+    S,
+    impl Fn(T) + Clone,
+    impl Fn() + Clone,
+    impl Fn(u64) + Clone,
+    impl Fn() + Clone,
+>
+where
+    T: 'static,
+    C: Encoder<T> + Decoder<T> + 'static,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+    S: 'static,
+    F: Fn(T) -> Fut + 'static,
+    Fut: Future<Output = Result<(), S>> + 'static,
+{
+    let UseOfflineOutboxOptions {
+        sync_tag,
+        stop_on_failure,
+    } = options;
+
+    let name: Rc<str> = name.into().into();
+    let send = Rc::new(send);
+
+    let is_online = use_online();
+
+    let (queue_length, set_queue_length) = create_signal(0_usize);
+    let (is_replaying, set_replaying) = create_signal(false);
+    let (progress, set_progress) = create_signal(ReplayProgress::default());
+    let (failures, set_failures) = create_signal(Vec::<OutboxFailure<_, S>>::new());
+    let (error, set_error) = create_signal(None::<UseOfflineOutboxError<_, _>>);
+
+    // Guards against concurrent replays. `None` after the hook has been disposed.
+    let replaying = store_value(false);
+    // Set when a replay is requested while another one is running.
+    let rerun = store_value(false);
+
+    let reload = {
+        let name = Rc::clone(&name);
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let name = Rc::clone(&name);
+
+                spawn_local(async move {
+                    match read_queue(&name).await {
+                        Ok(entries) => set_queue_length.set(entries.len()),
+                        Err(err) => set_error.set(Some(UseOfflineOutboxError::Storage(err))),
+                    }
+                });
+            }
+        }
+    };
+
+    let replay = {
+        let name = Rc::clone(&name);
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                match replaying.try_get_value() {
+                    Some(false) => {}
+                    Some(true) => {
+                        // pick up items that have been enqueued after the running replay read the queue
+                        rerun.set_value(true);
+                        return;
+                    }
+                    None => return,
+                }
+                replaying.set_value(true);
+                set_replaying.set(true);
+
+                let name = Rc::clone(&name);
+                let send = Rc::clone(&send);
+
+                spawn_local(async move {
+                    let result = loop {
+                        rerun.set_value(false);
+
+                        let result = replay_queue::<T, C, S, F, Fut>(
+                            &name,
+                            &send,
+                            stop_on_failure,
+                            is_online,
+                            replaying,
+                            set_queue_length,
+                            set_progress,
+                            set_failures,
+                        )
+                        .await;
+
+                        // the hook has been disposed in the meantime
+                        if replaying.try_get_value().is_none() {
+                            return;
+                        }
+
+                        if result.is_err() || !rerun.get_value() || !is_online.get_untracked() {
+                            break result;
+                        }
+                    };
+
+                    if let Err(err) = result {
+                        set_error.set(Some(UseOfflineOutboxError::Storage(err)));
+                    }
+
+                    replaying.set_value(false);
+                    set_replaying.set(false);
+                });
+            }
+        }
+    };
+
+    let register_sync: Rc<dyn Fn()> = match sync_tag {
+        Some(sync_tag) => {
+            let UseBackgroundSyncReturn {
+                is_supported,
+                last_completed,
+                register,
+                ..
+            } = use_background_sync();
+
+            let _ = watch_with_options(
+                move || last_completed.get(),
+                {
+                    let sync_tag = sync_tag.clone();
+                    let reload = reload.clone();
+
+                    move |completed, _, _| {
+                        if matches!(completed, Some(completed) if completed.tag == sync_tag) {
+                            reload();
+                        }
+                    }
+                },
+                WatchOptions::default(),
+            );
+
+            Rc::new(move || {
+                if is_supported.get_untracked() {
+                    register(&sync_tag);
+                }
+            })
+        }
+        None => Rc::new(|| {}),
+    };
+
+    let enqueue = {
+        let name = Rc::clone(&name);
+        let replay = replay.clone();
+
+        move |value: T| {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let encoded = if C::is_binary() {
+                    C::encode_bin(&value)
+                        .map(|bytes| JsValue::from(js_sys::Uint8Array::from(bytes.as_slice())))
+                } else {
+                    C::encode_str(&value).map(JsValue::from)
+                };
+
+                let payload = match encoded {
+                    Ok(payload) => payload,
+                    Err(err) => {
+                        set_error.set(Some(UseOfflineOutboxError::Codec(CodecError::Encode(err))));
+                        return;
+                    }
+                };
+
+                let name = Rc::clone(&name);
+                let replay = replay.clone();
+                let register_sync = Rc::clone(&register_sync);
+
+                spawn_local(async move {
+                    let record = js_sys::Object::new();
+                    js!(record["payload"] = payload);
+                    js!(record["enqueuedAt"] = js_sys::Date::now());
+                    js!(record["attempts"] = 0);
+
+                    match add_record(&name, &record).await {
+                        Ok(()) => {
+                            set_error.set(None);
+                            set_queue_length.update(|length| *length += 1);
+
+                            if is_online.get_untracked() {
+                                replay();
+                            } else {
+                                register_sync();
+                            }
+                        }
+                        Err(err) => set_error.set(Some(UseOfflineOutboxError::Storage(err))),
+                    }
+                });
+            }
+        }
+    };
+
+    let discard = {
+        let name = Rc::clone(&name);
+        let reload = reload.clone();
+
+        move |id: u64| {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let name = Rc::clone(&name);
+                let reload = reload.clone();
+
+                spawn_local(async move {
+                    match delete_record(&name, id).await {
+                        Ok(()) => {
+                            set_failures.update(|failures| failures.retain(|f| f.id != id));
+                            reload();
+                        }
+                        Err(err) => set_error.set(Some(UseOfflineOutboxError::Storage(err))),
+                    }
+                });
+            }
+        }
+    };
+
+    let clear = {
+        let name = Rc::clone(&name);
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let name = Rc::clone(&name);
+
+                spawn_local(async move {
+                    match clear_queue(&name).await {
+                        Ok(()) => {
+                            set_failures.update(|failures| failures.clear());
+                            set_queue_length.set(0);
+                        }
+                        Err(err) => set_error.set(Some(UseOfflineOutboxError::Storage(err))),
+                    }
+                });
+            }
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        reload();
+
+        let replay = replay.clone();
+        let _ = watch_with_options(
+            move || is_online.get(),
+            move |online, _, _| {
+                if *online {
+                    replay();
+                }
+            },
+            WatchOptions::default().immediate(true),
+        );
+    }
+
+    UseOfflineOutboxReturn {
+        queue_length: queue_length.into(),
+        is_replaying: is_replaying.into(),
+        progress: progress.into(),
+        failures: failures.into(),
+        error: error.into(),
+        enqueue,
+        replay,
+        discard,
+        clear,
+    }
+}
+
+/// Sends the items of the queue in order and removes them when they have been sent.
+#[cfg(not(feature = "ssr"))]
+#[allow(clippy::too_many_arguments)]
+async fn replay_queue<T, C, S, F, Fut>(
+    name: &str,
+    send: &F,
+    stop_on_failure: bool,
+    is_online: Signal<bool>,
+    replaying: StoredValue<bool>,
+    set_queue_length: WriteSignal<usize>,
+    set_progress: WriteSignal<ReplayProgress>,
+    set_failures: WriteSignal<Vec<OutboxFailure<HybridCoderError<<C as Decoder<T>>::Error>, S>>>,
+) -> Result<(), JsValue>
+where
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = Result<(), S>>,
+{
+    let entries = read_queue(name).await?;
+
+    set_queue_length.set(entries.len());
+    set_progress.set(ReplayProgress {
+        completed: 0,
+        total: entries.len(),
+    });
+    set_failures.update(|failures| failures.clear());
+
+    for (id, record) in entries {
+        // stop if the browser went offline or the hook has been disposed
+        if !is_online.get_untracked() || replaying.try_get_value().is_none() {
+            break;
+        }
+
+        let attempts = js!(record["attempts"])
+            .ok()
+            .and_then(|attempts| attempts.as_f64())
+            .unwrap_or_default() as u32
+            + 1;
+
+        let payload = js!(record["payload"]).unwrap_or(JsValue::UNDEFINED);
+
+        let decoded = if C::is_binary() {
+            C::decode_bin(&js_sys::Uint8Array::new(&payload).to_vec())
+        } else {
+            C::decode_str(&payload.as_string().unwrap_or_default())
+        };
+
+        let result = match decoded {
+            Ok(value) => send(value).await.map_err(OutboxItemError::Send),
+            Err(err) => Err(OutboxItemError::Decode(err)),
+        };
+
+        if replaying.try_get_value().is_none() {
+            break;
+        }
+
+        match result {
+            Ok(()) => {
+                delete_record(name, id).await?;
+
+                set_queue_length.update(|length| *length = length.saturating_sub(1));
+                set_progress.update(|progress| progress.completed += 1);
+            }
+            Err(error) => {
+                js!(record["attempts"] = attempts);
+                put_record(name, id, &record).await?;
+
+                set_failures.update(|failures| {
+                    failures.push(OutboxFailure {
+                        id,
+                        attempts,
+                        error,
+                    })
+                });
+
+                if stop_on_failure {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn outbox_store(
+    name: &str,
+    mode: web_sys::IdbTransactionMode,
+) -> Result<web_sys::IdbObjectStore, JsValue> {
+    let db = idb_open(name, OUTBOX_STORE, true).await?;
+    idb_store(&db, OUTBOX_STORE, mode)
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn read_queue(name: &str) -> Result<Vec<(u64, JsValue)>, JsValue> {
+    let store = outbox_store(name, web_sys::IdbTransactionMode::Readonly).await?;
+
+    Ok(idb_entries(&store)
+        .await?
+        .into_iter()
+        .filter_map(|(key, value)| key.as_f64().map(|key| (key as u64, value)))
+        .collect())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn add_record(name: &str, record: &JsValue) -> Result<(), JsValue> {
+    let store = outbox_store(name, web_sys::IdbTransactionMode::Readwrite).await?;
+    idb_request(&store.add(record)?).await.map(|_| ())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn put_record(name: &str, id: u64, record: &JsValue) -> Result<(), JsValue> {
+    let store = outbox_store(name, web_sys::IdbTransactionMode::Readwrite).await?;
+    idb_request(&store.put_with_key(record, &JsValue::from_f64(id as f64))?)
+        .await
+        .map(|_| ())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn delete_record(name: &str, id: u64) -> Result<(), JsValue> {
+    let store = outbox_store(name, web_sys::IdbTransactionMode::Readwrite).await?;
+    idb_request(&store.delete(&JsValue::from_f64(id as f64))?)
+        .await
+        .map(|_| ())
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn clear_queue(name: &str) -> Result<(), JsValue> {
+    let store = outbox_store(name, web_sys::IdbTransactionMode::Readwrite).await?;
+    idb_request(&store.clear()?).await.map(|_| ())
+}
+
+/// Progress of the current or latest replay of [`use_offline_outbox`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReplayProgress {
+    /// The number of items that have been sent successfully.
+    pub completed: usize,
+    /// The number of items that were in the queue when the replay started.
+    pub total: usize,
+}
+
+/// An item of [`use_offline_outbox`] that couldn't be sent.
+#[derive(Debug)]
+pub struct OutboxFailure<D, S> {
+    /// The key of the item in the queue. Pass it to `discard` to remove the item.
+    pub id: u64,
+    /// How many times sending the item has been attempted.
+    pub attempts: u32,
+    /// The error of the latest attempt.
+    pub error: OutboxItemError<D, S>,
+}
+
+/// Error of a single item of [`use_offline_outbox`].
+#[derive(Error, Debug)]
+pub enum OutboxItemError<D, S> {
+    #[error("failed to decode queued item")]
+    Decode(D),
+    #[error("failed to send queued item")]
+    Send(S),
+}
+
+/// Errors of [`use_offline_outbox`] that are not related to a single item.
+#[derive(Error, Debug)]
+pub enum UseOfflineOutboxError<E, D> {
+    #[error("IndexedDB error: {0:?}")]
+    Storage(JsValue),
+    #[error("failed to encode / decode item: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
+
+/// Options for [`use_offline_outbox_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseOfflineOutboxOptions {
+    /// If set, a Background Sync with this tag is registered when an item is enqueued while
+    /// offline. See [`use_background_sync`]. Defaults to `None`.
+    #[builder(into)]
+    sync_tag: Option<String>,
+
+    /// Stop replaying at the first item that fails to be sent so the order of the items is kept.
+    /// If `false` the failed item is skipped and the replay continues. Defaults to `true`.
+    stop_on_failure: bool,
+}
+
+impl Default for UseOfflineOutboxOptions {
+    fn default() -> Self {
+        Self {
+            sync_tag: None,
+            stop_on_failure: true,
+        }
+    }
+}
+
+/// Return type of [`use_offline_outbox`].
+pub struct UseOfflineOutboxReturn<T, E, D, S, EnqueueFn, ReplayFn, DiscardFn, ClearFn>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+    S: 'static,
+    EnqueueFn: Fn(T) + Clone,
+    ReplayFn: Fn() + Clone,
+    DiscardFn: Fn(u64) + Clone,
+    ClearFn: Fn() + Clone,
+{
+    /// The number of items waiting to be sent.
+    pub queue_length: Signal<usize>,
+
+    /// `true` while the queue is being replayed.
+    pub is_replaying: Signal<bool>,
+
+    /// Progress of the current or latest replay.
+    pub progress: Signal<ReplayProgress>,
+
+    /// The items that failed to be sent during the latest replay.
+    pub failures: Signal<Vec<OutboxFailure<D, S>>>,
+
+    /// The error of the latest queue operation if it failed.
+    pub error: Signal<Option<UseOfflineOutboxError<E, D>>>,
+
+    /// Appends an item to the queue. It's sent right away if the browser is online.
+    pub enqueue: EnqueueFn,
+
+    /// Sends the queued items now. If a replay is already running, the queue is read again
+    /// once it has finished so that items enqueued in the meantime are sent as well.
+    pub replay: ReplayFn,
+
+    /// Removes the item with the given id from the queue without sending it.
+    pub discard: DiscardFn,
+
+    /// Removes all items from the queue.
+    pub clear: ClearFn,
+}
@@ -0,0 +1,46 @@
+use crate::use_event_listener;
+use leptos::ev::{offline, online};
+use leptos::*;
+
+/// Reactive online state of the browser.
+///
+/// Uses [`navigator.onLine`](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/onLine)
+/// and updates on the `online` and `offline` events of `window`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_online;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let is_online = use_online();
+///
+/// view! {
+///     <Show when=move || !is_online.get()>
+///         <p>"You are offline"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this function returns a `Signal` that is always `true`.
+pub fn use_online() -> Signal<bool> {
+    #[cfg(feature = "ssr")]
+    {
+        Signal::derive(|| true)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let (is_online, set_online) = create_signal(window().navigator().on_line());
+
+        let _ = use_event_listener(window(), online, move |_| set_online.set(true));
+        let _ = use_event_listener(window(), offline, move |_| set_online.set(false));
+
+        is_online.into()
+    }
+}
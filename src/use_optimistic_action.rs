@@ -0,0 +1,257 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::UseRwSignal;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::future::Future;
+use std::rc::Rc;
+
+/// Optimistic updates with rollback.
+///
+/// Calling `execute` with an input applies `optimistic_update` to `target` right away so the UI
+/// doesn't have to wait for the server. Then `mutation` is run with the input.
+///
+/// - If it returns `Ok(None)` the optimistic update is kept.
+/// - If it returns `Ok(Some(value))` the value confirmed by the server replaces the optimistic one.
+/// - If it returns `Err` the optimistic update is rolled back and the failure is reported in
+///   `last_failure` and to `on_failure`.
+///
+/// Several mutations can be pending at the same time. Internally the last confirmed value is
+/// kept and the updates of the mutations that are still pending are applied on top of it. So
+/// when one of them fails, only its own update is rolled back. Changes to `target` that are made
+/// by others while mutations are pending are overwritten when a mutation finishes.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_optimistic_action, UseOptimisticActionReturn};
+/// #
+/// # async fn save_like(post_id: u32, liked: bool) -> Result<u32, String> { Ok(0) }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let likes = create_rw_signal(41_u32);
+///
+/// let UseOptimisticActionReturn {
+///     execute,
+///     is_pending,
+///     last_failure,
+///     ..
+/// } = use_optimistic_action(
+///     likes,
+///     |likes: &mut u32, liked: &bool| {
+///         if *liked { *likes += 1 } else { *likes -= 1 }
+///     },
+///     |liked: bool| async move {
+///         // the server answers with the new number of likes
+///         save_like(42, liked).await.map(Some)
+///     },
+/// );
+///
+/// view! {
+///     <button on:click=move |_| execute(true)>{likes} " likes"</button>
+///     <Show when=move || last_failure.with(Option::is_some)>"Couldn't save your like"</Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `execute` only applies the optimistic update. The mutation isn't run.
+pub fn use_optimistic_action<T, I, E, U, M, Fut>(
+    target: impl Into<UseRwSignal<T>>,
+    optimistic_update: U,
+    mutation: M,
+) -> UseOptimisticActionReturn<I, E, impl Fn(I) + Clone + 'static>
+where
+    T: Clone + 'static,
+    I: Clone + 'static,
+    E: 'static,
+    U: Fn(&mut T, &I) + 'static,
+    M: Fn(I) -> Fut + 'static,
+    Fut: Future<Output = Result<Option<T>, E>> + 'static,
+{
+    use_optimistic_action_with_options(
+        target,
+        optimistic_update,
+        mutation,
+        UseOptimisticActionOptions::default(),
+    )
+}
+
+/// Version of [`use_optimistic_action`] that takes a `UseOptimisticActionOptions`. See [`use_optimistic_action`] for how to use.
+pub fn use_optimistic_action_with_options<T, I, E, U, M, Fut>(
+    target: impl Into<UseRwSignal<T>>,
+    optimistic_update: U,
+    mutation: M,
+    options: UseOptimisticActionOptions<I, E>,
+) -> UseOptimisticActionReturn<I, E, impl Fn(I) + Clone + 'static>
+where
+    T: Clone + 'static,
+    I: Clone + 'static,
+    E: 'static,
+    U: Fn(&mut T, &I) + 'static,
+    M: Fn(I) -> Fut + 'static,
+    Fut: Future<Output = Result<Option<T>, E>> + 'static,
+{
+    let UseOptimisticActionOptions { on_failure } = options;
+
+    let target = target.into();
+    let optimistic_update = Rc::new(optimistic_update);
+    let mutation = Rc::new(mutation);
+
+    let (pending, set_pending) = create_signal(Vec::<(u64, I)>::new());
+    let (last_failure, set_last_failure) = create_signal(None::<OptimisticFailure<I, E>>);
+
+    // the value without the updates of the pending mutations
+    let confirmed = store_value(None::<T>);
+    let next_id = store_value(0_u64);
+
+    // Sets `target` to the confirmed value with the updates of all pending mutations applied.
+    let rebase = {
+        let optimistic_update = Rc::clone(&optimistic_update);
+
+        move || {
+            let Some(mut value) = confirmed.try_get_value().flatten() else {
+                return;
+            };
+
+            pending.with_untracked(|pending| {
+                for (_, input) in pending {
+                    optimistic_update(&mut value, input);
+                }
+            });
+
+            if pending.with_untracked(Vec::is_empty) {
+                confirmed.set_value(None);
+            }
+
+            target.set(value);
+        }
+    };
+
+    let execute = move |input: I| {
+        #[cfg(feature = "ssr")]
+        target.update(|value| optimistic_update(value, &input));
+
+        #[cfg(not(feature = "ssr"))]
+        {
+            if pending.with_untracked(Vec::is_empty) {
+                confirmed.set_value(Some(target.get_untracked()));
+            }
+
+            let id = next_id.get_value();
+            next_id.set_value(id + 1);
+
+            set_pending.update(|pending| pending.push((id, input.clone())));
+            target.update(|value| optimistic_update(value, &input));
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            let result = mutation(input.clone());
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+
+            let optimistic_update = Rc::clone(&optimistic_update);
+            let on_failure = Rc::clone(&on_failure);
+            let rebase = rebase.clone();
+
+            spawn_local(async move {
+                let result = result.await;
+
+                if set_pending
+                    .try_update(|pending| pending.retain(|(pending_id, _)| *pending_id != id))
+                    .is_none()
+                {
+                    // disposed in the meantime
+                    return;
+                }
+
+                match result {
+                    Ok(confirmed_value) => {
+                        confirmed.update_value(|confirmed| {
+                            if let Some(confirmed) = confirmed {
+                                match confirmed_value {
+                                    Some(value) => *confirmed = value,
+                                    None => optimistic_update(confirmed, &input),
+                                }
+                            }
+                        });
+                    }
+                    Err(error) => {
+                        let failure = OptimisticFailure { input, error };
+
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_failure(&failure);
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+
+                        set_last_failure.set(Some(failure));
+                    }
+                }
+
+                rebase();
+            });
+        }
+    };
+
+    UseOptimisticActionReturn {
+        pending_mutations: Signal::derive(move || {
+            pending.with(|pending| pending.iter().map(|(_, input)| input.clone()).collect())
+        }),
+        is_pending: Signal::derive(move || pending.with(|pending| !pending.is_empty())),
+        last_failure: last_failure.into(),
+        execute,
+    }
+}
+
+/// Options for [`use_optimistic_action_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseOptimisticActionOptions<I, E>
+where
+    I: 'static,
+    E: 'static,
+{
+    /// Called after the update of a failed mutation has been rolled back.
+    on_failure: Rc<dyn Fn(&OptimisticFailure<I, E>)>,
+}
+
+impl<I, E> Default for UseOptimisticActionOptions<I, E> {
+    fn default() -> Self {
+        Self {
+            on_failure: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// A failed mutation of [`use_optimistic_action`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptimisticFailure<I, E> {
+    /// The input the mutation has been executed with
+    pub input: I,
+    /// The error returned by the mutation
+    pub error: E,
+}
+
+/// Return type of [`use_optimistic_action`].
+pub struct UseOptimisticActionReturn<I, E, ExecuteFn>
+where
+    I: 'static,
+    E: 'static,
+    ExecuteFn: Fn(I) + Clone + 'static,
+{
+    /// Inputs of the mutations that haven't finished yet, in the order they have been executed
+    pub pending_mutations: Signal<Vec<I>>,
+    /// `true` while at least one mutation is pending
+    pub is_pending: Signal<bool>,
+    /// The most recent failed mutation. Its update has been rolled back.
+    pub last_failure: Signal<Option<OptimisticFailure<I, E>>>,
+    /// Applies the optimistic update for the input and runs the mutation
+    pub execute: ExecuteFn,
+}
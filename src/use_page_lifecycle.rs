@@ -0,0 +1,244 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{use_event_listener_with_options, UseEventListenerOptions};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::fmt;
+use std::rc::Rc;
+
+/// Reactively track the state of the page according to the
+/// [Page Lifecycle API](https://developer.chrome.com/docs/web-platform/page-lifecycle-api).
+///
+/// In addition to the document visibility this also tracks if the page has focus and whether
+/// it has been frozen or is about to be terminated.
+///
+/// | State        | Description                                                          |
+/// |--------------|----------------------------------------------------------------------|
+/// | `Active`     | The page is visible and has input focus.                             |
+/// | `Passive`    | The page is visible but doesn't have input focus.                    |
+/// | `Hidden`     | The page is not visible but it's not frozen either.                  |
+/// | `Frozen`     | The browser has suspended the page's task queues to save resources.  |
+/// | `Terminated` | The page has been unloaded and is not going to be put into bfcache.  |
+///
+/// ## Demo
+///
+/// [Link to Demo](https://github.com/Synphonyte/leptos-use/tree/main/examples/use_page_lifecycle)
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_page_lifecycle, PageLifecycleState};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let state = use_page_lifecycle();
+///
+/// let is_active = move || state.get() == PageLifecycleState::Active;
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The `freeze` event is the last chance to checkpoint state before the browser suspends the page.
+/// You can provide callbacks for `freeze` and `resume` as well as for every state change.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::logging::log;
+/// # use leptos_use::{use_page_lifecycle_with_options, UsePageLifecycleOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let state = use_page_lifecycle_with_options(
+///     UsePageLifecycleOptions::default()
+///         .on_freeze(|| log!("Save your state now"))
+///         .on_resume(|| log!("Welcome back"))
+///         .on_change(|old, new| log!("{old} -> {new}")),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this returns a `Signal` that always contains the value `PageLifecycleState::Hidden`.
+///
+/// ## See also
+///
+/// - [`use_document_visibility`]
+/// - [`use_window_focus`]
+pub fn use_page_lifecycle() -> Signal<PageLifecycleState> {
+    use_page_lifecycle_with_options(UsePageLifecycleOptions::default())
+}
+
+/// Version of [`use_page_lifecycle`] that takes a `UsePageLifecycleOptions`. See [`use_page_lifecycle`] for how to use.
+pub fn use_page_lifecycle_with_options(
+    options: UsePageLifecycleOptions,
+) -> Signal<PageLifecycleState> {
+    let UsePageLifecycleOptions {
+        on_freeze,
+        on_resume,
+        on_change,
+    } = options;
+
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        let initial_state = PageLifecycleState::Hidden;
+    } else {
+        let initial_state = current_state();
+    }}
+
+    let (state, set_state) = create_signal(initial_state);
+
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let update_state = move |next: PageLifecycleState| {
+            let prev = state.get_untracked();
+
+            if prev != next {
+                set_state.set(next);
+
+                #[cfg(debug_assertions)]
+                let zone = SpecialNonReactiveZone::enter();
+
+                on_change(prev, next);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(zone);
+            }
+        };
+
+        // The lifecycle events are dispatched on `window` and `document`. Listening on `window`
+        // in the capture phase catches all of them before any application listener can stop them.
+        let options = UseEventListenerOptions::default().capture(true);
+
+        for event_name in ["pageshow", "focus", "blur", "visibilitychange"] {
+            let update_state = update_state.clone();
+
+            let _ = use_event_listener_with_options(
+                window(),
+                ev::Custom::<ev::Event>::new(event_name),
+                move |_| update_state(current_state()),
+                options,
+            );
+        }
+
+        let _ = use_event_listener_with_options(
+            window(),
+            ev::Custom::<ev::Event>::new("resume"),
+            {
+                let update_state = update_state.clone();
+
+                move |_| {
+                    #[cfg(debug_assertions)]
+                    let zone = SpecialNonReactiveZone::enter();
+
+                    on_resume();
+
+                    #[cfg(debug_assertions)]
+                    SpecialNonReactiveZone::exit(zone);
+
+                    update_state(current_state());
+                }
+            },
+            options,
+        );
+
+        let _ = use_event_listener_with_options(
+            window(),
+            ev::Custom::<ev::Event>::new("freeze"),
+            {
+                let update_state = update_state.clone();
+
+                move |_| {
+                    #[cfg(debug_assertions)]
+                    let zone = SpecialNonReactiveZone::enter();
+
+                    on_freeze();
+
+                    #[cfg(debug_assertions)]
+                    SpecialNonReactiveZone::exit(zone);
+
+                    update_state(PageLifecycleState::Frozen);
+                }
+            },
+            options,
+        );
+
+        let _ = use_event_listener_with_options(
+            window(),
+            ev::pagehide,
+            move |evt| {
+                // if the page is persisted it may be restored from the bfcache
+                update_state(if evt.persisted() {
+                    PageLifecycleState::Frozen
+                } else {
+                    PageLifecycleState::Terminated
+                });
+            },
+            options,
+        );
+    }}
+
+    state.into()
+}
+
+/// Determines the state from the document visibility and focus.
+fn current_state() -> PageLifecycleState {
+    let document = document();
+
+    if document.visibility_state() == web_sys::VisibilityState::Hidden {
+        PageLifecycleState::Hidden
+    } else if document.has_focus().unwrap_or_default() {
+        PageLifecycleState::Active
+    } else {
+        PageLifecycleState::Passive
+    }
+}
+
+/// The state of the page as defined by the
+/// [Page Lifecycle API](https://developer.chrome.com/docs/web-platform/page-lifecycle-api#states).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum PageLifecycleState {
+    Active,
+    Passive,
+    Hidden,
+    Frozen,
+    Terminated,
+}
+
+impl fmt::Display for PageLifecycleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PageLifecycleState::Active => write!(f, "Active"),
+            PageLifecycleState::Passive => write!(f, "Passive"),
+            PageLifecycleState::Hidden => write!(f, "Hidden"),
+            PageLifecycleState::Frozen => write!(f, "Frozen"),
+            PageLifecycleState::Terminated => write!(f, "Terminated"),
+        }
+    }
+}
+
+/// Options for [`use_page_lifecycle_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePageLifecycleOptions {
+    /// Called when the page is about to be frozen. Use this to checkpoint state.
+    on_freeze: Rc<dyn Fn()>,
+
+    /// Called when a frozen page is resumed.
+    on_resume: Rc<dyn Fn()>,
+
+    /// Called with the previous and the new state whenever the state changes.
+    on_change: Rc<dyn Fn(PageLifecycleState, PageLifecycleState)>,
+}
+
+impl Default for UsePageLifecycleOptions {
+    fn default() -> Self {
+        Self {
+            on_freeze: Rc::new(|| {}),
+            on_resume: Rc::new(|| {}),
+            on_change: Rc::new(|_, _| {}),
+        }
+    }
+}
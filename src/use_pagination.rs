@@ -0,0 +1,261 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::utils::{get_query_param, set_query_param};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+
+/// Pagination state for lists and tables.
+///
+/// Pages are counted starting at `1`. The current page is always kept between `1` and
+/// `page_count`, also when `total` shrinks.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_pagination, UsePaginationReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (total, set_total) = create_signal(95_usize);
+///
+/// let UsePaginationReturn {
+///     page,
+///     page_count,
+///     offset,
+///     page_size,
+///     is_first_page,
+///     is_last_page,
+///     next,
+///     prev,
+///     ..
+/// } = use_pagination(total);
+///
+/// let items = move || (offset.get()..(offset.get() + page_size.get()).min(total.get()));
+///
+/// view! {
+///     <ul>
+///         <For each=items key=|i| *i let:i>
+///             <li>{i}</li>
+///         </For>
+///     </ul>
+///
+///     <button disabled=is_first_page on:click=move |_| prev()>"Previous"</button>
+///     <span>{page} " / " {page_count}</span>
+///     <button disabled=is_last_page on:click=move |_| next()>"Next"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## URL Query
+///
+/// With `query_param` the current page is kept in the URL so it survives reloads and can be
+/// linked to. The same works for the page size with `page_size_query_param`. Default values are
+/// removed from the URL.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_pagination_with_options, UsePaginationOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let total = 200_usize;
+/// let pagination = use_pagination_with_options(
+///     total,
+///     UsePaginationOptions::default()
+///         .page_size(25)
+///         .query_param("page")
+///         .page_size_query_param("per_page"),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the URL is not read so the page is always `initial_page`.
+pub fn use_pagination(
+    total: impl Into<MaybeSignal<usize>>,
+) -> UsePaginationReturn<
+    impl Fn(usize) + Clone,
+    impl Fn(usize) + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+> {
+    use_pagination_with_options(total, UsePaginationOptions::default())
+}
+
+/// Version of [`use_pagination`] that takes a `UsePaginationOptions`. See [`use_pagination`] for how to use.
+pub fn use_pagination_with_options(
+    total: impl Into<MaybeSignal<usize>>,
+    options: UsePaginationOptions,
+) -> UsePaginationReturn<
+    impl Fn(usize) + Clone,
+    impl Fn(usize) + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+> {
+    let UsePaginationOptions {
+        initial_page,
+        page_size: default_page_size,
+        query_param,
+        page_size_query_param,
+    } = options;
+
+    let total: MaybeSignal<usize> = total.into();
+    let total = Signal::from(total);
+
+    let query_value = |param: &Option<String>| {
+        param
+            .as_deref()
+            .and_then(get_query_param)
+            .and_then(|value| value.parse::<usize>().ok())
+    };
+
+    let (page_size, set_page_size_raw) = create_signal(
+        query_value(&page_size_query_param)
+            .unwrap_or(default_page_size)
+            .max(1),
+    );
+    let (raw_page, set_raw_page) =
+        create_signal(query_value(&query_param).unwrap_or(initial_page).max(1));
+
+    let page_count = Signal::derive(move || {
+        let total = total.get();
+        let page_size = page_size.get();
+        total.div_ceil(page_size).max(1)
+    });
+
+    let page = Signal::derive(move || raw_page.get().min(page_count.get()));
+
+    let offset = Signal::derive(move || (page.get() - 1) * page_size.get());
+    let is_first_page = Signal::derive(move || page.get() == 1);
+    let is_last_page = Signal::derive(move || page.get() == page_count.get());
+
+    if let Some(query_param) = query_param {
+        let _ = watch(
+            move || page.get(),
+            move |page, _, _| {
+                let value = (*page != initial_page).then(|| page.to_string());
+                set_query_param(&query_param, value.as_deref());
+            },
+            false,
+        );
+    }
+
+    if let Some(page_size_query_param) = page_size_query_param {
+        let _ = watch(
+            move || page_size.get(),
+            move |page_size, _, _| {
+                let value = (*page_size != default_page_size).then(|| page_size.to_string());
+                set_query_param(&page_size_query_param, value.as_deref());
+            },
+            false,
+        );
+    }
+
+    let set_page = move |new_page: usize| {
+        set_raw_page.set(new_page.clamp(1, page_count.get_untracked()));
+    };
+
+    let set_page_size = move |new_page_size: usize| {
+        let new_page_size = new_page_size.max(1);
+
+        // stay on the page that contains the first item of the current page
+        let first_item = offset.get_untracked();
+        set_page_size_raw.set(new_page_size);
+        set_page(first_item / new_page_size + 1);
+    };
+
+    let next = move || set_page(page.get_untracked() + 1);
+    let prev = move || set_page(page.get_untracked().saturating_sub(1));
+
+    UsePaginationReturn {
+        page,
+        set_page,
+        page_size: page_size.into(),
+        set_page_size,
+        total,
+        page_count,
+        offset,
+        is_first_page,
+        is_last_page,
+        next,
+        prev,
+    }
+}
+
+/// Options for [`use_pagination_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePaginationOptions {
+    /// Page to start on if the URL doesn't contain one. Defaults to `1`.
+    initial_page: usize,
+
+    /// Number of items per page if the URL doesn't contain it. Defaults to `10`.
+    page_size: usize,
+
+    /// Name of the URL query parameter that holds the current page. `None` disables URL
+    /// syncing. Defaults to `None`.
+    #[builder(into)]
+    query_param: Option<String>,
+
+    /// Name of the URL query parameter that holds the page size. `None` disables URL syncing.
+    /// Defaults to `None`.
+    #[builder(into)]
+    page_size_query_param: Option<String>,
+}
+
+impl Default for UsePaginationOptions {
+    fn default() -> Self {
+        Self {
+            initial_page: 1,
+            page_size: 10,
+            query_param: None,
+            page_size_query_param: None,
+        }
+    }
+}
+
+/// Return type of [`use_pagination`].
+pub struct UsePaginationReturn<SetPageFn, SetPageSizeFn, NextFn, PrevFn>
+where
+    SetPageFn: Fn(usize) + Clone,
+    SetPageSizeFn: Fn(usize) + Clone,
+    NextFn: Fn() + Clone,
+    PrevFn: Fn() + Clone,
+{
+    /// The current page, starting at `1`
+    pub page: Signal<usize>,
+
+    /// Sets the current page. It is clamped between `1` and `page_count`.
+    pub set_page: SetPageFn,
+
+    /// Number of items per page
+    pub page_size: Signal<usize>,
+
+    /// Sets the number of items per page. The page is changed so that the first item of the
+    /// current page stays visible.
+    pub set_page_size: SetPageSizeFn,
+
+    /// Total number of items
+    pub total: Signal<usize>,
+
+    /// Number of pages. At least `1` even if there are no items.
+    pub page_count: Signal<usize>,
+
+    /// Index of the first item of the current page
+    pub offset: Signal<usize>,
+
+    /// `true` if the current page is the first page
+    pub is_first_page: Signal<bool>,
+
+    /// `true` if the current page is the last page
+    pub is_last_page: Signal<bool>,
+
+    /// Moves to the next page if there is one
+    pub next: NextFn,
+
+    /// Moves to the previous page if there is one
+    pub prev: PrevFn,
+}
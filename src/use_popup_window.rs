@@ -0,0 +1,359 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::use_window::use_window;
+use crate::{use_event_listener, use_interval_fn_with_options, UseIntervalFnOptions};
+use cfg_if::cfg_if;
+use codee::{CodecError, Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+
+/// Opens and manages a child window (popup).
+///
+/// Messages can be sent to the popup with `post` and messages that the popup sends to this
+/// window with `window.opener.postMessage(...)` are received in `message`. Values are
+/// (en)decoded via the given codec, like in [`fn@crate::use_broadcast_channel`].
+///
+/// Browsers don't fire an event on the opener when a popup is closed, so `is_open` is updated by
+/// polling the popup every `poll_interval` milliseconds. When the component that calls this
+/// is cleaned up, the popup is closed as well.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_popup_window_with_options, UsePopupWindowOptions, UsePopupWindowReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UsePopupWindowReturn {
+///     is_open,
+///     open,
+///     close,
+///     post,
+///     message,
+///     ..
+/// } = use_popup_window_with_options::<String, FromToStringCodec>(
+///     "/auth/login",
+///     UsePopupWindowOptions::default().features("popup,width=500,height=600"),
+/// );
+///
+/// view! {
+///     <Show
+///         when=is_open
+///         fallback={
+///             let open = open.clone();
+///             move || {
+///                 let open = open.clone();
+///                 view! { <button on:click=move |_| open()>"Log in"</button> }
+///             }
+///         }
+///     >
+///         <button on:click={
+///             let close = close.clone();
+///             move |_| close()
+///         }>"Cancel"</button>
+///     </Show>
+///     <p>"Popup says: " {move || message.get().unwrap_or_default()}</p>
+/// }
+/// # }
+/// ```
+///
+/// The popup only receives messages from `post` if it has the origin `target_origin`, which
+/// is the origin of this page by default. Messages from the popup are only accepted from that
+/// origin as well.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_open` is always `false` and the functions do nothing.
+pub fn use_popup_window<T, C>(
+    url: impl Into<MaybeSignal<String>>,
+) -> UsePopupWindowReturn<
+    T,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn(&T) + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    use_popup_window_with_options::<T, C>(url, UsePopupWindowOptions::default())
+}
+
+/// Version of [`use_popup_window`] that takes a `UsePopupWindowOptions`. See [`use_popup_window`] for how to use.
+pub fn use_popup_window_with_options<T, C>(
+    url: impl Into<MaybeSignal<String>>,
+    options: UsePopupWindowOptions,
+) -> UsePopupWindowReturn<
+    T,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn(&T) + Clone,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    let UsePopupWindowOptions {
+        target,
+        features,
+        target_origin,
+        poll_interval,
+        close_on_cleanup,
+        on_close,
+    } = options;
+
+    let url: MaybeSignal<String> = url.into();
+
+    let target_origin = target_origin.unwrap_or_else(|| {
+        cfg_if! { if #[cfg(feature = "ssr")] {
+            "*".to_string()
+        } else {
+            window().location().origin().unwrap_or_else(|_| "*".to_string())
+        }}
+    });
+
+    let (popup, set_popup) = create_signal(None::<web_sys::Window>);
+    let (message, set_message) = create_signal(None::<T>);
+    let (error, set_error) = create_signal(
+        None::<UsePopupWindowError<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>>,
+    );
+
+    let on_closed = move || {
+        set_popup.set(None);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_close();
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    };
+
+    let poll = use_interval_fn_with_options(
+        {
+            let on_closed = on_closed.clone();
+
+            move || {
+                let is_closed = popup.with_untracked(|popup| {
+                    popup
+                        .as_ref()
+                        .map(|popup| popup.closed().unwrap_or(true))
+                        .unwrap_or_default()
+                });
+
+                if is_closed {
+                    on_closed();
+                }
+            }
+        },
+        poll_interval,
+        UseIntervalFnOptions::default().immediate(false),
+    );
+
+    {
+        let pause = poll.pause;
+        let resume = poll.resume;
+
+        let _ = watch(
+            move || popup.with(Option::is_some),
+            move |is_open, _, _| {
+                if *is_open {
+                    resume();
+                } else {
+                    pause();
+                }
+            },
+            false,
+        );
+    }
+
+    let open = move || {
+        #[cfg(not(feature = "ssr"))]
+        {
+            if let Some(popup) = popup.get_untracked() {
+                let _ = popup.focus();
+                return;
+            }
+
+            let opened = url.with_untracked(|url| match &features {
+                Some(features) => {
+                    window().open_with_url_and_target_and_features(url, &target, features)
+                }
+                None => window().open_with_url_and_target(url, &target),
+            });
+
+            match opened {
+                Ok(Some(opened)) => set_popup.set(Some(opened)),
+                Ok(None) => set_error.set(Some(UsePopupWindowError::Blocked)),
+                Err(err) => set_error.set(Some(UsePopupWindowError::Open(err))),
+            }
+        }
+    };
+
+    let close = move || {
+        if let Some(popup) = popup.get_untracked() {
+            let _ = popup.close();
+            on_closed();
+        }
+    };
+
+    let post = {
+        let target_origin = target_origin.clone();
+
+        move |data: &T| {
+            if let Some(popup) = popup.get_untracked() {
+                match C::encode(data) {
+                    Ok(msg) => {
+                        popup
+                            .post_message(&msg.into(), &target_origin)
+                            .map_err(|err| {
+                                set_error.set(Some(UsePopupWindowError::PostMessage(err)))
+                            })
+                            .ok();
+                    }
+                    Err(err) => {
+                        set_error.set(Some(UsePopupWindowError::Codec(CodecError::Encode(err))));
+                    }
+                }
+            }
+        }
+    };
+
+    let _ = use_event_listener(use_window(), ev::message, move |event| {
+        let Some(current) = popup.get_untracked() else {
+            return;
+        };
+
+        let is_from_popup = event
+            .source()
+            .is_some_and(|source| JsValue::from(source) == JsValue::from(current));
+
+        if !is_from_popup || (target_origin != "*" && event.origin() != target_origin) {
+            return;
+        }
+
+        if let Some(data) = event.data().as_string() {
+            match C::decode(&data) {
+                Ok(msg) => set_message.set(Some(msg)),
+                Err(err) => {
+                    set_error.set(Some(UsePopupWindowError::Codec(CodecError::Decode(err))))
+                }
+            }
+        } else {
+            set_error.set(Some(UsePopupWindowError::ValueNotString));
+        }
+    });
+
+    if close_on_cleanup {
+        on_cleanup({
+            let close = close.clone();
+            move || close()
+        });
+    }
+
+    UsePopupWindowReturn {
+        is_open: Signal::derive(move || popup.with(Option::is_some)),
+        popup: popup.into(),
+        message: message.into(),
+        error: error.into(),
+        open,
+        close,
+        post,
+    }
+}
+
+/// Options for [`use_popup_window_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePopupWindowOptions {
+    /// Name of the browsing context the popup is opened in. Defaults to `"_blank"`.
+    #[builder(into)]
+    target: String,
+
+    /// Window features like `"popup,width=500,height=600"`. Defaults to `None`.
+    #[builder(into)]
+    features: Option<String>,
+
+    /// Origin of the popup that messages are sent to and accepted from. `"*"` allows any origin.
+    /// Defaults to `None` which is the origin of this page.
+    #[builder(into)]
+    target_origin: Option<String>,
+
+    /// Interval in milliseconds in which the popup is checked for having been closed.
+    /// Defaults to `500`.
+    poll_interval: u64,
+
+    /// Close the popup when the component is cleaned up. Defaults to `true`.
+    close_on_cleanup: bool,
+
+    /// Called when the popup has been closed either by the user or by `close`.
+    on_close: Rc<dyn Fn()>,
+}
+
+impl Default for UsePopupWindowOptions {
+    fn default() -> Self {
+        Self {
+            target: "_blank".to_string(),
+            features: None,
+            target_origin: None,
+            poll_interval: 500,
+            close_on_cleanup: true,
+            on_close: Rc::new(|| {}),
+        }
+    }
+}
+
+/// Return type of [`use_popup_window`].
+pub struct UsePopupWindowReturn<T, OpenFn, CloseFn, PostFn, E, D>
+where
+    T: 'static,
+    OpenFn: Fn() + Clone,
+    CloseFn: Fn() + Clone,
+    PostFn: Fn(&T) + Clone,
+    E: 'static,
+    D: 'static,
+{
+    /// `true` while the popup is open
+    pub is_open: Signal<bool>,
+
+    /// The window object of the popup while it's open
+    pub popup: Signal<Option<web_sys::Window>>,
+
+    /// Latest message received from the popup
+    pub message: Signal<Option<T>>,
+
+    /// Latest error
+    pub error: Signal<Option<UsePopupWindowError<E, D>>>,
+
+    /// Opens the popup. If it's open already, it's focused.
+    pub open: OpenFn,
+
+    /// Closes the popup
+    pub close: CloseFn,
+
+    /// Sends a message to the popup
+    pub post: PostFn,
+}
+
+#[derive(Debug, Error)]
+pub enum UsePopupWindowError<E, D> {
+    #[error("the popup has been blocked by the browser")]
+    Blocked,
+    #[error("failed to open the popup")]
+    Open(JsValue),
+    #[error("failed to post message")]
+    PostMessage(JsValue),
+    #[error("failed to (de)encode value")]
+    Codec(CodecError<E, D>),
+    #[error("received value is not a string")]
+    ValueNotString,
+}
@@ -0,0 +1,69 @@
+use cfg_if::cfg_if;
+use leptos::*;
+
+/// Reactive [`navigator.languages`](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/languages).
+///
+/// Returns the languages preferred by the user as BCP 47 language tags, most preferred first.
+/// The signal is updated when the user changes the preferred languages.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_preferred_languages;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let languages = use_preferred_languages();
+///
+/// view! {
+///     <p>"You prefer " {move || languages.get().join(", ")}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server this returns a Signal that is always an empty `Vec`. In the browser the languages
+/// are read after the hydration so the hydrated HTML matches the one from the server.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_locale`]
+pub fn use_preferred_languages() -> Signal<Vec<String>> {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        Signal::derive(Vec::new)
+    } else {
+        use crate::{after_hydration, use_event_listener_with_options, UseEventListenerOptions};
+
+        let read_languages = || {
+            let navigator = window().navigator();
+            let languages = navigator
+                .languages()
+                .iter()
+                .filter_map(|language| language.as_string())
+                .collect::<Vec<_>>();
+
+            if languages.is_empty() {
+                navigator.language().into_iter().collect()
+            } else {
+                languages
+            }
+        };
+
+        let (languages, set_languages) = create_signal(Vec::new());
+
+        after_hydration(move || {
+            let _ = set_languages.try_set(read_languages());
+        });
+
+        let _ = use_event_listener_with_options(
+            window(),
+            ev::Custom::<ev::Event>::new("languagechange"),
+            move |_| set_languages.set(read_languages()),
+            UseEventListenerOptions::default().passive(true),
+        );
+
+        languages.into()
+    }}
+}
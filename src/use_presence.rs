@@ -0,0 +1,508 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ConnectionReadyState;
+use crate::utils::random_id;
+use crate::{
+    js_fut, use_event_source, use_interval_fn, use_websocket_with_options, watch,
+    UseEventSourceReturn, UseWebSocketOptions, UseWebSocketReturn,
+};
+use codee::string::FromToStringCodec;
+use codee::{CodecError, Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive "who's online" list on top of a WebSocket or Server-Sent Events transport.
+///
+/// This client periodically announces itself with its id and the given metadata. All peers that
+/// announce themselves are collected in a reactive map. Peers that leave or stop announcing
+/// themselves for `expire_after` ms are removed again.
+///
+/// The metadata is (en)decoded via the given codec. You can use any of the string codecs or a
+/// binary codec wrapped in `Base64`.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_presence, PresenceTransport, UsePresenceReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (status, set_status) = create_signal("available".to_string());
+///
+/// let UsePresenceReturn { peers, peer_count, .. } = use_presence::<String, FromToStringCodec>(
+///     PresenceTransport::WebSocket("wss://example.com/presence".to_string()),
+///     status,
+/// );
+///
+/// view! {
+///     <p>{peer_count} " others are online"</p>
+///     <ul>
+///         {move || peers.with(|peers| {
+///             peers
+///                 .values()
+///                 .map(|peer| view! { <li>{peer.id.clone()} ": " {peer.metadata.clone()}</li> })
+///                 .collect_view()
+///         })}
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ## Join and Leave Events
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_presence_with_options, PresenceTransport, UsePresenceOptions};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let presence = use_presence_with_options::<String, FromToStringCodec>(
+///     PresenceTransport::EventSource {
+///         url: "/presence/events".to_string(),
+///         post_url: "/presence".to_string(),
+///     },
+///     "Alice".to_string(),
+///     UsePresenceOptions::default()
+///         .on_join(|peer| logging::log!("{} joined", peer.metadata))
+///         .on_leave(|peer, reason| logging::log!("{} left ({reason:?})", peer.metadata)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Transports
+///
+/// The messages are strings with tab separated fields:
+///
+/// - `p <id> <metadata>` announces a client. It is sent every `heartbeat_interval` ms, when the
+///   metadata changes and when a new peer shows up so that it doesn't have to wait for the next
+///   heartbeat.
+/// - `l <id>` is sent by a client that leaves.
+///
+/// With `PresenceTransport::WebSocket` the backend has to relay every message to all other
+/// clients. With `PresenceTransport::EventSource` the messages are posted to `post_url` and the
+/// backend has to send them as unnamed events to all clients connected to `url`.
+/// `PresenceTransport::Custom` lets you plug in any other transport.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no connection is made and `peers` is always empty.
+pub fn use_presence<M, C>(
+    transport: PresenceTransport,
+    metadata: impl Into<MaybeSignal<M>>,
+) -> UsePresenceReturn<M, <C as Encoder<M>>::Error, <C as Decoder<M>>::Error>
+where
+    M: Clone + 'static,
+    C: Encoder<M, Encoded = String> + Decoder<M, Encoded = str>,
+{
+    use_presence_with_options::<M, C>(transport, metadata, UsePresenceOptions::default())
+}
+
+/// Version of [`use_presence`] that takes a `UsePresenceOptions`. See [`use_presence`] for how to use.
+pub fn use_presence_with_options<M, C>(
+    transport: PresenceTransport,
+    metadata: impl Into<MaybeSignal<M>>,
+    options: UsePresenceOptions<M>,
+) -> UsePresenceReturn<M, <C as Encoder<M>>::Error, <C as Decoder<M>>::Error>
+where
+    M: Clone + 'static,
+    C: Encoder<M, Encoded = String> + Decoder<M, Encoded = str>,
+{
+    let UsePresenceOptions {
+        id,
+        heartbeat_interval,
+        expire_after,
+        on_join,
+        on_leave,
+    } = options;
+
+    let metadata: Signal<M> = metadata.into().into();
+
+    // tabs separate the fields of the messages
+    let id: Rc<str> = id.unwrap_or_else(random_id).replace('\t', " ").into();
+
+    let (peers, set_peers) = create_signal(HashMap::<String, PresencePeer<M>>::new());
+    let (error, set_error) = create_signal(None::<UsePresenceError<_, _>>);
+
+    let sender = store_value(None::<Rc<dyn Fn(&str)>>);
+
+    let send = move |message: PresenceMessage| {
+        if let Some(send) = sender.try_get_value().flatten() {
+            send(&message.encode());
+        }
+    };
+
+    let announce = {
+        let id = Rc::clone(&id);
+
+        move || match metadata.with_untracked(C::encode) {
+            Ok(payload) => send(PresenceMessage::Present {
+                id: id.to_string(),
+                payload,
+            }),
+            Err(err) => set_error.set(Some(UsePresenceError::Codec(CodecError::Encode(err)))),
+        }
+    };
+
+    let handle_message: Rc<dyn Fn(&str)> = Rc::new({
+        let id = Rc::clone(&id);
+        let announce = announce.clone();
+        let on_leave = Rc::clone(&on_leave);
+
+        move |message: &str| {
+            let Some(message) = PresenceMessage::decode(message) else {
+                return;
+            };
+
+            match message {
+                PresenceMessage::Present {
+                    id: peer_id,
+                    payload,
+                } => {
+                    if peer_id == *id {
+                        return;
+                    }
+
+                    let peer = match C::decode(&payload) {
+                        Ok(peer_metadata) => PresencePeer {
+                            id: peer_id,
+                            metadata: peer_metadata,
+                            last_seen: js_sys::Date::now(),
+                        },
+                        Err(err) => {
+                            set_error.set(Some(UsePresenceError::Codec(CodecError::Decode(err))));
+                            return;
+                        }
+                    };
+
+                    let joined = set_peers
+                        .try_update(|peers| peers.insert(peer.id.clone(), peer.clone()).is_none())
+                        .unwrap_or_default();
+
+                    if joined {
+                        // let the new peer know about this client right away
+                        announce();
+
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_join(&peer);
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+                    }
+                }
+                PresenceMessage::Leave { id: peer_id } => {
+                    let peer = set_peers
+                        .try_update(|peers| peers.remove(&peer_id))
+                        .flatten();
+
+                    if let Some(peer) = peer {
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_leave(&peer, PresenceLeaveReason::Left);
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+                    }
+                }
+            }
+        }
+    });
+
+    // registered before the transport so the leave message is sent before the connection is closed
+    #[cfg(not(feature = "ssr"))]
+    {
+        let id = Rc::clone(&id);
+        on_cleanup(move || send(PresenceMessage::Leave { id: id.to_string() }));
+    }
+
+    match transport {
+        PresenceTransport::WebSocket(url) => {
+            let UseWebSocketReturn {
+                ready_state,
+                send: send_websocket,
+                ..
+            } = use_websocket_with_options::<String, FromToStringCodec>(
+                &url,
+                UseWebSocketOptions::default()
+                    .on_message(move |message: &String| handle_message(message)),
+            );
+
+            sender.set_value(Some(Rc::new(move |message: &str| {
+                send_websocket(&message.to_string())
+            })));
+
+            let announce = announce.clone();
+            let _ = watch(
+                move || ready_state.get(),
+                move |ready_state, _, _| {
+                    if *ready_state == ConnectionReadyState::Open {
+                        announce();
+                    }
+                },
+            );
+        }
+        PresenceTransport::EventSource { url, post_url } => {
+            let UseEventSourceReturn {
+                data, ready_state, ..
+            } = use_event_source::<String, FromToStringCodec>(&url);
+
+            let _ = watch(
+                move || data.get(),
+                move |data, _, _| {
+                    if let Some(data) = data {
+                        handle_message(data);
+                    }
+                },
+            );
+
+            sender.set_value(Some(Rc::new(move |message: &str| {
+                let post_url = post_url.clone();
+                let body = message.to_string();
+
+                spawn_local(async move {
+                    if let Err(err) = post_message(&post_url, &body).await {
+                        set_error.set(Some(UsePresenceError::Send(err)));
+                    }
+                });
+            })));
+
+            let announce = announce.clone();
+            let _ = watch(
+                move || ready_state.get(),
+                move |ready_state, _, _| {
+                    if *ready_state == ConnectionReadyState::Open {
+                        announce();
+                    }
+                },
+            );
+        }
+        PresenceTransport::Custom { incoming, send } => {
+            let _ = watch(
+                move || incoming.get(),
+                move |incoming, _, _| {
+                    if let Some(incoming) = incoming {
+                        handle_message(incoming);
+                    }
+                },
+            );
+
+            sender.set_value(Some(send));
+
+            #[cfg(not(feature = "ssr"))]
+            announce();
+        }
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = use_interval_fn(
+            {
+                let announce = announce.clone();
+
+                move || {
+                    announce();
+
+                    let expired_before = js_sys::Date::now() - expire_after as f64;
+                    let expired = set_peers
+                        .try_update(|peers| {
+                            let expired = peers
+                                .values()
+                                .filter(|peer| peer.last_seen < expired_before)
+                                .cloned()
+                                .collect::<Vec<_>>();
+
+                            peers.retain(|_, peer| peer.last_seen >= expired_before);
+
+                            expired
+                        })
+                        .unwrap_or_default();
+
+                    #[cfg(debug_assertions)]
+                    let prev = SpecialNonReactiveZone::enter();
+
+                    for peer in expired {
+                        on_leave(&peer, PresenceLeaveReason::Expired);
+                    }
+
+                    #[cfg(debug_assertions)]
+                    SpecialNonReactiveZone::exit(prev);
+                }
+            },
+            heartbeat_interval,
+        );
+
+        let _ = watch(move || metadata.get(), move |_, _, _| announce());
+    }
+
+    UsePresenceReturn {
+        id: id.to_string(),
+        peers: peers.into(),
+        peer_count: Signal::derive(move || peers.with(|peers| peers.len())),
+        error: error.into(),
+    }
+}
+
+async fn post_message(url: &str, body: &str) -> Result<(), JsValue> {
+    let mut init = web_sys::RequestInit::new();
+    init.method("POST").body(Some(&JsValue::from_str(body)));
+
+    let response = js_fut!(window().fetch_with_str_and_init(url, &init))
+        .await?
+        .unchecked_into::<web_sys::Response>();
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(JsValue::from_str(&format!(
+            "Unexpected response status {}",
+            response.status()
+        )))
+    }
+}
+
+/// How the presence messages are sent and received.
+pub enum PresenceTransport {
+    /// Messages are sent and received through a WebSocket connection to this URL.
+    /// The backend has to relay every message to all other clients.
+    WebSocket(String),
+
+    /// Messages are received as unnamed Server-Sent Events from `url` and sent as `POST`
+    /// requests to `post_url`.
+    EventSource { url: String, post_url: String },
+
+    /// Messages are received through `incoming` and sent with `send`.
+    Custom {
+        incoming: Signal<Option<String>>,
+        send: Rc<dyn Fn(&str)>,
+    },
+}
+
+/// Options for [`use_presence_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePresenceOptions<M>
+where
+    M: 'static,
+{
+    /// Id of this client. Defaults to a random id.
+    #[builder(into)]
+    id: Option<String>,
+
+    /// Interval in ms in which this client announces itself. Defaults to 10 seconds.
+    heartbeat_interval: u64,
+
+    /// Peers that haven't announced themselves for this many ms are removed. Should be a multiple
+    /// of `heartbeat_interval`. Defaults to 30 seconds.
+    expire_after: u64,
+
+    /// Called when a peer shows up.
+    on_join: Rc<dyn Fn(&PresencePeer<M>)>,
+
+    /// Called when a peer leaves or expires.
+    on_leave: Rc<dyn Fn(&PresencePeer<M>, PresenceLeaveReason)>,
+}
+
+impl<M> Default for UsePresenceOptions<M> {
+    fn default() -> Self {
+        Self {
+            id: None,
+            heartbeat_interval: 10_000,
+            expire_after: 30_000,
+            on_join: Rc::new(|_| {}),
+            on_leave: Rc::new(|_, _| {}),
+        }
+    }
+}
+
+/// Return type of [`use_presence`].
+pub struct UsePresenceReturn<M, E, D>
+where
+    M: 'static,
+    E: 'static,
+    D: 'static,
+{
+    /// Id of this client
+    pub id: String,
+
+    /// Currently present peers by id. Doesn't include this client.
+    pub peers: Signal<HashMap<String, PresencePeer<M>>>,
+
+    /// Number of currently present peers
+    pub peer_count: Signal<usize>,
+
+    /// The latest error
+    pub error: Signal<Option<UsePresenceError<E, D>>>,
+}
+
+/// A peer that is currently present.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PresencePeer<M> {
+    /// Id of the peer
+    pub id: String,
+
+    /// Latest metadata the peer has announced
+    pub metadata: M,
+
+    /// Time of the latest announcement in ms since the Unix epoch
+    pub last_seen: f64,
+}
+
+/// Why a peer is no longer present.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresenceLeaveReason {
+    /// The peer has sent a leave message.
+    Left,
+    /// The peer hasn't announced itself for `expire_after` ms.
+    Expired,
+}
+
+#[derive(Error, Debug)]
+pub enum UsePresenceError<E, D> {
+    #[error("failed to send message: {0:?}")]
+    Send(JsValue),
+    #[error("failed to encode / decode metadata: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
+
+enum PresenceMessage {
+    Present { id: String, payload: String },
+    Leave { id: String },
+}
+
+impl PresenceMessage {
+    fn encode(&self) -> String {
+        match self {
+            Self::Present { id, payload } => format!("p\t{id}\t{payload}"),
+            Self::Leave { id } => format!("l\t{id}"),
+        }
+    }
+
+    fn decode(message: &str) -> Option<Self> {
+        let mut fields = message.splitn(3, '\t');
+
+        let kind = fields.next()?;
+        let id = fields.next()?.to_string();
+
+        match kind {
+            "p" => Some(Self::Present {
+                id,
+                payload: fields.next()?.to_string(),
+            }),
+            "l" => Some(Self::Leave { id }),
+            _ => None,
+        }
+    }
+}
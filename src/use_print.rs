@@ -0,0 +1,345 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{js, js_fut, use_event_listener, use_window};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+
+/// Reactive printing of the page.
+///
+/// `is_printing` is `true` while the print dialog or the print preview is shown, which is
+/// when the `beforeprint` and `afterprint` events of the window are fired. This can be used to
+/// render things differently for print, e.g. to expand collapsed sections. `on_before_print` and
+/// `on_after_print` are called at the same moments.
+///
+/// Use [`use_element_print`] to print a single element instead of the whole page.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_print, UsePrintReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UsePrintReturn { is_printing, print } = use_print();
+///
+/// view! {
+///     <button on:click=move |_| print()>"Print"</button>
+///     <Show when=move || is_printing.get() fallback=|| view! { <p>"Summary"</p> }>
+///         <p>"All the details"</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_printing` is always `false` and `print` does nothing.
+pub fn use_print() -> UsePrintReturn<impl Fn() + Clone> {
+    use_print_with_options(UsePrintOptions::default())
+}
+
+/// Version of [`use_print`] that takes a `UsePrintOptions`. See [`use_print`] for how to use.
+pub fn use_print_with_options(options: UsePrintOptions) -> UsePrintReturn<impl Fn() + Clone> {
+    print_target(Signal::derive(|| None), options)
+}
+
+/// Prints a single element of the page.
+///
+/// The element is cloned into a hidden iframe together with the stylesheets of the page
+/// (the `<link rel="stylesheet">` and `<style>` elements matching `stylesheets`) and that
+/// iframe is printed. As soon as the stylesheets have been loaded the print dialog is shown.
+/// Afterwards the iframe is removed again.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_print_with_options, UsePrintOptions, UsePrintReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let invoice = create_node_ref::<Div>();
+///
+/// let UsePrintReturn { print, .. } = use_element_print_with_options(
+///     invoice,
+///     UsePrintOptions::default()
+///         .stylesheets("link[rel=stylesheet][href*=invoice]")
+///         .css("body { margin: 2cm; }"),
+/// );
+///
+/// view! {
+///     <div node_ref=invoice>"..."</div>
+///     <button on:click=move |_| print()>"Print invoice"</button>
+/// }
+/// # }
+/// ```
+///
+/// `is_printing` is `true` from calling `print` until the printed iframe has been removed.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_printing` is always `false` and `print` does nothing.
+pub fn use_element_print<El, T>(target: El) -> UsePrintReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_element_print_with_options(target, UsePrintOptions::default())
+}
+
+/// Version of [`use_element_print`] that takes a `UsePrintOptions`. See [`use_element_print`] for how to use.
+pub fn use_element_print_with_options<El, T>(
+    target: El,
+    options: UsePrintOptions,
+) -> UsePrintReturn<impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+
+    print_target(
+        Signal::derive(move || target.get().map(Into::into)),
+        options,
+    )
+}
+
+fn print_target(
+    target: Signal<Option<web_sys::Element>>,
+    options: UsePrintOptions,
+) -> UsePrintReturn<impl Fn() + Clone> {
+    let UsePrintOptions {
+        stylesheets,
+        css,
+        on_before_print,
+        on_after_print,
+    } = options;
+
+    let (is_printing, set_printing) = create_signal(false);
+
+    let before_print = move || {
+        set_printing.set(true);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_before_print();
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    };
+
+    let after_print = move || {
+        set_printing.set(false);
+
+        #[cfg(debug_assertions)]
+        let prev = SpecialNonReactiveZone::enter();
+
+        on_after_print();
+
+        #[cfg(debug_assertions)]
+        SpecialNonReactiveZone::exit(prev);
+    };
+
+    let _ = use_event_listener(use_window(), Custom::<ev::Event>::new("beforeprint"), {
+        let before_print = before_print.clone();
+        move |_| before_print()
+    });
+
+    let _ = use_event_listener(use_window(), Custom::<ev::Event>::new("afterprint"), {
+        let after_print = after_print.clone();
+        move |_| after_print()
+    });
+
+    // the iframe of an element that is being printed
+    let frame = store_value(None::<web_sys::HtmlIFrameElement>);
+
+    let remove_frame = move || {
+        if let Some(frame) = frame.try_update_value(Option::take).flatten() {
+            frame.remove();
+        }
+    };
+
+    on_cleanup(remove_frame);
+
+    let print = move || {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let Some(el) = target.get_untracked() else {
+                let _ = window().print();
+                return;
+            };
+
+            if frame.with_value(Option::is_some) {
+                return;
+            }
+
+            let Some(iframe) = create_print_frame(&el, &stylesheets, &css) else {
+                return;
+            };
+            frame.set_value(Some(iframe.clone()));
+
+            let before_print = before_print.clone();
+            let after_print = after_print.clone();
+
+            before_print();
+
+            spawn_local(async move {
+                let Some(frame_window) = iframe.content_window() else {
+                    remove_frame();
+                    after_print();
+                    return;
+                };
+
+                if let Some(loaded) = stylesheets_loaded(&iframe) {
+                    let _ = js_fut!(loaded).await;
+                }
+
+                // some browsers return from `print()` right away, others only when the dialog
+                // is closed. Both fire `afterprint` when it's closed.
+                let _ = frame_window.add_event_listener_with_callback(
+                    "afterprint",
+                    Closure::once_into_js(move || {
+                        remove_frame();
+                        after_print();
+                    })
+                    .unchecked_ref(),
+                );
+
+                let _ = frame_window.focus();
+                let _ = frame_window.print();
+            });
+        }
+    };
+
+    UsePrintReturn {
+        is_printing: is_printing.into(),
+        print,
+    }
+}
+
+/// Creates a hidden iframe that contains a clone of `el` and of the stylesheets matching
+/// the selector `stylesheets`.
+#[cfg(not(feature = "ssr"))]
+fn create_print_frame(
+    el: &web_sys::Element,
+    stylesheets: &str,
+    css: &Option<String>,
+) -> Option<web_sys::HtmlIFrameElement> {
+    let document = document();
+
+    let iframe = document
+        .create_element("iframe")
+        .ok()?
+        .unchecked_into::<web_sys::HtmlIFrameElement>();
+    let _ = iframe.set_attribute("aria-hidden", "true");
+    let _ = iframe.set_attribute(
+        "style",
+        "position: fixed; right: 0; bottom: 0; width: 0; height: 0; border: 0; visibility: hidden;",
+    );
+    document.body()?.append_child(&iframe).ok()?;
+
+    let frame_document = iframe.content_document()?;
+    let head = frame_document.query_selector("head").ok()??;
+    let body = frame_document.body()?;
+
+    // resolve relative URLs of stylesheets and images like in the page
+    let base = frame_document.create_element("base").ok()?;
+    let _ = base.set_attribute("href", &document.base_uri().ok()??);
+    let _ = head.append_child(&base);
+
+    if !stylesheets.is_empty() {
+        if let Ok(nodes) = document.query_selector_all(stylesheets) {
+            for i in 0..nodes.length() {
+                if let Some(node) = nodes.item(i) {
+                    if let Ok(clone) = node.clone_node_with_deep(true) {
+                        let _ = head.append_child(&clone);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(css) = css {
+        let style = frame_document.create_element("style").ok()?;
+        style.set_text_content(Some(css));
+        let _ = head.append_child(&style);
+    }
+
+    body.append_child(&el.clone_node_with_deep(true).ok()?)
+        .ok()?;
+
+    Some(iframe)
+}
+
+/// A promise that resolves when all `<link>` elements of the frame have loaded or failed.
+#[cfg(not(feature = "ssr"))]
+fn stylesheets_loaded(iframe: &web_sys::HtmlIFrameElement) -> Option<js_sys::Promise> {
+    let links = iframe
+        .content_document()?
+        .query_selector_all("link[rel=stylesheet]")
+        .ok()?;
+
+    let promises = js_sys::Array::new();
+
+    for i in 0..links.length() {
+        if let Some(link) = links.item(i) {
+            promises.push(&js_sys::Promise::new(&mut |resolve, _| {
+                js!(link["onload"] = resolve.clone());
+                js!(link["onerror"] = resolve);
+            }));
+        }
+    }
+
+    Some(js_sys::Promise::all(&promises))
+}
+
+/// Options for [`use_print_with_options`] and [`use_element_print_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UsePrintOptions {
+    /// CSS selector of the stylesheet elements of the page that are copied when printing an
+    /// element. An empty selector copies none.
+    /// Defaults to `"link[rel=stylesheet], style"`.
+    #[builder(into)]
+    stylesheets: String,
+
+    /// Additional CSS for printing an element. Defaults to `None`.
+    #[builder(into)]
+    css: Option<String>,
+
+    /// Called before the print dialog is shown
+    on_before_print: Rc<dyn Fn()>,
+
+    /// Called after the print dialog has been closed
+    on_after_print: Rc<dyn Fn()>,
+}
+
+impl Default for UsePrintOptions {
+    fn default() -> Self {
+        Self {
+            stylesheets: "link[rel=stylesheet], style".to_string(),
+            css: None,
+            on_before_print: Rc::new(|| {}),
+            on_after_print: Rc::new(|| {}),
+        }
+    }
+}
+
+/// Return type of [`use_print`] and [`use_element_print`].
+pub struct UsePrintReturn<PrintFn>
+where
+    PrintFn: Fn() + Clone,
+{
+    /// `true` while printing
+    pub is_printing: Signal<bool>,
+
+    /// Opens the print dialog
+    pub print: PrintFn,
+}
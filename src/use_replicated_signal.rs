@@ -0,0 +1,553 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{BackoffReconnectPolicy, ConnectionReadyState, ReconnectLimit, ReconnectPolicy};
+use crate::utils::random_id;
+use crate::{
+    use_broadcast_channel, use_interval_fn, use_websocket_with_options, watch,
+    UseBroadcastChannelReturn, UseWebSocketOptions, UseWebSocketReturn,
+};
+use codee::string::FromToStringCodec;
+use codee::{CodecError, Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A signal whose value is replicated to all tabs of the same origin and optionally to other
+/// clients through a WebSocket backend.
+///
+/// Concurrent changes are merged without conflicts by a last-writer-wins register: every change
+/// is stamped with a hybrid logical clock (wall clock time, a counter and the id of the replica
+/// that made the change) and all replicas keep the value with the highest stamp. This way all
+/// replicas end up with the same value no matter in which order they receive the changes.
+///
+/// Tabs are synchronized via a `BroadcastChannel`. Replicas that join later receive the current
+/// value from the others. Replicas announce themselves periodically which is used for
+/// `peer_count`.
+///
+/// Values are (en)decoded via the given codec. You can use any of the string codecs or a
+/// binary codec wrapped in `Base64`.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_replicated_signal, UseReplicatedSignalReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseReplicatedSignalReturn {
+///     value,
+///     set,
+///     peer_count,
+///     ..
+/// } = use_replicated_signal::<u32, FromToStringCodec>("counter", 0);
+///
+/// view! {
+///     <button on:click=move |_| set(value.get_untracked() + 1)>{value}</button>
+///     <p>{peer_count} " other tabs are connected"</p>
+/// }
+/// # }
+/// ```
+///
+/// ## WebSocket Backend
+///
+/// To replicate the value to other clients, provide the URL of a WebSocket backend that relays
+/// every message it receives to all other clients.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_replicated_signal_with_options, UseReplicatedSignalOptions};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let replicated = use_replicated_signal_with_options::<String, FromToStringCodec>(
+///     "document-title",
+///     "Untitled".to_string(),
+///     UseReplicatedSignalOptions::default().websocket_url("wss://example.com/replicate"),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The messages are text frames with tab separated fields:
+///
+/// - `u <key> <time> <counter> <replica> <payload>` changes the value.
+/// - `h <key> <replica>` announces a replica. Replicas that see this replica for the first time
+///   answer with their current value.
+/// - `b <key> <replica>` is sent by a replica that leaves.
+///
+/// The backend can persist the latest `u` message of every key and send it to new clients.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `value` is the initial value, `set` only changes the local value and nothing
+/// is replicated.
+pub fn use_replicated_signal<T, C>(
+    key: impl Into<String>,
+    initial: T,
+) -> UseReplicatedSignalReturn<
+    T,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+    impl Fn(T) + Clone,
+>
+where
+    T: Clone + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    use_replicated_signal_with_options::<T, C>(key, initial, UseReplicatedSignalOptions::default())
+}
+
+/// Version of [`use_replicated_signal`] that takes a `UseReplicatedSignalOptions`. See [`use_replicated_signal`] for how to use.
+pub fn use_replicated_signal_with_options<T, C>(
+    key: impl Into<String>,
+    initial: T,
+    options: UseReplicatedSignalOptions,
+) -> UseReplicatedSignalReturn<
+    T,
+    <C as Encoder<T>>::Error,
+    <C as Decoder<T>>::Error,
+    impl Fn(T) + Clone,
+>
+where
+    T: Clone + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    let UseReplicatedSignalOptions {
+        websocket_url,
+        cross_tab,
+        heartbeat_interval,
+        reconnect_policy,
+    } = options;
+
+    // tabs separate the fields of the messages
+    let key: Rc<str> = key.into().replace('\t', " ").into();
+    let replica: Rc<str> = random_id().into();
+
+    let (value, set_value) = create_signal(initial);
+    let (error, set_error) = create_signal(None::<CodecError<_, _>>);
+    let (peers, set_peers) = create_signal(HashMap::<String, f64>::new());
+
+    // stamp and encoded form of the current value
+    let stamp = store_value(LwwStamp::default());
+    let payload = store_value(None::<String>);
+
+    let senders = store_value(Vec::<Rc<dyn Fn(&str)>>::new());
+
+    let send = move |message: ReplicaMessage| {
+        let message = message.encode();
+        senders.with_value(|senders| {
+            for send in senders {
+                send(&message);
+            }
+        });
+    };
+
+    let send_hello = {
+        let key = Rc::clone(&key);
+        let replica = Rc::clone(&replica);
+
+        move || {
+            send(ReplicaMessage::Hello {
+                key: key.to_string(),
+                replica: replica.to_string(),
+            })
+        }
+    };
+
+    // Sends the current value if it has been set by anyone so far
+    let send_current = {
+        let key = Rc::clone(&key);
+
+        move || {
+            if let Some(payload) = payload.get_value() {
+                send(ReplicaMessage::Update {
+                    key: key.to_string(),
+                    stamp: stamp.get_value(),
+                    payload,
+                });
+            }
+        }
+    };
+
+    // Records that `peer` has been seen. Returns `true` if it hasn't been seen before.
+    let touch_peer = {
+        let replica = Rc::clone(&replica);
+
+        move |peer: &str| {
+            if peer == &*replica {
+                return false;
+            }
+
+            set_peers
+                .try_update(|peers| {
+                    peers
+                        .insert(peer.to_string(), js_sys::Date::now())
+                        .is_none()
+                })
+                .unwrap_or_default()
+        }
+    };
+
+    let handle_message: Rc<dyn Fn(&str)> = Rc::new({
+        let key = Rc::clone(&key);
+        let send_hello = send_hello.clone();
+        let send_current = send_current.clone();
+
+        move |message: &str| {
+            let Some(message) = ReplicaMessage::decode(message) else {
+                return;
+            };
+
+            if message.key() != &*key {
+                return;
+            }
+
+            match message {
+                ReplicaMessage::Update {
+                    stamp: remote_stamp,
+                    payload: remote_payload,
+                    ..
+                } => {
+                    touch_peer(&remote_stamp.replica);
+
+                    if remote_stamp <= stamp.get_value() {
+                        return;
+                    }
+
+                    match C::decode(&remote_payload) {
+                        Ok(remote_value) => {
+                            stamp.set_value(remote_stamp);
+                            payload.set_value(Some(remote_payload));
+                            set_value.set(remote_value);
+                        }
+                        Err(err) => set_error.set(Some(CodecError::Decode(err))),
+                    }
+                }
+                ReplicaMessage::Hello { replica: peer, .. } => {
+                    if touch_peer(&peer) {
+                        send_hello();
+                        send_current();
+                    }
+                }
+                ReplicaMessage::Bye { replica: peer, .. } => {
+                    set_peers.update(|peers| {
+                        peers.remove(&peer);
+                    });
+                }
+            }
+        }
+    });
+
+    if cross_tab {
+        let UseBroadcastChannelReturn { message, post, .. } =
+            use_broadcast_channel::<String, FromToStringCodec>(&format!(
+                "leptos-use-replicated-signal-{key}"
+            ));
+
+        let handle_message = Rc::clone(&handle_message);
+        let _ = watch(
+            move || message.get(),
+            move |message, _, _| {
+                if let Some(message) = message {
+                    handle_message(message);
+                }
+            },
+        );
+
+        senders.update_value(|senders| {
+            senders.push(Rc::new(move |message: &str| post(&message.to_string())))
+        });
+    }
+
+    let websocket_ready_state = websocket_url.map(|url| {
+        let handle_message = Rc::clone(&handle_message);
+
+        let UseWebSocketReturn {
+            ready_state,
+            send: send_websocket,
+            ..
+        } = use_websocket_with_options::<String, FromToStringCodec>(
+            &url,
+            UseWebSocketOptions::default()
+                .reconnect_policy(reconnect_policy)
+                .on_message(move |message: &String| handle_message(message)),
+        );
+
+        senders.update_value(|senders| {
+            senders.push(Rc::new(move |message: &str| {
+                send_websocket(&message.to_string())
+            }))
+        });
+
+        // announce this replica and push changes that have been made while disconnected
+        let send_hello = send_hello.clone();
+        let send_current = send_current.clone();
+        let _ = watch(
+            move || ready_state.get(),
+            move |ready_state, _, _| {
+                if *ready_state == ConnectionReadyState::Open {
+                    send_hello();
+                    send_current();
+                }
+            },
+        );
+
+        ready_state
+    });
+
+    let set = {
+        let key = Rc::clone(&key);
+        let replica = Rc::clone(&replica);
+
+        move |new_value: T| {
+            #[cfg(not(feature = "ssr"))]
+            match C::encode(&new_value) {
+                Ok(encoded) => {
+                    let new_stamp = stamp.get_value().next(&replica, js_sys::Date::now() as u64);
+
+                    stamp.set_value(new_stamp.clone());
+                    payload.set_value(Some(encoded.clone()));
+                    set_value.set(new_value);
+
+                    send(ReplicaMessage::Update {
+                        key: key.to_string(),
+                        stamp: new_stamp,
+                        payload: encoded,
+                    });
+                }
+                Err(err) => set_error.set(Some(CodecError::Encode(err))),
+            }
+
+            #[cfg(feature = "ssr")]
+            set_value.set(new_value);
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        send_hello();
+
+        let _ = use_interval_fn(
+            {
+                let send_hello = send_hello.clone();
+
+                move || {
+                    send_hello();
+
+                    // peers that missed three heartbeats are gone
+                    let expired_before = js_sys::Date::now() - 3.0 * heartbeat_interval as f64;
+                    set_peers.update(|peers| peers.retain(|_, seen| *seen >= expired_before));
+                }
+            },
+            heartbeat_interval,
+        );
+
+        let key = Rc::clone(&key);
+        let replica = Rc::clone(&replica);
+        on_cleanup(move || {
+            send(ReplicaMessage::Bye {
+                key: key.to_string(),
+                replica: replica.to_string(),
+            })
+        });
+    }
+
+    UseReplicatedSignalReturn {
+        value: value.into(),
+        set,
+        status: Signal::derive(move || match websocket_ready_state {
+            None => ReplicationStatus::Local,
+            Some(ready_state) => match ready_state.get() {
+                ConnectionReadyState::Open => ReplicationStatus::Connected,
+                ConnectionReadyState::Connecting => ReplicationStatus::Connecting,
+                _ => ReplicationStatus::Disconnected,
+            },
+        }),
+        peer_count: Signal::derive(move || peers.with(|peers| peers.len())),
+        error: error.into(),
+    }
+}
+
+/// Stamp of a change of a last-writer-wins register. Stamps are ordered by time, then counter
+/// and then replica id so that all replicas agree on the latest change.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct LwwStamp {
+    time: u64,
+    counter: u32,
+    replica: String,
+}
+
+impl LwwStamp {
+    /// Returns the stamp of a change that follows `self`. The time never goes backwards even if
+    /// the clock of this replica is behind the one that made the previous change.
+    fn next(&self, replica: &str, now: u64) -> Self {
+        let time = now.max(self.time);
+
+        Self {
+            time,
+            counter: if time == self.time {
+                self.counter + 1
+            } else {
+                0
+            },
+            replica: replica.to_string(),
+        }
+    }
+}
+
+enum ReplicaMessage {
+    Update {
+        key: String,
+        stamp: LwwStamp,
+        payload: String,
+    },
+    Hello {
+        key: String,
+        replica: String,
+    },
+    Bye {
+        key: String,
+        replica: String,
+    },
+}
+
+impl ReplicaMessage {
+    fn key(&self) -> &str {
+        match self {
+            Self::Update { key, .. } | Self::Hello { key, .. } | Self::Bye { key, .. } => key,
+        }
+    }
+
+    fn encode(&self) -> String {
+        match self {
+            Self::Update {
+                key,
+                stamp,
+                payload,
+            } => format!(
+                "u\t{key}\t{}\t{}\t{}\t{payload}",
+                stamp.time, stamp.counter, stamp.replica
+            ),
+            Self::Hello { key, replica } => format!("h\t{key}\t{replica}"),
+            Self::Bye { key, replica } => format!("b\t{key}\t{replica}"),
+        }
+    }
+
+    fn decode(message: &str) -> Option<Self> {
+        let mut fields = message.splitn(6, '\t');
+
+        let kind = fields.next()?;
+        let key = fields.next()?.to_string();
+
+        match kind {
+            "u" => Some(Self::Update {
+                key,
+                stamp: LwwStamp {
+                    time: fields.next()?.parse().ok()?,
+                    counter: fields.next()?.parse().ok()?,
+                    replica: fields.next()?.to_string(),
+                },
+                payload: fields.next()?.to_string(),
+            }),
+            "h" => Some(Self::Hello {
+                key,
+                replica: fields.next()?.to_string(),
+            }),
+            "b" => Some(Self::Bye {
+                key,
+                replica: fields.next()?.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Synchronization status of [`use_replicated_signal`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplicationStatus {
+    /// No WebSocket backend is configured. The value is only replicated to other tabs.
+    Local,
+    /// Connecting to the WebSocket backend.
+    Connecting,
+    /// Connected to the WebSocket backend.
+    Connected,
+    /// The connection to the WebSocket backend has been lost. Changes are sent as soon as it's
+    /// re-established.
+    Disconnected,
+}
+
+/// Options for [`use_replicated_signal_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseReplicatedSignalOptions {
+    /// URL of a WebSocket backend that relays the messages to other clients. Defaults to `None`.
+    #[builder(into)]
+    websocket_url: Option<String>,
+
+    /// Replicate the value to other tabs of the same origin. Defaults to `true`.
+    cross_tab: bool,
+
+    /// Interval in ms in which this replica announces itself to the others.
+    /// Peers that haven't been heard of for three intervals are no longer counted.
+    /// Defaults to `5000`.
+    heartbeat_interval: u64,
+
+    /// How to reconnect to the WebSocket backend. Defaults to unlimited attempts 3 seconds apart.
+    #[builder(skip)]
+    reconnect_policy: Rc<dyn ReconnectPolicy>,
+}
+
+impl UseReplicatedSignalOptions {
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`].
+    pub fn reconnect_policy(self, policy: impl ReconnectPolicy + 'static) -> Self {
+        Self {
+            reconnect_policy: Rc::new(policy),
+            ..self
+        }
+    }
+}
+
+impl Default for UseReplicatedSignalOptions {
+    fn default() -> Self {
+        Self {
+            websocket_url: None,
+            cross_tab: true,
+            heartbeat_interval: 5000,
+            reconnect_policy: Rc::new(
+                BackoffReconnectPolicy::default().limit(ReconnectLimit::Infinite),
+            ),
+        }
+    }
+}
+
+/// Return type of [`use_replicated_signal`].
+pub struct UseReplicatedSignalReturn<T, E, D, SetFn>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+    SetFn: Fn(T) + Clone,
+{
+    /// The current value.
+    pub value: Signal<T>,
+
+    /// Sets the value and replicates it.
+    pub set: SetFn,
+
+    /// Status of the connection to the WebSocket backend.
+    pub status: Signal<ReplicationStatus>,
+
+    /// The number of other replicas (tabs and clients) that are currently present.
+    pub peer_count: Signal<usize>,
+
+    /// The latest error if a value couldn't be encoded or decoded.
+    pub error: Signal<Option<CodecError<E, D>>>,
+}
@@ -0,0 +1,390 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ElementMaybeSignal, StorageType};
+use crate::storage::{use_storage_with_options, UseStorageOptions};
+use crate::utils::FilterOptions;
+use crate::{
+    use_event_listener, use_event_listener_with_options, use_window, UseEventListenerOptions,
+};
+use codee::string::FromToStringCodec;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{keydown, pointerdown, pointermove, pointerup};
+use leptos::*;
+use std::rc::Rc;
+use web_sys::{KeyboardEvent, PointerEvent};
+
+/// Resizable split panes.
+///
+/// The `container` holds two panes with the `handle` in between. Dragging the handle or pressing
+/// the arrow keys while it's focused resizes the panes. The size of the first pane is given in
+/// percent of the container and can be constrained with `min_size` and `max_size`. When the pane
+/// is dragged below the `collapse_threshold` it collapses to `0`.
+///
+/// The handle gets the ARIA attributes of a focusable
+/// [window splitter](https://www.w3.org/WAI/ARIA/apg/patterns/windowsplitter/).
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_resize_handle_with_options, UseResizeHandleOptions, UseResizeHandleReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let container = create_node_ref::<Div>();
+/// let handle = create_node_ref::<Div>();
+///
+/// let UseResizeHandleReturn {
+///     first_style,
+///     second_style,
+///     ..
+/// } = use_resize_handle_with_options(
+///     container,
+///     handle,
+///     UseResizeHandleOptions::default()
+///         .min_size(20.0)
+///         .max_size(80.0)
+///         .collapse_threshold(10.0)
+///         .storage_key("sidebar-size"),
+/// );
+///
+/// view! {
+///     <div node_ref=container style="display: flex; height: 100%">
+///         <aside style=first_style>"Sidebar"</aside>
+///         <div node_ref=handle style="width: 4px; cursor: col-resize"></div>
+///         <main style=second_style>"Content"</main>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ## Keyboard
+///
+/// - Arrow keys resize by `keyboard_step` percent.
+/// - `Home` and `End` resize to `min_size` and `max_size`.
+/// - `Enter` collapses or expands the first pane if `collapse_threshold` is set.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the size is the `initial_size` and nothing is persisted.
+pub fn use_resize_handle<ContainerEl, ContainerT, HandleEl, HandleT>(
+    container: ContainerEl,
+    handle: HandleEl,
+) -> UseResizeHandleReturn<impl Fn() + Clone, impl Fn() + Clone>
+where
+    ContainerEl: Into<ElementMaybeSignal<ContainerT, web_sys::Element>>,
+    ContainerT: Into<web_sys::Element> + Clone + 'static,
+    HandleEl: Into<ElementMaybeSignal<HandleT, web_sys::Element>>,
+    HandleT: Into<web_sys::Element> + Clone + 'static,
+{
+    use_resize_handle_with_options(container, handle, UseResizeHandleOptions::default())
+}
+
+/// Version of [`use_resize_handle`] that takes a `UseResizeHandleOptions`. See [`use_resize_handle`] for how to use.
+pub fn use_resize_handle_with_options<ContainerEl, ContainerT, HandleEl, HandleT>(
+    container: ContainerEl,
+    handle: HandleEl,
+    options: UseResizeHandleOptions,
+) -> UseResizeHandleReturn<impl Fn() + Clone, impl Fn() + Clone>
+where
+    ContainerEl: Into<ElementMaybeSignal<ContainerT, web_sys::Element>>,
+    ContainerT: Into<web_sys::Element> + Clone + 'static,
+    HandleEl: Into<ElementMaybeSignal<HandleT, web_sys::Element>>,
+    HandleT: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseResizeHandleOptions {
+        orientation,
+        initial_size,
+        min_size,
+        max_size,
+        collapse_threshold,
+        keyboard_step,
+        storage_key,
+        on_resize,
+    } = options;
+
+    let container = container.into();
+    let handle = handle.into();
+
+    let (size, set_size) = match storage_key {
+        Some(storage_key) => {
+            let (size, set_size, _) = use_storage_with_options::<f64, FromToStringCodec>(
+                StorageType::Local,
+                storage_key,
+                UseStorageOptions::default()
+                    .initial_value(initial_size)
+                    .filter(FilterOptions::debounce(200.0)),
+            );
+            (size, set_size)
+        }
+        None => {
+            let (size, set_size) = create_signal(initial_size);
+            (size.into(), set_size)
+        }
+    };
+
+    let (is_dragging, set_dragging) = create_signal(false);
+
+    // size before collapsing so expanding restores it
+    let expanded_size = store_value(initial_size.max(min_size));
+
+    // sets the size without applying the constraints
+    let apply_size = move |new_size: f64| {
+        if new_size != size.get_untracked() {
+            set_size.set(new_size);
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_resize(new_size);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    };
+
+    let resize = {
+        let apply_size = apply_size.clone();
+
+        move |new_size: f64| {
+            apply_size(match collapse_threshold {
+                Some(threshold) if new_size < threshold => 0.0,
+                _ => new_size.clamp(min_size, max_size),
+            })
+        }
+    };
+
+    let collapse = move || {
+        let current = size.get_untracked();
+        if current > 0.0 {
+            expanded_size.set_value(current);
+            apply_size(0.0);
+        }
+    };
+
+    let expand = {
+        let resize = resize.clone();
+
+        move || {
+            if size.get_untracked() == 0.0 {
+                resize(expanded_size.get_value());
+            }
+        }
+    };
+
+    let handle_target: Signal<Option<web_sys::EventTarget>> = {
+        let handle = handle.clone();
+        Signal::derive(move || handle.get().map(|handle| handle.into().into()))
+    };
+
+    let _ = use_event_listener(handle_target, pointerdown, move |event: PointerEvent| {
+        if event.button() != 0 {
+            return;
+        }
+
+        event.prevent_default();
+        set_dragging.set(true);
+    });
+
+    let _ = use_event_listener_with_options(
+        use_window(),
+        pointermove,
+        {
+            let resize = resize.clone();
+
+            move |event: PointerEvent| {
+                if !is_dragging.get_untracked() {
+                    return;
+                }
+
+                let Some(container) = container.get_untracked() else {
+                    return;
+                };
+
+                let rect = container.into().get_bounding_client_rect();
+
+                let (position, length) = match orientation {
+                    SplitOrientation::Horizontal => {
+                        (event.client_x() as f64 - rect.left(), rect.width())
+                    }
+                    SplitOrientation::Vertical => {
+                        (event.client_y() as f64 - rect.top(), rect.height())
+                    }
+                };
+
+                if length > 0.0 {
+                    resize(position / length * 100.0);
+                }
+            }
+        },
+        UseEventListenerOptions::default().passive(true),
+    );
+
+    let _ = use_event_listener(use_window(), pointerup, move |_| {
+        if is_dragging.get_untracked() {
+            set_dragging.set(false);
+        }
+    });
+
+    let _ = use_event_listener(handle_target, keydown, {
+        let collapse = collapse.clone();
+        let expand = expand.clone();
+        let resize = resize.clone();
+
+        move |event: KeyboardEvent| {
+            let current = size.get_untracked();
+
+            let (decrease, increase) = match orientation {
+                SplitOrientation::Horizontal => ("ArrowLeft", "ArrowRight"),
+                SplitOrientation::Vertical => ("ArrowUp", "ArrowDown"),
+            };
+
+            match event.key().as_str() {
+                key if key == decrease => resize(current - keyboard_step),
+                key if key == increase => resize(current.max(min_size) + keyboard_step),
+                "Home" => resize(min_size),
+                "End" => resize(max_size),
+                "Enter" if collapse_threshold.is_some() => {
+                    if current == 0.0 {
+                        expand();
+                    } else {
+                        collapse();
+                    }
+                }
+                _ => return,
+            }
+
+            event.prevent_default();
+        }
+    });
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let _ = watch(
+            move || (handle.get(), size.get()),
+            move |(handle, size), _, _| {
+                if let Some(handle) = handle {
+                    let handle: web_sys::Element = handle.clone().into();
+
+                    if !handle.has_attribute("tabindex") {
+                        let _ = handle.set_attribute("tabindex", "0");
+                    }
+                    let _ = handle.set_attribute("role", "separator");
+                    let _ = handle.set_attribute(
+                        "aria-orientation",
+                        match orientation {
+                            // the separator line is perpendicular to the direction of the split
+                            SplitOrientation::Horizontal => "vertical",
+                            SplitOrientation::Vertical => "horizontal",
+                        },
+                    );
+                    let _ = handle.set_attribute("aria-valuemin", &min_size.to_string());
+                    let _ = handle.set_attribute("aria-valuemax", &max_size.to_string());
+                    let _ = handle.set_attribute("aria-valuenow", &size.round().to_string());
+                }
+            },
+            true,
+        );
+    }
+
+    let basis = move |size: f64| format!("flex: 0 0 {size}%; overflow: auto;");
+
+    UseResizeHandleReturn {
+        size,
+        set_size,
+        is_dragging: is_dragging.into(),
+        is_collapsed: Signal::derive(move || size.get() == 0.0),
+        first_style: Signal::derive(move || basis(size.get())),
+        second_style: Signal::derive(move || "flex: 1 1 0%; overflow: auto;".to_string()),
+        collapse,
+        expand,
+    }
+}
+
+/// In which direction the panes of [`use_resize_handle`] are split.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SplitOrientation {
+    /// Panes are side by side and the handle resizes their widths.
+    #[default]
+    Horizontal,
+    /// Panes are on top of each other and the handle resizes their heights.
+    Vertical,
+}
+
+/// Options for [`use_resize_handle_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseResizeHandleOptions {
+    /// Direction of the split. Defaults to `SplitOrientation::Horizontal`.
+    orientation: SplitOrientation,
+
+    /// Initial size of the first pane in percent if there is none in storage. Defaults to `50.0`.
+    initial_size: f64,
+
+    /// Minimum size of the first pane in percent. Defaults to `0.0`.
+    min_size: f64,
+
+    /// Maximum size of the first pane in percent. Defaults to `100.0`.
+    max_size: f64,
+
+    /// When the first pane is resized below this size in percent it collapses to `0`.
+    /// Defaults to `None`.
+    #[builder(into)]
+    collapse_threshold: Option<f64>,
+
+    /// Percent by which the arrow keys resize the panes. Defaults to `5.0`.
+    keyboard_step: f64,
+
+    /// If set, the size is persisted in local storage under this key. Defaults to `None`.
+    #[builder(into)]
+    storage_key: Option<String>,
+
+    /// Called with the new size of the first pane in percent when it is resized.
+    on_resize: Rc<dyn Fn(f64)>,
+}
+
+impl Default for UseResizeHandleOptions {
+    fn default() -> Self {
+        Self {
+            orientation: SplitOrientation::default(),
+            initial_size: 50.0,
+            min_size: 0.0,
+            max_size: 100.0,
+            collapse_threshold: None,
+            keyboard_step: 5.0,
+            storage_key: None,
+            on_resize: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_resize_handle`].
+pub struct UseResizeHandleReturn<CollapseFn, ExpandFn>
+where
+    CollapseFn: Fn() + Clone,
+    ExpandFn: Fn() + Clone,
+{
+    /// Size of the first pane in percent of the container
+    pub size: Signal<f64>,
+
+    /// Sets the size of the first pane without applying the constraints
+    pub set_size: WriteSignal<f64>,
+
+    /// `true` while the handle is dragged
+    pub is_dragging: Signal<bool>,
+
+    /// `true` if the first pane is collapsed
+    pub is_collapsed: Signal<bool>,
+
+    /// Style attribute for the first pane, `"flex: 0 0 {size}%; overflow: auto;"`
+    pub first_style: Signal<String>,
+
+    /// Style attribute for the second pane that takes the remaining space
+    pub second_style: Signal<String>,
+
+    /// Collapses the first pane
+    pub collapse: CollapseFn,
+
+    /// Restores the size the first pane had before it was collapsed
+    pub expand: ExpandFn,
+}
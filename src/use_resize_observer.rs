@@ -5,9 +5,7 @@ use leptos::*;
 
 cfg_if! { if #[cfg(not(feature = "ssr"))] {
     use crate::use_supported;
-    use std::cell::RefCell;
-    use std::rc::Rc;
-    use wasm_bindgen::prelude::*;
+    use crate::utils::{ObserverPool, RESIZE_OBSERVER_POOL};
 }}
 
 /// Reports changes to the dimensions of an Element's content or the border-box.
@@ -44,6 +42,17 @@ cfg_if! { if #[cfg(not(feature = "ssr"))] {
 /// # }
 /// ```
 ///
+/// ## Shared Observers
+///
+/// All calls of `use_resize_observer` with the same `box_` option share one `ResizeObserver`
+/// instance. The entries it reports are dispatched to the callbacks of the hooks that observe the
+/// respective elements. This keeps the overhead low even if hundreds of elements are observed.
+///
+/// Because of this the `ResizeObserver` that is passed to the callback is shared as well. Please
+/// don't call `disconnect()` on it but use the returned `stop` function instead. Or use
+/// [`use_resize_observer_with_handle`] whose callback receives a [`ResizeObserverHandle`]
+/// instead. Calling `disconnect()` on it only stops observing the targets of this hook call.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this amounts to a no-op.
@@ -64,16 +73,78 @@ where
 }
 
 /// Version of [`use_resize_observer`] that takes a `web_sys::ResizeObserverOptions`. See [`use_resize_observer`] for how to use.
-#[cfg_attr(feature = "ssr", allow(unused_variables, unused_mut))]
 pub fn use_resize_observer_with_options<El, T, F>(
     target: El, // TODO : multiple elements?
-    mut callback: F,
+    callback: F,
     options: UseResizeObserverOptions,
 ) -> UseResizeObserverReturn<impl Fn() + Clone>
 where
     El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
     T: Into<web_sys::Element> + Clone + 'static,
     F: FnMut(Vec<web_sys::ResizeObserverEntry>, web_sys::ResizeObserver) + 'static,
+{
+    let mut callback = callback;
+
+    use_pooled_resize_observer(
+        target,
+        move |entries, observer, _| callback(entries, observer),
+        options,
+    )
+}
+
+/// Version of [`use_resize_observer_with_options`] whose callback receives a
+/// [`ResizeObserverHandle`] instead of the shared `ResizeObserver`.
+///
+/// ```
+/// # use leptos::{html::Div, *};
+/// # use leptos_use::{use_resize_observer_with_handle, UseResizeObserverOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// use_resize_observer_with_handle(
+///     el,
+///     move |_, handle| {
+///         // only observe the first resize
+///         handle.disconnect();
+///     },
+///     UseResizeObserverOptions::default(),
+/// );
+/// #
+/// # view! { <div node_ref=el></div> }
+/// # }
+/// ```
+pub fn use_resize_observer_with_handle<El, T, F>(
+    target: El,
+    callback: F,
+    options: UseResizeObserverOptions,
+) -> UseResizeObserverReturn<impl Fn() + Clone>
+where
+    El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+    F: FnMut(Vec<web_sys::ResizeObserverEntry>, ResizeObserverHandle) + 'static,
+{
+    let mut callback = callback;
+
+    use_pooled_resize_observer(
+        target,
+        move |entries, _, handle| callback(entries, handle),
+        options,
+    )
+}
+
+#[cfg_attr(feature = "ssr", allow(unused_variables))]
+fn use_pooled_resize_observer<El, T, F>(
+    target: El,
+    callback: F,
+    options: UseResizeObserverOptions,
+) -> UseResizeObserverReturn<impl Fn() + Clone>
+where
+    El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+    F: FnMut(Vec<web_sys::ResizeObserverEntry>, web_sys::ResizeObserver, ResizeObserverHandle)
+        + 'static,
 {
     #[cfg(feature = "ssr")]
     {
@@ -87,72 +158,47 @@ where
     {
         use crate::js;
 
-        let closure_js = Closure::<dyn FnMut(js_sys::Array, web_sys::ResizeObserver)>::new(
-            move |entries: js_sys::Array, observer| {
-                #[cfg(debug_assertions)]
-                let prev = SpecialNonReactiveZone::enter();
-
-                callback(
-                    entries
-                        .to_vec()
-                        .into_iter()
-                        .map(|v| v.unchecked_into::<web_sys::ResizeObserverEntry>())
-                        .collect(),
-                    observer,
-                );
-
-                #[cfg(debug_assertions)]
-                SpecialNonReactiveZone::exit(prev);
-            },
-        )
-        .into_js_value();
-
-        let observer: Rc<RefCell<Option<web_sys::ResizeObserver>>> = Rc::new(RefCell::new(None));
-
         let is_supported = use_supported(|| js!("ResizeObserver" in &window()));
 
-        let cleanup = {
-            let observer = Rc::clone(&observer);
+        let mut callback = callback;
+        let subscriber = ObserverPool::subscribe(
+            &RESIZE_OBSERVER_POOL,
+            move |entries, observer, subscriber| {
+                callback(entries, observer, ResizeObserverHandle { subscriber })
+            },
+        );
 
-            move || {
-                let mut observer = observer.borrow_mut();
-                if let Some(o) = observer.as_ref() {
-                    o.disconnect();
-                    *observer = None;
-                }
-            }
-        };
+        let cleanup = move || ObserverPool::unobserve_all(&RESIZE_OBSERVER_POOL, subscriber);
 
         let targets = target.into();
 
-        let stop_watch = {
-            let cleanup = cleanup.clone();
-
-            watch(
-                move || targets.get(),
-                move |targets, _, _| {
-                    cleanup();
-
-                    if is_supported.get_untracked() && !targets.is_empty() {
-                        let obs = web_sys::ResizeObserver::new(
-                            closure_js.clone().as_ref().unchecked_ref(),
-                        )
-                        .expect("failed to create ResizeObserver");
-
-                        for target in targets.iter().flatten() {
-                            let target: web_sys::Element = target.clone().into();
-                            obs.observe_with_options(&target, &options.clone().into());
-                        }
-                        observer.replace(Some(obs));
+        // one shared observer per box option
+        let key = format!("{:?}", options.box_);
+
+        let stop_watch = watch(
+            move || targets.get(),
+            move |targets, _, _| {
+                cleanup();
+
+                if is_supported.get_untracked() && !targets.is_empty() {
+                    for target in targets.iter().flatten() {
+                        let target: web_sys::Element = target.clone().into();
+                        ObserverPool::observe(
+                            &RESIZE_OBSERVER_POOL,
+                            &key,
+                            || options.clone().into(),
+                            subscriber,
+                            &target,
+                        );
                     }
-                },
-                true,
-            )
-        };
+                }
+            },
+            true,
+        );
 
         let stop = move || {
-            cleanup();
             stop_watch();
+            ObserverPool::unsubscribe(&RESIZE_OBSERVER_POOL, subscriber);
         };
 
         on_cleanup(stop.clone());
@@ -180,6 +226,23 @@ impl From<UseResizeObserverOptions> for web_sys::ResizeObserverOptions {
     }
 }
 
+/// Handle to the shared `ResizeObserver` that is passed to the callback of
+/// [`use_resize_observer_with_handle`].
+#[derive(Clone, Debug)]
+pub struct ResizeObserverHandle {
+    #[cfg(not(feature = "ssr"))]
+    subscriber: usize,
+}
+
+impl ResizeObserverHandle {
+    /// Stops observing the targets of the hook call this handle belongs to. Other hook calls
+    /// that share the same `ResizeObserver` are not affected.
+    pub fn disconnect(&self) {
+        #[cfg(not(feature = "ssr"))]
+        ObserverPool::unobserve_all(&RESIZE_OBSERVER_POOL, self.subscriber);
+    }
+}
+
 /// The return value of [`use_resize_observer`].
 pub struct UseResizeObserverReturn<F: Fn() + Clone> {
     /// Whether the browser supports the ResizeObserver API
@@ -0,0 +1,519 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ConnectionReadyState;
+use crate::{use_event_listener, UseRtcPeerConnectionReturn};
+use codee::{
+    CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    Event, MessageEvent, RtcDataChannel, RtcDataChannelEvent, RtcDataChannelInit,
+    RtcDataChannelState, RtcDataChannelType,
+};
+
+/// Creating and managing a WebRTC [RTCDataChannel](https://developer.mozilla.org/en-US/docs/Web/API/RTCDataChannel)
+/// of a connection created with [`crate::use_rtc_peer_connection`].
+///
+/// ## Usage
+///
+/// Values are (en)decoded via the given codec. You can use any of the codecs, string or binary.
+/// Messages that are sent while the channel isn't open are dropped.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_rtc_data_channel, use_rtc_peer_connection, UseRtcDataChannelReturn};
+/// # use leptos_use::core::ConnectionReadyState;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let peer = use_rtc_peer_connection();
+///
+/// let UseRtcDataChannelReturn {
+///     ready_state,
+///     message,
+///     send,
+///     ..
+/// } = use_rtc_data_channel::<String, FromToStringCodec>(peer, "chat");
+///
+/// let connected = move || ready_state.get() == ConnectionReadyState::Open;
+///
+/// view! {
+///     <p>{message}</p>
+///     <button on:click=move |_| send(&"Hello!".to_string()) disabled=move || !connected()>
+///         "Send"
+///     </button>
+/// }
+/// # }
+/// ```
+///
+/// See [`crate::use_rtc_peer_connection`] for how to connect the peers.
+///
+/// ### Negotiation
+///
+/// By default the channel is created by this peer. That has to happen before the offer is
+/// created so the channel is part of it. The other peer doesn't create the channel but accepts
+/// the one with the same label.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{
+/// #     use_rtc_data_channel_with_options, use_rtc_peer_connection, DataChannelNegotiation,
+/// #     UseRtcDataChannelOptions,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let peer = use_rtc_peer_connection();
+///
+/// let chat = use_rtc_data_channel_with_options::<String, FromToStringCodec>(
+///     peer,
+///     "chat",
+///     UseRtcDataChannelOptions::default().negotiation(DataChannelNegotiation::Accept),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// With [`DataChannelNegotiation::Negotiated`] both peers create the channel with the same id
+/// themselves.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the channel is never opened. `ready_state` is always `Closed` and `send` and
+/// `close` do nothing.
+pub fn use_rtc_data_channel<T, C>(
+    peer: UseRtcPeerConnectionReturn,
+    label: &str,
+) -> UseRtcDataChannelReturn<T, impl Fn() + Clone + 'static, impl Fn(&T) + Clone + 'static>
+where
+    T: 'static,
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    use_rtc_data_channel_with_options::<T, C>(peer, label, UseRtcDataChannelOptions::default())
+}
+
+/// Version of [`use_rtc_data_channel`] that takes a `UseRtcDataChannelOptions`. See [`use_rtc_data_channel`] for how to use.
+#[allow(clippy::type_complexity)]
+pub fn use_rtc_data_channel_with_options<T, C>(
+    peer: UseRtcPeerConnectionReturn,
+    label: &str,
+    options: UseRtcDataChannelOptions<
+        T,
+        HybridCoderError<<C as Encoder<T>>::Error>,
+        HybridCoderError<<C as Decoder<T>>::Error>,
+    >,
+) -> UseRtcDataChannelReturn<T, impl Fn() + Clone + 'static, impl Fn(&T) + Clone + 'static>
+where
+    T: 'static,
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    let UseRtcDataChannelOptions {
+        on_open,
+        on_message,
+        on_error,
+        on_close,
+        ordered,
+        max_retransmits,
+        max_packet_life_time,
+        negotiation,
+    } = options;
+
+    let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
+    let (message, set_message) = create_signal(None);
+    let channel_ref: StoredValue<Option<RtcDataChannel>> = store_value(None);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let unmounted = Rc::new(Cell::new(false));
+
+        let attach = {
+            let unmounted = Rc::clone(&unmounted);
+            let on_error = Rc::clone(&on_error);
+
+            move |channel: RtcDataChannel| {
+                channel.set_binary_type(RtcDataChannelType::Arraybuffer);
+                set_ready_state.set(data_channel_ready_state(&channel));
+
+                // onopen handler
+                {
+                    let unmounted = Rc::clone(&unmounted);
+                    let on_open = Rc::clone(&on_open);
+
+                    let onopen_closure = Closure::wrap(Box::new(move |_: Event| {
+                        if unmounted.get() {
+                            return;
+                        }
+
+                        set_ready_state.set(ConnectionReadyState::Open);
+
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_open();
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+                    })
+                        as Box<dyn FnMut(Event)>);
+                    channel.set_onopen(Some(onopen_closure.as_ref().unchecked_ref()));
+                    onopen_closure.forget();
+                }
+
+                // onmessage handler
+                {
+                    let unmounted = Rc::clone(&unmounted);
+                    let on_message = Rc::clone(&on_message);
+                    let on_error = Rc::clone(&on_error);
+
+                    let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
+                        if unmounted.get() {
+                            return;
+                        }
+
+                        let decoded =
+                            if let Ok(array_buffer) = e.data().dyn_into::<js_sys::ArrayBuffer>() {
+                                C::decode_bin(
+                                    js_sys::Uint8Array::new(&array_buffer).to_vec().as_slice(),
+                                )
+                            } else if let Some(txt) = e.data().as_string() {
+                                C::decode_str(&txt)
+                            } else {
+                                return;
+                            };
+
+                        match decoded {
+                            Ok(val) => {
+                                #[cfg(debug_assertions)]
+                                let prev = SpecialNonReactiveZone::enter();
+
+                                on_message(&val);
+
+                                #[cfg(debug_assertions)]
+                                SpecialNonReactiveZone::exit(prev);
+
+                                set_message.set(Some(val));
+                            }
+                            Err(err) => {
+                                on_error(CodecError::Decode(err).into());
+                            }
+                        }
+                    })
+                        as Box<dyn FnMut(MessageEvent)>);
+                    channel.set_onmessage(Some(onmessage_closure.as_ref().unchecked_ref()));
+                    onmessage_closure.forget();
+                }
+
+                // onerror handler
+                {
+                    let unmounted = Rc::clone(&unmounted);
+                    let on_error = Rc::clone(&on_error);
+
+                    let onerror_closure = Closure::wrap(Box::new(move |e: Event| {
+                        if unmounted.get() {
+                            return;
+                        }
+
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_error(UseRtcDataChannelError::Event(e));
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+                    })
+                        as Box<dyn FnMut(Event)>);
+                    channel.set_onerror(Some(onerror_closure.as_ref().unchecked_ref()));
+                    onerror_closure.forget();
+                }
+
+                // onclose handler
+                {
+                    let unmounted = Rc::clone(&unmounted);
+                    let on_close = Rc::clone(&on_close);
+
+                    let onclose_closure = Closure::wrap(Box::new(move |_: Event| {
+                        if unmounted.get() {
+                            return;
+                        }
+
+                        set_ready_state.set(ConnectionReadyState::Closed);
+
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_close();
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+                    })
+                        as Box<dyn FnMut(Event)>);
+                    channel.set_onclose(Some(onclose_closure.as_ref().unchecked_ref()));
+                    onclose_closure.forget();
+                }
+
+                channel_ref.set_value(Some(channel));
+            }
+        };
+
+        if let Some(pc) = peer.peer_connection() {
+            match negotiation {
+                DataChannelNegotiation::Create | DataChannelNegotiation::Negotiated(_) => {
+                    let mut init = RtcDataChannelInit::new();
+                    init.ordered(ordered);
+                    if let Some(max_retransmits) = max_retransmits {
+                        init.max_retransmits(max_retransmits);
+                    }
+                    if let Some(max_packet_life_time) = max_packet_life_time {
+                        init.max_packet_life_time(max_packet_life_time);
+                    }
+                    if let DataChannelNegotiation::Negotiated(id) = negotiation {
+                        init.negotiated(true).id(id);
+                    }
+
+                    attach(pc.create_data_channel_with_data_channel_dict(label, &init));
+                }
+                DataChannelNegotiation::Accept => {
+                    let label = label.to_string();
+                    set_ready_state.set(ConnectionReadyState::Connecting);
+
+                    let _ = use_event_listener(
+                        pc,
+                        Custom::<RtcDataChannelEvent>::new("datachannel"),
+                        move |e| {
+                            let channel = e.channel();
+
+                            if channel.label() == label && channel_ref.with_value(Option::is_none) {
+                                attach(channel);
+                            }
+                        },
+                    );
+                }
+            }
+        }
+
+        on_cleanup(move || {
+            unmounted.set(true);
+
+            channel_ref.try_with_value(|channel| {
+                if let Some(channel) = channel {
+                    channel.close();
+                }
+            });
+        });
+    }
+
+    let close = move || {
+        #[cfg(not(feature = "ssr"))]
+        if let Some(channel) = channel_ref.get_value() {
+            channel.close();
+            set_ready_state.set(data_channel_ready_state(&channel));
+        }
+    };
+
+    let send = {
+        let on_error = Rc::clone(&on_error);
+
+        move |value: &T| {
+            if ready_state.get_untracked() != ConnectionReadyState::Open {
+                return;
+            }
+
+            let Some(channel) = channel_ref.get_value() else {
+                return;
+            };
+
+            let result = if C::is_binary() {
+                match C::encode_bin(value) {
+                    Ok(val) => channel.send_with_u8_array(&val),
+                    Err(err) => {
+                        on_error(CodecError::Encode(err).into());
+                        return;
+                    }
+                }
+            } else {
+                match C::encode_str(value) {
+                    Ok(val) => channel.send_with_str(&val),
+                    Err(err) => {
+                        on_error(CodecError::Encode(err).into());
+                        return;
+                    }
+                }
+            };
+
+            if let Err(err) = result {
+                on_error(UseRtcDataChannelError::Send(err));
+            }
+        }
+    };
+
+    UseRtcDataChannelReturn {
+        ready_state: ready_state.into(),
+        message: message.into(),
+        close,
+        send,
+        channel: channel_ref,
+    }
+}
+
+fn data_channel_ready_state(channel: &RtcDataChannel) -> ConnectionReadyState {
+    match channel.ready_state() {
+        RtcDataChannelState::Connecting => ConnectionReadyState::Connecting,
+        RtcDataChannelState::Open => ConnectionReadyState::Open,
+        RtcDataChannelState::Closing => ConnectionReadyState::Closing,
+        _ => ConnectionReadyState::Closed,
+    }
+}
+
+/// Options for [`use_rtc_data_channel_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseRtcDataChannelOptions<T, E, D>
+where
+    T: ?Sized,
+{
+    /// Called when the channel has been opened.
+    on_open: Rc<dyn Fn()>,
+    /// Message callback for typed message decoded by codec.
+    #[builder(skip)]
+    on_message: Rc<dyn Fn(&T)>,
+    /// Error callback.
+    #[builder(skip)]
+    on_error: Rc<dyn Fn(UseRtcDataChannelError<E, D>)>,
+    /// Called when the channel has been closed.
+    on_close: Rc<dyn Fn()>,
+    /// If `true` messages are delivered in the order they were sent. Defaults to `true`.
+    ordered: bool,
+    /// Maximum number of times a message is retransmitted if it's lost.
+    /// Defaults to `None` which retransmits until it's delivered.
+    #[builder(into)]
+    max_retransmits: Option<u16>,
+    /// Maximum time in ms a message is retransmitted if it's lost.
+    /// Defaults to `None` which retransmits until it's delivered.
+    #[builder(into)]
+    max_packet_life_time: Option<u16>,
+    /// How the channel is established. Defaults to [`DataChannelNegotiation::Create`].
+    negotiation: DataChannelNegotiation,
+}
+
+impl<T: ?Sized, E, D> UseRtcDataChannelOptions<T, E, D> {
+    /// Error callback.
+    pub fn on_error<F>(self, handler: F) -> Self
+    where
+        F: Fn(UseRtcDataChannelError<E, D>) + 'static,
+    {
+        Self {
+            on_error: Rc::new(handler),
+            ..self
+        }
+    }
+
+    /// Message callback for typed message decoded by codec.
+    pub fn on_message<F>(self, handler: F) -> Self
+    where
+        F: Fn(&T) + 'static,
+    {
+        Self {
+            on_message: Rc::new(handler),
+            ..self
+        }
+    }
+}
+
+impl<T: ?Sized, E, D> Default for UseRtcDataChannelOptions<T, E, D> {
+    fn default() -> Self {
+        Self {
+            on_open: Rc::new(|| {}),
+            on_message: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
+            on_close: Rc::new(|| {}),
+            ordered: true,
+            max_retransmits: None,
+            max_packet_life_time: None,
+            negotiation: DataChannelNegotiation::default(),
+        }
+    }
+}
+
+/// How a data channel of [`use_rtc_data_channel`] is established.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DataChannelNegotiation {
+    /// This peer creates the channel and the other peer accepts it
+    #[default]
+    Create,
+    /// The other peer creates the channel and this peer waits for it
+    Accept,
+    /// Both peers create the channel with this id themselves
+    Negotiated(u16),
+}
+
+/// Return type of [`use_rtc_data_channel`].
+pub struct UseRtcDataChannelReturn<T, CloseFn, SendFn>
+where
+    T: 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    /// The current state of the channel.
+    pub ready_state: Signal<ConnectionReadyState>,
+    /// Latest message received.
+    pub message: Signal<Option<T>>,
+    /// Closes the channel
+    pub close: CloseFn,
+    /// Sends a message. It's dropped if the channel isn't open.
+    pub send: SendFn,
+
+    channel: StoredValue<Option<RtcDataChannel>>,
+}
+
+impl<T, CloseFn, SendFn> Clone for UseRtcDataChannelReturn<T, CloseFn, SendFn>
+where
+    T: 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ready_state: self.ready_state,
+            message: self.message,
+            close: self.close.clone(),
+            send: self.send.clone(),
+            channel: self.channel,
+        }
+    }
+}
+
+impl<T, CloseFn, SendFn> UseRtcDataChannelReturn<T, CloseFn, SendFn>
+where
+    T: 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    /// The underlying `RTCDataChannel` once it has been created or accepted
+    pub fn channel(&self) -> Option<RtcDataChannel> {
+        self.channel.get_value()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum UseRtcDataChannelError<E, D> {
+    #[error("RTCDataChannel error event")]
+    Event(Event),
+    #[error("Failed to send RTCDataChannel message: {0:?}")]
+    Send(JsValue),
+    #[error("RTCDataChannel codec error: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
@@ -0,0 +1,536 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::js_fut;
+use crate::use_event_listener;
+use default_struct_builder::DefaultBuilder;
+use js_sys::Array;
+use leptos::ev::Custom;
+use leptos::*;
+use std::future::Future;
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    Event, RtcConfiguration, RtcIceCandidate, RtcIceCandidateInit, RtcIceConnectionState,
+    RtcIceGatheringState, RtcPeerConnection, RtcPeerConnectionIceEvent, RtcPeerConnectionState,
+    RtcSdpType, RtcSessionDescriptionInit, RtcSignalingState,
+};
+
+/// Creating and managing a WebRTC [RTCPeerConnection](https://developer.mozilla.org/en-US/docs/Web/API/RTCPeerConnection).
+///
+/// The connection is created right away and closed when the component is unmounted. Its states
+/// are exposed as signals. Data is exchanged with the other peer through data channels, see
+/// [`crate::use_rtc_data_channel`].
+///
+/// ## Usage
+///
+/// WebRTC doesn't define how the peers find each other. The offer, the answer and the ICE
+/// candidates have to be exchanged through a signaling server of your choice. This example uses
+/// [`crate::use_websocket`] for that.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::{FromToStringCodec, JsonSerdeCodec};
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::{
+/// #     use_rtc_data_channel, use_rtc_peer_connection_with_options, use_websocket, IceCandidate,
+/// #     IceServer, SessionDescription, UseRtcDataChannelReturn, UseRtcPeerConnectionOptions,
+/// #     UseWebSocketReturn,
+/// # };
+/// #
+/// #[derive(Serialize, Deserialize, Clone)]
+/// enum Signaling {
+///     Offer(String),
+///     Answer(String),
+///     Candidate {
+///         candidate: String,
+///         sdp_mid: Option<String>,
+///         sdp_m_line_index: Option<u16>,
+///     },
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn { message, send, .. } =
+///     use_websocket::<Signaling, JsonSerdeCodec>("wss://signaling.example.com/room/42");
+///
+/// let peer = use_rtc_peer_connection_with_options(
+///     UseRtcPeerConnectionOptions::default()
+///         .ice_servers(vec![IceServer::new("stun:stun.l.google.com:19302")])
+///         .on_ice_candidate({
+///             let send = send.clone();
+///             move |candidate: IceCandidate| {
+///                 send(&Signaling::Candidate {
+///                     candidate: candidate.candidate,
+///                     sdp_mid: candidate.sdp_mid,
+///                     sdp_m_line_index: candidate.sdp_m_line_index,
+///                 })
+///             }
+///         }),
+/// );
+///
+/// // has to be created before the offer so it's part of it
+/// let UseRtcDataChannelReturn { message: chat_message, send: send_chat, .. } =
+///     use_rtc_data_channel::<String, FromToStringCodec>(peer, "chat");
+///
+/// let _ = watch(
+///     move || message.get(),
+///     {
+///         let send = send.clone();
+///
+///         move |message, _, _| {
+///             let Some(message) = message.clone() else { return };
+///             let send = send.clone();
+///
+///             spawn_local(async move {
+///                 match message {
+///                     Signaling::Offer(sdp) => {
+///                         let offer = SessionDescription::offer(sdp);
+///                         if let Ok(answer) = peer.create_answer(&offer).await {
+///                             send(&Signaling::Answer(answer.sdp));
+///                         }
+///                     }
+///                     Signaling::Answer(sdp) => {
+///                         let answer = SessionDescription::answer(sdp);
+///                         let _ = peer.set_remote_description(&answer).await;
+///                     }
+///                     Signaling::Candidate { candidate, sdp_mid, sdp_m_line_index } => {
+///                         let candidate = IceCandidate { candidate, sdp_mid, sdp_m_line_index };
+///                         let _ = peer.add_ice_candidate(&candidate).await;
+///                     }
+///                 }
+///             });
+///         }
+///     },
+///     false,
+/// );
+///
+/// let call = move |_| {
+///     let send = send.clone();
+///
+///     spawn_local(async move {
+///         if let Ok(offer) = peer.create_offer().await {
+///             send(&Signaling::Offer(offer.sdp));
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=call>"Call"</button>
+///     <p>"Connection: " {move || format!("{:?}", peer.connection_state.get())}</p>
+///     <p>"Other peer: " {chat_message}</p>
+///     <button on:click=move |_| send_chat(&"Hello!".to_string())>"Say hello"</button>
+/// }
+/// # }
+/// ```
+///
+/// ICE candidates that arrive before the remote description has been set are buffered and
+/// added once it is set.
+///
+/// ## Server-Side Rendering
+///
+/// On the server there is no peer connection. All states are `Closed` (`New` for the ICE
+/// gathering state) and the methods return [`RtcPeerConnectionError::Closed`].
+pub fn use_rtc_peer_connection() -> UseRtcPeerConnectionReturn {
+    use_rtc_peer_connection_with_options(UseRtcPeerConnectionOptions::default())
+}
+
+/// Version of [`use_rtc_peer_connection`] that takes a `UseRtcPeerConnectionOptions`. See [`use_rtc_peer_connection`] for how to use.
+pub fn use_rtc_peer_connection_with_options(
+    options: UseRtcPeerConnectionOptions,
+) -> UseRtcPeerConnectionReturn {
+    let UseRtcPeerConnectionOptions {
+        ice_servers,
+        on_ice_candidate,
+    } = options;
+
+    let (connection_state, set_connection_state) = create_signal(RtcPeerConnectionState::Closed);
+    let (ice_connection_state, set_ice_connection_state) =
+        create_signal(RtcIceConnectionState::Closed);
+    let (ice_gathering_state, set_ice_gathering_state) = create_signal(RtcIceGatheringState::New);
+    let (signaling_state, set_signaling_state) = create_signal(RtcSignalingState::Closed);
+
+    let states = RtcStateSetters {
+        connection_state: set_connection_state,
+        ice_connection_state: set_ice_connection_state,
+        ice_gathering_state: set_ice_gathering_state,
+        signaling_state: set_signaling_state,
+    };
+
+    let peer_connection = store_value(None::<RtcPeerConnection>);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let mut config = RtcConfiguration::new();
+        if !ice_servers.is_empty() {
+            config.ice_servers(&ice_servers.iter().map(IceServer::to_js).collect::<Array>());
+        }
+
+        if let Ok(pc) = RtcPeerConnection::new_with_configuration(&config) {
+            states.update(&pc);
+
+            for event in [
+                "connectionstatechange",
+                "iceconnectionstatechange",
+                "icegatheringstatechange",
+                "signalingstatechange",
+            ] {
+                let pc_ = pc.clone();
+                let _ = use_event_listener(pc.clone(), Custom::<Event>::new(event), move |_| {
+                    states.update(&pc_)
+                });
+            }
+
+            let _ = use_event_listener(
+                pc.clone(),
+                Custom::<RtcPeerConnectionIceEvent>::new("icecandidate"),
+                move |e| {
+                    // `None` or an empty candidate mark the end of the gathering
+                    if let Some(candidate) = e.candidate() {
+                        if candidate.candidate().is_empty() {
+                            return;
+                        }
+
+                        #[cfg(debug_assertions)]
+                        let prev = SpecialNonReactiveZone::enter();
+
+                        on_ice_candidate(IceCandidate::from_js(&candidate));
+
+                        #[cfg(debug_assertions)]
+                        SpecialNonReactiveZone::exit(prev);
+                    }
+                },
+            );
+
+            peer_connection.set_value(Some(pc.clone()));
+
+            on_cleanup(move || pc.close());
+        }
+    }
+
+    UseRtcPeerConnectionReturn {
+        connection_state: connection_state.into(),
+        ice_connection_state: ice_connection_state.into(),
+        ice_gathering_state: ice_gathering_state.into(),
+        signaling_state: signaling_state.into(),
+        peer_connection,
+        pending_candidates: store_value(Vec::new()),
+        states,
+    }
+}
+
+/// Options for [`use_rtc_peer_connection_with_options`].
+#[derive(DefaultBuilder, Clone)]
+pub struct UseRtcPeerConnectionOptions {
+    /// STUN and TURN servers that are used to find a route to the other peer.
+    /// Defaults to none which only works inside of the local network.
+    ice_servers: Vec<IceServer>,
+
+    /// Called for every local ICE candidate that has been found. Send it to the other peer
+    /// through your signaling channel and add it there with `add_ice_candidate`.
+    on_ice_candidate: Rc<dyn Fn(IceCandidate)>,
+}
+
+impl Default for UseRtcPeerConnectionOptions {
+    fn default() -> Self {
+        Self {
+            ice_servers: vec![],
+            on_ice_candidate: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// STUN or TURN server of [`use_rtc_peer_connection`].
+#[derive(DefaultBuilder, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IceServer {
+    /// URLs of the server like `"stun:stun.example.com"` or `"turn:turn.example.com"`.
+    #[builder(into)]
+    urls: Vec<String>,
+
+    /// Username for TURN servers. Defaults to `None`.
+    #[builder(into)]
+    username: Option<String>,
+
+    /// Credential for TURN servers. Defaults to `None`.
+    #[builder(into)]
+    credential: Option<String>,
+}
+
+impl IceServer {
+    /// Creates a server with a single URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::default().urls(vec![url.into()])
+    }
+
+    fn to_js(&self) -> web_sys::RtcIceServer {
+        let mut server = web_sys::RtcIceServer::new();
+        server.urls(
+            &self
+                .urls
+                .iter()
+                .map(|url| JsValue::from_str(url))
+                .collect::<Array>(),
+        );
+        if let Some(username) = &self.username {
+            server.username(username);
+        }
+        if let Some(credential) = &self.credential {
+            server.credential(credential);
+        }
+        server
+    }
+}
+
+/// ICE candidate that has to be exchanged with the other peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IceCandidate {
+    pub candidate: String,
+    pub sdp_mid: Option<String>,
+    pub sdp_m_line_index: Option<u16>,
+}
+
+impl IceCandidate {
+    fn from_js(candidate: &RtcIceCandidate) -> Self {
+        Self {
+            candidate: candidate.candidate(),
+            sdp_mid: candidate.sdp_mid(),
+            sdp_m_line_index: candidate.sdp_m_line_index(),
+        }
+    }
+
+    fn to_js(&self) -> RtcIceCandidateInit {
+        let mut init = RtcIceCandidateInit::new(&self.candidate);
+        init.sdp_mid(self.sdp_mid.as_deref())
+            .sdp_m_line_index(self.sdp_m_line_index);
+        init
+    }
+}
+
+/// Offer or answer that has to be exchanged with the other peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionDescription {
+    pub sdp_type: SdpType,
+    pub sdp: String,
+}
+
+impl SessionDescription {
+    /// Creates an offer description from the SDP received from the other peer.
+    pub fn offer(sdp: impl Into<String>) -> Self {
+        Self {
+            sdp_type: SdpType::Offer,
+            sdp: sdp.into(),
+        }
+    }
+
+    /// Creates an answer description from the SDP received from the other peer.
+    pub fn answer(sdp: impl Into<String>) -> Self {
+        Self {
+            sdp_type: SdpType::Answer,
+            sdp: sdp.into(),
+        }
+    }
+
+    fn from_js(sdp_type: SdpType, description: &JsValue) -> Self {
+        Self {
+            sdp_type,
+            sdp: js_sys::Reflect::get(description, &"sdp".into())
+                .ok()
+                .and_then(|sdp| sdp.as_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn to_js(&self) -> RtcSessionDescriptionInit {
+        let mut init = RtcSessionDescriptionInit::new(self.sdp_type.into());
+        init.sdp(&self.sdp);
+        init
+    }
+}
+
+/// Type of a [`SessionDescription`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SdpType {
+    Offer,
+    Answer,
+    Pranswer,
+    Rollback,
+}
+
+impl From<SdpType> for RtcSdpType {
+    fn from(sdp_type: SdpType) -> Self {
+        match sdp_type {
+            SdpType::Offer => RtcSdpType::Offer,
+            SdpType::Answer => RtcSdpType::Answer,
+            SdpType::Pranswer => RtcSdpType::Pranswer,
+            SdpType::Rollback => RtcSdpType::Rollback,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RtcStateSetters {
+    connection_state: WriteSignal<RtcPeerConnectionState>,
+    ice_connection_state: WriteSignal<RtcIceConnectionState>,
+    ice_gathering_state: WriteSignal<RtcIceGatheringState>,
+    signaling_state: WriteSignal<RtcSignalingState>,
+}
+
+impl RtcStateSetters {
+    fn update(&self, pc: &RtcPeerConnection) {
+        self.connection_state.set(pc.connection_state());
+        self.ice_connection_state.set(pc.ice_connection_state());
+        self.ice_gathering_state.set(pc.ice_gathering_state());
+        self.signaling_state.set(pc.signaling_state());
+    }
+}
+
+/// Return type of [`use_rtc_peer_connection`].
+#[derive(Clone, Copy)]
+pub struct UseRtcPeerConnectionReturn {
+    /// The overall state of the connection.
+    pub connection_state: Signal<RtcPeerConnectionState>,
+    /// The state of the ICE agent.
+    pub ice_connection_state: Signal<RtcIceConnectionState>,
+    /// If the ICE candidates are still being gathered.
+    pub ice_gathering_state: Signal<RtcIceGatheringState>,
+    /// Where in the offer/answer exchange the connection is.
+    pub signaling_state: Signal<RtcSignalingState>,
+
+    peer_connection: StoredValue<Option<RtcPeerConnection>>,
+    pending_candidates: StoredValue<Vec<IceCandidate>>,
+    states: RtcStateSetters,
+}
+
+impl UseRtcPeerConnectionReturn {
+    /// The underlying `RTCPeerConnection`. `None` on the server or if it couldn't be created.
+    pub fn peer_connection(&self) -> Option<RtcPeerConnection> {
+        self.peer_connection.try_get_value().flatten()
+    }
+
+    /// Creates an offer and sets it as local description. Send it to the other peer.
+    pub fn create_offer(
+        &self,
+    ) -> impl Future<Output = Result<SessionDescription, RtcPeerConnectionError>> {
+        let pc = self.peer_connection();
+
+        async move {
+            let pc = pc.ok_or(RtcPeerConnectionError::Closed)?;
+
+            let offer = js_fut!(pc.create_offer())
+                .await
+                .map_err(RtcPeerConnectionError::Failed)?;
+            let offer = SessionDescription::from_js(SdpType::Offer, &offer);
+
+            js_fut!(pc.set_local_description(&offer.to_js()))
+                .await
+                .map_err(RtcPeerConnectionError::Failed)?;
+
+            Ok(offer)
+        }
+    }
+
+    /// Sets the `offer` of the other peer as remote description, creates an answer and sets it
+    /// as local description. Send the answer back to the other peer.
+    pub fn create_answer(
+        &self,
+        offer: &SessionDescription,
+    ) -> impl Future<Output = Result<SessionDescription, RtcPeerConnectionError>> {
+        let remote = self.set_remote_description(offer);
+        let pc = self.peer_connection();
+
+        async move {
+            remote.await?;
+            let pc = pc.ok_or(RtcPeerConnectionError::Closed)?;
+
+            let answer = js_fut!(pc.create_answer())
+                .await
+                .map_err(RtcPeerConnectionError::Failed)?;
+            let answer = SessionDescription::from_js(SdpType::Answer, &answer);
+
+            js_fut!(pc.set_local_description(&answer.to_js()))
+                .await
+                .map_err(RtcPeerConnectionError::Failed)?;
+
+            Ok(answer)
+        }
+    }
+
+    /// Sets the description received from the other peer, usually the answer to an offer.
+    /// ICE candidates that have been buffered until now are added afterwards.
+    pub fn set_remote_description(
+        &self,
+        description: &SessionDescription,
+    ) -> impl Future<Output = Result<(), RtcPeerConnectionError>> {
+        let pc = self.peer_connection();
+        let pending_candidates = self.pending_candidates;
+        let description = description.to_js();
+
+        async move {
+            let pc = pc.ok_or(RtcPeerConnectionError::Closed)?;
+
+            js_fut!(pc.set_remote_description(&description))
+                .await
+                .map_err(RtcPeerConnectionError::Failed)?;
+
+            let candidates = pending_candidates
+                .try_update_value(std::mem::take)
+                .unwrap_or_default();
+
+            for candidate in candidates {
+                add_ice_candidate(&pc, &candidate).await?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Adds an ICE candidate received from the other peer. It's buffered if the remote
+    /// description hasn't been set yet.
+    pub fn add_ice_candidate(
+        &self,
+        candidate: &IceCandidate,
+    ) -> impl Future<Output = Result<(), RtcPeerConnectionError>> {
+        let pc = self.peer_connection();
+        let pending_candidates = self.pending_candidates;
+        let candidate = candidate.clone();
+
+        async move {
+            let pc = pc.ok_or(RtcPeerConnectionError::Closed)?;
+
+            if pc.remote_description().is_none() {
+                pending_candidates.try_update_value(|candidates| candidates.push(candidate));
+                return Ok(());
+            }
+
+            add_ice_candidate(&pc, &candidate).await
+        }
+    }
+
+    /// Closes the connection and all of its data channels.
+    pub fn close(&self) {
+        if let Some(pc) = self.peer_connection() {
+            pc.close();
+            // closing doesn't fire any state change events
+            self.states.update(&pc);
+        }
+    }
+}
+
+async fn add_ice_candidate(
+    pc: &RtcPeerConnection,
+    candidate: &IceCandidate,
+) -> Result<(), RtcPeerConnectionError> {
+    js_fut!(pc.add_ice_candidate_with_opt_rtc_ice_candidate_init(Some(&candidate.to_js())))
+        .await
+        .map(|_| ())
+        .map_err(RtcPeerConnectionError::Failed)
+}
+
+/// Error of the methods of [`UseRtcPeerConnectionReturn`]
+#[derive(Error, Debug, Clone)]
+pub enum RtcPeerConnectionError {
+    #[error("There is no peer connection. It has been closed or couldn't be created.")]
+    Closed,
+    #[error("WebRTC operation failed: {0:?}")]
+    Failed(JsValue),
+}
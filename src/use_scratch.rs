@@ -0,0 +1,424 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{now, ElementMaybeSignal, PointerType};
+use crate::use_event_listener;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointermove, pointerup};
+use leptos::*;
+use web_sys::PointerEvent;
+
+/// Capture freehand strokes drawn with the mouse, a pen or a finger on an element.
+///
+/// Every stroke is recorded as a list of points relative to the top left corner of `target`
+/// together with the pressure and the time since the stroke started. This is a building block
+/// for signature pads, sketches or annotations. The strokes can be rendered as SVG paths.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_scratch, UseScratchReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let pad = create_node_ref::<Div>();
+///
+/// let UseScratchReturn {
+///     svg_path,
+///     clear,
+///     undo,
+///     ..
+/// } = use_scratch(pad);
+///
+/// view! {
+///     <div node_ref=pad style="touch-action: none; width: 400px; height: 200px">
+///         <svg width="400" height="200">
+///             <path
+///                 d=svg_path
+///                 fill="none"
+///                 stroke="black"
+///                 stroke-width="2"
+///                 stroke-linecap="round"
+///                 stroke-linejoin="round"
+///             />
+///         </svg>
+///     </div>
+///     <button on:click=move |_| undo()>"Undo"</button>
+///     <button on:click=move |_| clear()>"Clear"</button>
+/// }
+/// # }
+/// ```
+///
+/// Please note that `target` should have the CSS property `touch-action: none`. Otherwise
+/// touch and pen input scrolls the page instead of drawing.
+///
+/// Each stroke can also be rendered on its own with [`ScratchStroke::to_svg_path`], for example
+/// to vary the stroke width by the pressure of its points.
+///
+/// ## Simplification
+///
+/// Pointer events produce a lot of points. With the option `min_distance` points that are
+/// closer than the given number of pixels to the previous one are skipped while drawing. With
+/// `simplify_tolerance` finished strokes are simplified with the
+/// [Ramer-Douglas-Peucker algorithm](https://en.wikipedia.org/wiki/Ramer%E2%80%93Douglas%E2%80%93Peucker_algorithm).
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_scratch_with_options, UseScratchOptions};
+/// # use leptos_use::core::PointerType;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let pad = create_node_ref::<Div>();
+/// let scratch = use_scratch_with_options(
+///     pad,
+///     UseScratchOptions::default()
+///         .pointer_types(vec![PointerType::Pen])
+///         .simplify_tolerance(1.5),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server there are no strokes and the returned functions do nothing.
+pub fn use_scratch<El, T>(
+    target: El,
+) -> UseScratchReturn<impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_scratch_with_options(target, UseScratchOptions::default())
+}
+
+/// Version of [`use_scratch`] that takes a `UseScratchOptions`. See [`use_scratch`] for how to use.
+pub fn use_scratch_with_options<El, T>(
+    target: El,
+    options: UseScratchOptions,
+) -> UseScratchReturn<impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseScratchOptions {
+        pointer_types,
+        min_distance,
+        simplify_tolerance,
+    } = options;
+
+    let target = target.into();
+    let element: Signal<Option<web_sys::Element>> =
+        Signal::derive(move || target.get().map(|target| target.into()));
+    let event_target: Signal<Option<web_sys::EventTarget>> =
+        Signal::derive(move || element.get().map(|element| element.into()));
+
+    let (strokes, set_strokes) = create_signal(Vec::<ScratchStroke>::new());
+    let (current, set_current) = create_signal(None::<ScratchStroke>);
+
+    // pointer id and event time stamp of the start of the current stroke
+    let active = store_value(None::<(i32, f64)>);
+
+    let to_point = move |event: &PointerEvent, start_time: f64| {
+        let (left, top) = element
+            .get_untracked()
+            .map(|element| {
+                let rect = element.get_bounding_client_rect();
+                (rect.left(), rect.top())
+            })
+            .unwrap_or_default();
+
+        ScratchPoint {
+            x: event.client_x() as f64 - left,
+            y: event.client_y() as f64 - top,
+            pressure: event.pressure() as f64,
+            time: event.time_stamp() - start_time,
+        }
+    };
+
+    let _ = use_event_listener(event_target, pointerdown, move |event: PointerEvent| {
+        let pointer_type = event.pointer_type().parse::<PointerType>().ok();
+
+        if event.button() != 0
+            || active.get_value().is_some()
+            || !pointer_type.is_some_and(|ty| pointer_types.contains(&ty))
+        {
+            return;
+        }
+
+        // keeps the mouse from selecting text
+        event.prevent_default();
+
+        if let Some(element) = element.get_untracked() {
+            let _ = element.set_pointer_capture(event.pointer_id());
+        }
+
+        let start_time = event.time_stamp();
+        active.set_value(Some((event.pointer_id(), start_time)));
+
+        set_current.set(Some(ScratchStroke {
+            points: vec![to_point(&event, start_time)],
+            pointer_type,
+            started_at: now(),
+        }));
+    });
+
+    let _ = use_event_listener(event_target, pointermove, move |event: PointerEvent| {
+        let Some((pointer_id, start_time)) = active.get_value() else {
+            return;
+        };
+        if event.pointer_id() != pointer_id {
+            return;
+        }
+
+        let point = to_point(&event, start_time);
+
+        let is_far_enough = current.with_untracked(|current| {
+            current
+                .as_ref()
+                .and_then(|stroke| stroke.points.last())
+                .map(|last| (point.x - last.x).hypot(point.y - last.y) >= min_distance)
+                .unwrap_or(true)
+        });
+
+        if is_far_enough {
+            set_current.update(|current| {
+                if let Some(stroke) = current {
+                    stroke.points.push(point);
+                }
+            });
+        }
+    });
+
+    let end_stroke = move |event: PointerEvent| {
+        if active.get_value().map(|(pointer_id, _)| pointer_id) != Some(event.pointer_id()) {
+            return;
+        }
+        active.set_value(None);
+
+        let Some(stroke) = current.get_untracked() else {
+            return;
+        };
+        set_current.set(None);
+
+        set_strokes.update(|strokes| strokes.push(stroke.simplify(simplify_tolerance)));
+    };
+
+    let _ = use_event_listener(event_target, pointerup, end_stroke);
+    let _ = use_event_listener(event_target, pointercancel, end_stroke);
+
+    let svg_path = Signal::derive(move || {
+        strokes.with(|strokes| {
+            current.with(|current| {
+                strokes
+                    .iter()
+                    .chain(current.iter())
+                    .map(ScratchStroke::to_svg_path)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+        })
+    });
+
+    let clear = move || {
+        set_strokes.update(|strokes| strokes.clear());
+    };
+
+    let undo = move || {
+        set_strokes.update(|strokes| {
+            strokes.pop();
+        });
+    };
+
+    UseScratchReturn {
+        strokes: strokes.into(),
+        set_strokes,
+        current: current.into(),
+        is_drawing: Signal::derive(move || current.with(Option::is_some)),
+        svg_path,
+        clear,
+        undo,
+    }
+}
+
+/// Options for [`use_scratch_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseScratchOptions {
+    /// Pointer types that can draw. Defaults to `[Mouse, Touch, Pen]`.
+    pointer_types: Vec<PointerType>,
+
+    /// Points that are closer than this to the previous point of the stroke are skipped.
+    /// In pixels. Defaults to `1.0`.
+    min_distance: f64,
+
+    /// Maximum distance in pixels a point may be away from the simplified stroke when a
+    /// stroke is finished. `0.0` keeps all points. Defaults to `0.0`.
+    simplify_tolerance: f64,
+}
+
+impl Default for UseScratchOptions {
+    fn default() -> Self {
+        Self {
+            pointer_types: vec![PointerType::Mouse, PointerType::Touch, PointerType::Pen],
+            min_distance: 1.0,
+            simplify_tolerance: 0.0,
+        }
+    }
+}
+
+/// A point of a [`ScratchStroke`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScratchPoint {
+    /// Horizontal position in pixels relative to the left edge of the target
+    pub x: f64,
+    /// Vertical position in pixels relative to the top edge of the target
+    pub y: f64,
+    /// Pressure between `0.0` and `1.0`. Devices without pressure support report `0.5` while a
+    /// button is pressed.
+    pub pressure: f64,
+    /// Time in ms since the start of the stroke
+    pub time: f64,
+}
+
+/// A stroke drawn on the target of [`use_scratch`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScratchStroke {
+    /// The points of the stroke in the order they have been drawn
+    pub points: Vec<ScratchPoint>,
+    /// The pointer type the stroke has been drawn with
+    pub pointer_type: Option<PointerType>,
+    /// Time in ms since the Unix epoch (like `Date.now()`) when the stroke has been started
+    pub started_at: f64,
+}
+
+impl ScratchStroke {
+    /// Time in ms from the first to the last point
+    pub fn duration(&self) -> f64 {
+        self.points.last().map(|last| last.time).unwrap_or_default()
+    }
+
+    /// Returns the stroke with only the points that are needed to stay within `tolerance`
+    /// pixels of the original stroke. The first and the last point are always kept.
+    pub fn simplify(&self, tolerance: f64) -> Self {
+        let points = &self.points;
+
+        if tolerance <= 0.0 || points.len() < 3 {
+            return self.clone();
+        }
+
+        let mut keep = vec![false; points.len()];
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+
+        let mut ranges = vec![(0, points.len() - 1)];
+
+        while let Some((start, end)) = ranges.pop() {
+            let (farthest, distance) = (start + 1..end)
+                .map(|i| {
+                    (
+                        i,
+                        distance_to_segment(&points[i], &points[start], &points[end]),
+                    )
+                })
+                .fold((start, 0.0), |max, candidate| {
+                    if candidate.1 > max.1 {
+                        candidate
+                    } else {
+                        max
+                    }
+                });
+
+            if distance > tolerance {
+                keep[farthest] = true;
+                ranges.push((start, farthest));
+                ranges.push((farthest, end));
+            }
+        }
+
+        Self {
+            points: points
+                .iter()
+                .zip(keep)
+                .filter_map(|(point, keep)| keep.then_some(*point))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the SVG path data (the `d` attribute) of the stroke. The points are connected by
+    /// quadratic curves through their midpoints which smoothens the stroke.
+    pub fn to_svg_path(&self) -> String {
+        let points = &self.points;
+
+        let Some(first) = points.first() else {
+            return String::new();
+        };
+
+        let mut path = format!("M{} {}", round(first.x), round(first.y));
+
+        match points.len() {
+            // a dot that is visible with round line caps
+            1 => path.push_str(&format!(" L{} {}", round(first.x), round(first.y))),
+            _ => {
+                for pair in points[1..].windows(2) {
+                    let (control, next) = (pair[0], pair[1]);
+                    path.push_str(&format!(
+                        " Q{} {} {} {}",
+                        round(control.x),
+                        round(control.y),
+                        round((control.x + next.x) / 2.0),
+                        round((control.y + next.y) / 2.0),
+                    ));
+                }
+
+                let last = points[points.len() - 1];
+                path.push_str(&format!(" L{} {}", round(last.x), round(last.y)));
+            }
+        }
+
+        path
+    }
+}
+
+fn distance_to_segment(point: &ScratchPoint, start: &ScratchPoint, end: &ScratchPoint) -> f64 {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let length_squared = dx * dx + dy * dy;
+
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((point.x - start.x) * dx + (point.y - start.y) * dy) / length_squared).clamp(0.0, 1.0)
+    };
+
+    (point.x - (start.x + t * dx)).hypot(point.y - (start.y + t * dy))
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Return type of [`use_scratch`].
+pub struct UseScratchReturn<ClearFn, UndoFn>
+where
+    ClearFn: Fn() + Clone,
+    UndoFn: Fn() + Clone,
+{
+    /// The finished strokes in the order they have been drawn
+    pub strokes: Signal<Vec<ScratchStroke>>,
+    /// Sets the finished strokes, for example to restore a saved drawing
+    pub set_strokes: WriteSignal<Vec<ScratchStroke>>,
+    /// The stroke that is being drawn right now
+    pub current: Signal<Option<ScratchStroke>>,
+    /// `true` while a stroke is being drawn
+    pub is_drawing: Signal<bool>,
+    /// SVG path data of all strokes including the current one
+    pub svg_path: Signal<String>,
+    /// Removes all finished strokes
+    pub clear: ClearFn,
+    /// Removes the last finished stroke
+    pub undo: UndoFn,
+}
@@ -0,0 +1,377 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut, use_event_listener, use_supported};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [Window Management API](https://developer.mozilla.org/en-US/docs/Web/API/Window_Management_API)
+/// (`window.getScreenDetails()`).
+///
+/// Lists all screens connected to the device and the screen the window is currently on. Both
+/// are updated when screens are added, removed or rearranged and when the window is moved to
+/// another screen.
+///
+/// Getting the screen details requires the `window-management` permission. If the permission
+/// hasn't been granted yet the browser asks the user for it. Depending on the browser this
+/// only works in reaction to a user gesture, so `request` can be called in a click handler.
+///
+/// Use [`open_popup_on_screen`] to open a popup window on a specific screen, e.g. to show a
+/// dashboard on the second monitor.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{open_popup_on_screen, use_screen_details, PopupOnScreenOptions, UseScreenDetailsReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseScreenDetailsReturn { screens, current_screen, .. } = use_screen_details();
+///
+/// view! {
+///     <p>"Current screen: " {move || current_screen.get().map(|s| s.label).unwrap_or_default()}</p>
+///     <For each=move || screens.get() key=|screen| screen.label.clone() let:screen>
+///         <button on:click={
+///             let screen = screen.clone();
+///             move |_| {
+///                 open_popup_on_screen("/dashboard", &screen, PopupOnScreenOptions::default());
+///             }
+///         }>
+///             "Open dashboard on " {screen.label.clone()}
+///         </button>
+///     </For>
+/// }
+/// # }
+/// ```
+///
+/// Until the screen details are available `screens` is empty and `current_screen` is the
+/// screen of `window.screen`, which has no label and no position.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `screens` is empty, `current_screen` is `None` and `request` does nothing.
+pub fn use_screen_details() -> UseScreenDetailsReturn<impl Fn() + Clone> {
+    use_screen_details_with_options(UseScreenDetailsOptions::default())
+}
+
+/// Version of [`use_screen_details`] that takes a `UseScreenDetailsOptions`. See [`use_screen_details`] for how to use.
+pub fn use_screen_details_with_options(
+    options: UseScreenDetailsOptions,
+) -> UseScreenDetailsReturn<impl Fn() + Clone> {
+    let UseScreenDetailsOptions {
+        immediate,
+        on_screens_change,
+        on_current_screen_change,
+    } = options;
+
+    let is_supported = use_supported(|| {
+        let window = window();
+        js!("getScreenDetails" in &window)
+    });
+
+    let (details, set_details) = create_signal(None::<web_sys::EventTarget>);
+    let (screens, set_screens) = create_signal(Vec::<ScreenInfo>::new());
+    let (current_screen, set_current_screen) = create_signal(None::<ScreenInfo>);
+    let (error, set_error) = create_signal(None::<JsValue>);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let window = window();
+        if let Ok(screen) = js!(window["screen"]) {
+            set_current_screen.set(Some(ScreenInfo::from_js(&screen)));
+        }
+    }
+
+    let update_screens = move || {
+        if let Some(details) = details.get_untracked() {
+            let list = js!(details["screens"])
+                .ok()
+                .filter(|list| list.is_array())
+                .map(|list| {
+                    list.unchecked_into::<js_sys::Array>()
+                        .iter()
+                        .map(|screen| ScreenInfo::from_js(&screen))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            set_screens.set(list.clone());
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_screens_change(&list);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+    };
+
+    let update_current_screen = move || {
+        if let Some(details) = details.get_untracked() {
+            if let Ok(screen) = js!(details["currentScreen"]) {
+                let screen = ScreenInfo::from_js(&screen);
+                set_current_screen.set(Some(screen.clone()));
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_current_screen_change(&screen);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        }
+    };
+
+    let _ = use_event_listener(details, Custom::<ev::Event>::new("screenschange"), {
+        let update_screens = update_screens.clone();
+        move |_| update_screens()
+    });
+
+    let _ = use_event_listener(details, Custom::<ev::Event>::new("currentscreenchange"), {
+        let update_current_screen = update_current_screen.clone();
+        move |_| update_current_screen()
+    });
+
+    let request = move || {
+        #[cfg(not(feature = "ssr"))]
+        if is_supported.get_untracked() {
+            let window = window();
+
+            let promise = js!(window["getScreenDetails"]).and_then(|get_screen_details| {
+                get_screen_details
+                    .unchecked_into::<js_sys::Function>()
+                    .call0(&window)
+            });
+
+            let update_screens = update_screens.clone();
+            let update_current_screen = update_current_screen.clone();
+
+            spawn_local(async move {
+                let result = match promise {
+                    Ok(promise) => js_fut!(promise.unchecked_into::<js_sys::Promise>()).await,
+                    Err(err) => Err(err),
+                };
+
+                match result {
+                    Ok(result) => {
+                        set_error.set(None);
+                        set_details.set(Some(result.unchecked_into()));
+                        update_screens();
+                        update_current_screen();
+                    }
+                    Err(err) => set_error.set(Some(err)),
+                }
+            });
+        }
+    };
+
+    if immediate {
+        request();
+    }
+
+    UseScreenDetailsReturn {
+        is_supported,
+        screens: screens.into(),
+        current_screen: current_screen.into(),
+        is_granted: Signal::derive(move || details.with(Option::is_some)),
+        error: error.into(),
+        request,
+    }
+}
+
+/// Opens `url` in a popup window on the given screen. Returns the new window or `None` if the
+/// popup has been blocked.
+///
+/// Without size the popup covers the available area of the screen. Otherwise it's centered on
+/// the screen.
+///
+/// ```
+/// # use leptos_use::{open_popup_on_screen, PopupOnScreenOptions, ScreenInfo};
+/// #
+/// # fn open(screen: ScreenInfo) {
+/// let popup = open_popup_on_screen(
+///     "/slides/presenter",
+///     &screen,
+///     PopupOnScreenOptions::default().width(800.0).height(600.0),
+/// );
+/// # }
+/// ```
+pub fn open_popup_on_screen(
+    url: &str,
+    screen: &ScreenInfo,
+    options: PopupOnScreenOptions,
+) -> Option<web_sys::Window> {
+    let PopupOnScreenOptions {
+        width,
+        height,
+        target,
+    } = options;
+
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        None
+    } else {
+        let width = width.unwrap_or(screen.avail_width).min(screen.avail_width);
+        let height = height.unwrap_or(screen.avail_height).min(screen.avail_height);
+
+        let left = screen.avail_left + (screen.avail_width - width) / 2.0;
+        let top = screen.avail_top + (screen.avail_height - height) / 2.0;
+
+        window()
+            .open_with_url_and_target_and_features(
+                url,
+                &target,
+                &format!("popup,left={left},top={top},width={width},height={height}"),
+            )
+            .ok()
+            .flatten()
+    }}
+}
+
+/// Options for [`use_screen_details_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseScreenDetailsOptions {
+    /// Request the screen details right away. If the permission hasn't been granted yet, the
+    /// user is asked for it. Defaults to `true`.
+    immediate: bool,
+
+    /// Called with all screens when screens are added, removed or changed.
+    on_screens_change: Rc<dyn Fn(&[ScreenInfo])>,
+
+    /// Called with the new current screen when the window is moved to another screen.
+    on_current_screen_change: Rc<dyn Fn(&ScreenInfo)>,
+}
+
+impl Default for UseScreenDetailsOptions {
+    fn default() -> Self {
+        Self {
+            immediate: true,
+            on_screens_change: Rc::new(|_| {}),
+            on_current_screen_change: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_screen_details`].
+pub struct UseScreenDetailsReturn<RequestFn>
+where
+    RequestFn: Fn() + Clone,
+{
+    /// Whether the Window Management API is supported
+    pub is_supported: Signal<bool>,
+
+    /// All screens connected to the device. Empty until the screen details are available.
+    pub screens: Signal<Vec<ScreenInfo>>,
+
+    /// The screen the window is currently on
+    pub current_screen: Signal<Option<ScreenInfo>>,
+
+    /// `true` as soon as the screen details are available which means the permission has been
+    /// granted
+    pub is_granted: Signal<bool>,
+
+    /// The error of the last `request`, e.g. if the permission has been denied
+    pub error: Signal<Option<JsValue>>,
+
+    /// Requests the screen details. Asks for the permission if needed.
+    pub request: RequestFn,
+}
+
+/// Options for [`open_popup_on_screen`].
+#[derive(DefaultBuilder, Clone, Debug)]
+pub struct PopupOnScreenOptions {
+    /// Width of the popup in CSS pixels. Defaults to the available width of the screen.
+    #[builder(into)]
+    width: Option<f64>,
+
+    /// Height of the popup in CSS pixels. Defaults to the available height of the screen.
+    #[builder(into)]
+    height: Option<f64>,
+
+    /// Name of the browsing context the popup is opened in. Defaults to `"_blank"`.
+    #[builder(into)]
+    target: String,
+}
+
+impl Default for PopupOnScreenOptions {
+    fn default() -> Self {
+        Self {
+            width: None,
+            height: None,
+            target: "_blank".to_string(),
+        }
+    }
+}
+
+/// A screen of [`use_screen_details`]. All values are in CSS pixels relative to the origin of
+/// the multi-screen workspace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScreenInfo {
+    /// Human readable name of the screen, e.g. `"DELL U2720Q"`
+    pub label: String,
+    /// Left edge of the screen
+    pub left: f64,
+    /// Top edge of the screen
+    pub top: f64,
+    /// Width of the screen
+    pub width: f64,
+    /// Height of the screen
+    pub height: f64,
+    /// Left edge of the area not covered by system UI like the taskbar
+    pub avail_left: f64,
+    /// Top edge of the area not covered by system UI like the taskbar
+    pub avail_top: f64,
+    /// Width of the area not covered by system UI like the taskbar
+    pub avail_width: f64,
+    /// Height of the area not covered by system UI like the taskbar
+    pub avail_height: f64,
+    /// Ratio of physical pixels to CSS pixels
+    pub device_pixel_ratio: f64,
+    /// `true` for the primary screen of the operating system
+    pub is_primary: bool,
+    /// `true` for screens built into the device, like a laptop display
+    pub is_internal: bool,
+}
+
+impl ScreenInfo {
+    fn from_js(screen: &JsValue) -> Self {
+        let number = |key: &str| {
+            js_sys::Reflect::get(screen, &key.into())
+                .ok()
+                .and_then(|value| value.as_f64())
+                .unwrap_or_default()
+        };
+        let boolean = |key: &str| {
+            js_sys::Reflect::get(screen, &key.into())
+                .ok()
+                .and_then(|value| value.as_bool())
+                .unwrap_or_default()
+        };
+
+        Self {
+            label: js_sys::Reflect::get(screen, &"label".into())
+                .ok()
+                .and_then(|label| label.as_string())
+                .unwrap_or_default(),
+            left: number("left"),
+            top: number("top"),
+            width: number("width"),
+            height: number("height"),
+            avail_left: number("availLeft"),
+            avail_top: number("availTop"),
+            avail_width: number("availWidth"),
+            avail_height: number("availHeight"),
+            device_pixel_ratio: match number("devicePixelRatio") {
+                ratio if ratio > 0.0 => ratio,
+                _ => window().device_pixel_ratio(),
+            },
+            is_primary: boolean("isPrimary"),
+            is_internal: boolean("isInternal"),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use cfg_if::cfg_if;
+use leptos::*;
+
+#[cfg(not(feature = "ssr"))]
+const SIDES: [&str; 4] = ["top", "right", "bottom", "left"];
+
+/// Reactive `env(safe-area-inset-*)`.
+///
+/// Returns the safe area insets of the screen in pixels, i.e. the space that is covered by
+/// notches, rounded corners or the home indicator of the device. They are updated when the
+/// window is resized, the orientation changes and when the document enters or leaves fullscreen.
+///
+/// This way you can compute notch aware layouts in Rust instead of only in CSS.
+///
+/// > The insets are only non-zero if the page opts into covering the whole screen with
+/// > `<meta name="viewport" content="viewport-fit=cover">`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_screen_safe_area, UseScreenSafeAreaReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseScreenSafeAreaReturn {
+///     top, right, bottom, left, ..
+/// } = use_screen_safe_area();
+///
+/// view! {
+///     <div style:padding-top=move || format!("{}px", top.get())>
+///         "Content that isn't hidden behind the notch"
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server all insets are `0.0`.
+pub fn use_screen_safe_area() -> UseScreenSafeAreaReturn<impl Fn() + Clone> {
+    let (top, set_top) = create_signal(0.0);
+    let (right, set_right) = create_signal(0.0);
+    let (bottom, set_bottom) = create_signal(0.0);
+    let (left, set_left) = create_signal(0.0);
+
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        let update = || {};
+    } else {
+        use crate::{use_event_listener_with_options, UseEventListenerOptions};
+        use wasm_bindgen::JsCast;
+
+        let root = document().document_element();
+
+        // `env()` can't be read directly so it's assigned to custom properties whose computed
+        // value can be read
+        if let Some(root) = root.as_ref() {
+            let style = root.unchecked_ref::<web_sys::HtmlElement>().style();
+
+            for side in SIDES {
+                let _ = style.set_property(
+                    &format!("--leptos-use-safe-area-{side}"),
+                    &format!("env(safe-area-inset-{side}, 0px)"),
+                );
+            }
+        }
+
+        let update = move || {
+            let Some(root) = root.as_ref() else {
+                return;
+            };
+
+            let Ok(Some(style)) = window().get_computed_style(root) else {
+                return;
+            };
+
+            let inset = |side: &str| {
+                style
+                    .get_property_value(&format!("--leptos-use-safe-area-{side}"))
+                    .ok()
+                    .and_then(|value| value.trim().trim_end_matches("px").parse::<f64>().ok())
+                    .unwrap_or_default()
+            };
+
+            set_top.set(inset("top"));
+            set_right.set(inset("right"));
+            set_bottom.set(inset("bottom"));
+            set_left.set(inset("left"));
+        };
+
+        update();
+
+        let listener_options = UseEventListenerOptions::default().passive(true);
+
+        let _ = use_event_listener_with_options(
+            window(),
+            ev::resize,
+            {
+                let update = update.clone();
+                move |_| update()
+            },
+            listener_options,
+        );
+
+        let _ = use_event_listener_with_options(
+            window(),
+            ev::Custom::<ev::Event>::new("orientationchange"),
+            {
+                let update = update.clone();
+                move |_| update()
+            },
+            listener_options,
+        );
+
+        let _ = use_event_listener_with_options(
+            document(),
+            ev::Custom::<ev::Event>::new("fullscreenchange"),
+            {
+                let update = update.clone();
+                move |_| update()
+            },
+            listener_options,
+        );
+    }}
+
+    UseScreenSafeAreaReturn {
+        top: top.into(),
+        right: right.into(),
+        bottom: bottom.into(),
+        left: left.into(),
+        update,
+    }
+}
+
+/// Return type of [`use_screen_safe_area`].
+pub struct UseScreenSafeAreaReturn<F>
+where
+    F: Fn() + Clone,
+{
+    /// Inset at the top in pixels
+    pub top: Signal<f64>,
+
+    /// Inset at the right in pixels
+    pub right: Signal<f64>,
+
+    /// Inset at the bottom in pixels
+    pub bottom: Signal<f64>,
+
+    /// Inset at the left in pixels
+    pub left: Signal<f64>,
+
+    /// Re-reads the insets. Only necessary if they change without one of the events above.
+    pub update: F,
+}
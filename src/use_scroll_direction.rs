@@ -0,0 +1,323 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{Direction, ElementMaybeSignal};
+use crate::{
+    use_event_listener_with_options, use_timeout_fn, UseEventListenerOptions, UseTimeoutFnReturn,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::scroll;
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive scroll direction and velocity of the window or an element.
+///
+/// Useful to hide a header while the user scrolls down and to show it again as soon as they
+/// scroll up. The direction only changes after scrolling at least `threshold` pixels the other
+/// way so small movements of the finger or the trackpad don't make the header flicker.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_scroll_direction, use_window, UseScrollDirectionReturn};
+/// # use leptos_use::core::Direction;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseScrollDirectionReturn { vertical, .. } = use_scroll_direction(use_window());
+///
+/// let is_hidden = move || vertical.get() == Some(Direction::Bottom);
+///
+/// view! {
+///     <header class:hidden=is_hidden>"..."</header>
+/// }
+/// # }
+/// ```
+///
+/// ## Velocity
+///
+/// `velocity_x` and `velocity_y` are given in pixels per second and are reset to `0.0` when
+/// the scrolling has stopped for `idle` milliseconds. `is_fast` tells if the velocity is above
+/// `velocity_threshold`, e.g. to skip animations while flinging through a list.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_scroll_direction_with_options, UseScrollDirectionOptions, UseScrollDirectionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UseScrollDirectionReturn {
+///     velocity_y,
+///     is_fast,
+///     ..
+/// } = use_scroll_direction_with_options(
+///     el,
+///     UseScrollDirectionOptions::default()
+///         .threshold(20.0)
+///         .velocity_threshold(2000.0),
+/// );
+///
+/// view! {
+///     <div node_ref=el style="height: 300px; overflow: auto">"..."</div>
+///     <p>{move || format!("{:.0} px/s", velocity_y.get())}</p>
+///     <Show when=is_fast>"Whoosh!"</Show>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the directions are always `None` and the velocities `0.0`.
+pub fn use_scroll_direction<El, T>(target: El) -> UseScrollDirectionReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    use_scroll_direction_with_options(target, UseScrollDirectionOptions::default())
+}
+
+/// Version of [`use_scroll_direction`] that takes a `UseScrollDirectionOptions`. See [`use_scroll_direction`] for how to use.
+pub fn use_scroll_direction_with_options<El, T>(
+    target: El,
+    options: UseScrollDirectionOptions,
+) -> UseScrollDirectionReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::EventTarget>>,
+    T: Into<web_sys::EventTarget> + Clone + 'static,
+{
+    let UseScrollDirectionOptions {
+        threshold,
+        velocity_threshold,
+        idle,
+    } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::EventTarget> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let (vertical, set_vertical) = create_signal(None::<Direction>);
+    let (horizontal, set_horizontal) = create_signal(None::<Direction>);
+    let (direction, set_direction) = create_signal(None::<Direction>);
+    let (direction_changed, set_direction_changed) = create_signal(false);
+    let (velocity_x, set_velocity_x) = create_signal(0.0);
+    let (velocity_y, set_velocity_y) = create_signal(0.0);
+
+    let state = store_value(ScrollState::default());
+
+    let _ = watch(
+        move || target.get(),
+        move |target, _, _| {
+            let (x, y) = target.as_ref().map(scroll_position).unwrap_or_default();
+            state.set_value(ScrollState {
+                x,
+                y,
+                anchor_x: x,
+                anchor_y: y,
+                time: None,
+            });
+        },
+        true,
+    );
+
+    let UseTimeoutFnReturn {
+        start: start_idle,
+        stop: stop_idle,
+        ..
+    } = use_timeout_fn(
+        move |_: ()| {
+            set_velocity_x.set(0.0);
+            set_velocity_y.set(0.0);
+        },
+        idle as f64,
+    );
+
+    let on_scroll = move |event: web_sys::Event| {
+        let Some(target) = target.get_untracked() else {
+            return;
+        };
+
+        let (x, y) = scroll_position(&target);
+        let time = event.time_stamp();
+
+        let ScrollState {
+            x: last_x,
+            y: last_y,
+            anchor_x,
+            anchor_y,
+            time: last_time,
+        } = state.get_value();
+
+        if let Some(last_time) = last_time.filter(|last_time| time > *last_time) {
+            let seconds = (time - last_time) / 1000.0;
+            set_velocity_x.set((x - last_x) / seconds);
+            set_velocity_y.set((y - last_y) / seconds);
+        }
+
+        let (anchor_x, new_horizontal) = step(
+            x,
+            anchor_x,
+            horizontal.get_untracked(),
+            Direction::Right,
+            Direction::Left,
+            threshold,
+        );
+        let (anchor_y, new_vertical) = step(
+            y,
+            anchor_y,
+            vertical.get_untracked(),
+            Direction::Bottom,
+            Direction::Top,
+            threshold,
+        );
+
+        let mut changed = false;
+
+        if new_horizontal != horizontal.get_untracked() {
+            set_horizontal.set(new_horizontal);
+            set_direction.set(new_horizontal);
+            changed = true;
+        }
+
+        if new_vertical != vertical.get_untracked() {
+            set_vertical.set(new_vertical);
+            set_direction.set(new_vertical);
+            changed = true;
+        }
+
+        if changed != direction_changed.get_untracked() {
+            set_direction_changed.set(changed);
+        }
+
+        state.set_value(ScrollState {
+            x,
+            y,
+            anchor_x,
+            anchor_y,
+            time: Some(time),
+        });
+
+        stop_idle();
+        start_idle(());
+    };
+
+    let _ = use_event_listener_with_options(
+        target,
+        scroll,
+        on_scroll,
+        UseEventListenerOptions::default().passive(true),
+    );
+
+    UseScrollDirectionReturn {
+        vertical: vertical.into(),
+        horizontal: horizontal.into(),
+        direction: direction.into(),
+        direction_changed: direction_changed.into(),
+        velocity_x: velocity_x.into(),
+        velocity_y: velocity_y.into(),
+        is_fast: Signal::derive(move || {
+            velocity_x.get().hypot(velocity_y.get()) >= velocity_threshold
+        }),
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+struct ScrollState {
+    x: f64,
+    y: f64,
+    anchor_x: f64,
+    anchor_y: f64,
+    time: Option<f64>,
+}
+
+/// Returns the new anchor and direction of one axis. While scrolling on in the current
+/// direction the anchor follows the position. The direction only changes after moving at least
+/// `threshold` pixels away from the anchor.
+fn step(
+    position: f64,
+    anchor: f64,
+    current: Option<Direction>,
+    forward: Direction,
+    backward: Direction,
+    threshold: f64,
+) -> (f64, Option<Direction>) {
+    let delta = position - anchor;
+
+    if (current == Some(forward) && delta > 0.0) || (current == Some(backward) && delta < 0.0) {
+        (position, current)
+    } else if delta != 0.0 && delta.abs() >= threshold {
+        (position, Some(if delta > 0.0 { forward } else { backward }))
+    } else {
+        (anchor, current)
+    }
+}
+
+fn scroll_position(target: &web_sys::EventTarget) -> (f64, f64) {
+    if let Some(window) = target.dyn_ref::<web_sys::Window>() {
+        (
+            window.scroll_x().unwrap_or_default(),
+            window.scroll_y().unwrap_or_default(),
+        )
+    } else if let Some(document) = target.dyn_ref::<web_sys::Document>() {
+        document
+            .scrolling_element()
+            .map(|el| (f64::from(el.scroll_left()), f64::from(el.scroll_top())))
+            .unwrap_or_default()
+    } else if let Some(el) = target.dyn_ref::<web_sys::Element>() {
+        (f64::from(el.scroll_left()), f64::from(el.scroll_top()))
+    } else {
+        (0.0, 0.0)
+    }
+}
+
+/// Options for [`use_scroll_direction_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseScrollDirectionOptions {
+    /// Distance in pixels that has to be scrolled the other way before the direction changes.
+    /// Defaults to `10.0`.
+    threshold: f64,
+
+    /// Velocity in pixels per second above which `is_fast` is `true`. Defaults to `1000.0`.
+    velocity_threshold: f64,
+
+    /// Time in milliseconds without scroll events after which the velocity is reset to `0.0`.
+    /// Defaults to `150`.
+    idle: u64,
+}
+
+impl Default for UseScrollDirectionOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 10.0,
+            velocity_threshold: 1000.0,
+            idle: 150,
+        }
+    }
+}
+
+/// Return type of [`use_scroll_direction`].
+pub struct UseScrollDirectionReturn {
+    /// `Direction::Bottom` while scrolling down and `Direction::Top` while scrolling up.
+    /// `None` until scrolled vertically by at least `threshold` pixels.
+    pub vertical: Signal<Option<Direction>>,
+
+    /// `Direction::Right` while scrolling right and `Direction::Left` while scrolling left.
+    /// `None` until scrolled horizontally by at least `threshold` pixels.
+    pub horizontal: Signal<Option<Direction>>,
+
+    /// The direction that has changed most recently on either axis
+    pub direction: Signal<Option<Direction>>,
+
+    /// `true` if the direction has changed with the latest scroll event
+    pub direction_changed: Signal<bool>,
+
+    /// Horizontal velocity in pixels per second. Positive when scrolling right.
+    pub velocity_x: Signal<f64>,
+
+    /// Vertical velocity in pixels per second. Positive when scrolling down.
+    pub velocity_y: Signal<f64>,
+
+    /// `true` while the velocity is above `velocity_threshold`
+    pub is_fast: Signal<bool>,
+}
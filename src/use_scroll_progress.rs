@@ -0,0 +1,303 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ElementMaybeSignal, ElementsMaybeSignal};
+use crate::use_window::use_window;
+use crate::{use_event_listener_with_options, use_resize_observer, UseEventListenerOptions};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{resize, scroll};
+use leptos::*;
+use std::marker::PhantomData;
+
+/// Reactive scroll progress of the document from `0.0` (top) to `1.0` (bottom).
+///
+/// Perfect for reading progress bars. The progress is updated when the page is scrolled and
+/// when the height of the content changes (measured with a `ResizeObserver`), e.g. when images
+/// are loaded or more content is fetched.
+///
+/// Use [`use_element_scroll_progress`] for a scrollable element instead of the document.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_scroll_progress, UseScrollProgressReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseScrollProgressReturn { progress, .. } = use_scroll_progress();
+///
+/// view! {
+///     <div
+///         class="fixed top-0 left-0 h-1 bg-blue-500"
+///         style:width=move || format!("{}%", progress.get() * 100.0)
+///     ></div>
+/// }
+/// # }
+/// ```
+///
+/// If the content fits without scrolling, the progress is `1.0`.
+///
+/// ## Sections
+///
+/// Give it the sections of the page to get the progress of each one of them as well, e.g. for a
+/// table of contents. A section is at `0.0` while its top edge is below the bottom of the
+/// viewport and at `1.0` as soon as its bottom edge has become visible.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Section;
+/// # use leptos_use::{use_scroll_progress_with_options, UseScrollProgressOptions, UseScrollProgressReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let intro = create_node_ref::<Section>();
+/// let details = create_node_ref::<Section>();
+///
+/// let UseScrollProgressReturn { sections, .. } = use_scroll_progress_with_options(
+///     UseScrollProgressOptions::default().sections(vec![intro, details]),
+/// );
+///
+/// view! {
+///     <nav>
+///         "Progress of the sections: " {move || format!("{:?}", sections.get())}
+///     </nav>
+///     <section node_ref=intro>...</section>
+///     <section node_ref=details>...</section>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the progress is always `0.0` and `sections` is empty.
+pub fn use_scroll_progress() -> UseScrollProgressReturn {
+    use_scroll_progress_with_options(UseScrollProgressOptions::default())
+}
+
+/// Version of [`use_scroll_progress`] that takes a `UseScrollProgressOptions`. See [`use_scroll_progress`] for how to use.
+pub fn use_scroll_progress_with_options<SectionsEl, SectionsT>(
+    options: UseScrollProgressOptions<SectionsEl, SectionsT>,
+) -> UseScrollProgressReturn
+where
+    SectionsEl: Into<ElementsMaybeSignal<SectionsT, web_sys::Element>>,
+    SectionsT: Into<web_sys::Element> + Clone + 'static,
+{
+    let container = Signal::derive(|| {
+        cfg_if! { if #[cfg(feature = "ssr")] {
+            None
+        } else {
+            let document = document();
+            document
+                .scrolling_element()
+                .or_else(|| document.document_element())
+        }}
+    });
+
+    scroll_progress(container, true, options)
+}
+
+/// Reactive scroll progress of a scrollable element from `0.0` (top) to `1.0` (bottom).
+///
+/// Works like [`use_scroll_progress`] but for an element with `overflow: auto` or
+/// `overflow: scroll`. Changes of the height of the content are detected for the direct
+/// children of the element.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_element_scroll_progress, UseScrollProgressReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let UseScrollProgressReturn { progress, .. } = use_element_scroll_progress(el);
+///
+/// view! {
+///     <progress max="1" value=progress></progress>
+///     <div node_ref=el style="height: 300px; overflow: auto">
+///         <article>...</article>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the progress is always `0.0` and `sections` is empty.
+pub fn use_element_scroll_progress<El, T>(target: El) -> UseScrollProgressReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_element_scroll_progress_with_options(target, UseScrollProgressOptions::default())
+}
+
+/// Version of [`use_element_scroll_progress`] that takes a `UseScrollProgressOptions`. See [`use_element_scroll_progress`] for how to use.
+pub fn use_element_scroll_progress_with_options<El, T, SectionsEl, SectionsT>(
+    target: El,
+    options: UseScrollProgressOptions<SectionsEl, SectionsT>,
+) -> UseScrollProgressReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+    SectionsEl: Into<ElementsMaybeSignal<SectionsT, web_sys::Element>>,
+    SectionsT: Into<web_sys::Element> + Clone + 'static,
+{
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let container = Signal::derive(move || target.get().map(Into::into));
+
+    scroll_progress(container, false, options)
+}
+
+fn scroll_progress<SectionsEl, SectionsT>(
+    container: Signal<Option<web_sys::Element>>,
+    is_document: bool,
+    options: UseScrollProgressOptions<SectionsEl, SectionsT>,
+) -> UseScrollProgressReturn
+where
+    SectionsEl: Into<ElementsMaybeSignal<SectionsT, web_sys::Element>>,
+    SectionsT: Into<web_sys::Element> + Clone + 'static,
+{
+    let sections: ElementsMaybeSignal<SectionsT, web_sys::Element> = options.sections.into();
+    let sections = Signal::derive(move || {
+        sections
+            .get()
+            .into_iter()
+            .map(|section| section.map(Into::into))
+            .collect::<Vec<Option<web_sys::Element>>>()
+    });
+
+    let (progress, set_progress) = create_signal(0.0);
+    let (section_progress, set_section_progress) = create_signal(Vec::<f64>::new());
+
+    let update = move || {
+        let Some(container) = container.get_untracked() else {
+            return;
+        };
+
+        let scrollable = f64::from(container.scroll_height() - container.client_height());
+        set_progress.set(if scrollable > 0.0 {
+            (f64::from(container.scroll_top()) / scrollable).clamp(0.0, 1.0)
+        } else {
+            1.0
+        });
+
+        let viewport_bottom = if is_document {
+            f64::from(container.client_height())
+        } else {
+            container.get_bounding_client_rect().top()
+                + f64::from(container.client_top() + container.client_height())
+        };
+
+        set_section_progress.set(
+            sections
+                .get_untracked()
+                .iter()
+                .map(|section| {
+                    section
+                        .as_ref()
+                        .map(|section| {
+                            let rect = section.get_bounding_client_rect();
+                            if rect.height() > 0.0 {
+                                ((viewport_bottom - rect.top()) / rect.height()).clamp(0.0, 1.0)
+                            } else if rect.top() <= viewport_bottom {
+                                1.0
+                            } else {
+                                0.0
+                            }
+                        })
+                        .unwrap_or_default()
+                })
+                .collect(),
+        );
+    };
+
+    let scroll_target = Signal::derive(move || {
+        if is_document {
+            use_window().as_ref().map(|window| window.clone().into())
+        } else {
+            container.get().map(Into::into)
+        }
+    });
+
+    let listener_options = UseEventListenerOptions::default().passive(true);
+
+    let _ =
+        use_event_listener_with_options(scroll_target, scroll, move |_| update(), listener_options);
+
+    let _ =
+        use_event_listener_with_options(use_window(), resize, move |_| update(), listener_options);
+
+    // the content of the document is observed through the body, the content of an element
+    // through its children
+    let observed = Signal::derive(move || {
+        let mut observed = vec![];
+
+        if let Some(container) = container.get() {
+            if is_document {
+                observed.push(document().body().map(Into::into));
+            } else {
+                let mut child = container.first_element_child();
+                while let Some(el) = child {
+                    child = el.next_element_sibling();
+                    observed.push(Some(el));
+                }
+                observed.push(Some(container));
+            }
+        }
+
+        observed.extend(sections.get());
+        observed
+    });
+
+    let _ = use_resize_observer(observed, move |_, _| update());
+
+    let _ = watch(
+        move || (container.get(), sections.get()),
+        move |_, _, _| update(),
+        true,
+    );
+
+    UseScrollProgressReturn {
+        progress: progress.into(),
+        sections: section_progress.into(),
+    }
+}
+
+/// Options for [`use_scroll_progress_with_options`] and [`use_element_scroll_progress_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseScrollProgressOptions<El, T>
+where
+    El: Into<ElementsMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    /// Sections of the content whose progress is tracked individually. Defaults to none.
+    sections: El,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl Default for UseScrollProgressOptions<Vec<web_sys::Element>, web_sys::Element> {
+    fn default() -> Self {
+        Self {
+            sections: vec![],
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Return type of [`use_scroll_progress`] and [`use_element_scroll_progress`].
+pub struct UseScrollProgressReturn {
+    /// Scroll progress from `0.0` (top) to `1.0` (bottom)
+    pub progress: Signal<f64>,
+
+    /// Progress of each of the `sections` from `0.0` to `1.0` in the same order. A section
+    /// is at `0.0` before its top edge and at `1.0` after its bottom edge has been scrolled into
+    /// view.
+    pub sections: Signal<Vec<f64>>,
+}
@@ -0,0 +1,381 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{
+    use_debounce_fn, use_event_listener, use_mutation_observer_with_options, use_resize_observer,
+    ScrollBehavior, UseMutationObserverOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{scroll, scrollend};
+use leptos::*;
+
+/// Scroll positions are fractional while the sizes are rounded, so edges are detected with a
+/// little tolerance.
+const EDGE_THRESHOLD_PIXELS: f64 = 1.0;
+
+/// Reactive state of a [CSS scroll snap](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_scroll_snap)
+/// container.
+///
+/// Tracks which child of `target` is currently snapped, which makes it easy to build carousels
+/// and galleries on top of native scrolling. The snapped child is the one whose snap position,
+/// according to its `scroll-snap-align`, is closest to the current scroll position. It's
+/// determined when scrolling has ended (`scrollend` or a short debounce in browsers that don't
+/// support it) and when children are added, removed or resized.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_scroll_snap, UseScrollSnapReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let carousel = create_node_ref::<Div>();
+///
+/// let UseScrollSnapReturn {
+///     index,
+///     count,
+///     is_start,
+///     is_end,
+///     snap_to,
+///     prev,
+///     next,
+///     ..
+/// } = use_scroll_snap(carousel);
+///
+/// view! {
+///     <div
+///         node_ref=carousel
+///         style="display: flex; overflow-x: auto; scroll-snap-type: x mandatory"
+///     >
+///         <img src="1.jpg" style="flex: 0 0 100%; scroll-snap-align: start" />
+///         <img src="2.jpg" style="flex: 0 0 100%; scroll-snap-align: start" />
+///         <img src="3.jpg" style="flex: 0 0 100%; scroll-snap-align: start" />
+///     </div>
+///
+///     <button on:click=move |_| prev() disabled=is_start>"Previous"</button>
+///     {move || (0..count.get()).map(|i| {
+///         let snap_to = snap_to.clone();
+///         view! {
+///             <button on:click=move |_| snap_to(i) class:active=move || index.get() == i>
+///                 {i + 1}
+///             </button>
+///         }
+///     }).collect_view()}
+///     <button on:click=move |_| next() disabled=is_end>"Next"</button>
+/// }
+/// # }
+/// ```
+///
+/// For vertical containers set the option `axis` to `ScrollSnapAxis::Vertical`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `index` and `count` are `0`, `is_start` and `is_end` are `true` and the
+/// returned functions do nothing.
+pub fn use_scroll_snap<El, T>(
+    target: El,
+) -> UseScrollSnapReturn<
+    impl Fn(usize) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_scroll_snap_with_options(target, UseScrollSnapOptions::default())
+}
+
+/// Version of [`use_scroll_snap`] that takes a `UseScrollSnapOptions`. See [`use_scroll_snap`] for how to use.
+pub fn use_scroll_snap_with_options<El, T>(
+    target: El,
+    options: UseScrollSnapOptions,
+) -> UseScrollSnapReturn<
+    impl Fn(usize) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseScrollSnapOptions {
+        axis,
+        behavior,
+        scroll_end_delay,
+    } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let (index, set_index) = create_signal(0_usize);
+    let (count, set_count) = create_signal(0_usize);
+    let (is_start, set_start) = create_signal(true);
+    let (is_end, set_end) = create_signal(true);
+    let (is_scrolling, set_scrolling) = create_signal(false);
+
+    let update_edges = move || {
+        let Some(el) = target.get_untracked() else {
+            return;
+        };
+
+        let (position, client_size, scroll_size) = match axis {
+            ScrollSnapAxis::Horizontal => (
+                f64::from(el.scroll_left()).abs(),
+                el.client_width(),
+                el.scroll_width(),
+            ),
+            ScrollSnapAxis::Vertical => (
+                f64::from(el.scroll_top()),
+                el.client_height(),
+                el.scroll_height(),
+            ),
+        };
+
+        let start = position <= EDGE_THRESHOLD_PIXELS;
+        let end =
+            position + f64::from(client_size) >= f64::from(scroll_size) - EDGE_THRESHOLD_PIXELS;
+
+        if start != is_start.get_untracked() {
+            set_start.set(start);
+        }
+        if end != is_end.get_untracked() {
+            set_end.set(end);
+        }
+    };
+
+    let update = move || {
+        let Some(el) = target.get_untracked() else {
+            return;
+        };
+
+        let child_count = el.child_element_count() as usize;
+        if child_count != count.get_untracked() {
+            set_count.set(child_count);
+        }
+
+        #[cfg(not(feature = "ssr"))]
+        {
+            let closest = snap_offsets(&el, axis)
+                .into_iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+                .map(|(i, _)| i);
+
+            if let Some(closest) = closest {
+                if closest != index.get_untracked() {
+                    set_index.set(closest);
+                }
+            }
+        }
+
+        update_edges();
+    };
+
+    let on_scroll_end = move || {
+        set_scrolling.set(false);
+        update();
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let supports_scroll_end =
+            js_sys::Reflect::has(&window(), &"onscrollend".into()).unwrap_or_default();
+        let debounced_scroll_end = use_debounce_fn(on_scroll_end, scroll_end_delay);
+
+        let _ = use_event_listener(target, scroll, move |_| {
+            if !is_scrolling.get_untracked() {
+                set_scrolling.set(true);
+            }
+            update_edges();
+
+            if !supports_scroll_end {
+                debounced_scroll_end();
+            }
+        });
+
+        let _ = use_event_listener(target, scrollend, move |_| on_scroll_end());
+    }
+
+    let _ = use_resize_observer(target, move |_, _| update());
+
+    let _ = use_mutation_observer_with_options(
+        target,
+        move |_, _| update(),
+        UseMutationObserverOptions::default().child_list(true),
+    );
+
+    let _ = watch(move || target.get(), move |_, _, _| update(), true);
+
+    let snap_to = move |i: usize| {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let Some(el) = target.get_untracked() else {
+                return;
+            };
+            let Some(offset) = snap_offsets(&el, axis).get(i).copied() else {
+                return;
+            };
+
+            let mut scroll_options = web_sys::ScrollToOptions::new();
+            scroll_options.behavior(behavior.get_untracked().into());
+
+            match axis {
+                ScrollSnapAxis::Horizontal => {
+                    scroll_options.left(f64::from(el.scroll_left()) + offset);
+                }
+                ScrollSnapAxis::Vertical => {
+                    scroll_options.top(f64::from(el.scroll_top()) + offset);
+                }
+            }
+
+            el.scroll_to_with_scroll_to_options(&scroll_options);
+        }
+    };
+
+    let prev = move || {
+        if !is_start.get_untracked() {
+            snap_to(index.get_untracked().saturating_sub(1));
+        }
+    };
+
+    let next = move || {
+        if !is_end.get_untracked() {
+            snap_to((index.get_untracked() + 1).min(count.get_untracked().saturating_sub(1)));
+        }
+    };
+
+    UseScrollSnapReturn {
+        index: index.into(),
+        count: count.into(),
+        is_start: is_start.into(),
+        is_end: is_end.into(),
+        is_scrolling: is_scrolling.into(),
+        snap_to,
+        prev,
+        next,
+    }
+}
+
+/// Returns for every child of `container` by how many pixels the container has to be scrolled
+/// along `axis` so the child is at its snap position.
+#[cfg(not(feature = "ssr"))]
+fn snap_offsets(container: &web_sys::Element, axis: ScrollSnapAxis) -> Vec<f64> {
+    let window = window();
+
+    let container_rect = container.get_bounding_client_rect();
+    let container_style = window.get_computed_style(container).ok().flatten();
+
+    let padding = |property: &str| {
+        container_style
+            .as_ref()
+            .and_then(|style| style.get_property_value(property).ok())
+            .and_then(|value| value.trim_end_matches("px").parse::<f64>().ok())
+            .unwrap_or_default()
+    };
+
+    let (container_start, container_end) = match axis {
+        ScrollSnapAxis::Horizontal => (
+            container_rect.left() + padding("scroll-padding-left"),
+            container_rect.right() - padding("scroll-padding-right"),
+        ),
+        ScrollSnapAxis::Vertical => (
+            container_rect.top() + padding("scroll-padding-top"),
+            container_rect.bottom() - padding("scroll-padding-bottom"),
+        ),
+    };
+
+    let children = container.children();
+
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .map(|child| {
+            let rect = child.get_bounding_client_rect();
+            let (start, end) = match axis {
+                ScrollSnapAxis::Horizontal => (rect.left(), rect.right()),
+                ScrollSnapAxis::Vertical => (rect.top(), rect.bottom()),
+            };
+
+            // `scroll-snap-align` is `<block> <inline>` or a single value for both
+            let align = window
+                .get_computed_style(&child)
+                .ok()
+                .flatten()
+                .and_then(|style| style.get_property_value("scroll-snap-align").ok())
+                .unwrap_or_default();
+            let mut values = align.split_whitespace();
+            let block = values.next().unwrap_or("start").to_string();
+            let inline = values.next().map(str::to_string).unwrap_or(block.clone());
+            let align = match axis {
+                ScrollSnapAxis::Horizontal => inline,
+                ScrollSnapAxis::Vertical => block,
+            };
+
+            match align.as_str() {
+                "center" => (start + end) / 2.0 - (container_start + container_end) / 2.0,
+                "end" => end - container_end,
+                _ => start - container_start,
+            }
+        })
+        .collect()
+}
+
+/// Options for [`use_scroll_snap_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseScrollSnapOptions {
+    /// The direction in which the container scrolls. Defaults to `ScrollSnapAxis::Horizontal`.
+    axis: ScrollSnapAxis,
+
+    /// How `snap_to`, `prev` and `next` scroll. Defaults to `ScrollBehavior::Smooth`.
+    #[builder(into)]
+    behavior: MaybeSignal<ScrollBehavior>,
+
+    /// Time in ms without scroll events after which scrolling is considered to have ended in
+    /// browsers that don't support the `scrollend` event. Defaults to `150.0`.
+    scroll_end_delay: f64,
+}
+
+impl Default for UseScrollSnapOptions {
+    fn default() -> Self {
+        Self {
+            axis: ScrollSnapAxis::default(),
+            behavior: ScrollBehavior::Smooth.into(),
+            scroll_end_delay: 150.0,
+        }
+    }
+}
+
+/// The direction in which the container of [`use_scroll_snap`] scrolls.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScrollSnapAxis {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+/// Return type of [`use_scroll_snap`].
+pub struct UseScrollSnapReturn<SnapToFn, PrevFn, NextFn>
+where
+    SnapToFn: Fn(usize) + Clone + 'static,
+    PrevFn: Fn() + Clone + 'static,
+    NextFn: Fn() + Clone + 'static,
+{
+    /// Index of the child that is currently snapped
+    pub index: Signal<usize>,
+    /// Number of children of the container
+    pub count: Signal<usize>,
+    /// `true` if the container is scrolled to the start
+    pub is_start: Signal<bool>,
+    /// `true` if the container is scrolled to the end
+    pub is_end: Signal<bool>,
+    /// `true` while the container is being scrolled
+    pub is_scrolling: Signal<bool>,
+    /// Scrolls to the child with the given index
+    pub snap_to: SnapToFn,
+    /// Scrolls to the previous child
+    pub prev: PrevFn,
+    /// Scrolls to the next child
+    pub next: NextFn,
+}
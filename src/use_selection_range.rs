@@ -0,0 +1,291 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{use_document, use_event_listener};
+use leptos::ev::{input, keyup, mouseup, select, Custom};
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive selection and caret of an `<input>` or a `<textarea>`.
+///
+/// Editor toolbars and inputs with mentions or emoji pickers need to know where the caret is
+/// and have to insert text there. `insert_at_caret` and `wrap_selection` edit the text like the
+/// user typed it, so undo and redo of the browser keep working and `input` events are fired.
+///
+/// All positions are in UTF-16 code units like in JavaScript.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Textarea;
+/// # use leptos_use::{use_selection_range, UseSelectionRangeReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Textarea>();
+///
+/// let UseSelectionRangeReturn {
+///     selection_start,
+///     selection_end,
+///     selected_text,
+///     insert_at_caret,
+///     wrap_selection,
+///     ..
+/// } = use_selection_range(el);
+///
+/// view! {
+///     <button on:mousedown=|e| e.prevent_default() on:click=move |_| wrap_selection("**", "**")>
+///         "Bold"
+///     </button>
+///     <button on:mousedown=|e| e.prevent_default() on:click=move |_| insert_at_caret("@")>
+///         "Mention"
+///     </button>
+///     <textarea node_ref=el></textarea>
+///     <p>{selection_start} " - " {selection_end} ": " {selected_text}</p>
+/// }
+/// # }
+/// ```
+///
+/// The buttons prevent the default of `mousedown` so that the textarea keeps the focus.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the selection is always empty and the functions do nothing.
+pub fn use_selection_range<El, T>(
+    target: El,
+) -> UseSelectionRangeReturn<
+    impl Fn(u32, u32, SelectionDirection) + Clone,
+    impl Fn(&str) + Clone,
+    impl Fn(&str, &str) + Clone,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+    let control = move || target.get_untracked().and_then(TextControl::new);
+
+    let (selection, set_selection) = create_signal(Selection::default());
+
+    let update = move || {
+        let new_selection = control()
+            .map(|control| control.selection())
+            .unwrap_or_default();
+
+        if selection.with_untracked(|selection| *selection != new_selection) {
+            set_selection.set(new_selection);
+        }
+    };
+
+    let _ = use_event_listener(
+        use_document(),
+        Custom::<ev::Event>::new("selectionchange"),
+        move |_| update(),
+    );
+    let _ = use_event_listener(target, select, move |_| update());
+    let _ = use_event_listener(target, input, move |_| update());
+    let _ = use_event_listener(target, keyup, move |_| update());
+    let _ = use_event_listener(target, mouseup, move |_| update());
+
+    let _ = watch(move || target.get(), move |_, _, _| update(), true);
+
+    let set_range = move |start: u32, end: u32, direction: SelectionDirection| {
+        if let Some(control) = control() {
+            control.set_selection(start, end, direction);
+            update();
+        }
+    };
+
+    let insert_at_caret = move |text: &str| {
+        if let Some(control) = control() {
+            control.insert(text);
+            update();
+        }
+    };
+
+    let wrap_selection = move |before: &str, after: &str| {
+        if let Some(control) = control() {
+            let Selection { start, text, .. } = control.selection();
+
+            control.insert(&format!("{before}{text}{after}"));
+
+            let start = start + utf16_len(before);
+            control.set_selection(start, start + utf16_len(&text), SelectionDirection::None);
+            update();
+        }
+    };
+
+    UseSelectionRangeReturn {
+        selection_start: Signal::derive(move || selection.with(|selection| selection.start)),
+        selection_end: Signal::derive(move || selection.with(|selection| selection.end)),
+        direction: Signal::derive(move || selection.with(|selection| selection.direction)),
+        selected_text: Signal::derive(move || selection.with(|selection| selection.text.clone())),
+        set_range,
+        insert_at_caret,
+        wrap_selection,
+    }
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+#[derive(Clone, Default, PartialEq)]
+struct Selection {
+    start: u32,
+    end: u32,
+    direction: SelectionDirection,
+    text: String,
+}
+
+enum TextControl {
+    Input(web_sys::HtmlInputElement),
+    TextArea(web_sys::HtmlTextAreaElement),
+}
+
+impl TextControl {
+    fn new(el: web_sys::Element) -> Option<Self> {
+        if let Some(input) = el.dyn_ref::<web_sys::HtmlInputElement>() {
+            Some(Self::Input(input.clone()))
+        } else {
+            el.dyn_ref::<web_sys::HtmlTextAreaElement>()
+                .map(|textarea| Self::TextArea(textarea.clone()))
+        }
+    }
+
+    fn selection(&self) -> Selection {
+        let (start, end, direction, value) = match self {
+            Self::Input(input) => (
+                input.selection_start(),
+                input.selection_end(),
+                input.selection_direction(),
+                input.value(),
+            ),
+            Self::TextArea(textarea) => (
+                textarea.selection_start(),
+                textarea.selection_end(),
+                textarea.selection_direction(),
+                textarea.value(),
+            ),
+        };
+
+        let start = start.ok().flatten().unwrap_or_default();
+        let end = end.ok().flatten().unwrap_or_default();
+
+        let text = value
+            .encode_utf16()
+            .skip(start as usize)
+            .take(end.saturating_sub(start) as usize)
+            .collect::<Vec<_>>();
+
+        Selection {
+            start,
+            end,
+            direction: match direction.ok().flatten().as_deref() {
+                Some("forward") => SelectionDirection::Forward,
+                Some("backward") => SelectionDirection::Backward,
+                _ => SelectionDirection::None,
+            },
+            text: String::from_utf16_lossy(&text),
+        }
+    }
+
+    fn set_selection(&self, start: u32, end: u32, direction: SelectionDirection) {
+        let direction = match direction {
+            SelectionDirection::Forward => "forward",
+            SelectionDirection::Backward => "backward",
+            SelectionDirection::None => "none",
+        };
+
+        let _ = match self {
+            Self::Input(input) => input.set_selection_range_with_direction(start, end, direction),
+            Self::TextArea(textarea) => {
+                textarea.set_selection_range_with_direction(start, end, direction)
+            }
+        };
+    }
+
+    fn element(&self) -> &web_sys::HtmlElement {
+        match self {
+            Self::Input(input) => input,
+            Self::TextArea(textarea) => textarea,
+        }
+    }
+
+    /// Replaces the selection with `text` and puts the caret after it.
+    fn insert(&self, text: &str) {
+        let _ = self.element().focus();
+
+        // `execCommand` is deprecated but the only way to keep the undo history
+        let inserted = document()
+            .unchecked_into::<web_sys::HtmlDocument>()
+            .exec_command_with_show_ui_and_value("insertText", false, text)
+            .unwrap_or_default();
+
+        if inserted {
+            return;
+        }
+
+        let Selection { start, end, .. } = self.selection();
+
+        let _ = match self {
+            Self::Input(input) => input.set_range_text_with_start_and_end(text, start, end),
+            Self::TextArea(textarea) => {
+                textarea.set_range_text_with_start_and_end(text, start, end)
+            }
+        };
+
+        let caret = start + utf16_len(text);
+        self.set_selection(caret, caret, SelectionDirection::None);
+
+        let mut init = web_sys::EventInit::new();
+        init.bubbles(true);
+        if let Ok(event) = web_sys::Event::new_with_event_init_dict("input", &init) {
+            let _ = self.element().dispatch_event(&event);
+        }
+    }
+}
+
+/// Direction of a selection of [`use_selection_range`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SelectionDirection {
+    /// The selection has been made from start to end
+    Forward,
+    /// The selection has been made from end to start
+    Backward,
+    /// The direction is unknown or the selection is collapsed
+    #[default]
+    None,
+}
+
+/// Return type of [`use_selection_range`].
+pub struct UseSelectionRangeReturn<SetRangeFn, InsertFn, WrapFn>
+where
+    SetRangeFn: Fn(u32, u32, SelectionDirection) + Clone,
+    InsertFn: Fn(&str) + Clone,
+    WrapFn: Fn(&str, &str) + Clone,
+{
+    /// Start of the selection or position of the caret
+    pub selection_start: Signal<u32>,
+
+    /// End of the selection. Equal to `selection_start` if nothing is selected.
+    pub selection_end: Signal<u32>,
+
+    /// Direction of the selection
+    pub direction: Signal<SelectionDirection>,
+
+    /// The selected text
+    pub selected_text: Signal<String>,
+
+    /// Selects the text from `start` to `end`. Setting both to the same value moves the caret.
+    pub set_range: SetRangeFn,
+
+    /// Replaces the selection with the text or inserts it at the caret
+    pub insert_at_caret: InsertFn,
+
+    /// Puts the first text before and the second text after the selection and keeps the
+    /// original text selected, like `wrap_selection("**", "**")` for bold text in Markdown
+    pub wrap_selection: WrapFn,
+}
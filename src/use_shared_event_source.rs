@@ -0,0 +1,140 @@
+use crate::core::{ConnectionReadyState, SharedConnections};
+use crate::utils::StringDecoder;
+use crate::{
+    use_event_source_with_options, UseEventSourceError, UseEventSourceOptions, UseEventSourceReturn,
+};
+use leptos::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Version of [`fn@crate::use_event_source`] that shares one connection per URL between all
+/// components that call it.
+///
+/// The first call for a URL opens the connection. Later calls with the same URL, value type and
+/// codec subscribe to it and get the same signals, so every event is received by all of them.
+/// The connection is closed when the last subscribing component is unmounted.
+///
+/// The connection is shared between the components below the one that called
+/// [`provide_shared_connections`]. Without it only components below the first caller share
+/// their connections.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_shared_event_source, UseEventSourceReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// #[component]
+/// fn Ticker() -> impl IntoView {
+///     let UseEventSourceReturn { data, .. } =
+///         use_shared_event_source::<String, FromToStringCodec>("https://event-source-url");
+///
+///     view! { <p>{data}</p> }
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// // only one connection is opened
+/// view! {
+///     <Ticker />
+///     <Ticker />
+///     <Ticker />
+/// }
+/// # }
+/// ```
+///
+/// The options are only used by the call that opens the connection. They're ignored while the
+/// connection is shared. `open` and `close` affect all subscribers.
+///
+/// ## Server-Side Rendering
+///
+/// Works the same as [`fn@crate::use_event_source`] on the server.
+///
+/// [`provide_shared_connections`]: crate::core::provide_shared_connections
+pub fn use_shared_event_source<T, C>(
+    url: &str,
+) -> UseEventSourceReturn<T, C::Error, impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
+where
+    T: Clone + PartialEq + 'static,
+    C: StringDecoder<T> + 'static,
+{
+    use_shared_event_source_with_options::<T, C>(url, UseEventSourceOptions::<T>::default())
+}
+
+/// Version of [`use_shared_event_source`] that takes a `UseEventSourceOptions`. See [`use_shared_event_source`] for how to use.
+pub fn use_shared_event_source_with_options<T, C>(
+    url: &str,
+    options: UseEventSourceOptions<T>,
+) -> UseEventSourceReturn<T, C::Error, impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
+where
+    T: Clone + PartialEq + 'static,
+    C: StringDecoder<T> + 'static,
+{
+    let shared = SharedConnections::use_or_provide().subscribe(url, {
+        let url = url.to_string();
+
+        move || {
+            let UseEventSourceReturn {
+                data,
+                ready_state,
+                event,
+                error,
+                reconnect_attempts,
+                reconnect_countdown,
+                open,
+                close,
+                event_source,
+            } = use_event_source_with_options::<T, C>(&url, options);
+
+            SharedEventSource::<T, C> {
+                data,
+                ready_state,
+                event,
+                error,
+                reconnect_attempts,
+                reconnect_countdown,
+                event_source,
+                open: Rc::new(open),
+                close: Rc::new(close),
+                _marker: PhantomData,
+            }
+        }
+    });
+
+    UseEventSourceReturn {
+        data: shared.data,
+        ready_state: shared.ready_state,
+        event: shared.event,
+        error: shared.error,
+        reconnect_attempts: shared.reconnect_attempts,
+        reconnect_countdown: shared.reconnect_countdown,
+        open: {
+            let open = Rc::clone(&shared.open);
+            move || open()
+        },
+        close: {
+            let close = Rc::clone(&shared.close);
+            move || close()
+        },
+        event_source: shared.event_source,
+    }
+}
+
+/// The parts of a [`UseEventSourceReturn`] that are handed to every subscriber.
+struct SharedEventSource<T, C>
+where
+    T: 'static,
+    C: StringDecoder<T> + 'static,
+{
+    data: Signal<Option<T>>,
+    ready_state: Signal<ConnectionReadyState>,
+    event: Signal<Option<web_sys::Event>>,
+    error: Signal<Option<UseEventSourceError<C::Error>>>,
+    reconnect_attempts: Signal<u64>,
+    reconnect_countdown: Signal<Option<u64>>,
+    event_source: Signal<Option<web_sys::EventSource>>,
+    open: Rc<dyn Fn()>,
+    close: Rc<dyn Fn()>,
+    _marker: PhantomData<C>,
+}
@@ -0,0 +1,246 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::StorageType;
+use crate::storage::{use_storage_with_options, UseStorageOptions};
+use crate::{use_broadcast_channel, watch, UseBroadcastChannelReturn};
+use codee::string::FromToStringCodec;
+use codee::{CodecError, Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+
+/// A signal that is shared between all tabs of the same origin.
+///
+/// Changes are sent to the other tabs through a `BroadcastChannel` and show up there instantly.
+/// The value is also persisted in storage so it survives reloads. When a tab is opened it asks the
+/// other tabs for the current value. This way it is up to date even if the value isn't persisted
+/// or has been changed in a tab that uses a different storage like session storage.
+///
+/// Values are (en)decoded via the given codec. You can use any of the string codecs or a
+/// binary codec wrapped in `Base64`.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_shared_signal, UseSharedSignalReturn};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseSharedSignalReturn {
+///     value: compact,
+///     set_value: set_compact,
+///     ..
+/// } = use_shared_signal::<bool, FromToStringCodec>("compact-mode");
+///
+/// view! {
+///     <label>
+///         <input
+///             type="checkbox"
+///             prop:checked=compact
+///             on:change=move |ev| set_compact.set(event_target_checked(&ev))
+///         />
+///         "Compact mode"
+///     </label>
+/// }
+/// # }
+/// ```
+///
+/// ## Storage
+///
+/// By default the value is persisted in local storage under `key`. You can choose a different
+/// storage or disable persistence with the `storage` option.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_shared_signal_with_options, UseSharedSignalOptions};
+/// # use codee::string::FromToStringCodec;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let shared = use_shared_signal_with_options::<u32, FromToStringCodec>(
+///     "unread-count",
+///     UseSharedSignalOptions::default().storage(None),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the value is `T::default()` and nothing is shared or persisted.
+///
+/// ## See also
+///
+/// * [`fn@crate::use_broadcast_channel`]
+/// * [`fn@crate::storage::use_storage`]
+/// * [`fn@crate::use_replicated_signal`]
+pub fn use_shared_signal<T, C>(
+    key: impl Into<String>,
+) -> UseSharedSignalReturn<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error>
+where
+    T: Default + Clone + PartialEq + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    use_shared_signal_with_options::<T, C>(key, UseSharedSignalOptions::default())
+}
+
+/// Version of [`use_shared_signal`] that takes a `UseSharedSignalOptions`. See [`use_shared_signal`] for how to use.
+pub fn use_shared_signal_with_options<T, C>(
+    key: impl Into<String>,
+    options: UseSharedSignalOptions,
+) -> UseSharedSignalReturn<T, <C as Encoder<T>>::Error, <C as Decoder<T>>::Error>
+where
+    T: Default + Clone + PartialEq + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    let UseSharedSignalOptions { storage } = options;
+
+    let key = key.into();
+
+    let (value, set_value) = match storage {
+        Some(storage) => {
+            let (value, set_value, _) = use_storage_with_options::<T, C>(
+                storage,
+                &key,
+                // the other tabs are kept in sync through the channel
+                UseStorageOptions::default().listen_to_storage_changes(false),
+            );
+
+            (value, set_value)
+        }
+        None => {
+            let (value, set_value) = create_signal(T::default());
+            (value.into(), set_value)
+        }
+    };
+
+    let (error, set_error) = create_signal(None::<CodecError<_, _>>);
+
+    // Encoded form of the latest value that has been sent or received. Used to avoid sending
+    // received values back.
+    let last_payload = store_value(None::<String>);
+
+    let UseBroadcastChannelReturn { message, post, .. } =
+        use_broadcast_channel::<String, FromToStringCodec>(&format!(
+            "leptos-use-shared-signal-{key}"
+        ));
+
+    let _ = watch(move || message.get(), {
+        let post = post.clone();
+
+        move |message, _, _| {
+            let Some(message) = message else {
+                return;
+            };
+
+            match SharedMessage::decode(message) {
+                Some(SharedMessage::Value(payload)) => match C::decode(&payload) {
+                    Ok(new_value) => {
+                        last_payload.set_value(Some(payload));
+                        set_value.set(new_value);
+                    }
+                    Err(err) => set_error.set(Some(CodecError::Decode(err))),
+                },
+                Some(SharedMessage::Request) => {
+                    // answer with the current value which might come from storage or be the
+                    // initial value and thus hasn't been sent yet
+                    match value.with_untracked(C::encode) {
+                        Ok(payload) => {
+                            last_payload.set_value(Some(payload.clone()));
+                            post(&SharedMessage::Value(payload).encode());
+                        }
+                        Err(err) => set_error.set(Some(CodecError::Encode(err))),
+                    }
+                }
+                None => {}
+            }
+        }
+    });
+
+    let _ = watch(move || value.get(), {
+        let post = post.clone();
+
+        move |value, _, _| match C::encode(value) {
+            Ok(payload) => {
+                if last_payload.with_value(|last| last.as_ref() == Some(&payload)) {
+                    return;
+                }
+
+                last_payload.set_value(Some(payload.clone()));
+                post(&SharedMessage::Value(payload).encode());
+            }
+            Err(err) => set_error.set(Some(CodecError::Encode(err))),
+        }
+    });
+
+    #[cfg(not(feature = "ssr"))]
+    post(&SharedMessage::Request.encode());
+
+    UseSharedSignalReturn {
+        value,
+        set_value,
+        error: error.into(),
+    }
+}
+
+/// Options for [`use_shared_signal_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSharedSignalOptions {
+    /// Storage the value is persisted in. `None` disables persistence.
+    /// Defaults to `Some(StorageType::Local)`.
+    storage: Option<StorageType>,
+}
+
+impl Default for UseSharedSignalOptions {
+    fn default() -> Self {
+        Self {
+            storage: Some(StorageType::Local),
+        }
+    }
+}
+
+/// Return type of [`use_shared_signal`].
+pub struct UseSharedSignalReturn<T, E, D>
+where
+    T: 'static,
+    E: 'static,
+    D: 'static,
+{
+    /// The current value
+    pub value: Signal<T>,
+
+    /// Sets the value in this and all other tabs
+    pub set_value: WriteSignal<T>,
+
+    /// The latest error that occurred while (en)decoding a value
+    pub error: Signal<Option<CodecError<E, D>>>,
+}
+
+enum SharedMessage {
+    /// The current value
+    Value(String),
+    /// Asks the other tabs for the current value
+    Request,
+}
+
+impl SharedMessage {
+    fn encode(&self) -> String {
+        match self {
+            Self::Value(payload) => format!("v\t{payload}"),
+            Self::Request => "r".to_string(),
+        }
+    }
+
+    fn decode(message: &str) -> Option<Self> {
+        match message.split_once('\t') {
+            Some(("v", payload)) => Some(Self::Value(payload.to_string())),
+            None if message == "r" => Some(Self::Request),
+            _ => None,
+        }
+    }
+}
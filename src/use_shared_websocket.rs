@@ -0,0 +1,182 @@
+use crate::core::{ConnectionReadyState, SharedConnections};
+use crate::{use_websocket_with_options, UseWebSocketOptions, UseWebSocketReturn};
+use codee::{Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary};
+use leptos::*;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use web_sys::WebSocket;
+
+/// Version of [`fn@crate::use_websocket`] that shares one connection per URL between all
+/// components that call it.
+///
+/// The first call for a URL opens the connection. Later calls with the same URL, message type
+/// and codec subscribe to it and get the same signals, so every message is received by all of
+/// them and all of them can send through it. The connection is closed when the last subscribing
+/// component is unmounted.
+///
+/// The connection is shared between the components below the one that called
+/// [`provide_shared_connections`]. Without it only components below the first caller share
+/// their connections.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_shared_websocket, UseWebSocketReturn};
+/// # use leptos_use::core::provide_shared_connections;
+/// #
+/// #[component]
+/// fn Chat() -> impl IntoView {
+///     let UseWebSocketReturn { message, send, .. } =
+///         use_shared_websocket::<String, FromToStringCodec>("wss://chat.example.com/");
+///
+///     view! {
+///         <p>{message}</p>
+///         <button on:click=move |_| send(&"Hi".to_string())>"Say hi"</button>
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     provide_shared_connections();
+///
+///     // both use the same connection
+///     view! {
+///         <Chat />
+///         <Chat />
+///     }
+/// }
+/// ```
+///
+/// The options are only used by the call that opens the connection. They're ignored while the
+/// connection is shared, including the callbacks like `on_message`. Use `subscribe` if every
+/// component needs to see every message and not only the latest one. `open` and `close` affect
+/// all subscribers.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the returned functions amount to no-ops.
+///
+/// [`provide_shared_connections`]: crate::core::provide_shared_connections
+pub fn use_shared_websocket<T, C>(
+    url: &str,
+) -> UseWebSocketReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() -> Signal<Option<T>> + Clone + 'static,
+>
+where
+    T: 'static,
+    C: Encoder<T> + Decoder<T> + 'static,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    use_shared_websocket_with_options::<T, C>(url, UseWebSocketOptions::default())
+}
+
+/// Version of [`use_shared_websocket`] that takes `UseWebSocketOptions`. See [`use_shared_websocket`] for how to use.
+pub fn use_shared_websocket_with_options<T, C>(
+    url: &str,
+    options: UseWebSocketOptions<
+        T,
+        HybridCoderError<<C as Encoder<T>>::Error>,
+        HybridCoderError<<C as Decoder<T>>::Error>,
+    >,
+) -> UseWebSocketReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn(&T) + Clone + 'static,
+    impl Fn() -> Signal<Option<T>> + Clone + 'static,
+>
+where
+    T: 'static,
+    C: Encoder<T> + Decoder<T> + 'static,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    let shared = SharedConnections::use_or_provide().subscribe(url, {
+        let url = url.to_string();
+
+        move || {
+            let UseWebSocketReturn {
+                ready_state,
+                message,
+                reconnect_attempts,
+                reconnect_countdown,
+                last_heartbeat,
+                buffered_amount,
+                ws,
+                open,
+                close,
+                send,
+                subscribe,
+            } = use_websocket_with_options::<T, C>(&url, options);
+
+            SharedWebSocket::<T, C> {
+                ready_state,
+                message,
+                reconnect_attempts,
+                reconnect_countdown,
+                last_heartbeat,
+                buffered_amount,
+                ws,
+                open: Rc::new(open),
+                close: Rc::new(close),
+                send: Rc::new(send),
+                subscribe: Rc::new(subscribe),
+                _marker: PhantomData,
+            }
+        }
+    });
+
+    UseWebSocketReturn {
+        ready_state: shared.ready_state,
+        message: shared.message,
+        reconnect_attempts: shared.reconnect_attempts,
+        reconnect_countdown: shared.reconnect_countdown,
+        last_heartbeat: shared.last_heartbeat,
+        buffered_amount: shared.buffered_amount,
+        ws: shared.ws.clone(),
+        open: {
+            let open = Rc::clone(&shared.open);
+            move || open()
+        },
+        close: {
+            let close = Rc::clone(&shared.close);
+            move || close()
+        },
+        send: {
+            let send = Rc::clone(&shared.send);
+            move |value: &T| send(value)
+        },
+        subscribe: {
+            let subscribe = Rc::clone(&shared.subscribe);
+            move || subscribe()
+        },
+    }
+}
+
+/// The parts of a [`UseWebSocketReturn`] that are handed to every subscriber.
+struct SharedWebSocket<T, C>
+where
+    T: 'static,
+{
+    ready_state: Signal<ConnectionReadyState>,
+    message: Signal<Option<T>>,
+    reconnect_attempts: Signal<u64>,
+    reconnect_countdown: Signal<Option<u64>>,
+    last_heartbeat: Signal<Option<f64>>,
+    buffered_amount: Signal<usize>,
+    ws: Option<WebSocket>,
+    open: Rc<dyn Fn()>,
+    close: Rc<dyn Fn()>,
+    send: Rc<dyn Fn(&T)>,
+    subscribe: Rc<dyn Fn() -> Signal<Option<T>>>,
+    _marker: PhantomData<C>,
+}
@@ -0,0 +1,386 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{use_scratch_with_options, ScratchStroke, UseScratchOptions, UseScratchReturn};
+use default_struct_builder::DefaultBuilder;
+use futures_util::future::{self, FutureExt, LocalBoxFuture};
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+/// Signature pad that can export the signature as PNG or SVG.
+///
+/// The strokes are captured with [`fn@crate::use_scratch`] on `target`. `to_svg` and `to_png`
+/// export them in the size of `target` as a `Blob` that can be uploaded or downloaded. The PNG
+/// is rendered with the `devicePixelRatio` of the screen so it's as crisp as the signature that
+/// has been drawn.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_signature_pad, UseSignaturePadReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let pad = create_node_ref::<Div>();
+///
+/// let UseSignaturePadReturn {
+///     svg_path,
+///     is_empty,
+///     undo,
+///     clear,
+///     to_png,
+///     ..
+/// } = use_signature_pad(pad);
+///
+/// let submit = move |_| {
+///     let png = to_png();
+///
+///     spawn_local(async move {
+///         if let Ok(blob) = png.await {
+///             // upload the blob
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <div node_ref=pad style="touch-action: none; width: 400px; height: 160px">
+///         <svg width="400" height="160">
+///             <path
+///                 d=svg_path
+///                 fill="none"
+///                 stroke="black"
+///                 stroke-width="2"
+///                 stroke-linecap="round"
+///                 stroke-linejoin="round"
+///             />
+///         </svg>
+///     </div>
+///     <button on:click=move |_| undo() disabled=is_empty>"Undo"</button>
+///     <button on:click=move |_| clear() disabled=is_empty>"Clear"</button>
+///     <button on:click=submit disabled=is_empty>"Submit"</button>
+/// }
+/// # }
+/// ```
+///
+/// The options `pen_color`, `pen_width` and `background` define how the signature looks in the
+/// exported images. Use the same values for the preview as in the example above.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the pad is empty. `to_svg` and `to_png` return `Err(UseSignaturePadError::NoElement)`.
+#[allow(clippy::type_complexity)]
+pub fn use_signature_pad<El, T>(
+    target: El,
+) -> UseSignaturePadReturn<
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() -> Result<web_sys::Blob, UseSignaturePadError> + Clone + 'static,
+    impl Fn() -> LocalBoxFuture<'static, Result<web_sys::Blob, UseSignaturePadError>> + Clone + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_signature_pad_with_options(target, UseSignaturePadOptions::default())
+}
+
+/// Version of [`use_signature_pad`] that takes a `UseSignaturePadOptions`. See [`use_signature_pad`] for how to use.
+#[allow(clippy::type_complexity)]
+pub fn use_signature_pad_with_options<El, T>(
+    target: El,
+    options: UseSignaturePadOptions,
+) -> UseSignaturePadReturn<
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() -> Result<web_sys::Blob, UseSignaturePadError> + Clone + 'static,
+    impl Fn() -> LocalBoxFuture<'static, Result<web_sys::Blob, UseSignaturePadError>> + Clone + 'static,
+>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseSignaturePadOptions {
+        pen_color,
+        pen_width,
+        background,
+        scale,
+        min_distance,
+        simplify_tolerance,
+    } = options;
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let UseScratchReturn {
+        strokes,
+        set_strokes,
+        is_drawing,
+        svg_path,
+        clear,
+        undo,
+        ..
+    } = use_scratch_with_options(
+        target,
+        UseScratchOptions::default()
+            .min_distance(min_distance)
+            .simplify_tolerance(simplify_tolerance),
+    );
+
+    // the export only contains finished strokes
+    let finished_path = Signal::derive(move || {
+        strokes.with(|strokes| {
+            strokes
+                .iter()
+                .map(|stroke| stroke.to_svg_path())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    });
+
+    // size of the target in CSS pixels
+    let size = move || {
+        target.get_untracked().map(|element| {
+            (
+                element.client_width().max(0) as u32,
+                element.client_height().max(0) as u32,
+            )
+        })
+    };
+
+    let to_svg = {
+        let pen_color = pen_color.clone();
+        let background = background.clone();
+
+        move || -> Result<web_sys::Blob, UseSignaturePadError> {
+            let (width, height) = size().ok_or(UseSignaturePadError::NoElement)?;
+
+            let background = background
+                .as_ref()
+                .map(|background| {
+                    format!(
+                        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+                        escape_attribute(background)
+                    )
+                })
+                .unwrap_or_default();
+
+            let path = finished_path.get_untracked();
+            let color = escape_attribute(&pen_color);
+
+            let svg = format!(
+                concat!(
+                    r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" "#,
+                    r#"viewBox="0 0 {width} {height}">{background}"#,
+                    r#"<path d="{path}" fill="none" stroke="{color}" stroke-width="{pen_width}" "#,
+                    r#"stroke-linecap="round" stroke-linejoin="round"/></svg>"#,
+                ),
+                width = width,
+                height = height,
+                background = background,
+                path = path,
+                color = color,
+                pen_width = pen_width,
+            );
+
+            let mut blob_options = web_sys::BlobPropertyBag::new();
+            blob_options.type_("image/svg+xml");
+
+            web_sys::Blob::new_with_str_sequence_and_options(
+                &js_sys::Array::of1(&JsValue::from_str(&svg)),
+                &blob_options,
+            )
+            .map_err(UseSignaturePadError::Export)
+        }
+    };
+
+    let to_png = move || -> LocalBoxFuture<'static, Result<web_sys::Blob, UseSignaturePadError>> {
+        #[cfg(feature = "ssr")]
+        return future::ready(Err(UseSignaturePadError::NoElement)).boxed_local();
+
+        #[cfg(not(feature = "ssr"))]
+        {
+            let Some((width, height)) = size() else {
+                return future::ready(Err(UseSignaturePadError::NoElement)).boxed_local();
+            };
+
+            let scale = scale.unwrap_or_else(|| window().device_pixel_ratio());
+
+            let canvas = draw_canvas(
+                width,
+                height,
+                scale,
+                &finished_path.get_untracked(),
+                &pen_color,
+                pen_width,
+                background.as_deref(),
+            );
+
+            async move {
+                let canvas = canvas.map_err(UseSignaturePadError::Export)?;
+                encode_png(&canvas)
+                    .await
+                    .map_err(UseSignaturePadError::Export)
+            }
+            .boxed_local()
+        }
+    };
+
+    UseSignaturePadReturn {
+        strokes,
+        set_strokes,
+        svg_path,
+        is_empty: Signal::derive(move || strokes.with(Vec::is_empty) && !is_drawing.get()),
+        is_drawing,
+        undo,
+        clear,
+        to_svg,
+        to_png,
+    }
+}
+
+/// Draws the path onto a new canvas with `scale` device pixels per CSS pixel.
+#[cfg(not(feature = "ssr"))]
+fn draw_canvas(
+    width: u32,
+    height: u32,
+    scale: f64,
+    path: &str,
+    pen_color: &str,
+    pen_width: f64,
+    background: Option<&str>,
+) -> Result<web_sys::HtmlCanvasElement, JsValue> {
+    let canvas: web_sys::HtmlCanvasElement = document().create_element("canvas")?.unchecked_into();
+    canvas.set_width((width as f64 * scale).round() as u32);
+    canvas.set_height((height as f64 * scale).round() as u32);
+
+    let context: web_sys::CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2d context not available"))?
+        .unchecked_into();
+
+    // draw in CSS pixels
+    context.scale(scale, scale)?;
+
+    if let Some(background) = background {
+        context.set_fill_style(&JsValue::from_str(background));
+        context.fill_rect(0.0, 0.0, width as f64, height as f64);
+    }
+
+    context.set_stroke_style(&JsValue::from_str(pen_color));
+    context.set_line_width(pen_width);
+    context.set_line_cap("round");
+    context.set_line_join("round");
+    context.stroke_with_path(&web_sys::Path2d::new_with_path_string(path)?);
+
+    Ok(canvas)
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn encode_png(canvas: &web_sys::HtmlCanvasElement) -> Result<web_sys::Blob, JsValue> {
+    let mut to_blob_result = Ok(());
+    let blob = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_blob = Closure::once_into_js(move |blob: JsValue| {
+            if blob.is_null() {
+                let _ = reject.call1(
+                    &JsValue::UNDEFINED,
+                    &JsValue::from_str("canvas couldn't be encoded"),
+                );
+            } else {
+                let _ = resolve.call1(&JsValue::UNDEFINED, &blob);
+            }
+        });
+
+        to_blob_result = canvas.to_blob(on_blob.unchecked_ref());
+    });
+    to_blob_result?;
+
+    wasm_bindgen_futures::JsFuture::from(blob)
+        .await
+        .map(JsCast::unchecked_into)
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+/// Options for [`use_signature_pad_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSignaturePadOptions {
+    /// CSS color of the strokes in the exported images. Defaults to `"black"`.
+    #[builder(into)]
+    pen_color: String,
+
+    /// Width of the strokes in the exported images in CSS pixels. Defaults to `2.0`.
+    pen_width: f64,
+
+    /// CSS color of the background of the exported images. Defaults to `None` which is
+    /// transparent.
+    #[builder(into)]
+    background: Option<String>,
+
+    /// Device pixels per CSS pixel of the exported PNG. Defaults to `None` which uses
+    /// `window.devicePixelRatio`.
+    #[builder(into)]
+    scale: Option<f64>,
+
+    /// See the option of the same name of [`fn@crate::use_scratch`]. Defaults to `1.0`.
+    min_distance: f64,
+
+    /// See the option of the same name of [`fn@crate::use_scratch`]. Defaults to `0.5`.
+    simplify_tolerance: f64,
+}
+
+impl Default for UseSignaturePadOptions {
+    fn default() -> Self {
+        Self {
+            pen_color: "black".to_string(),
+            pen_width: 2.0,
+            background: None,
+            scale: None,
+            min_distance: 1.0,
+            simplify_tolerance: 0.5,
+        }
+    }
+}
+
+/// Return type of [`use_signature_pad`].
+pub struct UseSignaturePadReturn<UndoFn, ClearFn, ToSvgFn, ToPngFn>
+where
+    UndoFn: Fn() + Clone,
+    ClearFn: Fn() + Clone,
+    ToSvgFn: Fn() -> Result<web_sys::Blob, UseSignaturePadError> + Clone,
+    ToPngFn: Fn() -> LocalBoxFuture<'static, Result<web_sys::Blob, UseSignaturePadError>> + Clone,
+{
+    /// The strokes of the signature
+    pub strokes: Signal<Vec<ScratchStroke>>,
+    /// Sets the strokes, for example to restore a saved signature
+    pub set_strokes: WriteSignal<Vec<ScratchStroke>>,
+    /// SVG path data of all strokes including the one that is being drawn for the preview
+    pub svg_path: Signal<String>,
+    /// `true` if nothing has been drawn
+    pub is_empty: Signal<bool>,
+    /// `true` while a stroke is being drawn
+    pub is_drawing: Signal<bool>,
+    /// Removes the last stroke
+    pub undo: UndoFn,
+    /// Removes all strokes
+    pub clear: ClearFn,
+    /// Exports the signature as an SVG image
+    pub to_svg: ToSvgFn,
+    /// Exports the signature as a PNG image
+    pub to_png: ToPngFn,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum UseSignaturePadError {
+    #[error("no element to export")]
+    NoElement,
+    #[error("failed to export signature: {0:?}")]
+    Export(JsValue),
+}
@@ -0,0 +1,251 @@
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Reactive sorted, filtered and windowed view of a list.
+///
+/// Meant for tables and long lists where the user picks the column to sort by, the direction
+/// and a search query. The items are never moved. Instead the hook keeps a list of indices into
+/// `items` which is only sorted again if the items, the filter, the sort column or the
+/// direction change. Changing the `window` only clones the items inside of it. This makes it
+/// cheap to render a small visible part of a large list, e.g. in a virtual list.
+///
+/// Sorting is stable so items that compare equal keep their original order.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_sort_and_filter, SortDirection, UseSortAndFilterOptions, UseSortAndFilterReturn};
+/// #
+/// #[derive(Clone, PartialEq)]
+/// pub struct Person {
+///     pub name: String,
+///     pub age: u16,
+/// }
+///
+/// #[derive(Clone, PartialEq)]
+/// pub enum Column {
+///     Name,
+///     Age,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let people: Vec<Person> = vec![];
+/// let (query, set_query) = create_signal(String::new());
+/// let (column, set_column) = create_signal(Some(Column::Name));
+/// let (direction, set_direction) = create_signal(SortDirection::Ascending);
+///
+/// let UseSortAndFilterReturn { items, len, .. } = use_sort_and_filter(
+///     people,
+///     UseSortAndFilterOptions::default()
+///         .sort_column(column)
+///         .direction(direction)
+///         .compare(|column: &Column, a: &Person, b: &Person| match column {
+///             Column::Name => a.name.cmp(&b.name),
+///             Column::Age => a.age.cmp(&b.age),
+///         })
+///         // signals read in the filter are tracked
+///         .filter(move |person: &Person| person.name.contains(&query.get())),
+/// );
+///
+/// view! {
+///     <input on:input=move |ev| set_query.set(event_target_value(&ev)) />
+///     <p>{len} " people found"</p>
+///     <ul>
+///         <For each=move || items.get() key=|person| person.name.clone() let:person>
+///             <li>{person.name}</li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+///
+/// ## Windowing
+///
+/// With `window` only a range of the sorted and filtered items is returned in `items`. `len`
+/// is still the number of all items that passed the filter.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_sort_and_filter, UseSortAndFilterOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let rows: Vec<u32> = (0..10_000).collect();
+/// let (visible, set_visible) = create_signal(Some(0..50));
+///
+/// let view = use_sort_and_filter(
+///     rows,
+///     UseSortAndFilterOptions::<u32, ()>::default()
+///         .filter(|row: &u32| row % 2 == 0)
+///         .window(visible),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## See also
+///
+/// * [`fn@crate::use_sorted`]
+pub fn use_sort_and_filter<T, C>(
+    items: impl Into<MaybeSignal<Vec<T>>>,
+    options: UseSortAndFilterOptions<T, C>,
+) -> UseSortAndFilterReturn<T>
+where
+    T: Clone + PartialEq + 'static,
+    C: 'static,
+{
+    let UseSortAndFilterOptions {
+        sort_column,
+        direction,
+        compare,
+        filter,
+        window,
+    } = options;
+
+    let items: MaybeSignal<Vec<T>> = items.into();
+    let items = Signal::from(items);
+
+    let filtered = create_memo(move |_| {
+        items.with(|items| {
+            items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| filter(item))
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let indices = create_memo(move |_| {
+        let mut indices = filtered.get();
+
+        sort_column.with(|column| {
+            if let Some(column) = column {
+                let direction = direction.get();
+
+                items.with(|items| {
+                    indices.sort_by(|a, b| {
+                        let ordering = compare(column, &items[*a], &items[*b]);
+
+                        match direction {
+                            SortDirection::Ascending => ordering,
+                            SortDirection::Descending => ordering.reverse(),
+                        }
+                    })
+                });
+            }
+        });
+
+        indices
+    });
+
+    let len = Signal::derive(move || indices.with(Vec::len));
+
+    let windowed = create_memo(move |_| {
+        indices.with(|indices| {
+            let Range { start, end } = window.get().unwrap_or(0..indices.len());
+
+            let end = end.min(indices.len());
+            let start = start.min(end);
+
+            items.with(|items| {
+                indices[start..end]
+                    .iter()
+                    .map(|index| items[*index].clone())
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    UseSortAndFilterReturn {
+        items: windowed.into(),
+        indices: indices.into(),
+        len,
+    }
+}
+
+/// Options for [`use_sort_and_filter`].
+#[derive(DefaultBuilder)]
+pub struct UseSortAndFilterOptions<T, C>
+where
+    T: 'static,
+    C: 'static,
+{
+    /// The column to sort by. It is passed to `compare`. `None` keeps the original order.
+    /// Defaults to `None`.
+    #[builder(into)]
+    sort_column: MaybeSignal<Option<C>>,
+
+    /// Sort direction. Defaults to `SortDirection::Ascending`.
+    #[builder(into)]
+    direction: MaybeSignal<SortDirection>,
+
+    /// Compares two items by the given column. Defaults to treating all items as equal.
+    compare: Rc<dyn Fn(&C, &T, &T) -> Ordering>,
+
+    /// Only items for which this returns `true` are kept. Signals that are read in here are
+    /// tracked. Defaults to keeping all items.
+    filter: Rc<dyn Fn(&T) -> bool>,
+
+    /// Range of the sorted and filtered items that is returned in `items`. `None` returns all
+    /// of them. Defaults to `None`.
+    #[builder(into)]
+    window: MaybeSignal<Option<Range<usize>>>,
+}
+
+impl<T, C> Default for UseSortAndFilterOptions<T, C>
+where
+    T: 'static,
+    C: 'static,
+{
+    fn default() -> Self {
+        Self {
+            sort_column: MaybeSignal::Static(None),
+            direction: MaybeSignal::default(),
+            compare: Rc::new(|_, _, _| Ordering::Equal),
+            filter: Rc::new(|_| true),
+            window: MaybeSignal::Static(None),
+        }
+    }
+}
+
+/// Sort direction of [`use_sort_and_filter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    /// Smallest item first
+    #[default]
+    Ascending,
+    /// Largest item first
+    Descending,
+}
+
+impl SortDirection {
+    /// Returns the opposite direction.
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+/// Return type of [`use_sort_and_filter`].
+pub struct UseSortAndFilterReturn<T>
+where
+    T: 'static,
+{
+    /// The sorted and filtered items inside of `window`
+    pub items: Signal<Vec<T>>,
+
+    /// Indices into the original items of all sorted and filtered items, ignoring `window`
+    pub indices: Signal<Vec<usize>>,
+
+    /// Number of items that passed the filter, ignoring `window`
+    pub len: Signal<usize>,
+}
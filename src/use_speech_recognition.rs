@@ -0,0 +1,264 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use wasm_bindgen::JsCast;
+use web_sys::{SpeechRecognition, SpeechRecognitionEvent};
+
+/// Reactive [SpeechRecognition](https://developer.mozilla.org/en-US/docs/Web/API/SpeechRecognition)
+/// of the Web Speech API.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_speech_recognition, UseSpeechRecognitionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseSpeechRecognitionReturn {
+///     is_supported,
+///     is_listening,
+///     result,
+///     interim_result,
+///     start,
+///     stop,
+///     ..
+/// } = use_speech_recognition();
+///
+/// view! {
+///     <button on:click=move |_| start() disabled=is_listening>"Start"</button>
+///     <button on:click=move |_| stop() disabled=move || !is_listening.get()>"Stop"</button>
+///     <p>{result} <i>{interim_result}</i></p>
+/// }
+/// # }
+/// ```
+///
+/// While the user is speaking, the recognized text that might still change is available as
+/// `interim_result`. Once the browser is confident about it, it's appended to `result`. Both are
+/// reset when `start` is called again.
+///
+/// ### Options
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_speech_recognition_with_options, UseSpeechRecognitionOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (lang, set_lang) = create_signal("de-DE".to_string());
+///
+/// let recognition = use_speech_recognition_with_options(
+///     UseSpeechRecognitionOptions::default()
+///         .lang(lang)
+///         .continuous(false)
+///         .interim_results(false),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Changes of `lang` are applied the next time `start` is called.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` is always `false` and `start` and `stop` do nothing.
+pub fn use_speech_recognition() -> UseSpeechRecognitionReturn<impl Fn() + Clone, impl Fn() + Clone>
+{
+    use_speech_recognition_with_options(UseSpeechRecognitionOptions::default())
+}
+
+/// Version of [`use_speech_recognition`] that takes a `UseSpeechRecognitionOptions`. See [`use_speech_recognition`] for how to use.
+pub fn use_speech_recognition_with_options(
+    options: UseSpeechRecognitionOptions,
+) -> UseSpeechRecognitionReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseSpeechRecognitionOptions {
+        lang,
+        continuous,
+        interim_results,
+        max_alternatives,
+    } = options;
+
+    let is_supported = use_supported(|| {
+        js!("SpeechRecognition" in &window()) || js!("webkitSpeechRecognition" in &window())
+    });
+
+    let (is_listening, set_listening) = create_signal(false);
+    let (result, set_result) = create_signal(String::new());
+    let (interim_result, set_interim_result) = create_signal(String::new());
+    let (error, set_error) = create_signal(None::<String>);
+
+    let recognition = if is_supported.get_untracked() {
+        create_speech_recognition()
+    } else {
+        None
+    };
+
+    if let Some(recognition) = &recognition {
+        recognition.set_continuous(continuous);
+        recognition.set_interim_results(interim_results);
+        recognition.set_max_alternatives(max_alternatives);
+
+        let _ = use_event_listener(
+            recognition.clone(),
+            Custom::<web_sys::Event>::new("start"),
+            move |_| {
+                set_listening.set(true);
+                set_error.set(None);
+            },
+        );
+
+        let _ = use_event_listener(
+            recognition.clone(),
+            Custom::<web_sys::Event>::new("end"),
+            move |_| set_listening.set(false),
+        );
+
+        let _ = use_event_listener(
+            recognition.clone(),
+            Custom::<web_sys::Event>::new("error"),
+            move |event| {
+                set_error.set(js!(event["error"]).ok().and_then(|error| error.as_string()));
+            },
+        );
+
+        let _ = use_event_listener(
+            recognition.clone(),
+            Custom::<SpeechRecognitionEvent>::new("result"),
+            move |event| {
+                let Some(results) = event.results() else {
+                    return;
+                };
+
+                let mut final_transcript = String::new();
+                let mut interim_transcript = String::new();
+
+                for speech_result in (0..results.length()).filter_map(|index| results.get(index)) {
+                    let Some(alternative) = speech_result.get(0) else {
+                        continue;
+                    };
+
+                    if speech_result.is_final() {
+                        final_transcript.push_str(&alternative.transcript());
+                    } else {
+                        interim_transcript.push_str(&alternative.transcript());
+                    }
+                }
+
+                set_result.set(final_transcript);
+                set_interim_result.set(interim_transcript);
+            },
+        );
+
+        on_cleanup({
+            let recognition = recognition.clone();
+            move || recognition.abort()
+        });
+    }
+
+    let start = {
+        let recognition = recognition.clone();
+
+        move || {
+            if let Some(recognition) = &recognition {
+                if is_listening.get_untracked() {
+                    return;
+                }
+
+                set_result.set(String::new());
+                set_interim_result.set(String::new());
+
+                recognition.set_lang(&lang.get_untracked());
+                let _ = recognition.start();
+            }
+        }
+    };
+
+    let stop = move || {
+        if let Some(recognition) = &recognition {
+            recognition.stop();
+        }
+    };
+
+    UseSpeechRecognitionReturn {
+        is_supported,
+        is_listening: is_listening.into(),
+        result: result.into(),
+        interim_result: interim_result.into(),
+        error: error.into(),
+        start,
+        stop,
+    }
+}
+
+/// Creates a recognition with the standard or the prefixed constructor of Chrome and Safari.
+fn create_speech_recognition() -> Option<SpeechRecognition> {
+    let window = window();
+
+    let constructor = ["SpeechRecognition", "webkitSpeechRecognition"]
+        .into_iter()
+        .find_map(|name| {
+            js_sys::Reflect::get(&window, &name.into())
+                .ok()
+                .and_then(|constructor| constructor.dyn_into::<js_sys::Function>().ok())
+        })?;
+
+    js_sys::Reflect::construct(&constructor, &js_sys::Array::new())
+        .ok()
+        .map(JsCast::unchecked_into)
+}
+
+/// Options for [`use_speech_recognition_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSpeechRecognitionOptions {
+    /// BCP 47 language tag of the language that is spoken. Defaults to `"en-US"`.
+    #[builder(into)]
+    lang: MaybeSignal<String>,
+
+    /// Keep listening after the user has stopped speaking until `stop` is called.
+    /// Defaults to `true`.
+    continuous: bool,
+
+    /// Provide results that aren't final yet in `interim_result`. Defaults to `true`.
+    interim_results: bool,
+
+    /// Maximum number of alternatives the browser computes for every result. Only the most
+    /// likely one is used. Defaults to `1`.
+    max_alternatives: u32,
+}
+
+impl Default for UseSpeechRecognitionOptions {
+    fn default() -> Self {
+        Self {
+            lang: "en-US".to_string().into(),
+            continuous: true,
+            interim_results: true,
+            max_alternatives: 1,
+        }
+    }
+}
+
+/// Return type of [`use_speech_recognition`].
+pub struct UseSpeechRecognitionReturn<StartFn, StopFn>
+where
+    StartFn: Fn() + Clone,
+    StopFn: Fn() + Clone,
+{
+    /// Whether the browser supports speech recognition
+    pub is_supported: Signal<bool>,
+    /// Whether the browser is currently listening
+    pub is_listening: Signal<bool>,
+    /// The final transcript of everything that has been recognized since `start` was called
+    pub result: Signal<String>,
+    /// The recognized text that isn't final yet
+    pub interim_result: Signal<String>,
+    /// The error code of the last error like `"not-allowed"` or `"no-speech"`
+    pub error: Signal<Option<String>>,
+    /// Starts listening. Asks for permission to use the microphone if necessary.
+    pub start: StartFn,
+    /// Stops listening. The speech that has been captured so far is still recognized.
+    pub stop: StopFn,
+}
@@ -0,0 +1,336 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, use_event_listener, use_supported};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{SpeechSynthesisUtterance, SpeechSynthesisVoice};
+
+/// Reactive [SpeechSynthesis](https://developer.mozilla.org/en-US/docs/Web/API/SpeechSynthesis)
+/// of the Web Speech API.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_speech_synthesis, UseSpeechSynthesisReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (text, set_text) = create_signal("Hello, world!".to_string());
+///
+/// let UseSpeechSynthesisReturn {
+///     is_speaking,
+///     speak,
+///     stop,
+///     ..
+/// } = use_speech_synthesis(text);
+///
+/// view! {
+///     <input prop:value=text on:input=move |e| set_text.set(event_target_value(&e)) />
+///     <button on:click=move |_| speak() disabled=is_speaking>"Speak"</button>
+///     <button on:click=move |_| stop() disabled=move || !is_speaking.get()>"Stop"</button>
+/// }
+/// # }
+/// ```
+///
+/// ### Voices
+///
+/// The voices that are available on the device are returned as `voices`. They are loaded
+/// asynchronously by some browsers so the list can be empty at first.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_speech_synthesis_with_options, UseSpeechSynthesisOptions};
+/// # use web_sys::SpeechSynthesisVoice;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (voice, set_voice) = create_signal(None::<SpeechSynthesisVoice>);
+/// let (rate, set_rate) = create_signal(1.5_f32);
+///
+/// let synthesis = use_speech_synthesis_with_options(
+///     "Hello, world!".to_string(),
+///     UseSpeechSynthesisOptions::default()
+///         .voice(voice)
+///         .rate(rate)
+///         .pitch(0.8_f32),
+/// );
+///
+/// let voices = synthesis.voices;
+/// let _ = watch(
+///     move || voices.get(),
+///     move |voices, _, _| {
+///         set_voice.set(voices.iter().find(|voice| voice.lang() == "en-GB").cloned());
+///     },
+///     true,
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The text and the options are read when `speak` is called. Changing them while speaking
+/// takes effect the next time `speak` is called.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` is always `false`, `voices` is empty and the functions do
+/// nothing.
+pub fn use_speech_synthesis(
+    text: impl Into<MaybeSignal<String>>,
+) -> UseSpeechSynthesisReturn<
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+> {
+    use_speech_synthesis_with_options(text, UseSpeechSynthesisOptions::default())
+}
+
+/// Version of [`use_speech_synthesis`] that takes a `UseSpeechSynthesisOptions`. See [`use_speech_synthesis`] for how to use.
+pub fn use_speech_synthesis_with_options(
+    text: impl Into<MaybeSignal<String>>,
+    options: UseSpeechSynthesisOptions,
+) -> UseSpeechSynthesisReturn<
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+    impl Fn() + Clone,
+> {
+    let UseSpeechSynthesisOptions {
+        lang,
+        voice,
+        pitch,
+        rate,
+        volume,
+    } = options;
+
+    let text = text.into();
+
+    let is_supported = use_supported(|| js!("speechSynthesis" in &window()));
+
+    let (is_speaking, set_speaking) = create_signal(false);
+    let (is_paused, set_paused) = create_signal(false);
+    let (voices, set_voices) = create_signal(Vec::<SpeechSynthesisVoice>::new());
+    let (error, set_error) = create_signal(None::<String>);
+
+    let synthesis = if is_supported.get_untracked() {
+        window().speech_synthesis().ok()
+    } else {
+        None
+    };
+
+    if let Some(synthesis) = &synthesis {
+        let read_voices = {
+            let synthesis = synthesis.clone();
+
+            move || {
+                set_voices.set(
+                    synthesis
+                        .get_voices()
+                        .iter()
+                        .map(JsCast::unchecked_into)
+                        .collect(),
+                )
+            }
+        };
+
+        read_voices();
+
+        let _ = use_event_listener(
+            synthesis.clone(),
+            Custom::<web_sys::Event>::new("voiceschanged"),
+            move |_| read_voices(),
+        );
+
+        on_cleanup({
+            let synthesis = synthesis.clone();
+
+            move || {
+                if is_speaking.get_untracked() {
+                    synthesis.cancel();
+                }
+            }
+        });
+    }
+
+    let speak = {
+        let synthesis = synthesis.clone();
+
+        move || {
+            let Some(synthesis) = &synthesis else {
+                return;
+            };
+
+            let Ok(utterance) = SpeechSynthesisUtterance::new_with_text(&text.get_untracked())
+            else {
+                return;
+            };
+
+            utterance.set_lang(&lang.get_untracked());
+            utterance.set_voice(voice.get_untracked().as_ref());
+            utterance.set_pitch(pitch.get_untracked());
+            utterance.set_rate(rate.get_untracked());
+            utterance.set_volume(volume.get_untracked());
+
+            attach_utterance_handlers(&utterance, set_speaking, set_paused, set_error);
+
+            synthesis.cancel();
+            synthesis.speak(&utterance);
+        }
+    };
+
+    let stop = {
+        let synthesis = synthesis.clone();
+
+        move || {
+            if let Some(synthesis) = &synthesis {
+                synthesis.cancel();
+                set_speaking.set(false);
+                set_paused.set(false);
+            }
+        }
+    };
+
+    let pause = {
+        let synthesis = synthesis.clone();
+
+        move || {
+            if let Some(synthesis) = &synthesis {
+                synthesis.pause();
+                set_paused.set(true);
+            }
+        }
+    };
+
+    let resume = move || {
+        if let Some(synthesis) = &synthesis {
+            synthesis.resume();
+            set_paused.set(false);
+        }
+    };
+
+    UseSpeechSynthesisReturn {
+        is_supported,
+        is_speaking: is_speaking.into(),
+        is_paused: is_paused.into(),
+        voices: voices.into(),
+        error: error.into(),
+        speak,
+        stop,
+        pause,
+        resume,
+    }
+}
+
+/// Updates the state signals from the events of `utterance`. Uses the `try_` methods because
+/// the utterance can outlive the component.
+fn attach_utterance_handlers(
+    utterance: &SpeechSynthesisUtterance,
+    set_speaking: WriteSignal<bool>,
+    set_paused: WriteSignal<bool>,
+    set_error: WriteSignal<Option<String>>,
+) {
+    let on_start = Closure::<dyn Fn()>::new(move || {
+        set_speaking.try_set(true);
+        set_paused.try_set(false);
+        set_error.try_set(None);
+    })
+    .into_js_value();
+    utterance.set_onstart(Some(on_start.unchecked_ref()));
+
+    let on_end = Closure::<dyn Fn()>::new(move || {
+        set_speaking.try_set(false);
+        set_paused.try_set(false);
+    })
+    .into_js_value();
+    utterance.set_onend(Some(on_end.unchecked_ref()));
+
+    let on_pause = Closure::<dyn Fn()>::new(move || {
+        set_paused.try_set(true);
+    })
+    .into_js_value();
+    utterance.set_onpause(Some(on_pause.unchecked_ref()));
+
+    let on_resume = Closure::<dyn Fn()>::new(move || {
+        set_paused.try_set(false);
+    })
+    .into_js_value();
+    utterance.set_onresume(Some(on_resume.unchecked_ref()));
+
+    let on_error = Closure::<dyn Fn(web_sys::Event)>::new(move |event: web_sys::Event| {
+        set_speaking.try_set(false);
+        set_paused.try_set(false);
+        set_error.try_set(js!(event["error"]).ok().and_then(|error| error.as_string()));
+    })
+    .into_js_value();
+    utterance.set_onerror(Some(on_error.unchecked_ref()));
+}
+
+/// Options for [`use_speech_synthesis_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSpeechSynthesisOptions {
+    /// BCP 47 language tag of the text. Defaults to `"en-US"`.
+    #[builder(into)]
+    lang: MaybeSignal<String>,
+
+    /// The voice to speak with. Pick one of the returned `voices`. Defaults to `None` which
+    /// means the default voice for `lang` is used.
+    #[builder(into)]
+    voice: MaybeSignal<Option<SpeechSynthesisVoice>>,
+
+    /// Pitch between `0.0` and `2.0`. Defaults to `1.0`.
+    #[builder(into)]
+    pitch: MaybeSignal<f32>,
+
+    /// Speed between `0.1` and `10.0`. Defaults to `1.0`.
+    #[builder(into)]
+    rate: MaybeSignal<f32>,
+
+    /// Volume between `0.0` and `1.0`. Defaults to `1.0`.
+    #[builder(into)]
+    volume: MaybeSignal<f32>,
+}
+
+impl Default for UseSpeechSynthesisOptions {
+    fn default() -> Self {
+        Self {
+            lang: "en-US".to_string().into(),
+            voice: MaybeSignal::Static(None),
+            pitch: MaybeSignal::Static(1.0),
+            rate: MaybeSignal::Static(1.0),
+            volume: MaybeSignal::Static(1.0),
+        }
+    }
+}
+
+/// Return type of [`use_speech_synthesis`].
+pub struct UseSpeechSynthesisReturn<SpeakFn, StopFn, PauseFn, ResumeFn>
+where
+    SpeakFn: Fn() + Clone,
+    StopFn: Fn() + Clone,
+    PauseFn: Fn() + Clone,
+    ResumeFn: Fn() + Clone,
+{
+    /// Whether the browser supports speech synthesis
+    pub is_supported: Signal<bool>,
+    /// Whether the text is being spoken. Also `true` while paused.
+    pub is_speaking: Signal<bool>,
+    /// Whether speaking is paused
+    pub is_paused: Signal<bool>,
+    /// The voices that are available on the device
+    pub voices: Signal<Vec<SpeechSynthesisVoice>>,
+    /// The error code of the last error like `"not-allowed"` or `"interrupted"`
+    pub error: Signal<Option<String>>,
+    /// Speaks the current text. Stops anything else that is being spoken.
+    pub speak: SpeakFn,
+    /// Stops speaking
+    pub stop: StopFn,
+    /// Pauses speaking
+    pub pause: PauseFn,
+    /// Resumes speaking after `pause` has been called
+    pub resume: ResumeFn,
+}
@@ -0,0 +1,464 @@
+use crate::core::ConnectionReadyState;
+use crate::{
+    js, use_event_source_with_options, watch, UseEventSourceOptions, UseEventSourceReturn,
+};
+use codee::string::FromToStringCodec;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::rc::Rc;
+use thiserror::Error;
+
+/// Reactive state that is kept in sync with a server via
+/// [Server-Sent-Events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events)
+/// that contain diffs instead of full snapshots.
+/// For this to work you have to enable the **`json_patch` feature** flag.
+///
+/// This is layered on top of [`use_event_source`]. The server sends named events:
+///
+/// - `snapshot` with the complete state as JSON. This replaces the state.
+/// - `patch` with a [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) (an array of operations).
+/// - `merge-patch` with a [JSON Merge Patch](https://datatracker.ietf.org/doc/html/rfc7396).
+///
+/// The names of the events can be changed in the options. Every patch is applied to the JSON
+/// representation of the state and the result is deserialized into `T`. If a patch can't be
+/// applied or the result can't be deserialized, the state stays unchanged and the error is
+/// reported in `error`. Patches are applied atomically, i.e. either all operations of a JSON
+/// Patch are applied or none.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_sse_json_patch, UseSseJsonPatchReturn};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+/// struct Dashboard {
+///     visitors: u32,
+///     messages: Vec<String>,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseSseJsonPatchReturn { state, error, .. } =
+///     use_sse_json_patch("https://event-source-url", Dashboard::default());
+///
+/// view! {
+///     <p>"Visitors: " {move || state.get().visitors}</p>
+///     <p>{move || error.with(|error| error.as_ref().map(|e| e.to_string()))}</p>
+/// }
+/// # }
+/// ```
+///
+/// With the server sending for example
+///
+/// ```text
+/// event: snapshot
+/// data: {"visitors": 3, "messages": []}
+///
+/// event: patch
+/// data: [{"op": "replace", "path": "/visitors", "value": 4}, {"op": "add", "path": "/messages/-", "value": "Hi"}]
+///
+/// event: merge-patch
+/// data: {"visitors": 5}
+/// ```
+///
+/// The connection can be configured with the `event_source` option which takes the same options
+/// as [`use_event_source_with_options`], e.g. to use the fetch transport for custom headers.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_sse_json_patch_with_options, EventSourceTransport, FetchTransportOptions,
+/// #     UseEventSourceOptions, UseSseJsonPatchOptions,
+/// # };
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # #[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+/// # struct Dashboard {
+/// #     visitors: u32,
+/// # }
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let dashboard = use_sse_json_patch_with_options(
+///     "https://event-source-url",
+///     Dashboard::default(),
+///     UseSseJsonPatchOptions::default()
+///         .patch_event("diff")
+///         .on_error(|err| leptos::logging::error!("{err}"))
+///         .event_source(UseEventSourceOptions::default().transport(
+///             EventSourceTransport::Fetch(FetchTransportOptions::default().headers(vec![(
+///                 "Authorization".to_string(),
+///                 "Bearer token".to_string(),
+///             )])),
+///         )),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `state` always stays the initial value.
+pub fn use_sse_json_patch<T>(
+    url: &str,
+    initial: T,
+) -> UseSseJsonPatchReturn<T, impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    use_sse_json_patch_with_options(url, initial, UseSseJsonPatchOptions::default())
+}
+
+/// Version of [`use_sse_json_patch`] that takes a `UseSseJsonPatchOptions`. See [`use_sse_json_patch`] for how to use.
+pub fn use_sse_json_patch_with_options<T>(
+    url: &str,
+    initial: T,
+    options: UseSseJsonPatchOptions,
+) -> UseSseJsonPatchReturn<T, impl Fn() + Clone + 'static, impl Fn() + Clone + 'static>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    let UseSseJsonPatchOptions {
+        snapshot_event,
+        patch_event,
+        merge_patch_event,
+        on_error,
+        event_source,
+    } = options;
+
+    let document = store_value(serde_json::to_value(&initial).unwrap_or(Value::Null));
+
+    let (state, set_state) = create_signal(initial);
+    let (error, set_error) = create_signal(None::<UseSseJsonPatchError>);
+
+    let UseEventSourceReturn {
+        ready_state,
+        event,
+        open,
+        close,
+        ..
+    } = use_event_source_with_options::<String, FromToStringCodec>(
+        url,
+        event_source.named_events([
+            snapshot_event.clone(),
+            patch_event.clone(),
+            merge_patch_event.clone(),
+        ]),
+    );
+
+    let apply_event = move |event_type: &str, data: &str| -> Result<(), UseSseJsonPatchError> {
+        let data = serde_json::from_str::<Value>(data).map_err(UseSseJsonPatchError::Parse)?;
+
+        let mut new_document = document.get_value();
+
+        if event_type == snapshot_event {
+            new_document = data;
+        } else if event_type == patch_event {
+            apply_json_patch(&mut new_document, &data)?;
+        } else if event_type == merge_patch_event {
+            apply_merge_patch(&mut new_document, &data);
+        } else {
+            return Ok(());
+        }
+
+        let new_state = serde_json::from_value::<T>(new_document.clone())
+            .map_err(UseSseJsonPatchError::Deserialize)?;
+
+        document.set_value(new_document);
+        set_state.set(new_state);
+
+        Ok(())
+    };
+
+    let _ = watch(
+        move || event.get(),
+        move |event, _, _| {
+            let Some(event) = event else {
+                return;
+            };
+
+            let data = js!(event["data"])
+                .ok()
+                .and_then(|data| data.as_string())
+                .unwrap_or_default();
+
+            match apply_event(&event.type_(), &data) {
+                Ok(()) => set_error.set(None),
+                Err(err) => {
+                    #[cfg(debug_assertions)]
+                    let prev = SpecialNonReactiveZone::enter();
+
+                    on_error(&err);
+
+                    #[cfg(debug_assertions)]
+                    SpecialNonReactiveZone::exit(prev);
+
+                    set_error.set(Some(err));
+                }
+            }
+        },
+    );
+
+    UseSseJsonPatchReturn {
+        state: state.into(),
+        error: error.into(),
+        ready_state,
+        open,
+        close,
+    }
+}
+
+/// Applies a [JSON Merge Patch](https://datatracker.ietf.org/doc/html/rfc7396) to `target`.
+fn apply_merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+
+    let Value::Object(target) = target else {
+        unreachable!()
+    };
+
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            apply_merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Applies a [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) to `document`.
+/// If one of the operations fails, `document` is left unchanged.
+fn apply_json_patch(document: &mut Value, patch: &Value) -> Result<(), JsonPatchError> {
+    let operations = patch.as_array().ok_or(JsonPatchError::NotAnArray)?;
+
+    let mut patched = document.clone();
+
+    for operation in operations {
+        let op = string_member(operation, "op")?;
+        let path = string_member(operation, "path")?;
+
+        match op {
+            "add" => add_value(&mut patched, path, value_member(operation)?.clone())?,
+            "remove" => {
+                remove_value(&mut patched, path)?;
+            }
+            "replace" => {
+                let target = patched
+                    .pointer_mut(path)
+                    .ok_or_else(|| JsonPatchError::PathNotFound(path.to_string()))?;
+                *target = value_member(operation)?.clone();
+            }
+            "move" => {
+                let from = string_member(operation, "from")?;
+                if path.starts_with(&format!("{from}/")) {
+                    return Err(JsonPatchError::InvalidPath(path.to_string()));
+                }
+
+                let value = remove_value(&mut patched, from)?;
+                add_value(&mut patched, path, value)?;
+            }
+            "copy" => {
+                let from = string_member(operation, "from")?;
+                let value = patched
+                    .pointer(from)
+                    .ok_or_else(|| JsonPatchError::PathNotFound(from.to_string()))?
+                    .clone();
+
+                add_value(&mut patched, path, value)?;
+            }
+            "test" => {
+                if patched.pointer(path) != Some(value_member(operation)?) {
+                    return Err(JsonPatchError::TestFailed(path.to_string()));
+                }
+            }
+            _ => return Err(JsonPatchError::UnknownOperation(op.to_string())),
+        }
+    }
+
+    *document = patched;
+
+    Ok(())
+}
+
+fn string_member<'a>(operation: &'a Value, name: &str) -> Result<&'a str, JsonPatchError> {
+    operation
+        .get(name)
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| JsonPatchError::MissingMember(name.to_string()))
+}
+
+fn value_member(operation: &Value) -> Result<&Value, JsonPatchError> {
+    operation
+        .get("value")
+        .ok_or_else(|| JsonPatchError::MissingMember("value".to_string()))
+}
+
+/// Splits a JSON pointer into the pointer of the parent and the unescaped last token.
+fn split_pointer(path: &str) -> Result<(&str, String), JsonPatchError> {
+    let (parent, last) = path
+        .rsplit_once('/')
+        .ok_or_else(|| JsonPatchError::InvalidPath(path.to_string()))?;
+
+    Ok((parent, last.replace("~1", "/").replace("~0", "~")))
+}
+
+fn add_value(document: &mut Value, path: &str, value: Value) -> Result<(), JsonPatchError> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+
+    let (parent, key) = split_pointer(path)?;
+
+    match document.pointer_mut(parent) {
+        Some(Value::Object(object)) => {
+            object.insert(key, value);
+        }
+        Some(Value::Array(array)) => {
+            let index = if key == "-" {
+                array.len()
+            } else {
+                array_index(&key, path)?
+            };
+
+            if index > array.len() {
+                return Err(JsonPatchError::PathNotFound(path.to_string()));
+            }
+
+            array.insert(index, value);
+        }
+        _ => return Err(JsonPatchError::PathNotFound(path.to_string())),
+    }
+
+    Ok(())
+}
+
+fn remove_value(document: &mut Value, path: &str) -> Result<Value, JsonPatchError> {
+    let (parent, key) = split_pointer(path)?;
+
+    let removed = match document.pointer_mut(parent) {
+        Some(Value::Object(object)) => object.remove(&key),
+        Some(Value::Array(array)) => {
+            let index = array_index(&key, path)?;
+            (index < array.len()).then(|| array.remove(index))
+        }
+        _ => None,
+    };
+
+    removed.ok_or_else(|| JsonPatchError::PathNotFound(path.to_string()))
+}
+
+fn array_index(key: &str, path: &str) -> Result<usize, JsonPatchError> {
+    // leading zeros are not allowed
+    if key.len() > 1 && key.starts_with('0') {
+        return Err(JsonPatchError::InvalidPath(path.to_string()));
+    }
+
+    key.parse()
+        .map_err(|_| JsonPatchError::InvalidPath(path.to_string()))
+}
+
+/// Errors of [`use_sse_json_patch`].
+#[derive(Error, Debug)]
+pub enum UseSseJsonPatchError {
+    #[error("failed to parse event data as JSON: {0}")]
+    Parse(serde_json::Error),
+
+    #[error("failed to apply JSON patch: {0}")]
+    Patch(#[from] JsonPatchError),
+
+    #[error("failed to deserialize the patched state: {0}")]
+    Deserialize(serde_json::Error),
+}
+
+/// Reasons why a [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) can't be applied.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum JsonPatchError {
+    #[error("the patch is not an array of operations")]
+    NotAnArray,
+
+    #[error("an operation is missing the member '{0}'")]
+    MissingMember(String),
+
+    #[error("unknown operation '{0}'")]
+    UnknownOperation(String),
+
+    #[error("invalid path '{0}'")]
+    InvalidPath(String),
+
+    #[error("path '{0}' not found")]
+    PathNotFound(String),
+
+    #[error("test of path '{0}' failed")]
+    TestFailed(String),
+}
+
+/// Options for [`use_sse_json_patch_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseSseJsonPatchOptions {
+    /// Name of the event that contains the complete state. Defaults to `"snapshot"`.
+    #[builder(into)]
+    snapshot_event: String,
+
+    /// Name of the event that contains a JSON Patch. Defaults to `"patch"`.
+    #[builder(into)]
+    patch_event: String,
+
+    /// Name of the event that contains a JSON Merge Patch. Defaults to `"merge-patch"`.
+    #[builder(into)]
+    merge_patch_event: String,
+
+    /// Called for every event that can't be applied.
+    on_error: Rc<dyn Fn(&UseSseJsonPatchError)>,
+
+    /// Options of the underlying [`use_event_source_with_options`]. The named events are set
+    /// to the three events above.
+    event_source: UseEventSourceOptions<String>,
+}
+
+impl Default for UseSseJsonPatchOptions {
+    fn default() -> Self {
+        Self {
+            snapshot_event: "snapshot".to_string(),
+            patch_event: "patch".to_string(),
+            merge_patch_event: "merge-patch".to_string(),
+            on_error: Rc::new(|_| {}),
+            event_source: UseEventSourceOptions::default(),
+        }
+    }
+}
+
+/// Return type of [`use_sse_json_patch`].
+pub struct UseSseJsonPatchReturn<T, OpenFn, CloseFn>
+where
+    T: 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+{
+    /// The state with all received patches applied.
+    pub state: Signal<T>,
+
+    /// The error of the latest event if it couldn't be applied.
+    pub error: Signal<Option<UseSseJsonPatchError>>,
+
+    /// The state of the underlying `EventSource` connection.
+    pub ready_state: Signal<ConnectionReadyState>,
+
+    /// (Re-)Opens the connection.
+    pub open: OpenFn,
+
+    /// Closes the connection.
+    pub close: CloseFn,
+}
@@ -0,0 +1,381 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::StorageType;
+use crate::storage::{use_storage_with_options, UseStorageOptions};
+use crate::utils::{get_query_param, set_query_param};
+use codee::string::FromToStringCodec;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::rc::Rc;
+
+/// State of a multi-step flow like a checkout or an onboarding form.
+///
+/// Every step has an id and an optional validation gate. The user can only move forward if
+/// all steps in between are valid. Moving backward is always possible.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_step_wizard, UseStepWizardReturn, WizardStep};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (email, set_email) = create_signal(String::new());
+///
+/// let UseStepWizardReturn {
+///     current_id,
+///     progress,
+///     can_next,
+///     is_first,
+///     next,
+///     prev,
+///     ..
+/// } = use_step_wizard(vec![
+///     WizardStep::new("account").is_valid(Signal::derive(move || email.get().contains('@'))),
+///     WizardStep::new("address"),
+///     WizardStep::new("confirm"),
+/// ]);
+///
+/// view! {
+///     <progress max="100" value=move || progress.get() />
+///
+///     <Show when=move || current_id.get() == "account">
+///         <input on:input=move |ev| set_email.set(event_target_value(&ev)) />
+///     </Show>
+///
+///     <button disabled=is_first on:click={
+///         let prev = prev.clone();
+///         move |_| { prev(); }
+///     }>"Back"</button>
+///     <button disabled=move || !can_next.get() on:click={
+///         let next = next.clone();
+///         move |_| { next(); }
+///     }>"Next"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Resumable Flows
+///
+/// With `storage_key` the id of the current step is persisted so the user continues where they
+/// left off after a reload. With `query_param` the id is kept in the URL so the current step can
+/// be linked to. Restored steps are not checked against the validation gates.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_step_wizard_with_options, UseStepWizardOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let wizard = use_step_wizard_with_options(
+///     vec!["account", "address", "confirm"],
+///     UseStepWizardOptions::default()
+///         .storage_key("checkout-step")
+///         .query_param("step"),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the initial step is `initial_step` or the first step. Storage and URL are not
+/// read.
+pub fn use_step_wizard<S>(
+    steps: impl Into<MaybeSignal<Vec<S>>>,
+) -> UseStepWizardReturn<
+    impl Fn() -> bool + Clone,
+    impl Fn() -> bool + Clone,
+    impl Fn(&str) -> bool + Clone,
+    impl Fn(usize) -> bool + Clone,
+    impl Fn() + Clone,
+>
+where
+    S: Into<WizardStep> + Clone + 'static,
+{
+    use_step_wizard_with_options(steps, UseStepWizardOptions::default())
+}
+
+/// Version of [`use_step_wizard`] that takes a `UseStepWizardOptions`. See [`use_step_wizard`] for how to use.
+pub fn use_step_wizard_with_options<S>(
+    steps: impl Into<MaybeSignal<Vec<S>>>,
+    options: UseStepWizardOptions,
+) -> UseStepWizardReturn<
+    impl Fn() -> bool + Clone,
+    impl Fn() -> bool + Clone,
+    impl Fn(&str) -> bool + Clone,
+    impl Fn(usize) -> bool + Clone,
+    impl Fn() + Clone,
+>
+where
+    S: Into<WizardStep> + Clone + 'static,
+{
+    let UseStepWizardOptions {
+        initial_step,
+        storage_key,
+        storage,
+        query_param,
+        on_change,
+    } = options;
+
+    let steps: MaybeSignal<Vec<S>> = steps.into();
+    let steps: Memo<Vec<WizardStep>> =
+        create_memo(move |_| steps.get().into_iter().map(Into::into).collect());
+
+    let position_of =
+        move |id: &str| steps.with_untracked(|steps| steps.iter().position(|step| step.id == id));
+
+    let stored = storage_key.map(|key| {
+        use_storage_with_options::<String, FromToStringCodec>(
+            storage,
+            key,
+            UseStorageOptions::default().listen_to_storage_changes(false),
+        )
+    });
+
+    let initial_index = {
+        let from_url = query_param.as_deref().and_then(get_query_param);
+        let from_storage = stored
+            .as_ref()
+            .map(|(value, _, _)| value.get_untracked())
+            .filter(|id| !id.is_empty());
+
+        from_url
+            .or(from_storage)
+            .or(initial_step)
+            .and_then(|id| position_of(&id))
+            .unwrap_or_default()
+    };
+
+    let (index, set_index) = create_signal(initial_index);
+
+    let step_count = Signal::derive(move || steps.with(Vec::len));
+
+    let current_id = Signal::derive(move || {
+        steps.with(|steps| {
+            steps
+                .get(index.get())
+                .map(|step| step.id.clone())
+                .unwrap_or_default()
+        })
+    });
+
+    let is_current_valid = Signal::derive(move || {
+        steps.with(|steps| {
+            steps
+                .get(index.get())
+                .map_or(true, |step| step.is_valid.get())
+        })
+    });
+
+    let is_first = Signal::derive(move || index.get() == 0);
+    let is_last = Signal::derive(move || index.get() + 1 >= step_count.get());
+    let can_next = Signal::derive(move || !is_last.get() && is_current_valid.get());
+
+    let progress = Signal::derive(move || {
+        let count = step_count.get();
+        if count == 0 {
+            0.0
+        } else {
+            (index.get() + 1) as f64 / count as f64 * 100.0
+        }
+    });
+
+    let delete_stored = stored.as_ref().map(|(_, _, delete)| delete.clone());
+
+    let _ = watch(
+        move || current_id.get(),
+        move |id, _, _| {
+            if let Some((_, set_stored, _)) = &stored {
+                set_stored.set(id.clone());
+            }
+
+            if let Some(query_param) = &query_param {
+                set_query_param(query_param, Some(id));
+            }
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            on_change(id);
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+        false,
+    );
+
+    let goto_index = move |target: usize| {
+        let current = index.get_untracked();
+
+        let is_allowed = steps.with_untracked(|steps| {
+            target < steps.len()
+                && steps[current.min(target)..target]
+                    .iter()
+                    .all(|step| step.is_valid.get_untracked())
+        });
+
+        if is_allowed && target != current {
+            set_index.set(target);
+        }
+
+        is_allowed
+    };
+
+    let goto = move |id: &str| position_of(id).is_some_and(goto_index);
+
+    let next = move || goto_index(index.get_untracked() + 1);
+
+    let prev = move || {
+        let current = index.get_untracked();
+        current > 0 && goto_index(current - 1)
+    };
+
+    let reset = move || {
+        set_index.set(0);
+
+        if let Some(delete) = &delete_stored {
+            delete();
+        }
+    };
+
+    UseStepWizardReturn {
+        index: index.into(),
+        current_id,
+        step_count,
+        progress,
+        is_first,
+        is_last,
+        can_next,
+        next,
+        prev,
+        goto,
+        goto_index,
+        reset,
+    }
+}
+
+/// A step of [`use_step_wizard`].
+#[derive(Clone)]
+pub struct WizardStep {
+    id: String,
+    is_valid: MaybeSignal<bool>,
+}
+
+impl WizardStep {
+    /// Creates a step that is always valid.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            is_valid: MaybeSignal::Static(true),
+        }
+    }
+
+    /// Validation gate of the step. The user can only move past this step if it is `true`.
+    pub fn is_valid(mut self, is_valid: impl Into<MaybeSignal<bool>>) -> Self {
+        self.is_valid = is_valid.into();
+        self
+    }
+
+    /// The id of the step
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl From<&str> for WizardStep {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for WizardStep {
+    fn from(id: String) -> Self {
+        Self::new(id)
+    }
+}
+
+/// Options for [`use_step_wizard_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseStepWizardOptions {
+    /// Id of the step to start with if no step has been restored. Defaults to the first step.
+    #[builder(into)]
+    initial_step: Option<String>,
+
+    /// Key under which the id of the current step is persisted. `None` disables persistence.
+    /// Defaults to `None`.
+    #[builder(into)]
+    storage_key: Option<String>,
+
+    /// Storage the current step is persisted in. Defaults to `StorageType::Session`.
+    storage: StorageType,
+
+    /// Name of the URL query parameter that holds the id of the current step. `None` disables
+    /// URL syncing. Defaults to `None`.
+    #[builder(into)]
+    query_param: Option<String>,
+
+    /// Called with the id of the new step whenever the current step changes.
+    on_change: Rc<dyn Fn(&str)>,
+}
+
+impl Default for UseStepWizardOptions {
+    fn default() -> Self {
+        Self {
+            initial_step: None,
+            storage_key: None,
+            storage: StorageType::Session,
+            query_param: None,
+            on_change: Rc::new(|_| {}),
+        }
+    }
+}
+
+/// Return type of [`use_step_wizard`].
+pub struct UseStepWizardReturn<NextFn, PrevFn, GotoFn, GotoIndexFn, ResetFn>
+where
+    NextFn: Fn() -> bool + Clone,
+    PrevFn: Fn() -> bool + Clone,
+    GotoFn: Fn(&str) -> bool + Clone,
+    GotoIndexFn: Fn(usize) -> bool + Clone,
+    ResetFn: Fn() + Clone,
+{
+    /// Index of the current step
+    pub index: Signal<usize>,
+
+    /// Id of the current step
+    pub current_id: Signal<String>,
+
+    /// Number of steps
+    pub step_count: Signal<usize>,
+
+    /// Progress in percent. The last step is `100.0`.
+    pub progress: Signal<f64>,
+
+    /// `true` if the current step is the first step
+    pub is_first: Signal<bool>,
+
+    /// `true` if the current step is the last step
+    pub is_last: Signal<bool>,
+
+    /// `true` if there is a next step and the current step is valid
+    pub can_next: Signal<bool>,
+
+    /// Moves to the next step if the current step is valid. Returns `true` if it moved.
+    pub next: NextFn,
+
+    /// Moves to the previous step. Returns `true` if it moved.
+    pub prev: PrevFn,
+
+    /// Moves to the step with the given id. Moving forward is only possible if the current
+    /// step and all steps in between are valid. Returns `true` if the step is reachable.
+    pub goto: GotoFn,
+
+    /// Like `goto` but takes the index of the step.
+    pub goto_index: GotoIndexFn,
+
+    /// Moves back to the first step and deletes the persisted step.
+    pub reset: ResetFn,
+}
@@ -0,0 +1,59 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use futures_util::stream::{abortable, Stream, StreamExt};
+use leptos::*;
+
+/// Reactive latest value of an async `Stream`.
+///
+/// Returns a signal that is `initial` until the stream yields its first item and from then on
+/// contains the latest item. The next item is only requested from the stream once the previous
+/// one has been set and all effects depending on the signal have run. So a stream that
+/// produces items on demand is never read faster than the app can process them.
+///
+/// The stream is dropped when the reactive owner is disposed.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use futures_util::stream;
+/// # use leptos_use::use_stream;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let progress = use_stream(stream::iter(vec![25, 50, 75, 100]), 0);
+///
+/// view! { <progress max="100" value=progress /> }
+/// # }
+/// ```
+///
+/// See [`signal_to_stream`] for the other direction.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the stream is not polled and the signal is always `initial`.
+pub fn use_stream<S, T>(stream: S, initial: T) -> Signal<T>
+where
+    S: Stream<Item = T> + 'static,
+    T: 'static,
+{
+    let (value, set_value) = create_signal(initial);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let (mut stream, abort_handle) = abortable(Box::pin(stream));
+
+        on_cleanup(move || abort_handle.abort());
+
+        spawn_local(async move {
+            while let Some(item) = stream.next().await {
+                if set_value.try_set(item).is_some() {
+                    // the signal has been disposed
+                    break;
+                }
+            }
+        });
+    }
+
+    value.into()
+}
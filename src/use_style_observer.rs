@@ -0,0 +1,279 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::utils::Pausable;
+use crate::{
+    use_event_listener, use_mutation_observer_with_options, use_raf_fn_with_options,
+    UseMutationObserverOptions, UseRafFnOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{
+    animationcancel, animationend, animationstart, transitioncancel, transitionend, transitionrun,
+};
+use leptos::*;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Reactive computed style properties of an element.
+///
+/// Lets the code react to state that lives in CSS, like a media query in a stylesheet or a
+/// class that is toggled somewhere else. The properties are read with
+/// [`getComputedStyle`](https://developer.mozilla.org/en-US/docs/Web/API/Window/getComputedStyle)
+/// when the `class` or `style` attribute of the element changes. While a CSS transition or
+/// animation is running they are read on every animation frame so the values follow it.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::use_style_observer;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let el = create_node_ref::<Div>();
+///
+/// let style = use_style_observer(el, &["width", "background-color", "display"]);
+///
+/// let width = style.length("width");
+/// let background = style.color("background-color");
+/// let is_hidden = Signal::derive(move || style.raw("display").get().as_deref() == Some("none"));
+///
+/// view! {
+///     <div node_ref=el class="w-1/2 bg-red-500 md:w-full md:bg-blue-500 transition-all">
+///         {move || format!("{:?}px", width.get())}
+///         {move || format!("{:?}", background.get())}
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// Changes that neither come with a change of the attributes nor with a transition, like a
+/// media query that starts to match, aren't detected unless `poll` is enabled, which reads the
+/// properties on every animation frame.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the values are always empty.
+pub fn use_style_observer<El, T>(target: El, properties: &[&str]) -> UseStyleObserverReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_style_observer_with_options(target, properties, UseStyleObserverOptions::default())
+}
+
+/// Version of [`use_style_observer`] that takes a `UseStyleObserverOptions`. See [`use_style_observer`] for how to use.
+pub fn use_style_observer_with_options<El, T>(
+    target: El,
+    properties: &[&str],
+    options: UseStyleObserverOptions,
+) -> UseStyleObserverReturn
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseStyleObserverOptions { poll } = options;
+
+    let properties = properties
+        .iter()
+        .map(|property| property.to_string())
+        .collect::<Vec<_>>();
+
+    let target: ElementMaybeSignal<T, web_sys::Element> = target.into();
+    let target = Signal::derive(move || target.get().map(Into::into));
+
+    let (values, set_values) = create_signal(HashMap::<String, String>::new());
+
+    let read = move || {
+        let Some(el) = target.get_untracked() else {
+            return;
+        };
+        let Ok(Some(style)) = window().get_computed_style(&el) else {
+            return;
+        };
+
+        let new_values = properties
+            .iter()
+            .map(|property| {
+                (
+                    property.clone(),
+                    style.get_property_value(property).unwrap_or_default(),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        if values.with_untracked(|values| *values != new_values) {
+            set_values.set(new_values);
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let Pausable { pause, resume, .. } = use_raf_fn_with_options(
+            {
+                let read = read.clone();
+                move |_| read()
+            },
+            UseRafFnOptions::default().immediate(poll),
+        );
+
+        // number of transitions and animations that are running right now
+        let running = store_value(0_usize);
+
+        let start = {
+            let resume = resume.clone();
+            move || {
+                running.update_value(|running| *running += 1);
+                resume();
+            }
+        };
+
+        let stop = {
+            let read = read.clone();
+            move || {
+                running.update_value(|running| *running = running.saturating_sub(1));
+                if running.get_value() == 0 && !poll {
+                    pause();
+                }
+                read();
+            }
+        };
+
+        let _ = use_event_listener(target, transitionrun, {
+            let start = start.clone();
+            move |_| start()
+        });
+        let _ = use_event_listener(target, animationstart, move |_| start());
+
+        let _ = use_event_listener(target, transitionend, {
+            let stop = stop.clone();
+            move |_| stop()
+        });
+        let _ = use_event_listener(target, transitioncancel, {
+            let stop = stop.clone();
+            move |_| stop()
+        });
+        let _ = use_event_listener(target, animationend, {
+            let stop = stop.clone();
+            move |_| stop()
+        });
+        let _ = use_event_listener(target, animationcancel, move |_| stop());
+
+        let _ = use_mutation_observer_with_options(
+            target,
+            {
+                let read = read.clone();
+                move |_, _| read()
+            },
+            UseMutationObserverOptions::default()
+                .attribute_filter(vec!["class".to_string(), "style".to_string()]),
+        );
+
+        let _ = watch(move || target.get(), move |_, _, _| read(), true);
+    }
+
+    UseStyleObserverReturn {
+        values: values.into(),
+    }
+}
+
+/// Options for [`use_style_observer_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseStyleObserverOptions {
+    /// Read the properties on every animation frame, not only while transitions or animations
+    /// are running. Defaults to `false`.
+    poll: bool,
+}
+
+impl Default for UseStyleObserverOptions {
+    fn default() -> Self {
+        Self { poll: false }
+    }
+}
+
+/// Return type of [`use_style_observer`].
+#[derive(Copy, Clone)]
+pub struct UseStyleObserverReturn {
+    /// The computed values of all observed properties by property name
+    pub values: Signal<HashMap<String, String>>,
+}
+
+impl UseStyleObserverReturn {
+    /// The computed value of `property` as it is returned by the browser. `None` if the
+    /// property isn't observed or the element isn't available.
+    pub fn raw(&self, property: &str) -> Signal<Option<String>> {
+        let values = self.values;
+        let property = property.to_string();
+
+        Signal::derive(move || values.with(|values| values.get(&property).cloned()))
+    }
+
+    /// The computed value of `property` as a length in pixels like `12.5` for `"12.5px"`.
+    /// `None` for other values like `"auto"`.
+    pub fn length(&self, property: &str) -> Signal<Option<f64>> {
+        self.parsed(property, |value| value.strip_suffix("px")?.parse().ok())
+    }
+
+    /// The computed value of `property` as a unitless number like `0.5` for `opacity`.
+    pub fn number(&self, property: &str) -> Signal<Option<f64>> {
+        self.parsed(property, |value| value.parse().ok())
+    }
+
+    /// The computed value of `property` as a color. Browsers compute colors as `rgb()` or
+    /// `rgba()`.
+    pub fn color(&self, property: &str) -> Signal<Option<CssColor>> {
+        self.parsed(property, |value| value.parse().ok())
+    }
+
+    fn parsed<V>(&self, property: &str, parse: fn(&str) -> Option<V>) -> Signal<Option<V>>
+    where
+        V: Clone + PartialEq + 'static,
+    {
+        let raw = self.raw(property);
+
+        create_memo(move |_| raw.with(|value| value.as_deref().map(str::trim).and_then(parse)))
+            .into()
+    }
+}
+
+/// A color in the sRGB color space as returned by `getComputedStyle`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CssColor {
+    /// Red from `0.0` to `255.0`
+    pub r: f64,
+    /// Green from `0.0` to `255.0`
+    pub g: f64,
+    /// Blue from `0.0` to `255.0`
+    pub b: f64,
+    /// Alpha from `0.0` to `1.0`
+    pub a: f64,
+}
+
+impl FromStr for CssColor {
+    type Err = ();
+
+    /// Parses `rgb(r, g, b)`, `rgba(r, g, b, a)` and the space separated form
+    /// `rgb(r g b / a)`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let inner = value
+            .strip_prefix("rgba(")
+            .or_else(|| value.strip_prefix("rgb("))
+            .and_then(|inner| inner.strip_suffix(')'))
+            .ok_or(())?;
+
+        let components = inner
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|component| !component.is_empty())
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ())?;
+
+        match components[..] {
+            [r, g, b] => Ok(Self { r, g, b, a: 1.0 }),
+            [r, g, b, a] => Ok(Self { r, g, b, a }),
+            _ => Err(()),
+        }
+    }
+}
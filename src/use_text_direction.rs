@@ -0,0 +1,281 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{
+    use_mutation_observer_with_options, watch_with_options, UseMutationObserverOptions,
+    WatchOptions,
+};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+/// Reactive [dir](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/dir) of an element's text.
+///
+/// Reads and writes the `dir` attribute of the target element which defaults to `<html>`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_text_direction, TextDirection, UseTextDirectionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseTextDirectionReturn { dir, set_dir, is_rtl } = use_text_direction();
+///
+/// set_dir.set(TextDirection::Rtl);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Detecting the Direction
+///
+/// The direction can be detected from a locale or from a sample of the text.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_locale, use_text_direction, TextDirection, UseLocaleReturn, UseTextDirectionReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseLocaleReturn { locale, .. } = use_locale(["en", "ar", "he"]);
+/// let UseTextDirectionReturn { set_dir, .. } = use_text_direction();
+///
+/// create_effect(move |_| set_dir.set(TextDirection::from_locale(&locale.get())));
+///
+/// assert_eq!(TextDirection::from_text("שלום world"), TextDirection::Rtl);
+/// assert_eq!(TextDirection::from_text("123 hello"), TextDirection::Ltr);
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server `dir` is the `initial_value` and no attribute is changed.
+pub fn use_text_direction() -> UseTextDirectionReturn {
+    use_text_direction_with_options(UseTextDirectionOptions::default())
+}
+
+/// Version of [`use_text_direction`] that takes a `UseTextDirectionOptions`. See [`use_text_direction`] for how to use.
+pub fn use_text_direction_with_options<El, T>(
+    options: UseTextDirectionOptions<El, T>,
+) -> UseTextDirectionReturn
+where
+    El: Clone,
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseTextDirectionOptions {
+        target,
+        initial_value,
+        observe,
+        ..
+    } = options;
+
+    let (dir, set_dir) = create_signal(initial_value);
+
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        let el_signal: ElementMaybeSignal<T, web_sys::Element> = target.into();
+
+        let read_dir = {
+            let el_signal = el_signal.clone();
+
+            move || {
+                if let Some(el) = el_signal.get_untracked() {
+                    let el: web_sys::Element = el.into();
+                    let new_dir = el
+                        .get_attribute("dir")
+                        .and_then(|attr| attr.parse().ok())
+                        .unwrap_or(initial_value);
+
+                    if new_dir != dir.get_untracked() {
+                        set_dir.set(new_dir);
+                    }
+                }
+            }
+        };
+
+        if observe {
+            let read_dir = read_dir.clone();
+
+            let _ = use_mutation_observer_with_options::<ElementMaybeSignal<T, web_sys::Element>, T, _>(
+                el_signal.clone(),
+                move |_, _| read_dir(),
+                UseMutationObserverOptions::default().attribute_filter(vec!["dir".to_string()]),
+            );
+        }
+
+        {
+            let el_signal = el_signal.clone();
+
+            let _ = watch_with_options(
+                move || el_signal.get(),
+                move |_, _, _| read_dir(),
+                WatchOptions::default().immediate(true),
+            );
+        }
+
+        let _ = watch(
+            move || dir.get(),
+            move |dir, _, _| {
+                if let Some(el) = el_signal.get_untracked() {
+                    let el: web_sys::Element = el.into();
+
+                    if el.get_attribute("dir").as_deref() != Some(dir.as_str()) {
+                        let _ = el.set_attribute("dir", dir.as_str());
+                    }
+                }
+            },
+            false,
+        );
+    }}
+
+    UseTextDirectionReturn {
+        dir: dir.into(),
+        set_dir,
+        is_rtl: Signal::derive(move || dir.get() == TextDirection::Rtl),
+    }
+}
+
+/// Direction of text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    /// Left to right
+    #[default]
+    Ltr,
+    /// Right to left
+    Rtl,
+    /// Let the browser decide from the content
+    Auto,
+}
+
+/// Languages that are written right to left
+const RTL_LANGUAGES: [&str; 17] = [
+    "ar", "arc", "ckb", "dv", "fa", "ha", "he", "iw", "khw", "ks", "ku", "ps", "sd", "syr", "ug",
+    "ur", "yi",
+];
+
+impl TextDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+            Self::Auto => "auto",
+        }
+    }
+
+    /// Returns the direction the language of the given locale, e.g. `"ar-EG"`, is written in.
+    /// Locales with an explicit script like `"ku-Latn"` or `"az-Arab"` are respected.
+    pub fn from_locale(locale: &str) -> Self {
+        let locale = locale.replace('_', "-").to_lowercase();
+        let mut subtags = locale.split('-');
+
+        let language = subtags.next().unwrap_or_default();
+
+        // script subtags have four letters
+        if let Some(script) = subtags.find(|subtag| subtag.len() == 4) {
+            return match script {
+                "arab" | "hebr" | "syrc" | "thaa" | "nkoo" | "adlm" | "rohg" => Self::Rtl,
+                _ => Self::Ltr,
+            };
+        }
+
+        if RTL_LANGUAGES.contains(&language) {
+            Self::Rtl
+        } else {
+            Self::Ltr
+        }
+    }
+
+    /// Returns the direction of the first character with a strong direction in `text`
+    /// similar to what `dir="auto"` does. Returns `Ltr` if there is no such character.
+    pub fn from_text(text: &str) -> Self {
+        for c in text.chars() {
+            if is_rtl_char(c) {
+                return Self::Rtl;
+            }
+
+            if c.is_alphabetic() {
+                return Self::Ltr;
+            }
+        }
+
+        Self::Ltr
+    }
+}
+
+/// `true` for characters of the Hebrew, Arabic, Syriac, Thaana, N'Ko and related blocks
+fn is_rtl_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+    )
+}
+
+impl Display for TextDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for TextDirection {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "ltr" => Ok(Self::Ltr),
+            "rtl" => Ok(Self::Rtl),
+            "auto" => Ok(Self::Auto),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Options for [`use_text_direction_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseTextDirectionOptions<El, T>
+where
+    El: Clone,
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    /// Element to read the `dir` attribute from and write it to. Defaults to `"html"`.
+    target: El,
+
+    /// Direction that is used if the target has no valid `dir` attribute. Defaults to `Ltr`.
+    initial_value: TextDirection,
+
+    /// If `true` a `MutationObserver` keeps `dir` up to date with changes of the attribute made
+    /// by other code. Defaults to `false`.
+    observe: bool,
+
+    #[builder(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl Default for UseTextDirectionOptions<&'static str, web_sys::Element> {
+    fn default() -> Self {
+        Self {
+            target: "html",
+            initial_value: TextDirection::Ltr,
+            observe: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Return type of [`use_text_direction`].
+pub struct UseTextDirectionReturn {
+    /// The current direction
+    pub dir: Signal<TextDirection>,
+
+    /// Changes the direction and writes it to the `dir` attribute of the target
+    pub set_dir: WriteSignal<TextDirection>,
+
+    /// `true` if the direction is `Rtl`
+    pub is_rtl: Signal<bool>,
+}
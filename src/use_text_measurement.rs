@@ -0,0 +1,395 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{use_font_loading, UseFontLoadingReturn};
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+
+/// Maximum number of measured lines that are cached.
+const CACHE_SIZE: usize = 1000;
+
+/// Reactive size of a text when it's rendered with a given font.
+///
+/// Needed to truncate text, to size elements to their text or to lay out the labels of a chart
+/// before anything is rendered. By default the text is measured with a shared canvas and
+/// [`measureText`](https://developer.mozilla.org/en-US/docs/Web/API/CanvasRenderingContext2D/measureText).
+/// Measurements are cached and the cache is cleared when fonts are loaded (see
+/// [`fn@crate::use_font_loading`]), so the size is correct once a web font has arrived.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_text_measurement, UseTextMeasurementReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (text, set_text) = create_signal("Hello World".to_string());
+///
+/// let UseTextMeasurementReturn { width, height, .. } =
+///     use_text_measurement(text, "16px sans-serif");
+///
+/// view! {
+///     <input on:input=move |e| set_text.set(event_target_value(&e)) prop:value=text />
+///     <p>{move || format!("{:.1} x {:.1}", width.get(), height.get())}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Wrapping
+///
+/// With `max_width` the text is wrapped at whitespace like the browser does for
+/// `white-space: normal`. `line_count` tells how many lines are needed.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_text_measurement_with_options, UseTextMeasurementOptions, UseTextMeasurementReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseTextMeasurementReturn { line_count, .. } = use_text_measurement_with_options(
+///     "A long text that is going to be wrapped into several lines",
+///     "14px Inter",
+///     UseTextMeasurementOptions::default()
+///         .max_width(Some(120.0))
+///         .line_height(Some(20.0)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// The canvas doesn't know about CSS like `letter-spacing` or `text-transform`. Use
+/// `TextMeasurementMethod::Element` to measure with a hidden element instead, which is slower
+/// but takes the styles of the document into account.
+///
+/// For measuring many texts once, like the tick labels of a chart, use [`measure_text`].
+///
+/// ## Server-Side Rendering
+///
+/// On the server the size is always zero.
+pub fn use_text_measurement(
+    text: impl Into<MaybeSignal<String>>,
+    font: impl Into<MaybeSignal<String>>,
+) -> UseTextMeasurementReturn {
+    use_text_measurement_with_options(text, font, UseTextMeasurementOptions::default())
+}
+
+/// Version of [`use_text_measurement`] that takes a `UseTextMeasurementOptions`. See [`use_text_measurement`] for how to use.
+pub fn use_text_measurement_with_options(
+    text: impl Into<MaybeSignal<String>>,
+    font: impl Into<MaybeSignal<String>>,
+    options: UseTextMeasurementOptions,
+) -> UseTextMeasurementReturn {
+    let UseTextMeasurementOptions {
+        max_width,
+        line_height,
+        method,
+    } = options;
+
+    let text = text.into();
+    let font = font.into();
+
+    let UseFontLoadingReturn { fonts_changed, .. } = use_font_loading();
+
+    let fonts_generation = store_value(0_usize);
+
+    let size = create_memo(move |_| {
+        let generation = fonts_changed.get();
+        if generation != fonts_generation.get_value() {
+            fonts_generation.set_value(generation);
+            clear_cache();
+        }
+
+        let max_width = max_width.get();
+        let line_height = line_height.get();
+
+        font.with(|font| {
+            text.with(|text| match method {
+                TextMeasurementMethod::Canvas => {
+                    measure_wrapped(text, font, max_width, line_height)
+                }
+                TextMeasurementMethod::Element => {
+                    measure_with_element(text, font, max_width, line_height)
+                }
+            })
+        })
+    });
+
+    UseTextMeasurementReturn {
+        width: Signal::derive(move || size.get().width),
+        height: Signal::derive(move || size.get().height),
+        line_count: Signal::derive(move || size.get().line_count),
+    }
+}
+
+/// Measures a single line of `text` with `font` (like `"bold 12px sans-serif"`) on a shared
+/// canvas. The measurements are cached. Returns a zero size on the server.
+///
+/// ```
+/// # use leptos_use::measure_text;
+/// #
+/// # fn label_widths(labels: &[&str]) -> f64 {
+/// let widest = labels
+///     .iter()
+///     .map(|label| measure_text(label, "12px sans-serif").width)
+///     .fold(0.0, f64::max);
+/// # widest
+/// # }
+/// ```
+pub fn measure_text(text: &str, font: &str) -> TextSize {
+    measure_line(text, font)
+        .map(|line| TextSize {
+            width: line.width,
+            height: line.height,
+            line_count: 1,
+        })
+        .unwrap_or_default()
+}
+
+/// Measures `text` on the canvas and wraps it at whitespace if it's wider than `max_width`.
+fn measure_wrapped(
+    text: &str,
+    font: &str,
+    max_width: Option<f64>,
+    line_height: Option<f64>,
+) -> TextSize {
+    let mut lines = vec![];
+
+    for paragraph in text.split('\n') {
+        let Some(max_width) = max_width else {
+            lines.push(paragraph.to_string());
+            continue;
+        };
+
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            if line.is_empty() {
+                line.push_str(word);
+                continue;
+            }
+
+            let candidate = format!("{line} {word}");
+            let fits = measure_line(&candidate, font)
+                .map(|metrics| metrics.width <= max_width)
+                .unwrap_or(true);
+
+            if fits {
+                line = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+
+        lines.push(line);
+    }
+
+    let mut width = 0.0_f64;
+    let mut natural_line_height = 0.0_f64;
+
+    for line in &lines {
+        if let Some(metrics) = measure_line(line, font) {
+            width = width.max(metrics.width);
+            natural_line_height = natural_line_height.max(metrics.height);
+        }
+    }
+
+    TextSize {
+        width,
+        height: line_height.unwrap_or(natural_line_height) * lines.len() as f64,
+        line_count: lines.len(),
+    }
+}
+
+#[derive(Copy, Clone)]
+struct LineMetrics {
+    width: f64,
+    height: f64,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Option<web_sys::CanvasRenderingContext2d>> = const { RefCell::new(None) };
+    static CACHE: RefCell<HashMap<(String, String), LineMetrics>> = RefCell::new(HashMap::new());
+    static ELEMENT: RefCell<Option<web_sys::HtmlElement>> = const { RefCell::new(None) };
+}
+
+fn clear_cache() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+fn measure_line(text: &str, font: &str) -> Option<LineMetrics> {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        None
+    } else {
+        let key = (font.to_string(), text.to_string());
+
+        if let Some(metrics) = CACHE.with(|cache| cache.borrow().get(&key).copied()) {
+            return Some(metrics);
+        }
+
+        let metrics = CONTEXT.with(|context| {
+            let mut context = context.borrow_mut();
+
+            if context.is_none() {
+                *context = document()
+                    .create_element("canvas")
+                    .ok()
+                    .and_then(|canvas| {
+                        canvas
+                            .unchecked_into::<web_sys::HtmlCanvasElement>()
+                            .get_context("2d")
+                            .ok()
+                            .flatten()
+                    })
+                    .map(JsCast::unchecked_into);
+            }
+
+            let context = context.as_ref()?;
+            context.set_font(font);
+            let metrics = context.measure_text(text).ok()?;
+
+            Some(LineMetrics {
+                width: metrics.width(),
+                height: metrics.font_bounding_box_ascent() + metrics.font_bounding_box_descent(),
+            })
+        })?;
+
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.len() >= CACHE_SIZE {
+                cache.clear();
+            }
+            cache.insert(key, metrics);
+        });
+
+        Some(metrics)
+    }}
+}
+
+/// Measures `text` with a hidden element that is shared by all instances.
+fn measure_with_element(
+    text: &str,
+    font: &str,
+    max_width: Option<f64>,
+    line_height: Option<f64>,
+) -> TextSize {
+    cfg_if! { if #[cfg(feature = "ssr")] {
+        TextSize::default()
+    } else {
+        ELEMENT
+            .with(|element| {
+                let mut element = element.borrow_mut();
+
+                if element.is_none() {
+                    let el = document()
+                        .create_element("div")
+                        .ok()?
+                        .unchecked_into::<web_sys::HtmlElement>();
+                    let _ = el.set_attribute("aria-hidden", "true");
+                    document().body()?.append_child(&el).ok()?;
+                    *element = Some(el);
+                }
+
+                let el = element.as_ref()?;
+                let style = el.style();
+                style.set_css_text(
+                    "position: absolute; top: 0; left: -10000px; visibility: hidden; \
+                     pointer-events: none; overflow-wrap: break-word;",
+                );
+                let _ = style.set_property("font", font);
+                let _ = style.set_property(
+                    "line-height",
+                    &line_height.map_or("normal".to_string(), |height| format!("{height}px")),
+                );
+
+                // height of a single line to count the lines
+                let _ = style.set_property("white-space", "pre");
+                let _ = style.set_property("width", "max-content");
+                el.set_text_content(Some("X"));
+                let single_line_height = el.get_bounding_client_rect().height();
+
+                if let Some(max_width) = max_width {
+                    let _ = style.set_property("white-space", "pre-wrap");
+                    let _ = style.set_property("max-width", &format!("{max_width}px"));
+                }
+                el.set_text_content(Some(text));
+                let rect = el.get_bounding_client_rect();
+
+                Some(TextSize {
+                    width: rect.width(),
+                    height: rect.height(),
+                    line_count: if single_line_height > 0.0 {
+                        (rect.height() / single_line_height).round() as usize
+                    } else {
+                        0
+                    },
+                })
+            })
+            .unwrap_or_default()
+    }}
+}
+
+/// How [`use_text_measurement`] measures the text.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TextMeasurementMethod {
+    /// With `measureText` of a canvas. Fast but ignores the CSS of the document.
+    #[default]
+    Canvas,
+
+    /// With a hidden element. Slower because it forces a layout but respects the CSS of the
+    /// document like `letter-spacing`.
+    Element,
+}
+
+/// Options for [`use_text_measurement_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseTextMeasurementOptions {
+    /// Width in pixels at which the text is wrapped. Defaults to `None` which means the text
+    /// is only broken at newlines.
+    #[builder(into)]
+    max_width: MaybeSignal<Option<f64>>,
+
+    /// Height of a line in pixels. Defaults to `None` which means the height of the font.
+    #[builder(into)]
+    line_height: MaybeSignal<Option<f64>>,
+
+    /// How the text is measured. Defaults to `TextMeasurementMethod::Canvas`.
+    method: TextMeasurementMethod,
+}
+
+impl Default for UseTextMeasurementOptions {
+    fn default() -> Self {
+        Self {
+            max_width: None.into(),
+            line_height: None.into(),
+            method: TextMeasurementMethod::default(),
+        }
+    }
+}
+
+/// Return type of [`use_text_measurement`].
+pub struct UseTextMeasurementReturn {
+    /// Width of the widest line in pixels
+    pub width: Signal<f64>,
+
+    /// Height of all lines in pixels
+    pub height: Signal<f64>,
+
+    /// Number of lines
+    pub line_count: Signal<usize>,
+}
+
+/// Size of a text as measured by [`use_text_measurement`] and [`measure_text`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TextSize {
+    /// Width of the widest line in pixels
+    pub width: f64,
+    /// Height of all lines in pixels
+    pub height: f64,
+    /// Number of lines
+    pub line_count: usize,
+}
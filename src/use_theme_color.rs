@@ -0,0 +1,224 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use crate::core::MaybeRwSignal;
+use cfg_if::cfg_if;
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive `<meta name="theme-color">`.
+///
+/// The theme color tints the browser UI around the page, like the address bar on mobile.
+/// Setting the color replaces the `theme-color` meta tags of the document by the ones managed by
+/// this function. Setting it to `None` or disposing the owner of this function restores the
+/// original tags.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::use_theme_color;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (theme_color, set_theme_color) = use_theme_color();
+///
+/// set_theme_color.set(Some("#3f51b5".into()));
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Light and Dark Colors
+///
+/// A [`ThemeColor`] can consist of several colors with a media query each. Every color is
+/// rendered as its own meta tag with a `media` attribute so the browser picks the right one
+/// by itself.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_theme_color_with_options, ThemeColor, UseThemeColorOptions};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let _ = use_theme_color_with_options(
+///     UseThemeColorOptions::default().new_color(Some(ThemeColor::light_dark("#ffffff", "#121212"))),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Passing a Source Signal
+///
+/// The color can also follow a signal. For example to derive it from the color mode of your
+/// app instead of the preference of the browser:
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_color_mode, use_theme_color_with_options, ColorMode, UseColorModeReturn,
+/// #     UseThemeColorOptions,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseColorModeReturn { mode, .. } = use_color_mode();
+///
+/// let _ = use_theme_color_with_options(
+///     UseThemeColorOptions::default().new_color(Signal::derive(move || {
+///         Some(match mode.get() {
+///             ColorMode::Dark => "#121212".into(),
+///             _ => "#ffffff".into(),
+///         })
+///     })),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server only the signals work but no meta tags are changed.
+pub fn use_theme_color() -> (Signal<Option<ThemeColor>>, WriteSignal<Option<ThemeColor>>) {
+    use_theme_color_with_options(UseThemeColorOptions::default())
+}
+
+/// Version of [`use_theme_color`] that accepts a `UseThemeColorOptions`. See [`use_theme_color`] for more details.
+pub fn use_theme_color_with_options(
+    options: UseThemeColorOptions,
+) -> (Signal<Option<ThemeColor>>, WriteSignal<Option<ThemeColor>>) {
+    let UseThemeColorOptions { new_color } = options;
+
+    let (theme_color, set_theme_color) = new_color.into_signal();
+
+    cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        // the meta tags of the document that are replaced while a color is set
+        let original = store_value(Vec::<web_sys::Element>::new());
+        let managed = store_value(Vec::<web_sys::Element>::new());
+
+        let restore = move || {
+            managed.update_value(|managed| {
+                for meta in managed.drain(..) {
+                    meta.remove();
+                }
+            });
+
+            if let Some(head) = document().head() {
+                original.update_value(|original| {
+                    for meta in original.drain(..) {
+                        let _ = head.append_child(&meta);
+                    }
+                });
+            }
+        };
+
+        let apply = move |color: &ThemeColor| {
+            let Some(head) = document().head() else {
+                return;
+            };
+
+            managed.update_value(|managed| {
+                for meta in managed.drain(..) {
+                    meta.remove();
+                }
+            });
+
+            if let Ok(metas) = head.query_selector_all("meta[name=\"theme-color\"]") {
+                original.update_value(|original| {
+                    for i in 0..metas.length() {
+                        let meta: web_sys::Element =
+                            metas.get(i).expect("checked length").unchecked_into();
+                        meta.remove();
+                        original.push(meta);
+                    }
+                });
+            }
+
+            for (media, color) in color.entries() {
+                let Ok(meta) = document().create_element("meta") else {
+                    continue;
+                };
+
+                let _ = meta.set_attribute("name", "theme-color");
+                let _ = meta.set_attribute("content", color);
+                if let Some(media) = media {
+                    let _ = meta.set_attribute("media", media);
+                }
+
+                let _ = head.append_child(&meta);
+                managed.update_value(|managed| managed.push(meta));
+            }
+        };
+
+        let _ = watch(
+            move || theme_color.get(),
+            move |new_color, prev_color, _| {
+                if Some(new_color) != prev_color {
+                    match new_color {
+                        Some(new_color) => apply(new_color),
+                        None => restore(),
+                    }
+                }
+            },
+            true,
+        );
+
+        on_cleanup(restore);
+    }}
+
+    (theme_color, set_theme_color)
+}
+
+/// The theme color(s) set by [`use_theme_color`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ThemeColor {
+    /// A single color for all media.
+    Color(String),
+
+    /// Several colors with the media query that they apply to, e.g.
+    /// `("(prefers-color-scheme: dark)", "#121212")`.
+    Media(Vec<(String, String)>),
+}
+
+impl ThemeColor {
+    /// Uses `light` if the user prefers a light color scheme and `dark` if they prefer a dark one.
+    pub fn light_dark(light: impl Into<String>, dark: impl Into<String>) -> Self {
+        Self::Media(vec![
+            ("(prefers-color-scheme: light)".to_string(), light.into()),
+            ("(prefers-color-scheme: dark)".to_string(), dark.into()),
+        ])
+    }
+
+    fn entries(&self) -> Vec<(Option<&str>, &str)> {
+        match self {
+            Self::Color(color) => vec![(None, color.as_str())],
+            Self::Media(colors) => colors
+                .iter()
+                .map(|(media, color)| (Some(media.as_str()), color.as_str()))
+                .collect(),
+        }
+    }
+}
+
+impl From<&str> for ThemeColor {
+    fn from(color: &str) -> Self {
+        Self::Color(color.to_string())
+    }
+}
+
+impl From<String> for ThemeColor {
+    fn from(color: String) -> Self {
+        Self::Color(color)
+    }
+}
+
+/// Options for [`use_theme_color_with_options`].
+#[derive(DefaultBuilder, Default)]
+pub struct UseThemeColorOptions {
+    /// Initial theme color. Can be a signal in which case updates will change the theme color.
+    /// `None` keeps the meta tags of the document. Defaults to `None`.
+    #[builder(into)]
+    new_color: MaybeRwSignal<Option<ThemeColor>>,
+}
@@ -1,6 +1,9 @@
+use crate::core::now;
+use crate::utils::{use_visibility_scheduler, TimerHiddenBehavior};
+use default_struct_builder::DefaultBuilder;
 use leptos::leptos_dom::helpers::TimeoutHandle;
 use leptos::*;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::time::Duration;
@@ -31,6 +34,39 @@ use std::time::Duration;
 /// # view! { }
 /// # }
 /// ```
+///
+/// Calling `start` while the timeout is pending restarts it with the new argument.
+///
+/// ### Hidden Page
+///
+/// Browsers throttle timers in background tabs. With the option `hidden_behavior` the timeout
+/// can be stopped while the page is hidden instead. See [`TimerHiddenBehavior`] for details.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_timeout_fn_with_options, UseTimeoutFnOptions, UseTimeoutFnReturn};
+/// # use leptos_use::utils::TimerHiddenBehavior;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseTimeoutFnReturn {
+///     start,
+///     is_suspended,
+///     ..
+/// } = use_timeout_fn_with_options(
+///     |_: ()| {
+///         // show a notification
+///     },
+///     10000.0,
+///     UseTimeoutFnOptions::default().hidden_behavior(TimerHiddenBehavior::Pause),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// `is_suspended` is `true` while the timeout is stopped because of this and `hidden_duration`
+/// tells you for how long the page has been hidden the last time.
 pub fn use_timeout_fn<CbFn, Arg, D>(
     callback: CbFn,
     delay: D,
@@ -40,11 +76,33 @@ where
     Arg: 'static,
     D: Into<MaybeSignal<f64>>,
 {
+    use_timeout_fn_with_options(callback, delay, UseTimeoutFnOptions::default())
+}
+
+/// Version of [`use_timeout_fn`] that takes `UseTimeoutFnOptions`. See [`use_timeout_fn`] for how to use.
+pub fn use_timeout_fn_with_options<CbFn, Arg, D>(
+    callback: CbFn,
+    delay: D,
+    options: UseTimeoutFnOptions,
+) -> UseTimeoutFnReturn<impl Fn(Arg) + Clone, Arg, impl Fn() + Clone>
+where
+    CbFn: Fn(Arg) + Clone + 'static,
+    Arg: 'static,
+    D: Into<MaybeSignal<f64>>,
+{
+    let UseTimeoutFnOptions { hidden_behavior } = options;
+
     let delay = delay.into();
 
     let (is_pending, set_pending) = create_signal(false);
 
     let timer = Rc::new(Cell::new(None::<TimeoutHandle>));
+    let pending_arg = Rc::new(RefCell::new(None::<Arg>));
+
+    // Time when the callback is due
+    let due_at = store_value(0.0_f64);
+    // Time that was left when the timeout has been suspended
+    let remaining = store_value(0.0_f64);
 
     let clear = {
         let timer = Rc::clone(&timer);
@@ -56,45 +114,97 @@ where
         }
     };
 
+    let fire = {
+        let timer = Rc::clone(&timer);
+        let pending_arg = Rc::clone(&pending_arg);
+
+        move || {
+            set_pending.set(false);
+            timer.set(None);
+
+            if let Some(arg) = pending_arg.take() {
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                callback(arg);
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+            }
+        }
+    };
+
+    let schedule = {
+        let timer = Rc::clone(&timer);
+        let fire = fire.clone();
+
+        move |delay: f64| {
+            due_at.set_value(now() + delay);
+
+            let handle =
+                set_timeout_with_handle(fire.clone(), Duration::from_millis(delay as u64)).ok();
+
+            timer.set(handle);
+        }
+    };
+
+    let scheduler = use_visibility_scheduler(
+        hidden_behavior,
+        move || is_pending.get_untracked(),
+        {
+            let clear = clear.clone();
+
+            move || {
+                remaining.set_value(due_at.get_value() - now());
+                clear();
+            }
+        },
+        {
+            let schedule = schedule.clone();
+
+            move || {
+                let delay = match hidden_behavior {
+                    TimerHiddenBehavior::CatchUp => due_at.get_value() - now(),
+                    _ => remaining.get_value(),
+                };
+
+                if delay > 0.0 {
+                    schedule(delay);
+                } else {
+                    fire();
+                }
+            }
+        },
+    );
+
     let stop = {
         let clear = clear.clone();
+        let pending_arg = Rc::clone(&pending_arg);
 
         move || {
             set_pending.set(false);
+            scheduler.cancel();
             clear();
+            pending_arg.take();
         }
     };
 
     let start = {
-        let timer = Rc::clone(&timer);
-        let callback = callback.clone();
+        let clear = clear.clone();
 
         move |arg: Arg| {
+            clear();
             set_pending.set(true);
+            pending_arg.replace(Some(arg));
 
-            let handle = set_timeout_with_handle(
-                {
-                    let timer = Rc::clone(&timer);
-                    let callback = callback.clone();
-
-                    move || {
-                        set_pending.set(false);
-                        timer.set(None);
-
-                        #[cfg(debug_assertions)]
-                        let prev = SpecialNonReactiveZone::enter();
+            let delay = delay.get_untracked();
 
-                        callback(arg);
-
-                        #[cfg(debug_assertions)]
-                        SpecialNonReactiveZone::exit(prev);
-                    }
-                },
-                Duration::from_millis(delay.get_untracked() as u64),
-            )
-            .ok();
-
-            timer.set(handle);
+            if scheduler.defer() {
+                due_at.set_value(now() + delay);
+                remaining.set_value(delay);
+            } else {
+                schedule(delay);
+            }
         }
     };
 
@@ -102,12 +212,21 @@ where
 
     UseTimeoutFnReturn {
         is_pending: is_pending.into(),
+        is_suspended: scheduler.is_suspended.into(),
+        hidden_duration: scheduler.hidden_duration.into(),
         start,
         stop,
         _marker: PhantomData,
     }
 }
 
+/// Options for [`use_timeout_fn_with_options`]
+#[derive(DefaultBuilder, Default)]
+pub struct UseTimeoutFnOptions {
+    /// What happens while the page is hidden. Defaults to [`TimerHiddenBehavior::Continue`].
+    pub hidden_behavior: TimerHiddenBehavior,
+}
+
 /// Return type of [`use_timeout_fn`].
 pub struct UseTimeoutFnReturn<StartFn, StartArg, StopFn>
 where
@@ -117,6 +236,14 @@ where
     /// Whether the timeout is pending. When the `callback` is called this is set to `false`.
     pub is_pending: Signal<bool>,
 
+    /// `true` while the timeout is stopped because the page is hidden. See the option
+    /// `hidden_behavior`.
+    pub is_suspended: Signal<bool>,
+
+    /// How long in ms the page has been hidden the last time. Updated when the page becomes
+    /// visible again. Always `0.0` with [`TimerHiddenBehavior::Continue`].
+    pub hidden_duration: Signal<f64>,
+
     /// Start the timeout. The `callback` will be called after `delay` milliseconds.
     pub start: StartFn,
 
@@ -1,11 +1,15 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
 use crate::core::now;
 use crate::utils::Pausable;
 use crate::{
-    use_interval_fn_with_options, use_raf_fn_with_options, UseIntervalFnOptions, UseRafFnOptions,
+    js_fut, use_interval_fn, use_interval_fn_with_options, use_raf_fn_with_options,
+    UseIntervalFnOptions, UseIntervalFnReturn, UseRafFnOptions,
 };
 use default_struct_builder::DefaultBuilder;
 use leptos::*;
 use std::rc::Rc;
+use wasm_bindgen::JsCast;
 
 /// Reactive current timestamp.
 ///
@@ -40,16 +44,50 @@ use std::rc::Rc;
 ///     is_active,
 ///     pause,
 ///     resume,
+///     ..
 /// } = use_timestamp_with_controls();
 /// #
 /// # view! { }
 /// # }
 /// ```
 ///
+/// ### Clock Sources
+///
+/// By default the timestamp is read from `Date.now()` which follows changes of the system clock.
+/// With the option `clock` you can choose a different [`TimestampClock`]:
+///
+/// - `TimestampClock::Performance` is based on `performance.now()` which is monotonic and has a
+///   higher resolution.
+/// - `TimestampClock::Server` is synchronized with the clock of your backend. This way countdowns
+///   and schedules agree with it even if the clock of the user's device is off.
+///
+/// To synchronize with the server, the endpoint given to [`ServerClock`] is requested a few times.
+/// It has to respond with its current time in milliseconds since the Unix epoch. The round trip
+/// time is measured for every request and the server time is assumed to be read halfway through
+/// it. The estimate of the request with the shortest round trip is used. Until the first
+/// synchronization has finished the local clock is used.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_timestamp_with_controls_and_options, ServerClock, UseTimestampOptions, UseTimestampReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseTimestampReturn { timestamp, server_offset, .. } = use_timestamp_with_controls_and_options(
+///     UseTimestampOptions::default()
+///         .interval(1000)
+///         .clock(ServerClock::new("/api/time").resync_interval(60_000)),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
 /// ## Server-Side Rendering
 ///
 /// On the server this function will return a signal with the milliseconds since the Unix epoch.
 /// But the signal will never update (as there's no `request_animation_frame` on the server).
+/// `TimestampClock::Server` uses the local clock there since it's the backend's clock anyway.
 pub fn use_timestamp() -> Signal<f64> {
     use_timestamp_with_controls().timestamp
 }
@@ -71,12 +109,52 @@ pub fn use_timestamp_with_controls_and_options(options: UseTimestampOptions) ->
         immediate,
         interval,
         callback,
+        clock,
     } = options;
 
-    let (ts, set_ts) = create_signal(now() + offset);
+    let (server_offset, set_server_offset) = create_signal(None::<f64>);
+
+    let (is_monotonic, server_clock) = match clock {
+        TimestampClock::Date => (false, None),
+        TimestampClock::Performance => (true, None),
+        TimestampClock::Server(server_clock) => (true, Some(server_clock)),
+    };
+
+    let read_clock = move || {
+        if is_monotonic {
+            performance_now() + server_offset.get_untracked().unwrap_or_default()
+        } else {
+            now()
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    if let Some(server_clock) = server_clock {
+        let resync_interval = server_clock.resync_interval;
+        let server_clock = Rc::new(server_clock);
+
+        let sync = move || {
+            let server_clock = Rc::clone(&server_clock);
+
+            spawn_local(async move {
+                if let Some(offset) = estimate_server_offset(&server_clock).await {
+                    // the hook might have been disposed in the meantime
+                    let _ = set_server_offset.try_set(Some(offset));
+                }
+            });
+        };
+
+        sync();
+
+        if let Some(resync_interval) = resync_interval {
+            let _ = use_interval_fn(sync, resync_interval);
+        }
+    }
+
+    let (ts, set_ts) = create_signal(read_clock() + offset);
 
     let update = move || {
-        set_ts.set(now() + offset);
+        set_ts.set(read_clock() + offset);
     };
 
     let cb = {
@@ -111,14 +189,16 @@ pub fn use_timestamp_with_controls_and_options(options: UseTimestampOptions) ->
                 is_active,
                 pause: Rc::new(pause),
                 resume: Rc::new(resume),
+                server_offset: server_offset.into(),
             }
         }
 
         TimestampInterval::Interval(interval) => {
-            let Pausable {
+            let UseIntervalFnReturn {
                 pause,
                 resume,
                 is_active,
+                ..
             } = use_interval_fn_with_options(
                 cb,
                 interval,
@@ -130,6 +210,7 @@ pub fn use_timestamp_with_controls_and_options(options: UseTimestampOptions) ->
                 is_active,
                 pause: Rc::new(pause),
                 resume: Rc::new(resume),
+                server_offset: server_offset.into(),
             }
         }
     }
@@ -150,6 +231,119 @@ pub struct UseTimestampOptions {
 
     /// Callback to be called whenever the timestamp is updated.
     callback: Rc<dyn Fn(f64)>,
+
+    /// Where the timestamp is read from. Defaults to `TimestampClock::Date`.
+    #[builder(into)]
+    clock: TimestampClock,
+}
+
+/// Clock source for [`UseTimestampOptions`].
+#[derive(Clone, Default)]
+pub enum TimestampClock {
+    /// `Date.now()`. Follows changes of the system clock.
+    #[default]
+    Date,
+
+    /// `performance.timeOrigin + performance.now()`. Monotonic and with a higher resolution.
+    Performance,
+
+    /// Like `Performance` but synchronized with the clock of a server.
+    Server(ServerClock),
+}
+
+impl From<ServerClock> for TimestampClock {
+    fn from(value: ServerClock) -> Self {
+        Self::Server(value)
+    }
+}
+
+/// Configures how [`TimestampClock::Server`] is synchronized.
+#[derive(DefaultBuilder, Clone)]
+pub struct ServerClock {
+    /// URL of the endpoint that responds with its current time in milliseconds since the Unix epoch.
+    #[builder(into)]
+    url: String,
+
+    /// How many requests are made per synchronization. The one with the shortest round trip is
+    /// used. Defaults to `3`.
+    samples: usize,
+
+    /// Interval in milliseconds in which the clock is synchronized again.
+    /// Defaults to `None` which synchronizes only once.
+    #[builder(into)]
+    resync_interval: Option<u64>,
+
+    /// Reads the server time from the response body. Defaults to parsing the body as a number.
+    parse_response: Rc<dyn Fn(&str) -> Option<f64>>,
+}
+
+impl ServerClock {
+    /// Synchronizes with the endpoint at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            samples: 3,
+            resync_interval: None,
+            parse_response: Rc::new(|body| body.trim().parse().ok()),
+        }
+    }
+}
+
+/// SSR safe `performance.timeOrigin + performance.now()`.
+fn performance_now() -> f64 {
+    #[cfg(not(feature = "ssr"))]
+    if let Some(performance) = window().performance() {
+        return performance.time_origin() + performance.now();
+    }
+
+    now()
+}
+
+/// Estimates how far the server clock is ahead of [`performance_now`].
+async fn estimate_server_offset(server_clock: &ServerClock) -> Option<f64> {
+    // (round trip, offset) of the best sample
+    let mut best = None::<(f64, f64)>;
+
+    for _ in 0..server_clock.samples.max(1) {
+        let sent = performance_now();
+        let Some(server_time) = fetch_server_time(server_clock).await else {
+            continue;
+        };
+        let received = performance_now();
+
+        let round_trip = received - sent;
+        // assume the server read its clock halfway through the round trip
+        let offset = server_time + round_trip / 2.0 - received;
+
+        let is_better = match best {
+            Some((best_round_trip, _)) => round_trip < best_round_trip,
+            None => true,
+        };
+
+        if is_better {
+            best = Some((round_trip, offset));
+        }
+    }
+
+    best.map(|(_, offset)| offset)
+}
+
+async fn fetch_server_time(server_clock: &ServerClock) -> Option<f64> {
+    let mut init = web_sys::RequestInit::new();
+    init.cache(web_sys::RequestCache::NoStore);
+
+    let response = js_fut!(window().fetch_with_str_and_init(&server_clock.url, &init))
+        .await
+        .ok()?
+        .unchecked_into::<web_sys::Response>();
+
+    if !response.ok() {
+        return None;
+    }
+
+    let body = js_fut!(response.text().ok()?).await.ok()?.as_string()?;
+
+    (server_clock.parse_response)(&body)
 }
 
 /// Interval type for [`UseTimestampOptions`].
@@ -175,6 +369,7 @@ impl Default for UseTimestampOptions {
             immediate: true,
             interval: TimestampInterval::RequestAnimationFrame,
             callback: Rc::new(|_| {}),
+            clock: TimestampClock::default(),
         }
     }
 }
@@ -192,4 +387,8 @@ pub struct UseTimestampReturn {
 
     /// Resume the timestamp updating
     pub resume: Rc<dyn Fn()>,
+
+    /// How far the server clock is ahead of the local clock in milliseconds. `None` until the
+    /// first synchronization of `TimestampClock::Server` has finished and for the other clocks.
+    pub server_offset: Signal<Option<f64>>,
 }
@@ -0,0 +1,357 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{js, js_fut};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Reactive [User-Agent Client Hints](https://developer.mozilla.org/en-US/docs/Web/API/User-Agent_Client_Hints_API)
+/// (`navigator.userAgentData`).
+///
+/// The low entropy values (brands, mobile and platform) are available immediately. The high
+/// entropy values (like the platform version or the device model) are requested asynchronously
+/// and filled in as soon as the browser provides them.
+///
+/// Browsers that don't support client hints (Firefox and Safari) get the values from parsing
+/// `navigator.userAgent` instead. This is done by [`parse_user_agent`] which you can also use
+/// on the server with the `User-Agent` header.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_user_agent_data, UseUserAgentDataReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseUserAgentDataReturn { data, .. } = use_user_agent_data();
+///
+/// view! {
+///     <Show when=move || data.with(|data| data.platform == "Android")>
+///         <a href="https://play.google.com">"Get the app"</a>
+///     </Show>
+///     <p>{move || data.with(|data| data.model.clone().unwrap_or_default())}</p>
+/// }
+/// # }
+/// ```
+///
+/// Grease brands like `"Not_A Brand"` that browsers add to prevent sniffing are removed from
+/// `brands`.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `data` is `UserAgentData::default()`.
+pub fn use_user_agent_data() -> UseUserAgentDataReturn {
+    use_user_agent_data_with_options(UseUserAgentDataOptions::default())
+}
+
+/// Version of [`use_user_agent_data`] that takes a `UseUserAgentDataOptions`. See [`use_user_agent_data`] for how to use.
+pub fn use_user_agent_data_with_options(
+    options: UseUserAgentDataOptions,
+) -> UseUserAgentDataReturn {
+    let UseUserAgentDataOptions { hints } = options;
+
+    let (data, set_data) = create_signal(UserAgentData::default());
+    let (is_supported, set_supported) = create_signal(false);
+    let (is_complete, set_complete) = create_signal(false);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let navigator = window().navigator();
+
+        let ua_data = js!(navigator["userAgentData"])
+            .ok()
+            .filter(|ua_data| !ua_data.is_undefined() && !ua_data.is_null());
+
+        if let Some(ua_data) = ua_data {
+            set_supported.set(true);
+            set_data.set(UserAgentData {
+                brands: get_brands(&ua_data, "brands"),
+                mobile: js!(ua_data["mobile"])
+                    .ok()
+                    .and_then(|mobile| mobile.as_bool())
+                    .unwrap_or_default(),
+                platform: get_string(&ua_data, "platform").unwrap_or_default(),
+                from_client_hints: true,
+                ..Default::default()
+            });
+
+            spawn_local(async move {
+                let hints = hints
+                    .iter()
+                    .map(|hint| JsValue::from_str(hint))
+                    .collect::<js_sys::Array>();
+
+                let values = match js!(ua_data["getHighEntropyValues"]) {
+                    Ok(get_values) => match get_values
+                        .unchecked_into::<js_sys::Function>()
+                        .call1(&ua_data, &hints)
+                    {
+                        Ok(promise) => js_fut!(promise.unchecked_into::<js_sys::Promise>())
+                            .await
+                            .ok(),
+                        Err(_) => None,
+                    },
+                    Err(_) => None,
+                };
+
+                if let Some(values) = values {
+                    set_data.update(|data| {
+                        let full_version_list = get_brands(&values, "fullVersionList");
+                        if !full_version_list.is_empty() {
+                            data.brands = full_version_list;
+                        }
+
+                        data.platform_version = get_string(&values, "platformVersion");
+                        data.model = get_string(&values, "model").filter(|model| !model.is_empty());
+                        data.architecture = get_string(&values, "architecture");
+                        data.bitness = get_string(&values, "bitness");
+                    });
+                }
+
+                set_complete.set(true);
+            });
+        } else {
+            set_data.set(parse_user_agent(
+                &navigator.user_agent().unwrap_or_default(),
+            ));
+            set_complete.set(true);
+        }
+    }
+
+    UseUserAgentDataReturn {
+        data: data.into(),
+        is_supported: is_supported.into(),
+        is_complete: is_complete.into(),
+    }
+}
+
+fn get_string(object: &JsValue, key: &str) -> Option<String> {
+    js_sys::Reflect::get(object, &key.into()).ok()?.as_string()
+}
+
+fn get_brands(object: &JsValue, key: &str) -> Vec<UserAgentBrand> {
+    js_sys::Reflect::get(object, &key.into())
+        .ok()
+        .filter(|brands| brands.is_array())
+        .map(|brands| {
+            brands
+                .unchecked_into::<js_sys::Array>()
+                .iter()
+                .filter_map(|brand| {
+                    Some(UserAgentBrand {
+                        brand: get_string(&brand, "brand")?,
+                        version: get_string(&brand, "version").unwrap_or_default(),
+                    })
+                })
+                .filter(|brand| !is_grease_brand(&brand.brand))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_grease_brand(brand: &str) -> bool {
+    brand.contains("Not") && brand.contains("Brand")
+}
+
+/// Parses a user agent string into [`UserAgentData`].
+///
+/// Only the major version of the browser is extracted. On Windows the platform version is the
+/// version of Windows NT, which is `10.0` for Windows 10 and 11.
+///
+/// ```
+/// # use leptos_use::parse_user_agent;
+/// #
+/// let data = parse_user_agent(
+///     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Safari/605.1.15",
+/// );
+///
+/// assert_eq!(data.platform, "macOS");
+/// assert_eq!(data.platform_version.as_deref(), Some("10.15.7"));
+/// assert_eq!(data.brands[0].brand, "Safari");
+/// assert_eq!(data.brands[0].version, "17");
+/// assert!(!data.mobile);
+/// ```
+pub fn parse_user_agent(user_agent: &str) -> UserAgentData {
+    let token_version = |token: &str| {
+        user_agent.split_once(token).map(|(_, rest)| {
+            rest.split(|c: char| c == ' ' || c == ';' || c == ')')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+    };
+
+    let major = |version: String| version.split('.').next().unwrap_or_default().to_string();
+
+    let browser = [
+        ("Edg/", "Microsoft Edge"),
+        ("EdgiOS/", "Microsoft Edge"),
+        ("OPR/", "Opera"),
+        ("SamsungBrowser/", "Samsung Internet"),
+        ("Firefox/", "Firefox"),
+        ("FxiOS/", "Firefox"),
+        ("CriOS/", "Google Chrome"),
+        ("Chrome/", "Google Chrome"),
+    ]
+    .into_iter()
+    .find_map(|(token, brand)| token_version(token).map(|version| (brand, version)))
+    .or_else(|| {
+        if user_agent.contains("Safari/") {
+            token_version("Version/").map(|version| ("Safari", version))
+        } else {
+            None
+        }
+    });
+
+    let brands = browser
+        .map(|(brand, version)| {
+            vec![UserAgentBrand {
+                brand: brand.to_string(),
+                version: major(version),
+            }]
+        })
+        .unwrap_or_default();
+
+    let (platform, platform_version) = if user_agent.contains("Windows") {
+        ("Windows", token_version("Windows NT "))
+    } else if ["iPhone", "iPad", "iPod"]
+        .iter()
+        .any(|device| user_agent.contains(device))
+    {
+        ("iOS", token_version(" OS ").map(|v| v.replace('_', ".")))
+    } else if user_agent.contains("Android") {
+        ("Android", token_version("Android "))
+    } else if user_agent.contains("CrOS") {
+        ("Chrome OS", None)
+    } else if user_agent.contains("Mac OS X") {
+        (
+            "macOS",
+            token_version("Mac OS X ").map(|v| v.replace('_', ".")),
+        )
+    } else if user_agent.contains("Linux") {
+        ("Linux", None)
+    } else {
+        ("Unknown", None)
+    };
+
+    let model = if platform == "Android" {
+        parse_android_model(user_agent)
+    } else {
+        None
+    };
+
+    let lower = user_agent.to_lowercase();
+    let (architecture, bitness) = if ["x86_64", "x64", "win64", "wow64", "amd64"]
+        .iter()
+        .any(|arch| lower.contains(arch))
+    {
+        (Some("x86"), Some("64"))
+    } else if ["arm64", "aarch64"].iter().any(|arch| lower.contains(arch)) {
+        (Some("arm"), Some("64"))
+    } else {
+        (None, None)
+    };
+
+    UserAgentData {
+        brands,
+        mobile: user_agent.contains("Mobi") || user_agent.contains("iPhone"),
+        platform: platform.to_string(),
+        platform_version,
+        model,
+        architecture: architecture.map(str::to_string),
+        bitness: bitness.map(str::to_string),
+        from_client_hints: false,
+    }
+}
+
+/// Extracts the model from e.g. `"Mozilla/5.0 (Linux; Android 13; Pixel 7 Build/TQ3A.230901.001) ..."`
+fn parse_android_model(user_agent: &str) -> Option<String> {
+    let (_, rest) = user_agent.split_once('(')?;
+    let details = rest.split(')').next()?;
+    let model = details.split(';').nth(2)?.trim();
+    let model = model.split(" Build/").next().unwrap_or(model);
+
+    // reduced user agents don't contain the model anymore
+    if model.is_empty() || model == "K" {
+        None
+    } else {
+        Some(model.to_string())
+    }
+}
+
+/// Options for [`use_user_agent_data_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseUserAgentDataOptions {
+    /// High entropy hints that are requested. Defaults to
+    /// `["platformVersion", "model", "architecture", "bitness", "fullVersionList"]`.
+    hints: Vec<String>,
+}
+
+impl Default for UseUserAgentDataOptions {
+    fn default() -> Self {
+        Self {
+            hints: [
+                "platformVersion",
+                "model",
+                "architecture",
+                "bitness",
+                "fullVersionList",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Return type of [`use_user_agent_data`].
+pub struct UseUserAgentDataReturn {
+    /// The user agent data
+    pub data: Signal<UserAgentData>,
+
+    /// `true` if the browser supports client hints
+    pub is_supported: Signal<bool>,
+
+    /// `true` as soon as the high entropy values have been received (or parsed from the user
+    /// agent string)
+    pub is_complete: Signal<bool>,
+}
+
+/// Information about the browser and the device. Returned by [`use_user_agent_data`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserAgentData {
+    /// Browser brands with their versions, e.g. `"Google Chrome"` and `"Chromium"`
+    pub brands: Vec<UserAgentBrand>,
+
+    /// `true` if the browser runs on a mobile device
+    pub mobile: bool,
+
+    /// Operating system, e.g. `"Windows"`, `"macOS"`, `"Android"`
+    pub platform: String,
+
+    /// Version of the operating system
+    pub platform_version: Option<String>,
+
+    /// Model of the device. Usually only reported on Android.
+    pub model: Option<String>,
+
+    /// CPU architecture, e.g. `"x86"` or `"arm"`
+    pub architecture: Option<String>,
+
+    /// CPU bitness, e.g. `"64"`
+    pub bitness: Option<String>,
+
+    /// `true` if the data comes from client hints and not from parsing the user agent string
+    pub from_client_hints: bool,
+}
+
+/// A browser brand of [`UserAgentData`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserAgentBrand {
+    /// Name of the brand, e.g. `"Google Chrome"`
+    pub brand: String,
+
+    /// Version of the brand. Only the major version unless the full version list was requested.
+    pub version: String,
+}
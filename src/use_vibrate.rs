@@ -0,0 +1,339 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::{
+    js, js_fut, use_event_listener, use_interval_fn_with_options, use_media_query, use_supported,
+    UseIntervalFnOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::Custom;
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+/// Reactive [Vibration API](https://developer.mozilla.org/en-US/docs/Web/API/Vibration_API)
+///
+/// Most modern mobile devices include vibration hardware, which allows software code to provide
+/// physical feedback to the user by causing the device to shake.
+///
+/// The pattern is a list of durations in ms that alternate between vibrating and pausing.
+/// There are presets for common kinds of feedback in [`VibratePreset`].
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_vibrate_with_options, UseVibrateOptions, UseVibrateReturn, VibratePreset};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseVibrateReturn { vibrate, .. } = use_vibrate_with_options(
+///     UseVibrateOptions::default().pattern(VibratePreset::Success),
+/// );
+///
+/// view! {
+///     <button on:click=move |_| vibrate()>"Save"</button>
+/// }
+/// # }
+/// ```
+///
+/// ## Reactive Pattern
+///
+/// The pattern can be a signal. By default the device vibrates every time it changes.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_vibrate_with_options, UseVibrateOptions, VibratePreset};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (pattern, set_pattern) = create_signal(VibratePreset::Tap.pattern());
+///
+/// use_vibrate_with_options(UseVibrateOptions::default().pattern(pattern));
+///
+/// // vibrates
+/// set_pattern.set(VibratePreset::Error.pattern());
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Loop
+///
+/// With `interval` the pattern is repeated every `interval` ms until `stop` is called.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_vibrate_with_options, UseVibrateOptions, UseVibrateReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseVibrateReturn { vibrate, stop, .. } = use_vibrate_with_options(
+///     UseVibrateOptions::default()
+///         .pattern(vec![300, 100, 300])
+///         .interval(2000),
+/// );
+///
+/// // start the loop
+/// vibrate();
+///
+/// // when the alarm is dismissed
+/// stop();
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Suppression
+///
+/// Vibrations are suppressed while the user prefers reduced motion and while the battery is
+/// low and not charging (as far as the browser reports it). Both can be turned off in the
+/// options.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `is_supported` is `false` and the functions do nothing.
+pub fn use_vibrate() -> UseVibrateReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_vibrate_with_options(UseVibrateOptions::default())
+}
+
+/// Version of [`use_vibrate`] that takes a `UseVibrateOptions`. See [`use_vibrate`] for how to use.
+pub fn use_vibrate_with_options(
+    options: UseVibrateOptions,
+) -> UseVibrateReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseVibrateOptions {
+        pattern,
+        interval,
+        vibrate_on_change,
+        respect_reduced_motion,
+        low_battery_level,
+    } = options;
+
+    let is_supported = use_supported(|| js!("vibrate" in &window().navigator()));
+
+    let prefers_reduced_motion = use_media_query("(prefers-reduced-motion: reduce)");
+
+    let (battery, set_battery) = create_signal(None::<web_sys::EventTarget>);
+    let (is_battery_low, set_battery_low) = create_signal(false);
+
+    #[cfg(not(feature = "ssr"))]
+    if let Some(low_battery_level) = low_battery_level {
+        let update_battery_low = move || {
+            if let Some(battery) = battery.get_untracked() {
+                let level = js!(battery["level"]).ok().and_then(|level| level.as_f64());
+                let charging = js!(battery["charging"])
+                    .ok()
+                    .and_then(|charging| charging.as_bool());
+
+                set_battery_low.set(
+                    charging == Some(false)
+                        && level.is_some_and(|level| level <= low_battery_level),
+                );
+            }
+        };
+
+        for event in ["levelchange", "chargingchange"] {
+            let _ = use_event_listener(battery, Custom::<ev::Event>::new(event), move |_| {
+                update_battery_low()
+            });
+        }
+
+        let navigator = window().navigator();
+        let promise = js!(navigator["getBattery"])
+            .ok()
+            .filter(|get_battery| get_battery.is_function())
+            .and_then(|get_battery| {
+                get_battery
+                    .unchecked_into::<js_sys::Function>()
+                    .call0(&navigator)
+                    .ok()
+            });
+
+        if let Some(promise) = promise {
+            spawn_local(async move {
+                if let Ok(manager) = js_fut!(promise.unchecked_into::<js_sys::Promise>()).await {
+                    set_battery.set(Some(manager.unchecked_into()));
+                    update_battery_low();
+                }
+            });
+        }
+    }
+
+    let is_suppressed = Signal::derive(move || {
+        (respect_reduced_motion && prefers_reduced_motion.get()) || is_battery_low.get()
+    });
+
+    let vibrate_once = {
+        let pattern = pattern.clone();
+
+        move || {
+            if !is_supported.get_untracked() || is_suppressed.get_untracked() {
+                return;
+            }
+
+            #[cfg(not(feature = "ssr"))]
+            pattern.with_untracked(|pattern| {
+                let pattern = pattern
+                    .iter()
+                    .map(|duration| wasm_bindgen::JsValue::from(*duration))
+                    .collect::<js_sys::Array>();
+
+                let _ = window().navigator().vibrate_with_pattern(&pattern);
+            });
+        }
+    };
+
+    let pausable = use_interval_fn_with_options(
+        vibrate_once.clone(),
+        interval.max(1),
+        UseIntervalFnOptions::default().immediate(false),
+    );
+    let is_looping = pausable.is_active;
+
+    let vibrate = {
+        let resume = pausable.resume.clone();
+
+        move || {
+            vibrate_once();
+
+            if interval > 0 && !is_suppressed.get_untracked() {
+                resume();
+            }
+        }
+    };
+
+    let stop = {
+        let pause = pausable.pause;
+
+        move || {
+            pause();
+
+            #[cfg(not(feature = "ssr"))]
+            if is_supported.get_untracked() {
+                let _ = window().navigator().vibrate_with_duration(0);
+            }
+        }
+    };
+
+    if vibrate_on_change {
+        let vibrate = vibrate.clone();
+        let _ = watch(move || pattern.get(), move |_, _, _| vibrate(), false);
+    }
+
+    {
+        let stop = stop.clone();
+        let _ = watch(
+            move || is_suppressed.get(),
+            move |is_suppressed, _, _| {
+                if *is_suppressed {
+                    stop();
+                }
+            },
+            false,
+        );
+    }
+
+    UseVibrateReturn {
+        is_supported,
+        is_suppressed,
+        is_looping,
+        vibrate,
+        stop,
+    }
+}
+
+/// Options for [`use_vibrate_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseVibrateOptions {
+    /// Vibration pattern. A list of durations in ms alternating between vibrating and pausing.
+    /// Accepts a [`VibratePreset`] as well. Defaults to `VibratePreset::Tap`.
+    #[builder(into)]
+    pattern: MaybeSignal<Vec<u32>>,
+
+    /// If greater than `0` the pattern is repeated every `interval` ms after `vibrate` has been
+    /// called until `stop` is called. Defaults to `0`.
+    interval: u64,
+
+    /// Vibrate every time the pattern changes. Defaults to `true`.
+    vibrate_on_change: bool,
+
+    /// Don't vibrate while the user prefers reduced motion. Defaults to `true`.
+    respect_reduced_motion: bool,
+
+    /// Don't vibrate while the battery level is at or below this value (from `0.0` to `1.0`)
+    /// and the device isn't charging. `None` ignores the battery. Defaults to `Some(0.15)`.
+    #[builder(into)]
+    low_battery_level: Option<f64>,
+}
+
+impl Default for UseVibrateOptions {
+    fn default() -> Self {
+        Self {
+            pattern: VibratePreset::Tap.into(),
+            interval: 0,
+            vibrate_on_change: true,
+            respect_reduced_motion: true,
+            low_battery_level: Some(0.15),
+        }
+    }
+}
+
+/// Return type of [`use_vibrate`].
+pub struct UseVibrateReturn<VibrateFn, StopFn>
+where
+    VibrateFn: Fn() + Clone,
+    StopFn: Fn() + Clone,
+{
+    /// Whether the Vibration API is supported by the browser
+    pub is_supported: Signal<bool>,
+
+    /// `true` while vibrations are suppressed because of reduced motion or a low battery
+    pub is_suppressed: Signal<bool>,
+
+    /// `true` while the pattern is repeated
+    pub is_looping: Signal<bool>,
+
+    /// Vibrates with the pattern. Starts the loop if `interval` is set.
+    pub vibrate: VibrateFn,
+
+    /// Stops the current vibration and the loop
+    pub stop: StopFn,
+}
+
+/// Common vibration patterns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VibratePreset {
+    /// A very short tap, e.g. for selection changes
+    Tap,
+    /// Two short taps
+    DoubleTap,
+    /// Feedback for a successful action
+    Success,
+    /// Feedback for an action that needs attention
+    Warning,
+    /// Feedback for a failed action
+    Error,
+    /// A heartbeat like pattern, e.g. for incoming calls when repeated
+    Heartbeat,
+    /// A single long vibration
+    Long,
+}
+
+impl VibratePreset {
+    /// The durations in ms of this preset.
+    pub fn pattern(&self) -> Vec<u32> {
+        match self {
+            Self::Tap => vec![10],
+            Self::DoubleTap => vec![10, 60, 10],
+            Self::Success => vec![20, 40, 40],
+            Self::Warning => vec![60, 80, 60],
+            Self::Error => vec![80, 50, 80, 50, 80],
+            Self::Heartbeat => vec![40, 120, 60],
+            Self::Long => vec![400],
+        }
+    }
+}
+
+impl From<VibratePreset> for MaybeSignal<Vec<u32>> {
+    fn from(preset: VibratePreset) -> Self {
+        MaybeSignal::Static(preset.pattern())
+    }
+}
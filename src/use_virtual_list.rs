@@ -0,0 +1,327 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::ElementMaybeSignal;
+use crate::{use_element_size, use_event_listener, UseElementSizeReturn};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::scroll;
+use leptos::*;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Render only the visible part of a long list.
+///
+/// `list` is rendered inside of the scroll `container`. Only the items that are visible in the
+/// container plus a few more before and after (see option `overscan`) are returned in `list`.
+/// The wrapper around the rendered items gets the size of the complete list so the scrollbar
+/// looks as if all items were rendered.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_virtual_list, UseVirtualListReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (items, set_items) = create_signal((0..100_000).collect::<Vec<i32>>());
+///
+/// let container = create_node_ref::<Div>();
+///
+/// let UseVirtualListReturn {
+///     list,
+///     container_style,
+///     wrapper_style,
+///     scroll_to,
+///     ..
+/// } = use_virtual_list(items, container, 30.0);
+///
+/// view! {
+///     <button on:click=move |_| scroll_to(5000)>"Go to 5000"</button>
+///
+///     <div node_ref=container style=move || format!("height: 300px; {}", container_style.get())>
+///         <div style=wrapper_style>
+///             <For each=move || list.get() key=|(index, _)| *index let:item>
+///                 <div style="height: 30px">"Row " {item.1}</div>
+///             </For>
+///         </div>
+///     </div>
+/// }
+/// # }
+/// ```
+///
+/// ### Item Sizes
+///
+/// The size of the items in the direction of scrolling has to be known in advance. If not all
+/// items have the same size, pass a function that returns the size of the item with the given
+/// index.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_virtual_list, VirtualListItemSize};
+/// # use std::rc::Rc;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let (items, set_items) = create_signal((0..100_000).collect::<Vec<i32>>());
+/// # let container = create_node_ref::<Div>();
+/// let virtual_list = use_virtual_list(
+///     items,
+///     container,
+///     VirtualListItemSize::Dynamic(Rc::new(|index| if index % 2 == 0 { 40.0 } else { 20.0 })),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ### Horizontal Lists
+///
+/// Set the option `orientation` to `VirtualListOrientation::Horizontal` for a list that
+/// scrolls horizontally. The sizes are widths then.
+///
+/// ## Server-Side Rendering
+///
+/// On the server the size of the container is unknown so only the first item plus the
+/// `overscan` items are rendered. `scroll_to` does nothing.
+pub fn use_virtual_list<T, El, ElT>(
+    list: impl Into<MaybeSignal<Vec<T>>>,
+    container: El,
+    item_size: impl Into<VirtualListItemSize>,
+) -> UseVirtualListReturn<T, impl Fn(usize) + Clone + 'static>
+where
+    T: Clone + 'static,
+    El: Into<ElementMaybeSignal<ElT, web_sys::Element>>,
+    ElT: Into<web_sys::Element> + Clone + 'static,
+{
+    use_virtual_list_with_options(list, container, item_size, UseVirtualListOptions::default())
+}
+
+/// Version of [`use_virtual_list`] that takes a `UseVirtualListOptions`. See [`use_virtual_list`] for how to use.
+pub fn use_virtual_list_with_options<T, El, ElT>(
+    list: impl Into<MaybeSignal<Vec<T>>>,
+    container: El,
+    item_size: impl Into<VirtualListItemSize>,
+    options: UseVirtualListOptions,
+) -> UseVirtualListReturn<T, impl Fn(usize) + Clone + 'static>
+where
+    T: Clone + 'static,
+    El: Into<ElementMaybeSignal<ElT, web_sys::Element>>,
+    ElT: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseVirtualListOptions {
+        orientation,
+        overscan,
+    } = options;
+
+    let list = list.into();
+    let item_size = store_value(item_size.into());
+
+    let container: ElementMaybeSignal<ElT, web_sys::Element> = container.into();
+    let container = Signal::derive(move || container.get().map(Into::into));
+
+    let UseElementSizeReturn { width, height } = use_element_size(container);
+    let container_size = Signal::derive(move || match orientation {
+        VirtualListOrientation::Vertical => height.get(),
+        VirtualListOrientation::Horizontal => width.get(),
+    });
+
+    let (scroll_offset, set_scroll_offset) = create_signal(0.0_f64);
+
+    let read_scroll_offset = move |container: &web_sys::Element| match orientation {
+        VirtualListOrientation::Vertical => container.scroll_top() as f64,
+        VirtualListOrientation::Horizontal => container.scroll_left() as f64,
+    };
+
+    let _ = use_event_listener(container, scroll, move |_| {
+        if let Some(container) = container.get_untracked() {
+            set_scroll_offset.set(read_scroll_offset(&container));
+        }
+    });
+
+    let len = create_memo(move |_| list.with(Vec::len));
+
+    // Offsets of the items and the total size at the end. Only needed for dynamic sizes.
+    let offsets = create_memo(move |_| {
+        item_size.with_value(|item_size| match item_size {
+            VirtualListItemSize::Fixed(_) => vec![],
+            VirtualListItemSize::Dynamic(size_of) => {
+                let mut offset = 0.0;
+                let mut offsets = Vec::with_capacity(len.get() + 1);
+
+                for index in 0..len.get() {
+                    offsets.push(offset);
+                    offset += size_of(index);
+                }
+                offsets.push(offset);
+
+                offsets
+            }
+        })
+    });
+
+    let offset_of = move |index: usize| {
+        item_size.with_value(|item_size| match item_size {
+            VirtualListItemSize::Fixed(size) => index as f64 * size,
+            VirtualListItemSize::Dynamic(_) => offsets.with(|offsets| {
+                offsets
+                    .get(index)
+                    .or(offsets.last())
+                    .copied()
+                    .unwrap_or_default()
+            }),
+        })
+    };
+
+    // index of the item at the given offset
+    let index_at = move |offset: f64| {
+        let last = len.get().saturating_sub(1);
+
+        let index = item_size.with_value(|item_size| match item_size {
+            VirtualListItemSize::Fixed(size) if *size > 0.0 => (offset / size).max(0.0) as usize,
+            VirtualListItemSize::Fixed(_) => 0,
+            VirtualListItemSize::Dynamic(_) => offsets.with(|offsets| {
+                offsets
+                    .partition_point(|item_offset| *item_offset <= offset)
+                    .saturating_sub(1)
+            }),
+        });
+
+        index.min(last)
+    };
+
+    let range = create_memo(move |_| {
+        let len = len.get();
+        if len == 0 {
+            return 0..0;
+        }
+
+        let offset = scroll_offset.get();
+        let start = index_at(offset);
+        let end = index_at(offset + container_size.get()) + 1;
+
+        start.saturating_sub(overscan)..(end + overscan).min(len)
+    });
+
+    let visible = Signal::derive(move || {
+        let range = range.get();
+
+        list.with(|list| {
+            let range = range.start.min(list.len())..range.end.min(list.len());
+
+            list[range.clone()]
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(index, item)| (range.start + index, item))
+                .collect()
+        })
+    });
+
+    let container_style = Signal::derive(move || {
+        match orientation {
+            VirtualListOrientation::Vertical => "overflow-y: auto;",
+            VirtualListOrientation::Horizontal => "overflow-x: auto;",
+        }
+        .to_string()
+    });
+
+    let wrapper_style = Signal::derive(move || {
+        let total = offset_of(len.get());
+        let offset = offset_of(range.get().start);
+
+        match orientation {
+            VirtualListOrientation::Vertical => format!(
+                "width: 100%; box-sizing: border-box; height: {total}px; padding-top: {offset}px;"
+            ),
+            VirtualListOrientation::Horizontal => format!(
+                "height: 100%; box-sizing: border-box; width: {total}px; padding-left: {offset}px; display: flex;"
+            ),
+        }
+    });
+
+    let scroll_to = move |index: usize| {
+        let Some(container) = container.get_untracked() else {
+            return;
+        };
+
+        let offset = offset_of(index);
+
+        match orientation {
+            VirtualListOrientation::Vertical => container.set_scroll_top(offset as i32),
+            VirtualListOrientation::Horizontal => container.set_scroll_left(offset as i32),
+        }
+
+        set_scroll_offset.set(read_scroll_offset(&container));
+    };
+
+    UseVirtualListReturn {
+        list: visible,
+        range: range.into(),
+        container_style,
+        wrapper_style,
+        scroll_to,
+    }
+}
+
+/// Options for [`use_virtual_list_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseVirtualListOptions {
+    /// The direction in which the list scrolls. Defaults to `VirtualListOrientation::Vertical`.
+    orientation: VirtualListOrientation,
+
+    /// Number of items that are rendered before and after the visible ones so scrolling
+    /// doesn't show empty space. Defaults to `5`.
+    overscan: usize,
+}
+
+impl Default for UseVirtualListOptions {
+    fn default() -> Self {
+        Self {
+            orientation: VirtualListOrientation::default(),
+            overscan: 5,
+        }
+    }
+}
+
+/// The direction in which the list of [`use_virtual_list`] scrolls.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum VirtualListOrientation {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+/// Size of the items of [`use_virtual_list`] in the direction of scrolling in pixels.
+#[derive(Clone)]
+pub enum VirtualListItemSize {
+    /// All items have the same size
+    Fixed(f64),
+    /// Function that returns the size of the item with the given index
+    Dynamic(Rc<dyn Fn(usize) -> f64>),
+}
+
+impl From<f64> for VirtualListItemSize {
+    fn from(size: f64) -> Self {
+        Self::Fixed(size)
+    }
+}
+
+/// Return type of [`use_virtual_list`].
+pub struct UseVirtualListReturn<T, ScrollToFn>
+where
+    T: 'static,
+    ScrollToFn: Fn(usize) + Clone,
+{
+    /// The items to render together with their index in the complete list
+    pub list: Signal<Vec<(usize, T)>>,
+    /// Indices of the items in `list`
+    pub range: Signal<Range<usize>>,
+    /// Style for the scroll container
+    pub container_style: Signal<String>,
+    /// Style for the element that wraps the rendered items inside of the container
+    pub wrapper_style: Signal<String>,
+    /// Scrolls the container to the item with the given index
+    pub scroll_to: ScrollToFn,
+}
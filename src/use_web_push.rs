@@ -0,0 +1,372 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::now;
+use crate::{
+    js, js_fut, use_event_listener, use_permission, use_supported, PermissionState,
+    ServiceWorkerRegistrationError,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::visibilitychange;
+use leptos::*;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{PushManager, PushSubscription, ServiceWorkerRegistration};
+
+/// Subscriptions that expire within this many milliseconds are renewed.
+const RESUBSCRIBE_BEFORE_EXPIRATION: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Reactive [Push API](https://developer.mozilla.org/en-US/docs/Web/API/Push_API).
+///
+/// Subscribes to push messages with the `PushManager` of the service worker `registration` that
+/// is returned by [`fn@crate::use_service_worker`]. Send the returned subscription to your
+/// backend so it can push messages to this browser. Your service worker receives them in its
+/// `push` event and can show them with `registration.showNotification()`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{
+/// #     use_service_worker, use_web_push_with_options, UseServiceWorkerReturn, UseWebPushOptions,
+/// #     UseWebPushReturn,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseServiceWorkerReturn { registration, .. } = use_service_worker();
+///
+/// let UseWebPushReturn {
+///     is_subscribed,
+///     subscription_json,
+///     subscribe,
+///     unsubscribe,
+///     ..
+/// } = use_web_push_with_options(
+///     registration,
+///     UseWebPushOptions::default().application_server_key("<your public VAPID key>"),
+/// );
+///
+/// let _ = watch(
+///     move || subscription_json.get(),
+///     |subscription_json, _, _| {
+///         if let Some(subscription_json) = subscription_json {
+///             // send the subscription to your backend
+///         }
+///     },
+///     true,
+/// );
+///
+/// view! {
+///     <button on:click=move |_| subscribe() disabled=is_subscribed>"Enable notifications"</button>
+///     <button on:click=move |_| unsubscribe() disabled=move || !is_subscribed.get()>
+///         "Disable notifications"
+///     </button>
+/// }
+/// # }
+/// ```
+///
+/// Subscribing asks the user for permission to show notifications. Browsers only allow this in
+/// reaction to a user interaction so call `subscribe` in an event handler. The state of this
+/// permission is returned as `permission`.
+///
+/// ### Resubscribing
+///
+/// Push subscriptions can expire or be revoked by the browser. The existing subscription is
+/// checked when `registration` is available, when `permission` changes and when the page becomes
+/// visible again. If the subscription is about to expire or has vanished while the permission is
+/// still granted, a new one is created. The new subscription has a different endpoint which is
+/// why you should watch `subscription_json` or `endpoint` and send every change to your backend.
+/// Set the option `resubscribe` to `false` to turn this off.
+///
+/// ## Server-Side Rendering
+///
+/// On the server `subscription` is always `None` and `subscribe` and `unsubscribe` do nothing.
+pub fn use_web_push(
+    registration: Signal<Result<ServiceWorkerRegistration, ServiceWorkerRegistrationError>>,
+) -> UseWebPushReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    use_web_push_with_options(registration, UseWebPushOptions::default())
+}
+
+/// Version of [`use_web_push`] that takes a `UseWebPushOptions`. See [`use_web_push`] for how to use.
+pub fn use_web_push_with_options(
+    registration: Signal<Result<ServiceWorkerRegistration, ServiceWorkerRegistrationError>>,
+    options: UseWebPushOptions,
+) -> UseWebPushReturn<impl Fn() + Clone, impl Fn() + Clone> {
+    let UseWebPushOptions {
+        application_server_key,
+        user_visible_only,
+        resubscribe,
+    } = options;
+
+    let is_supported = use_supported(|| {
+        js!("PushManager" in &window()) && js!("serviceWorker" in &window().navigator())
+    });
+
+    let permission = use_permission("notifications");
+
+    let (subscription, set_subscription) = create_signal(None::<PushSubscription>);
+    let (error, set_error) = create_signal(None::<UseWebPushError>);
+
+    let push_manager = move || -> Result<PushManager, UseWebPushError> {
+        if !is_supported.get_untracked() {
+            return Err(UseWebPushError::NotSupported);
+        }
+
+        registration.with_untracked(|registration| match registration {
+            Ok(registration) => registration.push_manager().map_err(UseWebPushError::Js),
+            Err(_) => Err(UseWebPushError::NoRegistration),
+        })
+    };
+
+    let on_result = move |result: Result<Option<PushSubscription>, UseWebPushError>| match result {
+        Ok(new_subscription) => {
+            set_subscription.set(new_subscription);
+            set_error.set(None);
+        }
+        Err(err) => set_error.set(Some(err)),
+    };
+
+    let subscribe = {
+        let application_server_key = application_server_key.clone();
+
+        move || {
+            #[cfg(not(feature = "ssr"))]
+            {
+                let push_manager = push_manager();
+                let application_server_key = application_server_key.clone();
+
+                spawn_local(async move {
+                    let result = match push_manager {
+                        Ok(push_manager) => subscribe_push(
+                            &push_manager,
+                            application_server_key.as_deref(),
+                            user_visible_only,
+                        )
+                        .await
+                        .map(Some),
+                        Err(err) => Err(err),
+                    };
+
+                    on_result(result);
+                });
+            }
+        }
+    };
+
+    let unsubscribe = move || {
+        #[cfg(not(feature = "ssr"))]
+        {
+            let Some(subscription) = subscription.get_untracked() else {
+                return;
+            };
+
+            spawn_local(async move {
+                on_result(unsubscribe_push(&subscription).await.map(|_| None));
+            });
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        // Loads the existing subscription and renews it if necessary
+        let refresh = move || {
+            let Ok(push_manager) = push_manager() else {
+                return;
+            };
+
+            let application_server_key = application_server_key.clone();
+            let had_subscription = subscription.with_untracked(Option::is_some);
+
+            spawn_local(async move {
+                let current = match get_push_subscription(&push_manager).await {
+                    Ok(current) => current,
+                    Err(err) => return on_result(Err(err)),
+                };
+
+                let needs_new = match &current {
+                    Some(current) => expires_soon(current),
+                    None => {
+                        had_subscription && permission.get_untracked() == PermissionState::Granted
+                    }
+                };
+
+                if !resubscribe || !needs_new {
+                    return on_result(Ok(current));
+                }
+
+                if let Some(current) = &current {
+                    let _ = unsubscribe_push(current).await;
+                }
+
+                on_result(
+                    subscribe_push(
+                        &push_manager,
+                        application_server_key.as_deref(),
+                        user_visible_only,
+                    )
+                    .await
+                    .map(Some),
+                );
+            });
+        };
+
+        let _ = watch(
+            move || {
+                (
+                    registration.with(|registration| registration.is_ok()),
+                    permission.get(),
+                )
+            },
+            {
+                let refresh = refresh.clone();
+                move |_, _, _| refresh()
+            },
+            true,
+        );
+
+        let _ = use_event_listener(document(), visibilitychange, move |_| {
+            if document().visibility_state() == web_sys::VisibilityState::Visible {
+                refresh();
+            }
+        });
+    }
+
+    UseWebPushReturn {
+        is_supported,
+        permission,
+        subscription: subscription.into(),
+        endpoint: Signal::derive(move || {
+            subscription.with(|subscription| subscription.as_ref().map(PushSubscription::endpoint))
+        }),
+        subscription_json: Signal::derive(move || {
+            subscription.with(|subscription| {
+                subscription
+                    .as_ref()
+                    .and_then(|subscription| js_sys::JSON::stringify(subscription).ok())
+                    .map(String::from)
+            })
+        }),
+        is_subscribed: Signal::derive(move || subscription.with(Option::is_some)),
+        error: error.into(),
+        subscribe,
+        unsubscribe,
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn get_push_subscription(
+    push_manager: &PushManager,
+) -> Result<Option<PushSubscription>, UseWebPushError> {
+    let promise = push_manager
+        .get_subscription()
+        .map_err(UseWebPushError::Js)?;
+
+    let subscription = js_fut!(promise).await.map_err(UseWebPushError::Js)?;
+
+    Ok((!subscription.is_null()).then(|| subscription.unchecked_into()))
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn subscribe_push(
+    push_manager: &PushManager,
+    application_server_key: Option<&str>,
+    user_visible_only: bool,
+) -> Result<PushSubscription, UseWebPushError> {
+    let options = js_sys::Object::new();
+    js!(options["userVisibleOnly"] = user_visible_only);
+    if let Some(application_server_key) = application_server_key {
+        js!(options["applicationServerKey"] = application_server_key);
+    }
+
+    let promise = push_manager
+        .subscribe_with_options(options.unchecked_ref())
+        .map_err(UseWebPushError::Js)?;
+
+    js_fut!(promise)
+        .await
+        .map(JsCast::unchecked_into)
+        .map_err(UseWebPushError::Js)
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn unsubscribe_push(subscription: &PushSubscription) -> Result<(), UseWebPushError> {
+    let promise = subscription.unsubscribe().map_err(UseWebPushError::Js)?;
+
+    js_fut!(promise)
+        .await
+        .map(|_| ())
+        .map_err(UseWebPushError::Js)
+}
+
+/// Whether the subscription expires within [`RESUBSCRIBE_BEFORE_EXPIRATION`].
+fn expires_soon(subscription: &PushSubscription) -> bool {
+    js!(subscription["expirationTime"])
+        .ok()
+        .and_then(|expiration_time| expiration_time.as_f64())
+        .map(|expiration_time| expiration_time - now() < RESUBSCRIBE_BEFORE_EXPIRATION)
+        .unwrap_or(false)
+}
+
+/// Options for [`use_web_push_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebPushOptions {
+    /// The public VAPID key of your push server encoded as base64url. Most browsers require it.
+    /// Defaults to `None`.
+    #[builder(into)]
+    application_server_key: Option<String>,
+
+    /// Promise that every push message results in a visible notification. Chrome only allows
+    /// subscriptions where this is `true`. Defaults to `true`.
+    user_visible_only: bool,
+
+    /// Renew subscriptions that are about to expire or have been removed by the browser.
+    /// Defaults to `true`.
+    resubscribe: bool,
+}
+
+impl Default for UseWebPushOptions {
+    fn default() -> Self {
+        Self {
+            application_server_key: None,
+            user_visible_only: true,
+            resubscribe: true,
+        }
+    }
+}
+
+/// Return type of [`use_web_push`].
+pub struct UseWebPushReturn<SubscribeFn, UnsubscribeFn>
+where
+    SubscribeFn: Fn() + Clone,
+    UnsubscribeFn: Fn() + Clone,
+{
+    /// Whether the Push API is supported
+    pub is_supported: Signal<bool>,
+    /// Permission to show notifications which is required for push messages
+    pub permission: Signal<PermissionState>,
+    /// The current push subscription
+    pub subscription: Signal<Option<PushSubscription>>,
+    /// The URL your backend sends the push messages to
+    pub endpoint: Signal<Option<String>>,
+    /// The subscription as JSON including the endpoint and the keys. This is what your backend
+    /// needs to send push messages.
+    pub subscription_json: Signal<Option<String>>,
+    /// Whether there is a push subscription
+    pub is_subscribed: Signal<bool>,
+    /// The error of the last call to the `PushManager`
+    pub error: Signal<Option<UseWebPushError>>,
+    /// Subscribes to push messages. Asks for permission if necessary.
+    pub subscribe: SubscribeFn,
+    /// Removes the push subscription
+    pub unsubscribe: UnsubscribeFn,
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum UseWebPushError {
+    #[error("the Push API is not supported")]
+    NotSupported,
+    #[error("no service worker registration available")]
+    NoRegistration,
+    #[error("push subscription failed: {0:?}")]
+    Js(JsValue),
+}
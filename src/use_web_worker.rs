@@ -0,0 +1,285 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use codee::{
+    CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::*;
+use std::marker::PhantomData;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use web_sys::{ErrorEvent, MessageEvent, Worker, WorkerOptions, WorkerType};
+
+/// Reactive [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Worker).
+///
+/// Spawns a worker from the script at `url` and exchanges messages with it. Outgoing messages
+/// of type `Tx` are encoded and incoming messages are decoded into `Rx` via the given codec.
+/// You can use any of the codecs, string or binary.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::JsonSerdeCodec;
+/// # use leptos_use::{use_web_worker, UseWebWorkerReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebWorkerReturn {
+///     data,
+///     post_message,
+///     terminate,
+///     ..
+/// } = use_web_worker::<Vec<u32>, u64, JsonSerdeCodec>("./sum_worker.js");
+///
+/// view! {
+///     <button on:click=move |_| post_message(&vec![1, 2, 3])>"Sum"</button>
+///     <p>"Result: " {move || data.get()}</p>
+///     <button on:click=move |_| terminate()>"Stop"</button>
+/// }
+/// # }
+/// ```
+///
+/// Codec errors, failed `postMessage` calls and errors thrown inside the worker are available in
+/// `error`.
+///
+/// To start the worker as an ES module, pass `WorkerType::Module` to the option `worker_type`.
+///
+/// If you want to run a Rust function in a worker and await its result, have a look at
+/// [`fn@crate::use_worker_fn`].
+///
+/// ## Server-Side Rendering
+///
+/// On the server no worker is created. `status` is always `WebWorkerStatus::Pending` and
+/// `post_message` and `terminate` do nothing.
+pub fn use_web_worker<Tx, Rx, C>(
+    url: &str,
+) -> UseWebWorkerReturn<
+    Tx,
+    Rx,
+    HybridCoderError<<C as Encoder<Tx>>::Error>,
+    HybridCoderError<<C as Decoder<Rx>>::Error>,
+    impl Fn(&Tx) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    Tx: 'static,
+    Rx: 'static,
+    C: Encoder<Tx> + Decoder<Rx>,
+    C: IsBinary<Rx, <C as Decoder<Rx>>::Encoded>,
+    C: HybridDecoder<Rx, <C as Decoder<Rx>>::Encoded, Error = <C as Decoder<Rx>>::Error>,
+    C: HybridEncoder<Tx, <C as Encoder<Tx>>::Encoded, Error = <C as Encoder<Tx>>::Error>,
+{
+    use_web_worker_with_options::<Tx, Rx, C>(url, UseWebWorkerOptions::default())
+}
+
+/// Version of [`use_web_worker`] that takes a `UseWebWorkerOptions`. See [`use_web_worker`] for how to use.
+pub fn use_web_worker_with_options<Tx, Rx, C>(
+    url: &str,
+    options: UseWebWorkerOptions,
+) -> UseWebWorkerReturn<
+    Tx,
+    Rx,
+    HybridCoderError<<C as Encoder<Tx>>::Error>,
+    HybridCoderError<<C as Decoder<Rx>>::Error>,
+    impl Fn(&Tx) + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    Tx: 'static,
+    Rx: 'static,
+    C: Encoder<Tx> + Decoder<Rx>,
+    C: IsBinary<Rx, <C as Decoder<Rx>>::Encoded>,
+    C: HybridDecoder<Rx, <C as Decoder<Rx>>::Encoded, Error = <C as Decoder<Rx>>::Error>,
+    C: HybridEncoder<Tx, <C as Encoder<Tx>>::Encoded, Error = <C as Encoder<Tx>>::Error>,
+{
+    let UseWebWorkerOptions { worker_type } = options;
+
+    let (data, set_data) = create_signal(None::<Rx>);
+    let (error, set_error) = create_signal(None);
+    let (status, set_status) = create_signal(WebWorkerStatus::Pending);
+    let worker_ref: StoredValue<Option<Worker>> = store_value(None);
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let mut worker_options = WorkerOptions::new();
+        worker_options.type_(worker_type);
+
+        match Worker::new_with_options(url, &worker_options) {
+            Ok(worker) => {
+                // onmessage handler
+                {
+                    let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
+                        let data = e.data();
+
+                        let decoded = if let Some(bytes) = data.dyn_ref::<js_sys::Uint8Array>() {
+                            C::decode_bin(bytes.to_vec().as_slice())
+                        } else if let Ok(array_buffer) =
+                            data.clone().dyn_into::<js_sys::ArrayBuffer>()
+                        {
+                            C::decode_bin(
+                                js_sys::Uint8Array::new(&array_buffer).to_vec().as_slice(),
+                            )
+                        } else if let Some(txt) = data.as_string() {
+                            C::decode_str(&txt)
+                        } else {
+                            return;
+                        };
+
+                        match decoded {
+                            Ok(val) => set_data.set(Some(val)),
+                            Err(err) => set_error.set(Some(CodecError::Decode(err).into())),
+                        }
+                    })
+                        as Box<dyn FnMut(MessageEvent)>);
+                    worker.set_onmessage(Some(onmessage_closure.as_ref().unchecked_ref()));
+                    onmessage_closure.forget();
+                }
+
+                // onerror handler
+                {
+                    let onerror_closure = Closure::wrap(Box::new(move |e: ErrorEvent| {
+                        set_error.set(Some(UseWebWorkerError::Event(e)));
+                    })
+                        as Box<dyn FnMut(ErrorEvent)>);
+                    worker.set_onerror(Some(onerror_closure.as_ref().unchecked_ref()));
+                    onerror_closure.forget();
+                }
+
+                worker_ref.set_value(Some(worker));
+                set_status.set(WebWorkerStatus::Running);
+            }
+            Err(err) => {
+                set_error.set(Some(UseWebWorkerError::Create(err)));
+                set_status.set(WebWorkerStatus::Terminated);
+            }
+        }
+
+        on_cleanup(move || {
+            worker_ref.try_with_value(|worker| {
+                if let Some(worker) = worker {
+                    worker.terminate();
+                }
+            });
+        });
+    }
+
+    let terminate = move || {
+        if let Some(worker) = worker_ref.get_value() {
+            worker.terminate();
+            worker_ref.set_value(None);
+            set_status.set(WebWorkerStatus::Terminated);
+        }
+    };
+
+    let post_message = move |value: &Tx| {
+        let Some(worker) = worker_ref.get_value() else {
+            return;
+        };
+
+        let encoded = if C::is_binary() {
+            C::encode_bin(value).map(|val| JsValue::from(js_sys::Uint8Array::from(val.as_slice())))
+        } else {
+            C::encode_str(value).map(|val| JsValue::from_str(&val))
+        };
+
+        match encoded {
+            Ok(message) => {
+                if let Err(err) = worker.post_message(&message) {
+                    set_error.set(Some(UseWebWorkerError::Post(err)));
+                }
+            }
+            Err(err) => {
+                set_error.set(Some(CodecError::Encode(err).into()));
+            }
+        }
+    };
+
+    UseWebWorkerReturn {
+        data: data.into(),
+        error: error.into(),
+        status: status.into(),
+        post_message,
+        terminate,
+        worker: worker_ref,
+        _marker: PhantomData,
+    }
+}
+
+/// Options for [`use_web_worker_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebWorkerOptions {
+    /// How the worker script is loaded. Defaults to `WorkerType::Classic`.
+    worker_type: WorkerType,
+}
+
+impl Default for UseWebWorkerOptions {
+    fn default() -> Self {
+        Self {
+            worker_type: WorkerType::Classic,
+        }
+    }
+}
+
+/// The state of the worker of [`use_web_worker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebWorkerStatus {
+    /// The worker hasn't been created. This is always the case on the server.
+    Pending,
+    /// The worker has been created and receives messages
+    Running,
+    /// The worker has been terminated or couldn't be created
+    Terminated,
+}
+
+/// Return type of [`use_web_worker`].
+pub struct UseWebWorkerReturn<Tx, Rx, E, D, PostFn, TerminateFn>
+where
+    Tx: 'static,
+    Rx: 'static,
+    E: 'static,
+    D: 'static,
+    PostFn: Fn(&Tx) + Clone + 'static,
+    TerminateFn: Fn() + Clone + 'static,
+{
+    /// Latest message received from the worker
+    pub data: Signal<Option<Rx>>,
+    /// Latest error
+    pub error: Signal<Option<UseWebWorkerError<E, D>>>,
+    /// The state of the worker
+    pub status: Signal<WebWorkerStatus>,
+    /// Encodes and sends a message to the worker
+    pub post_message: PostFn,
+    /// Terminates the worker immediately. Afterwards no more messages are sent or received.
+    pub terminate: TerminateFn,
+
+    worker: StoredValue<Option<Worker>>,
+    _marker: PhantomData<Tx>,
+}
+
+impl<Tx, Rx, E, D, PostFn, TerminateFn> UseWebWorkerReturn<Tx, Rx, E, D, PostFn, TerminateFn>
+where
+    Tx: 'static,
+    Rx: 'static,
+    E: 'static,
+    D: 'static,
+    PostFn: Fn(&Tx) + Clone + 'static,
+    TerminateFn: Fn() + Clone + 'static,
+{
+    /// The underlying `Worker` while it's running
+    pub fn worker(&self) -> Option<Worker> {
+        self.worker.get_value()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum UseWebWorkerError<E, D> {
+    #[error("failed to create worker: {0:?}")]
+    Create(JsValue),
+    #[error("error in worker: {}", .0.message())]
+    Event(ErrorEvent),
+    #[error("failed to post message to worker: {0:?}")]
+    Post(JsValue),
+    #[error("worker codec error: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
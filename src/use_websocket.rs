@@ -1,13 +1,29 @@
 #![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
 
 use cfg_if::cfg_if;
-use leptos::{leptos_dom::helpers::TimeoutHandle, *};
-use std::cell::Cell;
+use futures_util::sink::Sink;
+use futures_util::stream::{Stream, StreamExt};
+use leptos::{
+    leptos_dom::helpers::{IntervalHandle, TimeoutHandle},
+    *,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::future;
+use std::marker::PhantomData;
+use std::pin::Pin;
 use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 use thiserror::Error;
 
-use crate::core::ConnectionReadyState;
+use crate::core::{
+    resolve_reconnect_policy, ConnectionReadyState, ReconnectCountdown, ReconnectLimit,
+    ReconnectPolicy,
+};
+use crate::js_fut;
+use crate::{signal_to_stream, ManagedAbortController};
 use codee::{
     CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
 };
@@ -110,6 +126,110 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// }
 /// ```
 ///
+/// ## Multiple Consumers
+///
+/// `message` only holds the latest message. If several independent parts of your app consume
+/// the messages, each of them can `subscribe` to get its own signal that receives every message.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_websocket, UseWebSocketReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn { subscribe, .. } =
+///     use_websocket::<String, FromToStringCodec>("wss://echo.websocket.events/");
+///
+/// let chat = subscribe();
+/// let (log, set_log) = create_signal(Vec::<String>::new());
+///
+/// create_effect(move |_| {
+///     if let Some(message) = chat.get() {
+///         set_log.update(|log| log.push(message));
+///     }
+/// });
+///
+/// let notifications = subscribe();
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Streams
+///
+/// To use the messages in async code turn the return value into a `Stream` of the received
+/// messages and a `Sink` to send messages with `into_split`. There are also `into_stream` and
+/// `into_sink` if you only need one of them.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use futures_util::{SinkExt, StreamExt};
+/// # use leptos_use::use_websocket;
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (mut messages, mut sink) =
+///     use_websocket::<String, FromToStringCodec>("wss://echo.websocket.events/").into_split();
+///
+/// spawn_local(async move {
+///     while let Some(message) = messages.next().await {
+///         let _ = sink.send(message.to_uppercase()).await;
+///     }
+/// });
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Reconnecting
+///
+/// When the connection is lost it is re-established according to the `reconnect_policy`
+/// (see [`ReconnectPolicy`]). `reconnect_attempts` counts the attempts and while waiting for the
+/// next one `reconnect_countdown` contains the remaining seconds.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_websocket_with_options, UseWebSocketOptions, UseWebSocketReturn};
+/// # use leptos_use::core::{BackoffReconnectPolicy, ReconnectBackoff, ReconnectLimit};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn {
+///     reconnect_countdown,
+///     ..
+/// } = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://echo.websocket.events/",
+///     UseWebSocketOptions::default().reconnect_policy(
+///         BackoffReconnectPolicy::default()
+///             .limit(ReconnectLimit::Infinite)
+///             .interval(1000)
+///             .backoff(ReconnectBackoff::Exponential { factor: 2.0 })
+///             .max_interval(30_000)
+///             .jitter(0.2),
+///     ),
+/// );
+///
+/// view! {
+///     <Show when=move || reconnect_countdown.get().is_some()>
+///         <p>"Reconnecting in " {reconnect_countdown} "s..."</p>
+///     </Show>
+/// }
+/// # }
+/// ```
+///
+/// Proxies often drop idle connections without closing them, so the connection is never
+/// noticed to be lost. Set a `heartbeat` (see [`WebSocketHeartbeat`]) to send a ping
+/// periodically and reconnect when the server doesn't answer in time. `last_heartbeat` contains
+/// the time of the last answer.
+///
+/// Messages that are sent while the connection isn't open are dropped. Set a `send_queue` (see
+/// [`WebSocketSendQueue`]) to buffer them and send them once the connection is open again.
+/// `buffered_amount` contains the number of bytes that are waiting in the queue or in the buffer
+/// of the `WebSocket`. It's updated whenever a message is sent or queued.
+///
 /// ## Relative Paths
 ///
 /// If the provided `url` is relative, it will be resolved relative to the current page.
@@ -124,6 +244,43 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// | https://example.com/some/where | //otherdomain.com/api/ws | wss://otherdomain.com/api/ws        |
 ///
 ///
+/// ## Authentication
+///
+/// Browsers can't send custom headers like `Authorization` with the `WebSocket` handshake.
+/// With [`WebSocketTicketAuth`] a short-lived ticket is fetched from an authenticated HTTP endpoint
+/// before every (re)connect and appended to the URL or to the subprotocols.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{
+/// #     use_websocket_with_options, TicketPlacement, UseWebSocketOptions, UseWebSocketReturn,
+/// #     WebSocketTicketAuth,
+/// # };
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let (token, set_token) = create_signal("access-token".to_string());
+///
+/// let UseWebSocketReturn { message, .. } = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://some.websocket.server/",
+///     UseWebSocketOptions::default().ticket_auth(
+///         WebSocketTicketAuth::new("/api/ws-ticket")
+///             .headers(Signal::derive(move || {
+///                 vec![("Authorization".to_string(), format!("Bearer {}", token.get()))]
+///             }))
+///             .placement(TicketPlacement::Query("ticket".to_string()))
+///             .on_unauthorized(move || {
+///                 // refresh the access token here
+///                 set_token.set("new-access-token".to_string());
+///             }),
+///     ),
+/// );
+/// #
+/// # view! {}
+/// # }
+/// ```
+///
 /// ## Usage with `provide_context`
 ///
 /// The return value of `use_websocket` utilizes several type parameters which can make it
@@ -225,6 +382,10 @@ use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
 /// # }
 /// ```
 ///
+/// Instead of providing the connection yourself you can also use
+/// [`fn@crate::use_shared_websocket`] which shares one connection per URL between all components
+/// that call it.
+///
 /// ## Server-Side Rendering
 ///
 /// On the server the returned functions amount to no-ops.
@@ -235,6 +396,7 @@ pub fn use_websocket<T, C>(
     impl Fn() + Clone + 'static,
     impl Fn() + Clone + 'static,
     impl Fn(&T) + Clone + 'static,
+    impl Fn() -> Signal<Option<T>> + Clone + 'static,
 >
 where
     T: 'static,
@@ -259,6 +421,7 @@ pub fn use_websocket_with_options<T, C>(
     impl Fn() + Clone + 'static,
     impl Fn() + Clone + 'static,
     impl Fn(&T) + Clone + 'static,
+    impl Fn() -> Signal<Option<T>> + Clone + 'static,
 >
 where
     T: 'static,
@@ -278,43 +441,127 @@ where
         on_close,
         reconnect_limit,
         reconnect_interval,
+        reconnect_policy,
         immediate,
         protocols,
+        ticket_auth,
+        heartbeat,
+        send_queue,
+        abort_controller,
     } = options;
 
+    let reconnect_policy =
+        resolve_reconnect_policy(reconnect_policy, reconnect_limit, reconnect_interval);
+
     let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
     let (message, set_message) = create_signal(None);
     let ws_ref: StoredValue<Option<WebSocket>> = store_value(None);
 
     let reconnect_timer_ref: StoredValue<Option<TimeoutHandle>> = store_value(None);
 
-    let reconnect_times_ref: StoredValue<u64> = store_value(0);
+    let clear_reconnect_timer = move || {
+        if let Some(timer) = reconnect_timer_ref.try_update_value(Option::take).flatten() {
+            timer.clear();
+        }
+    };
+
+    let (reconnect_attempts, set_reconnect_attempts) = create_signal(0_u64);
+    let connection_lost_at_ref: StoredValue<Option<f64>> = store_value(None);
+    let reconnect_countdown = ReconnectCountdown::new();
     let manually_closed_ref: StoredValue<bool> = store_value(false);
 
     let unmounted = Rc::new(Cell::new(false));
 
     let connect_ref: StoredValue<Option<Rc<dyn Fn()>>> = store_value(None);
 
+    let (last_heartbeat, set_last_heartbeat) = create_signal(None::<f64>);
+    let heartbeat_interval_ref: StoredValue<Option<IntervalHandle>> = store_value(None);
+    let heartbeat_timeout_ref: StoredValue<Option<TimeoutHandle>> = store_value(None);
+
+    let stop_heartbeat = move || {
+        if let Some(interval) = heartbeat_interval_ref
+            .try_update_value(Option::take)
+            .flatten()
+        {
+            interval.clear();
+        }
+        if let Some(timeout) = heartbeat_timeout_ref
+            .try_update_value(Option::take)
+            .flatten()
+        {
+            timeout.clear();
+        }
+    };
+
+    let send_queue_ref: StoredValue<VecDeque<QueuedMessage>> = store_value(VecDeque::new());
+    let (buffered_amount, set_buffered_amount) = create_signal(0_usize);
+
+    let update_buffered_amount = move || {
+        let queued =
+            send_queue_ref.with_value(|queue| queue.iter().map(QueuedMessage::len).sum::<usize>());
+        let buffered = ws_ref
+            .get_value()
+            .map_or(0, |web_socket| web_socket.buffered_amount() as usize);
+
+        set_buffered_amount.set(queued + buffered);
+    };
+
+    let subscribers: StoredValue<Vec<(u64, Rc<dyn Fn(RawMessage)>)>> = store_value(vec![]);
+    let next_subscriber_id: StoredValue<u64> = store_value(0);
+
+    let notify_subscribers = move |raw: RawMessage<'_>| {
+        let subscribers = subscribers.with_value(|subscribers| {
+            subscribers
+                .iter()
+                .map(|(_, subscriber)| Rc::clone(subscriber))
+                .collect::<Vec<_>>()
+        });
+
+        for subscriber in subscribers {
+            subscriber(raw);
+        }
+    };
+
     #[cfg(not(feature = "ssr"))]
     {
         let reconnect_ref: StoredValue<Option<Rc<dyn Fn()>>> = store_value(None);
         reconnect_ref.set_value({
             Some(Rc::new(move || {
+                // `error` is usually followed by `close`. Only schedule one attempt.
+                if reconnect_timer_ref.with_value(|timer| timer.is_some()) {
+                    return;
+                }
+
+                let now = js_sys::Date::now();
+                let connection_lost_at = connection_lost_at_ref.get_value().unwrap_or(now);
+                connection_lost_at_ref.set_value(Some(connection_lost_at));
+
+                let attempts = reconnect_attempts.get_untracked();
+
                 if !manually_closed_ref.get_value()
-                    && !reconnect_limit.is_exceeded_by(reconnect_times_ref.get_value())
+                    && reconnect_policy
+                        .should_reconnect(attempts, (now - connection_lost_at) as u64)
                     && ws_ref
                         .get_value()
-                        .map_or(false, |ws: WebSocket| ws.ready_state() != WebSocket::OPEN)
+                        .map_or(true, |ws: WebSocket| ws.ready_state() != WebSocket::OPEN)
                 {
+                    let delay = reconnect_policy.delay(attempts);
+                    reconnect_countdown.start(delay);
+
                     reconnect_timer_ref.set_value(
                         set_timeout_with_handle(
                             move || {
+                                reconnect_countdown.stop();
+
+                                if manually_closed_ref.get_value() {
+                                    return;
+                                }
                                 if let Some(connect) = connect_ref.get_value() {
                                     connect();
-                                    reconnect_times_ref.update_value(|current| *current += 1);
+                                    set_reconnect_attempts.update(|current| *current += 1);
                                 }
                             },
-                            Duration::from_millis(reconnect_interval),
+                            delay,
                         )
                         .ok(),
                     );
@@ -322,17 +569,84 @@ where
             }))
         });
 
-        connect_ref.set_value({
-            let unmounted = Rc::clone(&unmounted);
-            let on_error = Rc::clone(&on_error);
+        let pong = heartbeat.as_ref().map(|heartbeat| heartbeat.pong.clone());
 
-            Some(Rc::new(move || {
-                reconnect_timer_ref.set_value(None);
+        let start_heartbeat = move |web_socket: WebSocket| {
+            stop_heartbeat();
 
-                if let Some(web_socket) = ws_ref.get_value() {
+            let Some(WebSocketHeartbeat {
+                message,
+                interval,
+                timeout,
+                ..
+            }) = heartbeat.clone()
+            else {
+                return;
+            };
+
+            // no pong in time means the connection died without being closed
+            let on_timeout = {
+                let web_socket = web_socket.clone();
+
+                move || {
+                    stop_heartbeat();
+
+                    web_socket.set_onopen(None);
+                    web_socket.set_onmessage(None);
+                    web_socket.set_onerror(None);
+                    web_socket.set_onclose(None);
                     let _ = web_socket.close();
+                    set_ready_state.set(ConnectionReadyState::Closed);
+
+                    if let Some(reconnect) = reconnect_ref.get_value() {
+                        reconnect();
+                    }
                 }
+            };
+
+            heartbeat_interval_ref.set_value(
+                set_interval_with_handle(
+                    move || {
+                        if web_socket.ready_state() != WebSocket::OPEN {
+                            return;
+                        }
+
+                        let _ = web_socket.send_with_str(&message);
+
+                        if heartbeat_timeout_ref.with_value(Option::is_none) {
+                            heartbeat_timeout_ref.set_value(
+                                set_timeout_with_handle(
+                                    on_timeout.clone(),
+                                    Duration::from_millis(timeout),
+                                )
+                                .ok(),
+                            );
+                        }
+                    },
+                    Duration::from_millis(interval),
+                )
+                .ok(),
+            );
+        };
+
+        let received_heartbeat = move || {
+            set_last_heartbeat.set(Some(js_sys::Date::now()));
+
+            if let Some(timeout) = heartbeat_timeout_ref
+                .try_update_value(Option::take)
+                .flatten()
+            {
+                timeout.clear();
+            }
+        };
+
+        let connect_id_ref: StoredValue<u64> = store_value(0);
+
+        let setup_web_socket: Rc<dyn Fn(String, Option<Vec<String>>)> = {
+            let unmounted = Rc::clone(&unmounted);
+            let on_error = Rc::clone(&on_error);
 
+            Rc::new(move |url: String, protocols: Option<Vec<String>>| {
                 let web_socket = {
                     protocols.as_ref().map_or_else(
                         || WebSocket::new(&url).unwrap_throw(),
@@ -353,6 +667,8 @@ where
                 {
                     let unmounted = Rc::clone(&unmounted);
                     let on_open = Rc::clone(&on_open);
+                    let start_heartbeat = start_heartbeat.clone();
+                    let web_socket = web_socket.clone();
 
                     let onopen_closure = Closure::wrap(Box::new(move |e: Event| {
                         if unmounted.get() {
@@ -368,6 +684,23 @@ where
                         SpecialNonReactiveZone::exit(prev);
 
                         set_ready_state.set(ConnectionReadyState::Open);
+                        set_reconnect_attempts.set(0);
+                        connection_lost_at_ref.set_value(None);
+
+                        let queued = send_queue_ref
+                            .try_update_value(std::mem::take)
+                            .unwrap_or_default();
+                        for message in queued {
+                            let _ = match message {
+                                QueuedMessage::Text(text) => web_socket.send_with_str(&text),
+                                QueuedMessage::Bytes(bytes) => {
+                                    web_socket.send_with_u8_array(&bytes)
+                                }
+                            };
+                        }
+                        update_buffered_amount();
+
+                        start_heartbeat(web_socket.clone());
                     })
                         as Box<dyn FnMut(Event)>);
                     web_socket.set_onopen(Some(onopen_closure.as_ref().unchecked_ref()));
@@ -382,12 +715,25 @@ where
                     let on_message_raw = Rc::clone(&on_message_raw);
                     let on_message_raw_bytes = Rc::clone(&on_message_raw_bytes);
                     let on_error = Rc::clone(&on_error);
+                    let pong = pong.clone();
 
                     let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
                         if unmounted.get() {
                             return;
                         }
 
+                        match &pong {
+                            // any message shows that the connection is alive
+                            Some(None) => received_heartbeat(),
+                            Some(Some(pong)) => {
+                                if e.data().as_string().as_ref() == Some(pong) {
+                                    received_heartbeat();
+                                    return;
+                                }
+                            }
+                            None => {}
+                        }
+
                         e.data().dyn_into::<js_sys::ArrayBuffer>().map_or_else(
                             |_| {
                                 e.data().dyn_into::<js_sys::JsString>().map_or_else(
@@ -408,6 +754,8 @@ where
                                         #[cfg(debug_assertions)]
                                         SpecialNonReactiveZone::exit(prev);
 
+                                        notify_subscribers(RawMessage::Text(&txt));
+
                                         match C::decode_str(&txt) {
                                             Ok(val) => {
                                                 #[cfg(debug_assertions)]
@@ -439,6 +787,8 @@ where
                                 #[cfg(debug_assertions)]
                                 SpecialNonReactiveZone::exit(prev);
 
+                                notify_subscribers(RawMessage::Bytes(&array));
+
                                 match C::decode_bin(array.as_slice()) {
                                     Ok(val) => {
                                         #[cfg(debug_assertions)]
@@ -473,6 +823,8 @@ where
                             return;
                         }
 
+                        stop_heartbeat();
+
                         if let Some(reconnect) = &reconnect_ref.get_value() {
                             reconnect();
                         }
@@ -502,6 +854,8 @@ where
                             return;
                         }
 
+                        stop_heartbeat();
+
                         if let Some(reconnect) = &reconnect_ref.get_value() {
                             reconnect();
                         }
@@ -522,17 +876,120 @@ where
                 }
 
                 ws_ref.set_value(Some(web_socket));
+            })
+        };
+
+        connect_ref.set_value({
+            let unmounted = Rc::clone(&unmounted);
+            let on_error = Rc::clone(&on_error);
+
+            Some(Rc::new(move || {
+                clear_reconnect_timer();
+                stop_heartbeat();
+
+                if let Some(web_socket) = ws_ref.get_value() {
+                    let _ = web_socket.close();
+                }
+
+                connect_id_ref.update_value(|id| *id += 1);
+
+                let Some(ticket_auth) = ticket_auth.clone() else {
+                    setup_web_socket(url.clone(), protocols.clone());
+                    return;
+                };
+
+                let connect_id = connect_id_ref.get_value();
+                set_ready_state.set(ConnectionReadyState::Connecting);
+
+                let url = url.clone();
+                let protocols = protocols.clone();
+                let unmounted = Rc::clone(&unmounted);
+                let on_error = Rc::clone(&on_error);
+                let setup_web_socket = Rc::clone(&setup_web_socket);
+
+                spawn_local(async move {
+                    let result = ticket_auth.fetch_ticket().await;
+
+                    // closed, unmounted or replaced by a new connection in the meantime
+                    if unmounted.get() || connect_id_ref.try_get_value() != Some(connect_id) {
+                        return;
+                    }
+
+                    if manually_closed_ref.get_value() {
+                        set_ready_state.set(ConnectionReadyState::Closed);
+                        return;
+                    }
+
+                    match result {
+                        Ok(ticket) => {
+                            let (url, protocols) = ticket_auth.apply(&url, protocols, &ticket);
+                            setup_web_socket(url, protocols);
+                        }
+                        Err(err) => {
+                            #[cfg(debug_assertions)]
+                            let prev = SpecialNonReactiveZone::enter();
+
+                            on_error(UseWebSocketError::Ticket(err));
+
+                            #[cfg(debug_assertions)]
+                            SpecialNonReactiveZone::exit(prev);
+
+                            set_ready_state.set(ConnectionReadyState::Closed);
+
+                            if let Some(reconnect) = reconnect_ref.get_value() {
+                                reconnect();
+                            }
+                        }
+                    }
+                });
             }))
         });
     }
 
+    // Buffer a message while not connected (only if the option `send_queue` is set)
+    let enqueue = {
+        let on_error = Rc::clone(&on_error);
+
+        move |message: QueuedMessage| {
+            let Some(WebSocketSendQueue { capacity, overflow }) = send_queue else {
+                return;
+            };
+
+            let accepted = send_queue_ref
+                .try_update_value(|queue| {
+                    // only `DropOldest` makes room in a full queue
+                    if queue.len() >= capacity
+                        && (overflow != SendQueueOverflow::DropOldest
+                            || queue.pop_front().is_none())
+                    {
+                        return false;
+                    }
+
+                    queue.push_back(message);
+                    true
+                })
+                .unwrap_or_default();
+
+            update_buffered_amount();
+
+            if !accepted && overflow == SendQueueOverflow::Error {
+                on_error(UseWebSocketError::SendQueueFull);
+            }
+        }
+    };
+
     // Send text (String)
     let send_str = {
+        let enqueue = enqueue.clone();
+
         Box::new(move |data: &str| {
             if ready_state.get_untracked() == ConnectionReadyState::Open {
                 if let Some(web_socket) = ws_ref.get_value() {
                     let _ = web_socket.send_with_str(data);
+                    update_buffered_amount();
                 }
+            } else {
+                enqueue(QueuedMessage::Text(data.to_string()));
             }
         })
     };
@@ -542,7 +999,10 @@ where
         if ready_state.get_untracked() == ConnectionReadyState::Open {
             if let Some(web_socket) = ws_ref.get_value() {
                 let _ = web_socket.send_with_u8_array(data);
+                update_buffered_amount();
             }
+        } else {
+            enqueue(QueuedMessage::Bytes(data.to_vec()));
         }
     };
 
@@ -564,23 +1024,54 @@ where
         }
     };
 
+    let subscribe = move || {
+        let (message, set_message) = create_signal(None::<T>);
+
+        let id = next_subscriber_id.get_value();
+        next_subscriber_id.set_value(id + 1);
+
+        // every subscriber decodes the message itself so `T` doesn't have to be `Clone`
+        let subscriber: Rc<dyn Fn(RawMessage)> = Rc::new(move |raw: RawMessage<'_>| {
+            let decoded = match raw {
+                RawMessage::Text(txt) => C::decode_str(txt).ok(),
+                RawMessage::Bytes(bytes) => C::decode_bin(bytes).ok(),
+            };
+
+            if let Some(val) = decoded {
+                set_message.set(Some(val));
+            }
+        });
+        subscribers.update_value(|subscribers| subscribers.push((id, subscriber)));
+
+        on_cleanup(move || {
+            subscribers.try_update_value(|subscribers| {
+                subscribers.retain(|(subscriber_id, _)| *subscriber_id != id)
+            });
+        });
+
+        Signal::from(message)
+    };
+
     // Open connection
     let open = move || {
-        reconnect_times_ref.set_value(0);
+        manually_closed_ref.set_value(false);
+        clear_reconnect_timer();
+        reconnect_countdown.stop();
+        set_reconnect_attempts.set(0);
+        connection_lost_at_ref.set_value(None);
         if let Some(connect) = connect_ref.get_value() {
             connect();
         }
     };
 
     // Close connection
-    let close = {
-        reconnect_timer_ref.set_value(None);
-
-        move || {
-            manually_closed_ref.set_value(true);
-            if let Some(web_socket) = ws_ref.get_value() {
-                let _ = web_socket.close();
-            }
+    let close = move || {
+        manually_closed_ref.set_value(true);
+        clear_reconnect_timer();
+        reconnect_countdown.stop();
+        stop_heartbeat();
+        if let Some(web_socket) = ws_ref.get_value() {
+            let _ = web_socket.close();
         }
     };
 
@@ -592,6 +1083,10 @@ where
     });
 
     // clean up (unmount)
+    if let Some(abort_controller) = abort_controller {
+        abort_controller.on_abort(close.clone());
+    }
+
     on_cleanup(move || {
         unmounted.set(true);
         close();
@@ -600,33 +1095,40 @@ where
     UseWebSocketReturn {
         ready_state: ready_state.into(),
         message: message.into(),
+        reconnect_attempts: reconnect_attempts.into(),
+        reconnect_countdown: reconnect_countdown.seconds(),
+        last_heartbeat: last_heartbeat.into(),
+        buffered_amount: buffered_amount.into(),
         ws: ws_ref.get_value(),
         open,
         close,
         send,
+        subscribe,
     }
 }
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub enum ReconnectLimit {
-    Infinite,
-    Limited(u64),
-}
 
-impl Default for ReconnectLimit {
-    fn default() -> Self {
-        ReconnectLimit::Limited(3)
-    }
+/// A message that waits in the send queue until the connection is open
+enum QueuedMessage {
+    Text(String),
+    Bytes(Vec<u8>),
 }
 
-impl ReconnectLimit {
-    pub fn is_exceeded_by(self, times: u64) -> bool {
+impl QueuedMessage {
+    fn len(&self) -> usize {
         match self {
-            ReconnectLimit::Infinite => false,
-            ReconnectLimit::Limited(limit) => times >= limit,
+            Self::Text(text) => text.len(),
+            Self::Bytes(bytes) => bytes.len(),
         }
     }
 }
 
+/// An undecoded message that is passed to the subscribers
+#[derive(Clone, Copy)]
+enum RawMessage<'a> {
+    Text(&'a str),
+    Bytes(&'a [u8]),
+}
+
 type RcFnBytes = Rc<dyn Fn(&[u8])>;
 
 /// Options for [`use_websocket_with_options`].
@@ -650,16 +1152,37 @@ where
     /// `WebSocket` close callback.
     on_close: Rc<dyn Fn(CloseEvent)>,
     /// Retry times. Defaults to `ReconnectLimit::Limited(3)`. Use `ReconnectLimit::Infinite` for
-    /// infinite retries.
+    /// infinite retries. Ignored if a `reconnect_policy` is set.
     reconnect_limit: ReconnectLimit,
-    /// Retry interval in ms. Defaults to 3000.
+    /// Retry interval in ms. Defaults to 3000. Ignored if a `reconnect_policy` is set.
     reconnect_interval: u64,
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`]. Defaults to `None` which uses `reconnect_limit` and
+    /// `reconnect_interval`.
+    #[builder(skip)]
+    reconnect_policy: Option<Rc<dyn ReconnectPolicy>>,
     /// If `true` the `WebSocket` connection will immediately be opened when calling this function.
     /// If `false` you have to manually call the `open` function.
     /// Defaults to `true`.
     immediate: bool,
     /// Sub protocols. See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/WebSocket#protocols).
     protocols: Option<Vec<String>>,
+    /// Fetch a short-lived ticket before every (re)connect and pass it to the server.
+    /// See [`WebSocketTicketAuth`]. Defaults to `None`.
+    #[builder(into)]
+    ticket_auth: Option<WebSocketTicketAuth>,
+    /// Periodically send a ping and reconnect if the server doesn't answer in time.
+    /// See [`WebSocketHeartbeat`]. Defaults to `None`.
+    #[builder(into)]
+    heartbeat: Option<WebSocketHeartbeat>,
+    /// Buffer messages that are sent while the connection isn't open and send them in order
+    /// once it's open. See [`WebSocketSendQueue`]. Defaults to `None` which drops these messages.
+    #[builder(into)]
+    send_queue: Option<WebSocketSendQueue>,
+    /// If set, the connection is closed when the controller is aborted.
+    /// See [`crate::use_abort_controller`]. Defaults to `None`.
+    #[builder(into)]
+    abort_controller: Option<ManagedAbortController>,
 }
 
 impl<T: ?Sized, E, D> UseWebSocketOptions<T, E, D> {
@@ -684,6 +1207,15 @@ impl<T: ?Sized, E, D> UseWebSocketOptions<T, E, D> {
             ..self
         }
     }
+
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`]. Replaces `reconnect_limit` and `reconnect_interval`.
+    pub fn reconnect_policy(self, policy: impl ReconnectPolicy + 'static) -> Self {
+        Self {
+            reconnect_policy: Some(Rc::new(policy)),
+            ..self
+        }
+    }
 }
 
 impl<T: ?Sized, E, D> Default for UseWebSocketOptions<T, E, D> {
@@ -697,25 +1229,282 @@ impl<T: ?Sized, E, D> Default for UseWebSocketOptions<T, E, D> {
             on_close: Rc::new(|_| {}),
             reconnect_limit: ReconnectLimit::default(),
             reconnect_interval: 3000,
+            reconnect_policy: None,
             immediate: true,
             protocols: Default::default(),
+            ticket_auth: None,
+            heartbeat: None,
+            send_queue: None,
+            abort_controller: None,
+        }
+    }
+}
+
+/// Send queue of [`use_websocket`].
+///
+/// Messages that are sent while the connection is being established or re-established are
+/// buffered in the queue instead of being dropped. Once the connection is open they are sent in
+/// the order they were sent in.
+#[derive(DefaultBuilder, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WebSocketSendQueue {
+    /// Maximum number of messages in the queue. Defaults to `100`.
+    capacity: usize,
+
+    /// What happens when a message is sent while the queue is full.
+    /// Defaults to [`SendQueueOverflow::DropOldest`].
+    overflow: SendQueueOverflow,
+}
+
+impl Default for WebSocketSendQueue {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            overflow: SendQueueOverflow::DropOldest,
+        }
+    }
+}
+
+/// What to do with a message that is sent while the [`WebSocketSendQueue`] is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SendQueueOverflow {
+    /// Remove the oldest message from the queue to make room for the new one
+    #[default]
+    DropOldest,
+    /// Drop the new message
+    DropNewest,
+    /// Drop the new message and call `on_error` with [`UseWebSocketError::SendQueueFull`]
+    Error,
+}
+
+/// Heartbeat of [`use_websocket`].
+///
+/// Connections that are idle for a while are often dropped silently by proxies and load
+/// balancers without the `WebSocket` being closed. With a heartbeat the `message` is sent as
+/// text every `interval` ms. If the server doesn't answer within `timeout` ms the connection is
+/// considered dead, it's closed and re-established according to the `reconnect_policy`.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_websocket_with_options, UseWebSocketOptions, UseWebSocketReturn, WebSocketHeartbeat};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn { last_heartbeat, .. } = use_websocket_with_options::<String, FromToStringCodec>(
+///     "wss://echo.websocket.events/",
+///     UseWebSocketOptions::default().heartbeat(
+///         WebSocketHeartbeat::default()
+///             .message("ping")
+///             .pong("pong")
+///             .interval(20_000)
+///             .timeout(5_000),
+///     ),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+#[derive(DefaultBuilder, Clone, Debug, PartialEq)]
+pub struct WebSocketHeartbeat {
+    /// Text that is sent as ping. Defaults to `"ping"`.
+    #[builder(into)]
+    message: String,
+
+    /// The answer of the server. If set, only this exact text counts as answer and it isn't
+    /// passed on as a message. If `None` any message counts as answer. Defaults to `None`.
+    #[builder(into)]
+    pong: Option<String>,
+
+    /// Interval between two pings in ms. Defaults to 30 seconds.
+    interval: u64,
+
+    /// Time in ms to wait for the answer before the connection is considered dead.
+    /// Defaults to 10 seconds.
+    timeout: u64,
+}
+
+impl Default for WebSocketHeartbeat {
+    fn default() -> Self {
+        Self {
+            message: "ping".to_string(),
+            pong: None,
+            interval: 30_000,
+            timeout: 10_000,
+        }
+    }
+}
+
+/// Authentication of [`use_websocket`] with short-lived tickets.
+///
+/// Browsers can't send custom headers like `Authorization` with the `WebSocket` handshake.
+/// A common workaround is to fetch a ticket from an authenticated HTTP endpoint first and pass it
+/// to the `WebSocket` server in the URL or as a subprotocol. A new ticket is fetched before every
+/// (re)connect.
+#[derive(DefaultBuilder, Clone)]
+pub struct WebSocketTicketAuth {
+    /// The URL of the endpoint that issues the tickets.
+    #[builder(into)]
+    endpoint: String,
+
+    /// The HTTP method of the ticket request. Defaults to `"POST"`.
+    #[builder(into)]
+    method: String,
+
+    /// Headers of the ticket request like `Authorization`. They are read on every request so you
+    /// can provide a signal with refreshed credentials. Defaults to none.
+    #[builder(into)]
+    headers: MaybeSignal<Vec<(String, String)>>,
+
+    /// Send cookies with the ticket request, also to other origins. Defaults to `true`.
+    with_credentials: bool,
+
+    /// Extracts the ticket from the response body. Defaults to the whole body without
+    /// surrounding whitespace.
+    extract_ticket: Rc<dyn Fn(&str) -> Option<String>>,
+
+    /// Where to put the ticket. Defaults to the query parameter `ticket`.
+    placement: TicketPlacement,
+
+    /// Called when the endpoint responds with `401` or `403`. Use this to refresh expired
+    /// credentials, for example by updating the signal of `headers`. The connection is retried
+    /// according to the `reconnect_policy`.
+    on_unauthorized: Rc<dyn Fn()>,
+}
+
+impl Default for WebSocketTicketAuth {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            method: "POST".to_string(),
+            headers: MaybeSignal::default(),
+            with_credentials: true,
+            extract_ticket: Rc::new(|body| Some(body.trim().to_string())),
+            placement: TicketPlacement::default(),
+            on_unauthorized: Rc::new(|| {}),
         }
     }
 }
 
+impl WebSocketTicketAuth {
+    /// Creates the ticket authentication for the given endpoint with the default options.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self::default().endpoint(endpoint)
+    }
+
+    async fn fetch_ticket(&self) -> Result<String, JsValue> {
+        let headers = web_sys::Headers::new()?;
+        for (name, value) in self.headers.get_untracked() {
+            headers.append(&name, &value)?;
+        }
+
+        let mut init = web_sys::RequestInit::new();
+        init.method(&self.method)
+            .headers(&headers)
+            .credentials(if self.with_credentials {
+                web_sys::RequestCredentials::Include
+            } else {
+                web_sys::RequestCredentials::SameOrigin
+            });
+
+        let response = js_fut!(window().fetch_with_str_and_init(&self.endpoint, &init))
+            .await?
+            .unchecked_into::<web_sys::Response>();
+
+        if response.status() == 401 || response.status() == 403 {
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            (self.on_unauthorized)();
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        }
+
+        if !response.ok() {
+            return Err(JsValue::from_str(&format!(
+                "Unexpected response status {}",
+                response.status()
+            )));
+        }
+
+        let body = js_fut!(response.text()?)
+            .await?
+            .as_string()
+            .unwrap_or_default();
+
+        (self.extract_ticket)(&body).ok_or_else(|| JsValue::from_str("No ticket in response"))
+    }
+
+    fn apply(
+        &self,
+        url: &str,
+        protocols: Option<Vec<String>>,
+        ticket: &str,
+    ) -> (String, Option<Vec<String>>) {
+        match &self.placement {
+            TicketPlacement::Query(name) => {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                let url = format!(
+                    "{url}{separator}{}={}",
+                    js_sys::encode_uri_component(name),
+                    js_sys::encode_uri_component(ticket)
+                );
+
+                (url, protocols)
+            }
+            TicketPlacement::Protocol(prefix) => {
+                let mut protocols = protocols.unwrap_or_default();
+                protocols.push(format!("{prefix}{ticket}"));
+
+                (url.to_string(), Some(protocols))
+            }
+        }
+    }
+}
+
+/// Where [`WebSocketTicketAuth`] puts the ticket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TicketPlacement {
+    /// Appends the ticket to the URL as query parameter with the given name.
+    Query(String),
+
+    /// Adds the ticket with the given prefix to the subprotocols, e.g. `"ticket."` results in
+    /// the subprotocol `"ticket.<ticket>"`. Please note that subprotocols may only contain
+    /// characters that are valid in HTTP tokens.
+    Protocol(String),
+}
+
+impl Default for TicketPlacement {
+    fn default() -> Self {
+        Self::Query("ticket".to_string())
+    }
+}
+
 /// Return type of [`use_websocket`].
 #[derive(Clone)]
-pub struct UseWebSocketReturn<T, OpenFn, CloseFn, SendFn>
+pub struct UseWebSocketReturn<T, OpenFn, CloseFn, SendFn, SubscribeFn>
 where
     T: 'static,
     OpenFn: Fn() + Clone + 'static,
     CloseFn: Fn() + Clone + 'static,
     SendFn: Fn(&T) + Clone + 'static,
+    SubscribeFn: Fn() -> Signal<Option<T>> + Clone + 'static,
 {
     /// The current state of the `WebSocket` connection.
     pub ready_state: Signal<ConnectionReadyState>,
     /// Latest message received from `WebSocket`.
     pub message: Signal<Option<T>>,
+    /// Number of reconnection attempts since the connection was lost. Reset to `0` when the
+    /// connection is opened.
+    pub reconnect_attempts: Signal<u64>,
+    /// Seconds until the next reconnection attempt or `None` if no attempt is scheduled.
+    pub reconnect_countdown: Signal<Option<u64>>,
+    /// Time in ms since the Unix epoch (like `Date.now()`) when the server has last answered a
+    /// heartbeat. Always `None` if no `heartbeat` is configured.
+    pub last_heartbeat: Signal<Option<f64>>,
+    /// Number of bytes of the messages that wait in the send queue plus the `bufferedAmount` of
+    /// the `WebSocket`. Updated whenever a message is sent or queued.
+    pub buffered_amount: Signal<usize>,
     /// The `WebSocket` instance.
     pub ws: Option<WebSocket>,
     /// Opens the `WebSocket` connection
@@ -724,6 +1513,115 @@ where
     pub close: CloseFn,
     /// Sends data through the socket
     pub send: SendFn,
+    /// Returns a new signal that receives every message from now on independently of `message`
+    /// and of other subscribers. The subscription ends when the reactive owner of the caller
+    /// is disposed.
+    pub subscribe: SubscribeFn,
+}
+
+impl<T, OpenFn, CloseFn, SendFn, SubscribeFn>
+    UseWebSocketReturn<T, OpenFn, CloseFn, SendFn, SubscribeFn>
+where
+    T: 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+    SubscribeFn: Fn() -> Signal<Option<T>> + Clone + 'static,
+{
+    /// Returns a stream of every message that is received from now on. The stream ends when
+    /// the reactive owner of the caller is disposed.
+    pub fn into_stream(self) -> impl Stream<Item = T>
+    where
+        T: Clone,
+    {
+        signal_to_stream((self.subscribe)()).filter_map(future::ready)
+    }
+
+    /// Returns a sink that sends the messages. It only accepts messages while the connection
+    /// is open and waits for the connection to be (re-)established otherwise.
+    pub fn into_sink(self) -> impl Sink<T, Error = Infallible> {
+        WebSocketSink::new(self.send, self.ready_state)
+    }
+
+    /// Returns both [`UseWebSocketReturn::into_stream`] and [`UseWebSocketReturn::into_sink`].
+    pub fn into_split(self) -> (impl Stream<Item = T>, impl Sink<T, Error = Infallible>)
+    where
+        T: Clone,
+    {
+        (
+            signal_to_stream((self.subscribe)()).filter_map(future::ready),
+            WebSocketSink::new(self.send, self.ready_state),
+        )
+    }
+}
+
+/// Sink returned by [`UseWebSocketReturn::into_sink`].
+struct WebSocketSink<T, SendFn> {
+    send: SendFn,
+    ready_state: Signal<ConnectionReadyState>,
+    waker: Rc<RefCell<Option<Waker>>>,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T, SendFn> WebSocketSink<T, SendFn>
+where
+    SendFn: Fn(&T),
+{
+    fn new(send: SendFn, ready_state: Signal<ConnectionReadyState>) -> Self {
+        let waker = Rc::new(RefCell::new(None::<Waker>));
+
+        let _ = watch(
+            move || ready_state.get(),
+            {
+                let waker = Rc::clone(&waker);
+
+                move |ready_state, _, _| {
+                    if *ready_state == ConnectionReadyState::Open {
+                        if let Some(waker) = waker.borrow_mut().take() {
+                            waker.wake();
+                        }
+                    }
+                }
+            },
+            false,
+        );
+
+        Self {
+            send,
+            ready_state,
+            waker,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, SendFn> Sink<T> for WebSocketSink<T, SendFn>
+where
+    SendFn: Fn(&T),
+{
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.ready_state.get_untracked() == ConnectionReadyState::Open {
+            Poll::Ready(Ok(()))
+        } else {
+            *self.waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        (self.send)(&item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
 }
 
 #[derive(Error, Debug)]
@@ -732,6 +1630,10 @@ pub enum UseWebSocketError<E, D> {
     Event(Event),
     #[error("WebSocket codec error: {0}")]
     Codec(#[from] CodecError<E, D>),
+    #[error("Failed to fetch WebSocket ticket: {0:?}")]
+    Ticket(JsValue),
+    #[error("WebSocket send queue is full")]
+    SendQueueFull,
 }
 
 fn normalize_url(url: &str) -> String {
@@ -0,0 +1,503 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{BackoffReconnectPolicy, ConnectionReadyState, ReconnectPolicy};
+use crate::{use_websocket_with_options, UseWebSocketOptions, UseWebSocketReturn};
+use codee::string::FromToStringCodec;
+use codee::{Decoder, Encoder};
+use default_struct_builder::DefaultBuilder;
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Request/response calls and server-push notifications over a WebSocket.
+///
+/// Built on [`fn@crate::use_websocket`]. Every request gets an id that the server sends back
+/// with its response. `call` returns a future that resolves with the matching response, fails
+/// after a timeout or when the connection is lost, and can be cancelled. All incoming messages
+/// that aren't responses are notifications that end up in the `notification` signal.
+///
+/// How requests, responses and notifications look like on the wire is up to you. Implement
+/// [`RpcProtocol`] for your message type and provide a codec for it. You can use any of the
+/// string codecs or a binary codec wrapped in `Base64`.
+///
+/// > Please check [the codec chapter](https://leptos-use.rs/codecs.html) to see what codecs are
+///   available and what feature flags they require.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_websocket_rpc, RpcIncoming, RpcProtocol, UseWebSocketRpcReturn};
+/// # use codee::string::JsonSerdeCodec;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(tag = "type")]
+/// enum Message {
+///     Request { id: u64, method: String, params: Vec<f64> },
+///     Response { id: u64, result: f64 },
+///     Notification { text: String },
+/// }
+///
+/// impl RpcProtocol for Message {
+///     type Request = (String, Vec<f64>);
+///     type Response = f64;
+///     type Notification = String;
+///
+///     fn request(id: u64, (method, params): Self::Request) -> Self {
+///         Message::Request { id, method, params }
+///     }
+///
+///     fn incoming(self) -> Option<RpcIncoming<f64, String>> {
+///         match self {
+///             Message::Response { id, result } => {
+///                 Some(RpcIncoming::Response { id, response: result })
+///             }
+///             Message::Notification { text } => Some(RpcIncoming::Notification(text)),
+///             Message::Request { .. } => None,
+///         }
+///     }
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketRpcReturn { call, notification, .. } =
+///     use_websocket_rpc::<Message, JsonSerdeCodec>("wss://example.com/rpc");
+///
+/// let (sum, set_sum) = create_signal(None::<f64>);
+///
+/// let add = move |_| {
+///     let result = call(("add".to_string(), vec![1.0, 2.0]));
+///
+///     spawn_local(async move {
+///         if let Ok(result) = result.await {
+///             set_sum.set(Some(result));
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=add>"1 + 2"</button>
+///     <p>{sum}</p>
+///     <p>"Latest notification: " {notification}</p>
+/// }
+/// # }
+/// ```
+///
+/// ## Timeouts and Cancellation
+///
+/// A call fails with `RpcError::Timeout` if there is no response within `timeout` ms. Calling
+/// `cancel` on the returned [`RpcCall`] or dropping it stops waiting for the response. A
+/// response that arrives later is ignored.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no connection is made. Every call fails with `RpcError::NotConnected` and
+/// `notification` is always `None`.
+pub fn use_websocket_rpc<P, C>(
+    url: &str,
+) -> UseWebSocketRpcReturn<
+    P,
+    <C as Encoder<P>>::Error,
+    <C as Decoder<P>>::Error,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn(P::Request) -> RpcCall<P::Response, <C as Encoder<P>>::Error> + Clone + 'static,
+>
+where
+    P: RpcProtocol + 'static,
+    C: Encoder<P, Encoded = String> + Decoder<P, Encoded = str>,
+{
+    use_websocket_rpc_with_options::<P, C>(url, UseWebSocketRpcOptions::default())
+}
+
+/// Version of [`use_websocket_rpc`] that takes a `UseWebSocketRpcOptions`. See [`use_websocket_rpc`] for how to use.
+pub fn use_websocket_rpc_with_options<P, C>(
+    url: &str,
+    options: UseWebSocketRpcOptions,
+) -> UseWebSocketRpcReturn<
+    P,
+    <C as Encoder<P>>::Error,
+    <C as Decoder<P>>::Error,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn(P::Request) -> RpcCall<P::Response, <C as Encoder<P>>::Error> + Clone + 'static,
+>
+where
+    P: RpcProtocol + 'static,
+    C: Encoder<P, Encoded = String> + Decoder<P, Encoded = str>,
+{
+    let UseWebSocketRpcOptions {
+        timeout,
+        reconnect_policy,
+        immediate,
+        protocols,
+    } = options;
+
+    let (notification, set_notification) = create_signal(None::<P::Notification>);
+    let (error, set_error) = create_signal(None::<<C as Decoder<P>>::Error>);
+
+    let calls = store_value(HashMap::<
+        u64,
+        PendingCall<P::Response, <C as Encoder<P>>::Error>,
+    >::new());
+    let (pending, set_pending) = create_signal(0_usize);
+    let next_id = store_value(0_u64);
+
+    // removes the call and completes it with `result` if it's still pending
+    let finish = move |id: u64, result: CallResult<P::Response, <C as Encoder<P>>::Error>| {
+        let call = calls.try_update_value(|calls| calls.remove(&id)).flatten();
+
+        if let Some(call) = call {
+            set_pending.set(calls.with_value(HashMap::len));
+            call.complete(result);
+        }
+    };
+
+    let UseWebSocketReturn {
+        ready_state,
+        send,
+        open,
+        close,
+        ..
+    } = use_websocket_with_options::<String, FromToStringCodec>(
+        url,
+        UseWebSocketOptions::default()
+            .reconnect_policy(reconnect_policy)
+            .immediate(immediate)
+            .protocols(protocols)
+            .on_message(move |message: &String| match C::decode(message) {
+                Ok(message) => match message.incoming() {
+                    Some(RpcIncoming::Response { id, response }) => finish(id, Ok(response)),
+                    Some(RpcIncoming::Notification(value)) => set_notification.set(Some(value)),
+                    None => {}
+                },
+                Err(err) => set_error.set(Some(err)),
+            }),
+    );
+
+    // responses to calls of a lost connection will never arrive
+    let _ = watch(
+        move || ready_state.get(),
+        move |ready_state, _, _| {
+            if *ready_state != ConnectionReadyState::Open {
+                let ids = calls.with_value(|calls| calls.keys().copied().collect::<Vec<_>>());
+
+                for id in ids {
+                    finish(id, Err(RpcError::Disconnected));
+                }
+            }
+        },
+        false,
+    );
+
+    on_cleanup(move || {
+        let ids = calls
+            .try_with_value(|calls| calls.keys().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        for id in ids {
+            finish(id, Err(RpcError::Disconnected));
+        }
+    });
+
+    let remove: Rc<dyn Fn(u64)> = Rc::new(move |id| finish(id, Err(RpcError::Cancelled)));
+
+    let call = move |request: P::Request| {
+        let id = next_id.try_update_value(|next_id| {
+            *next_id += 1;
+            *next_id
+        });
+
+        let state = Rc::new(RefCell::new(CallState::default()));
+        let rpc_call = RpcCall {
+            id: id.unwrap_or_default(),
+            state: Rc::clone(&state),
+            remove: Rc::clone(&remove),
+        };
+
+        let Some(id) = id else {
+            rpc_call.fail(RpcError::NotConnected);
+            return rpc_call;
+        };
+
+        if ready_state.get_untracked() != ConnectionReadyState::Open {
+            rpc_call.fail(RpcError::NotConnected);
+            return rpc_call;
+        }
+
+        let message = match C::encode(&P::request(id, request)) {
+            Ok(message) => message,
+            Err(err) => {
+                rpc_call.fail(RpcError::Encode(err));
+                return rpc_call;
+            }
+        };
+
+        let timeout_handle = timeout.and_then(|timeout| {
+            set_timeout_with_handle(
+                move || finish(id, Err(RpcError::Timeout)),
+                Duration::from_millis(timeout),
+            )
+            .ok()
+        });
+
+        calls.update_value(|calls| {
+            calls.insert(
+                id,
+                PendingCall {
+                    state,
+                    timeout_handle,
+                },
+            );
+        });
+        set_pending.set(calls.with_value(HashMap::len));
+
+        send(&message);
+
+        rpc_call
+    };
+
+    UseWebSocketRpcReturn {
+        ready_state,
+        notification: notification.into(),
+        pending: pending.into(),
+        error: error.into(),
+        call,
+        open,
+        close,
+    }
+}
+
+/// How the messages of [`use_websocket_rpc`] carry requests, responses and notifications.
+///
+/// Implement this for the message type that is sent over the WebSocket.
+pub trait RpcProtocol: Sized {
+    /// What is passed to `call`
+    type Request;
+
+    /// What a call resolves with. To pass errors of the server use a `Result`.
+    type Response;
+
+    /// Messages that the server sends on its own
+    type Notification;
+
+    /// Creates the message that is sent for `request`. The response has to carry the same `id`.
+    fn request(id: u64, request: Self::Request) -> Self;
+
+    /// Tells if an incoming message is a response or a notification. Messages for which this
+    /// returns `None` are ignored.
+    fn incoming(self) -> Option<RpcIncoming<Self::Response, Self::Notification>>;
+}
+
+/// An incoming message of [`use_websocket_rpc`]. See [`RpcProtocol::incoming`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RpcIncoming<Res, N> {
+    /// The response to the request with the `id`
+    Response { id: u64, response: Res },
+    /// A message that the server sent on its own
+    Notification(N),
+}
+
+type CallResult<R, E> = Result<R, RpcError<E>>;
+
+struct CallState<R, E> {
+    result: Option<CallResult<R, E>>,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl<R, E> Default for CallState<R, E> {
+    fn default() -> Self {
+        Self {
+            result: None,
+            done: false,
+            waker: None,
+        }
+    }
+}
+
+struct PendingCall<R, E> {
+    state: Rc<RefCell<CallState<R, E>>>,
+    timeout_handle: Option<TimeoutHandle>,
+}
+
+impl<R, E> PendingCall<R, E> {
+    fn complete(self, result: CallResult<R, E>) {
+        if let Some(timeout_handle) = self.timeout_handle {
+            timeout_handle.clear();
+        }
+
+        complete(&self.state, result);
+    }
+}
+
+fn complete<R, E>(state: &RefCell<CallState<R, E>>, result: CallResult<R, E>) {
+    let waker = {
+        let mut state = state.borrow_mut();
+
+        if state.done {
+            return;
+        }
+
+        state.done = true;
+        state.result = Some(result);
+        state.waker.take()
+    };
+
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// A call of [`use_websocket_rpc`]. Resolves with the response or an error.
+///
+/// Dropping it stops waiting for the response just like `cancel`.
+pub struct RpcCall<R, E> {
+    id: u64,
+    state: Rc<RefCell<CallState<R, E>>>,
+    remove: Rc<dyn Fn(u64)>,
+}
+
+impl<R, E> RpcCall<R, E> {
+    /// The id the request has been sent with
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Stops waiting for the response. The call fails with `RpcError::Cancelled`.
+    pub fn cancel(&self) {
+        (self.remove)(self.id);
+        self.fail(RpcError::Cancelled);
+    }
+
+    fn fail(&self, error: RpcError<E>) {
+        complete(&self.state, Err(error));
+    }
+}
+
+impl<R, E> Future for RpcCall<R, E> {
+    type Output = CallResult<R, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None if state.done => Poll::Ready(Err(RpcError::Cancelled)),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<R, E> Drop for RpcCall<R, E> {
+    fn drop(&mut self) {
+        if !self.state.borrow().done {
+            (self.remove)(self.id);
+        }
+    }
+}
+
+/// Options for [`use_websocket_rpc_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWebSocketRpcOptions {
+    /// Time in ms after which a call without a response fails with `RpcError::Timeout`.
+    /// `None` waits forever. Defaults to 30 seconds.
+    #[builder(into)]
+    timeout: Option<u64>,
+
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// Defaults to at most 3 attempts 3 seconds apart. See [`ReconnectPolicy`].
+    #[builder(skip)]
+    reconnect_policy: Rc<dyn ReconnectPolicy>,
+
+    /// If `true` the connection is opened immediately. If `false` you have to call `open`.
+    /// Defaults to `true`.
+    immediate: bool,
+
+    /// Sub protocols. See [MDN Docs](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/WebSocket#protocols).
+    protocols: Option<Vec<String>>,
+}
+
+impl UseWebSocketRpcOptions {
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`].
+    pub fn reconnect_policy(self, policy: impl ReconnectPolicy + 'static) -> Self {
+        Self {
+            reconnect_policy: Rc::new(policy),
+            ..self
+        }
+    }
+}
+
+impl Default for UseWebSocketRpcOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Some(30_000),
+            reconnect_policy: Rc::new(BackoffReconnectPolicy::default()),
+            immediate: true,
+            protocols: None,
+        }
+    }
+}
+
+/// Return type of [`use_websocket_rpc`].
+pub struct UseWebSocketRpcReturn<P, E, D, OpenFn, CloseFn, CallFn>
+where
+    P: RpcProtocol + 'static,
+    E: 'static,
+    D: 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+    CallFn: Fn(P::Request) -> RpcCall<P::Response, E> + Clone + 'static,
+{
+    /// The current state of the connection
+    pub ready_state: Signal<ConnectionReadyState>,
+
+    /// Latest notification of the server
+    pub notification: Signal<Option<P::Notification>>,
+
+    /// Number of calls that wait for their response
+    pub pending: Signal<usize>,
+
+    /// The latest error decoding an incoming message
+    pub error: Signal<Option<D>>,
+
+    /// Sends a request and returns the call that resolves with its response
+    pub call: CallFn,
+
+    /// Opens the connection
+    pub open: OpenFn,
+
+    /// Closes the connection
+    pub close: CloseFn,
+}
+
+/// Why a call of [`use_websocket_rpc`] failed.
+#[derive(Error, Debug)]
+pub enum RpcError<E> {
+    #[error("Not connected")]
+    NotConnected,
+
+    #[error("The connection has been lost before the response arrived")]
+    Disconnected,
+
+    #[error("No response within the timeout")]
+    Timeout,
+
+    #[error("The call has been cancelled")]
+    Cancelled,
+
+    #[error("Failed to encode the request")]
+    Encode(E),
+}
@@ -1,27 +1,28 @@
-use crate::core::ConnectionReadyState;
-use async_trait::async_trait;
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{
+    BackoffReconnectPolicy, ConnectionReadyState, ReconnectCountdown, ReconnectPolicy,
+};
+use crate::{js, js_fut};
+use codee::{CodecError, Decoder, Encoder};
 use default_struct_builder::DefaultBuilder;
-use js_sys::Reflect;
-use leptos::leptos_dom::helpers::TimeoutHandle;
-use leptos::*;
+use leptos::{leptos_dom::helpers::TimeoutHandle, *};
 use std::cell::{Cell, RefCell};
+use std::future::Future;
 use std::rc::Rc;
-use std::time::Duration;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::JsFuture;
-
-#[cfg(any(feature = "bincode", feature = "msgpack"))]
-use serde::{Deserialize, Serialize};
-
-#[cfg(feature = "msgpack")]
-use rmp_serde::{from_slice, to_vec};
 use thiserror::Error;
-use web_sys::WebTransportBidirectionalStream;
-
-#[cfg(feature = "bincode")]
-use bincode::serde::{decode_from_slice as from_slice, encode_to_vec as to_vec};
+use wasm_bindgen::prelude::*;
 
-/// This still under development and will not arrive before Leptos 0.7.
+/// Reactive [WebTransport](https://developer.mozilla.org/en-US/docs/Web/API/WebTransport) client.
+///
+/// WebTransport runs on top of HTTP/3 and offers unreliable datagrams as well as any number of
+/// reliable streams over one connection. It's well suited for latency-sensitive streaming where
+/// a WebSocket would suffer from head-of-line blocking.
+///
+/// Datagrams are encoded and decoded with the binary codec `C` similar to [`crate::use_websocket`].
+/// The latest received datagram is available as the signal `datagram`.
+///
+/// > This requires the `--cfg=web_sys_unstable_apis` flag for `web-sys`.
 ///
 /// ## Demo
 ///
@@ -31,720 +32,805 @@ use bincode::serde::{decode_from_slice as from_slice, encode_to_vec as to_vec};
 ///
 /// ```
 /// # use leptos::*;
-/// # use leptos_use::use_webtransport;
+/// # use codee::binary::MsgpackSerdeCodec;
+/// # use leptos_use::{use_webtransport, UseWebTransportReturn};
+/// # use leptos_use::core::ConnectionReadyState;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Serialize, Deserialize)]
+/// struct Position {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebTransportReturn {
+///     ready_state,
+///     datagram,
+///     send_datagram,
+///     ..
+/// } = use_webtransport::<Position, MsgpackSerdeCodec>("https://example.com:4433/game");
+///
+/// let send_position = move |_| send_datagram(&Position { x: 1.0, y: 2.0 });
+///
+/// view! {
+///     <button on:click=send_position disabled=move || ready_state.get() != ConnectionReadyState::Open>
+///         "Send"
+///     </button>
+///     <p>
+///         {move || datagram.with(|position| position.as_ref().map(|p| format!("{}, {}", p.x, p.y)))}
+///     </p>
+/// }
+/// # }
+/// ```
+///
+/// ## Streams
+///
+/// Streams are opened with `open_send_stream` (unidirectional) and `open_bidir_stream`. Streams
+/// opened by the server are passed to the callbacks `on_receive_stream` and `on_bidir_stream`.
+///
+/// A stream is a sequence of bytes without message boundaries. To send and receive whole
+/// messages, every message is prefixed by its length as a 4 byte big endian unsigned integer.
+/// The server has to use the same framing. Messages can be encoded and decoded with any binary
+/// codec independently of the one used for datagrams.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::binary::MsgpackSerdeCodec;
+/// # use leptos_use::{use_webtransport, UseWebTransportReturn};
 /// #
 /// # #[component]
 /// # fn Demo() -> impl IntoView {
-/// use_webtransport();
+/// let transport = use_webtransport::<String, MsgpackSerdeCodec>("https://example.com:4433/chat");
+///
+/// let open_chat = move |_| {
+///     let stream = transport.open_bidir_stream();
+///
+///     spawn_local(async move {
+///         if let Ok(stream) = stream.await {
+///             let _ = stream.send_stream.send::<String, MsgpackSerdeCodec>(&"Hello".to_string());
+///
+///             let reply = stream.receive_stream.receive::<String, MsgpackSerdeCodec>();
+///             // ...
+///         }
+///     });
+/// };
 /// #
 /// # view! { }
 /// # }
 /// ```
-pub fn use_webtransport(url: &str) -> UseWebTransportReturn {
-    use_webtransport_with_options(url, UseWebTransportOptions::default())
+///
+/// ## Reconnecting
+///
+/// When the connection is lost it is re-established according to the `reconnect_policy`
+/// (see [`ReconnectPolicy`]). Streams don't survive a reconnect and have to be opened again.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no connection is opened. `ready_state` is always `Closed`, the functions do
+/// nothing and opening a stream fails with [`WebTransportError::NotConnected`].
+pub fn use_webtransport<T, C>(
+    url: &str,
+) -> UseWebTransportReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn(&T) + Clone + 'static,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = Vec<u8>> + Decoder<T, Encoded = [u8]>,
+{
+    use_webtransport_with_options::<T, C>(url, UseWebTransportOptions::default())
 }
 
-/// Version of [`use_webtransport`] that takes a `UseWebtransportOptions`. See [`use_webtransport`] for how to use.
-pub fn use_webtransport_with_options(
+/// Version of [`use_webtransport`] that takes a `UseWebTransportOptions`. See [`use_webtransport`] for how to use.
+pub fn use_webtransport_with_options<T, C>(
     url: &str,
-    options: UseWebTransportOptions,
-) -> UseWebTransportReturn {
+    options: UseWebTransportOptions<<C as Encoder<T>>::Error, <C as Decoder<T>>::Error>,
+) -> UseWebTransportReturn<
+    T,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn(&T) + Clone + 'static,
+>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = Vec<u8>> + Decoder<T, Encoded = [u8]>,
+{
     let UseWebTransportOptions {
         on_open,
-        // on_error,
+        on_error,
         on_close,
         on_receive_stream,
         on_bidir_stream,
-        reconnect_limit,
-        reconnect_interval,
+        reconnect_policy,
         immediate,
     } = options;
+
     let url = url.to_string();
 
     let (ready_state, set_ready_state) = create_signal(ConnectionReadyState::Closed);
-    let ready_state: Signal<_> = ready_state.into();
+    let (datagram, set_datagram) = create_signal(None::<T>);
+    let (reconnect_attempts, set_reconnect_attempts) = create_signal(0_u64);
+    let reconnect_countdown = ReconnectCountdown::new();
 
-    let transport = Rc::new(RefCell::new(None::<web_sys::WebTransport>));
-    let datagrams_reader_initialized = Rc::new(Cell::new(false));
-    let datagrams_writer = Rc::new(RefCell::new(None::<web_sys::WritableStreamDefaultWriter>));
+    let transport_ref: StoredValue<Option<web_sys::WebTransport>> = store_value(None);
+    let datagram_writer_ref: StoredValue<Option<web_sys::WritableStreamDefaultWriter>> =
+        store_value(None);
 
-    let reconnect_timer = Rc::new(Cell::new(None::<TimeoutHandle>));
-    let reconnect_count = Rc::new(Cell::new(0_u64));
+    let reconnect_timer_ref: StoredValue<Option<TimeoutHandle>> = store_value(None);
+    let connection_lost_at_ref: StoredValue<Option<f64>> = store_value(None);
+    let manually_closed_ref: StoredValue<bool> = store_value(false);
+    let connect_ref: StoredValue<Option<Rc<dyn Fn()>>> = store_value(None);
 
     let unmounted = Rc::new(Cell::new(false));
 
-    let connect_ref = store_value(None::<Rc<dyn Fn()>>);
+    #[cfg(not(feature = "ssr"))]
+    {
+        let owner = Owner::current();
 
-    let reconnect = Rc::new({
-        let reconnect_timer = Rc::clone(&reconnect_timer);
-        let reconnect_count = Rc::clone(&reconnect_count);
+        let reconnect: Rc<dyn Fn()> = Rc::new(move || {
+            if reconnect_timer_ref.with_value(|timer| timer.is_some()) {
+                return;
+            }
+
+            let now = js_sys::Date::now();
+            let connection_lost_at = connection_lost_at_ref.get_value().unwrap_or(now);
+            connection_lost_at_ref.set_value(Some(connection_lost_at));
+
+            let attempts = reconnect_attempts.get_untracked();
 
-        move || {
-            if reconnect_count.get() < reconnect_limit
-                && ready_state.get_untracked() == ConnectionReadyState::Open
+            if !manually_closed_ref.get_value()
+                && reconnect_policy.should_reconnect(attempts, (now - connection_lost_at) as u64)
             {
-                reconnect_timer.set(
+                let delay = reconnect_policy.delay(attempts);
+                reconnect_countdown.start(delay);
+
+                reconnect_timer_ref.set_value(
                     set_timeout_with_handle(
                         move || {
+                            reconnect_countdown.stop();
+                            reconnect_timer_ref.set_value(None);
+
+                            if manually_closed_ref.get_value() {
+                                return;
+                            }
                             if let Some(connect) = connect_ref.get_value() {
                                 connect();
-                                reconnect_count.set(reconnect_count.get() + 1);
+                                set_reconnect_attempts.update(|current| *current += 1);
                             }
                         },
-                        Duration::from_millis(reconnect_interval),
+                        delay,
                     )
                     .ok(),
-                )
+                );
             }
-        }
-    });
+        });
 
-    connect_ref.set_value(Some(Rc::new({
-        let transport = Rc::clone(&transport);
-        let reconnect_timer = Rc::clone(&reconnect_timer);
-        let on_open = Rc::clone(&on_open);
-        let on_bidir_stream = Rc::clone(&on_bidir_stream);
-        let on_receive_stream = Rc::clone(&on_receive_stream);
+        let connect_id_ref: StoredValue<u64> = store_value(0);
 
-        move || {
-            reconnect_timer.set(None);
+        let on_error = Rc::clone(&on_error);
+        let unmounted = Rc::clone(&unmounted);
+
+        connect_ref.set_value(Some(Rc::new(move || {
+            reconnect_timer_ref.set_value(None);
 
-            if let Some(transport) = transport.borrow().as_ref() {
+            if let Some(transport) = transport_ref.try_update_value(Option::take).flatten() {
                 transport.close();
             }
+            datagram_writer_ref.set_value(None);
+
+            connect_id_ref.update_value(|id| *id += 1);
+            let connect_id = connect_id_ref.get_value();
+            // callbacks of a previous connection are ignored
+            let is_stale = {
+                let unmounted = Rc::clone(&unmounted);
+                move || unmounted.get() || connect_id_ref.get_value() != connect_id
+            };
+
+            let transport = match web_sys::WebTransport::new(&url) {
+                Ok(transport) => transport,
+                Err(err) => {
+                    on_error(UseWebTransportError::Connection(err));
+                    set_ready_state.set(ConnectionReadyState::Closed);
+                    reconnect();
+                    return;
+                }
+            };
+            transport_ref.set_value(Some(transport.clone()));
+            set_ready_state.set(ConnectionReadyState::Connecting);
 
-            let options = web_sys::WebTransportOptions::new();
-            transport.replace(Some(
-                web_sys::WebTransport::new_with_options(&url, &options).unwrap_throw(),
-            ));
+            let on_open = Rc::clone(&on_open);
+            let on_error = Rc::clone(&on_error);
+            let on_close = Rc::clone(&on_close);
+            let on_receive_stream = Rc::clone(&on_receive_stream);
+            let on_bidir_stream = Rc::clone(&on_bidir_stream);
+            let reconnect = Rc::clone(&reconnect);
+            let owner = owner.clone();
 
-            set_ready_state.set(ConnectionReadyState::Connecting);
+            spawn_local(async move {
+                let ready = js_fut!(transport.ready()).await;
 
-            spawn_local({
-                let transport = Rc::clone(&transport);
-                let on_open = Rc::clone(&on_open);
-                let on_bidir_stream = Rc::clone(&on_bidir_stream);
-                let on_receive_stream = Rc::clone(&on_receive_stream);
-
-                async move {
-                    let transport = transport.borrow();
-                    let transport = transport.as_ref().expect("Transport should be set");
-
-                    match js_fut!(transport.ready()).await {
-                        Ok(_) => {
-                            set_ready_state.set(ConnectionReadyState::Open);
-                            on_open();
-
-                            listen_to_stream(
-                                transport.incoming_bidirectional_streams(),
-                                move |value| {
-                                    let stream: web_sys::WebTransportBidirectionalStream =
-                                        value.unchecked_into();
-
-                                    if let Ok(stream) = create_bidir_stream(stream, ready_state) {
-                                        on_bidir_stream(stream);
-                                    }
-                                },
-                                || {},
-                            );
-                            listen_to_stream(
-                                transport.incoming_unidirectional_streams(),
-                                move |value| {
-                                    let stream: web_sys::ReadableStream = value.unchecked_into();
-
-                                    let (state, set_state, bytes) = create_state_and_bytes_signal(
-                                        stream.unchecked_into(),
-                                        ready_state,
-                                    );
-
-                                    on_receive_stream(ReceiveStream {
-                                        bytes,
-                                        state,
-                                        set_state,
-                                    });
-                                },
-                                || {},
-                            );
-                        }
-                        Err(e) => {
-                            // TODO : handle error?
-                            set_ready_state.set(ConnectionReadyState::Closed);
-                        }
-                    }
+                if is_stale() {
+                    return;
                 }
-            });
-        }
-    })));
 
-    let open = {
-        let reconnect_count = Rc::clone(&reconnect_count);
+                if let Err(err) = ready {
+                    set_ready_state.set(ConnectionReadyState::Closed);
+                    on_error(UseWebTransportError::Connection(err));
+                    reconnect();
+                    return;
+                }
 
-        move || {
-            reconnect_count.set(0);
-            if let Some(connect) = connect_ref.get_value() {
-                connect();
-            }
-        }
-    };
+                set_ready_state.set(ConnectionReadyState::Open);
+                set_reconnect_attempts.set(0);
+                connection_lost_at_ref.set_value(None);
 
-    let on_closed = {
-        let reconnect = Rc::clone(&reconnect);
-        let unmounted = Rc::clone(&unmounted);
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
 
-        move || {
-            if unmounted.get() {
-                return;
-            }
+                on_open();
 
-            // TODO
-            // reconnect();
-        }
-    };
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
 
-    let close = {
-        let transport = Rc::clone(&transport);
-        let reconnect_count = Rc::clone(&reconnect_count);
+                read_stream(
+                    transport.datagrams().readable(),
+                    {
+                        let on_error = Rc::clone(&on_error);
 
-        move || {
-            reconnect_count.set(reconnect_limit);
+                        move |chunk| {
+                            let bytes = js_sys::Uint8Array::new(&chunk).to_vec();
 
-            if let Some(transport) = transport.take() {
-                transport.close();
-                set_ready_state.set(ConnectionReadyState::Closing);
+                            match C::decode(&bytes) {
+                                Ok(value) => set_datagram.set(Some(value)),
+                                Err(err) => on_error(CodecError::Decode(err).into()),
+                            }
+                        }
+                    },
+                    || {},
+                );
 
-                spawn_local(async move {
-                    let result = js_fut!(transport.closed()).await;
-                    set_ready_state.set(ConnectionReadyState::Closed);
+                read_stream(
+                    transport.incoming_unidirectional_streams(),
+                    {
+                        let owner = owner.clone();
+
+                        move |stream| {
+                            let stream = with_owner_or_detached(owner.clone(), || {
+                                ReceiveStream::new(stream.unchecked_into())
+                            });
+
+                            #[cfg(debug_assertions)]
+                            let prev = SpecialNonReactiveZone::enter();
 
-                    on_closed();
+                            on_receive_stream(stream);
 
-                    match result {
-                        Ok(_) => {}
-                        Err(e) => {
-                            // TODO : handle error?
+                            #[cfg(debug_assertions)]
+                            SpecialNonReactiveZone::exit(prev);
                         }
-                    }
-                });
-            }
+                    },
+                    || {},
+                );
+
+                read_stream(
+                    transport.incoming_bidirectional_streams(),
+                    move |stream| {
+                        let stream = with_owner_or_detached(owner.clone(), || {
+                            BidirStream::new(stream.unchecked_into())
+                        });
+
+                        if let Ok(stream) = stream {
+                            #[cfg(debug_assertions)]
+                            let prev = SpecialNonReactiveZone::enter();
+
+                            on_bidir_stream(stream);
+
+                            #[cfg(debug_assertions)]
+                            SpecialNonReactiveZone::exit(prev);
+                        }
+                    },
+                    || {},
+                );
+
+                let closed = js_fut!(transport.closed()).await;
+
+                if is_stale() {
+                    return;
+                }
+
+                set_ready_state.set(ConnectionReadyState::Closed);
+
+                if let Err(err) = closed {
+                    on_error(UseWebTransportError::Connection(err));
+                }
+
+                #[cfg(debug_assertions)]
+                let prev = SpecialNonReactiveZone::enter();
+
+                on_close();
+
+                #[cfg(debug_assertions)]
+                SpecialNonReactiveZone::exit(prev);
+
+                reconnect();
+            });
+        })));
+    }
+
+    let open = move || {
+        manually_closed_ref.set_value(false);
+        reconnect_countdown.stop();
+        set_reconnect_attempts.set(0);
+        connection_lost_at_ref.set_value(None);
+        if let Some(connect) = connect_ref.get_value() {
+            connect();
         }
     };
 
-    let (datagrams_signal, set_datagrams) = create_signal(None::<Vec<u8>>);
+    let close = move || {
+        manually_closed_ref.set_value(true);
+        reconnect_countdown.stop();
+        if let Some(timer) = reconnect_timer_ref.try_update_value(Option::take).flatten() {
+            timer.clear();
+        }
+        datagram_writer_ref.set_value(None);
+        if let Some(transport) = transport_ref.try_update_value(Option::take).flatten() {
+            transport.close();
+            set_ready_state.set(ConnectionReadyState::Closing);
+        }
+    };
 
-    let datagrams = Signal::derive({
-        let transport = Rc::clone(&transport);
-        let datagrams_reader_initialized = Rc::clone(&datagrams_reader_initialized);
+    let send_datagram = {
+        let on_error = Rc::clone(&on_error);
 
-        move || {
-            let transport = Rc::clone(&transport);
+        move |value: &T| {
+            if ready_state.get_untracked() != ConnectionReadyState::Open {
+                return;
+            }
 
-            lazy_initialize_u8_reader(
-                ready_state,
-                Rc::clone(&datagrams_reader_initialized),
-                move || {
+            let bytes = match C::encode(value) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    on_error(CodecError::Encode(err).into());
+                    return;
+                }
+            };
+
+            if datagram_writer_ref.with_value(Option::is_none) {
+                let writer = transport_ref.with_value(|transport| {
                     transport
-                        .borrow()
                         .as_ref()
-                        .expect("transport should be set a this point")
-                        .datagrams()
-                        .readable()
-                },
-                set_datagrams,
-                || {},
-            );
+                        .and_then(|transport| transport.datagrams().writable().get_writer().ok())
+                });
+                datagram_writer_ref.set_value(writer);
+            }
 
-            datagrams_signal.get()
+            datagram_writer_ref.with_value(|writer| {
+                if let Some(writer) = writer {
+                    let _ = writer.write_with_chunk(&js_sys::Uint8Array::from(bytes.as_slice()));
+                }
+            });
         }
-    });
-
-    {
-        let unmounted = Rc::clone(&unmounted);
-
-        on_cleanup(move || {
-            unmounted.set(true);
-            close();
-        });
-    }
+    };
 
     if immediate {
         open();
     }
 
+    on_cleanup(move || {
+        unmounted.set(true);
+        close();
+    });
+
     UseWebTransportReturn {
-        transport,
-        ready_state,
-        datagrams,
-        datagrams_writer,
+        ready_state: ready_state.into(),
+        datagram: datagram.into(),
+        reconnect_attempts: reconnect_attempts.into(),
+        reconnect_countdown: reconnect_countdown.seconds(),
+        open,
+        close,
+        send_datagram,
+        transport: transport_ref,
     }
 }
 
-fn get_or_create_datagrams_writer(
-    datagrams_writer: Rc<RefCell<Option<web_sys::WritableStreamDefaultWriter>>>,
-    transport: &web_sys::WebTransport,
-) -> web_sys::WritableStreamDefaultWriter {
-    let writer = datagrams_writer.borrow().clone();
-
-    if let Some(writer) = writer {
-        writer
-    } else {
-        let writer = transport
-            .datagrams()
-            .writable()
-            .get_writer()
-            .expect("should be able to get the writer");
-        datagrams_writer.replace(Some(writer.clone()));
-        writer
+/// Runs `f` with `owner` so the signals of streams are disposed together with the hook.
+fn with_owner_or_detached<R>(owner: Option<Owner>, f: impl FnOnce() -> R) -> R {
+    match owner {
+        Some(owner) => with_owner(owner, f),
+        None => f(),
     }
 }
 
-fn lazy_initialize_u8_reader(
-    ready_state: Signal<ConnectionReadyState>,
-    initialized: Rc<Cell<bool>>,
-    get_readable_stream: impl Fn() -> web_sys::ReadableStream,
-    set_signal: WriteSignal<Option<Vec<u8>>>,
-    on_done: impl Fn() + 'static,
+/// Calls `on_chunk` for every chunk read from `stream` and `on_done` when it has ended or
+/// failed.
+#[cfg(not(feature = "ssr"))]
+fn read_stream(
+    stream: web_sys::ReadableStream,
+    on_chunk: impl Fn(JsValue) + 'static,
+    on_done: impl FnOnce() + 'static,
 ) {
-    lazy_initialize_reader(
-        ready_state,
-        initialized,
-        get_readable_stream,
-        move |value| {
-            let value: js_sys::Uint8Array = value.into();
-            set_signal.set(Some(value.to_vec()));
-        },
-        on_done,
-    );
-}
+    let reader = stream
+        .get_reader()
+        .unchecked_into::<web_sys::ReadableStreamDefaultReader>();
 
-fn lazy_initialize_reader(
-    ready_state: Signal<ConnectionReadyState>,
-    initialized: Rc<Cell<bool>>,
-    get_readable_stream: impl Fn() -> web_sys::ReadableStream,
-    on_value: impl Fn(JsValue) + 'static,
-    on_done: impl Fn() + 'static,
-) {
-    if ready_state.get() == ConnectionReadyState::Open {
-        if !initialized.get() {
-            initialized.set(true);
+    spawn_local(async move {
+        while let Ok(result) = js_fut!(reader.read()).await {
+            let done = js!(result["done"])
+                .ok()
+                .and_then(|done| done.as_bool())
+                .unwrap_or(true);
+
+            if done {
+                break;
+            }
 
-            listen_to_stream(get_readable_stream(), on_value, move || {
-                initialized.set(false);
-                on_done();
-            });
+            if let Ok(chunk) = js!(result["value"]) {
+                on_chunk(chunk);
+            }
         }
-    }
+
+        on_done();
+    });
 }
 
-fn listen_to_stream(
-    readable_stream: web_sys::ReadableStream,
-    on_value: impl Fn(JsValue) + 'static,
-    on_done: impl Fn() + 'static,
-) {
-    let reader: web_sys::ReadableStreamDefaultReader =
-        readable_stream.get_reader().unchecked_into();
+/// Prefixes `message` with its length as 4 byte big endian unsigned integer.
+fn frame(message: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(message.len() + 4);
+    framed.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    framed.extend_from_slice(message);
+    framed
+}
 
-    spawn_local(async move {
-        loop {
-            let result = js_fut!(reader.read()).await;
-            match result {
-                Ok(result) => {
-                    let done = js!(result["done"])
-                        .expect("done should always be there")
-                        .as_bool()
-                        .unwrap_or(true);
-
-                    if done {
-                        // TODO : close connection?
-                        break;
-                    }
+/// Reassembles the messages framed by [`frame`] from the chunks of a stream.
+#[derive(Default)]
+struct FrameDecoder {
+    buffer: Vec<u8>,
+}
 
-                    let value = js!(result["value"]).expect("if not done there should be a value");
+impl FrameDecoder {
+    /// Adds a chunk and returns the messages that are complete now.
+    fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        self.buffer.extend_from_slice(chunk);
 
-                    on_value(value);
-                }
-                Err(..) => {
-                    // TODO : error handling?
-                    break;
-                }
+        let mut messages = vec![];
+
+        while self.buffer.len() >= 4 {
+            let len = u32::from_be_bytes([
+                self.buffer[0],
+                self.buffer[1],
+                self.buffer[2],
+                self.buffer[3],
+            ]) as usize;
+
+            if self.buffer.len() < 4 + len {
+                break;
             }
+
+            messages.push(self.buffer[4..4 + len].to_vec());
+            self.buffer.drain(..4 + len);
         }
 
-        on_done();
-    });
+        messages
+    }
 }
 
 /// Options for [`use_webtransport_with_options`].
 #[derive(DefaultBuilder)]
-pub struct UseWebTransportOptions {
-    /// Callback when `WebTransport` is ready.
+pub struct UseWebTransportOptions<E, D> {
+    /// Callback when the connection is ready.
     on_open: Rc<dyn Fn()>,
 
     /// Error callback.
-    // TODO : ? on_error: Rc<dyn Fn(WebTransportError)>,
+    #[builder(skip)]
+    on_error: Rc<dyn Fn(UseWebTransportError<E, D>)>,
 
-    /// Callback when `WebTransport` is closed.
+    /// Callback when the connection is closed.
     on_close: Rc<dyn Fn()>,
 
-    /// Callback when the server opens a one-way stream.
+    /// Callback when the server opens a unidirectional stream.
     on_receive_stream: Rc<dyn Fn(ReceiveStream)>,
 
     /// Callback when the server opens a bidirectional stream.
     on_bidir_stream: Rc<dyn Fn(BidirStream)>,
 
-    /// Retry times. Defaults to 3.
-    reconnect_limit: u64,
-
-    /// Retry interval in ms. Defaults to 3000.
-    reconnect_interval: u64,
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// Defaults to at most 3 attempts 3 seconds apart. See [`ReconnectPolicy`].
+    #[builder(skip)]
+    reconnect_policy: Rc<dyn ReconnectPolicy>,
 
-    /// If `true` the `WebSocket` connection will immediately be opened when calling this function.
+    /// If `true` the connection will immediately be opened when calling this function.
     /// If `false` you have to manually call the `open` function.
     /// Defaults to `true`.
     immediate: bool,
 }
 
-impl Default for UseWebTransportOptions {
+impl<E, D> UseWebTransportOptions<E, D> {
+    /// When and how often to try to reconnect after the connection has been lost.
+    /// See [`ReconnectPolicy`].
+    pub fn reconnect_policy(self, policy: impl ReconnectPolicy + 'static) -> Self {
+        Self {
+            reconnect_policy: Rc::new(policy),
+            ..self
+        }
+    }
+
+    /// Error callback.
+    pub fn on_error<F>(self, handler: F) -> Self
+    where
+        F: Fn(UseWebTransportError<E, D>) + 'static,
+    {
+        Self {
+            on_error: Rc::new(handler),
+            ..self
+        }
+    }
+}
+
+impl<E, D> Default for UseWebTransportOptions<E, D> {
     fn default() -> Self {
         Self {
             on_open: Rc::new(|| {}),
-            // on_error: Rc::new(|_| {}),
+            on_error: Rc::new(|_| {}),
             on_close: Rc::new(|| {}),
             on_receive_stream: Rc::new(|_| {}),
             on_bidir_stream: Rc::new(|_| {}),
-            reconnect_limit: 3,
-            reconnect_interval: 3000,
+            reconnect_policy: Rc::new(BackoffReconnectPolicy::default()),
             immediate: true,
         }
     }
 }
 
-/// Wether the stream is open or closed
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub enum StreamState {
-    Open,
-    Closed,
+/// Return type of [`use_webtransport`].
+pub struct UseWebTransportReturn<T, OpenFn, CloseFn, SendFn>
+where
+    T: 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    /// The current state of the connection.
+    pub ready_state: Signal<ConnectionReadyState>,
+    /// Latest datagram received.
+    pub datagram: Signal<Option<T>>,
+    /// Number of reconnection attempts since the connection was lost. Reset to `0` when the
+    /// connection is opened.
+    pub reconnect_attempts: Signal<u64>,
+    /// Seconds until the next reconnection attempt or `None` if no attempt is scheduled.
+    pub reconnect_countdown: Signal<Option<u64>>,
+    /// Opens the connection
+    pub open: OpenFn,
+    /// Closes the connection
+    pub close: CloseFn,
+    /// Sends a datagram. Datagrams are unreliable and are dropped if the connection isn't open.
+    pub send_datagram: SendFn,
+
+    transport: StoredValue<Option<web_sys::WebTransport>>,
 }
 
-#[async_trait(?Send)]
-/// Trait to close a stream
-pub trait CloseableStream {
-    /// Getter for the stream state (open/closed)
-    fn state(&self) -> Signal<StreamState>;
-
-    /// Close the stream ignoring any potential errors
-    fn close(&self);
-
-    /// Close the stream asynchronously with a result providing potential errors
-    async fn close_async(&self) -> Result<(), WebTransportError>;
+impl<T, OpenFn, CloseFn, SendFn> Clone for UseWebTransportReturn<T, OpenFn, CloseFn, SendFn>
+where
+    T: 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ready_state: self.ready_state,
+            datagram: self.datagram,
+            reconnect_attempts: self.reconnect_attempts,
+            reconnect_countdown: self.reconnect_countdown,
+            open: self.open.clone(),
+            close: self.close.clone(),
+            send_datagram: self.send_datagram.clone(),
+            transport: self.transport,
+        }
+    }
 }
 
-#[async_trait(?Send)]
-/// Trait to send data in a stream
-pub trait SendableStream: CloseableStream {
-    /// Getter for the stream writer
-    fn writer(&self) -> &web_sys::WritableStreamDefaultWriter;
-
-    /// Send data in the form of bytes ignoring potential errors
-    fn send_bytes(&self, data: &[u8]) {
-        if self.state().get() == StreamState::Open {
-            let arr = js_sys::Uint8Array::from(data);
-            let _ = self.writer().write_with_chunk(&arr);
-        }
+impl<T, OpenFn, CloseFn, SendFn> UseWebTransportReturn<T, OpenFn, CloseFn, SendFn>
+where
+    T: 'static,
+    OpenFn: Fn() + Clone + 'static,
+    CloseFn: Fn() + Clone + 'static,
+    SendFn: Fn(&T) + Clone + 'static,
+{
+    /// The underlying `WebTransport` if connected
+    pub fn transport(&self) -> Option<web_sys::WebTransport> {
+        self.transport.get_value()
     }
 
-    /// Send data in the form of bytes asynchronously with a result providing potential errors
-    async fn send_bytes_async(&self, data: &[u8]) -> Result<(), SendError> {
-        if self.state().get() != StreamState::Open {
-            return Err(SendError::StreamNotOpen);
-        }
+    /// Opens a unidirectional stream to the server
+    pub fn open_send_stream(&self) -> impl Future<Output = Result<SendStream, WebTransportError>> {
+        let transport = self.transport.get_value();
 
-        let arr = js_sys::Uint8Array::from(data);
-        let _ = js_fut!(self.writer().write_with_chunk(&arr))
-            .await
-            .map_err(|e| SendError::FailedToWrite(e));
+        async move {
+            let transport = transport.ok_or(WebTransportError::NotConnected)?;
 
-        Ok(())
-    }
+            let stream = js_fut!(transport.create_unidirectional_stream())
+                .await
+                .map_err(WebTransportError::FailedToOpenStream)?;
 
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Send data in the form of a serializable object ignoring potential errors
-    /// Requires the feature `msgpack` or `bincode`
-    fn send<T: Serialize>(&self, data: &T) {
-        self.send_bytes(
-            to_vec(data)
-                .expect("Serialization should not fail")
-                .as_slice(),
-        );
+            SendStream::new(stream.unchecked_into())
+        }
     }
 
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Send data in the form of a serializable object asynchronously with a result providing potential errors
-    /// Requires the feature `msgpack` or `bincode`
-    async fn send_async<T: Serialize>(&self, data: &T) -> Result<(), SendError> {
-        let serialized = to_vec(data)?;
-        self.send_bytes_async(&serialized).await
-    }
-}
+    /// Opens a bidirectional stream to the server
+    pub fn open_bidir_stream(
+        &self,
+    ) -> impl Future<Output = Result<BidirStream, WebTransportError>> {
+        let transport = self.transport.get_value();
+        let owner = Owner::current();
 
-#[async_trait(?Send)]
-/// Trait to receive data in a stream
-pub trait ReceivableStream: CloseableStream {
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Receive data in the form of a serializable object ignoring potential errors
-    fn receive<T: for<'a> Deserialize<'a>>(&self) -> Signal<Option<T>>;
+        async move {
+            let transport = transport.ok_or(WebTransportError::NotConnected)?;
 
-    #[cfg(any(feature = "msgpack", feature = "bincode"))]
-    /// Receive data in the form of a serializable object asynchronously with a result providing potential errors
-    fn try_receive<T: for<'a> Deserialize<'a>>(&self) -> Signal<Option<Result<T, ReceiveError>>>;
-}
+            let stream = js_fut!(transport.create_bidirectional_stream())
+                .await
+                .map_err(WebTransportError::FailedToOpenStream)?;
 
-#[derive(Clone, Debug)]
-/// Stream for sending data
-pub struct SendStream {
-    writer: web_sys::WritableStreamDefaultWriter,
-    state: Signal<StreamState>,
-    set_state: WriteSignal<StreamState>,
+            with_owner_or_detached(owner, || BidirStream::new(stream.unchecked_into()))
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
-/// Stream for receiving data
-#[allow(dead_code)]
-pub struct ReceiveStream {
-    pub bytes: Signal<Option<Vec<u8>>>,
-    state: Signal<StreamState>,
-    set_state: WriteSignal<StreamState>,
+/// Whether a stream is open or closed
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum StreamState {
+    Open,
+    Closed,
 }
 
-// TODO : implement ReceiveStream
-
+/// Stream for sending messages to the other side.
 #[derive(Clone, Debug)]
-/// Bidirectional stream for sending and receiving data
-pub struct BidirStream {
+pub struct SendStream {
     writer: web_sys::WritableStreamDefaultWriter,
-    pub bytes: Signal<Option<Vec<u8>>>,
-    state: Signal<StreamState>,
     set_state: WriteSignal<StreamState>,
-}
 
-macro_rules! impl_receivable_stream {
-    ($ty:ty) => {
-        impl BidirStream {
-            #[cfg(any(feature = "msgpack", feature = "bincode"))]
-            pub fn receive<T: for<'a> Deserialize<'a>>(&self) -> Signal<Option<T>> {
-                let bytes = self.bytes;
-
-                Signal::derive(move || {
-                    if self.state.get() != StreamState::Open {
-                        None
-                    } else {
-                        bytes
-                            .get()
-                            .and_then(|bytes| from_slice(bytes.as_slice()).ok())
-                    }
-                })
-            }
-
-            #[cfg(any(feature = "msgpack", feature = "bincode"))]
-            pub fn try_receive<T: for<'a> Deserialize<'a>>(
-                &self,
-            ) -> Signal<Option<Result<T, ReceiveError>>> {
-                let bytes = self.bytes;
-
-                Signal::derive(move || {
-                    if self.state.get() != StreamState::Open {
-                        None
-                    } else {
-                        bytes.get().map(|bytes| Ok(from_slice(bytes.as_slice())?))
-                    }
-                })
-            }
-        }
-    };
+    /// Whether the stream is open or closed
+    pub state: Signal<StreamState>,
 }
 
-impl_receivable_stream!(ReceiveStream);
-impl_receivable_stream!(BidirStream);
-
-macro_rules! impl_sendable_stream {
-    ($ty:ty) => {
-        #[async_trait(?Send)]
-        impl SendableStream for $ty {
-            #[inline(always)]
-            fn writer(&self) -> &web_sys::WritableStreamDefaultWriter {
-                &self.writer
-            }
-        }
-    };
-}
-
-impl_sendable_stream!(SendStream);
-impl_sendable_stream!(BidirStream);
+impl SendStream {
+    fn new(stream: web_sys::WritableStream) -> Result<Self, WebTransportError> {
+        let writer = stream
+            .get_writer()
+            .map_err(WebTransportError::FailedToOpenWriter)?;
 
-macro_rules! impl_closable_stream {
-    ($ty:ty) => {
-        #[async_trait(?Send)]
-        impl CloseableStream for $ty {
-            #[inline(always)]
-            fn state(&self) -> Signal<StreamState> {
-                self.state
-            }
+        let (state, set_state) = create_signal(StreamState::Open);
 
-            #[inline(always)]
-            fn close(&self) {
-                spawn_local(async {
-                    self.close_async().await.ok();
-                })
-            }
+        Ok(Self {
+            writer,
+            set_state,
+            state: state.into(),
+        })
+    }
 
-            async fn close_async(&self) -> Result<(), WebTransportError> {
-                let _ = js_fut!(self.writer.close()).await.map_err(|e| {
-                    let error = WebTransportError::OnCloseWriter(e);
-                    self.set_state.set(StreamState::Closed);
-                    error
-                })?;
+    /// Sends `data` as one message. Does nothing if the stream is closed.
+    pub fn send_bytes(&self, data: &[u8]) {
+        if self.state.get_untracked() == StreamState::Open {
+            let chunk = js_sys::Uint8Array::from(frame(data).as_slice());
+            let _ = self.writer.write_with_chunk(&chunk);
+        }
+    }
 
-                self.set_state.set(StreamState::Closed);
+    /// Encodes `value` with the codec `C` and sends it as one message. Does nothing if the
+    /// stream is closed.
+    pub fn send<T, C>(&self, value: &T) -> Result<(), C::Error>
+    where
+        C: Encoder<T, Encoded = Vec<u8>>,
+    {
+        self.send_bytes(&C::encode(value)?);
+        Ok(())
+    }
 
-                Ok(())
-            }
+    /// Closes the stream after all messages have been sent.
+    pub fn close(&self) {
+        if self.state.get_untracked() == StreamState::Open {
+            self.set_state.set(StreamState::Closed);
+            let _ = self.writer.close();
         }
-    };
+    }
 }
 
-impl_closable_stream!(SendStream);
-impl_closable_stream!(BidirStream);
-
-/// Return type of [`use_webtransport`].
+/// Stream for receiving messages from the other side.
 #[derive(Clone, Debug)]
-pub struct UseWebTransportReturn {
-    transport: Rc<RefCell<Option<web_sys::WebTransport>>>,
-    datagrams_writer: Rc<RefCell<Option<web_sys::WritableStreamDefaultWriter>>>,
-
-    /// The current state of the `WebTransport` connection.
-    pub ready_state: Signal<ConnectionReadyState>,
+pub struct ReceiveStream {
+    /// Latest message received as bytes
+    pub bytes: Signal<Option<Vec<u8>>>,
 
-    /// Latest datagrams message received
-    pub datagrams: Signal<Option<Vec<u8>>>,
+    /// Whether the stream is open or closed. It's closed when the other side has closed it.
+    pub state: Signal<StreamState>,
 }
 
-impl UseWebTransportReturn {
-    /// Access to the underlying `WebTransport`
-    pub async fn transport(&self) -> Option<web_sys::WebTransport> {
-        self.transport.borrow().clone()
-    }
+impl ReceiveStream {
+    fn new(stream: web_sys::ReadableStream) -> Self {
+        let (bytes, set_bytes) = create_signal(None::<Vec<u8>>);
+        let (state, set_state) = create_signal(StreamState::Open);
 
-    /// Sends binary data through the datagrams stream
-    pub fn send_datagrams(&self, data: &[u8]) {
-        if let Some(transport) = self.transport.borrow().as_ref() {
-            let writer =
-                get_or_create_datagrams_writer(Rc::clone(&self.datagrams_writer), transport);
+        #[cfg(not(feature = "ssr"))]
+        {
+            let decoder = RefCell::new(FrameDecoder::default());
 
-            let arr = js_sys::Uint8Array::from(data);
-            let _ = writer.write_with_chunk(&arr);
-        }
-    }
+            read_stream(
+                stream,
+                move |chunk| {
+                    let chunk = js_sys::Uint8Array::new(&chunk).to_vec();
 
-    // TODO : send_datagrams_async
+                    for message in decoder.borrow_mut().push(&chunk) {
+                        set_bytes.try_set(Some(message));
+                    }
+                },
+                move || {
+                    set_state.try_set(StreamState::Closed);
+                },
+            );
+        }
 
-    /// Open a unidirectional send stream
-    pub async fn open_send_stream(&self) -> Result<SendStream, WebTransportError> {
-        if let Some(transport) = self.transport.borrow().as_ref() {
-            let result = js_fut!(transport.create_unidirectional_stream())
-                .await
-                .map_err(|e| WebTransportError::FailedToOpenStream(e))?;
-            let stream: web_sys::WritableStream = result.unchecked_into();
-            let writer = stream
-                .get_writer()
-                .map_err(|e| WebTransportError::FailedToOpenWriter(e))?;
-
-            let (state, set_state) = create_signal(StreamState::Open);
-
-            Ok(SendStream {
-                writer,
-                state: state.into(),
-                set_state,
-            })
-        } else {
-            Err(WebTransportError::NotConnected)
+        Self {
+            bytes: bytes.into(),
+            state: state.into(),
         }
     }
 
-    /// Open a bidirectional stream
-    pub async fn open_bidir_stream(&self) -> Result<BidirStream, WebTransportError> {
-        if let Some(transport) = self.transport.borrow().as_ref() {
-            let result = js_fut!(transport.create_bidirectional_stream())
-                .await
-                .map_err(|e| WebTransportError::FailedToOpenStream(e))?;
-            let stream: web_sys::WebTransportBidirectionalStream = result.unchecked_into();
-            let ready_state = self.ready_state;
+    /// Returns a signal of the latest message decoded with the codec `C`. Messages that fail to
+    /// decode are skipped.
+    pub fn receive<T, C>(&self) -> Signal<Option<T>>
+    where
+        T: 'static,
+        C: Decoder<T, Encoded = [u8]>,
+    {
+        let bytes = self.bytes;
+        let (value, set_value) = create_signal(None::<T>);
+
+        let _ = watch(
+            move || bytes.get(),
+            move |bytes, _, _| {
+                if let Some(Ok(decoded)) = bytes.as_deref().map(C::decode) {
+                    set_value.set(Some(decoded));
+                }
+            },
+            true,
+        );
 
-            create_bidir_stream(stream, ready_state)
-        } else {
-            Err(WebTransportError::NotConnected)
-        }
+        value.into()
     }
 }
 
-fn create_state_and_bytes_signal(
-    stream: web_sys::ReadableStream,
-    ready_state: Signal<ConnectionReadyState>,
-) -> (
-    Signal<StreamState>,
-    WriteSignal<StreamState>,
-    Signal<Option<Vec<u8>>>,
-) {
-    let (state, set_state) = create_signal(StreamState::Open);
-
-    let bytes = Signal::derive({
-        let reader_initialized = Rc::new(Cell::new(false));
-        let (message_signal, set_message) = create_signal(None::<Vec<u8>>);
-
-        let stream = stream.clone();
-
-        move || {
-            let stream = stream.clone();
-
-            lazy_initialize_u8_reader(
-                ready_state,
-                Rc::clone(&reader_initialized),
-                move || stream.clone().unchecked_into(),
-                set_message,
-                move || {
-                    set_state.set(StreamState::Closed);
-                },
-            );
-
-            message_signal.get()
-        }
-    });
+/// Bidirectional stream for sending and receiving messages.
+#[derive(Clone, Debug)]
+pub struct BidirStream {
+    /// Sending half of the stream
+    pub send_stream: SendStream,
 
-    (state.into(), set_state, bytes)
+    /// Receiving half of the stream
+    pub receive_stream: ReceiveStream,
 }
 
-fn create_bidir_stream(
-    stream: WebTransportBidirectionalStream,
-    ready_state: Signal<ConnectionReadyState>,
-) -> Result<BidirStream, WebTransportError> {
-    let writer = stream
-        .writable()
-        .get_writer()
-        .map_err(|e| WebTransportError::FailedToOpenWriter(e))?;
-
-    let (state, set_state, bytes) =
-        create_state_and_bytes_signal(stream.readable().unchecked_into(), ready_state);
-
-    let bidir_stream = BidirStream {
-        writer,
-        bytes,
-        state,
-        set_state,
-    };
+impl BidirStream {
+    fn new(stream: web_sys::WebTransportBidirectionalStream) -> Result<Self, WebTransportError> {
+        Ok(Self {
+            send_stream: SendStream::new(stream.writable().unchecked_into())?,
+            receive_stream: ReceiveStream::new(stream.readable().unchecked_into()),
+        })
+    }
+}
 
-    Ok(bidir_stream)
+/// Error type of [`UseWebTransportOptions::on_error`]
+#[derive(Error, Debug)]
+pub enum UseWebTransportError<E, D> {
+    #[error("WebTransport connection error: {0:?}")]
+    Connection(JsValue),
+    #[error("WebTransport codec error: {0}")]
+    Codec(#[from] CodecError<E, D>),
 }
 
-/// Error enum for [`UseWebTransportOptions::on_error`]
-#[derive(Debug, Clone, Error)]
+/// Error when opening a stream of [`use_webtransport`]
+#[derive(Error, Debug, Clone)]
 pub enum WebTransportError {
     #[error("The `WebTransport` is not connected yet. Call `open` first.")]
     NotConnected,
@@ -752,41 +838,4 @@ pub enum WebTransportError {
     FailedToOpenStream(JsValue),
     #[error("Failed to open writer: {0:?}")]
     FailedToOpenWriter(JsValue),
-    #[error("Failed to open reader: {0:?}")]
-    FailedToOpenReader(JsValue),
-    #[error("Failed to read from stream: {0:?}")]
-    FailedToRead(JsValue),
-    #[error("Failed to close writer: {0:?}")]
-    OnCloseWriter(JsValue),
-    #[error("Failed to close reader: {0:?}")]
-    OnCloseReader(JsValue),
-}
-
-/// Error enum for [`SendStream::send`]
-#[derive(Error, Debug)]
-pub enum SendError {
-    #[error("Stream is not open")]
-    StreamNotOpen,
-
-    #[error("Failed to write to stream")]
-    FailedToWrite(JsValue),
-
-    #[cfg(feature = "bincode")]
-    #[error("Serialization failed: {0}")]
-    SerializationFailed(#[from] bincode::Error),
-
-    #[cfg(feature = "msgpack")]
-    #[error("Serialization failed: {0}")]
-    SerializationFailed(#[from] rmp_serde::encode::Error),
-}
-
-#[derive(Error, Debug)]
-pub enum ReceiveError {
-    #[cfg(feature = "bincode")]
-    #[error("Serialization failed: {0}")]
-    DeserializationFailed(#[from] bincode::Error),
-
-    #[cfg(feature = "msgpack")]
-    #[error("Serialization failed: {0}")]
-    DeserializationFailed(#[from] rmp_serde::decode::Error),
 }
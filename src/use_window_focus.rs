@@ -1,6 +1,6 @@
 #![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
 
-use crate::use_event_listener;
+use crate::{after_hydration, use_event_listener};
 use cfg_if::cfg_if;
 use leptos::ev::{blur, focus};
 use leptos::*;
@@ -28,17 +28,16 @@ use leptos::*;
 ///
 /// ## Server-Side Rendering
 ///
-/// On the server this returns a `Signal` that is always `true`.
+/// On the server this returns a `Signal` that is always `true`. While hydrating it's `true` as well
+/// and the actual focus is read once the hydration has finished.
 pub fn use_window_focus() -> Signal<bool> {
-    cfg_if! { if #[cfg(feature = "ssr")] {
-        let initial_focus = true;
-    } else {
-        let initial_focus = document().has_focus().unwrap_or_default();
-    }}
-
-    let (focused, set_focused) = create_signal(initial_focus);
+    let (focused, set_focused) = create_signal(true);
 
     cfg_if! { if #[cfg(not(feature = "ssr"))] {
+        after_hydration(move || {
+            let _ = set_focused.try_set(document().has_focus().unwrap_or_default());
+        });
+
         let _ = use_event_listener(window(), blur, move |_| set_focused.set(false));
         let _ = use_event_listener(window(), focus, move |_| set_focused.set(true));
     }}
@@ -1,7 +1,8 @@
 #![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
 
-use crate::{use_event_listener_with_options, use_window, UseEventListenerOptions};
-use cfg_if::cfg_if;
+use crate::{
+    after_hydration, use_event_listener_with_options, use_window, UseEventListenerOptions,
+};
 use leptos::ev::scroll;
 use leptos::*;
 
@@ -27,25 +28,24 @@ use leptos::*;
 ///
 /// ## Server-Side Rendering
 ///
-/// On the server this returns `Signal`s that are always `0.0`.
+/// On the server this returns `Signal`s that are always `0.0`. In the browser the scroll position
+/// is read once the hydration has finished so the first render matches the server.
 pub fn use_window_scroll() -> (Signal<f64>, Signal<f64>) {
-    cfg_if! { if #[cfg(feature = "ssr")] {
-        let initial_x = 0.0;
-        let initial_y = 0.0;
-    } else {
-        let initial_x = window().scroll_x().unwrap_or_default();
-        let initial_y = window().scroll_y().unwrap_or_default();
-    }}
-    let (x, set_x) = create_signal(initial_x);
-    let (y, set_y) = create_signal(initial_y);
+    let (x, set_x) = create_signal(0.0);
+    let (y, set_y) = create_signal(0.0);
+
+    let update = move || {
+        let _ = set_x.try_set(window().scroll_x().unwrap_or_default());
+        let _ = set_y.try_set(window().scroll_y().unwrap_or_default());
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    after_hydration(update);
 
     let _ = use_event_listener_with_options(
         use_window(),
         scroll,
-        move |_| {
-            set_x.set(window().scroll_x().unwrap_or_default());
-            set_y.set(window().scroll_y().unwrap_or_default());
-        },
+        move |_| update(),
         UseEventListenerOptions::default()
             .capture(false)
             .passive(true),
@@ -0,0 +1,116 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports))]
+
+use crate::core::Size;
+use crate::{
+    after_hydration, use_event_listener_with_options, use_window, UseEventListenerOptions,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::resize;
+use leptos::*;
+
+/// Reactive window size.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_use::{use_window_size, UseWindowSizeReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWindowSizeReturn { width, height } = use_window_size();
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the width and height are always `initial_size` which defaults to
+/// `Size { width: f64::INFINITY, height: f64::INFINITY }`. While hydrating they stay at
+/// `initial_size` as well and the real size is read after the hydration has finished.
+pub fn use_window_size() -> UseWindowSizeReturn {
+    use_window_size_with_options(UseWindowSizeOptions::default())
+}
+
+/// Version of [`use_window_size`] that takes a `UseWindowSizeOptions`. See [`use_window_size`] for how to use.
+pub fn use_window_size_with_options(options: UseWindowSizeOptions) -> UseWindowSizeReturn {
+    let UseWindowSizeOptions {
+        initial_size,
+        include_scrollbar,
+    } = options;
+
+    let (width, set_width) = create_signal(initial_size.width);
+    let (height, set_height) = create_signal(initial_size.height);
+
+    let update = move || {
+        if include_scrollbar {
+            let _ = set_width.try_set(
+                window()
+                    .inner_width()
+                    .ok()
+                    .and_then(|width| width.as_f64())
+                    .unwrap_or_default(),
+            );
+            let _ = set_height.try_set(
+                window()
+                    .inner_height()
+                    .ok()
+                    .and_then(|height| height.as_f64())
+                    .unwrap_or_default(),
+            );
+        } else if let Some(root) = document().document_element() {
+            let _ = set_width.try_set(root.client_width() as f64);
+            let _ = set_height.try_set(root.client_height() as f64);
+        }
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        after_hydration(update);
+
+        let _ = use_event_listener_with_options(
+            use_window(),
+            resize,
+            move |_| update(),
+            UseEventListenerOptions::default().passive(true),
+        );
+    }
+
+    UseWindowSizeReturn {
+        width: width.into(),
+        height: height.into(),
+    }
+}
+
+/// Options for [`use_window_size_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWindowSizeOptions {
+    /// The size that is returned on the server. Defaults to
+    /// `Size { width: f64::INFINITY, height: f64::INFINITY }`.
+    initial_size: Size,
+
+    /// If `true` the size includes the scrollbars (`window.innerWidth`), otherwise it doesn't
+    /// (`document.documentElement.clientWidth`). Defaults to `true`.
+    include_scrollbar: bool,
+}
+
+impl Default for UseWindowSizeOptions {
+    fn default() -> Self {
+        Self {
+            initial_size: Size {
+                width: f64::INFINITY,
+                height: f64::INFINITY,
+            },
+            include_scrollbar: true,
+        }
+    }
+}
+
+/// Return type of [`use_window_size`].
+pub struct UseWindowSizeReturn {
+    /// The width of the window.
+    pub width: Signal<f64>,
+    /// The height of the window.
+    pub height: Signal<f64>,
+}
@@ -0,0 +1,733 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use codee::{
+    CodecError, Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary,
+};
+use default_struct_builder::DefaultBuilder;
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+use web_sys::{ErrorEvent, MessageEvent, Worker, WorkerOptions, WorkerType};
+
+/// Runs a Rust function in a pool of [Web Workers](https://developer.mozilla.org/en-US/docs/Web/API/Worker)
+/// and awaits its result.
+///
+/// The function is compiled into a separate worker binary that registers it with
+/// [`register_worker_fn`]. The input of type `I` and the output of type `O` are (en)coded with
+/// the given codec on both sides. You can use any of the codecs, string or binary.
+///
+/// ## Usage
+///
+/// The worker binary, for example `src/bin/fib_worker.rs`:
+///
+/// ```no_run
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::register_worker_fn;
+/// #
+/// fn fib(n: u64) -> u64 {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// }
+///
+/// fn main() {
+///     register_worker_fn::<u64, u64, FromToStringCodec, _>(fib);
+/// }
+/// ```
+///
+/// With Trunk it can be built next to your app with
+/// `<link data-trunk rel="rust" href="Cargo.toml" data-bin="fib_worker" data-type="worker" />`
+/// in your `index.html`. Then in your app:
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::string::FromToStringCodec;
+/// # use leptos_use::{use_worker_fn, UseWorkerFnReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWorkerFnReturn { run, status, .. } =
+///     use_worker_fn::<u64, u64, FromToStringCodec>("./fib_worker.js");
+///
+/// let (result, set_result) = create_signal(None::<u64>);
+///
+/// let calculate = move |_| {
+///     let call = run(&40);
+///
+///     spawn_local(async move {
+///         if let Ok(fib) = call.await {
+///             set_result.set(Some(fib));
+///         }
+///     });
+/// };
+///
+/// view! {
+///     <button on:click=calculate>"Calculate"</button>
+///     <p>{move || format!("{:?}", status.get())}</p>
+///     <p>{result}</p>
+/// }
+/// # }
+/// ```
+///
+/// ### Pool
+///
+/// By default there is a single worker and the calls are run one after another. Set the option
+/// `pool_size` to run several calls in parallel. Workers are created when they are needed and
+/// calls that find no idle worker wait for one.
+///
+/// ### Termination and Restart
+///
+/// If a worker crashes, for example because the function panicked, or if a call takes longer
+/// than the option `timeout`, that worker is terminated and the call fails. The next call gets a
+/// fresh worker.
+///
+/// `terminate` stops all workers and fails all running and waiting calls with
+/// [`UseWorkerFnError::Terminated`]. Later calls fail right away. `restart` stops all workers
+/// in the same way but allows new calls afterwards.
+///
+/// ## Server-Side Rendering
+///
+/// On the server no worker is created. `status` is always `WorkerFnStatus::Pending` and every
+/// call fails with [`UseWorkerFnError::Terminated`].
+#[allow(clippy::type_complexity)]
+pub fn use_worker_fn<I, O, C>(
+    url: &str,
+) -> UseWorkerFnReturn<
+    I,
+    O,
+    HybridCoderError<<C as Encoder<I>>::Error>,
+    HybridCoderError<<C as Decoder<O>>::Error>,
+    impl Fn(
+            &I,
+        ) -> WorkerFnCall<
+            O,
+            HybridCoderError<<C as Encoder<I>>::Error>,
+            HybridCoderError<<C as Decoder<O>>::Error>,
+        > + Clone
+        + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    I: 'static,
+    O: 'static,
+    C: Encoder<I> + Decoder<O>,
+    C: IsBinary<O, <C as Decoder<O>>::Encoded>,
+    C: HybridDecoder<O, <C as Decoder<O>>::Encoded, Error = <C as Decoder<O>>::Error>,
+    C: HybridEncoder<I, <C as Encoder<I>>::Encoded, Error = <C as Encoder<I>>::Error>,
+{
+    use_worker_fn_with_options::<I, O, C>(url, UseWorkerFnOptions::default())
+}
+
+/// Version of [`use_worker_fn`] that takes a `UseWorkerFnOptions`. See [`use_worker_fn`] for how to use.
+#[allow(clippy::type_complexity)]
+pub fn use_worker_fn_with_options<I, O, C>(
+    url: &str,
+    options: UseWorkerFnOptions,
+) -> UseWorkerFnReturn<
+    I,
+    O,
+    HybridCoderError<<C as Encoder<I>>::Error>,
+    HybridCoderError<<C as Decoder<O>>::Error>,
+    impl Fn(
+            &I,
+        ) -> WorkerFnCall<
+            O,
+            HybridCoderError<<C as Encoder<I>>::Error>,
+            HybridCoderError<<C as Decoder<O>>::Error>,
+        > + Clone
+        + 'static,
+    impl Fn() + Clone + 'static,
+    impl Fn() + Clone + 'static,
+>
+where
+    I: 'static,
+    O: 'static,
+    C: Encoder<I> + Decoder<O>,
+    C: IsBinary<O, <C as Decoder<O>>::Encoded>,
+    C: HybridDecoder<O, <C as Decoder<O>>::Encoded, Error = <C as Decoder<O>>::Error>,
+    C: HybridEncoder<I, <C as Encoder<I>>::Encoded, Error = <C as Encoder<I>>::Error>,
+{
+    let UseWorkerFnOptions {
+        pool_size,
+        timeout,
+        worker_type,
+    } = options;
+
+    let (status, set_status) = create_signal(WorkerFnStatus::Pending);
+
+    let pool = Rc::new(WorkerFnPool {
+        url: url.to_string(),
+        worker_type,
+        pool_size: pool_size.max(1),
+        timeout,
+        decode: decode_output::<I, O, C>,
+        set_status,
+        state: RefCell::new(PoolState::default()),
+    });
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let pool = Rc::clone(&pool);
+        on_cleanup(move || pool.stop(true));
+    }
+
+    let run = {
+        let pool = Rc::clone(&pool);
+
+        move |input: &I| {
+            #[cfg(feature = "ssr")]
+            return WorkerFnCall::done(Err(UseWorkerFnError::Terminated));
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                let encoded = if C::is_binary() {
+                    C::encode_bin(input)
+                        .map(|val| JsValue::from(js_sys::Uint8Array::from(val.as_slice())))
+                } else {
+                    C::encode_str(input).map(|val| JsValue::from_str(&val))
+                };
+
+                match encoded {
+                    Ok(encoded) => pool.run(encoded),
+                    Err(err) => WorkerFnCall::done(Err(CodecError::Encode(err).into())),
+                }
+            }
+        }
+    };
+
+    let terminate = {
+        let pool = Rc::clone(&pool);
+        move || pool.stop(true)
+    };
+
+    let restart = move || pool.stop(false);
+
+    UseWorkerFnReturn {
+        run,
+        status: status.into(),
+        terminate,
+        restart,
+        _marker: PhantomData,
+    }
+}
+
+/// Registers `f` as the function that is run by [`use_worker_fn`].
+///
+/// Call this in the `main` function of the worker binary. Every message the worker receives is
+/// decoded into `I`, passed to `f` and the encoded output is sent back. Use the same codec as
+/// in the call of [`use_worker_fn`].
+pub fn register_worker_fn<I, O, C, F>(f: F)
+where
+    I: 'static,
+    O: 'static,
+    F: Fn(I) -> O + 'static,
+    C: Decoder<I> + Encoder<O>,
+    C: IsBinary<I, <C as Decoder<I>>::Encoded>,
+    C: HybridDecoder<I, <C as Decoder<I>>::Encoded, Error = <C as Decoder<I>>::Error>,
+    C: HybridEncoder<O, <C as Encoder<O>>::Encoded, Error = <C as Encoder<O>>::Error>,
+{
+    let scope = js_sys::global().unchecked_into::<web_sys::DedicatedWorkerGlobalScope>();
+    let reply_scope = scope.clone();
+
+    let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
+        let data = e.data();
+
+        let input = if let Some(bytes) = data.dyn_ref::<js_sys::Uint8Array>() {
+            C::decode_bin(bytes.to_vec().as_slice()).ok()
+        } else if let Some(txt) = data.as_string() {
+            C::decode_str(&txt).ok()
+        } else {
+            None
+        };
+
+        let output = input.ok_or("failed to decode the input").and_then(|input| {
+            let output = f(input);
+
+            let encoded = if C::is_binary() {
+                C::encode_bin(&output)
+                    .map(|val| JsValue::from(js_sys::Uint8Array::from(val.as_slice())))
+            } else {
+                C::encode_str(&output).map(|val| JsValue::from_str(&val))
+            };
+
+            encoded.map_err(|_| "failed to encode the output")
+        });
+
+        let response = js_sys::Object::new();
+        let _ = match output {
+            Ok(output) => js_sys::Reflect::set(&response, &RESPONSE_OK.into(), &output),
+            Err(message) => js_sys::Reflect::set(&response, &RESPONSE_ERR.into(), &message.into()),
+        };
+
+        let _ = reply_scope.post_message(&response);
+    }) as Box<dyn FnMut(MessageEvent)>);
+    scope.set_onmessage(Some(onmessage_closure.as_ref().unchecked_ref()));
+    onmessage_closure.forget();
+}
+
+const RESPONSE_OK: &str = "ok";
+const RESPONSE_ERR: &str = "err";
+
+#[allow(clippy::type_complexity)]
+fn decode_output<I, O, C>(
+    response: &JsValue,
+) -> Result<
+    O,
+    UseWorkerFnError<
+        HybridCoderError<<C as Encoder<I>>::Error>,
+        HybridCoderError<<C as Decoder<O>>::Error>,
+    >,
+>
+where
+    C: Encoder<I> + Decoder<O>,
+    C: HybridDecoder<O, <C as Decoder<O>>::Encoded, Error = <C as Decoder<O>>::Error>,
+{
+    let output = js_sys::Reflect::get(response, &RESPONSE_OK.into()).unwrap_or_default();
+
+    let decoded = if let Some(bytes) = output.dyn_ref::<js_sys::Uint8Array>() {
+        C::decode_bin(bytes.to_vec().as_slice())
+    } else if let Some(txt) = output.as_string() {
+        C::decode_str(&txt)
+    } else {
+        let message = js_sys::Reflect::get(response, &RESPONSE_ERR.into())
+            .ok()
+            .and_then(|message| message.as_string())
+            .unwrap_or_else(|| "unexpected response".to_string());
+
+        return Err(UseWorkerFnError::Worker(message));
+    };
+
+    decoded.map_err(|err| CodecError::Decode(err).into())
+}
+
+/// The workers of a [`use_worker_fn`] and the calls waiting for one.
+struct WorkerFnPool<O, E, D> {
+    url: String,
+    worker_type: WorkerType,
+    pool_size: usize,
+    timeout: Option<u64>,
+    decode: fn(&JsValue) -> Result<O, UseWorkerFnError<E, D>>,
+    set_status: WriteSignal<WorkerFnStatus>,
+    state: RefCell<PoolState<O, E, D>>,
+}
+
+struct PoolState<O, E, D> {
+    workers: Vec<PoolWorker<O, E, D>>,
+    queue: VecDeque<Job<O, E, D>>,
+    next_worker_id: u64,
+    terminated: bool,
+    // `None` until the first call has finished
+    last_succeeded: Option<bool>,
+}
+
+impl<O, E, D> Default for PoolState<O, E, D> {
+    fn default() -> Self {
+        Self {
+            workers: Vec::new(),
+            queue: VecDeque::new(),
+            next_worker_id: 0,
+            terminated: false,
+            last_succeeded: None,
+        }
+    }
+}
+
+struct PoolWorker<O, E, D> {
+    id: u64,
+    worker: Worker,
+    job: Option<Job<O, E, D>>,
+    timeout_handle: Option<TimeoutHandle>,
+}
+
+impl<O, E, D> PoolWorker<O, E, D> {
+    /// Terminates the worker and returns the call it was running.
+    fn stop(self) -> Option<Job<O, E, D>> {
+        self.worker.terminate();
+
+        if let Some(timeout_handle) = self.timeout_handle {
+            timeout_handle.clear();
+        }
+
+        self.job
+    }
+}
+
+struct Job<O, E, D> {
+    input: JsValue,
+    state: Rc<RefCell<CallState<O, E, D>>>,
+}
+
+impl<O, E, D> Job<O, E, D> {
+    fn complete(self, result: Result<O, UseWorkerFnError<E, D>>) {
+        let waker = {
+            let mut state = self.state.borrow_mut();
+            state.result = Some(result);
+            state.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<O, E, D> WorkerFnPool<O, E, D>
+where
+    O: 'static,
+    E: 'static,
+    D: 'static,
+{
+    fn run(self: &Rc<Self>, input: JsValue) -> WorkerFnCall<O, E, D> {
+        if self.state.borrow().terminated {
+            return WorkerFnCall::done(Err(UseWorkerFnError::Terminated));
+        }
+
+        let call = WorkerFnCall::pending();
+
+        self.state.borrow_mut().queue.push_back(Job {
+            input,
+            state: Rc::clone(&call.state),
+        });
+        self.dispatch();
+
+        call
+    }
+
+    fn spawn(self: &Rc<Self>, id: u64) -> Result<Worker, JsValue> {
+        let mut worker_options = WorkerOptions::new();
+        worker_options.type_(self.worker_type);
+
+        let worker = Worker::new_with_options(&self.url, &worker_options)?;
+
+        // onmessage handler
+        {
+            let pool = Rc::downgrade(self);
+
+            let onmessage_closure = Closure::wrap(Box::new(move |e: MessageEvent| {
+                if let Some(pool) = pool.upgrade() {
+                    pool.finish(id, e.data());
+                }
+            }) as Box<dyn FnMut(MessageEvent)>);
+            worker.set_onmessage(Some(onmessage_closure.as_ref().unchecked_ref()));
+            onmessage_closure.forget();
+        }
+
+        // onerror handler
+        {
+            let pool = Rc::downgrade(self);
+
+            let onerror_closure = Closure::wrap(Box::new(move |e: ErrorEvent| {
+                if let Some(pool) = pool.upgrade() {
+                    pool.fail_worker(id, UseWorkerFnError::Worker(e.message()));
+                }
+            }) as Box<dyn FnMut(ErrorEvent)>);
+            worker.set_onerror(Some(onerror_closure.as_ref().unchecked_ref()));
+            onerror_closure.forget();
+        }
+
+        Ok(worker)
+    }
+
+    /// Hands the waiting calls to idle workers and creates new workers if there is room.
+    fn dispatch(self: &Rc<Self>) {
+        loop {
+            let mut state = self.state.borrow_mut();
+
+            if state.queue.is_empty() {
+                break;
+            }
+
+            let index = match state.workers.iter().position(|worker| worker.job.is_none()) {
+                Some(index) => index,
+                None if state.workers.len() < self.pool_size => {
+                    let id = state.next_worker_id;
+                    state.next_worker_id += 1;
+
+                    match self.spawn(id) {
+                        Ok(worker) => {
+                            state.workers.push(PoolWorker {
+                                id,
+                                worker,
+                                job: None,
+                                timeout_handle: None,
+                            });
+                            state.workers.len() - 1
+                        }
+                        Err(err) => {
+                            if let Some(job) = state.queue.pop_front() {
+                                job.complete(Err(UseWorkerFnError::Create(err)));
+                            }
+                            state.last_succeeded = Some(false);
+                            continue;
+                        }
+                    }
+                }
+                None => break,
+            };
+
+            let Some(job) = state.queue.pop_front() else {
+                break;
+            };
+
+            let worker = &mut state.workers[index];
+
+            if let Err(err) = worker.worker.post_message(&job.input) {
+                job.complete(Err(UseWorkerFnError::Post(err)));
+                state.last_succeeded = Some(false);
+                continue;
+            }
+
+            if let Some(timeout) = self.timeout {
+                let pool = Rc::downgrade(self);
+                let id = worker.id;
+
+                worker.timeout_handle = set_timeout_with_handle(
+                    move || {
+                        if let Some(pool) = pool.upgrade() {
+                            pool.fail_worker(id, UseWorkerFnError::Timeout);
+                        }
+                    },
+                    Duration::from_millis(timeout),
+                )
+                .ok();
+            }
+
+            worker.job = Some(job);
+        }
+
+        self.update_status();
+    }
+
+    /// Completes the call of the worker `id` with its response.
+    fn finish(self: &Rc<Self>, id: u64, response: JsValue) {
+        let job = {
+            let mut state = self.state.borrow_mut();
+
+            let Some(worker) = state.workers.iter_mut().find(|worker| worker.id == id) else {
+                return;
+            };
+
+            if let Some(timeout_handle) = worker.timeout_handle.take() {
+                timeout_handle.clear();
+            }
+
+            worker.job.take()
+        };
+
+        if let Some(job) = job {
+            let result = (self.decode)(&response);
+            self.state.borrow_mut().last_succeeded = Some(result.is_ok());
+            job.complete(result);
+        }
+
+        self.dispatch();
+    }
+
+    /// Terminates the worker `id` and fails its call with `error`.
+    fn fail_worker(self: &Rc<Self>, id: u64, error: UseWorkerFnError<E, D>) {
+        let job = {
+            let mut state = self.state.borrow_mut();
+
+            let Some(index) = state.workers.iter().position(|worker| worker.id == id) else {
+                return;
+            };
+
+            let job = state.workers.remove(index).stop();
+            if job.is_some() {
+                state.last_succeeded = Some(false);
+            }
+
+            job
+        };
+
+        if let Some(job) = job {
+            job.complete(Err(error));
+        }
+
+        self.dispatch();
+    }
+
+    /// Terminates all workers and fails all calls. If `terminated` is `true` later calls fail
+    /// as well.
+    fn stop(&self, terminated: bool) {
+        let (workers, queue) = {
+            let mut state = self.state.borrow_mut();
+            state.terminated = terminated;
+            state.last_succeeded = None;
+
+            (
+                std::mem::take(&mut state.workers),
+                std::mem::take(&mut state.queue),
+            )
+        };
+
+        for job in workers
+            .into_iter()
+            .filter_map(PoolWorker::stop)
+            .chain(queue)
+        {
+            job.complete(Err(UseWorkerFnError::Terminated));
+        }
+
+        self.update_status();
+    }
+
+    fn update_status(&self) {
+        let status = {
+            let state = self.state.borrow();
+
+            if state.terminated {
+                WorkerFnStatus::Terminated
+            } else if !state.queue.is_empty()
+                || state.workers.iter().any(|worker| worker.job.is_some())
+            {
+                WorkerFnStatus::Running
+            } else {
+                match state.last_succeeded {
+                    None => WorkerFnStatus::Pending,
+                    Some(true) => WorkerFnStatus::Success,
+                    Some(false) => WorkerFnStatus::Error,
+                }
+            }
+        };
+
+        // the hook might have been disposed already
+        let _ = self.set_status.try_set(status);
+    }
+}
+
+struct CallState<O, E, D> {
+    result: Option<Result<O, UseWorkerFnError<E, D>>>,
+    waker: Option<Waker>,
+}
+
+/// A call of the function of [`use_worker_fn`]. Resolves with the output of the function or an
+/// error.
+///
+/// Dropping it doesn't stop the call. The result is just discarded.
+pub struct WorkerFnCall<O, E, D> {
+    state: Rc<RefCell<CallState<O, E, D>>>,
+}
+
+impl<O, E, D> WorkerFnCall<O, E, D> {
+    fn pending() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(CallState {
+                result: None,
+                waker: None,
+            })),
+        }
+    }
+
+    fn done(result: Result<O, UseWorkerFnError<E, D>>) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(CallState {
+                result: Some(result),
+                waker: None,
+            })),
+        }
+    }
+}
+
+impl<O, E, D> Future for WorkerFnCall<O, E, D> {
+    type Output = Result<O, UseWorkerFnError<E, D>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Options for [`use_worker_fn_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseWorkerFnOptions {
+    /// The maximum number of workers that run calls in parallel. Defaults to `1`.
+    pool_size: usize,
+
+    /// Time in ms after which a call fails and its worker is terminated.
+    /// Defaults to `None` which waits forever.
+    #[builder(into)]
+    timeout: Option<u64>,
+
+    /// How the worker script is loaded. Defaults to `WorkerType::Classic`.
+    worker_type: WorkerType,
+}
+
+impl Default for UseWorkerFnOptions {
+    fn default() -> Self {
+        Self {
+            pool_size: 1,
+            timeout: None,
+            worker_type: WorkerType::Classic,
+        }
+    }
+}
+
+/// The state of the calls of [`use_worker_fn`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerFnStatus {
+    /// No call has finished yet. This is always the case on the server.
+    Pending,
+    /// At least one call is running or waiting for a worker
+    Running,
+    /// The last call returned successfully
+    Success,
+    /// The last call failed
+    Error,
+    /// The workers have been terminated with `terminate`
+    Terminated,
+}
+
+/// Return type of [`use_worker_fn`].
+pub struct UseWorkerFnReturn<I, O, E, D, RunFn, TerminateFn, RestartFn>
+where
+    I: 'static,
+    O: 'static,
+    E: 'static,
+    D: 'static,
+    RunFn: Fn(&I) -> WorkerFnCall<O, E, D> + Clone + 'static,
+    TerminateFn: Fn() + Clone + 'static,
+    RestartFn: Fn() + Clone + 'static,
+{
+    /// Runs the function with the input in a worker. Await the returned call for the output.
+    pub run: RunFn,
+    /// The state of the calls
+    pub status: Signal<WorkerFnStatus>,
+    /// Terminates all workers and fails all running and waiting calls. Later calls fail as well.
+    pub terminate: TerminateFn,
+    /// Terminates all workers and fails all running and waiting calls. Later calls start new workers.
+    pub restart: RestartFn,
+
+    _marker: PhantomData<(I, O, E, D)>,
+}
+
+#[derive(Error, Debug)]
+pub enum UseWorkerFnError<E, D> {
+    #[error("failed to create worker: {0:?}")]
+    Create(JsValue),
+    #[error("failed to post message to worker: {0:?}")]
+    Post(JsValue),
+    #[error("error in worker: {0}")]
+    Worker(String),
+    #[error("worker call timed out")]
+    Timeout,
+    #[error("worker has been terminated")]
+    Terminated,
+    #[error("worker codec error: {0}")]
+    Codec(#[from] CodecError<E, D>),
+}
@@ -0,0 +1,482 @@
+#![cfg_attr(feature = "ssr", allow(unused_variables, unused_imports, dead_code))]
+
+use crate::core::{ElementMaybeSignal, Position};
+use crate::{use_event_listener, use_event_listener_with_options, UseEventListenerOptions};
+use default_struct_builder::DefaultBuilder;
+use leptos::ev::{pointercancel, pointerdown, pointermove, pointerup, wheel};
+use leptos::*;
+use std::collections::HashMap;
+use web_sys::{PointerEvent, WheelEvent};
+
+/// Zoom and pan state of a viewport like a canvas, an SVG or a map.
+///
+/// Scrolling the wheel over the `target` zooms around the cursor, dragging pans and pinching with
+/// two fingers zooms and pans at the same time. The resulting transform is available as a signal
+/// and as a CSS style for the content inside of the `target`.
+///
+/// ## Usage
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_zoom_pan_with_options, UseZoomPanOptions, UseZoomPanReturn};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let viewport = create_node_ref::<Div>();
+///
+/// let UseZoomPanReturn {
+///     style,
+///     reset,
+///     fit_to_content,
+///     ..
+/// } = use_zoom_pan_with_options(viewport, UseZoomPanOptions::default().min_scale(0.5).max_scale(4.0));
+///
+/// view! {
+///     <div node_ref=viewport style="overflow: hidden; touch-action: none; width: 600px; height: 400px">
+///         <div style=style>
+///             <svg width="1200" height="800">"..."</svg>
+///         </div>
+///     </div>
+///     <button on:click=move |_| reset()>"Reset"</button>
+///     <button on:click=move |_| fit_to_content()>"Fit"</button>
+/// }
+/// # }
+/// ```
+///
+/// The content is the first child of the `target`. Set `touch-action: none` on the `target` so
+/// that the browser doesn't scroll or zoom the page while pinching.
+///
+/// ## Bounds
+///
+/// With `extent` the viewport is kept inside of a rectangle in content coordinates. If the
+/// viewport is larger than the extent, the extent is centered.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos::html::Div;
+/// # use leptos_use::{use_zoom_pan_with_options, UseZoomPanOptions, ZoomPanExtent};
+/// #
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// # let viewport = create_node_ref::<Div>();
+/// let zoom_pan = use_zoom_pan_with_options(
+///     viewport,
+///     UseZoomPanOptions::default().extent(ZoomPanExtent {
+///         min_x: 0.0,
+///         min_y: 0.0,
+///         max_x: 1200.0,
+///         max_y: 800.0,
+///     }),
+/// );
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// ## Server-Side Rendering
+///
+/// On the server the transform is always the identity.
+pub fn use_zoom_pan<El, T>(
+    target: El,
+) -> UseZoomPanReturn<impl Fn(f64, Option<Position>) + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    use_zoom_pan_with_options(target, UseZoomPanOptions::default())
+}
+
+/// Version of [`use_zoom_pan`] that takes a `UseZoomPanOptions`. See [`use_zoom_pan`] for how to use.
+pub fn use_zoom_pan_with_options<El, T>(
+    target: El,
+    options: UseZoomPanOptions,
+) -> UseZoomPanReturn<impl Fn(f64, Option<Position>) + Clone, impl Fn() + Clone, impl Fn() + Clone>
+where
+    El: Into<ElementMaybeSignal<T, web_sys::Element>>,
+    T: Into<web_sys::Element> + Clone + 'static,
+{
+    let UseZoomPanOptions {
+        min_scale,
+        max_scale,
+        wheel_speed,
+        extent,
+        fit_padding,
+    } = options;
+
+    let target = target.into();
+    let element: Signal<Option<web_sys::Element>> =
+        Signal::derive(move || target.get().map(|target| target.into()));
+    let event_target: Signal<Option<web_sys::EventTarget>> =
+        Signal::derive(move || element.get().map(|element| element.into()));
+
+    let (transform, set_transform) = create_signal(ZoomPanTransform::default());
+    let (is_panning, set_panning) = create_signal(false);
+
+    // positions of the pointers that are currently down by pointer id
+    let pointers = store_value(HashMap::<i32, Position>::new());
+
+    let viewport_size = move || {
+        element
+            .get_untracked()
+            .map(|element| {
+                (
+                    element.client_width() as f64,
+                    element.client_height() as f64,
+                )
+            })
+            .unwrap_or_default()
+    };
+
+    // Applies the scale bounds and the extent
+    let constrain = move |transform: ZoomPanTransform| {
+        let scale = transform.scale.clamp(min_scale, max_scale);
+        let mut transform = ZoomPanTransform { scale, ..transform };
+
+        if let Some(extent) = extent {
+            let (width, height) = viewport_size();
+
+            transform.x = constrain_axis(transform.x, scale, width, extent.min_x, extent.max_x);
+            transform.y = constrain_axis(transform.y, scale, height, extent.min_y, extent.max_y);
+        }
+
+        transform
+    };
+
+    let set_constrained = move |new_transform: ZoomPanTransform| {
+        set_transform.set(constrain(new_transform));
+    };
+
+    // zooms to `scale` while keeping `center` (in viewport coordinates) at the same place
+    let zoom_to = move |scale: f64, center: Option<Position>| {
+        let current = transform.get_untracked();
+        let scale = scale.clamp(min_scale, max_scale);
+
+        let center = center.unwrap_or_else(|| {
+            let (width, height) = viewport_size();
+            Position {
+                x: width / 2.0,
+                y: height / 2.0,
+            }
+        });
+
+        let ratio = scale / current.scale;
+
+        set_constrained(ZoomPanTransform {
+            scale,
+            x: center.x - (center.x - current.x) * ratio,
+            y: center.y - (center.y - current.y) * ratio,
+        });
+    };
+
+    let to_viewport = move |client_x: f64, client_y: f64| {
+        element
+            .get_untracked()
+            .map(|element| {
+                let rect = element.get_bounding_client_rect();
+                Position {
+                    x: client_x - rect.left(),
+                    y: client_y - rect.top(),
+                }
+            })
+            .unwrap_or_default()
+    };
+
+    let _ = use_event_listener_with_options(
+        event_target,
+        wheel,
+        move |event: WheelEvent| {
+            event.prevent_default();
+
+            // lines and pages are roughly converted to pixels
+            let delta = event.delta_y()
+                * match event.delta_mode() {
+                    WheelEvent::DOM_DELTA_LINE => 16.0,
+                    WheelEvent::DOM_DELTA_PAGE => 800.0,
+                    _ => 1.0,
+                };
+
+            let center = to_viewport(event.client_x() as f64, event.client_y() as f64);
+            zoom_to(
+                transform.get_untracked().scale * (-delta * wheel_speed).exp(),
+                Some(center),
+            );
+        },
+        UseEventListenerOptions::default().passive(false),
+    );
+
+    let _ = use_event_listener(event_target, pointerdown, move |event: PointerEvent| {
+        if event.pointer_type() == "mouse" && event.button() != 0 {
+            return;
+        }
+
+        if let Some(element) = element.get_untracked() {
+            let _ = element.set_pointer_capture(event.pointer_id());
+        }
+
+        pointers.update_value(|pointers| {
+            pointers.insert(
+                event.pointer_id(),
+                to_viewport(event.client_x() as f64, event.client_y() as f64),
+            );
+        });
+        set_panning.set(true);
+    });
+
+    let _ = use_event_listener(event_target, pointermove, move |event: PointerEvent| {
+        let position = to_viewport(event.client_x() as f64, event.client_y() as f64);
+
+        let Some((previous, other)) = pointers
+            .try_update_value(|pointers| {
+                let previous = pointers.insert(event.pointer_id(), position)?;
+                let other = pointers
+                    .iter()
+                    .find(|(id, _)| **id != event.pointer_id())
+                    .map(|(_, other)| *other);
+
+                Some((previous, other))
+            })
+            .flatten()
+        else {
+            return;
+        };
+
+        let current = transform.get_untracked();
+
+        match other {
+            // pinch: the midpoint pans and the change of the distance zooms
+            Some(other) => {
+                let previous_distance = distance(previous, other);
+                let ratio = if previous_distance > 0.0 {
+                    distance(position, other) / previous_distance
+                } else {
+                    1.0
+                };
+
+                let previous_center = midpoint(previous, other);
+                let center = midpoint(position, other);
+
+                let scale = (current.scale * ratio).clamp(min_scale, max_scale);
+                let ratio = scale / current.scale;
+
+                set_constrained(ZoomPanTransform {
+                    scale,
+                    x: center.x - (previous_center.x - current.x) * ratio,
+                    y: center.y - (previous_center.y - current.y) * ratio,
+                });
+            }
+            None => set_constrained(ZoomPanTransform {
+                x: current.x + position.x - previous.x,
+                y: current.y + position.y - previous.y,
+                ..current
+            }),
+        }
+    });
+
+    let end_pointer = move |event: PointerEvent| {
+        let is_empty = pointers
+            .try_update_value(|pointers| {
+                pointers.remove(&event.pointer_id());
+                pointers.is_empty()
+            })
+            .unwrap_or(true);
+
+        if is_empty && is_panning.get_untracked() {
+            set_panning.set(false);
+        }
+    };
+
+    let _ = use_event_listener(event_target, pointerup, end_pointer);
+    let _ = use_event_listener(event_target, pointercancel, end_pointer);
+
+    let reset = move || set_constrained(ZoomPanTransform::default());
+
+    let fit_to_content = move || {
+        let Some(element) = element.get_untracked() else {
+            return;
+        };
+        let Some(content) = element.first_element_child() else {
+            return;
+        };
+
+        let current = transform.get_untracked();
+        let viewport = element.get_bounding_client_rect();
+        let rect = content.get_bounding_client_rect();
+
+        // bounding box of the content in content coordinates
+        let left = (rect.left() - viewport.left() - current.x) / current.scale;
+        let top = (rect.top() - viewport.top() - current.y) / current.scale;
+        let width = rect.width() / current.scale;
+        let height = rect.height() / current.scale;
+
+        let (viewport_width, viewport_height) = viewport_size();
+        let available_width = viewport_width - 2.0 * fit_padding;
+        let available_height = viewport_height - 2.0 * fit_padding;
+
+        if width <= 0.0 || height <= 0.0 || available_width <= 0.0 || available_height <= 0.0 {
+            return;
+        }
+
+        let scale = (available_width / width)
+            .min(available_height / height)
+            .clamp(min_scale, max_scale);
+
+        set_constrained(ZoomPanTransform {
+            scale,
+            x: (viewport_width - width * scale) / 2.0 - left * scale,
+            y: (viewport_height - height * scale) / 2.0 - top * scale,
+        });
+    };
+
+    UseZoomPanReturn {
+        transform: transform.into(),
+        set_transform,
+        style: Signal::derive(move || transform.get().to_style()),
+        is_panning: is_panning.into(),
+        zoom_to,
+        reset,
+        fit_to_content,
+    }
+}
+
+/// Translation of one axis so that the viewport of the given length stays inside of
+/// `min..max` in content coordinates.
+fn constrain_axis(translation: f64, scale: f64, length: f64, min: f64, max: f64) -> f64 {
+    let extent_length = (max - min) * scale;
+
+    if extent_length <= length {
+        // center the extent
+        (length - extent_length) / 2.0 - min * scale
+    } else {
+        translation.clamp(length - max * scale, -min * scale)
+    }
+}
+
+fn distance(a: Position, b: Position) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn midpoint(a: Position, b: Position) -> Position {
+    Position {
+        x: (a.x + b.x) / 2.0,
+        y: (a.y + b.y) / 2.0,
+    }
+}
+
+/// Scale and translation of the content. A point `p` of the content is shown at
+/// `p * scale + (x, y)` in the viewport.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZoomPanTransform {
+    pub scale: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Default for ZoomPanTransform {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+}
+
+impl ZoomPanTransform {
+    /// Converts a point of the content to viewport coordinates.
+    pub fn apply(&self, point: Position) -> Position {
+        Position {
+            x: point.x * self.scale + self.x,
+            y: point.y * self.scale + self.y,
+        }
+    }
+
+    /// Converts a point of the viewport, e.g. the position of the mouse, to content coordinates.
+    pub fn invert(&self, point: Position) -> Position {
+        Position {
+            x: (point.x - self.x) / self.scale,
+            y: (point.y - self.y) / self.scale,
+        }
+    }
+
+    /// Style attribute for the content,
+    /// `"transform: translate({x}px, {y}px) scale({scale}); transform-origin: 0 0;"`
+    pub fn to_style(&self) -> String {
+        format!(
+            "transform: translate({}px, {}px) scale({}); transform-origin: 0 0;",
+            self.x, self.y, self.scale
+        )
+    }
+}
+
+/// Rectangle in content coordinates that the viewport of [`use_zoom_pan`] is kept inside of.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZoomPanExtent {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+/// Options for [`use_zoom_pan_with_options`].
+#[derive(DefaultBuilder)]
+pub struct UseZoomPanOptions {
+    /// Minimum scale. Defaults to `0.1`.
+    min_scale: f64,
+
+    /// Maximum scale. Defaults to `10.0`.
+    max_scale: f64,
+
+    /// How fast the wheel zooms. Scrolling by `n` pixels multiplies the scale by
+    /// `e^(-n * wheel_speed)`. Defaults to `0.002`.
+    wheel_speed: f64,
+
+    /// Rectangle in content coordinates that the viewport is kept inside of. Defaults to `None`.
+    #[builder(into)]
+    extent: Option<ZoomPanExtent>,
+
+    /// Space in pixels that `fit_to_content` leaves around the content. Defaults to `0.0`.
+    fit_padding: f64,
+}
+
+impl Default for UseZoomPanOptions {
+    fn default() -> Self {
+        Self {
+            min_scale: 0.1,
+            max_scale: 10.0,
+            wheel_speed: 0.002,
+            extent: None,
+            fit_padding: 0.0,
+        }
+    }
+}
+
+/// Return type of [`use_zoom_pan`].
+pub struct UseZoomPanReturn<ZoomFn, ResetFn, FitFn>
+where
+    ZoomFn: Fn(f64, Option<Position>) + Clone,
+    ResetFn: Fn() + Clone,
+    FitFn: Fn() + Clone,
+{
+    /// The current transform
+    pub transform: Signal<ZoomPanTransform>,
+
+    /// Sets the transform without applying the constraints
+    pub set_transform: WriteSignal<ZoomPanTransform>,
+
+    /// Style attribute for the content. See [`ZoomPanTransform::to_style`].
+    pub style: Signal<String>,
+
+    /// `true` while the content is dragged or pinched
+    pub is_panning: Signal<bool>,
+
+    /// Zooms to the given scale around the given point in viewport coordinates or the center of
+    /// the viewport if it's `None`
+    pub zoom_to: ZoomFn,
+
+    /// Resets to the identity transform
+    pub reset: ResetFn,
+
+    /// Zooms and pans so that the content fits into the viewport
+    pub fit_to_content: FitFn,
+}
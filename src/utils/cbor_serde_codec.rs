@@ -0,0 +1,57 @@
+use codee::{Decoder, Encoder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A binary codec that uses [CBOR](https://cbor.io/) via [`ciborium`] to encode and decode data.
+///
+/// Like every binary codec it can be used directly with binary transports like
+/// [`crate::use_websocket`] and [`crate::use_webtransport`] as well as with the storage
+/// functions like [`crate::storage::use_local_storage`] which store it base64 encoded.
+///
+/// ```
+/// # use leptos::*;
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::storage::use_local_storage;
+/// # use leptos_use::utils::CborSerdeCodec;
+/// # use leptos_use::{use_websocket, UseWebSocketReturn};
+/// #
+/// #[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+/// struct Position {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// let UseWebSocketReturn { message, send, .. } =
+///     use_websocket::<Position, CborSerdeCodec>("wss://example.com/positions");
+///
+/// let (last_position, set_last_position, _) =
+///     use_local_storage::<Position, CborSerdeCodec>("last-position");
+/// #
+/// # view! { }
+/// # }
+/// ```
+///
+/// Only available with the feature `cbor`.
+pub struct CborSerdeCodec;
+
+impl<T: Serialize> Encoder<T> for CborSerdeCodec {
+    type Error = ciborium::ser::Error<std::io::Error>;
+    type Encoded = Vec<u8>;
+
+    fn encode(val: &T) -> Result<Self::Encoded, Self::Error> {
+        let mut buffer = Vec::new();
+        ciborium::into_writer(val, &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl<T: DeserializeOwned> Decoder<T> for CborSerdeCodec {
+    type Error = ciborium::de::Error<std::io::Error>;
+    type Encoded = [u8];
+
+    fn decode(val: &Self::Encoded) -> Result<T, Self::Error> {
+        ciborium::from_reader(val)
+    }
+}
@@ -0,0 +1,121 @@
+#![cfg_attr(feature = "ssr", allow(dead_code))]
+
+use leptos::window;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Opens (and if necessary creates) the IndexedDB database `name` with the object store
+/// `store`. If `auto_increment` is `true` the store generates its keys itself.
+///
+/// If the database exists already but doesn't have the store yet, it's upgraded to the next
+/// version which creates the store. So several stores can share a database.
+pub(crate) async fn idb_open(
+    name: &str,
+    store: &str,
+    auto_increment: bool,
+) -> Result<web_sys::IdbDatabase, JsValue> {
+    let db = idb_open_version(name, None, store, auto_increment).await?;
+
+    if db.object_store_names().contains(store) {
+        return Ok(db);
+    }
+
+    let version = db.version() as u32 + 1;
+    db.close();
+
+    idb_open_version(name, Some(version), store, auto_increment).await
+}
+
+async fn idb_open_version(
+    name: &str,
+    version: Option<u32>,
+    store: &str,
+    auto_increment: bool,
+) -> Result<web_sys::IdbDatabase, JsValue> {
+    let factory = window()
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB is not supported"))?;
+
+    let request = match version {
+        Some(version) => factory.open_with_u32(name, version)?,
+        None => factory.open(name)?,
+    };
+
+    let on_upgrade_needed = {
+        let request = request.clone();
+        let store = store.to_string();
+
+        Closure::once_into_js(move || {
+            let Ok(db) = request.result() else {
+                return;
+            };
+            let db = db.unchecked_into::<web_sys::IdbDatabase>();
+
+            if !db.object_store_names().contains(&store) {
+                let mut parameters = web_sys::IdbObjectStoreParameters::new();
+                parameters.auto_increment(auto_increment);
+
+                let _ = db.create_object_store_with_optional_parameters(&store, &parameters);
+            }
+        })
+    };
+    request.set_onupgradeneeded(Some(on_upgrade_needed.unchecked_ref()));
+
+    idb_request(&request)
+        .await
+        .map(|db| db.unchecked_into::<web_sys::IdbDatabase>())
+}
+
+/// Returns the object store `store` of `db` in a new transaction with the given `mode`.
+pub(crate) fn idb_store(
+    db: &web_sys::IdbDatabase,
+    store: &str,
+    mode: web_sys::IdbTransactionMode,
+) -> Result<web_sys::IdbObjectStore, JsValue> {
+    db.transaction_with_str_and_mode(store, mode)?
+        .object_store(store)
+}
+
+/// Waits for `request` to finish and returns its result.
+pub(crate) async fn idb_request(request: &web_sys::IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_done = {
+            let request = request.clone();
+
+            Closure::<dyn FnMut()>::new(move || match request.error() {
+                Ok(Some(error)) => {
+                    let _ = reject.call1(&JsValue::UNDEFINED, &error);
+                }
+                Ok(None) => {
+                    let result = request.result().unwrap_or(JsValue::UNDEFINED);
+                    let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+                }
+                Err(error) => {
+                    let _ = reject.call1(&JsValue::UNDEFINED, &error);
+                }
+            })
+            .into_js_value()
+        };
+
+        request.set_onsuccess(Some(on_done.unchecked_ref()));
+        request.set_onerror(Some(on_done.unchecked_ref()));
+    });
+
+    wasm_bindgen_futures::JsFuture::from(promise).await
+}
+
+/// Returns all keys and values of `store` ordered by key.
+pub(crate) async fn idb_entries(
+    store: &web_sys::IdbObjectStore,
+) -> Result<Vec<(JsValue, JsValue)>, JsValue> {
+    // both requests run in the same transaction so they see the same state
+    let keys = store.get_all_keys()?;
+    let values = store.get_all()?;
+
+    let keys = idb_request(&keys).await?.unchecked_into::<js_sys::Array>();
+    let values = idb_request(&values)
+        .await?
+        .unchecked_into::<js_sys::Array>();
+
+    Ok(keys.iter().zip(values.iter()).collect())
+}
@@ -1,13 +1,31 @@
+#[cfg(feature = "cbor")]
+mod cbor_serde_codec;
 mod filters;
+mod idb;
 mod is;
 mod js;
 mod js_value_from_to_string;
+mod observer_pool;
 mod pausable;
+mod query_param;
+mod random_id;
 mod signal_filtered;
+mod sse_parser;
+mod string_coder;
 mod use_derive_signal;
+mod visibility_scheduler;
 
+#[cfg(feature = "cbor")]
+pub use cbor_serde_codec::*;
 pub use filters::*;
+pub(crate) use idb::*;
 pub use is::*;
 pub(crate) use js_value_from_to_string::*;
+pub(crate) use observer_pool::*;
 pub use pausable::*;
+pub(crate) use query_param::*;
+pub(crate) use random_id::*;
 pub(crate) use signal_filtered::*;
+pub use sse_parser::*;
+pub use string_coder::*;
+pub use visibility_scheduler::*;
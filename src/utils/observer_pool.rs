@@ -0,0 +1,363 @@
+#![cfg_attr(feature = "ssr", allow(dead_code, unused_imports))]
+
+use leptos::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::thread::LocalKey;
+use wasm_bindgen::prelude::*;
+
+/// Observers like `ResizeObserver` or `IntersectionObserver` that can be shared between many
+/// hook calls. One observer instance is created per set of options and the entries it reports
+/// are dispatched to the callbacks that observe the respective targets together with the observer
+/// and the id of the respective subscriber.
+pub(crate) trait PooledObserver: JsCast + Clone + 'static {
+    type Entry: JsCast + Clone;
+    type Options;
+
+    fn create(callback: &js_sys::Function, options: &Self::Options) -> Self;
+
+    fn observe_target(&self, target: &web_sys::Element, options: &Self::Options);
+
+    fn unobserve_target(&self, target: &web_sys::Element);
+
+    fn disconnect_all(&self);
+
+    fn target_of(entry: &Self::Entry) -> web_sys::Element;
+}
+
+impl PooledObserver for web_sys::ResizeObserver {
+    type Entry = web_sys::ResizeObserverEntry;
+    type Options = web_sys::ResizeObserverOptions;
+
+    fn create(callback: &js_sys::Function, _: &Self::Options) -> Self {
+        web_sys::ResizeObserver::new(callback).expect("failed to create ResizeObserver")
+    }
+
+    fn observe_target(&self, target: &web_sys::Element, options: &Self::Options) {
+        self.observe_with_options(target, options);
+    }
+
+    fn unobserve_target(&self, target: &web_sys::Element) {
+        self.unobserve(target);
+    }
+
+    fn disconnect_all(&self) {
+        self.disconnect();
+    }
+
+    fn target_of(entry: &Self::Entry) -> web_sys::Element {
+        entry.target()
+    }
+}
+
+impl PooledObserver for web_sys::IntersectionObserver {
+    type Entry = web_sys::IntersectionObserverEntry;
+    type Options = web_sys::IntersectionObserverInit;
+
+    fn create(callback: &js_sys::Function, options: &Self::Options) -> Self {
+        web_sys::IntersectionObserver::new_with_options(callback, options)
+            .expect("failed to create IntersectionObserver")
+    }
+
+    fn observe_target(&self, target: &web_sys::Element, _: &Self::Options) {
+        self.observe(target);
+    }
+
+    fn unobserve_target(&self, target: &web_sys::Element) {
+        self.unobserve(target);
+    }
+
+    fn disconnect_all(&self) {
+        self.disconnect();
+    }
+
+    fn target_of(entry: &Self::Entry) -> web_sys::Element {
+        entry.target()
+    }
+}
+
+thread_local! {
+    pub(crate) static RESIZE_OBSERVER_POOL: RefCell<ObserverPool<web_sys::ResizeObserver>> =
+        RefCell::new(ObserverPool::default());
+
+    pub(crate) static INTERSECTION_OBSERVER_POOL: RefCell<ObserverPool<web_sys::IntersectionObserver>> =
+        RefCell::new(ObserverPool::default());
+
+    static NEXT_ELEMENT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+pub(crate) type ObserverPoolRef<O> = &'static LocalKey<RefCell<ObserverPool<O>>>;
+
+type PoolCallback<O> = Rc<RefCell<dyn FnMut(Vec<<O as PooledObserver>::Entry>, O, usize)>>;
+
+/// A pool of shared observers keyed by a string that identifies their options.
+pub(crate) struct ObserverPool<O: PooledObserver> {
+    observers: HashMap<String, PooledEntry<O>>,
+    subscribers: HashMap<usize, PoolCallback<O>>,
+    /// Observer keys and target ids by subscriber so that a subscriber's targets can be
+    /// removed without visiting all the others.
+    subscriptions: HashMap<usize, Vec<(String, u64)>>,
+    next_subscriber_id: usize,
+}
+
+struct PooledEntry<O: PooledObserver> {
+    observer: O,
+    options: O::Options,
+    /// Observed targets by element id.
+    targets: HashMap<u64, ObservedTarget>,
+}
+
+struct ObservedTarget {
+    element: web_sys::Element,
+    subscribers: Vec<usize>,
+    /// Subscribers that started observing the target while it was observed already. The entry
+    /// that is reported after re-observing it is only delivered to them.
+    pending_initial: Vec<usize>,
+}
+
+impl<O: PooledObserver> Default for ObserverPool<O> {
+    fn default() -> Self {
+        Self {
+            observers: HashMap::new(),
+            subscribers: HashMap::new(),
+            subscriptions: HashMap::new(),
+            next_subscriber_id: 0,
+        }
+    }
+}
+
+impl<O: PooledObserver> ObserverPool<O> {
+    /// Registers a callback and returns the id that is used to observe targets with it.
+    /// The callback receives this id along with the entries of its targets and the observer.
+    pub fn subscribe(
+        pool: ObserverPoolRef<O>,
+        callback: impl FnMut(Vec<O::Entry>, O, usize) + 'static,
+    ) -> usize {
+        pool.with(|pool| {
+            let mut pool = pool.borrow_mut();
+
+            let id = pool.next_subscriber_id;
+            pool.next_subscriber_id += 1;
+            pool.subscribers
+                .insert(id, Rc::new(RefCell::new(callback)) as PoolCallback<O>);
+
+            id
+        })
+    }
+
+    /// Observes `target` with the shared observer for `key` and reports its entries to `subscriber`.
+    /// The observer is created with `options` if it doesn't exist yet.
+    pub fn observe(
+        pool: ObserverPoolRef<O>,
+        key: &str,
+        options: impl FnOnce() -> O::Options,
+        subscriber: usize,
+        target: &web_sys::Element,
+    ) {
+        let target_id = element_id(target);
+
+        pool.with(|pool_cell| {
+            let mut pool_ref = pool_cell.borrow_mut();
+            let pool_ref = &mut *pool_ref;
+
+            let pooled = pool_ref
+                .observers
+                .entry(key.to_string())
+                .or_insert_with(|| {
+                    let options = options();
+                    let observer = O::create(
+                        dispatch_closure(pool, key.to_string()).unchecked_ref(),
+                        &options,
+                    );
+
+                    PooledEntry {
+                        observer,
+                        options,
+                        targets: HashMap::new(),
+                    }
+                });
+
+            let observed = pooled
+                .targets
+                .entry(target_id)
+                .or_insert_with(|| ObservedTarget {
+                    element: target.clone(),
+                    subscribers: vec![],
+                    pending_initial: vec![],
+                });
+
+            if observed.subscribers.contains(&subscriber) {
+                return;
+            }
+
+            // Re-observing an already observed target makes the observer report its current
+            // state again so the new subscriber receives an initial entry as well.
+            if !observed.subscribers.is_empty() {
+                pooled.observer.unobserve_target(target);
+                observed.pending_initial.push(subscriber);
+            }
+            observed.subscribers.push(subscriber);
+
+            pool_ref
+                .subscriptions
+                .entry(subscriber)
+                .or_default()
+                .push((key.to_string(), target_id));
+
+            // observers always report asynchronously so this can't re-enter the pool
+            pooled.observer.observe_target(target, &pooled.options);
+        });
+    }
+
+    /// Stops observing all targets of `subscriber`. Observers without any targets left are disconnected.
+    pub fn unobserve_all(pool: ObserverPoolRef<O>, subscriber: usize) {
+        let mut to_unobserve = vec![];
+        let mut to_disconnect = vec![];
+
+        pool.with(|pool| {
+            let mut pool = pool.borrow_mut();
+
+            let Some(subscriptions) = pool.subscriptions.remove(&subscriber) else {
+                return;
+            };
+
+            for (key, target_id) in subscriptions {
+                let Some(pooled) = pool.observers.get_mut(&key) else {
+                    continue;
+                };
+
+                if let Some(observed) = pooled.targets.get_mut(&target_id) {
+                    observed.subscribers.retain(|s| *s != subscriber);
+                    observed.pending_initial.retain(|s| *s != subscriber);
+
+                    if observed.subscribers.is_empty() {
+                        if let Some(observed) = pooled.targets.remove(&target_id) {
+                            to_unobserve.push((pooled.observer.clone(), observed.element));
+                        }
+                    }
+                }
+
+                if pooled.targets.is_empty() {
+                    if let Some(pooled) = pool.observers.remove(&key) {
+                        to_disconnect.push(pooled.observer);
+                    }
+                }
+            }
+        });
+
+        for (observer, target) in to_unobserve {
+            observer.unobserve_target(&target);
+        }
+
+        for observer in to_disconnect {
+            observer.disconnect_all();
+        }
+    }
+
+    /// Stops observing all targets of `subscriber` and removes its callback.
+    pub fn unsubscribe(pool: ObserverPoolRef<O>, subscriber: usize) {
+        Self::unobserve_all(pool, subscriber);
+
+        pool.with(|pool| {
+            pool.borrow_mut().subscribers.remove(&subscriber);
+        });
+    }
+}
+
+/// Creates the JS callback of the shared observer for `key` that dispatches the reported entries
+/// to the subscribers that observe the respective targets.
+fn dispatch_closure<O: PooledObserver>(pool: ObserverPoolRef<O>, key: String) -> JsValue {
+    Closure::<dyn FnMut(js_sys::Array, JsValue)>::new(
+        move |entries: js_sys::Array, observer: JsValue| {
+            let observer = observer.unchecked_into::<O>();
+
+            // collect the callbacks first so they can (un)subscribe without a double borrow
+            let calls = pool.with(|pool| {
+                let mut pool = pool.borrow_mut();
+                let pool = &mut *pool;
+
+                let Some(pooled) = pool.observers.get_mut(&key) else {
+                    return vec![];
+                };
+
+                let mut calls: Vec<(usize, Vec<O::Entry>)> = vec![];
+
+                for entry in entries.iter() {
+                    let entry = entry.unchecked_into::<O::Entry>();
+
+                    let Some(target_id) = existing_element_id(&O::target_of(&entry)) else {
+                        continue;
+                    };
+
+                    let Some(observed) = pooled.targets.get_mut(&target_id) else {
+                        continue;
+                    };
+
+                    // the entry was caused by re-observing for new subscribers so the others
+                    // have received it already
+                    let subscribers = if observed.pending_initial.is_empty() {
+                        observed.subscribers.clone()
+                    } else {
+                        std::mem::take(&mut observed.pending_initial)
+                    };
+
+                    for subscriber in subscribers {
+                        match calls.iter_mut().find(|(s, _)| *s == subscriber) {
+                            Some((_, entries)) => entries.push(entry.clone()),
+                            None => calls.push((subscriber, vec![entry.clone()])),
+                        }
+                    }
+                }
+
+                calls
+                    .into_iter()
+                    .filter_map(|(subscriber, entries)| {
+                        pool.subscribers
+                            .get(&subscriber)
+                            .map(|callback| (Rc::clone(callback), entries, subscriber))
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            #[cfg(debug_assertions)]
+            let prev = SpecialNonReactiveZone::enter();
+
+            for (callback, entries, subscriber) in calls {
+                (callback.borrow_mut())(entries, observer.clone(), subscriber);
+            }
+
+            #[cfg(debug_assertions)]
+            SpecialNonReactiveZone::exit(prev);
+        },
+    )
+    .into_js_value()
+}
+
+const ELEMENT_ID_PROPERTY: &str = "__leptosUseObserverPoolId";
+
+/// Returns a unique id for the element which is stored on the element itself.
+pub(crate) fn element_id(element: &web_sys::Element) -> u64 {
+    existing_element_id(element).unwrap_or_else(|| {
+        let id = NEXT_ELEMENT_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        });
+
+        let _ = js_sys::Reflect::set(
+            element,
+            &ELEMENT_ID_PROPERTY.into(),
+            &JsValue::from_f64(id as f64),
+        );
+
+        id
+    })
+}
+
+fn existing_element_id(element: &web_sys::Element) -> Option<u64> {
+    js_sys::Reflect::get(element, &ELEMENT_ID_PROPERTY.into())
+        .ok()
+        .and_then(|id| id.as_f64())
+        .map(|id| id as u64)
+}
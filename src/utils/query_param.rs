@@ -0,0 +1,49 @@
+/// Returns the value of the query parameter `name` of the current URL.
+#[cfg(not(feature = "ssr"))]
+pub(crate) fn get_query_param(name: &str) -> Option<String> {
+    let search = leptos::window().location().search().ok()?;
+
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(name)
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) fn get_query_param(_name: &str) -> Option<String> {
+    None
+}
+
+/// Sets (or removes if `value` is `None`) the query parameter `name` of the current URL
+/// without adding an entry to the history.
+#[cfg(not(feature = "ssr"))]
+pub(crate) fn set_query_param(name: &str, value: Option<&str>) {
+    let window = leptos::window();
+
+    let Ok(href) = window.location().href() else {
+        return;
+    };
+    let Ok(url) = web_sys::Url::new(&href) else {
+        return;
+    };
+
+    let params = url.search_params();
+    if params.get(name).as_deref() == value {
+        return;
+    }
+
+    match value {
+        Some(value) => params.set(name, value),
+        None => params.delete(name),
+    }
+
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(
+            &history.state().unwrap_or(wasm_bindgen::JsValue::NULL),
+            "",
+            Some(&url.href()),
+        );
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) fn set_query_param(_name: &str, _value: Option<&str>) {}
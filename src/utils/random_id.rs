@@ -0,0 +1,10 @@
+/// Returns a random id that is unique enough to tell apart tabs and clients.
+#[cfg(not(feature = "ssr"))]
+pub(crate) fn random_id() -> String {
+    format!("{:x}", (js_sys::Math::random() * u64::MAX as f64) as u64)
+}
+
+#[cfg(feature = "ssr")]
+pub(crate) fn random_id() -> String {
+    "server".to_string()
+}
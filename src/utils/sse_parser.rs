@@ -0,0 +1,164 @@
+/// Incremental parser for the
+/// [`text/event-stream`](https://html.spec.whatwg.org/multipage/server-sent-events.html#parsing-an-event-stream)
+/// format. Chunks can be split at arbitrary positions. Lines can end with `\n`, `\r` or `\r\n`.
+///
+/// This is what [`fn@crate::use_event_source`] uses for its `fetch` based transport.
+///
+/// ```
+/// # use leptos_use::utils::SseParser;
+/// #
+/// let mut parser = SseParser::new(String::new());
+///
+/// // a comment, an event with two lines of data and a reconnection time. The `\r\n` at the
+/// // end of the first chunk is split across two chunks.
+/// let mut parsed = parser.feed(": keep-alive\r\nevent: update\r\ndata: first\r");
+/// assert!(parsed.events.is_empty());
+///
+/// parsed = parser.feed("\ndata: second\rid: 42\nretry: 3000\r\n\r\n");
+/// assert_eq!(parsed.events.len(), 1);
+/// assert_eq!(parsed.events[0].event_type, "update");
+/// assert_eq!(parsed.events[0].data, "first\nsecond");
+/// assert_eq!(parsed.events[0].last_event_id, "42");
+/// assert_eq!(parsed.retry, Some(3000));
+/// assert_eq!(parser.last_event_id(), "42");
+///
+/// // a line and the empty line that dispatches the event split in the middle
+/// assert!(parser.feed("da").events.is_empty());
+/// parsed = parser.feed("ta: plain\n\n");
+/// assert_eq!(parsed.events[0].event_type, "message");
+/// assert_eq!(parsed.events[0].data, "plain");
+/// ```
+pub struct SseParser {
+    buffer: String,
+    /// `true` if the last line ended with `\r` which might be followed by a `\n` that belongs
+    /// to the same line ending.
+    after_cr: bool,
+    data: String,
+    event_type: String,
+    last_event_id: String,
+}
+
+/// An event dispatched by the [`SseParser`].
+pub struct SseEvent {
+    pub event_type: String,
+    pub data: String,
+    pub last_event_id: String,
+}
+
+/// Result of [`SseParser::feed`].
+#[derive(Default)]
+pub struct SseParsed {
+    pub events: Vec<SseEvent>,
+    /// The latest reconnection time in ms sent by the server.
+    pub retry: Option<u64>,
+}
+
+impl SseParser {
+    /// Creates a parser that continues with the `last_event_id` of a previous connection.
+    pub fn new(last_event_id: String) -> Self {
+        Self {
+            buffer: String::new(),
+            after_cr: false,
+            data: String::new(),
+            event_type: String::new(),
+            last_event_id,
+        }
+    }
+
+    /// The last event id that has been received. Sent as `Last-Event-ID` header when reconnecting.
+    pub fn last_event_id(&self) -> &str {
+        &self.last_event_id
+    }
+
+    /// Parses all complete lines of `chunk` together with what's left over from previous chunks.
+    pub fn feed(&mut self, chunk: &str) -> SseParsed {
+        self.buffer.push_str(chunk);
+
+        let mut parsed = SseParsed::default();
+        let mut start = 0;
+
+        loop {
+            if self.after_cr {
+                // wait for the next chunk to know if the line ending is `\r\n`
+                if start == self.buffer.len() {
+                    break;
+                }
+
+                self.after_cr = false;
+
+                if self.buffer[start..].starts_with('\n') {
+                    start += 1;
+                    continue;
+                }
+            }
+
+            let Some(len) = self.buffer[start..].find(|c| c == '\r' || c == '\n') else {
+                break;
+            };
+
+            let end = start + len;
+            let line = self.buffer[start..end].to_string();
+            self.after_cr = self.buffer.as_bytes()[end] == b'\r';
+            start = end + 1;
+
+            self.parse_line(&line, &mut parsed);
+        }
+
+        self.buffer.drain(..start);
+
+        parsed
+    }
+
+    fn parse_line(&mut self, line: &str, parsed: &mut SseParsed) {
+        if line.is_empty() {
+            self.dispatch(parsed);
+            return;
+        }
+
+        // comment
+        if line.starts_with(':') {
+            return;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event_type = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = value.to_string(),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    parsed.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self, parsed: &mut SseParsed) {
+        let event_type = std::mem::take(&mut self.event_type);
+
+        if self.data.is_empty() {
+            return;
+        }
+
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+
+        parsed.events.push(SseEvent {
+            event_type: if event_type.is_empty() {
+                "message".to_string()
+            } else {
+                event_type
+            },
+            data,
+            last_event_id: self.last_event_id.clone(),
+        });
+    }
+}
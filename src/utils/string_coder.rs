@@ -0,0 +1,99 @@
+use base64::Engine;
+use codee::{Decoder, Encoder, HybridCoderError, HybridDecoder, HybridEncoder, IsBinary};
+use thiserror::Error;
+
+/// Encodes values for places that only store or send strings like the browser storages or
+/// server-sent events.
+///
+/// Implemented for every string and binary codec. String codecs are used as they are and the
+/// output of binary codecs is base64 encoded. This is the same format as `codee`'s `Base64`
+/// adapter so values that have been written with it can still be read.
+///
+/// ```
+/// # use leptos::*;
+/// # use codee::binary::MsgpackSerdeCodec;
+/// # use serde::{Deserialize, Serialize};
+/// # use leptos_use::storage::use_local_storage;
+/// #
+/// #[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+/// struct Settings {
+///     volume: f64,
+/// }
+///
+/// # #[component]
+/// # fn Demo() -> impl IntoView {
+/// // no `Base64` wrapper required
+/// let (settings, set_settings, _) = use_local_storage::<Settings, MsgpackSerdeCodec>("settings");
+/// #
+/// # view! { }
+/// # }
+/// ```
+pub trait StringEncoder<T> {
+    type Error;
+
+    fn encode_string(val: &T) -> Result<String, Self::Error>;
+}
+
+/// Decodes values that have been stored or sent as strings. See [`StringEncoder`].
+pub trait StringDecoder<T> {
+    type Error;
+
+    fn decode_string(val: &str) -> Result<T, Self::Error>;
+}
+
+impl<T, C> StringEncoder<T> for C
+where
+    C: Encoder<T> + Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridEncoder<T, <C as Encoder<T>>::Encoded, Error = <C as Encoder<T>>::Error>,
+{
+    type Error = StringCoderError<<C as Encoder<T>>::Error>;
+
+    fn encode_string(val: &T) -> Result<String, Self::Error> {
+        if C::is_binary() {
+            C::encode_bin(val)
+                .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+                .map_err(Into::into)
+        } else {
+            C::encode_str(val).map_err(Into::into)
+        }
+    }
+}
+
+impl<T, C> StringDecoder<T> for C
+where
+    C: Decoder<T>,
+    C: IsBinary<T, <C as Decoder<T>>::Encoded>,
+    C: HybridDecoder<T, <C as Decoder<T>>::Encoded, Error = <C as Decoder<T>>::Error>,
+{
+    type Error = StringCoderError<<C as Decoder<T>>::Error>;
+
+    fn decode_string(val: &str) -> Result<T, Self::Error> {
+        if C::is_binary() {
+            let bytes = base64::engine::general_purpose::STANDARD.decode(val)?;
+            C::decode_bin(&bytes).map_err(Into::into)
+        } else {
+            C::decode_str(val).map_err(Into::into)
+        }
+    }
+}
+
+/// Error of [`StringEncoder`] and [`StringDecoder`].
+#[derive(Error, Debug)]
+pub enum StringCoderError<E> {
+    #[error("codec error: {0:?}")]
+    Coder(E),
+    #[error("the codec doesn't support this format")]
+    NotImplemented,
+    #[error("invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+}
+
+impl<E> From<HybridCoderError<E>> for StringCoderError<E> {
+    fn from(err: HybridCoderError<E>) -> Self {
+        match err {
+            HybridCoderError::NotImplemented => StringCoderError::NotImplemented,
+            HybridCoderError::Coder(err) => StringCoderError::Coder(err),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::core::now;
+use crate::use_document_visibility;
+use leptos::*;
+use web_sys::VisibilityState;
+
+/// What a timer of [`fn@crate::use_interval`], [`fn@crate::use_interval_fn`] or
+/// [`fn@crate::use_timeout_fn`] does while the page is hidden.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimerHiddenBehavior {
+    /// Keep the timer running. Browsers usually throttle timers in background tabs so it
+    /// might fire late.
+    #[default]
+    Continue,
+
+    /// Stop the timer while the page is hidden and continue when it becomes visible again as if
+    /// the time in between didn't pass. The time that was left until the next call is kept.
+    Pause,
+
+    /// Stop the timer while the page is hidden. When it becomes visible again, the callback is
+    /// called right away if it has been missed in the meantime.
+    CatchUp,
+}
+
+/// Suspends a timer while the page is hidden depending on a [`TimerHiddenBehavior`].
+/// Shared by the timer functions so they behave the same.
+#[derive(Clone, Copy)]
+pub(crate) struct VisibilityScheduler {
+    behavior: TimerHiddenBehavior,
+    visibility: Signal<VisibilityState>,
+    /// `true` while the timer is suspended because the page is hidden
+    pub is_suspended: ReadSignal<bool>,
+    set_suspended: WriteSignal<bool>,
+    /// Time in ms the page has been hidden the last time
+    pub hidden_duration: ReadSignal<f64>,
+}
+
+impl VisibilityScheduler {
+    /// Returns `true` if the timer shouldn't be started now because the page is hidden.
+    /// In this case the timer counts as suspended and will be restored when the page becomes
+    /// visible.
+    pub fn defer(&self) -> bool {
+        let defer = cfg!(not(feature = "ssr"))
+            && self.behavior != TimerHiddenBehavior::Continue
+            && self.visibility.get_untracked() == VisibilityState::Hidden;
+
+        if defer && !self.is_suspended.get_untracked() {
+            self.set_suspended.set(true);
+        }
+
+        defer
+    }
+
+    /// Forgets about a suspended timer because it has been stopped.
+    pub fn cancel(&self) {
+        if self.is_suspended.get_untracked() {
+            self.set_suspended.set(false);
+        }
+    }
+}
+
+/// Watches the document visibility for a timer.
+///
+/// When the page becomes hidden while `is_running` returns `true`, `suspend` is called and the
+/// timer should be stopped. When the page becomes visible again, `restore` is called and the
+/// timer should be started again. For [`TimerHiddenBehavior::Continue`] the visibility isn't
+/// watched at all.
+pub(crate) fn use_visibility_scheduler(
+    behavior: TimerHiddenBehavior,
+    is_running: impl Fn() -> bool + 'static,
+    suspend: impl Fn() + 'static,
+    restore: impl Fn() + 'static,
+) -> VisibilityScheduler {
+    // `Continue` doesn't care about the visibility so it doesn't listen to it at all
+    let visibility = if behavior == TimerHiddenBehavior::Continue {
+        Signal::derive(|| VisibilityState::Visible)
+    } else {
+        use_document_visibility()
+    };
+
+    let (is_suspended, set_suspended) = create_signal(false);
+    let (hidden_duration, set_hidden_duration) = create_signal(0.0_f64);
+
+    let hidden_since = store_value(
+        (cfg!(not(feature = "ssr")) && visibility.get_untracked() == VisibilityState::Hidden)
+            .then(now),
+    );
+
+    let _ = watch(
+        move || visibility.get(),
+        move |visibility, _, _| {
+            if *visibility == VisibilityState::Hidden {
+                hidden_since.set_value(Some(now()));
+
+                if behavior != TimerHiddenBehavior::Continue
+                    && is_running()
+                    && !is_suspended.get_untracked()
+                {
+                    set_suspended.set(true);
+                    suspend();
+                }
+            } else {
+                if let Some(since) = hidden_since.get_value() {
+                    hidden_since.set_value(None);
+                    set_hidden_duration.set(now() - since);
+                }
+
+                if is_suspended.get_untracked() {
+                    set_suspended.set(false);
+                    restore();
+                }
+            }
+        },
+        false,
+    );
+
+    VisibilityScheduler {
+        behavior,
+        visibility,
+        is_suspended,
+        set_suspended,
+        hidden_duration,
+    }
+}